@@ -27,6 +27,8 @@ use crate::SCHEMA;
 use crate::DEPENDENCY_DATABASE;
 use crate::FAKE_DEPENDENCY_DATABASE;
 use crate::GAME_SELECTED;
+use crate::DRY_RUN;
+use crate::SCHEMA_LOCKED;
 use crate::GlobalMatch;
 use crate::background_thread_extra;
 use crate::common::*;
@@ -34,6 +36,7 @@ use crate::common::coding_helpers::*;
 use crate::common::communications::*;
 use crate::error::{Error, ErrorKind};
 use crate::packfile::{PackFile, PFHFlags};
+use crate::packfile::extraction_hooks::ExtractionHooks;
 use crate::packedfile::*;
 use crate::packedfile::loc::*;
 use crate::packedfile::db::*;
@@ -62,6 +65,16 @@ pub fn background_loop(
     let mut pack_file_decoded = PackFile::new();
     let mut pack_file_decoded_extra = PackFile::new();
 
+    // This one keeps track of the edits done to the opened PackFile, so we can show the user a summary of their work.
+    let mut session_edit_stats = SessionEditStats::default();
+
+    // This one keeps the last version of the open DB Tables the UI knows about, so we can count the edits done to them when they're
+    // saved without decoding them again. They're only decoded again if their definition changed in the meantime.
+    let mut session_db_entries: BTreeMap<Vec<String>, (TableDefinition, Vec<Vec<DecodedData>>)> = BTreeMap::new();
+
+    // This one keeps the commands that edited the opened PackFile, so they can be exported and replayed later.
+    let mut command_log: Vec<background_thread_extra::LoggedCommand> = vec![];
+
     //---------------------------------------------------------------------------------------//
     // Looping forever and ever...
     //---------------------------------------------------------------------------------------//
@@ -76,6 +89,10 @@ pub fn background_loop(
             // If you got a message...
             Ok(data) => {
 
+                // If we are in "Dry Run" mode, the commands that edit the PackFile must not change it. The ones that report
+                // their changes are done over a copy of the PackFile, and the ones that don't are just ignored.
+                let dry_run = *DRY_RUN.lock().unwrap();
+
                 // Act depending on what that message is.
                 match data {
 
@@ -84,6 +101,9 @@ pub fn background_loop(
 
                         // Create the new PackFile.
                         pack_file_decoded = PackFile::new();
+                        session_edit_stats = SessionEditStats::default();
+                        session_db_entries.clear();
+                        command_log.clear();
                     }
 
                     // In case we want to reset the Secondary PackFile to his original state (dummy)...
@@ -98,7 +118,10 @@ pub fn background_loop(
                         let game_selected = GAME_SELECTED.lock().unwrap();
                         let pack_version = SUPPORTED_GAMES.get(&**game_selected).unwrap().id;
                         pack_file_decoded = background_thread_extra::new_packfile("unknown.pack".to_string(), pack_version);
-                        *SCHEMA.lock().unwrap() = Schema::load(&SUPPORTED_GAMES.get(&**game_selected).unwrap().schema).ok();
+                        session_edit_stats = SessionEditStats::default();
+                        session_db_entries.clear();
+                        command_log.clear();
+                        *SCHEMA.lock().unwrap() = Schema::load_with_patches(&SUPPORTED_GAMES.get(&**game_selected).unwrap().schema).ok();
                         sender.send(Data::U32(pack_file_decoded.pfh_file_type.get_value())).unwrap();
                     }
 
@@ -108,6 +131,9 @@ pub fn background_loop(
                         match background_thread_extra::open_packfiles(&paths, false, SETTINGS.lock().unwrap().settings_bool["use_lazy_loading"], false) {
                             Ok(pack_file) => {
                                 pack_file_decoded = pack_file;
+                                session_edit_stats = SessionEditStats::default();
+                                session_db_entries.clear();
+                                command_log.clear();
                                 sender.send(Data::PackFileUIData(pack_file_decoded.create_ui_data())).unwrap();
                             }
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
@@ -130,8 +156,11 @@ pub fn background_loop(
                     Commands::SavePackFile => {
 
                         // If it passed all the checks, then try to save it and return the result.
-                        match background_thread_extra::save_packfile(&mut pack_file_decoded, None, SETTINGS.lock().unwrap().settings_bool["allow_editing_of_ca_packfiles"]) {
-                            Ok(_) => sender.send(Data::I64(pack_file_decoded.timestamp)).unwrap(),
+                        match background_thread_extra::save_packfile(&mut pack_file_decoded, None, SETTINGS.lock().unwrap().settings_bool["allow_editing_of_ca_packfiles"], dry_run) {
+                            Ok(_) => {
+                                remove_autosave_shadow();
+                                sender.send(Data::I64(pack_file_decoded.timestamp)).unwrap();
+                            }
                             Err(error) => {
                                 match error.kind() {
                                     ErrorKind::PackFileIsNotAFile => sender.send(Data::Error(error)).unwrap(),
@@ -155,12 +184,34 @@ pub fn background_loop(
                         };
 
                         // Try to save the PackFile and return the results.
-                        match background_thread_extra::save_packfile(&mut pack_file_decoded, Some(path.to_path_buf()), SETTINGS.lock().unwrap().settings_bool["allow_editing_of_ca_packfiles"]) {
-                            Ok(_) => sender.send(Data::I64(pack_file_decoded.timestamp)).unwrap(),
+                        match background_thread_extra::save_packfile(&mut pack_file_decoded, Some(path.to_path_buf()), SETTINGS.lock().unwrap().settings_bool["allow_editing_of_ca_packfiles"], dry_run) {
+                            Ok(_) => {
+                                remove_autosave_shadow();
+                                sender.send(Data::I64(pack_file_decoded.timestamp)).unwrap();
+                            }
                             Err(error) => sender.send(Data::Error(Error::from(ErrorKind::SavePackFileGeneric(format!("{}", error))))).unwrap(),
                         }
                     }
 
+                    // In case we want to save a shadow copy of the PackFile in the autosaves folder...
+                    Commands::SaveAutosave => {
+
+                        // Don't autosave empty PackFiles, or the changes done in "Dry Run" mode.
+                        if dry_run || pack_file_decoded.packed_files.is_empty() { sender.send(Data::Success).unwrap(); }
+                        else {
+                            match background_thread_extra::save_autosave_shadow(&mut pack_file_decoded) {
+                                Ok(_) => sender.send(Data::Success).unwrap(),
+                                Err(error) => sender.send(Data::Error(error)).unwrap(),
+                            }
+                        }
+                    }
+
+                    // In case we want to change the path of the open PackFile, like when recovering it from an autosave...
+                    Commands::SetPackFilePath => {
+                        let path: PathBuf = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        pack_file_decoded.file_path = path;
+                    }
+
                     // In case we want to "Load All CA PackFiles"...
                     Commands::LoadAllCAPackFiles => {
                         match get_game_selected_data_packfiles_paths() {
@@ -183,8 +234,15 @@ pub fn background_loop(
                         // Wait until we get the needed data from the UI thread.
                         let new_type = if let Data::PFHFileType(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
-                        // Change the type of the PackFile.
-                        pack_file_decoded.pfh_file_type = new_type;
+                        // Change the type of the PackFile. In "Dry Run" mode, we send back what would have changed, and the type the PackFile still has.
+                        if dry_run {
+                            let report = background_thread_extra::get_dry_run_report(&format!("the type of the PackFile would have been changed from {:?} to {:?}.", pack_file_decoded.pfh_file_type, new_type));
+                            sender.send(Data::StringPFHFileType((report, pack_file_decoded.pfh_file_type))).unwrap();
+                        }
+                        else {
+                            pack_file_decoded.pfh_file_type = new_type;
+                            sender.send(Data::Success).unwrap();
+                        }
                     }
 
                     // In case we want to change the "Include Last Modified Date" setting of the PackFile...
@@ -194,29 +252,145 @@ pub fn background_loop(
                         let state: bool = if let Data::Bool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
                         // If it can be deserialized as a bool, change the state of the "Include Last Modified Date" setting of the PackFile.
-                        pack_file_decoded.bitmask.set(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS, state);
+                        if dry_run {
+                            let change = if state { "the Last Modified Date would have been included in the index." } else { "the Last Modified Date would have been removed from the index." };
+                            sender.send(Data::String(background_thread_extra::get_dry_run_report(change))).unwrap();
+                        }
+                        else {
+                            pack_file_decoded.bitmask.set(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS, state);
+                            sender.send(Data::Success).unwrap();
+                        }
                     }
 
                     // In case we want to compress/decompress the PackedFiles of the currently open PackFile...
                     Commands::ChangeDataIsCompressed => {
                         let state: bool = if let Data::Bool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
-                        pack_file_decoded.enable_compresion(state);
+                        if dry_run {
+                            let change = format!("the data of {} PackedFiles would have been {}.", pack_file_decoded.packed_files.len(), if state { "compressed" } else { "decompressed" });
+                            sender.send(Data::String(background_thread_extra::get_dry_run_report(&change))).unwrap();
+                        }
+                        else {
+                            pack_file_decoded.enable_compresion(state);
+                            sender.send(Data::Success).unwrap();
+                        }
                     }
 
-                    // In case we want to save an schema...
-                    Commands::SaveSchema => {
+                    // In case we want to save a definition from the decoder into the local patch of the schema...
+                    Commands::SaveSchemaPatch => {
+                        let (path, table_definition) = if let Data::VecStringTableDefinition(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        let schema_file = SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().schema.to_owned();
+                        let result = edit_schema(&mut SCHEMA.lock().unwrap(), *SCHEMA_LOCKED.lock().unwrap(), |schema| {
+                            match schema {
+                                Some(ref mut schema) => {
+                                    let table_name = pack_file_decoded.get_table_definition_name(&path[1]);
+                                    let old_definition = schema.get_table_definitions(table_name)
+                                        .map(|x| &schema.tables_definitions[x])
+                                        .and_then(|x| x.get_table_version(table_definition.version).map(|y| x.versions[y].clone()));
+                                    let new_patches = SchemaPatch::get_patches(table_name, old_definition.as_ref(), &table_definition);
+
+                                    let mut patches = SchemaPatch::load(&schema_file)?;
+                                    SchemaPatch::add_patches(&mut patches, new_patches.to_vec());
+                                    SchemaPatch::save(&patches, &schema_file)?;
+                                    schema.apply_patches(&new_patches);
+                                    Ok(())
+                                }
+                                None => Err(ErrorKind::SchemaNotFound)?,
+                            }
+                        });
 
-                        // Wait until we get the needed data from the UI thread.
-                        let new_schema: Schema = if let Data::Schema(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
-                        match Schema::save(&new_schema, &SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().schema) {
-                            Ok(_) => {
-                                *SCHEMA.lock().unwrap() = Some(new_schema);
-                                sender.send(Data::Success).unwrap();
-                            },
+                        match result {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap()
                         }
                     }
 
+                    // In case we want to get the local patches of the schema...
+                    Commands::GetSchemaPatches => {
+                        match SchemaPatch::load(&SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().schema) {
+                            Ok(patches) => sender.send(Data::VecString(patches.iter().map(|x| x.to_string()).collect())).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to remove one of the local patches of the schema...
+                    Commands::RemoveSchemaPatch => {
+                        let index = if let Data::Usize(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        let schema_file = SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().schema.to_owned();
+                        let result = edit_schema(&mut SCHEMA.lock().unwrap(), *SCHEMA_LOCKED.lock().unwrap(), |schema| {
+                            let mut patches = SchemaPatch::load(&schema_file)?;
+                            if index < patches.len() { patches.remove(index); }
+                            SchemaPatch::save(&patches, &schema_file)?;
+                            *schema = Schema::load_with_patches(&schema_file).ok();
+                            Ok(())
+                        });
+
+                        match result {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to import the definitions of the Assembly Kit for the table versions we don't have...
+                    Commands::ImportAssemblyKitDefinitions => {
+                        let schema_file = SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().schema.to_owned();
+                        let result = edit_schema(&mut SCHEMA.lock().unwrap(), *SCHEMA_LOCKED.lock().unwrap(), |schema| {
+                            let raw_db_path = get_game_selected_assembly_kit_raw_db_path().ok_or_else(|| Error::from(ErrorKind::AssemblyKitNotFound))?;
+                            let definitions = get_assembly_kit_definitions(&raw_db_path)?;
+                            let mut table_versions = get_table_versions(&DEPENDENCY_DATABASE.lock().unwrap());
+                            table_versions.extend(get_table_versions(&pack_file_decoded.packed_files));
+
+                            match schema {
+                                Some(ref mut schema) => {
+                                    let new_patches = import_assembly_kit_definitions(schema, &definitions, &table_versions);
+                                    let mut patches = SchemaPatch::load(&schema_file)?;
+                                    SchemaPatch::add_patches(&mut patches, new_patches.to_vec());
+                                    SchemaPatch::save(&patches, &schema_file)?;
+                                    Ok((definitions.len(), new_patches.iter().map(|x| x.to_string()).collect::<Vec<String>>()))
+                                }
+                                None => Err(ErrorKind::SchemaNotFound)?,
+                            }
+                        });
+
+                        match result {
+                            Ok(data) => sender.send(Data::UsizeVecString(data)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want the definition of a version of a table from the Assembly Kit...
+                    Commands::GetAssemblyKitDefinition => {
+                        let (table_name, version) = if let Data::StringI32(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        let result = get_game_selected_assembly_kit_raw_db_path().ok_or_else(|| Error::from(ErrorKind::AssemblyKitNotFound))
+                            .and_then(|raw_db_path| get_assembly_kit_definition(&raw_db_path, &table_name))
+                            .map(|definition| TableDefinition::new_from_assembly_kit(&definition, version, &table_name));
+
+                        match result {
+                            Ok(table_definition) => sender.send(Data::TableDefinition(table_definition)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to test a definition against all the tables of the same folder and version we have...
+                    Commands::TestTableDefinition => {
+                        let (path, table_definition) = if let Data::VecStringTableDefinition(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        let mut results = DB::test_definition(&path[1], &table_definition, &pack_file_decoded.packed_files, false);
+                        results.append(&mut DB::test_definition(pack_file_decoded.get_table_definition_name(&path[1]), &table_definition, &DEPENDENCY_DATABASE.lock().unwrap(), true));
+                        sender.send(Data::VecDefinitionTestResult(results)).unwrap();
+                    }
+
+                    // In case we want to guess the fields of a table after the ones of the provided definition...
+                    Commands::GuessTableFields => {
+                        let (path, table_definition) = if let Data::VecStringTableDefinition(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        let result = pack_file_decoded.packed_files.iter().find(|x| x.path == path)
+                            .ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))
+                            .and_then(|packed_file| packed_file.get_data())
+                            .and_then(|data| DB::get_header_data(&data).map(|(_, entry_count, initial_index)| DB::guess_fields(&data, initial_index, entry_count, &table_definition.fields)));
+                        match result {
+                            Ok(guesses) => sender.send(Data::VecFieldGuess(guesses)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
                     // In case we want to change the current settings...
                     Commands::SetSettings => {
                         let new_settings = if let Data::Settings(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
@@ -253,7 +427,7 @@ pub fn background_loop(
                         sender.send(Data::Bool(!pack_file_decoded.get_file_name().is_empty())).unwrap();
 
                         // Try to load the Schema for this game.
-                        *SCHEMA.lock().unwrap() = Schema::load(&SUPPORTED_GAMES.get(&*game_selected).unwrap().schema).ok();
+                        *SCHEMA.lock().unwrap() = Schema::load_with_patches(&SUPPORTED_GAMES.get(&*game_selected).unwrap().schema).ok();
 
                         // Change the `dependency_database` for that game.
                         *DEPENDENCY_DATABASE.lock().unwrap() = background_thread_extra::load_dependency_packfiles(&pack_file_decoded.pack_files);
@@ -270,6 +444,7 @@ pub fn background_loop(
                         if SETTINGS.lock().unwrap().settings_bool["check_for_missing_table_definitions"] {
                             let mut counter = 0;
                             let mut table_list = String::new();
+                            let table_aliases = pack_file_decoded.table_aliases.clone();
                             for i in pack_file_decoded.packed_files.iter_mut() {
                                 if i.path.starts_with(&["db".to_owned()]) {
                                     if let Some(ref schema) = *SCHEMA.lock().unwrap() {
@@ -277,7 +452,7 @@ pub fn background_loop(
                                         // For some stupid reason, this fails with decompresion sometimes.
                                         match i.get_data_and_keep_it() {
                                             Ok(data) => {
-                                                if let Err(error) = db::DB::read(&data, &i.path[1], &schema) {
+                                                if let Err(error) = db::DB::read(&data, table_aliases.get(&i.path[1]).unwrap_or(&i.path[1]), &schema) {
                                                     if error.kind() != ErrorKind::DBTableContainsListField {
                                                         match db::DB::get_header_data(&data) {
                                                             Ok((_, entry_count, _)) => {
@@ -322,7 +497,7 @@ pub fn background_loop(
                     Commands::PatchSiegeAI => {
                         
                         // First, we try to patch the PackFile.
-                        match background_thread_extra::patch_siege_ai(&mut pack_file_decoded) {
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, background_thread_extra::LoggedCommand::PatchSiegeAI, |pack_file| background_thread_extra::patch_siege_ai(pack_file)) {
                             Ok(result) => sender.send(Data::StringVecPathType(result)).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap()
                         }
@@ -333,11 +508,14 @@ pub fn background_loop(
 
                         // Reload the currently loaded schema, just in case it was updated.
                         let data = if let Data::VersionsVersions(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
-                        match update_schemas(&data.0, &data.1) {
-                            Ok(_) => {
-                                *SCHEMA.lock().unwrap() = Schema::load(&SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().schema).ok();
-                                sender.send(Data::Success).unwrap();
-                            }
+                        let result = edit_schema(&mut SCHEMA.lock().unwrap(), *SCHEMA_LOCKED.lock().unwrap(), |schema| {
+                            update_schemas(&data.0, &data.1)?;
+                            *schema = Schema::load_with_patches(&SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().schema).ok();
+                            Ok(())
+                        });
+
+                        match result {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
                         }
                     }
@@ -349,14 +527,17 @@ pub fn background_loop(
                         let data = if let Data::VecPathBufVecVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
                         // For each file...
+                        let old_paths = pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
                         for index in 0..data.0.len() {
 
                             // Try to add it to the PackFile. If it fails, report it and stop adding files.
-                            if let Err(error) = background_thread_extra::add_file_to_packfile(&mut pack_file_decoded, &data.0[index], data.1[index].to_vec()) {
+                            let command = background_thread_extra::LoggedCommand::AddPackedFile { file_path: data.0[index].to_path_buf(), path: data.1[index].to_vec() };
+                            if let Err(error) = background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::add_file_to_packfile(pack_file, &data.0[index], data.1[index].to_vec())) {
                                 sender.send(Data::Error(error)).unwrap();
                                 break;
                             }
                         }
+                        session_edit_stats.record_files_change(&old_paths, &pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>());
 
                         // If nothing failed, send back success.
                         sender.send(Data::Success).unwrap();
@@ -367,7 +548,11 @@ pub fn background_loop(
                         
                         // Delete the PackedFiles from the PackFile, changing his return in case of success.
                         let item_types = if let Data::VecPathType(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
-                        sender.send(Data::VecPathType(background_thread_extra::delete_from_packfile(&mut pack_file_decoded, &item_types))).unwrap();
+                        let old_paths = pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+                        let command = background_thread_extra::LoggedCommand::DeletePackedFiles { item_types: item_types.to_vec() };
+                        let deleted_item_types = background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::delete_from_packfile(pack_file, &item_types));
+                        session_edit_stats.record_files_change(&old_paths, &pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>());
+                        sender.send(Data::VecPathType(deleted_item_types)).unwrap();
                     }
 
                     // In case we want to extract PackedFiles from a PackFile...
@@ -378,7 +563,9 @@ pub fn background_loop(
                         match background_thread_extra::extract_from_packfile(
                             &pack_file_decoded,
                             &data.0,
-                            &data.1
+                            &data.1,
+                            &ExtractionHooks::new(&SETTINGS.lock().unwrap().get_trim_nulls_on_extraction_games()),
+                            &GAME_SELECTED.lock().unwrap()
                         ) {
                             Ok(result) => sender.send(Data::String(result)).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
@@ -418,13 +605,18 @@ pub fn background_loop(
                         let data = if let Data::VecStringPackedFileType(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
                         // Create the PackedFile.
-                        match create_packed_file(
-                            &mut pack_file_decoded,
-                            data.1,
-                            data.0,
-                        ) {
+                        let old_paths = pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+                        let command = background_thread_extra::LoggedCommand::CreatePackedFile { path: data.0.to_vec(), packed_file_type: data.1.clone() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| create_packed_file(
+                            pack_file,
+                            data.1.clone(),
+                            data.0.to_vec(),
+                        )) {
                             // Send the result back.
-                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Ok(_) => {
+                                session_edit_stats.record_files_change(&old_paths, &pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>());
+                                sender.send(Data::Success).unwrap();
+                            }
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
                         }
                     }
@@ -458,14 +650,19 @@ pub fn background_loop(
                         let path_type = if let Data::PathType(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
                         // Try to add the PackedFile to the main PackFile.
-                        match background_thread_extra::add_packedfile_to_packfile(
+                        let old_paths = pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+                        let command = background_thread_extra::LoggedCommand::AddPackedFileFromPackFile { source_pack_file_path: pack_file_decoded_extra.file_path.to_path_buf(), path_type: path_type.clone() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::add_packedfile_to_packfile(
                             &pack_file_decoded_extra,
-                            &mut pack_file_decoded,
+                            pack_file,
                             &path_type
-                        ) {
+                        )) {
 
                             // In case of success, get the list of copied PackedFiles and send it back.
-                            Ok(path_types_added) => sender.send(Data::VecPathType(path_types_added)).unwrap(),
+                            Ok(path_types_added) => {
+                                session_edit_stats.record_files_change(&old_paths, &pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>());
+                                sender.send(Data::VecPathType(path_types_added)).unwrap();
+                            }
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
                         }
                     }
@@ -474,19 +671,59 @@ pub fn background_loop(
                     Commands::MassImportTSV => {
 
                         // Try to import all the importable files from the provided path.
-                        let data = if let Data::OptionStringVecPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
-                        match tsv_mass_import(&data.1, data.0, &mut pack_file_decoded) {
-                            Ok(result) => sender.send(Data::VecVecStringVecVecString(result)).unwrap(),
+                        let data = if let Data::OptionStringVecPathBufBool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        let command = background_thread_extra::LoggedCommand::MassImportTSV { tsv_paths: data.1.to_vec(), name: data.0.clone(), skip_bad_rows: data.2 };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| tsv_mass_import(&data.1, data.0, pack_file, data.2)) {
+                            Ok(result) => sender.send(Data::VecVecStringVecVecStringVecString(result)).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
                         }
                     }
 
+                    // In case we want to Mass-Import the TSV Files of a folder, using his structure to know where they go...
+                    Commands::MassImportTSVFromFolder => {
+                        let path = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let command = background_thread_extra::LoggedCommand::MassImportTSVFromFolder { folder_path: path.to_path_buf() };
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| tsv_mass_import_from_folder(&path, pack_file, schema)) {
+                                    Ok(result) => sender.send(Data::VecVecStringVecString(result)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to check the TSV Files we want to Mass-Import...
+                    Commands::MassImportTSVPreview => {
+                        let paths = if let Data::VecPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        sender.send(Data::VecString(tsv_mass_import_preview(&paths))).unwrap();
+                    }
+
                     // In case we want to Mass-Export TSV Files...
                     Commands::MassExportTSV => {
 
                         // Try to export all the exportable files to the provided path.
                         let path = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
-                        match tsv_mass_export(&path, &mut pack_file_decoded) {
+                        match tsv_mass_export(&path, &mut pack_file_decoded, false) {
+                            Ok(result) => sender.send(Data::String(result)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to Mass-Export TSV Files, but only of the tables modified since the PackFile was saved...
+                    Commands::MassExportTSVModified => {
+                        let path = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match tsv_mass_export(&path, &mut pack_file_decoded, true) {
+                            Ok(result) => sender.send(Data::String(result)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to Mass-Export JSON Files...
+                    Commands::MassExportJSON => {
+                        let path = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match json_mass_export(&path, &mut pack_file_decoded) {
                             Ok(result) => sender.send(Data::String(result)).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
                         }
@@ -523,12 +760,21 @@ pub fn background_loop(
                         // Wait until we get the needed data from the UI thread.
                         let data = if let Data::LocVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
+                        if !dry_run {
+                            if let Some(packed_file) = pack_file_decoded.packed_files.iter_mut().find(|x| x.path == data.1) {
+                                if let Ok(old_loc) = packed_file.get_data_and_keep_it().and_then(|raw_data| Loc::read(&raw_data)) {
+                                    session_edit_stats.record_table_edit(&old_loc.entries, &data.0.entries);
+                                }
+                            }
+                        }
+
                         // Update the PackFile to reflect the changes.
-                        background_thread_extra::update_packed_file_data_loc(
-                            &data.0,
-                            &mut pack_file_decoded,
-                            &data.1
-                        );
+                        let command = background_thread_extra::LoggedCommand::SetTableData { path: data.1.to_vec(), entries: data.0.entries.to_vec() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::update_packed_file_data_loc(&data.0, pack_file, &data.1)) {
+                            Ok(_) if dry_run => sender.send(Data::String(background_thread_extra::get_dry_run_report(&format!("{} would have been saved with {} rows.", data.1.join("/"), data.0.entries.len())))).unwrap(),
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
                     }
 
                     // In case we want to decode a DB PackedFile...
@@ -537,6 +783,9 @@ pub fn background_loop(
                         // Wait until we get the needed data from the UI thread.
                         let path = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
+                        // If the table's folder has an alias, we have to decode it with the definition of the aliased table.
+                        let table_definition_name = pack_file_decoded.get_table_definition_name(&path[1]).to_owned();
+
                         // Depending if there is an Schema for this game or not...
                         match *SCHEMA.lock().unwrap() {
                             Some(ref schema) => {
@@ -548,10 +797,13 @@ pub fn background_loop(
                                             Ok(data) => {
                                                 match DB::read(
                                                     &data,
-                                                    &packed_file.path[1],
+                                                    &table_definition_name,
                                                     schema,
                                                 ) {
-                                                    Ok(packed_file_decoded) => sender.send(Data::DB(packed_file_decoded)).unwrap(),
+                                                    Ok(packed_file_decoded) => {
+                                                        session_db_entries.insert(path.to_vec(), (packed_file_decoded.table_definition.clone(), packed_file_decoded.entries.to_vec()));
+                                                        sender.send(Data::DB(packed_file_decoded)).unwrap();
+                                                    }
                                                     Err(error) => sender.send(Data::Error(error)).unwrap(),
                                                 }
                                             }
@@ -573,12 +825,25 @@ pub fn background_loop(
                         // Wait until we get the needed data from the UI thread.
                         let data = if let Data::DBVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
+                        if !dry_run {
+                            match session_db_entries.get(&data.1) {
+                                Some((old_definition, old_entries)) if *old_definition == data.0.table_definition => session_edit_stats.record_table_edit(old_entries, &data.0.entries),
+                                _ => if let Some(ref schema) = *SCHEMA.lock().unwrap() {
+                                    if let Ok((_, old_entries)) = get_table_data(&mut pack_file_decoded, &data.1, schema) {
+                                        session_edit_stats.record_table_edit(&old_entries, &data.0.entries);
+                                    }
+                                }
+                            }
+                            session_db_entries.insert(data.1.to_vec(), (data.0.table_definition.clone(), data.0.entries.to_vec()));
+                        }
+
                         // Update the PackFile to reflect the changes.
-                        background_thread_extra::update_packed_file_data_db(
-                            &data.0,
-                            &mut pack_file_decoded,
-                            &data.1
-                        );
+                        let command = background_thread_extra::LoggedCommand::SetTableData { path: data.1.to_vec(), entries: data.0.entries.to_vec() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::update_packed_file_data_db(&data.0, pack_file, &data.1)) {
+                            Ok(_) if dry_run => sender.send(Data::String(background_thread_extra::get_dry_run_report(&format!("{} would have been saved with {} rows.", data.1.join("/"), data.0.entries.len())))).unwrap(),
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
                     }
 
 
@@ -586,7 +851,25 @@ pub fn background_loop(
                     Commands::ImportTSVPackedFile => {
                         let data = if let Data::TableDefinitionPathBufStringI32(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
                         match import_tsv(&data.0, &data.1, &data.2, data.3) {
-                            Ok(data) => sender.send(Data::VecVecDecodedData(data)).unwrap(),
+                            Ok(data) => sender.send(Data::VecVecDecodedDataVecStringVecTSVError(data)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to import a TSV file made for another version of a DB Table into it...
+                    Commands::ImportTSVPackedFileFromOtherVersion => {
+                        let data = if let Data::TableDefinitionPathBufStringI32(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match import_tsv_from_other_version(&data.0, &data.1, &data.2) {
+                            Ok(data) => sender.send(Data::VecVecDecodedDataVecStringVecTSVError(data)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to import a JSON file into a Loc PackedFile...
+                    Commands::ImportJSONLocPackedFile => {
+                        let data = if let Data::TableDefinitionPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match Loc::import_json(&data.0, &data.1) {
+                            Ok(loc) => sender.send(Data::VecVecDecodedDataVecStringVecTSVError((loc.entries, vec![], vec![]))).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
                         }
                     }
@@ -600,6 +883,33 @@ pub fn background_loop(
                         }
                     }
 
+                    // In case we want to export a DB Table/Loc PackedFile into a JSON file...
+                    Commands::ExportJSONPackedFile => {
+                        let data = if let Data::VecVecDecodedDataPathBufTableDefinitionTupleStrI32(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match export_json(&data.0, &data.1, &data.2, (&(data.3).0, (data.3).1)) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to import a CSV file into a DB Table/Loc PackedFile...
+                    Commands::ImportCSVPackedFile => {
+                        let data = if let Data::TableDefinitionPathBufU8DelimitedQuoting(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match import_delimited(&data.0, &data.1, data.2, data.3) {
+                            Ok(data) => sender.send(Data::VecVecDecodedDataVecStringVecTSVError(data)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export a DB Table/Loc PackedFile into a CSV file...
+                    Commands::ExportCSVPackedFile => {
+                        let data = if let Data::VecVecDecodedDataPathBufVecStringU8DelimitedQuoting(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        match export_delimited(&data.0, &data.1, &data.2, data.3, data.4) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
                     // In case we want to decode a Plain Text PackedFile...
                     Commands::DecodePackedFileText => {
 
@@ -646,11 +956,12 @@ pub fn background_loop(
                         let encoded_text = encode_string_u8(&data.0);
 
                         // Update the PackFile to reflect the changes.
-                        background_thread_extra::update_packed_file_data_text(
-                            &encoded_text,
-                            &mut pack_file_decoded,
-                            &data.1
-                        );
+                        let command = background_thread_extra::LoggedCommand::SetTextData { path: data.1.to_vec(), text: data.0.to_owned() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::update_packed_file_data_text(&encoded_text, pack_file, &data.1)) {
+                            Ok(_) if dry_run => sender.send(Data::String(background_thread_extra::get_dry_run_report(&format!("{} would have been saved with {} bytes.", data.1.join("/"), encoded_text.len())))).unwrap(),
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
                     }
 
                     // In case we want to decode a RigidModel...
@@ -687,11 +998,13 @@ pub fn background_loop(
                         let data = if let Data::RigidModelVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
 
                         // Update the PackFile to reflect the changes.
-                        background_thread_extra::update_packed_file_data_rigid(
-                            &data.0,
-                            &mut pack_file_decoded,
-                            &data.1
-                        );
+                        if dry_run { sender.send(Data::String(background_thread_extra::get_dry_run_report(&format!("{} would have been saved.", data.1.join("/"))))).unwrap(); }
+                        else {
+                            match background_thread_extra::update_packed_file_data_rigid(&data.0, &mut pack_file_decoded, &data.1) {
+                                Ok(_) => sender.send(Data::Success).unwrap(),
+                                Err(error) => sender.send(Data::Error(error)).unwrap(),
+                            }
+                        }
                     }
 
                     // In case we want to patch a decoded RigidModel from Attila to Warhammer...
@@ -711,11 +1024,13 @@ pub fn background_loop(
                                     Ok(_) => {
 
                                         // Update the PackFile to reflect the changes.
-                                        background_thread_extra::update_packed_file_data_rigid(
-                                            &data.0,
-                                            &mut pack_file_decoded,
-                                            &data.1
-                                        );
+                                        if !dry_run {
+                                            background_thread_extra::update_packed_file_data_rigid(
+                                                &data.0,
+                                                &mut pack_file_decoded,
+                                                &data.1
+                                            );
+                                        }
 
                                         // Send back the patched PackedFile.
                                         sender.send(Data::RigidModel(data.0)).unwrap()
@@ -772,7 +1087,11 @@ pub fn background_loop(
                     // In case we want to "Rename a PackedFile"...
                     Commands::RenamePackedFiles => {
                         let data = if let Data::VecPathTypeString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
-                        sender.send(Data::VecPathTypeString(background_thread_extra::rename_packed_files(&mut pack_file_decoded, &data))).unwrap();
+                        let old_paths = pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+                        let command = background_thread_extra::LoggedCommand::RenamePackedFiles { renaming_data: data.to_vec() };
+                        let renamed_data = background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::rename_packed_files(pack_file, &data));
+                        session_edit_stats.record_files_rename(&old_paths, &pack_file_decoded.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>());
+                        sender.send(Data::VecPathTypeString(renamed_data)).unwrap();
                     }
 
                     // In case we want to get a PackedFile's data...
@@ -831,7 +1150,7 @@ pub fn background_loop(
 
                     // In case we want to optimize our PackFile...
                     Commands::OptimizePackFile => {
-                        match background_thread_extra::optimize_packfile(&mut pack_file_decoded) {
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, background_thread_extra::LoggedCommand::OptimizePackFile, |pack_file| background_thread_extra::optimize_packfile(pack_file, &DEPENDENCY_DATABASE.lock().unwrap())) {
                             Ok(deleted_packed_files) => sender.send(Data::VecPathType(deleted_packed_files)).unwrap(),
                             Err(_) => sender.send(Data::Error(Error::from(ErrorKind::PackedFileDataCouldNotBeLoaded))).unwrap(),
                         }
@@ -860,27 +1179,34 @@ pub fn background_loop(
                        
                         // Wait until we get the needed data from the UI thread.
                         let list = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
-                        pack_file_decoded.save_packfiles_list(list);
+                        if !dry_run {
+                            pack_file_decoded.save_packfiles_list(list);
 
-                        // Update the dependency database.
-                        *DEPENDENCY_DATABASE.lock().unwrap() = background_thread_extra::load_dependency_packfiles(&pack_file_decoded.pack_files);
+                            // Update the dependency database.
+                            *DEPENDENCY_DATABASE.lock().unwrap() = background_thread_extra::load_dependency_packfiles(&pack_file_decoded.pack_files);
+                        }
                     }
 
-                    // In case we want to get the dependency data for a table's column....
-                    Commands::DecodeDependencyDB => {
+                    // In case we want to get the data referenced by the reference columns of a table....
+                    Commands::GetReferenceData => {
 
-                        // Get the entire dependency data for the provided definition, all at once.
+                        // Get the referenced data for the provided definition, all at once, so the table only asks for it once.
                         let table_definition = if let Data::TableDefinition(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
                         let dependency_data = match SCHEMA.lock().unwrap().clone() {
                             Some(schema) => {
                                 let mut dep_db = DEPENDENCY_DATABASE.lock().unwrap();
                                 let fake_dep_db = FAKE_DEPENDENCY_DATABASE.lock().unwrap();
 
-                                // Due to how mutability works, we have first to get the data of every table,
-                                // then iterate them and decode them. Ignore any errors.
+                                // Due to how mutability works, we have first to get the data of every referenced table,
+                                // then iterate them and decode them. Tables not referenced by this one are not loaded. Ignore any errors.
+                                let referenced_tables = get_referenced_tables(&table_definition);
+                                let table_aliases = pack_file_decoded.table_aliases.clone();
                                 for packed_file in pack_file_decoded.packed_files.iter_mut() {
-                                    if packed_file.path.starts_with(&["db".to_owned()]) {
-                                        let _x = packed_file.load_data();
+                                    if packed_file.path.len() == 3 && packed_file.path[0] == "db" {
+                                        let table_name = table_aliases.get(&packed_file.path[1]).unwrap_or(&packed_file.path[1]);
+                                        if referenced_tables.contains(table_name) {
+                                            let _x = packed_file.load_data();
+                                        }
                                     }
                                 }
 
@@ -891,6 +1217,47 @@ pub fn background_loop(
                         sender.send(Data::BTreeMapI32VecString(dependency_data)).unwrap();
                     }
 
+                    // In case we want to find the row referenced by a cell...
+                    Commands::FindReferencedRow => {
+                        let (table_name, column_name, value) = if let Data::StringStringString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let mut dep_db = DEPENDENCY_DATABASE.lock().unwrap();
+                                match find_referenced_row(&table_name, &column_name, &value, &schema, &mut dep_db, &pack_file_decoded) {
+                                    Some(referenced_row) => sender.send(Data::ReferencedRow(referenced_row)).unwrap(),
+                                    None => sender.send(Data::Error(Error::from(ErrorKind::DBReferencedRowNotFound(value, format!("{}_tables", table_name))))).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the cells referencing a key of a table...
+                    Commands::FindUsages => {
+                        let (path, column_name, value) = if let Data::VecStringStringString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let table_name = pack_file_decoded.get_table_definition_name(&path[1]).trim_end_matches("_tables").to_owned();
+                                sender.send(Data::VecGlobalMatch(find_usages(&table_name, &column_name, &value, &schema, &pack_file_decoded))).unwrap();
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to get the vanilla rows of a table, to compare it with ours...
+                    Commands::GetVanillaRows => {
+                        let (path, table_definition) = if let Data::VecStringTableDefinition(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match get_vanilla_rows(&pack_file_decoded, &mut DEPENDENCY_DATABASE.lock().unwrap(), &path, &table_definition, &schema) {
+                                    Ok(vanilla_rows) => sender.send(Data::BTreeMapVecStringVecOptionString(vanilla_rows)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
                     // In case we want to use Kailua to check if your script has errors...
                     Commands::CheckScriptWithKailua => {
 
@@ -962,6 +1329,7 @@ pub fn background_loop(
                         let mut matches: Vec<GlobalMatch> = vec![];
                         let mut error = false;
                         let loc_definition = TableDefinition::new_loc_definition();
+                        let table_aliases = pack_file_decoded.table_aliases.clone();
                         for packed_file in &mut pack_file_decoded.packed_files {
                             let path = packed_file.path.to_vec();
                             let packedfile_name = path.last().unwrap().to_owned();
@@ -1066,7 +1434,7 @@ pub fn background_loop(
                                     };
 
                                     if let Some(ref schema) = *SCHEMA.lock().unwrap() {   
-                                        if let Ok(packed_file) = DB::read(&data, &path[1], &schema) {
+                                        if let Ok(packed_file) = DB::read(&data, table_aliases.get(&path[1]).unwrap_or(&path[1]), &schema) {
 
                                             let mut matches_in_file = vec![];
                                             for (index, row) in packed_file.entries.iter().enumerate() {
@@ -1116,6 +1484,7 @@ pub fn background_loop(
                         let mut matches: Vec<GlobalMatch> = vec![];
                         let loc_definition = TableDefinition::new_loc_definition();
                         let mut error = false;
+                        let table_aliases = pack_file_decoded.table_aliases.clone();
                         for packed_file in &mut pack_file_decoded.packed_files {
 
                             // We need to take into account that we may pass here incomplete paths.
@@ -1231,7 +1600,7 @@ pub fn background_loop(
                                         };
 
                                         if let Some(ref schema) = *SCHEMA.lock().unwrap() {   
-                                            if let Ok(packed_file) = DB::read(&data, &path[1], &schema) {
+                                            if let Ok(packed_file) = DB::read(&data, table_aliases.get(&path[1]).unwrap_or(&path[1]), &schema) {
 
                                                 let mut matches_in_file = vec![];
                                                 for (index, row) in packed_file.entries.iter().enumerate() {
@@ -1339,8 +1708,9 @@ pub fn background_loop(
                     Commands::MergeTables => {
 
                         // Delete the PackedFiles from the PackFile, changing his return in case of success.
-                        let (paths, name, delete_source_files, table_types) = if let Data::VecVecStringStringBoolBool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
-                        match merge_tables(&mut pack_file_decoded, &paths, &name, delete_source_files, table_types) {
+                        let (paths, name, delete_source_files, table_types, remove_duplicates) = if let Data::VecVecStringStringBoolBoolBool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        let command = background_thread_extra::LoggedCommand::MergeTables { source_paths: paths.to_vec(), name: name.to_owned(), delete_source_paths: delete_source_files, table_type: table_types, remove_duplicates };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| merge_tables(pack_file, &paths, &name, delete_source_files, table_types, remove_duplicates)) {
                             Ok(data) => sender.send(Data::VecStringVecPathType(data)).unwrap(),
                             Err(error) => sender.send(Data::Error(error)).unwrap(),
                         }
@@ -1363,7 +1733,569 @@ pub fn background_loop(
                     // In case we want to save notes to the current PackFile...
                     Commands::SetNotes => {
                         let notes = if let Data::String(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
-                        pack_file_decoded.notes = Some(notes);
+                        if !dry_run { pack_file_decoded.notes = Some(notes); }
+                    }
+
+                    // In case we want to remove the BOMs of all the Text PackedFiles of the PackFile...
+                    Commands::StripBomsFromText => {
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, background_thread_extra::LoggedCommand::StripBomsFromText, |pack_file| strip_boms_from_text(pack_file)) {
+                            Ok(paths) => sender.send(Data::VecVecString(paths)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to count the localisable characters of the PackFile...
+                    Commands::CountLocalisableCharacters => {
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match count_localisable_characters(&mut pack_file_decoded, schema) {
+                                    Ok(counts) => sender.send(Data::VecVecStringUsizeUsizeVecVecString(counts)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to enable/disable the "Dry Run" mode...
+                    Commands::SetDryRun => {
+                        let state = if let Data::Bool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        *DRY_RUN.lock().unwrap() = state;
+                    }
+
+                    // In case we want to find the circular references between the tables of the schema...
+                    Commands::FindReferenceCycles => {
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => sender.send(Data::VecVecString(schema.find_reference_cycles())).unwrap(),
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to get the loc coverage of the DB Tables of the PackFile...
+                    Commands::LocCoverageReport => {
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match get_loc_coverage(&mut pack_file_decoded, schema) {
+                                    Ok(coverages) => sender.send(Data::VecLocCoverage(coverages)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to set (or remove) the alias of a table folder of the PackFile...
+                    Commands::SetTableAlias => {
+                        let (table_folder, table_definition_name) = if let Data::StringString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        if !dry_run {
+                            if table_definition_name.is_empty() { pack_file_decoded.table_aliases.remove(&table_folder); }
+                            else { pack_file_decoded.table_aliases.insert(table_folder, table_definition_name); }
+                        }
+                    }
+
+                    // In case we want to know the name of the table definition used to decode the tables of a table folder...
+                    Commands::GetTableDefinitionName => {
+                        let table_folder = if let Data::String(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        sender.send(Data::String(pack_file_decoded.get_table_definition_name(&table_folder).to_owned())).unwrap();
+                    }
+
+                    // In case we want to export the distinct values of one or more columns...
+                    Commands::ExportColumnEnums => {
+                        let (column_refs, path) = if let Data::VecStringPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match export_column_enums(&mut pack_file_decoded, &mut DEPENDENCY_DATABASE.lock().unwrap(), schema, &column_refs, &path) {
+                                    Ok(_) => sender.send(Data::Success).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the keys with non-ASCII characters of a DB Table or Loc PackedFile...
+                    Commands::FindNonAsciiKeys => {
+                        let path = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match find_non_ascii_keys(&mut pack_file_decoded, &path, schema) {
+                                    Ok(keys) => sender.send(Data::VecNonAsciiKey(keys)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to split a DB Table by the values of one of his columns...
+                    Commands::SplitTableByColumn => {
+                        let (path, column, keep_original) = if let Data::VecStringStringBool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let command = background_thread_extra::LoggedCommand::SplitTableByColumn { path: path.to_vec(), column: column.to_owned(), keep_original };
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| split_table_by_column(pack_file, &path, &column, keep_original, schema)) {
+                                    Ok(paths) => sender.send(Data::VecVecString(paths)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to restore a deleted PackedFile, without undoing anything done after deleting it...
+                    Commands::RestoreDeletedFile => {
+                        let (path, overwrite) = if let Data::VecStringBool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        let command = background_thread_extra::LoggedCommand::RestoreDeletedFile { path: path.to_vec(), overwrite };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| pack_file.restore_deleted_packedfile(&path, overwrite)) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to discard the changes done to a PackedFile, reloading it from the PackFile on disk...
+                    Commands::ReloadPackedFileFromDisk => {
+                        let path = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        let command = background_thread_extra::LoggedCommand::ReloadPackedFileFromDisk { path: path.to_vec() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| pack_file.reload_packedfile_from_disk(&path)) {
+                            Ok(_) if dry_run => sender.send(Data::String(background_thread_extra::get_dry_run_report(&format!("{} would have been reloaded from the PackFile on disk.", path.join("/"))))).unwrap(),
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the tables of the schema without reference data...
+                    Commands::FindUnannotatedTables => {
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => sender.send(Data::VecString(schema.find_unannotated_tables())).unwrap(),
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export the translation matrix of one or more Loc PackedFiles...
+                    Commands::ExportTranslationMatrix => {
+                        let (locs, path) = if let Data::VecVecStringPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match export_translation_matrix(&mut pack_file_decoded, &locs, &path) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to remove the trailing null bytes of the Text PackedFiles...
+                    Commands::TrimTrailingNulls => {
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, background_thread_extra::LoggedCommand::TrimTrailingNulls, |pack_file| trim_trailing_nulls(pack_file)) {
+                            Ok(paths) => sender.send(Data::VecVecString(paths)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to check if the PackFile decodes fine with the schema of a game...
+                    Commands::ValidatePackForGame => {
+                        let game = if let Data::String(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match Schema::load_with_patches(&SUPPORTED_GAMES.get(&*game).unwrap().schema) {
+                            Ok(schema) => {
+                                match validate_pack_for_schema(&mut pack_file_decoded, &schema) {
+                                    Ok(failures) => sender.send(Data::VecVecStringString(failures)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            Err(_) => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to check if a group of columns of a table have unique values, like a composite key...
+                    Commands::CheckCompositeUniqueness => {
+                        let (path, columns) = if let Data::VecStringVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match check_composite_uniqueness(&mut pack_file_decoded, &path, &columns, schema) {
+                                    Ok(conflicts) => sender.send(Data::VecVecUsize(conflicts)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to append a snapshot of the row counts of the tables to a log file...
+                    Commands::AppendRowCountSnapshot => {
+                        let log_path = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match append_row_count_snapshot(&mut pack_file_decoded, &log_path) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the PackedFiles that are identical to their vanilla counterpart...
+                    Commands::FindRedundantOverrides => {
+                        match find_redundant_overrides(&mut pack_file_decoded, &mut DEPENDENCY_DATABASE.lock().unwrap()) {
+                            Ok(paths) => sender.send(Data::VecVecString(paths)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to get a key that's not in use for a new row of a table...
+                    Commands::SuggestUniqueKey => {
+                        let (path, base) = if let Data::VecStringString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match suggest_unique_key(&mut pack_file_decoded, &path, &base, schema) {
+                                    Ok(key) => sender.send(Data::String(key)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export a table side by side with his vanilla version...
+                    Commands::ExportVanillaModComparisonTsv => {
+                        let (path, out) = if let Data::VecStringPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match export_vanilla_mod_comparison(&mut pack_file_decoded, &mut DEPENDENCY_DATABASE.lock().unwrap(), &path, schema, &out) {
+                                    Ok(_) => sender.send(Data::Success).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the assets of the PackFile not referenced by any table...
+                    Commands::FindUnreferencedAssets => {
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match find_unreferenced_assets(&mut pack_file_decoded, &mut DEPENDENCY_DATABASE.lock().unwrap(), schema) {
+                                    Ok(paths) => sender.send(Data::VecVecString(paths)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to run an edit script over the tables of the PackFile...
+                    Commands::RunEditScript => {
+                        let operations = if let Data::VecEditOperation(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let command = background_thread_extra::LoggedCommand::RunEditScript { operations: operations.to_vec() };
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| run_edit_script(pack_file, &operations, schema)) {
+                                    Ok(report) => sender.send(Data::VecString(report)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to get the size of the tables of the PackFile by table type...
+                    Commands::SizeByTableType => {
+                        sender.send(Data::BTreeMapStringU64(get_size_by_table_type(&pack_file_decoded))).unwrap();
+                    }
+
+                    // In case we want to lock/unlock the schema...
+                    Commands::SetSchemaLocked => {
+                        let state = if let Data::Bool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        *SCHEMA_LOCKED.lock().unwrap() = state;
+                    }
+
+                    // In case we want to find the string cells longer than expected in the tables of the PackFile...
+                    Commands::FindOversizedCells => {
+                        let threshold = if let Data::Usize(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match find_oversized_cells(&mut pack_file_decoded, threshold, schema) {
+                                    Ok(cells) => sender.send(Data::VecOversizedCell(cells)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export the tree of the PackFile as a HTML page...
+                    Commands::ExportTreeAsHtml => {
+                        let out = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match export_tree_as_html(&pack_file_decoded, &out) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to check the columns of a Loc PackedFile are in the right order...
+                    Commands::VerifyLocColumnOrder => {
+                        let path = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        if !path.last().map_or(false, |x| x.ends_with(".loc")) { sender.send(Data::Error(Error::from(ErrorKind::LocPackedFileIsNotALocPackedFile))).unwrap(); }
+                        else {
+
+                            // The definition has to come from the PackedFile itself. The one we use to decode Locs is always right.
+                            match pack_file_decoded.packed_files.iter_mut().find(|x| x.path == path) {
+                                Some(packed_file) => match packed_file.get_data_and_keep_it().and_then(|data| Loc::read_definition(&data)) {
+                                    Ok(table_definition) => sender.send(Data::VecString(verify_loc_column_order(&table_definition))).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                                None => sender.send(Data::Error(Error::from(ErrorKind::PackedFileNotFound))).unwrap(),
+                            }
+                        }
+                    }
+
+                    // In case we want to get the stats of the edits done to the PackFile since it was opened...
+                    Commands::GetSessionEditStats => {
+                        sender.send(Data::SessionEditStats(session_edit_stats.clone())).unwrap();
+                    }
+
+                    // In case we want to find the PackedFiles whose paths only differ in their case...
+                    Commands::FindCaseCollisions => {
+                        sender.send(Data::VecVecVecString(pack_file_decoded.get_case_collisions())).unwrap();
+                    }
+
+                    // In case we want to export a report of what the optimizer would do with the PackFile...
+                    Commands::OptimizationReport => {
+                        let out = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match background_thread_extra::export_optimization_report(&mut pack_file_decoded, &DEPENDENCY_DATABASE.lock().unwrap(), &out) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to replace every occurrence of a value in a column of a table...
+                    Commands::ReplaceInColumn => {
+                        let data = if let Data::VecStringStringStringString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let command = background_thread_extra::LoggedCommand::ReplaceInColumn { path: data.0.to_vec(), column: data.1.to_owned(), from: data.2.to_owned(), to: data.3.to_owned() };
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| replace_in_column(pack_file, &data.0, &data.1, &data.2, &data.3, schema)) {
+                                    Ok(replaced) => sender.send(Data::Usize(replaced)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to check the key of a table is unique and not empty...
+                    Commands::ValidateKeyColumn => {
+                        let path = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match validate_key_column(&mut pack_file_decoded, &path, schema) {
+                                    Ok(violations) => sender.send(Data::VecString(violations)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export the Loc rows whose text contains a substring...
+                    Commands::ExportLocMatches => {
+                        let data = if let Data::StringBoolPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match export_loc_matches(&mut pack_file_decoded, &data.0, data.1, &data.2) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to merge the identical rows next to each other in a table...
+                    Commands::MergeIdenticalAdjacentRows => {
+                        let path = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let command = background_thread_extra::LoggedCommand::MergeIdenticalAdjacentRows { path: path.to_vec() };
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| merge_identical_adjacent_rows(pack_file, &path, schema)) {
+                                    Ok(removed) => sender.send(Data::Usize(removed)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export the checksums of the tables of the PackFile...
+                    Commands::ExportTableChecksums => {
+                        let out = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match export_table_checksums(&mut pack_file_decoded, schema, &out) {
+                                    Ok(_) => sender.send(Data::Success).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export the log of the commands that edited the PackFile...
+                    Commands::ExportCommandLog => {
+                        let out = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match background_thread_extra::export_command_log(&command_log, &out) {
+                            Ok(_) => sender.send(Data::Success).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to replay a command log over the PackFile...
+                    Commands::ReplayCommandLog => {
+                        let path = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match background_thread_extra::load_command_log(&path) {
+                            Ok(commands) => {
+                                let schema = SCHEMA.lock().unwrap();
+                                let command = background_thread_extra::LoggedCommand::ReplayCommandLog { commands: commands.to_vec() };
+
+                                // In "Dry Run" mode the replay already works over a copy, so it doesn't need to make another one.
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| background_thread_extra::replay_command_log(pack_file, &commands, schema.as_ref(), &DEPENDENCY_DATABASE.lock().unwrap(), dry_run)) {
+                                    Ok(report) => sender.send(Data::VecString(report)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export the selected tables to a SQLite database...
+                    Commands::ExportToSQLite => {
+                        let data = if let Data::VecPathTypePathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match export_to_sqlite(&mut pack_file_decoded, &data.0, schema, &data.1) {
+                                    Ok(counts) => sender.send(Data::UsizeUsize(counts)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to import the tables of a SQLite database into the PackFile...
+                    Commands::ImportFromSQLite => {
+                        let path = if let Data::PathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let command = background_thread_extra::LoggedCommand::ImportFromSQLite { path: path.to_path_buf() };
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| import_from_sqlite(pack_file, schema, &path)) {
+                                    Ok(result) => sender.send(Data::VecVecStringVecVecStringVecString(result)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the loc keys needed by the DB Tables of the PackFile that don't exist...
+                    Commands::FindMissingLocKeys => {
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                match find_missing_loc_keys(&mut pack_file_decoded, &mut DEPENDENCY_DATABASE.lock().unwrap(), schema) {
+                                    Ok(missing_keys) => sender.send(Data::VecMissingLocKey(missing_keys)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to add blank entries for some loc keys to a Loc PackedFile...
+                    Commands::AddLocStubs => {
+                        let (path, loc_keys) = if let Data::VecStringVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        let command = background_thread_extra::LoggedCommand::AddLocStubs { path: path.to_vec(), loc_keys: loc_keys.to_vec() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| add_loc_stubs(pack_file, &path, &loc_keys)) {
+                            Ok(result) => sender.send(Data::UsizeBool(result)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the loc keys defined more than once in the PackFile...
+                    Commands::FindDuplicatedLocKeys => {
+                        match find_duplicated_loc_keys(&mut pack_file_decoded) {
+                            Ok(duplicated_keys) => sender.send(Data::VecDuplicatedLocKey(duplicated_keys)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to keep only one of the entries of some duplicated loc keys...
+                    Commands::ResolveDuplicatedLocKeys => {
+                        let resolutions = if let Data::VecStringUsize(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        let command = background_thread_extra::LoggedCommand::ResolveDuplicatedLocKeys { resolutions: resolutions.to_vec() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| resolve_duplicated_loc_keys(pack_file, &resolutions)) {
+                            Ok(paths) => sender.send(Data::VecVecString(paths)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to generate the loc entries of the rows of a DB Table...
+                    Commands::GenerateLocEntries => {
+                        let (table_path, loc_path, templates) = if let Data::VecStringVecStringVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let command = background_thread_extra::LoggedCommand::GenerateLocEntries { table_path: table_path.to_vec(), loc_path: loc_path.to_vec(), templates: templates.to_vec() };
+                                match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| generate_loc_entries(pack_file, &table_path, &loc_path, &templates, schema)) {
+                                    Ok(result) => sender.send(Data::UsizeUsizeBool(result)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to export some Loc PackedFiles to a bilingual TSV file...
+                    Commands::ExportBilingualTSV => {
+                        let (locs, target_pack_file_path, path) = if let Data::VecVecStringPathBufPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match export_bilingual_tsv(&mut pack_file_decoded, &locs, &target_pack_file_path, &path) {
+                            Ok(missing_keys) => sender.send(Data::VecString(missing_keys)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to import a bilingual TSV file into a Loc PackedFile...
+                    Commands::ImportBilingualTSV => {
+                        let (loc_path, path) = if let Data::VecStringPathBuf(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        let command = background_thread_extra::LoggedCommand::ImportBilingualTSV { loc_path: loc_path.to_vec(), path: path.to_path_buf() };
+                        match background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| import_bilingual_tsv(pack_file, &loc_path, &path)) {
+                            Ok(result) => sender.send(Data::UsizeVecString(result)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
+                    }
+
+                    // In case we want to find the loc entries that still have the english text...
+                    Commands::FindUntranslatedLocEntries => {
+                        let ignored_patterns = if let Data::VecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        let mut english_locs = background_thread_extra::load_english_loc_packfiles();
+                        if english_locs.is_empty() { sender.send(Data::Error(Error::from(ErrorKind::EnglishLocPackNotFound))).unwrap(); }
+                        else {
+                            match find_untranslated_loc_entries(&mut pack_file_decoded, &mut english_locs, &ignored_patterns) {
+                                Ok(matches) => sender.send(Data::VecGlobalMatch(matches)).unwrap(),
+                                Err(error) => sender.send(Data::Error(error)).unwrap(),
+                            }
+                        }
+                    }
+
+                    // In case we want to preview or apply the renaming of a key prefix...
+                    Commands::RenameKeyPrefix => {
+                        let (old_prefix, new_prefix, rename_db_cells, apply) = if let Data::StringStringBoolBool(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match *SCHEMA.lock().unwrap() {
+                            Some(ref schema) => {
+                                let result = if apply {
+                                    let command = background_thread_extra::LoggedCommand::RenameKeyPrefix { old_prefix: old_prefix.to_owned(), new_prefix: new_prefix.to_owned(), rename_db_cells };
+                                    background_thread_extra::edit_packfile(&mut pack_file_decoded, dry_run, &mut command_log, command, |pack_file| rename_key_prefix(pack_file, &old_prefix, &new_prefix, rename_db_cells, true, schema))
+                                } else { rename_key_prefix(&mut pack_file_decoded, &old_prefix, &new_prefix, rename_db_cells, false, schema) };
+
+                                match result {
+                                    Ok(changes) => sender.send(Data::VecKeyPrefixChange(changes)).unwrap(),
+                                    Err(error) => sender.send(Data::Error(error)).unwrap(),
+                                }
+                            }
+                            None => sender.send(Data::Error(Error::from(ErrorKind::SchemaNotFound))).unwrap(),
+                        }
+                    }
+
+                    // In case we want to compare a table with one of the "Add from PackFile" PackFile...
+                    Commands::DiffTableWithPackFile => {
+                        let (path, other_path) = if let Data::VecStringVecString(data) = check_message_validity_recv(&receiver_data) { data } else { panic!(THREADS_MESSAGE_ERROR) };
+                        match diff_table_with_packfile(&pack_file_decoded, &pack_file_decoded_extra, &path, &other_path) {
+                            Ok(diff) => sender.send(Data::TableDiff(diff)).unwrap(),
+                            Err(error) => sender.send(Data::Error(error)).unwrap(),
+                        }
                     }
                 }
             }
@@ -12,7 +12,9 @@
 // As a rule, there should be no UI-related stuff in this module or his childrens.
 
 use bincode::deserialize;
+use serde_derive::{Serialize, Deserialize};
 
+use std::collections::BTreeMap;
 use std::fs::{File, DirBuilder};
 use std::io::{Read, Write};
 use std::path::PathBuf;
@@ -21,17 +23,138 @@ use std::io::BufWriter;
 
 use crate::SUPPORTED_GAMES;
 use crate::GAME_SELECTED;
-use crate::DEPENDENCY_DATABASE;
 use crate::SCHEMA;
 use crate::SETTINGS;
 use crate::common::*;
+use crate::common::coding_helpers::encode_string_u8;
 use crate::error::{Error, ErrorKind, Result};
 use crate::packfile::{PackFile, PFHVersion, PFHFileType, PathType};
+use crate::packfile::extraction_hooks::ExtractionHooks;
 use crate::packfile::packedfile::PackedFile;
-use crate::packedfile::DecodedData;
+use crate::packedfile::{DecodedData, EditOperation, PackedFileType, add_loc_stubs, create_packed_file, generate_loc_entries, get_row_counts, import_bilingual_tsv, import_from_sqlite, merge_identical_adjacent_rows, merge_tables, new_tsv_writer};
+use crate::packedfile::{rename_key_prefix, replace_in_column, resolve_duplicated_loc_keys, run_edit_script, save_tsv_writer, set_table_data, split_table_by_column, strip_boms_from_text};
+use crate::packedfile::{trim_trailing_nulls, tsv_mass_import, tsv_mass_import_from_folder};
 use crate::packedfile::loc::Loc;
 use crate::packedfile::db::DB;
 use crate::packedfile::rigidmodel::RigidModel;
+use crate::schema::Schema;
+
+/// This enum holds a command that edited the open PackFile, with everything needed to do it again.
+/// A list of these is the command log of the session, which can be exported and replayed (see `replay_command_log()`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum LoggedCommand {
+    AddPackedFile {
+        file_path: PathBuf,
+        path: Vec<String>,
+    },
+    DeletePackedFiles {
+        item_types: Vec<PathType>,
+    },
+    RenamePackedFiles {
+        renaming_data: Vec<(PathType, String)>,
+    },
+    CreatePackedFile {
+        path: Vec<String>,
+        packed_file_type: PackedFileType,
+    },
+    RunEditScript {
+        operations: Vec<EditOperation>,
+    },
+    ReplaceInColumn {
+        path: Vec<String>,
+        column: String,
+        from: String,
+        to: String,
+    },
+    MergeIdenticalAdjacentRows {
+        path: Vec<String>,
+    },
+    PatchSiegeAI,
+    AddPackedFileFromPackFile {
+        source_pack_file_path: PathBuf,
+        path_type: PathType,
+    },
+    MassImportTSV {
+        tsv_paths: Vec<PathBuf>,
+        name: Option<String>,
+        skip_bad_rows: bool,
+    },
+    MassImportTSVFromFolder {
+        folder_path: PathBuf,
+    },
+    OptimizePackFile,
+    MergeTables {
+        source_paths: Vec<Vec<String>>,
+        name: String,
+        delete_source_paths: bool,
+        table_type: bool,
+        remove_duplicates: bool,
+    },
+    StripBomsFromText,
+    SplitTableByColumn {
+        path: Vec<String>,
+        column: String,
+        keep_original: bool,
+    },
+    RestoreDeletedFile {
+        path: Vec<String>,
+        overwrite: bool,
+    },
+    ReloadPackedFileFromDisk {
+        path: Vec<String>,
+    },
+    TrimTrailingNulls,
+    ImportFromSQLite {
+        path: PathBuf,
+    },
+    AddLocStubs {
+        path: Vec<String>,
+        loc_keys: Vec<String>,
+    },
+    ResolveDuplicatedLocKeys {
+        resolutions: Vec<(String, usize)>,
+    },
+    GenerateLocEntries {
+        table_path: Vec<String>,
+        loc_path: Vec<String>,
+        templates: Vec<String>,
+    },
+    ImportBilingualTSV {
+        loc_path: Vec<String>,
+        path: PathBuf,
+    },
+    RenameKeyPrefix {
+        old_prefix: String,
+        new_prefix: String,
+        rename_db_cells: bool,
+    },
+    ReplayCommandLog {
+        commands: Vec<LoggedCommand>,
+    },
+    SetTableData {
+        path: Vec<String>,
+        entries: Vec<Vec<DecodedData>>,
+    },
+    SetTextData {
+        path: Vec<String>,
+        text: String,
+    },
+}
+
+/// This trait tells `edit_packfile()` if the operation it run worked, so only the commands that worked get into the command log.
+pub trait EditResult {
+    fn is_success(&self) -> bool;
+}
+
+/// Operations that can fail return a `Result`, and they only worked if it's `Ok`.
+impl<T> EditResult for Result<T> {
+    fn is_success(&self) -> bool { self.is_ok() }
+}
+
+/// Operations that return a list (like deleting or renaming) can't fail. They just skip what they can't do.
+impl<T> EditResult for Vec<T> {
+    fn is_success(&self) -> bool { true }
+}
 
 /*
 --------------------------------------------------------
@@ -190,6 +313,26 @@ pub fn load_dependency_packfiles(dependencies: &[String]) -> Vec<PackedFile> {
     packed_files
 }
 
+/// This function loads all the Loc PackedFiles from the english Loc PackFile (`local_en.pack`) of the game selected, if it's configured.
+/// It returns them in a Vec<PackedFile>.
+pub fn load_english_loc_packfiles() -> Vec<PackedFile> {
+    let mut packed_files = vec![];
+    if let Some(paths) = get_game_selected_loc_pack_path() {
+        for path in paths.iter().filter(|x| x.file_name().unwrap().to_string_lossy() == "local_en.pack") {
+            if let Ok(pack_file) = open_packfiles(&[path.to_path_buf()], false, true, false) {
+                for packed_file in pack_file.packed_files.iter() {
+                    if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+                        let mut packed_file = packed_file.clone();
+                        let _ = packed_file.load_data();
+                        packed_files.push(packed_file);
+                    }
+                }
+            }
+        }
+    }
+    packed_files
+}
+
 /// This function is a special open function, to get all the fake DB files from the PAK file of the Game Selected,
 /// if it does has one.
 pub fn load_fake_dependency_packfiles() -> Vec<DB> {
@@ -335,23 +478,51 @@ pub fn open_packfiles(
 /// - pack_file: a &mut pack_file::PackFile. It's the PackFile we are going to save.
 /// - new_path: an Option<PathBuf> with the path were we are going to save the PackFile. None if we
 ///   are saving it in the same path it's when we opened it.
+/// - dry_run: if true, the PackFile is not saved. Instead, we return an error reporting where it would have been saved.
 pub fn save_packfile(
     mut pack_file: &mut PackFile,
     new_path: Option<PathBuf>,
-    is_editing_of_ca_packfiles_allowed: bool
+    is_editing_of_ca_packfiles_allowed: bool,
+    dry_run: bool,
 ) -> Result<()> {
 
     // If any of the problematic masks in the header is set or is one of CA's, return an error.
     if !pack_file.is_editable(is_editing_of_ca_packfiles_allowed) { return Err(ErrorKind::PackFileIsNonEditable)? }
 
-    // If we receive a new path, update it. Otherwise, ensure the file actually exists on disk.
+    // If we don't receive a new path, ensure the file actually exists on disk.
+    if new_path.is_none() && !pack_file.file_path.is_file() { return Err(ErrorKind::PackFileIsNotAFile)? }
+
+    // Saving in "Dry Run" mode would persist the changes, so we just report what we would have saved.
+    if dry_run {
+        let path = new_path.as_ref().unwrap_or(&pack_file.file_path);
+        return Err(ErrorKind::SavePackFileGeneric(format!("\"Dry Run\" mode is enabled, so nothing has been saved. Otherwise, {} PackedFiles would have been saved to <i>{}</i>.", pack_file.packed_files.len(), path.to_string_lossy())))?
+    }
+
+    // If we receive a new path, update it. And then we try to save it.
     if let Some(path) = new_path { pack_file.file_path = path; }
-    else if !pack_file.file_path.is_file() { return Err(ErrorKind::PackFileIsNotAFile)? }
-    
-    // And we try to save it.
     PackFile::save(&mut pack_file)
 }
 
+/// This function is used to save a shadow copy of the open PackFile into the `autosaves` folder, so it can be
+/// recovered if RPFM crashes. The open PackFile keeps his path and timestamp, as the shadow copy is not a real save.
+/// It requires:
+/// - pack_file: a &mut pack_file::PackFile. It's the PackFile we are going to autosave.
+pub fn save_autosave_shadow(pack_file: &mut PackFile) -> Result<()> {
+    DirBuilder::new().recursive(true).create(get_autosave_folder_path())?;
+    let (shadow_path, source_path) = get_autosave_shadow_paths();
+
+    let file_path = pack_file.file_path.to_path_buf();
+    let timestamp = pack_file.timestamp;
+    pack_file.file_path = shadow_path;
+    let result = PackFile::save(pack_file);
+    pack_file.file_path = file_path;
+    pack_file.timestamp = timestamp;
+    result?;
+
+    File::create(source_path)?.write_all(pack_file.file_path.to_string_lossy().as_bytes())?;
+    Ok(())
+}
+
 /// This function is used to add a file to a PackFile, processing it and turning it into a PackedFile.
 /// It returns a success or error message, depending on whether the file has been added, or not.
 /// It requires:
@@ -513,6 +684,33 @@ pub fn add_packedfile_to_packfile(
     Ok(path_types_added)
 }
 
+/// This function is used to run an operation that edits the provided PackFile. If `dry_run` is true, the operation
+/// is done over a copy of the PackFile, so we get his result (what he would have changed) without the PackFile changing.
+/// The copy shares the data of the PackedFiles with the PackFile, so only the PackedFiles the operation changes get their data copied.
+///
+/// Every edit goes through here, so this is also where the command log gets filled: if the operation worked
+/// and it's not a "Dry Run", the provided command is added to the log.
+pub fn edit_packfile<T, F>(
+    pack_file: &mut PackFile,
+    dry_run: bool,
+    command_log: &mut Vec<LoggedCommand>,
+    command: LoggedCommand,
+    operation: F,
+) -> T where F: FnOnce(&mut PackFile) -> T, T: EditResult {
+    if dry_run { operation(&mut pack_file.clone()) }
+    else {
+        let result = operation(pack_file);
+        if result.is_success() { command_log.push(command); }
+        result
+    }
+}
+
+/// This function returns the report of a change not done to the PackFile because we are in "Dry Run" mode.
+/// It's for the changes that have no result to show, so the UI can tell the user what would have changed.
+pub fn get_dry_run_report(change: &str) -> String {
+    format!("\"Dry Run\" mode is enabled, so the PackFile has not been changed. Otherwise, {}", change)
+}
+
 /// This function is used to delete a PackedFile or a group of PackedFiles of the provided types
 /// from the PackFile. We just need the open PackFile and the PathTypes of the files/folders to delete.
 pub fn delete_from_packfile(
@@ -597,7 +795,7 @@ pub fn delete_from_packfile(
                     PathType::File(path) => {
    
                         let index = pack_file.packed_files.iter().position(|x| &x.path == path).unwrap();
-                        pack_file.remove_packedfile(index);
+                        pack_file.delete_packedfile(index);
                     },
 
                     PathType::Folder(path) => {
@@ -613,7 +811,7 @@ pub fn delete_from_packfile(
                         }
 
                         // For each PackedFile we want to remove (in reverse), we remove it individually.
-                        indexes.iter().rev().for_each(|index| pack_file.remove_packedfile(*index));
+                        indexes.iter().rev().for_each(|index| pack_file.delete_packedfile(*index));
                     },
 
                     _ => unreachable!(),
@@ -622,7 +820,7 @@ pub fn delete_from_packfile(
         },
 
         // If the PackFile is selected, get it just extract the PackFile and everything will get extracted with it.
-        4 | 5 | 6 | 7 => pack_file.remove_all_packedfiles(),
+        4 | 5 | 6 | 7 => (0..pack_file.packed_files.len()).rev().for_each(|index| pack_file.delete_packedfile(index)),
 
         // No paths selected, none selected, invalid path selected, or invalid value. 
         0 | 8..=255 => {},
@@ -643,11 +841,14 @@ pub fn extract_from_packfile(
     pack_file: &PackFile,
     item_types: &[PathType],
     extracted_path: &PathBuf,
+    extraction_hooks: &ExtractionHooks,
+    game: &str,
 ) -> Result<String> {
 
     // These variables are here to keep track of what we have extracted and what files failed.
     let (mut file, mut folder, mut packfile, mut none) = (0, 0, 0, 0);
     let mut files_extracted = 0;
+    let mut extracted_files = vec![];
     let mut error_files = vec![];
 
     // We need to "clean" the selected path list to ensure we don't pass stuff already deleted.
@@ -739,7 +940,10 @@ pub fn extract_from_packfile(
                         current_path.push(&file_name);
                         let mut file = BufWriter::new(File::create(&current_path)?);
                         match file.write_all(&packed_file.get_data()?){
-                            Ok(_) => files_extracted += 1,
+                            Ok(_) => {
+                                files_extracted += 1;
+                                extracted_files.push((packed_file.path.to_vec(), current_path.clone()));
+                            },
                             Err(_) => error_files.push(format!("{:?}", current_path)),
                         }
                     },
@@ -761,7 +965,10 @@ pub fn extract_from_packfile(
                                 current_path.push(&file_name);
                                 let mut file = BufWriter::new(File::create(&current_path)?);
                                 match file.write_all(&packed_file.get_data()?){
-                                    Ok(_) => files_extracted += 1,
+                                    Ok(_) => {
+                                        files_extracted += 1;
+                                        extracted_files.push((packed_file.path.to_vec(), current_path.clone()));
+                                    },
                                     Err(_) => error_files.push(format!("{:?}", current_path)),
                                 }
                             }
@@ -791,7 +998,10 @@ pub fn extract_from_packfile(
                 current_path.push(&file_name);
                 let mut file = BufWriter::new(File::create(&current_path)?);
                 match file.write_all(&packed_file.get_data()?){
-                    Ok(_) => files_extracted += 1,
+                    Ok(_) => {
+                        files_extracted += 1;
+                        extracted_files.push((packed_file.path.to_vec(), current_path.clone()));
+                    },
                     Err(_) => error_files.push(format!("{:?}", current_path)),
                 }
             }
@@ -807,8 +1017,10 @@ pub fn extract_from_packfile(
         return Err(ErrorKind::ExtractError(error_files_string))?
     }
 
-    // If we reach this, return success.
-    Ok(format!("{} files extracted. No errors detected.", files_extracted))
+    // Once everything is extracted, run the hooks of the game over the extracted files, and report what they did.
+    let hooks_report = extraction_hooks.run(game, &extracted_files)?;
+    if hooks_report.is_empty() { Ok(format!("{} files extracted. No errors detected.", files_extracted)) }
+    else { Ok(format!("{} files extracted. No errors detected.<br/><br/>Post-extraction hooks applied:<ul>{}</ul>", files_extracted, hooks_report.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>())) }
 }
 
 /// This function is used to rename anything in the TreeView (PackFile not included).
@@ -884,9 +1096,10 @@ pub fn update_packed_file_data_loc(
     packed_file_data_decoded: &Loc,
     pack_file: &mut PackFile,
     path: &[String],
-) {
-    let packed_file = &mut pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound)).unwrap();
+) -> Result<()> {
+    let packed_file = pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
     packed_file.set_data(Loc::save(packed_file_data_decoded));
+    Ok(())
 }
 
 /// Like the other one, but this one requires a PackedFile.
@@ -903,10 +1116,11 @@ pub fn update_packed_file_data_db(
     packed_file_data_decoded: &DB,
     pack_file: &mut PackFile,
     path: &[String],
-) {
+) -> Result<()> {
 
-    let packed_file = &mut pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound)).unwrap();
+    let packed_file = pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
     packed_file.set_data(DB::save(packed_file_data_decoded));
+    Ok(())
 }
 
 // Same as the other one, but it requires a PackedFile to modify instead the entire PackFile.
@@ -923,9 +1137,10 @@ pub fn update_packed_file_data_text(
     packed_file_data_decoded: &[u8],
     pack_file: &mut PackFile,
     path: &[String],
-) {
-    let packed_file = &mut pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound)).unwrap();
+) -> Result<()> {
+    let packed_file = pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
     packed_file.set_data(packed_file_data_decoded.to_vec());
+    Ok(())
 }
 
 /// This function saves the data of the edited RigidModel PackedFile in the main PackFile after a change has
@@ -936,7 +1151,7 @@ pub fn update_packed_file_data_rigid(
     pack_file: &mut PackFile,
     path: &[String],
 ) -> Result<String> {
-    let packed_file = &mut pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound)).unwrap();
+    let packed_file = pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
     packed_file.set_data(RigidModel::save(packed_file_data_decoded)?);
 
     Ok(format!("RigidModel PackedFile updated successfully."))
@@ -1109,7 +1324,7 @@ pub fn patch_rigid_model_attila_to_warhammer (
 /// This function is used to optimize the size of a PackFile. It does two things: removes unchanged rows
 /// from tables (and if the table is empty, it removes it too) and it cleans the PackFile of extra .xml files 
 /// often created by map editors. It requires just the PackFile to optimize and the dependency PackFile.
-pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
+pub fn optimize_packfile(pack_file: &mut PackFile, dependency_database: &[PackedFile]) -> Result<Vec<PathType>> {
     
     // List of PackedFiles to delete. This includes empty DB Tables and empty Loc PackedFiles.
     let mut files_to_delete: Vec<Vec<String>> = vec![];
@@ -1117,7 +1332,7 @@ pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
 
     // Get a list of every Loc and DB PackedFiles in our dependency's files. For performance reasons, we decode every one of them here.
     // Otherwise, they may have to be decoded multiple times, making this function take ages to finish. 
-    let game_locs = DEPENDENCY_DATABASE.lock().unwrap().iter()
+    let game_locs = dependency_database.iter()
         .filter(|x| x.path.last().unwrap().ends_with(".loc"))
         .map(|x| x.get_data())
         .filter(|x| x.is_ok())
@@ -1127,7 +1342,7 @@ pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
         .collect::<Vec<Loc>>();
 
     let mut game_dbs = if let Some(ref schema) = *SCHEMA.lock().unwrap() {
-        DEPENDENCY_DATABASE.lock().unwrap().iter()
+        dependency_database.iter()
             .filter(|x| x.path.len() == 3 && x.path[0] == "db")
             .map(|x| (x.get_data(), x.path[1].to_owned()))
             .filter(|x| x.0.is_ok())
@@ -1143,7 +1358,8 @@ pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
         .for_each(|x| if let DecodedData::Float(data) = x { *data = (*data * 1000f32).round() / 1000f32 })
     ));
 
-    let database_path_list = DEPENDENCY_DATABASE.lock().unwrap().iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+    let database_path_list = dependency_database.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+    let table_aliases = pack_file.table_aliases.clone();
     for mut packed_file in &mut pack_file.packed_files {
 
         // Unless we specifically wanted to, ignore the same-name-as-vanilla files,
@@ -1155,7 +1371,8 @@ pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
             if let Some(ref schema) = *SCHEMA.lock().unwrap() {
 
                 // Try to decode our table.
-                let mut optimized_table = match DB::read(&(packed_file.get_data_and_keep_it()?), &packed_file.path[1], &schema) {
+                let table_name = table_aliases.get(&packed_file.path[1]).unwrap_or(&packed_file.path[1]).to_owned();
+                let mut optimized_table = match DB::read(&(packed_file.get_data_and_keep_it()?), &table_name, &schema) {
                     Ok(table) => table,
                     Err(_) => continue,
                 };
@@ -1166,19 +1383,12 @@ pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
                     .for_each(|x| if let DecodedData::Float(data) = x { *data = (*data * 1000f32).round() / 1000f32 })
                 );
 
-                // For each vanilla DB Table that coincide with our own, compare it row by row, cell by cell, with our own DB Table. Then delete in reverse every coincidence.
-                for game_db in &game_dbs {
-                    if game_db.db_type == optimized_table.db_type && game_db.version == optimized_table.version {
-                        let rows_to_delete = optimized_table.entries.iter().enumerate().filter(|(_, entry)| game_db.entries.contains(entry)).map(|(row, _)| row).collect::<Vec<usize>>();
-                        for row in rows_to_delete.iter().rev() {
-                            optimized_table.entries.remove(*row);
-                        } 
-                    }
-                }
+                // Remove every row that's also in a vanilla DB Table of the same type and version.
+                let emptied = optimized_table.optimize_table(&game_dbs);
 
                 // Save the data to the PackFile and, if it's empty, add it to the deletion list.
                 update_packed_file_data_db_2(&optimized_table, &mut packed_file);
-                if optimized_table.entries.is_empty() { files_to_delete.push(packed_file.path.to_vec()); }
+                if emptied { files_to_delete.push(packed_file.path.to_vec()); }
             }
 
             // Otherwise, we just check if it's empty. In that case, we delete it.
@@ -1196,17 +1406,12 @@ pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
                 Err(_) => continue,
             };
 
-            // For each vanilla Loc, compare it row by row, cell by cell, with our own Loc. Then delete in reverse every coincidence.
-            for game_loc in &game_locs {
-                let rows_to_delete = optimized_loc.entries.iter().enumerate().filter(|(_, entry)| game_loc.entries.contains(entry)).map(|(row, _)| row).collect::<Vec<usize>>();
-                for row in rows_to_delete.iter().rev() {
-                    optimized_loc.entries.remove(*row);
-                } 
-            }
+            // Remove every row that's also in any of the vanilla Locs.
+            let emptied = optimized_loc.optimize_table(&game_locs);
 
             // Save the data to the PackFile and, if it's empty, add it to the deletion list.
             update_packed_file_data_loc_2(&optimized_loc, &mut packed_file);
-            if optimized_loc.entries.is_empty() { files_to_delete.push(packed_file.path.to_vec()); }
+            if emptied { files_to_delete.push(packed_file.path.to_vec()); }
         }
     }
 
@@ -1224,3 +1429,210 @@ pub fn optimize_packfile(pack_file: &mut PackFile) -> Result<Vec<PathType>> {
     // Return the deleted file's types.
     Ok(deleted_files_type)
 }
+
+/// This function returns a report of what `optimize_packfile()` would do with the provided PackFile and dependency database, without changing it.
+///
+/// The first row is the header. Then there is a row for each DB Table or Loc PackedFile the optimizer would change, with
+/// his row count, how many rows would be removed, the estimated bytes saved and if the table would be emptied (and deleted).
+pub fn get_optimization_report(pack_file: &mut PackFile, dependency_database: &[PackedFile]) -> Result<Vec<Vec<String>>> {
+    let rows_before = get_row_counts(pack_file)?;
+    let sizes_before = pack_file.packed_files.iter().map(|x| (x.path.to_vec(), x.get_size())).collect::<BTreeMap<Vec<String>, u32>>();
+
+    // The optimization is done in "Dry Run" mode, over a copy of the PackFile, so nothing gets logged.
+    let (deleted_files, rows_after, sizes_after) = edit_packfile(pack_file, true, &mut vec![], LoggedCommand::OptimizePackFile, |pack_file| -> Result<_> {
+        let deleted_files = optimize_packfile(pack_file, dependency_database)?.iter().filter_map(|x| if let PathType::File(path) = x { Some(path.to_vec()) } else { None }).collect::<Vec<Vec<String>>>();
+        let rows_after = get_row_counts(pack_file)?.into_iter().collect::<BTreeMap<Vec<String>, u32>>();
+        let sizes_after = pack_file.packed_files.iter().map(|x| (x.path.to_vec(), x.get_size())).collect::<BTreeMap<Vec<String>, u32>>();
+        Ok((deleted_files, rows_after, sizes_after))
+    })?;
+
+    let mut report = vec![vec!["Path".to_owned(), "Rows".to_owned(), "Rows Removed".to_owned(), "Bytes Saved".to_owned(), "Emptied".to_owned()]];
+    for (path, rows) in &rows_before {
+        let emptied = deleted_files.contains(path);
+        let rows_removed = rows - rows_after.get(path).cloned().unwrap_or(0);
+        if rows_removed > 0 || emptied {
+            let bytes_saved = i64::from(sizes_before[path]) - i64::from(sizes_after.get(path).cloned().unwrap_or(0));
+            report.push(vec![path.join("/"), rows.to_string(), rows_removed.to_string(), bytes_saved.to_string(), emptied.to_string()]);
+        }
+    }
+
+    Ok(report)
+}
+
+/// This function exports the report of what `optimize_packfile()` would do with the provided PackFile
+/// (see `get_optimization_report()`) to a TSV file.
+pub fn export_optimization_report(pack_file: &mut PackFile, dependency_database: &[PackedFile], out: &PathBuf) -> Result<()> {
+    let report = get_optimization_report(pack_file, dependency_database)?;
+    let mut writer = new_tsv_writer();
+
+    for row in &report {
+        writer.serialize(row)?;
+    }
+
+    save_tsv_writer(writer, out)?;
+    Ok(())
+}
+
+/// This function saves the provided command log to a JSON file.
+pub fn export_command_log(command_log: &[LoggedCommand], out: &PathBuf) -> Result<()> {
+    let mut file = BufWriter::new(File::create(&out)?);
+    file.write_all(serde_json::to_string_pretty(command_log)?.as_bytes())?;
+    Ok(())
+}
+
+/// This function loads a command log from a JSON file made by `export_command_log()`.
+pub fn load_command_log(path: &PathBuf) -> Result<Vec<LoggedCommand>> {
+    let file = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(file)?)
+}
+
+/// This function applies the commands of a command log to the provided PackFile, in order, as a transaction: the commands are
+/// done over a copy of the PackFile, which only replaces it if every command worked. If any of them fails, the PackFile is left untouched.
+///
+/// If `in_place` is true, the PackFile is already a copy we can discard (like the one of "Dry Run" mode, or the one of the replay
+/// this one is part of), so the commands are done directly over it instead of over another copy.
+///
+/// The commands that edit tables need the schema. It returns a line of report for each command.
+pub fn replay_command_log(
+    pack_file: &mut PackFile,
+    command_log: &[LoggedCommand],
+    schema: Option<&Schema>,
+    dependency_database: &[PackedFile],
+    in_place: bool,
+) -> Result<Vec<String>> {
+    let mut new_pack_file = if in_place { None } else { Some(pack_file.clone()) };
+    let mut report = vec![];
+    {
+        let target = match new_pack_file { Some(ref mut new_pack_file) => new_pack_file, None => &mut *pack_file };
+        for (index, command) in command_log.iter().enumerate() {
+            match replay_command(target, command, schema, dependency_database) {
+                Ok(line) => report.push(line),
+                Err(error) => Err(ErrorKind::CommandLogReplayFailed(index + 1, error.to_string()))?,
+            }
+        }
+    }
+
+    if let Some(new_pack_file) = new_pack_file { *pack_file = new_pack_file; }
+    Ok(report)
+}
+
+/// This function applies a command of a command log to the provided PackFile, returning a line of report about what it did.
+fn replay_command(
+    pack_file: &mut PackFile,
+    command: &LoggedCommand,
+    schema: Option<&Schema>,
+    dependency_database: &[PackedFile],
+) -> Result<String> {
+    let get_schema = || schema.ok_or_else(|| Error::from(ErrorKind::SchemaNotFound));
+    match command {
+        LoggedCommand::AddPackedFile { file_path, path } => {
+            add_file_to_packfile(pack_file, file_path, path.to_vec())?;
+            Ok(format!("PackedFile added: {}", path.join("/")))
+        }
+        LoggedCommand::DeletePackedFiles { item_types } => {
+            let deleted_item_types = delete_from_packfile(pack_file, item_types);
+            Ok(format!("Items deleted: {}", deleted_item_types.len()))
+        }
+        LoggedCommand::RenamePackedFiles { renaming_data } => {
+            let renamed_data = rename_packed_files(pack_file, renaming_data);
+            Ok(format!("Items renamed: {}", renamed_data.len()))
+        }
+        LoggedCommand::CreatePackedFile { path, packed_file_type } => {
+            create_packed_file(pack_file, packed_file_type.clone(), path.to_vec())?;
+            Ok(format!("PackedFile created: {}", path.join("/")))
+        }
+        LoggedCommand::RunEditScript { operations } => {
+            let lines = run_edit_script(pack_file, operations, get_schema()?)?;
+            Ok(format!("Edit script run: {} operations", lines.len()))
+        }
+        LoggedCommand::ReplaceInColumn { path, column, from, to } => {
+            let replaced = replace_in_column(pack_file, path, column, from, to, get_schema()?)?;
+            Ok(format!("Values replaced in the column \"{}\" of {}: {}", column, path.join("/"), replaced))
+        }
+        LoggedCommand::MergeIdenticalAdjacentRows { path } => {
+            let removed = merge_identical_adjacent_rows(pack_file, path, get_schema()?)?;
+            Ok(format!("Rows merged in {}: {}", path.join("/"), removed))
+        }
+        LoggedCommand::PatchSiegeAI => {
+            let (message, _) = patch_siege_ai(pack_file)?;
+            Ok(message)
+        }
+        LoggedCommand::AddPackedFileFromPackFile { source_pack_file_path, path_type } => {
+            let source_pack_file = open_packfiles(&[source_pack_file_path.to_path_buf()], false, true, false)?;
+            let added = add_packedfile_to_packfile(&source_pack_file, pack_file, path_type)?;
+            Ok(format!("Items added from {}: {}", source_pack_file_path.to_string_lossy(), added.len()))
+        }
+        LoggedCommand::MassImportTSV { tsv_paths, name, skip_bad_rows } => {
+            let (overwritten, added, _) = tsv_mass_import(tsv_paths, name.clone(), pack_file, *skip_bad_rows)?;
+            Ok(format!("TSV files imported: {}", overwritten.len() + added.len()))
+        }
+        LoggedCommand::MassImportTSVFromFolder { folder_path } => {
+            let (imported, _) = tsv_mass_import_from_folder(folder_path, pack_file, get_schema()?)?;
+            Ok(format!("TSV files imported from {}: {}", folder_path.to_string_lossy(), imported.len()))
+        }
+        LoggedCommand::OptimizePackFile => {
+            let deleted = optimize_packfile(pack_file, dependency_database)?;
+            Ok(format!("PackFile optimized. PackedFiles deleted: {}", deleted.len()))
+        }
+        LoggedCommand::MergeTables { source_paths, name, delete_source_paths, table_type, remove_duplicates } => {
+            let (path, _) = merge_tables(pack_file, source_paths, name, *delete_source_paths, *table_type, *remove_duplicates)?;
+            Ok(format!("Tables merged into {}: {}", path.join("/"), source_paths.len()))
+        }
+        LoggedCommand::StripBomsFromText => {
+            let paths = strip_boms_from_text(pack_file)?;
+            Ok(format!("BOMs stripped: {}", paths.len()))
+        }
+        LoggedCommand::SplitTableByColumn { path, column, keep_original } => {
+            let paths = split_table_by_column(pack_file, path, column, *keep_original, get_schema()?)?;
+            Ok(format!("{} split by the column \"{}\": {} tables", path.join("/"), column, paths.len()))
+        }
+        LoggedCommand::RestoreDeletedFile { path, overwrite } => {
+            pack_file.restore_deleted_packedfile(path, *overwrite)?;
+            Ok(format!("PackedFile restored: {}", path.join("/")))
+        }
+        LoggedCommand::ReloadPackedFileFromDisk { path } => {
+            pack_file.reload_packedfile_from_disk(path)?;
+            Ok(format!("PackedFile reloaded from disk: {}", path.join("/")))
+        }
+        LoggedCommand::TrimTrailingNulls => {
+            let paths = trim_trailing_nulls(pack_file)?;
+            Ok(format!("Trailing nulls trimmed: {}", paths.len()))
+        }
+        LoggedCommand::ImportFromSQLite { path } => {
+            let (created, replaced, _) = import_from_sqlite(pack_file, get_schema()?, path)?;
+            Ok(format!("Tables imported from {}: {}", path.to_string_lossy(), created.len() + replaced.len()))
+        }
+        LoggedCommand::AddLocStubs { path, loc_keys } => {
+            let (added, _) = add_loc_stubs(pack_file, path, loc_keys)?;
+            Ok(format!("Loc stubs added to {}: {}", path.join("/"), added))
+        }
+        LoggedCommand::ResolveDuplicatedLocKeys { resolutions } => {
+            let paths = resolve_duplicated_loc_keys(pack_file, resolutions)?;
+            Ok(format!("Duplicated loc keys resolved: {} Loc PackedFiles edited", paths.len()))
+        }
+        LoggedCommand::GenerateLocEntries { table_path, loc_path, templates } => {
+            let (generated, _, _) = generate_loc_entries(pack_file, table_path, loc_path, templates, get_schema()?)?;
+            Ok(format!("Loc entries generated in {}: {}", loc_path.join("/"), generated))
+        }
+        LoggedCommand::ImportBilingualTSV { loc_path, path } => {
+            let (imported, _) = import_bilingual_tsv(pack_file, loc_path, path)?;
+            Ok(format!("Entries imported into {}: {}", loc_path.join("/"), imported))
+        }
+        LoggedCommand::RenameKeyPrefix { old_prefix, new_prefix, rename_db_cells } => {
+            let changes = rename_key_prefix(pack_file, old_prefix, new_prefix, *rename_db_cells, true, get_schema()?)?;
+            Ok(format!("Keys renamed from \"{}\" to \"{}\": {}", old_prefix, new_prefix, changes.len()))
+        }
+        LoggedCommand::ReplayCommandLog { commands } => {
+            let lines = replay_command_log(pack_file, commands, schema, dependency_database, true)?;
+            Ok(format!("Command log replayed: {} commands", lines.len()))
+        }
+        LoggedCommand::SetTableData { path, entries } => {
+            set_table_data(pack_file, path, entries.to_vec(), get_schema()?)?;
+            Ok(format!("Table data set in {}: {} rows", path.join("/"), entries.len()))
+        }
+        LoggedCommand::SetTextData { path, text } => {
+            update_packed_file_data_text(&encode_string_u8(text), pack_file, path)?;
+            Ok(format!("Text set in {}", path.join("/")))
+        }
+    }
+}
@@ -17,10 +17,16 @@
 // decode/encode. These are used specially in PackedFiles.
 //
 // Note: the specific decoders return tuples with (value, index of the new thing to decode).
+//
+// Streaming helpers write the same bytes as the specific encoders directly into a `Write` sink,
+// so big PackedFiles can be encoded without allocating a Vec<u8> for every single value.
 
-use byteorder::{ByteOrder, LittleEndian};
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use encoding::{Encoding, DecoderTrap};
 use encoding::all::ISO_8859_1;
+
+use std::io::Write;
+
 use crate::error::{Error, ErrorKind, Result};
 
 //-----------------------------------------------------//
@@ -92,6 +98,15 @@ pub fn decode_float_f32(float_encoded: &[u8]) -> Result<f32> {
     }
 }
 
+/// Common helper. This function allows us to decode a f64 encoded float.
+#[allow(dead_code)]
+pub fn decode_float_f64(float_encoded: &[u8]) -> Result<f64> {
+    match float_encoded.len() {
+        8 => Ok(LittleEndian::read_f64(float_encoded)),
+        _ => Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode an f64 number:</p><ul><li>Required bytes: 8.</li><li>Provided bytes: {}.</li></ul>", float_encoded.len())))?
+    }
+}
+
 /// Common helper. This function allows us to decode an UTF-8 encoded String.
 #[allow(dead_code)]
 pub fn decode_string_u8(string_encoded: &[u8]) -> Result<String> {
@@ -208,6 +223,14 @@ pub fn encode_float_f32(float_decoded: f32) -> Vec<u8> {
     float_encoded.to_vec()
 }
 
+/// Common helper. This function allows us to encode a f64 decoded Float.
+#[allow(dead_code)]
+pub fn encode_float_f64(float_decoded: f64) -> Vec<u8> {
+    let mut float_encoded: [u8;8] = [0;8];
+    LittleEndian::write_f64(&mut float_encoded, float_decoded);
+    float_encoded.to_vec()
+}
+
 /// Common helper. This function allows us to encode an UTF-8 decoded String.
 #[allow(dead_code)]
 pub fn encode_string_u8(string_decoded: &str) -> Vec<u8> {
@@ -303,6 +326,14 @@ pub fn decode_packedfile_float_f32(packed_file_data: &[u8], index: &mut usize) -
     result
 }
 
+/// Specific helper. This function allows us to decode an f64 encoded float, moving the index to the byte where the next data starts.
+#[allow(dead_code)]
+pub fn decode_packedfile_float_f64(packed_file_data: &[u8], index: &mut usize) -> Result<f64> {
+    let result = decode_float_f64(packed_file_data);
+    if result.is_ok() { *index += 8; }
+    result
+}
+
 /// Specific helper. This function allows us to decode an UTF-8 encoded String, moving the index to the byte where the next data starts.
 #[allow(dead_code)]
 pub fn decode_packedfile_string_u8(packed_file_data: &[u8], mut index: &mut usize) -> Result<String> {
@@ -628,3 +659,43 @@ pub fn encode_packedfile_optional_string_u16(optional_string_u16_decoded: &str)
 
     optional_string_u16_encoded
 }
+
+//-----------------------------------------------------//
+//        Encoding helpers (Streaming encoders)
+//-----------------------------------------------------//
+
+/// Streaming helper. This function writes an UTF-8 decoded String with his lenght (an u16 integer) before it. Same bytes as `encode_packedfile_string_u8()`.
+pub fn write_packedfile_string_u8<W: Write>(writer: &mut W, string_u8_decoded: &str) -> Result<()> {
+    writer.write_u16::<LittleEndian>(string_u8_decoded.len() as u16)?;
+    writer.write_all(string_u8_decoded.as_bytes())?;
+    Ok(())
+}
+
+/// Streaming helper. This function writes an UTF-16 decoded String with his lenght (an u16 integer) before it. Same bytes as `encode_packedfile_string_u16()`.
+pub fn write_packedfile_string_u16<W: Write>(writer: &mut W, string_u16_decoded: &str) -> Result<()> {
+    writer.write_u16::<LittleEndian>((string_u16_decoded.encode_utf16().count() * 2) as u16 / 2)?;
+    for character in string_u16_decoded.encode_utf16() { writer.write_u16::<LittleEndian>(character)?; }
+    Ok(())
+}
+
+/// Streaming helper. This function writes an UTF-8 decoded String with a boolean (true if it's not empty) and his lenght
+/// (an u16 integer) before it. Same bytes as `encode_packedfile_optional_string_u8()`.
+pub fn write_packedfile_optional_string_u8<W: Write>(writer: &mut W, optional_string_u8_decoded: &str) -> Result<()> {
+    if optional_string_u8_decoded.is_empty() { writer.write_u8(encode_bool(false))?; }
+    else {
+        writer.write_u8(encode_bool(true))?;
+        write_packedfile_string_u8(writer, optional_string_u8_decoded)?;
+    }
+    Ok(())
+}
+
+/// Streaming helper. This function writes an UTF-16 decoded String with a boolean (true if it's not empty) and his lenght
+/// (an u16 integer) before it. Same bytes as `encode_packedfile_optional_string_u16()`.
+pub fn write_packedfile_optional_string_u16<W: Write>(writer: &mut W, optional_string_u16_decoded: &str) -> Result<()> {
+    if optional_string_u16_decoded.is_empty() { writer.write_u8(encode_bool(false))?; }
+    else {
+        writer.write_u8(encode_bool(true))?;
+        write_packedfile_string_u16(writer, optional_string_u16_decoded)?;
+    }
+    Ok(())
+}
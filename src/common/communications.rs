@@ -43,11 +43,19 @@ pub enum Commands {
     OpenPackFileExtra,
     SavePackFile,
     SavePackFileAs,
+    SaveAutosave,
+    SetPackFilePath,
     LoadAllCAPackFiles,
     SetPackFileType,
     ChangeIndexIncludesTimestamp,
     ChangeDataIsCompressed,
-    SaveSchema,
+    SaveSchemaPatch,
+    GetSchemaPatches,
+    RemoveSchemaPatch,
+    ImportAssemblyKitDefinitions,
+    GetAssemblyKitDefinition,
+    TestTableDefinition,
+    GuessTableFields,
     SetSettings,
     SetShortcuts,
     SetGameSelected,
@@ -65,7 +73,11 @@ pub enum Commands {
     GetPackFileExtraDataForTreeView,
     AddPackedFileFromPackFile,
     MassImportTSV,
+    MassImportTSVPreview,
+    MassImportTSVFromFolder,
     MassExportTSV,
+    MassExportTSVModified,
+    MassExportJSON,
     DecodePackedFileLoc,
     EncodePackedFileLoc,
     DecodePackedFileDB,
@@ -84,19 +96,77 @@ pub enum Commands {
     GeneratePakFile,
     GetPackFilesList,
     SetPackFilesList,
-    DecodeDependencyDB,
+    GetReferenceData,
+    FindReferencedRow,
+    FindUsages,
+    GetVanillaRows,
     CheckScriptWithKailua,
     GlobalSearch,
     UpdateGlobalSearchData,
     OpenWithExternalProgram,
     OpenContainingFolder,
     ImportTSVPackedFile,
+    ImportTSVPackedFileFromOtherVersion,
+    ImportJSONLocPackedFile,
     ExportTSVPackedFile,
+    ExportJSONPackedFile,
+    ImportCSVPackedFile,
+    ExportCSVPackedFile,
     CheckTables,
     MergeTables,
     GenerateSchemaDiff,
     GetNotes,
     SetNotes,
+    StripBomsFromText,
+    CountLocalisableCharacters,
+    SetDryRun,
+    FindReferenceCycles,
+    LocCoverageReport,
+    SetTableAlias,
+    GetTableDefinitionName,
+    ExportColumnEnums,
+    FindNonAsciiKeys,
+    SplitTableByColumn,
+    RestoreDeletedFile,
+    ReloadPackedFileFromDisk,
+    FindUnannotatedTables,
+    ExportTranslationMatrix,
+    TrimTrailingNulls,
+    ValidatePackForGame,
+    CheckCompositeUniqueness,
+    AppendRowCountSnapshot,
+    FindRedundantOverrides,
+    SuggestUniqueKey,
+    ExportVanillaModComparisonTsv,
+    FindUnreferencedAssets,
+    RunEditScript,
+    SizeByTableType,
+    SetSchemaLocked,
+    FindOversizedCells,
+    ExportTreeAsHtml,
+    VerifyLocColumnOrder,
+    GetSessionEditStats,
+    FindCaseCollisions,
+    OptimizationReport,
+    ReplaceInColumn,
+    ValidateKeyColumn,
+    ExportLocMatches,
+    MergeIdenticalAdjacentRows,
+    ExportTableChecksums,
+    ExportCommandLog,
+    ReplayCommandLog,
+    ExportToSQLite,
+    ImportFromSQLite,
+    FindMissingLocKeys,
+    AddLocStubs,
+    FindDuplicatedLocKeys,
+    ResolveDuplicatedLocKeys,
+    GenerateLocEntries,
+    ExportBilingualTSV,
+    ImportBilingualTSV,
+    FindUntranslatedLocEntries,
+    RenameKeyPrefix,
+    DiffTableWithPackFile,
 }
 
 /// This enum is meant to send data back and forward between threads. Variants here are 
@@ -112,6 +182,7 @@ pub enum Data {
     U32(u32),
     I32(i32),
     I64(i64),
+    Usize(usize),
 
     String(String),
     StringVecString((String, Vec<String>)),
@@ -121,15 +192,18 @@ pub enum Data {
     
     Settings(Settings),
     Shortcuts(Shortcuts),
-    Schema(Schema),
 
     PFHFileType(PFHFileType),
     PackFileUIData(PackFileUIData),
 
     PackedFile(PackedFile),
     TableDefinitionPathBufStringI32((TableDefinition, PathBuf, String, i32)),
-    VecVecDecodedData((Vec<Vec<DecodedData>>)),
+    TableDefinitionPathBuf((TableDefinition, PathBuf)),
+    VecVecDecodedDataVecStringVecTSVError((Vec<Vec<DecodedData>>, Vec<String>, Vec<TSVError>)),
     VecVecDecodedDataPathBufVecStringTupleStrI32((Vec<Vec<DecodedData>>, PathBuf, Vec<String>, (String, i32))),
+    TableDefinitionPathBufU8DelimitedQuoting((TableDefinition, PathBuf, u8, DelimitedQuoting)),
+    VecVecDecodedDataPathBufVecStringU8DelimitedQuoting((Vec<Vec<DecodedData>>, PathBuf, Vec<String>, u8, DelimitedQuoting)),
+    VecVecDecodedDataPathBufTableDefinitionTupleStrI32((Vec<Vec<DecodedData>>, PathBuf, TableDefinition, (String, i32))),
 
     Loc(Loc),
     LocVecString((Loc, Vec<String>)),
@@ -142,14 +216,13 @@ pub enum Data {
 
     PathType(PathType),
 
-    OptionStringVecPathBuf((Option<String>, Vec<PathBuf>)),
+    OptionStringVecPathBufBool((Option<String>, Vec<PathBuf>, bool)),
     StringI64VecVecString((String, i64, Vec<Vec<String>>)),
     StringVecPathType((String, Vec<PathType>)),
     VecPathBufVecVecString((Vec<PathBuf>, Vec<Vec<String>>)),
     VecString(Vec<String>),
     VecStringPackedFileType((Vec<String>, PackedFileType)),
-    VecVecStringStringBoolBool((Vec<Vec<String>>, String, bool, bool)),
-    VecVecStringVecVecString((Vec<Vec<String>>, Vec<Vec<String>>)),
+    VecVecStringStringBoolBoolBool((Vec<Vec<String>>, String, bool, bool, bool)),
     VecGlobalMatch(Vec<GlobalMatch>),
     VersionsVersions((Versions, Versions)),
     VecPathTypeString(Vec<(PathType, String)>),
@@ -159,6 +232,49 @@ pub enum Data {
     VecPathBuf(Vec<PathBuf>),
     TableDefinition(TableDefinition),
     BTreeMapI32VecString(BTreeMap<i32, Vec<String>>),
+    VecVecString(Vec<Vec<String>>),
+    VecVecStringVecString((Vec<Vec<String>>, Vec<String>)),
+    VecVecStringUsizeUsizeVecVecString((Vec<(Vec<String>, usize)>, usize, Vec<Vec<String>>)),
+    VecLocCoverage(Vec<LocCoverage>),
+    StringString((String, String)),
+    StringI32((String, i32)),
+    StringStringString((String, String, String)),
+    VecStringStringString((Vec<String>, String, String)),
+    VecStringTableDefinition((Vec<String>, TableDefinition)),
+    VecDefinitionTestResult(Vec<DefinitionTestResult>),
+    VecFieldGuess(Vec<FieldGuess>),
+    BTreeMapVecStringVecOptionString(BTreeMap<Vec<String>, Vec<Option<String>>>),
+    ReferencedRow(ReferencedRow),
+    VecStringPathBuf((Vec<String>, PathBuf)),
+    VecNonAsciiKey(Vec<NonAsciiKey>),
+    VecStringStringBool((Vec<String>, String, bool)),
+    VecStringBool((Vec<String>, bool)),
+    VecVecStringPathBuf((Vec<Vec<String>>, PathBuf)),
+    VecVecStringString(Vec<(Vec<String>, String)>),
+    VecStringVecString((Vec<String>, Vec<String>)),
+    VecVecUsize(Vec<Vec<usize>>),
+    VecStringString((Vec<String>, String)),
+    VecEditOperation(Vec<EditOperation>),
+    BTreeMapStringU64(BTreeMap<String, u64>),
+    VecOversizedCell(Vec<OversizedCell>),
+    SessionEditStats(SessionEditStats),
+    VecVecVecString(Vec<Vec<Vec<String>>>),
+    VecStringStringStringString((Vec<String>, String, String, String)),
+    StringBoolPathBuf((String, bool, PathBuf)),
+    UsizeUsize((usize, usize)),
+    VecVecStringVecVecStringVecString((Vec<Vec<String>>, Vec<Vec<String>>, Vec<String>)),
+    VecMissingLocKey(Vec<MissingLocKey>),
+    UsizeBool((usize, bool)),
+    VecDuplicatedLocKey(Vec<DuplicatedLocKey>),
+    VecStringUsize(Vec<(String, usize)>),
+    VecStringVecStringVecString((Vec<String>, Vec<String>, Vec<String>)),
+    UsizeUsizeBool((usize, usize, bool)),
+    VecVecStringPathBufPathBuf((Vec<Vec<String>>, PathBuf, PathBuf)),
+    UsizeVecString((usize, Vec<String>)),
+    StringStringBoolBool((String, String, bool, bool)),
+    VecKeyPrefixChange(Vec<KeyPrefixChange>),
+    TableDiff(TableDiff),
+    StringPFHFileType((String, PFHFileType)),
 }
 
 /// This functions serves as "message checker" for the communication between threads, for situations where we can hang the thread.
@@ -14,7 +14,7 @@
 
 use chrono::{Utc, DateTime};
 
-use std::fs::{File, read_dir};
+use std::fs::{File, read_dir, read_to_string, remove_file};
 use std::path::{Path, PathBuf};
 
 use crate::SUPPORTED_GAMES;
@@ -180,6 +180,52 @@ pub fn get_last_modified_time_from_file(file: &File) -> i64 {
     last_modified_time.naive_utc().timestamp()
 }
 
+/// Get the path of the `autosaves` folder of RPFM, where the autosave shadow copies of the open PackFile are stored.
+#[allow(dead_code)]
+pub fn get_autosave_folder_path() -> PathBuf {
+    RPFM_PATH.to_path_buf().join(PathBuf::from("autosaves"))
+}
+
+/// Get the paths of the autosave shadow copy and of the file with the path of the PackFile it belongs to, in that order.
+#[allow(dead_code)]
+pub fn get_autosave_shadow_paths() -> (PathBuf, PathBuf) {
+    let folder_path = get_autosave_folder_path();
+    (folder_path.join(PathBuf::from("autosave.pack")), folder_path.join(PathBuf::from("autosave.path")))
+}
+
+/// Get the autosave shadow copy left behind by a previous session, if there is one.
+///
+/// The shadow copy is stored as `autosaves/autosave.pack`, with the path of the PackFile it belongs to
+/// stored in `autosaves/autosave.path`. This returns both paths, in that order.
+#[allow(dead_code)]
+pub fn get_autosave_shadow() -> Option<(PathBuf, PathBuf)> {
+    let (shadow_path, source_path) = get_autosave_shadow_paths();
+    if shadow_path.is_file() {
+        let source_path = PathBuf::from(read_to_string(source_path).ok()?.trim());
+        Some((shadow_path, source_path))
+    } else { None }
+}
+
+/// Remove the autosave shadow copy, if there is one. Used when the PackFile has been saved or RPFM is properly closed,
+/// as the shadow copy is no longer needed. Errors are ignored, as a leftover shadow copy is only offered for recovery if it's newer than his PackFile.
+#[allow(dead_code)]
+pub fn remove_autosave_shadow() {
+    let (shadow_path, source_path) = get_autosave_shadow_paths();
+    let _ = remove_file(shadow_path);
+    let _ = remove_file(source_path);
+}
+
+/// This function decides if an autosave shadow copy should be offered for recovery, depending on
+/// his timestamp and the one of his PackFile on disk. If the PackFile is not on disk (it was never saved,
+/// or it has been deleted) the shadow copy is always worth recovering.
+#[allow(dead_code)]
+pub fn should_recover_autosave(shadow_timestamp: i64, disk_timestamp: Option<i64>) -> bool {
+    match disk_timestamp {
+        Some(disk_timestamp) => shadow_timestamp > disk_timestamp,
+        None => true,
+    }
+}
+
 /// Get the `/data` path of the game selected, straighoutta settings, if it's configured.
 #[allow(dead_code)]
 pub fn get_game_selected_data_path() -> Option<PathBuf> {
@@ -202,6 +248,13 @@ pub fn get_game_selected_assembly_kit_path() -> Option<PathBuf> {
     } else { None }
 }
 
+/// Get the raw DB folder (the one with the `TWaD_*.xml` files) of the Assembly Kit of the game selected. If it's not configured
+/// in the settings, we use the default one within the `/assembly_kit` path of the game selected.
+pub fn get_game_selected_assembly_kit_raw_db_path() -> Option<PathBuf> {
+    if let Some(Some(path)) = SETTINGS.lock().unwrap().paths.get("assembly_kit_raw_db_path") { return Some(path.to_path_buf()) }
+    get_game_selected_assembly_kit_path().map(|path| path.join(PathBuf::from("raw_data/db")))
+}
+
 /// Get the `/data/xxx.pack` path of the PackFile with db tables of the game selected, straighoutta settings, if it's configured.
 #[allow(dead_code)]
 pub fn get_game_selected_db_pack_path() -> Option<Vec<PathBuf>> {
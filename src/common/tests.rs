@@ -10,9 +10,14 @@
 
 // This module contain tests to make sure we don't break any of the encoding/decoding functions in
 // a update, as these are used all over the program and if one of them breaks, half of the program
-// will break with it.
+// will break with it. It also contains the fixtures shared by the tests of the other modules.
 
 use crate::coding_helpers::*;
+use crate::common::*;
+use crate::packedfile::DecodedData;
+use crate::packedfile::db::DB;
+use crate::packfile::packedfile::PackedFile;
+use crate::schema::{Field, FieldType, TableDefinition, TableDefinitions};
 
 //-----------------------------------------------------//
 //          Decoding helpers (Common decoders)
@@ -678,3 +683,69 @@ fn test_encode_packedfile_optional_string_u16() {
     assert_eq!(encode_packedfile_optional_string_u16("Waha"), vec![1, 4, 0, 87, 0, 97, 0, 104, 0, 97, 0]);
     assert_eq!(encode_packedfile_optional_string_u16(""), vec![0]);
 }
+
+/// Test to make sure the streaming encoders write the same bytes as the specific encoders.
+#[test]
+fn test_write_packedfile_strings() {
+    for string in &["Waha", "", "Wähä ✓"] {
+        let mut data = vec![];
+        write_packedfile_string_u8(&mut data, string).unwrap();
+        assert_eq!(data, encode_packedfile_string_u8(string));
+
+        let mut data = vec![];
+        write_packedfile_string_u16(&mut data, string).unwrap();
+        assert_eq!(data, encode_packedfile_string_u16(string));
+
+        let mut data = vec![];
+        write_packedfile_optional_string_u8(&mut data, string).unwrap();
+        assert_eq!(data, encode_packedfile_optional_string_u8(string));
+
+        let mut data = vec![];
+        write_packedfile_optional_string_u16(&mut data, string).unwrap();
+        assert_eq!(data, encode_packedfile_optional_string_u16(string));
+    }
+}
+
+//-----------------------------------------------------//
+//                  Autosave helpers
+//-----------------------------------------------------//
+
+/// Test to make sure the autosave recovery decision (`should_recover_autosave()`) works properly.
+#[test]
+fn test_should_recover_autosave() {
+
+    // Check that a shadow copy newer than the PackFile on disk is offered for recovery.
+    assert_eq!(should_recover_autosave(1_500_000_100, Some(1_500_000_000)), true);
+
+    // Check that a shadow copy older than, or as old as, the PackFile on disk is not offered.
+    assert_eq!(should_recover_autosave(1_500_000_000, Some(1_500_000_100)), false);
+    assert_eq!(should_recover_autosave(1_500_000_000, Some(1_500_000_000)), false);
+
+    // Check that, if the PackFile is not on disk, the shadow copy is always offered.
+    assert_eq!(should_recover_autosave(1_500_000_000, None), true);
+}
+
+//-----------------------------------------------------//
+//                      Fixtures
+//-----------------------------------------------------//
+
+/// This function creates a TableDefinitions with a key column, and a column referencing each one of the provided tables.
+pub fn new_table_definitions_with_references(name: &str, references: &[&str]) -> TableDefinitions {
+    let mut table_definition = TableDefinition::new(1);
+    table_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    for reference in references {
+        table_definition.fields.push(Field::new(format!("{}_ref", reference), FieldType::StringU8, false, Some((reference.to_string(), "key".to_owned())), String::new()));
+    }
+
+    let mut table_definitions = TableDefinitions::new(name);
+    table_definitions.add_table_definition(table_definition);
+    table_definitions
+}
+
+/// This function creates a DB PackedFile in the provided path with the provided rows. The table is the one in the path,
+/// and the version is the one of the provided definition.
+pub fn new_db_packed_file(path: &[String], table_definition: &TableDefinition, entries: Vec<Vec<DecodedData>>) -> PackedFile {
+    let mut db = DB::new(&path[1], table_definition.version, table_definition.clone());
+    db.entries = entries;
+    PackedFile::read_from_vec(path.to_vec(), 0, false, db.save())
+}
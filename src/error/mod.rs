@@ -51,12 +51,18 @@ pub enum ErrorKind {
     // Error for when serializing and deserializing bincode files.
     BincodeSerializerError,
 
+    // Error for when something fails while working with a SQLite database. It contains the message of the error.
+    SQLiteError(String),
+
     // Error for when trying to do something to a file that doesn't exists anymore.
     NonExistantFile,
 
     // Error for when we're trying to merge two invalid files.
     InvalidFilesForMerging,
 
+    // Error for when we're trying to compare two tables of different types.
+    InvalidFilesForDiffing,
+
     // Error for when we're trying add/rename/whatever a file with a reserved path.
     ReservedFiles,
 
@@ -108,6 +114,12 @@ pub enum ErrorKind {
     ImportTSVInvalidVersion,
     TSVErrorGeneric,
 
+    // Error for when a value we're exporting to a delimited file without quotes needs them. It contains the row and the column of the value.
+    ExportDelimitedUnquotedValue(usize, usize),
+
+    // Error for when the delimiter chosen for a delimited file is not a single ASCII character. It contains the delimiter.
+    InvalidDelimiter(String),
+
     //-----------------------------------------------------//
     //                 PackFile Errors
     //-----------------------------------------------------//
@@ -189,15 +201,24 @@ pub enum ErrorKind {
     // Error for when a DB Table is empty and it doesn't have an schema, so it's undecodeable.
     DBTableEmptyWithNoTableDefinition,
 
+    // Error for when the decoder cannot guess the fields needed to decode the rest of a DB Table.
+    DBTableFieldsCannotBeGuessed,
+
     // Error for when we find missing references when checking a DB Table.
     DBMissingReferences(Vec<String>),
 
+    // Error for when we cannot find the row referenced by a cell. It contains the value of the cell and the referenced table.
+    DBReferencedRowNotFound(String, String),
+
     // Error for when we don't have an schema to use.
     SchemaNotFound,
 
     // Error for when we don't have a table definition for an specific version of a table.
     SchemaTableDefinitionNotFound,
 
+    // Error for when we try to change the schema while it's locked.
+    SchemaLocked,
+
     //--------------------------------//
     // RigidModel Errors
     //--------------------------------//
@@ -264,6 +285,9 @@ pub enum ErrorKind {
     // This error is to be used when a decoding/encoding operation using the decoding/encoding helpers fails.
     HelperDecodingEncodingError(String),
 
+    // Error for when a field of a table fails to decode. Contains the row, the name and type of the field, the offset of the byte where it starts and the cause.
+    TableFieldDecode(usize, String, String, usize, String),
+
     //-----------------------------------------------------//
     //                  MyMod Errors
     //-----------------------------------------------------//
@@ -277,6 +301,12 @@ pub enum ErrorKind {
     // Error for when the path of a Game is not configured.
     GamePathNotConfigured,
 
+    // Error for when the raw DB folder of the Assembly Kit of the Game Selected cannot be found.
+    AssemblyKitNotFound,
+
+    // Error for when the Assembly Kit doesn't have a definition for a table.
+    AssemblyKitDefinitionNotFound(String),
+
     // Error for when the MyMod path is not configured and it needs it to be.
     MyModPathNotConfigured,
 
@@ -312,6 +342,9 @@ pub enum ErrorKind {
     // Errors for when we fail to mass-import/export TSV files.
     MassImport(String),
 
+    // Error for when the PackedFiles we're trying to Mass-Import already exist in the PackFile. It contains the list of them.
+    MassImportConflicts(String),
+
     // Error for when the introduced input (usually, a name) is empty and it cannot be empty.
     EmptyInput,
 
@@ -324,6 +357,54 @@ pub enum ErrorKind {
     // Error for when the folder we are trying to create already exist in the current path.
     FolderAlreadyInPackFile,
 
+    // Error for when a column reference (like "table.column") provided by the user is not valid.
+    InvalidColumnReference(String),
+
+    // Error for when we try to do something that needs a DB Table or a Loc PackedFile with another kind of PackedFile.
+    PackedFileIsNotATable,
+
+    // Error for when the column provided by the user doesn't exist in the table.
+    ColumnNotFound(String),
+
+    // Error for when the highlight rule provided by the user is not valid.
+    InvalidHighlightRule,
+
+    // Error for when the maths operation provided by the user cannot be parsed.
+    InvalidMathsOperation,
+
+    // Error for when the regex provided by the user is not valid. It contains the regex.
+    InvalidRegex(String),
+
+    // Error for when the row provided by the user doesn't exist in the table.
+    RowNotFound(usize),
+
+    // Error for when the user provides something that's not a valid positive number where we expect one.
+    InvalidNumber(String),
+
+    // Error for when an operation of an edit script fails. It contains the number of the operation and the reason.
+    EditScriptOperationFailed(usize, String),
+
+    // Error for when a value of a JSON file we're importing into a Loc PackedFile is not a string. It contains the key of the value.
+    ImportJSONInvalidValue(String),
+
+    // Error for when a command of a command log fails while replaying it. It contains the number of the command and the reason.
+    CommandLogReplayFailed(usize, String),
+
+    // Error for when the columns of a SQLite table we're importing don't match his definition. It contains the name of the table and the offending columns.
+    ImportSQLiteInvalidColumns(String, Vec<String>),
+
+    // Error for when we don't know where a SQLite table we're importing goes, or it doesn't have a definition in the schema. It contains the name of the table.
+    ImportSQLiteUnknownTable(String),
+
+    // Error for when we try to generate the loc keys of a table without templates, and his definition doesn't have localisable fields either.
+    LocKeyTemplatesNotFound,
+
+    // Error for when we try to generate the loc keys of a table without key columns.
+    LocKeyTableWithoutKeyColumns,
+
+    // Error for when we try to compare our Loc PackedFiles with the english ones, but we couldn't load any english Loc PackedFile.
+    EnglishLocPackNotFound,
+
     //-----------------------------------------------------//
     //                  Common Errors
     //-----------------------------------------------------//
@@ -384,8 +465,10 @@ impl Display for ErrorKind {
             ErrorKind::TOMLSerializerError => write!(f, "<p>This should never happen.</p>"),
             ErrorKind::XMLDeserializerError => write!(f, "<p>This should never happen.</p>"),
             ErrorKind::BincodeSerializerError => write!(f, "<p>This should never happen.</p>"),
+            ErrorKind::SQLiteError(error) => write!(f, "<p>Error while working with the SQLite database:</p><p>{}</p>", error),
             ErrorKind::NonExistantFile => write!(f, "<p>The file you tried to... use doesn't exist. This is a bug, because if everything worked propetly, you'll never see this message.</p>"),
-            ErrorKind::InvalidFilesForMerging => write!(f, "<p>The files you selected are not all LOCs, neither DB Tables of the same type and version.</p>"),
+            ErrorKind::InvalidFilesForMerging => write!(f, "<p>The files you selected are not all LOCs, neither DB Tables of the same type.</p>"),
+            ErrorKind::InvalidFilesForDiffing => write!(f, "<p>The tables you tried to compare are not of the same type.</p>"),
             ErrorKind::ReservedFiles => write!(f, "<p>One or more of the files you're trying to add/create/rename to have a reserved name. Those names are reserved for internal use in RPFM. Please, try again with another name.</p>"),
 
             //-----------------------------------------------------//
@@ -411,9 +494,11 @@ impl Display for ErrorKind {
             //-----------------------------------------------------//
             ErrorKind::ImportTSVIncorrectRow(row, column) => write!(f, "<p>This TSV file has an error in the <b>row <i>{}</i></b>, <b>field <i>{}</i></b> (both starting at 1). Please, check it and make sure the value in that field is a valid value for that column.</p>", row + 1, column + 1),
             ErrorKind::ImportTSVWrongTypeTable => write!(f, "<p>This TSV file either belongs to another table, to a localisation PackedFile, it's broken or it's incompatible with RPFM.</p>"),
-            ErrorKind::ImportTSVWrongVersion => write!(f, "<p>This TSV file belongs to another version of this table. If you want to use it, consider creating a new empty table, fill it with enough empty rows, open this file in a TSV editor, like Excel or LibreOffice, and copy column by column.</p><p>A more automatic solution is on the way, but not yet there.</p>"),
+            ErrorKind::ImportTSVWrongVersion => write!(f, "<p>This TSV file belongs to another version of this table. If you want to use it, import it from the table itself, which can convert it to the version of the table.</p>"),
             ErrorKind::ImportTSVInvalidVersion => write!(f, "<p>This TSV file has an invalid version value at line 1.</p>"),
             ErrorKind::TSVErrorGeneric => write!(f, "<p>Error while trying to import/export a TSV file.</p>"),
+            ErrorKind::ExportDelimitedUnquotedValue(row, column) => write!(f, "<p>The value in the <b>row <i>{}</i></b>, <b>field <i>{}</i></b> (both starting at 1) has the delimiter, quotes or newlines, so it cannot be exported without quoting. Please, choose another quoting option.</p>", row + 1, column + 1),
+            ErrorKind::InvalidDelimiter(delimiter) => write!(f, "<p>The delimiter <i>\"{}\"</i> is not valid. It must be a single ASCII character.</p>", delimiter),
 
             //-----------------------------------------------------//
             //                 PackFile Errors
@@ -467,9 +552,12 @@ impl Display for ErrorKind {
             ErrorKind::DBTableReplaceInvalidData => write!(f, "<p>Error while trying to replace the data of a Cell.</p><p>This means you tried to replace a number cell with text, or used a too big, too low or invalid number. Don't do it. It wont end well.</p>"),
             ErrorKind::DBTableDecode(cause) => write!(f, "<p>Error while trying to decode the DB Table:</p><p>{}</p>", cause),
             ErrorKind::DBTableEmptyWithNoTableDefinition => write!(f, "<p>This DB Table is empty and there is not a Table Definition for it. That means is undecodeable.</p>"),
+            ErrorKind::DBTableFieldsCannotBeGuessed => write!(f, "<p>Couldn't find a sequence of fields that decodes the rest of the table. Make sure the fields already in the list are right, or decode a few more by hand and try again.</p>"),
             ErrorKind::DBMissingReferences(references) => write!(f, "<p>The currently open PackFile has reference errors in the following tables:<ul>{}</ul></p>", references.iter().map(|x| format!("<li>{}<li>", x)).collect::<String>()),
+            ErrorKind::DBReferencedRowNotFound(value, table) => write!(f, "<p>There is no row with the key <i>'{}'</i> in the table <i>'{}'</i>, neither in the open PackFile nor in the dependency PackFiles.</p>", value, table),
             ErrorKind::SchemaNotFound => write!(f, "<p>There is no Schema for the Game Selected.</p>"),
             ErrorKind::SchemaTableDefinitionNotFound => write!(f, "<p>There is no Table Definition for this specific version of the table in the Schema.</p>"),
+            ErrorKind::SchemaLocked => write!(f, "<p>The Schema is locked, so it cannot be changed. Unlock it from <i>Tools/Lock Schema</i> and try again.</p>"),
 
             //--------------------------------//
             // RigidModel Errors
@@ -512,6 +600,7 @@ impl Display for ErrorKind {
             //-----------------------------------------------------//
             ErrorKind::StringFromUTF8 => write!(f, "<p>Error while converting data to an UTF-8 String.</p>"),
             ErrorKind::HelperDecodingEncodingError(cause) => write!(f, "{}", cause),
+            ErrorKind::TableFieldDecode(row, field_name, field_type, offset, cause) => write!(f, "<p>Error trying to decode the <i><b>Row {}</b></i>, field <i><b>{}</b></i> as a <b><i>{}</b></i> value at byte <i><b>{}</b></i>: {}.</p>", row + 1, field_name, field_type, offset, cause),
 
             //-----------------------------------------------------//
             //                  MyMod Errors
@@ -519,6 +608,8 @@ impl Display for ErrorKind {
             ErrorKind::MyModNotInstalled => write!(f, "<p>The currently selected MyMod is not installed.</p>"),
             ErrorKind::MyModInstallFolderDoesntExists => write!(f, "<p>Destination folder (..xxx/data) doesn't exist. You sure you configured the right folder for the game?</p>"),
             ErrorKind::GamePathNotConfigured => write!(f, "<p>Game Path not configured. Go to <i>'PackFile/Preferences'</i> and configure it.</p>"),
+            ErrorKind::AssemblyKitNotFound => write!(f, "<p>The raw DB folder of the Assembly Kit (the one with the <i>TWaD_*.xml</i> files) of the Game Selected has not been found. Go to <i>'PackFile/Preferences'</i> and configure it, or the path of the game.</p>"),
+            ErrorKind::AssemblyKitDefinitionNotFound(table_name) => write!(f, "<p>The Assembly Kit doesn't have a definition for the table <i>{}</i>.</p>", table_name),
             ErrorKind::MyModPathNotConfigured => write!(f, "<p>MyMod path is not configured. Configure it in the settings and try again.</p>"),
             ErrorKind::MyModDeleteWithoutMyModSelected => write!(f, "<p>You can't delete the selected MyMod if there is no MyMod selected.</p>"),
             ErrorKind::MyModPackFileDeletedFolderNotFound => write!(f, "<p>The Mod's PackFile has been deleted, but his assets folder is nowhere to be found.</p>"),
@@ -536,10 +627,27 @@ impl Display for ErrorKind {
             //-----------------------------------------------------//
             ErrorKind::ExtractError(errors) => write!(f, "<p>There has been a problem extracting the following files:</p><ul>{:#?}</ul>", errors),
             ErrorKind::MassImport(errors) => write!(f, "<p>The following files returned error when trying to import them:</p><ul>{}</ul><p>No files have been imported.</p>", errors),
+            ErrorKind::MassImportConflicts(paths) => write!(f, "<p>The following PackedFiles already exist in the PackFile:</p><ul>{}</ul><p>No files have been imported.</p>", paths),
             ErrorKind::EmptyInput => write!(f, "<p>Only my hearth can be empty.</p>"),
             ErrorKind::NoFilesToImport => write!(f, "<p>It's mathematically impossible to successfully import zero TSV files.</p>"),
             ErrorKind::FileAlreadyInPackFile => write!(f, "<p>The provided file/s already exists in the current path.</p>"),
             ErrorKind::FolderAlreadyInPackFile => write!(f, "<p>That folder already exists in the current path.</p>"),
+            ErrorKind::InvalidColumnReference(column_ref) => write!(f, "<p>The column reference <i>\"{}\"</i> is not valid. Column references must be like <i>table_name.column_name</i>.</p>", column_ref),
+            ErrorKind::PackedFileIsNotATable => write!(f, "<p>This PackedFile is neither a DB Table nor a Loc PackedFile.</p>"),
+            ErrorKind::ColumnNotFound(column) => write!(f, "<p>The column <i>\"{}\"</i> doesn't exist in this table.</p>", column),
+            ErrorKind::InvalidHighlightRule => write!(f, "<p>This highlight rule is not valid. Rules must be like <i>column operator value</i> (<i>tier > 3</i>), with one of the supported operators and colors.</p>"),
+            ErrorKind::InvalidMathsOperation => write!(f, "<p>This maths operation is not valid. Operations must be like <i>x * 1.1</i> or <i>x + 50</i>, with <i>x</i> being the current value of each cell.</p>"),
+            ErrorKind::InvalidRegex(regex) => write!(f, "<p>The regex <i>\"{}\"</i> is not valid.</p>", regex),
+            ErrorKind::RowNotFound(row) => write!(f, "<p>The row <i>{}</i> doesn't exist in this table.</p>", row),
+            ErrorKind::InvalidNumber(number) => write!(f, "<p><i>\"{}\"</i> is not a valid positive number.</p>", number),
+            ErrorKind::EditScriptOperationFailed(operation, reason) => write!(f, "<p>The operation <b>{}</b> of the edit script failed, so none of the changes of the script have been applied. The reason was:</p>{}", operation, reason),
+            ErrorKind::ImportJSONInvalidValue(key) => write!(f, "<p>The value of the key <i>\"{}\"</i> is not a string. Only JSON files with a flat object of keys and texts can be imported into Loc PackedFiles.</p>", key),
+            ErrorKind::CommandLogReplayFailed(command, reason) => write!(f, "<p>The command <b>{}</b> of the command log failed, so none of the commands of the log have been applied. The reason was:</p>{}", command, reason),
+            ErrorKind::ImportSQLiteInvalidColumns(table, columns) => write!(f, "<p>The table <i>\"{}\"</i> has not been imported, because the following columns are missing, are not in the definition of the table or have invalid values:</p><ul>{}</ul>", table, columns.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>()),
+            ErrorKind::ImportSQLiteUnknownTable(table) => write!(f, "<p>The table <i>\"{}\"</i> has not been imported, because it's not a table exported by RPFM or there is no definition for it in the schema.</p>", table),
+            ErrorKind::LocKeyTemplatesNotFound => write!(f, "<p>There are no templates to generate the loc keys of this table, and his definition doesn't have localisable fields to use instead. Provide the templates (<i>{{table}}_onscreen_name_{{key}}</i>) when generating them, or set the default ones in the settings.</p>"),
+            ErrorKind::LocKeyTableWithoutKeyColumns => write!(f, "<p>The loc keys of this table cannot be generated, because his definition doesn't have key columns.</p>"),
+            ErrorKind::EnglishLocPackNotFound => write!(f, "<p>There are no english Loc PackedFiles to compare with. Make sure the path of the game selected is configured and that his <i>'local_en.pack'</i> exists.</p>"),
 
             //-----------------------------------------------------//
             //                  Common Errors
@@ -636,6 +744,13 @@ impl From<serde_xml_rs::Error> for Error {
     }
 }
 
+/// Implementation to create a custom error from a rusqlite::Error.
+impl From<rusqlite::Error> for Error {
+    fn from(error: rusqlite::Error) -> Error {
+        Error::from(ErrorKind::SQLiteError(error.to_string()))
+    }
+}
+
 impl From<Box<bincode::ErrorKind>> for Error {
     fn from(_: Box<bincode::ErrorKind>) -> Error {
         Error::from(ErrorKind::BincodeSerializerError)
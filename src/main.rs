@@ -57,6 +57,7 @@ use qt_gui::color::Color;
 use qt_gui::cursor::Cursor;
 use qt_gui::desktop_services::DesktopServices;
 use qt_gui::font::Font;
+use qt_gui::gui_application::GuiApplication;
 use qt_gui::icon::Icon;
 use qt_gui::key_sequence::KeySequence;
 use qt_gui::list::ListStandardItemMutPtr;
@@ -74,11 +75,12 @@ use qt_core::qt::{CaseSensitivity, ContextMenuPolicy, Orientation, ShortcutConte
 use qt_core::slots::{SlotBool, SlotNoArgs, SlotStringRef, SlotCInt, SlotModelIndexRef, SlotItemSelectionRefItemSelectionRef};
 use qt_core::sort_filter_proxy_model::SortFilterProxyModel;
 use qt_core::reg_exp::RegExp;
+use qt_core::timer::Timer;
 use qt_core::variant::Variant;
 use cpp_utils::StaticCast;
 
 use std::env::args;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::DerefMut;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -88,7 +90,8 @@ use std::sync::mpsc::{channel, Sender, Receiver};
 use std::ffi::OsStr;
 use std::panic;
 use std::path::{Path, PathBuf};
-use std::fs::{DirBuilder, copy, remove_file, remove_dir_all};
+use std::fs::{DirBuilder, File, copy, remove_file, remove_dir_all};
+use std::io::BufReader;
 
 use chrono::NaiveDateTime;
 use indexmap::map::IndexMap;
@@ -96,7 +99,7 @@ use lazy_static::lazy_static;
 
 use crate::common::*;
 use crate::common::communications::*;
-use crate::error::{ErrorKind, logger::Report, Result};
+use crate::error::{Error, ErrorKind, logger::Report, Result};
 use crate::main_extra::*;
 use crate::packfile::{CompressionState, PathType};
 use crate::packfile::packedfile::PackedFile;
@@ -112,6 +115,7 @@ use crate::ui::packedfile_table::db_decoder::*;
 use crate::ui::packedfile_table::dependency_manager::*;
 use crate::ui::packedfile_table::packedfile_db::*;
 use crate::ui::packedfile_table::packedfile_loc::*;
+use crate::ui::packedfile_table::packedfile_table_extras::select_search_matches;
 use crate::ui::packedfile_text::packedfile_text::*;
 use crate::ui::packedfile_text::packfile_notes::*;
 use crate::ui::packedfile_rigidmodel::*;
@@ -152,6 +156,10 @@ mod schema;
 mod updater;
 mod ui;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 // Statics, so we don't need to pass them everywhere to use them.
 lazy_static! {
 
@@ -564,7 +572,7 @@ lazy_static! {
     static ref SHORTCUTS: Arc<Mutex<Shortcuts>> = Arc::new(Mutex::new(Shortcuts::load().unwrap_or_else(|_|Shortcuts::new())));
 
     /// The current GameSelected. Same as the one above, only edited from the background thread.
-    static ref GAME_SELECTED: Arc<Mutex<String>> = Arc::new(Mutex::new(SETTINGS.lock().unwrap().settings_string["default_game"].to_owned()));
+    static ref GAME_SELECTED: Arc<Mutex<String>> = Arc::new(Mutex::new(SETTINGS.lock().unwrap().get_default_game()));
 
     /// PackedFiles from the dependencies of the currently open PackFile.
     static ref DEPENDENCY_DATABASE: Mutex<Vec<PackedFile>> = Mutex::new(vec![]);
@@ -582,9 +590,20 @@ lazy_static! {
     /// NOTE: This affects both DB Tables and Loc PackedFiles.
     static ref TABLE_STATES_UI: Mutex<BTreeMap<Vec<String>, TableStateUI>> = Mutex::new(TableStateUI::load().unwrap_or_else(|_| TableStateUI::new()));
 
+    /// Names of the tables that have their key columns frozen. Unlike the rest of the table state, this is never cleared.
+    static ref FROZEN_KEY_COLUMNS: Mutex<BTreeSet<String>> = Mutex::new(load_frozen_key_columns().unwrap_or_else(|_| BTreeSet::new()));
+
     /// Variable to lock/unlock certain actions of the Folder TreeView.
     static ref IS_FOLDER_TREE_VIEW_LOCKED: Arc<Mutex<bool>> = Arc::new(Mutex::new(false));
 
+    /// Variable to know if we are in "Dry Run" mode. In this mode, the commands that edit the open PackFile only report
+    /// what they would change, without changing anything. Only edited from the background thread.
+    static ref DRY_RUN: Arc<Mutex<bool>> = Arc::new(Mutex::new(false));
+
+    /// Variable to know if the schema is locked. While locked, the commands that change the loaded schema are blocked.
+    /// Only edited from the background thread.
+    static ref SCHEMA_LOCKED: Arc<Mutex<bool>> = Arc::new(Mutex::new(false));
+
     /// Docs & Patreon URLs.
     static ref DOCS_BASE_URL: &'static str = "https://frodo45127.github.io/rpfm/";
     static ref PATREON_URL: &'static str = "https://www.patreon.com/RPFM";
@@ -616,7 +635,7 @@ pub enum Mode {
 /// This enum represents a match when using the "Global Search" feature.
 ///  - `DB`: (path, Vec(column_name, column_number, row_number, text).
 ///  - `Loc`: (path, Vec(column_name, row_number, text)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum GlobalMatch {
     DB((Vec<String>, Vec<(String, i32, i64, String)>)),
     Loc((Vec<String>, Vec<(String, i32, i64, String)>)),
@@ -754,7 +773,9 @@ pub struct AppUI {
     pub context_menu_create_loc: *mut Action,
     pub context_menu_create_text: *mut Action,
     pub context_menu_mass_import_tsv: *mut Action,
+    pub context_menu_mass_import_tsv_folder: *mut Action,
     pub context_menu_mass_export_tsv: *mut Action,
+    pub context_menu_mass_export_json: *mut Action,
     pub context_menu_rename: *mut Action,
     pub context_menu_delete: *mut Action,
     pub context_menu_extract: *mut Action,
@@ -766,6 +787,7 @@ pub struct AppUI {
     pub context_menu_open_notes: *mut Action,
     pub context_menu_check_tables: *mut Action,
     pub context_menu_merge_tables: *mut Action,
+    pub context_menu_generate_loc_entries: *mut Action,
     pub context_menu_global_search: *mut Action,
 
     //-------------------------------------------------------------------------------//
@@ -773,6 +795,13 @@ pub struct AppUI {
     //-------------------------------------------------------------------------------//
     pub tree_view_expand_all: *mut Action,
     pub tree_view_collapse_all: *mut Action,
+    pub tree_view_open_packedfile: *mut Action,
+
+    //-------------------------------------------------------------------------------//
+    // "Special" actions for the Global Search.
+    //-------------------------------------------------------------------------------//
+    pub global_search_show_usages: *mut Action,
+    pub global_search_show_untranslated: *mut Action,
 }
 
 /// Main function.
@@ -973,6 +1002,7 @@ fn main() {
         let menu_bar_mymod = unsafe { menu_bar.as_mut().unwrap().add_menu(&QString::from_std_str("&MyMod")) };
         let menu_bar_game_seleted = unsafe { menu_bar.as_mut().unwrap().add_menu(&QString::from_std_str("&Game Selected")) };
         let menu_bar_special_stuff = unsafe { menu_bar.as_mut().unwrap().add_menu(&QString::from_std_str("&Special Stuff")) };
+        let menu_bar_tools = unsafe { menu_bar.as_mut().unwrap().add_menu(&QString::from_std_str("&Tools")) };
         let menu_bar_about = unsafe { menu_bar.as_mut().unwrap().add_menu(&QString::from_std_str("&About")) };
         
         // Submenus.
@@ -1130,7 +1160,9 @@ fn main() {
             context_menu_create_text: menu_create.as_mut().unwrap().add_action(&QString::from_std_str("Create &Text")),
 
             context_menu_mass_import_tsv: menu_create.as_mut().unwrap().add_action(&QString::from_std_str("Mass-Import TSV")),
+            context_menu_mass_import_tsv_folder: menu_create.as_mut().unwrap().add_action(&QString::from_std_str("Mass-Import TSV from Folder")),
             context_menu_mass_export_tsv: menu_create.as_mut().unwrap().add_action(&QString::from_std_str("Mass-Export TSV")),
+            context_menu_mass_export_json: menu_create.as_mut().unwrap().add_action(&QString::from_std_str("Mass-Export JSON")),
 
             context_menu_rename: folder_tree_view_context_menu.add_action(&QString::from_std_str("&Rename")),
             context_menu_delete: folder_tree_view_context_menu.add_action(&QString::from_std_str("&Delete")),
@@ -1145,6 +1177,7 @@ fn main() {
             
             context_menu_check_tables: folder_tree_view_context_menu.add_action(&QString::from_std_str("&Check Tables")),
             context_menu_merge_tables: folder_tree_view_context_menu.add_action(&QString::from_std_str("&Merge Tables")),
+            context_menu_generate_loc_entries: folder_tree_view_context_menu.add_action(&QString::from_std_str("&Generate Loc Entries...")),
             context_menu_global_search: folder_tree_view_context_menu.add_action(&QString::from_std_str("&Global Search")),
 
             //-------------------------------------------------------------------------------//
@@ -1152,6 +1185,13 @@ fn main() {
             //-------------------------------------------------------------------------------//
             tree_view_expand_all: Action::new(&QString::from_std_str("&Expand All")).into_raw(),
             tree_view_collapse_all: Action::new(&QString::from_std_str("&Collapse All")).into_raw(),
+            tree_view_open_packedfile: Action::new(()).into_raw(),
+
+            //-------------------------------------------------------------------------------//
+            // "Special" actions for the Global Search.
+            //-------------------------------------------------------------------------------//
+            global_search_show_usages: Action::new(()).into_raw(),
+            global_search_show_untranslated: Action::new(()).into_raw(),
         }};
 
         // The "Change PackFile Type" submenu should be an ActionGroup.
@@ -1282,6 +1322,9 @@ fn main() {
         // This cannot go into lazy_static because StandardItem is not send.
         let table_state_data = Rc::new(RefCell::new(TableStateData::new()));
 
+        // The TableViews of the open tables, so we can reach them after opening them.
+        let open_table_views = Rc::new(RefCell::new(BTreeMap::new()));
+
         // Put the stuff we need to move to the slots in Rc<Refcell<>>, so we can clone it without issues.
         let receiver_qt = Rc::new(RefCell::new(receiver_qt));
         let packedfiles_open_in_packedfile_view = Rc::new(RefCell::new(BTreeMap::new()));
@@ -1298,8 +1341,11 @@ fn main() {
         let global_search_explicit_paths = Rc::new(RefCell::new(vec![]));
 
         // Signal to save the tables states to disk when we're about to close RPFM. We ignore the error here, as at this point we cannot report it to the user.
+        // We also remove the autosave, as it's only needed if RPFM doesn't close properly.
         let slot_save_states = SlotNoArgs::new(move || {
             let _y = TableStateUI::save();
+            let _y = save_frozen_key_columns();
+            remove_autosave_shadow();
         });
         app.deref_mut().signals().about_to_quit().connect(&slot_save_states);
 
@@ -1323,6 +1369,16 @@ fn main() {
         let mymod_stuff = Rc::new(RefCell::new(result.0));
         let mymod_stuff_slots = Rc::new(RefCell::new(result.1));
 
+        // Build the "Tools" Menu.
+        let (_tools_stuff, _tools_slots) = build_tools_menu(
+            &sender_qt,
+            &sender_qt_data,
+            &receiver_qt,
+            app_ui,
+            menu_bar_tools,
+            &packedfiles_open_in_packedfile_view,
+        );
+
         // Build the "Open From Content" and "Open From Data" submenus.
         let open_from_slots = Rc::new(RefCell::new(vec![]));
 
@@ -1336,11 +1392,14 @@ fn main() {
             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(false);
+            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(false);
+            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(false);
             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -1360,6 +1419,7 @@ fn main() {
         unsafe { app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().tree_view["mass_import_tsv"]))); }
         unsafe { app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().tree_view["mass_export_tsv"]))); }
         unsafe { app_ui.context_menu_merge_tables.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().tree_view["merge_tables"]))); }
+        unsafe { app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().tree_view["generate_loc_entries"]))); }
         unsafe { app_ui.context_menu_delete.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().tree_view["delete"]))); }
         unsafe { app_ui.context_menu_extract.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().tree_view["extract"]))); }
         unsafe { app_ui.context_menu_rename.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().tree_view["rename"]))); }
@@ -1385,6 +1445,7 @@ fn main() {
         unsafe { app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { app_ui.context_menu_merge_tables.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { app_ui.context_menu_delete.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { app_ui.context_menu_extract.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { app_ui.context_menu_rename.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
@@ -1410,6 +1471,7 @@ fn main() {
         unsafe { app_ui.folder_tree_view.as_mut().unwrap().add_action(app_ui.context_menu_mass_import_tsv); }
         unsafe { app_ui.folder_tree_view.as_mut().unwrap().add_action(app_ui.context_menu_mass_export_tsv); }
         unsafe { app_ui.folder_tree_view.as_mut().unwrap().add_action(app_ui.context_menu_merge_tables); }
+        unsafe { app_ui.folder_tree_view.as_mut().unwrap().add_action(app_ui.context_menu_generate_loc_entries); }
         unsafe { app_ui.folder_tree_view.as_mut().unwrap().add_action(app_ui.context_menu_delete); }
         unsafe { app_ui.folder_tree_view.as_mut().unwrap().add_action(app_ui.context_menu_extract); }
         unsafe { app_ui.folder_tree_view.as_mut().unwrap().add_action(app_ui.context_menu_rename); }
@@ -1510,8 +1572,11 @@ fn main() {
         unsafe { app_ui.context_menu_create_db.as_mut().unwrap().set_status_tip(&QString::from_std_str("Open the dialog to create a DB Table (used by the game for... most of the things).")); }
         unsafe { app_ui.context_menu_create_text.as_mut().unwrap().set_status_tip(&QString::from_std_str("Open the dialog to create a Plain Text File. It accepts different extensions, like '.xml', '.lua', '.txt',....")); }
         unsafe { app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_status_tip(&QString::from_std_str("Import a bunch of TSV files at the same time. It automatically checks if they are DB Tables, Locs or invalid TSVs, and imports them all at once. Existing files will be overwritten!")); }
+        unsafe { app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_status_tip(&QString::from_std_str("Import all the TSV files of a folder, using where they are in it to know where they go: 'db/units_tables/units.tsv' goes to 'db/units_tables/units', and the ones in 'text' become Locs. Nothing is imported if any of them already exists.")); }
         unsafe { app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export every DB Table and Loc PackedFile from this PackFile as TSV files at the same time. Existing files will be overwritten!")); }
+        unsafe { app_ui.context_menu_mass_export_json.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export every DB Table and Loc PackedFile from this PackFile as JSON files at the same time. Existing files will be overwritten!")); }
         unsafe { app_ui.context_menu_merge_tables.as_mut().unwrap().set_status_tip(&QString::from_std_str("Merge multple DB Tables/Loc PackedFiles into one.")); }
+        unsafe { app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_status_tip(&QString::from_std_str("Generate the loc keys of the rows of the selected DB Table from a few templates, and add blank entries for the ones that don't exist yet to a Loc PackedFile.")); }
         unsafe { app_ui.context_menu_delete.as_mut().unwrap().set_status_tip(&QString::from_std_str("Delete the selected File/Folder.")); }
         unsafe { app_ui.context_menu_extract.as_mut().unwrap().set_status_tip(&QString::from_std_str("Extract the selected File/Folder from the PackFile.")); }
         unsafe { app_ui.context_menu_rename.as_mut().unwrap().set_status_tip(&QString::from_std_str("Rename the selected File/Folder. Remember, whitespaces are NOT ALLOWED and duplicated names in the same folder will NOT BE RENAMED.")); }
@@ -1928,43 +1993,77 @@ fn main() {
                 // Send the type to the Background Thread.
                 sender_qt.send(Commands::SetPackFileType).unwrap();
                 sender_qt_data.send(Data::PFHFileType(packfile_type)).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
 
-                // Modify the PackFile.
-                update_treeview(
-                    &sender_qt,
-                    &sender_qt_data,
-                    &receiver_qt,
-                    &app_ui,
-                    app_ui.folder_tree_view,
-                    Some(app_ui.folder_tree_filter),
-                    app_ui.folder_tree_model,
-                    TreeViewOperation::Modify(vec![TreePathType::PackFile]),
-                );
+                    // Modify the PackFile.
+                    Data::Success => update_treeview(
+                        &sender_qt,
+                        &sender_qt_data,
+                        &receiver_qt,
+                        &app_ui,
+                        app_ui.folder_tree_view,
+                        Some(app_ui.folder_tree_filter),
+                        app_ui.folder_tree_model,
+                        TreeViewOperation::Modify(vec![TreePathType::PackFile]),
+                    ),
+
+                    // In "Dry Run" mode, go back to the type the PackFile still has, and report what would have changed.
+                    Data::StringPFHFileType((report, pfh_file_type)) => {
+                        match pfh_file_type {
+                            PFHFileType::Boot => unsafe { app_ui.change_packfile_type_boot.as_mut().unwrap().set_checked(true); }
+                            PFHFileType::Release => unsafe { app_ui.change_packfile_type_release.as_mut().unwrap().set_checked(true); }
+                            PFHFileType::Patch => unsafe { app_ui.change_packfile_type_patch.as_mut().unwrap().set_checked(true); }
+                            PFHFileType::Mod => unsafe { app_ui.change_packfile_type_mod.as_mut().unwrap().set_checked(true); }
+                            PFHFileType::Movie => unsafe { app_ui.change_packfile_type_movie.as_mut().unwrap().set_checked(true); }
+                            PFHFileType::Other(_) => unsafe { app_ui.change_packfile_type_other.as_mut().unwrap().set_checked(true); }
+                        }
+                        show_dialog(app_ui.window, true, report);
+                    }
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
             }
         ));
 
         // What happens when we change the value of "Include Last Modified Date" action.
         let slot_index_includes_timestamp = SlotBool::new(clone!(
             sender_qt,
-            sender_qt_data => move |_| {
+            sender_qt_data,
+            receiver_qt => move |_| {
 
                 // Get the current value of the action.
                 let state = unsafe { app_ui.change_packfile_type_index_includes_timestamp.as_ref().unwrap().is_checked() };
 
-                // Send the new state to the background thread.
+                // Send the new state to the background thread. In "Dry Run" mode, undo the change in the action and report what would have changed.
                 sender_qt.send(Commands::ChangeIndexIncludesTimestamp).unwrap();
                 sender_qt_data.send(Data::Bool(state)).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::Success => {},
+                    Data::String(report) => {
+                        unsafe { app_ui.change_packfile_type_index_includes_timestamp.as_mut().unwrap().set_checked(!state); }
+                        show_dialog(app_ui.window, true, report);
+                    }
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
             }
         ));
 
         // What happens when we enable/disable compression on the current PackFile.
         let slot_data_is_compressed = SlotBool::new(clone!(
             sender_qt,
-            sender_qt_data => move |_| {
+            sender_qt_data,
+            receiver_qt => move |_| {
 
                 let state = unsafe { app_ui.change_packfile_type_data_is_compressed.as_ref().unwrap().is_checked() };
                 sender_qt.send(Commands::ChangeDataIsCompressed).unwrap();
                 sender_qt_data.send(Data::Bool(state)).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::Success => {},
+                    Data::String(report) => {
+                        unsafe { app_ui.change_packfile_type_data_is_compressed.as_mut().unwrap().set_checked(!state); }
+                        show_dialog(app_ui.window, true, report);
+                    }
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
             }
         ));
 
@@ -2436,7 +2535,9 @@ fn main() {
                             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(true);
@@ -2467,6 +2568,7 @@ fn main() {
                             }
                         }
                         unsafe { app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(enable_db_decoder); }
+                        unsafe { app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(enable_db_decoder); }
                     },
 
                     // Only one or more folders selected.
@@ -2476,7 +2578,9 @@ fn main() {
                         unsafe {
                             app_ui.context_menu_add_from_packfile.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_check_tables.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_create_db.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_merge_tables.as_mut().unwrap().set_enabled(false);
@@ -2484,6 +2588,7 @@ fn main() {
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -2514,12 +2619,15 @@ fn main() {
                             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_merge_tables.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -2540,12 +2648,15 @@ fn main() {
                             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_merge_tables.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -2566,12 +2677,15 @@ fn main() {
                             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_merge_tables.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -2592,11 +2706,14 @@ fn main() {
                             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -2617,12 +2734,15 @@ fn main() {
                             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(true);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_merge_tables.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(true);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -2643,12 +2763,15 @@ fn main() {
                             app_ui.context_menu_create_loc.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_create_text.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_merge_tables.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_delete.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_extract.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_rename.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_decoder.as_mut().unwrap().set_enabled(false);
+                            app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_dependency_manager.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_containing_folder.as_mut().unwrap().set_enabled(false);
                             app_ui.context_menu_open_with_external_program.as_mut().unwrap().set_enabled(false);
@@ -2670,8 +2793,11 @@ fn main() {
                 if !is_there_a_dependency_database || !is_there_a_schema {
                     unsafe { app_ui.context_menu_check_tables.as_mut().unwrap().set_enabled(false); }
                     unsafe { app_ui.context_menu_create_db.as_mut().unwrap().set_enabled(false); }
+                    unsafe { app_ui.context_menu_generate_loc_entries.as_mut().unwrap().set_enabled(false); }
                     unsafe { app_ui.context_menu_mass_import_tsv.as_mut().unwrap().set_enabled(false); }
+                    unsafe { app_ui.context_menu_mass_import_tsv_folder.as_mut().unwrap().set_enabled(false); }
                     unsafe { app_ui.context_menu_mass_export_tsv.as_mut().unwrap().set_enabled(false); }
+                    unsafe { app_ui.context_menu_mass_export_json.as_mut().unwrap().set_enabled(false); }
                 }
             }
         ));
@@ -3371,15 +3497,21 @@ fn main() {
                     }
                     if data.1.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::NoFilesToImport) }
 
-                    // Otherwise, try to import all of them and report the result.
+                    // Otherwise, check them first and, if any row has errors, ask the user if he wants to import them anyway, without those rows.
                     else {
+                        sender_qt.send(Commands::MassImportTSVPreview).unwrap();
+                        sender_qt_data.send(Data::VecPathBuf(data.1.to_vec())).unwrap();
+                        let errors = if let Data::VecString(data) = check_message_validity_recv2(&receiver_qt) { data } else { panic!(THREADS_MESSAGE_ERROR); };
+                        if !errors.is_empty() && !ask_import_tsv_anyway(&app_ui, &errors) { return }
+
+                        // Then, try to import all of them and report the result.
                         unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
                         sender_qt.send(Commands::MassImportTSV).unwrap();
-                        sender_qt_data.send(Data::OptionStringVecPathBuf(data)).unwrap();
+                        sender_qt_data.send(Data::OptionStringVecPathBufBool((data.0, data.1, true))).unwrap();
                         match check_message_validity_tryrecv(&receiver_qt) {
                             
                             // If it's success....
-                            Data::VecVecStringVecVecString(paths) => {
+                            Data::VecVecStringVecVecStringVecString(paths) => {
 
                                 // Get the list of paths to add, removing those we "replaced".
                                 let mut paths_to_add = paths.1.to_vec();
@@ -3411,6 +3543,11 @@ fn main() {
                                     let data = TableStateData::new_empty();
                                     table_state_data.borrow_mut().insert(path.to_vec(), data);
                                 }
+
+                                // If any column has been ignored, warn the user.
+                                if !paths.2.is_empty() {
+                                    show_dialog(app_ui.window, true, format!("<p>The following columns are not in the definitions of their tables, so they have been ignored:</p><ul>{}</ul>", paths.2.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>()));
+                                }
                             }
 
                             Data::Error(error) => show_dialog(app_ui.window, true, error),
@@ -3424,6 +3561,67 @@ fn main() {
             }
         ));
 
+        // What happens when we trigger the "Mass-Import TSV from Folder" Action.
+        let slot_contextual_menu_mass_import_tsv_folder = SlotBool::new(clone!(
+            packedfiles_open_in_packedfile_view,
+            global_search_explicit_paths,
+            table_state_data,
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+
+                // Don't do anything if there is a PackedFile open. This fixes the situation where you could overwrite data already in the UI.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsOpen) }
+
+                // Get a "Folder-only" FileDialog.
+                let import_path = unsafe { FileDialog::get_existing_directory_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select folder to import")
+                )) };
+
+                if !import_path.is_empty() {
+                    let import_path = PathBuf::from(import_path.to_std_string());
+                    if import_path.is_dir() {
+                        unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                        sender_qt.send(Commands::MassImportTSVFromFolder).unwrap();
+                        sender_qt_data.send(Data::PathBuf(import_path)).unwrap();
+                        match check_message_validity_tryrecv(&receiver_qt) {
+                            Data::VecVecStringVecString((mut paths, warnings)) => {
+
+                                // Update the TreeView with all the new files at once.
+                                update_treeview(
+                                    &sender_qt,
+                                    &sender_qt_data,
+                                    &receiver_qt,
+                                    &app_ui,
+                                    app_ui.folder_tree_view,
+                                    Some(app_ui.folder_tree_filter),
+                                    app_ui.folder_tree_model,
+                                    TreeViewOperation::Add(paths.iter().map(|x| TreePathType::File(x.to_vec())).collect()),
+                                );
+
+                                // Reset the data history of the new files.
+                                for path in &paths {
+                                    table_state_data.borrow_mut().insert(path.to_vec(), TableStateData::new_empty());
+                                }
+
+                                // Update the global search stuff, if needed.
+                                global_search_explicit_paths.borrow_mut().append(&mut paths);
+                                unsafe { update_global_search_stuff.as_mut().unwrap().trigger(); }
+
+                                if !warnings.is_empty() {
+                                    show_dialog(app_ui.window, true, format!("<p>The following files and columns have been ignored:</p><ul>{}</ul>", warnings.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>()));
+                                }
+                            }
+                            Data::Error(error) => show_dialog(app_ui.window, false, error),
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
+                        unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                    }
+                }
+            }
+        ));
+
         // What happens when we trigger the "Mass-Export TSV" Action.
         let slot_contextual_menu_mass_export_tsv = SlotBool::new(clone!(
             sender_qt,
@@ -3440,8 +3638,41 @@ fn main() {
                 if !export_path.is_empty() {
                     let export_path = PathBuf::from(export_path.to_std_string());
                     if export_path.is_dir() {
+
+                        // Exporting only the modified tables keeps the exported folder clean when only a few of them get touched.
+                        let only_modified = ask_question(&app_ui, "<p>Do you want to export only the tables modified since the PackFile was last saved?</p>");
                         unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
-                        sender_qt.send(Commands::MassExportTSV).unwrap();
+                        sender_qt.send(if only_modified { Commands::MassExportTSVModified } else { Commands::MassExportTSV }).unwrap();
+                        sender_qt_data.send(Data::PathBuf(export_path)).unwrap();
+                        match check_message_validity_tryrecv(&receiver_qt) {
+                            Data::String(response) => show_dialog(app_ui.window, true, response),
+                            Data::Error(error) => show_dialog(app_ui.window, true, error),
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
+                        unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                    }
+                }
+            }
+        ));
+
+        // What happens when we trigger the "Mass-Export JSON" Action.
+        let slot_contextual_menu_mass_export_json = SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+
+                // Get a "Folder-only" FileDialog.
+                let export_path = unsafe { FileDialog::get_existing_directory_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select destination folder")
+                )) };
+
+                // If we got an export path and it's not empty, try to export all exportable files there.
+                if !export_path.is_empty() {
+                    let export_path = PathBuf::from(export_path.to_std_string());
+                    if export_path.is_dir() {
+                        unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                        sender_qt.send(Commands::MassExportJSON).unwrap();
                         sender_qt_data.send(Data::PathBuf(export_path)).unwrap();
                         match check_message_validity_tryrecv(&receiver_qt) {
                             Data::String(response) => show_dialog(app_ui.window, true, response),
@@ -3471,6 +3702,58 @@ fn main() {
             }
         ));
 
+        // What happens when we trigger the "Generate Loc Entries" action in the Contextual Menu.
+        let slot_contextual_menu_generate_loc_entries = SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // Only continue if we have a DB Table selected.
+                let selected_items = get_item_types_from_main_treeview_selection(&app_ui);
+                let table_path = match selected_items.get(0) {
+                    Some(TreePathType::File(path)) if selected_items.len() == 1 && path.len() == 3 && path[0] == "db" => path.to_vec(),
+                    _ => return,
+                };
+
+                if let Some(values) = create_text_input_dialog(&app_ui, "Generate Loc Entries", &[
+                    "Path of the Loc PackedFile (text/db/my_mod.loc). It's created if it doesn't exist.",
+                    "Templates for the loc keys, separated by commas ({table}_onscreen_name_{key}). Leave it empty to use the ones in the settings.",
+                ]) {
+                    let loc_path = values[0].split('/').map(|x| x.trim().to_owned()).filter(|x| !x.is_empty()).collect::<Vec<String>>();
+                    if loc_path.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput); }
+
+                    // This cannot be done if the Loc PackedFile is open, as we may overwrite the changes when closing it.
+                    if packedfiles_open_in_packedfile_view.borrow().values().any(|x| *x.borrow() == loc_path) {
+                        return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen);
+                    }
+
+                    let templates = if values[1].trim().is_empty() { SETTINGS.lock().unwrap().settings_string["loc_key_templates"].to_owned() } else { values[1].to_owned() };
+                    let templates = templates.split(',').map(|x| x.trim().to_owned()).filter(|x| !x.is_empty()).collect::<Vec<String>>();
+
+                    sender_qt.send(Commands::GenerateLocEntries).unwrap();
+                    sender_qt_data.send(Data::VecStringVecStringVecString((table_path, loc_path.to_vec(), templates))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::UsizeUsizeBool((created, skipped, is_new)) => {
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                if is_new { TreeViewOperation::Add(vec![TreePathType::File(loc_path.to_vec())]) } else { TreeViewOperation::Modify(vec![TreePathType::File(loc_path.to_vec())]) },
+                            );
+                            show_dialog(app_ui.window, true, format!("<p>{} loc entries created in {}.</p><p>{} loc entries skipped, as they already existed.</p>", created, loc_path.join("/"), skipped));
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        ));
+
         // What happens when we trigger the "Merge" action in the Contextual Menu.
         let slot_contextual_menu_merge_tables = SlotBool::new(clone!(
             sender_qt,
@@ -3492,9 +3775,8 @@ fn main() {
                     }
                 }
 
-                // Then DB Tables. The conditions are that they're in the same db folder and with the same version.
-                // If ANY of these fails (until the "update table version" feature it's done), we fail the pass.
-                // Due to performance reasons, the version thing will be done later.
+                // Then DB Tables. The condition is that they're in the same db folder. Tables with different
+                // versions are converted to the newest one when merging them.
                 let mut db_pass = true;
                 let mut db_folder = String::new();
                 for path in &selected_paths {
@@ -3546,7 +3828,7 @@ fn main() {
                     }
 
                     // Get the info for the merged file.
-                    if let Some((mut name, delete_source_files)) = create_merge_tables_dialog(&app_ui) {
+                    if let Some((mut name, delete_source_files, remove_duplicates)) = create_merge_tables_dialog(&app_ui) {
 
                         // If it's a loc file and the name doesn't end in a ".loc" termination, call it ".loc".
                         if loc_pass && !name.ends_with(".loc") {
@@ -3554,7 +3836,7 @@ fn main() {
                         }
 
                         sender_qt.send(Commands::MergeTables).unwrap();
-                        sender_qt_data.send(Data::VecVecStringStringBoolBool((selected_paths, name, delete_source_files, if db_pass { true } else { false }))).unwrap();
+                        sender_qt_data.send(Data::VecVecStringStringBoolBoolBool((selected_paths, name, delete_source_files, if db_pass { true } else { false }, remove_duplicates))).unwrap();
                         match check_message_validity_recv2(&receiver_qt) {
                             Data::VecStringVecPathType((path_to_add, items_to_remove)) => {
                                 let items_to_remove = items_to_remove.iter().map(|x| From::from(x)).collect::<Vec<TreePathType>>();
@@ -3967,6 +4249,7 @@ fn main() {
             sender_qt_data,
             receiver_qt,
             table_state_data,
+            open_table_views,
             global_search_explicit_paths,
             slots,
             packedfiles_open_in_packedfile_view => move |_| {
@@ -3991,7 +4274,8 @@ fn main() {
                     &path,
                     &global_search_explicit_paths,
                     update_global_search_stuff,
-                    &table_state_data
+                    &table_state_data,
+                    &open_table_views
                 )));
 
                 // Tell the program there is an open PackedFile.
@@ -4037,6 +4321,7 @@ fn main() {
             receiver_qt,
             slots,
             table_state_data,
+            open_table_views,
             packedfiles_open_in_packedfile_view => move |_| {
 
                 if let Err(error) = open_packedfile(
@@ -4049,6 +4334,7 @@ fn main() {
                     &slots,
                     update_global_search_stuff,
                     &table_state_data,
+                    &open_table_views,
                     1
                 ) { show_dialog(app_ui.window, false, error); }
             }
@@ -4110,8 +4396,11 @@ fn main() {
         unsafe { app_ui.context_menu_create_loc.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_create_packed_file_loc); }
         unsafe { app_ui.context_menu_create_text.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_create_packed_file_text); }
         unsafe { app_ui.context_menu_mass_import_tsv.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_mass_import_tsv); }
+        unsafe { app_ui.context_menu_mass_import_tsv_folder.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_mass_import_tsv_folder); }
         unsafe { app_ui.context_menu_mass_export_tsv.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_mass_export_tsv); }
+        unsafe { app_ui.context_menu_mass_export_json.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_mass_export_json); }
         unsafe { app_ui.context_menu_merge_tables.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_merge_tables); }
+        unsafe { app_ui.context_menu_generate_loc_entries.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_generate_loc_entries); }
         unsafe { app_ui.context_menu_delete.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_delete); }
         unsafe { app_ui.context_menu_extract.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_extract); }
         unsafe { app_ui.context_menu_open_decoder.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_open_decoder); }
@@ -4273,6 +4562,7 @@ fn main() {
             sender_qt_data,
             receiver_qt,
             table_state_data,
+            open_table_views,
             packedfiles_open_in_packedfile_view => move || {
 
                 if let Err(error) = open_packedfile(
@@ -4285,6 +4575,7 @@ fn main() {
                     &slots,
                     update_global_search_stuff,
                     &table_state_data,
+                    &open_table_views,
                     0
                 ) { show_dialog(app_ui.window, false, error); }
             }
@@ -4333,102 +4624,9 @@ fn main() {
                             // Otherwise...
                             else {
 
-                                // Show the matches section in the main window and make sure both tables are empty.
+                                // Show the matches section in the main window and load the matches in it.
                                 unsafe { global_search_widget.as_mut().unwrap().show(); }
-                                unsafe { model_matches_db.as_mut().unwrap().clear(); }
-                                unsafe { model_matches_loc.as_mut().unwrap().clear(); }
-
-                                // For each match, generate an entry in their respective table, 
-                                for match_found in &matches {
-                                    match match_found {
-
-                                        // In case of Loc PackedFiles...
-                                        GlobalMatch::Loc((path, matches)) => {
-                                            for match_found in matches.iter() {
-
-                                                // Create a new list of StandardItem.
-                                                let mut qlist = ListStandardItemMutPtr::new(());
-
-                                                // Create an empty row.
-                                                let clean_path: PathBuf = path.iter().collect();
-                                                let clean_path = clean_path.to_string_lossy();
-                                                let mut file = StandardItem::new(&QString::from_std_str(clean_path));
-                                                let mut column = StandardItem::new(&QString::from_std_str(&match_found.0));
-                                                let mut column_number = StandardItem::new(&QString::from_std_str(&format!("{:?}", match_found.1)));
-                                                let mut row = StandardItem::new(&QString::from_std_str(format!("{:?}", match_found.2 + 1)));
-                                                let mut text = StandardItem::new(&QString::from_std_str(&match_found.3));
-                                                file.set_editable(false);
-                                                column.set_editable(false);
-                                                column_number.set_editable(false);
-                                                row.set_editable(false);
-                                                text.set_editable(false);
-
-                                                // Add an empty row to the list.
-                                                unsafe { qlist.append_unsafe(&file.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&row.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&text.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column_number.into_raw()); }
-
-                                                // Append the new row.
-                                                unsafe { model_matches_loc.as_mut().unwrap().append_row(&qlist); }
-                                            }
-                                        }
-
-                                        // In case of DB Tables...
-                                        GlobalMatch::DB((path, matches)) => {
-                                            for match_found in matches.iter() {
-
-                                                // Create a new list of StandardItem.
-                                                let mut qlist = ListStandardItemMutPtr::new(());
-
-                                                // Create an empty row.
-                                                let clean_path: PathBuf = path.iter().collect();
-                                                let clean_path = clean_path.to_string_lossy();
-                                                let mut file = StandardItem::new(&QString::from_std_str(clean_path));
-                                                let mut column = StandardItem::new(&QString::from_std_str(&match_found.0));
-                                                let mut column_number = StandardItem::new(&QString::from_std_str(&format!("{:?}", match_found.1)));
-                                                let mut row = StandardItem::new(&QString::from_std_str(format!("{:?}", match_found.2 + 1)));
-                                                let mut text = StandardItem::new(&QString::from_std_str(&match_found.3));
-                                                file.set_editable(false);
-                                                column.set_editable(false);
-                                                column_number.set_editable(false);
-                                                row.set_editable(false);
-                                                text.set_editable(false);
-
-                                                // Add an empty row to the list.
-                                                unsafe { qlist.append_unsafe(&file.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&row.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&text.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column_number.into_raw()); }
-
-                                                // Append the new row.
-                                                unsafe { model_matches_db.as_mut().unwrap().append_row(&qlist); }
-                                            }
-                                        }
-                                    }
-                                }
-
-                                // Hide the column number column for tables.
-                                unsafe { table_view_matches_db.as_mut().unwrap().hide_column(4); }
-                                unsafe { table_view_matches_loc.as_mut().unwrap().hide_column(4); }
-
-                                unsafe { model_matches_db.as_mut().unwrap().set_header_data((0, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("PackedFile")))); }
-                                unsafe { model_matches_db.as_mut().unwrap().set_header_data((1, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Column")))); }
-                                unsafe { model_matches_db.as_mut().unwrap().set_header_data((2, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Row")))); }
-                                unsafe { model_matches_db.as_mut().unwrap().set_header_data((3, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Match")))); }
-
-                                unsafe { model_matches_loc.as_mut().unwrap().set_header_data((0, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("PackedFile")))); }
-                                unsafe { model_matches_loc.as_mut().unwrap().set_header_data((1, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Column")))); }
-                                unsafe { model_matches_loc.as_mut().unwrap().set_header_data((2, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Row")))); }
-                                unsafe { model_matches_loc.as_mut().unwrap().set_header_data((3, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Match")))); }
-
-                                unsafe { table_view_matches_db.as_mut().unwrap().horizontal_header().as_mut().unwrap().resize_sections(ResizeMode::ResizeToContents); }
-                                unsafe { table_view_matches_loc.as_mut().unwrap().horizontal_header().as_mut().unwrap().resize_sections(ResizeMode::ResizeToContents); }
-
-                                unsafe { table_view_matches_db.as_mut().unwrap().sort_by_column((0, SortOrder::Ascending)); }
-                                unsafe { table_view_matches_loc.as_mut().unwrap().sort_by_column((0, SortOrder::Ascending)); }
+                                load_global_search_matches(model_matches_db, model_matches_loc, table_view_matches_db, table_view_matches_loc, &matches);
                             }
                         }
 
@@ -4445,9 +4643,104 @@ fn main() {
             }
         ));
 
+        // What happens when a table asks for the usages of one of his keys. The table sends the search to the background thread
+        // before triggering this, so here we only have to wait for the results and show them in the "Global Search" view.
+        let slot_global_search_show_usages = SlotNoArgs::new(clone!(
+            global_search_pattern,
+            receiver_qt => move || {
+
+                // Create the dialog to show the response.
+                let mut dialog;
+                unsafe { dialog = MessageBox::new_unsafe((
+                    message_box::Icon::Information,
+                    &QString::from_std_str("Find Usages"),
+                    &QString::from_std_str("<p>Searching in progress... Please wait.</p>"),
+                    Flags::from_int(0), // No button.
+                    app_ui.window as *mut Widget,
+                )); }
+
+                // Set it to be modal, and show it. Don't execute it, just show it.
+                dialog.set_modal(true);
+                dialog.set_standard_buttons(Flags::from_int(0));
+                dialog.show();
+
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecGlobalMatch(matches) => {
+
+                        // If there are no matches, just report it.
+                        if matches.is_empty() {
+                            dialog.set_standard_buttons(Flags::from_int(2_097_152));
+                            dialog.set_text(&QString::from_std_str("<p>No usages found.</p>"));
+                            dialog.exec();
+                        }
+
+                        // Otherwise, show them in the "Global Search" view. Usages are always in DB Tables.
+                        else {
+                            unsafe { global_search_widget.as_mut().unwrap().show(); }
+                            load_global_search_matches(model_matches_db, model_matches_loc, table_view_matches_db, table_view_matches_loc, &matches);
+
+                            // These results don't come from a pattern, so they cannot be updated when the PackFile changes.
+                            *global_search_pattern.borrow_mut() = None;
+                        }
+                    }
+
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        ));
+
+        // What happens when we search for untranslated loc entries. The search is sent to the background thread before triggering this,
+        // so here we only have to wait for the results and show them in the "Global Search" view.
+        let slot_global_search_show_untranslated = SlotNoArgs::new(clone!(
+            global_search_pattern,
+            receiver_qt => move || {
+
+                // Create the dialog to show the response.
+                let mut dialog;
+                unsafe { dialog = MessageBox::new_unsafe((
+                    message_box::Icon::Information,
+                    &QString::from_std_str("Find Untranslated Loc Entries"),
+                    &QString::from_std_str("<p>Comparing with the english Loc PackedFiles... Please wait.</p>"),
+                    Flags::from_int(0), // No button.
+                    app_ui.window as *mut Widget,
+                )); }
+
+                // Set it to be modal, and show it. Don't execute it, just show it.
+                dialog.set_modal(true);
+                dialog.set_standard_buttons(Flags::from_int(0));
+                dialog.show();
+
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecGlobalMatch(matches) => {
+
+                        // If there are no matches, just report it.
+                        if matches.is_empty() {
+                            dialog.set_standard_buttons(Flags::from_int(2_097_152));
+                            dialog.set_text(&QString::from_std_str("<p>No untranslated loc entries found.</p>"));
+                            dialog.exec();
+                        }
+
+                        // Otherwise, show them in the "Global Search" view. Untranslated entries are always in Loc PackedFiles.
+                        else {
+                            unsafe { global_search_widget.as_mut().unwrap().show(); }
+                            load_global_search_matches(model_matches_db, model_matches_loc, table_view_matches_db, table_view_matches_loc, &matches);
+
+                            // These results don't come from a pattern, so they cannot be updated when the PackFile changes.
+                            *global_search_pattern.borrow_mut() = None;
+                        }
+                    }
+
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        ));
+
         // What happens when we activate one of the matches in the "Loc Matches" table.
         let slot_load_match_loc = SlotModelIndexRef::new(clone!(
             packedfiles_open_in_packedfile_view,
+            open_table_views,
             slot_open_packedfile => move |model_index_filter| {
 
                 // Map the ModelIndex to his real ModelIndex in the full model.
@@ -4479,20 +4772,20 @@ fn main() {
 
                     // Close any open PackedFile, the open the PackedFile and select the match in it.
                     purge_them_all(&app_ui, &packedfiles_open_in_packedfile_view);
+                    open_table_views.borrow_mut().remove(&path);
                     let action = Action::new(()).into_raw();
                     unsafe { action.as_mut().unwrap().signals().triggered().connect(&*slot_open_packedfile); }
                     unsafe { action.as_mut().unwrap().trigger(); }
 
-                    // Then, select the match and scroll to it.
-                    let packed_file_table = unsafe { app_ui.packed_file_splitter.as_mut().unwrap().widget(0).as_mut().unwrap().layout().as_mut().unwrap().item_at(0).as_mut().unwrap().widget() as *mut TableView };
-                    let packed_file_model = unsafe { packed_file_table.as_mut().unwrap().model() };
-                    let selection_model = unsafe { packed_file_table.as_mut().unwrap().selection_model() };
-                    unsafe { selection_model.as_mut().unwrap().select((
-                        &packed_file_model.as_mut().unwrap().index((row, column)),
-                        Flags::from_enum(SelectionFlag::ClearAndSelect)
-                    )); }
-
-                    unsafe { packed_file_table.as_mut().unwrap().scroll_to(&packed_file_model.as_mut().unwrap().index((row, column))); }
+                    // Then, select the match and scroll to it. The row of the match is the one in the data, so we have to map it
+                    // to the one in the view, in case the table has been reopened sorted or filtered.
+                    let packed_file_table = open_table_views.borrow().get(&path).cloned();
+                    if let Some(packed_file_table) = packed_file_table {
+                        let packed_file_filter = unsafe { packed_file_table.as_mut().unwrap().model() as *mut SortFilterProxyModel };
+                        let packed_file_model = unsafe { packed_file_filter.as_mut().unwrap().source_model() };
+                        let model_index = unsafe { packed_file_filter.as_mut().unwrap().map_from_source(&packed_file_model.as_mut().unwrap().index((row, column))) };
+                        select_search_matches(packed_file_table, &[&model_index], 0);
+                    }
                     
                 }
                 else { show_dialog(app_ui.window, false, ErrorKind::PackedFileNotInFilter); }
@@ -4502,6 +4795,7 @@ fn main() {
         // What happens when we activate one of the matches in the "DB Matches" table.
         let slot_load_match_db = SlotModelIndexRef::new(clone!(
             packedfiles_open_in_packedfile_view,
+            open_table_views,
             slot_open_packedfile => move |model_index_filter| {
 
                 // Map the ModelIndex to his real ModelIndex in the full model.
@@ -4533,20 +4827,20 @@ fn main() {
 
                     // Close any open PackedFile, the open the PackedFile.
                     purge_them_all(&app_ui, &packedfiles_open_in_packedfile_view);
+                    open_table_views.borrow_mut().remove(&path);
                     let action = Action::new(()).into_raw();
                     unsafe { action.as_mut().unwrap().signals().triggered().connect(&*slot_open_packedfile); }
                     unsafe { action.as_mut().unwrap().trigger(); }
 
-                    // Then, select the match and scroll to it.
-                    let packed_file_table = unsafe { app_ui.packed_file_splitter.as_mut().unwrap().widget(0).as_mut().unwrap().layout().as_mut().unwrap().item_at(0).as_mut().unwrap().widget() as *mut TableView };
-                    let packed_file_model = unsafe { packed_file_table.as_mut().unwrap().model() };
-                    let selection_model = unsafe { packed_file_table.as_mut().unwrap().selection_model() };
-                    unsafe { selection_model.as_mut().unwrap().select((
-                        &packed_file_model.as_mut().unwrap().index((row, column)),
-                        Flags::from_enum(SelectionFlag::ClearAndSelect)
-                    )); }
-
-                    unsafe { packed_file_table.as_mut().unwrap().scroll_to(&packed_file_model.as_mut().unwrap().index((row, column))); }
+                    // Then, select the match and scroll to it. The row of the match is the one in the data, so we have to map it
+                    // to the one in the view, in case the table has been reopened sorted or filtered.
+                    let packed_file_table = open_table_views.borrow().get(&path).cloned();
+                    if let Some(packed_file_table) = packed_file_table {
+                        let packed_file_filter = unsafe { packed_file_table.as_mut().unwrap().model() as *mut SortFilterProxyModel };
+                        let packed_file_model = unsafe { packed_file_filter.as_mut().unwrap().source_model() };
+                        let model_index = unsafe { packed_file_filter.as_mut().unwrap().map_from_source(&packed_file_model.as_mut().unwrap().index((row, column))) };
+                        select_search_matches(packed_file_table, &[&model_index], 0);
+                    }
 
                 }
                 else { show_dialog(app_ui.window, false, ErrorKind::PackedFileNotInFilter); }
@@ -4601,108 +4895,11 @@ fn main() {
 
                         // Get the data from the operation...
                         match check_message_validity_tryrecv(&receiver_qt) {
-                            Data::VecGlobalMatch(matches) => {
-
-                                unsafe { model_matches_db.as_mut().unwrap().clear(); }
-                                unsafe { model_matches_loc.as_mut().unwrap().clear(); }
-
-                                // For each match, generate an entry in their respective table, 
-                                for match_found in &matches {
-                                    match match_found {
-
-                                        // In case of Loc PackedFiles...
-                                        GlobalMatch::Loc((path, matches)) => {
-                                            for match_found in matches.iter() {
-
-                                                // Create a new list of StandardItem.
-                                                let mut qlist = ListStandardItemMutPtr::new(());
-
-                                                // Create an empty row.
-                                                let clean_path: PathBuf = path.iter().collect();
-                                                let clean_path = clean_path.to_string_lossy();
-                                                let mut file = StandardItem::new(&QString::from_std_str(clean_path));
-                                                let mut column = StandardItem::new(&QString::from_std_str(&match_found.0));
-                                                let mut column_number = StandardItem::new(&QString::from_std_str(&format!("{:?}", match_found.1)));
-                                                let mut row = StandardItem::new(&QString::from_std_str(format!("{:?}", match_found.2 + 1)));
-                                                let mut text = StandardItem::new(&QString::from_std_str(&match_found.3));
-                                                file.set_editable(false);
-                                                column.set_editable(false);
-                                                column_number.set_editable(false);
-                                                row.set_editable(false);
-                                                text.set_editable(false);
-
-                                                // Add an empty row to the list.
-                                                unsafe { qlist.append_unsafe(&file.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&row.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&text.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column_number.into_raw()); }
-
-                                                // Append the new row.
-                                                unsafe { model_matches_loc.as_mut().unwrap().append_row(&qlist); }
-                                            }
-                                        }
-
-                                        // In case of DB Tables...
-                                        GlobalMatch::DB((path, matches)) => {
-                                            for match_found in matches.iter() {
-
-                                                // Create a new list of StandardItem.
-                                                let mut qlist = ListStandardItemMutPtr::new(());
-
-                                                // Create an empty row.
-                                                let clean_path: PathBuf = path.iter().collect();
-                                                let clean_path = clean_path.to_string_lossy();
-                                                let mut file = StandardItem::new(&QString::from_std_str(clean_path));
-                                                let mut column = StandardItem::new(&QString::from_std_str(&match_found.0));
-                                                let mut column_number = StandardItem::new(&QString::from_std_str(&format!("{:?}", match_found.1)));
-                                                let mut row = StandardItem::new(&QString::from_std_str(format!("{:?}", match_found.2 + 1)));
-                                                let mut text = StandardItem::new(&QString::from_std_str(&match_found.3));
-                                                file.set_editable(false);
-                                                column.set_editable(false);
-                                                column_number.set_editable(false);
-                                                row.set_editable(false);
-                                                text.set_editable(false);
-
-                                                // Add an empty row to the list.
-                                                unsafe { qlist.append_unsafe(&file.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&row.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&text.into_raw()); }
-                                                unsafe { qlist.append_unsafe(&column_number.into_raw()); }
-
-                                                // Append the new row.
-                                                unsafe { model_matches_db.as_mut().unwrap().append_row(&qlist); }
-                                            }
-                                        }
-                                    }
-                                }
-
-                                // Hide the column number column for tables.
-                                unsafe { table_view_matches_db.as_mut().unwrap().hide_column(4); }
-                                unsafe { table_view_matches_loc.as_mut().unwrap().hide_column(4); }
-                            }
+                            Data::VecGlobalMatch(matches) => load_global_search_matches(model_matches_db, model_matches_loc, table_view_matches_db, table_view_matches_loc, &matches),
 
                             // In ANY other situation, it's a message problem.
                             _ => panic!(THREADS_MESSAGE_ERROR),
                         }
-
-                        // Reconfigure the columns.
-                        unsafe { model_matches_db.as_mut().unwrap().set_header_data((0, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("PackedFile")))); }
-                        unsafe { model_matches_db.as_mut().unwrap().set_header_data((1, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Column")))); }
-                        unsafe { model_matches_db.as_mut().unwrap().set_header_data((2, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Row")))); }
-                        unsafe { model_matches_db.as_mut().unwrap().set_header_data((3, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Match")))); }
-
-                        unsafe { model_matches_loc.as_mut().unwrap().set_header_data((0, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("PackedFile")))); }
-                        unsafe { model_matches_loc.as_mut().unwrap().set_header_data((1, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Column")))); }
-                        unsafe { model_matches_loc.as_mut().unwrap().set_header_data((2, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Row")))); }
-                        unsafe { model_matches_loc.as_mut().unwrap().set_header_data((3, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Match")))); }
-
-                        unsafe { table_view_matches_db.as_mut().unwrap().horizontal_header().as_mut().unwrap().resize_sections(ResizeMode::ResizeToContents); }
-                        unsafe { table_view_matches_loc.as_mut().unwrap().horizontal_header().as_mut().unwrap().resize_sections(ResizeMode::ResizeToContents); }
-
-                        unsafe { table_view_matches_db.as_mut().unwrap().sort_by_column((0, SortOrder::Ascending)); }
-                        unsafe { table_view_matches_loc.as_mut().unwrap().sort_by_column((0, SortOrder::Ascending)); }
                     }
                 }
             }
@@ -4777,8 +4974,20 @@ fn main() {
             ); 
         });
 
+        // What happens when something outside the TreeView wants to open the PackedFile selected in it.
+        let slot_open_selected_packedfile = SlotNoArgs::new(clone!(
+            packedfiles_open_in_packedfile_view,
+            slot_open_packedfile => move || {
+                purge_them_all(&app_ui, &packedfiles_open_in_packedfile_view);
+                let action = Action::new(()).into_raw();
+                unsafe { action.as_mut().unwrap().signals().triggered().connect(&*slot_open_packedfile); }
+                unsafe { action.as_mut().unwrap().trigger(); }
+            }
+        ));
+
         // Action to try to open a PackedFile.
         unsafe { app_ui.folder_tree_view.as_ref().unwrap().signals().activated().connect(&*slot_open_packedfile); }
+        unsafe { app_ui.tree_view_open_packedfile.as_ref().unwrap().signals().triggered().connect(&slot_open_selected_packedfile); }
 
         // In windows "activated" means double click, so we need to add this action too to compensate it.
         if cfg!(target_os = "windows") {
@@ -4790,6 +4999,8 @@ fn main() {
         
         // Global search actions.
         unsafe { app_ui.context_menu_global_search.as_ref().unwrap().signals().triggered().connect(&slot_contextual_menu_global_search); }
+        unsafe { app_ui.global_search_show_usages.as_ref().unwrap().signals().triggered().connect(&slot_global_search_show_usages); }
+        unsafe { app_ui.global_search_show_untranslated.as_ref().unwrap().signals().triggered().connect(&slot_global_search_show_untranslated); }
         unsafe { table_view_matches_loc.as_mut().unwrap().signals().double_clicked().connect(&slot_load_match_loc); }
         unsafe { table_view_matches_db.as_mut().unwrap().signals().double_clicked().connect(&slot_load_match_db); }
         unsafe { close_matches_button.as_mut().unwrap().signals().released().connect(&slot_close_global_search); }
@@ -4887,11 +5098,95 @@ fn main() {
         // Show the Main Window...
         unsafe { app_ui.window.as_mut().unwrap().show(); }
 
+        // If we left an autosave behind in the last session and it's newer than his PackFile, offer to recover it.
+        // If the user refuses, we leave the autosave where it is, until the next autosave or RPFM is closed properly.
+        let mut autosave_recovered = false;
+        if SETTINGS.lock().unwrap().settings_bool["autosave_recovery_prompt"] {
+            if let Some((shadow_path, source_path)) = get_autosave_shadow() {
+                let shadow_timestamp = File::open(&shadow_path).ok().map(|x| get_last_modified_time_from_file(&x));
+                let disk_timestamp = File::open(&source_path).ok().map(|x| get_last_modified_time_from_file(&x));
+                if let Some(shadow_timestamp) = shadow_timestamp {
+                    if should_recover_autosave(shadow_timestamp, disk_timestamp) {
+
+                        let mut dialog = unsafe { MessageBox::new_unsafe((
+                            message_box::Icon::Information,
+                            &QString::from_std_str("Autosave found"),
+                            &QString::from_std_str(format!("<p>RPFM found an autosave of <i>{}</i> newer than the PackFile on disk. This usually means RPFM didn't close properly the last time.</p><p>Do you want to recover it?</p>", source_path.to_string_lossy())),
+                            Flags::from_int(4_194_304), // Cancel button.
+                            app_ui.window as *mut Widget,
+                        )) };
+
+                        dialog.add_button((&QString::from_std_str("&Recover"), message_box::ButtonRole::AcceptRole));
+                        dialog.set_modal(true);
+                        dialog.show();
+
+                        // If we hit "Recover", open the autosave.
+                        if dialog.exec() == 0 {
+                            autosave_recovered = true;
+                            if let Err(error) = open_packfile(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &[shadow_path],
+                                &app_ui,
+                                &mymod_stuff,
+                                &mode,
+                                "",
+                                &packedfiles_open_in_packedfile_view,
+                                close_global_search_action,
+                                &table_state_data,
+                            ) { show_dialog(app_ui.window, false, error); }
+
+                            // Point the recovered PackFile to his original path, so saving it overwrites the original instead of the autosave,
+                            // and mark it as modified, as its contents are not yet on disk.
+                            else {
+                                sender_qt.send(Commands::SetPackFilePath).unwrap();
+                                sender_qt_data.send(Data::PathBuf(source_path.to_path_buf())).unwrap();
+
+                                let pack_file_name = source_path.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+                                let root = unsafe { app_ui.folder_tree_model.as_mut().unwrap().item(0) };
+                                unsafe { root.as_mut().unwrap().set_text(&QString::from_std_str(&pack_file_name)); }
+                                unsafe { root.as_mut().unwrap().set_data((&Variant::new0(1i32), 21)); }
+                                *IS_MODIFIED.lock().unwrap() = update_packfile_state(None, &app_ui);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Timer to autosave the open PackFile every X minutes, as set in the settings. It ticks every minute, so changes to the interval apply without restarting RPFM.
+        let autosave_minutes = Rc::new(RefCell::new(0u32));
+        let autosave_timer = Timer::new().into_raw();
+        unsafe { autosave_timer.as_mut().unwrap().set_interval(60_000); }
+        let slot_autosave = SlotNoArgs::new(clone!(
+            sender_qt,
+            receiver_qt => move || {
+                match SETTINGS.lock().unwrap().get_autosave_interval() {
+                    Some(interval) => {
+                        *autosave_minutes.borrow_mut() += 1;
+                        if *autosave_minutes.borrow() >= interval {
+                            *autosave_minutes.borrow_mut() = 0;
+                            sender_qt.send(Commands::SaveAutosave).unwrap();
+                            match check_message_validity_tryrecv(&receiver_qt) {
+                                Data::Success => {},
+                                Data::Error(error) => show_dialog(app_ui.window, false, error),
+                                _ => panic!(THREADS_MESSAGE_ERROR),
+                            }
+                        }
+                    }
+                    None => *autosave_minutes.borrow_mut() = 0,
+                }
+            }
+        ));
+        unsafe { autosave_timer.as_mut().unwrap().signals().timeout().connect(&slot_autosave); }
+        unsafe { autosave_timer.as_mut().unwrap().start(()); }
+
         // We get all the Arguments provided when starting RPFM, just in case we passed it a path.
         let arguments = args().collect::<Vec<String>>();
 
         // If we have an argument (we open RPFM by clicking in a PackFile directly)...
-        if arguments.len() > 1 {
+        if arguments.len() > 1 && !autosave_recovered {
 
             // Turn the fist argument into a Path.
             let path = PathBuf::from(&arguments[1]);
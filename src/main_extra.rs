@@ -204,6 +204,7 @@ pub fn open_packedfile(
     slots: &Rc<RefCell<Vec<TheOneSlot>>>,
     update_global_search_stuff: *mut Action,
     table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
+    open_table_views: &Rc<RefCell<BTreeMap<Vec<String>, *mut TableView>>>,
     view_position: i32,
 ) -> Result<()> {
 
@@ -252,6 +253,7 @@ pub fn open_packedfile(
                             &global_search_explicit_paths,
                             update_global_search_stuff,
                             table_state_data,
+                            open_table_views,
                         ) {
                             Ok(new_slots) => { slots.borrow_mut().push(TheOneSlot::Table(new_slots)); },
                             Err(error) => return Err(ErrorKind::LocDecode(format!("{}", error)))?,
@@ -276,7 +278,8 @@ pub fn open_packedfile(
                             &path,
                             &global_search_explicit_paths,
                             update_global_search_stuff,
-                            table_state_data
+                            table_state_data,
+                            open_table_views
                         ) {
                             Ok(new_slots) => { slots.borrow_mut().push(TheOneSlot::Table(new_slots)); },
                             Err(error) => return Err(ErrorKind::DBTableDecode(format!("{}", error)))?,
@@ -373,6 +376,31 @@ pub fn open_packedfile(
     Ok(())
 }
 
+/// This function reopens the PackedFiles open in the provided views, so they show the changes done to them in the background thread.
+/// The first view is reopened first, as opening a PackedFile there closes every other view. Views we cannot reopen are closed.
+pub fn reopen_packedfiles(
+    app_ui: &AppUI,
+    open_packedfiles: &BTreeMap<i32, Vec<String>>,
+    packedfiles_open_in_packedfile_view: &Rc<RefCell<BTreeMap<i32, Rc<RefCell<Vec<String>>>>>>,
+) {
+    for (view, path) in open_packedfiles {
+        let item = get_item_from_type(app_ui.folder_tree_model, &TreePathType::File(path.to_vec()));
+        let model_index = unsafe { app_ui.folder_tree_model.as_mut().unwrap().index_from_item(item) };
+        let filtered_index = unsafe { app_ui.folder_tree_filter.as_ref().unwrap().map_from_source(&model_index) };
+
+        // If it's not in the current TreeView Filter we CAN'T OPEN IT.
+        if filtered_index.is_valid() {
+            let selection_model = unsafe { app_ui.folder_tree_view.as_mut().unwrap().selection_model() };
+            unsafe { selection_model.as_mut().unwrap().select((&filtered_index, Flags::from_enum(SelectionFlag::ClearAndSelect))); }
+            expand_treeview_to_item(app_ui.folder_tree_view, app_ui.folder_tree_filter, app_ui.folder_tree_model, path);
+
+            if *view == 0 { unsafe { app_ui.tree_view_open_packedfile.as_mut().unwrap().trigger(); }}
+            else { unsafe { app_ui.context_menu_open_in_multi_view.as_mut().unwrap().trigger(); }}
+        }
+        else { purge_that_one_specifically(app_ui, *view, packedfiles_open_in_packedfile_view); }
+    }
+}
+
 /// This function is used to save ANY supported PackFile. If the PackFile doesn't exist or we want to save it
 /// with another name, it opens a dialog asking for a path.
 pub fn save_packfile(
@@ -1057,173 +1085,1776 @@ pub fn build_my_mod_menu(
     (mymod_stuff, mymod_slots)
 }
 
-
-/// This function takes care of the re-creation of the "Open From Content" and "Open From Data" submenus.
-/// This has to be executed every time we change the Game Selected.
-pub fn build_open_from_submenus(
+/// This function builds the "Tools" Menu, with all the PackFile-wide tools that don't belong to any specific game.
+pub fn build_tools_menu(
     sender_qt: &Sender<Commands>,
     sender_qt_data: &Sender<Data>,
     receiver_qt: &Rc<RefCell<Receiver<Data>>>,
     app_ui: AppUI,
-    submenu_open_from_content: *mut Menu,
-    submenu_open_from_data: *mut Menu,
-    mode: &Rc<RefCell<Mode>>,
+    menu_bar_tools: *mut Menu,
     packedfiles_open_in_packedfile_view: &Rc<RefCell<BTreeMap<i32, Rc<RefCell<Vec<String>>>>>>,
-    mymod_stuff: &Rc<RefCell<MyModStuff>>,
-    close_global_search_action: *mut Action,
-    table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
-) -> Vec<SlotBool<'static>> {
+) -> (ToolsStuff, ToolsSlots) {
+
+    // Create the actions of the menu.
+    let tools_stuff = unsafe { ToolsStuff {
+            strip_boms_from_text: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Strip &BOMs from Text Files")),
+            count_localisable_characters: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("&Count Localisable Characters")),
+            dry_run_mode: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("&Dry Run Mode")),
+            find_reference_cycles: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find &Reference Cycles")),
+            loc_coverage_report: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("&Loc Coverage Report")),
+            set_table_alias: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Set Table &Alias...")),
+            export_column_enums: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export Column &Enums...")),
+            find_non_ascii_keys: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find &Non-ASCII Keys")),
+            split_table_by_column: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("S&plit Table by Column...")),
+            restore_deleted_file: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Res&tore Deleted File...")),
+            find_unannotated_tables: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find &Unannotated Tables")),
+            export_translation_matrix: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export &Translation Matrix...")),
+            trim_trailing_nulls: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Trim Trailing N&ulls from Text Files")),
+            validate_pack_for_game: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("&Validate PackFile for Game...")),
+            check_composite_uniqueness: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Check &Composite Uniqueness...")),
+            append_row_count_snapshot: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Append Row &Count Snapshot...")),
+            find_redundant_overrides: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find Redundant &Overrides")),
+            suggest_unique_key: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Suggest Unique &Key...")),
+            export_vanilla_mod_comparison: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export Vanilla/&Mod Comparison...")),
+            find_unreferenced_assets: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find Unre&ferenced Assets")),
+            run_edit_script: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Run Edit Scr&ipt...")),
+            size_by_table_type: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Si&ze by Table Type")),
+            lock_schema: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Loc&k Schema")),
+            find_oversized_cells: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find &Oversized Cells...")),
+            export_tree_as_html: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export Tree as &HTML...")),
+            verify_loc_column_order: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Verify Loc Column &Order")),
+            session_edit_stats: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Session Edit St&ats")),
+            find_case_collisions: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find &Case Collisions")),
+            export_optimization_report: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export Optimi&zation Report...")),
+            replace_in_column: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Replace in Col&umn...")),
+            validate_key_column: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Validate &Key Column")),
+            export_loc_matches: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export Loc &Matches...")),
+            merge_identical_adjacent_rows: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Merge Identical Adjacent &Rows")),
+            export_table_checksums: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export Table Check&sums...")),
+            export_command_log: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export Co&mmand Log...")),
+            replay_command_log: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Re&play Command Log...")),
+            export_to_sqlite: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export to S&QLite...")),
+            import_from_sqlite: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("&Import from SQLite...")),
+            find_missing_loc_keys: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find Missing &Loc Keys")),
+            find_duplicated_loc_keys: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find &Duplicated Loc Keys")),
+            export_bilingual_tsv: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Export &Bilingual TSV...")),
+            import_bilingual_tsv: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Import Bilingual &TSV...")),
+            find_untranslated_loc_entries: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Find &Untranslated Loc Entries")),
+            rename_key_prefix: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Rename &Key Prefix...")),
+            diff_table_with_packfile: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Diff Table with &PackFile...")),
+            schema_patches: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Sc&hema Patches...")),
+            import_assembly_kit_definitions: menu_bar_tools.as_mut().unwrap().add_action(&QString::from_std_str("Import Assembly &Kit Definitions")),
+        }
+    };
 
-    // First, we clear the list, just in case this is a "Rebuild" of the menu.
-    unsafe { submenu_open_from_content.as_mut().unwrap().clear(); }
-    unsafe { submenu_open_from_data.as_mut().unwrap().clear(); }
+    // The "Dry Run" and "Lock Schema" actions are toggles, so it needs to be checkable.
+    unsafe { tools_stuff.dry_run_mode.as_mut().unwrap().set_checkable(true); }
+    unsafe { tools_stuff.lock_schema.as_mut().unwrap().set_checkable(true); }
+
+    // Status tips for the actions.
+    unsafe { tools_stuff.strip_boms_from_text.as_mut().unwrap().set_status_tip(&QString::from_std_str("Remove the UTF-8 BOM from the start of every Text PackedFile in the PackFile, as some of the game's parsers break with them. Tables are not touched.")); }
+    unsafe { tools_stuff.count_localisable_characters.as_mut().unwrap().set_status_tip(&QString::from_std_str("Count the characters of all the text in the Loc PackedFiles and the localisable columns of the DB Tables of the PackFile, to estimate how much work a translation will take.")); }
+    unsafe { tools_stuff.dry_run_mode.as_mut().unwrap().set_status_tip(&QString::from_std_str("Toggle the Dry Run mode. While it's enabled, the actions that edit the PackFile only show what they would change, without changing it, and saving only reports where the PackFile would have been saved.")); }
+    unsafe { tools_stuff.find_reference_cycles.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the tables of the schema that end up referencing themselves, directly or through other tables.")); }
+    unsafe { tools_stuff.loc_coverage_report.as_mut().unwrap().set_status_tip(&QString::from_std_str("Check, for each DB Table of the PackFile, how many of the loc keys his rows need exist, are blank or are missing.")); }
+    unsafe { tools_stuff.set_table_alias.as_mut().unwrap().set_status_tip(&QString::from_std_str("Make RPFM decode the tables of a folder with a custom name (db/my_custom_units) using the definition of another table (units_tables). Aliases are saved in the PackFile.")); }
+    unsafe { tools_stuff.export_column_enums.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export to a TSV file the distinct values of one or more columns (like units.category), searching in the tables of the PackFile and the dependency database.")); }
+    unsafe { tools_stuff.find_non_ascii_keys.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the keys with non-ASCII characters (like accented letters) of the selected DB Table or Loc PackedFile, as some parts of the games only support ASCII keys.")); }
+    unsafe { tools_stuff.split_table_by_column.as_mut().unwrap().set_status_tip(&QString::from_std_str("Split the selected DB Table into multiple tables, one for each distinct value of the provided column.")); }
+    unsafe { tools_stuff.restore_deleted_file.as_mut().unwrap().set_status_tip(&QString::from_std_str("Restore a file deleted from the PackFile since it was open, without undoing anything done after deleting it.")); }
+    unsafe { tools_stuff.find_unannotated_tables.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the tables of the schema without reference data, as they cannot be checked for missing references.")); }
+    unsafe { tools_stuff.export_translation_matrix.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export the selected Loc PackedFiles to a TSV file with one row per key and one column per Loc PackedFile, to compare their translations side by side.")); }
+    unsafe { tools_stuff.trim_trailing_nulls.as_mut().unwrap().set_status_tip(&QString::from_std_str("Remove the trailing null bytes from the end of every Text PackedFile in the PackFile, as some of the game's parsers break with them. Tables are not touched.")); }
+    unsafe { tools_stuff.validate_pack_for_game.as_mut().unwrap().set_status_tip(&QString::from_std_str("Try to decode every DB Table and Loc PackedFile of the PackFile with the schema of the selected game, to check if the PackFile is compatible with it.")); }
+    unsafe { tools_stuff.check_composite_uniqueness.as_mut().unwrap().set_status_tip(&QString::from_std_str("Check that no two rows of the selected DB Table or Loc PackedFile have the same values in all the provided columns, as if they were a composite key.")); }
+    unsafe { tools_stuff.append_row_count_snapshot.as_mut().unwrap().set_status_tip(&QString::from_std_str("Append the current row count of every table of the PackFile to a log file, so you can track how your mod grows over time.")); }
+    unsafe { tools_stuff.find_redundant_overrides.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the PackedFiles of the PackFile that are exactly the same as their vanilla version. Vanilla files are taken from the dependency database of the Game Selected.")); }
+    unsafe { tools_stuff.suggest_unique_key.as_mut().unwrap().set_status_tip(&QString::from_std_str("Suggest a key for a new row of the selected DB Table or Loc PackedFile that's not already in use, based on the one you provide. The key is copied to the clipboard.")); }
+    unsafe { tools_stuff.export_vanilla_mod_comparison.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export the selected DB Table or Loc PackedFile to a TSV file, with each row next to the vanilla row with the same key, so you can review your changes. Vanilla rows are taken from the dependency database of the Game Selected.")); }
+    unsafe { tools_stuff.find_unreferenced_assets.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the images and models of the PackFile not referenced by any table, either from the PackFile or from the game files. Only columns marked as file paths in the schema are checked.")); }
+    unsafe { tools_stuff.run_edit_script.as_mut().unwrap().set_status_tip(&QString::from_std_str("Apply the edits of a JSON script (cell edits, new rows and deleted rows) to the tables of the PackFile. If any of the edits fails, none of them is applied.")); }
+    unsafe { tools_stuff.size_by_table_type.as_mut().unwrap().set_status_tip(&QString::from_std_str("Show how much space the tables of the PackFile take, grouped by table type, so you know where to start optimizing.")); }
+    unsafe { tools_stuff.lock_schema.as_mut().unwrap().set_status_tip(&QString::from_std_str("Toggle the schema lock. While it's enabled, the actions that change the schema (saving definitions from the decoder or updating the schemas) are blocked, so you can experiment without breaking it.")); }
+    unsafe { tools_stuff.find_oversized_cells.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the text cells of all the tables in the PackFile longer than the amount of characters you provide, as they usually are text pasted in the wrong column.")); }
+    unsafe { tools_stuff.export_tree_as_html.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export the tree of the PackFile to a HTML page, with the type and size of each PackedFile, so you can share what's inside it.")); }
+    unsafe { tools_stuff.verify_loc_column_order.as_mut().unwrap().set_status_tip(&QString::from_std_str("Check that the columns of the selected Loc PackedFile are the key, the text and the tooltip flag, in that order, as any other layout makes it show and export wrong.")); }
+    unsafe { tools_stuff.session_edit_stats.as_mut().unwrap().set_status_tip(&QString::from_std_str("Show how many rows and files have been added, removed, edited or renamed since the PackFile was opened.")); }
+    unsafe { tools_stuff.find_case_collisions.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the PackedFiles whose paths only differ by case, as they overwrite each other when extracted on Windows.")); }
+    unsafe { tools_stuff.export_optimization_report.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export a report of the rows the optimizer would remove from each table, and the tables it would delete, without changing the PackFile.")); }
+    unsafe { tools_stuff.replace_in_column.as_mut().unwrap().set_status_tip(&QString::from_std_str("Replace every cell of a column of the selected table equal to a value with another value, in one go.")); }
+    unsafe { tools_stuff.validate_key_column.as_mut().unwrap().set_status_tip(&QString::from_std_str("Check the key of the selected table holds unique, non-empty values, as a broken key breaks the lookups and references of the game.")); }
+    unsafe { tools_stuff.export_loc_matches.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export to a TSV file every Loc row whose text contains some text, with his file and key, so a term can be fixed in batch.")); }
+    unsafe { tools_stuff.merge_identical_adjacent_rows.as_mut().unwrap().set_status_tip(&QString::from_std_str("Collapse every run of identical rows next to each other in the selected table into a single row, like the ones some broken imports leave behind.")); }
+    unsafe { tools_stuff.export_table_checksums.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export a checksum of the contents of each table, independent of the order of the rows, so you can detect when a table changed between builds.")); }
+    unsafe { tools_stuff.export_command_log.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export the commands that edited the open PackFile since it was opened to a JSON file, so they can be replayed later.")); }
+    unsafe { tools_stuff.replay_command_log.as_mut().unwrap().set_status_tip(&QString::from_std_str("Apply the commands of an exported command log to the open PackFile. If any of them fails, none of them are applied.")); }
+    unsafe { tools_stuff.export_to_sqlite.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export the DB Tables and Loc PackedFiles selected in the TreeView to a SQLite database, with a table for each one of them, named like his path.")); }
+    unsafe { tools_stuff.import_from_sqlite.as_mut().unwrap().set_status_tip(&QString::from_std_str("Import the tables of a SQLite database made with 'Export to SQLite' into the open PackFile, replacing the tables in the paths they were exported from.")); }
+    unsafe { tools_stuff.find_missing_loc_keys.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the loc keys the rows of the DB Tables of the PackFile need that don't exist, neither in the PackFile nor in the game files, and optionally add blank entries for them to a Loc PackedFile.")); }
+    unsafe { tools_stuff.find_duplicated_loc_keys.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the loc keys defined more than once in the Loc PackedFiles of the PackFile, as the game only uses one of them, and optionally pick the entry to keep for each one.")); }
+    unsafe { tools_stuff.export_bilingual_tsv.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export the selected Loc PackedFiles to a TSV file with the key, the text and the text of the same key in another PackFile (like a translation or the vanilla loc pack of another language), for translators.")); }
+    unsafe { tools_stuff.import_bilingual_tsv.as_mut().unwrap().set_status_tip(&QString::from_std_str("Import the translated texts of a TSV file made with 'Export Bilingual TSV' into the selected Loc PackedFile. Only the text of the keys in the file is changed, and the missing keys are added.")); }
+    unsafe { tools_stuff.find_untranslated_loc_entries.as_mut().unwrap().set_status_tip(&QString::from_std_str("Find the entries of the Loc PackedFiles of the PackFile with the same text as the entry with the same key in the english Loc PackedFiles of the game selected. Keys matching the patterns provided (by default, the ones in the settings) are ignored.")); }
+    unsafe { tools_stuff.rename_key_prefix.as_mut().unwrap().set_status_tip(&QString::from_std_str("Replace a prefix of the loc keys of all the Loc PackedFiles of the PackFile with another one and, optionally, of the keys of the DB Tables with localisable fields and the cells referencing them. The changes are previewed before applying them.")); }
+    unsafe { tools_stuff.diff_table_with_packfile.as_mut().unwrap().set_status_tip(&QString::from_std_str("Compare one of the open DB Tables or Loc PackedFiles with a table of another PackFile (by default, the one in the same path), showing the added, removed and changed rows. Rows are matched by their key columns.")); }
+    unsafe { tools_stuff.schema_patches.as_mut().unwrap().set_status_tip(&QString::from_std_str("List the local patches of the schema of the game selected (the changes saved from the decoder, which are applied on top of the schema after every update), and remove the ones you no longer want.")); }
+    unsafe { tools_stuff.import_assembly_kit_definitions.as_mut().unwrap().set_status_tip(&QString::from_std_str("Import from the Assembly Kit of the game selected the definitions of the versions of the tables (in the dependency PackFiles and the open PackFile) the schema doesn't have yet. The imported definitions are saved as local patches of the schema.")); }
 
     // And we create the slots.
-    let mut open_from_slots = vec![];
+    let tools_slots = ToolsSlots {
 
-    //---------------------------------------------------------------------------------------//
-    // Build the menus...
-    //---------------------------------------------------------------------------------------//
+        // This slot is used for the "Strip BOMs from Text Files" action.
+        strip_boms_from_text: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
 
-    // Get the path of every PackFile in the data folder (if it's configured) and make an action for each one of them.
-    if let Some(ref mut paths) = get_game_selected_content_packfiles_paths() {
-        paths.sort_unstable_by_key(|x| x.file_name().unwrap().to_string_lossy().as_ref().to_owned());
-        for path in paths {
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
 
-            // That means our file is a valid PackFile and it needs to be added to the menu.
-            let mod_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
+                sender_qt.send(Commands::StripBomsFromText).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecVecString(paths) => {
+                        if paths.is_empty() { show_dialog(app_ui.window, true, "No Text PackedFile with a BOM has been found."); }
+                        else {
+                            let message = format!("<p>The BOM has been removed from the following PackedFiles:</p><ul>{}</ul>", paths.iter().map(|x| format!("<li>{}</li>", x.join("/"))).collect::<String>());
+                            let paths = paths.iter().map(|x| TreePathType::File(x.to_vec())).collect::<Vec<TreePathType>>();
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Modify(paths),
+                            );
+                            show_dialog(app_ui.window, true, message);
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
 
-            // Create the action for it.
-            let open_mod_action;
-            unsafe { open_mod_action = submenu_open_from_content.as_mut().unwrap().add_action(&QString::from_std_str(mod_name)); }
+        // This slot is used for the "Count Localisable Characters" action.
+        count_localisable_characters: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::CountLocalisableCharacters).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecVecStringUsizeUsizeVecVecString((counts, total, failed)) => {
+                        let mut text = format!("<p>Localisable characters in this PackFile: {}</p>", total);
+                        if !failed.is_empty() { text.push_str(&format!("<p>{} PackedFiles couldn't be decoded, so they're not counted.</p>", failed.len())); }
+
+                        let headers = vec!["PackedFile".to_owned(), "Characters".to_owned()];
+                        let rows = counts.iter().map(|x| vec![x.0.join("/"), x.1.to_string()])
+                            .chain(failed.iter().map(|x| vec![x.join("/"), "Couldn't be decoded".to_owned()]))
+                            .collect::<Vec<Vec<String>>>();
+                        create_results_dialog(&app_ui, "Localisable Characters", &text, &headers, &rows);
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
 
-            // Create the slot for that action.
-            let slot_open_mod = SlotBool::new(clone!(
-                mode,
-                mymod_stuff,
-                path,
-                packedfiles_open_in_packedfile_view,
-                close_global_search_action,
-                table_state_data,
-                sender_qt,
-                sender_qt_data,
-                receiver_qt => move |_| {
+        // This slot is used for the "Dry Run Mode" action.
+        dry_run_mode: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |state| {
+                sender_qt.send(Commands::SetDryRun).unwrap();
+                sender_qt_data.send(Data::Bool(state)).unwrap();
+
+                // When we leave the "Dry Run" mode, the TreeView may still show the changes we tried, which never
+                // reached the PackFile. So we rebuild it from the PackFile to discard them.
+                if !state {
+                    update_treeview(
+                        &sender_qt,
+                        &sender_qt_data,
+                        &receiver_qt,
+                        &app_ui,
+                        app_ui.folder_tree_view,
+                        Some(app_ui.folder_tree_filter),
+                        app_ui.folder_tree_model,
+                        TreeViewOperation::Build(false),
+                    );
+                }
+            }
+        )),
 
-                    // Check first if there has been changes in the PackFile.
-                    if are_you_sure(&app_ui, false) {
+        // This slot is used for the "Find Reference Cycles" action.
+        find_reference_cycles: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::FindReferenceCycles).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecVecString(cycles) => {
+                        if cycles.is_empty() { show_dialog(app_ui.window, true, "No circular references have been found in the schema."); }
+                        else {
+                            let cycles = cycles.iter().map(|x| format!("<li>{}</li>", x.join(" → "))).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<p>The following circular references have been found in the schema:</p><ul>{}</ul>", cycles));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
 
-                        // Try to open it, and report it case of error.
-                        if let Err(error) = open_packfile(
+        // This slot is used for the "Loc Coverage Report" action.
+        loc_coverage_report: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::LocCoverageReport).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecLocCoverage(coverages) => {
+                        if coverages.is_empty() { show_dialog(app_ui.window, true, "No DB Table with localisable columns has been found in this PackFile."); }
+                        else {
+                            let rows = coverages.iter().map(|x| format!(
+                                "<tr><td>{}</td><td>{:.2}%</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                x.path.join("/"),
+                                x.get_coverage(),
+                                x.present,
+                                x.blank,
+                                x.missing
+                            )).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<table><tr><th>Table</th><th>Coverage</th><th>Present</th><th>Blank</th><th>Missing</th></tr>{}</table>", rows));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Set Table Alias" action.
+        set_table_alias: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(values) = create_text_input_dialog(&app_ui, "Set Table Alias", &["Table folder (my_custom_units).", "Table definition to use (units_tables). Leave it empty to remove the alias."]) {
+                    if !values[0].is_empty() {
+                        sender_qt.send(Commands::SetTableAlias).unwrap();
+                        sender_qt_data.send(Data::StringString((values[0].to_owned(), values[1].to_owned()))).unwrap();
+                        update_treeview(
                             &sender_qt,
                             &sender_qt_data,
                             &receiver_qt,
-                            &[path.to_path_buf()],
                             &app_ui,
-                            &mymod_stuff,
-                            &mode,
-                            "",
-                            &packedfiles_open_in_packedfile_view,
-                            close_global_search_action,
-                            &table_state_data,
-                        ) { show_dialog(app_ui.window, false, error); }
+                            app_ui.folder_tree_view,
+                            Some(app_ui.folder_tree_filter),
+                            app_ui.folder_tree_model,
+                            TreeViewOperation::Modify(vec![TreePathType::PackFile]),
+                        );
                     }
                 }
-            ));
+            }
+        )),
 
-            // Add the slot to the list.
-            open_from_slots.push(slot_open_mod);
+        // This slot is used for the "Export Column Enums" action.
+        export_column_enums: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(values) = create_text_input_dialog(&app_ui, "Export Column Enums", &["Columns to export, separated by commas (units.category, land_units.class)."]) {
+                    let column_refs = values[0].split(',').map(|x| x.trim().to_owned()).filter(|x| !x.is_empty()).collect::<Vec<String>>();
+                    if column_refs.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput); }
+                    if let Some(path) = get_save_file_path(&app_ui, "Export Column Enums", "TSV Files (*.tsv)", "tsv") {
+                        sender_qt.send(Commands::ExportColumnEnums).unwrap();
+                        sender_qt_data.send(Data::VecStringPathBuf((column_refs, path))).unwrap();
+                        match check_message_validity_tryrecv(&receiver_qt) {
+                            Data::Success => show_dialog(app_ui.window, true, "Column values exported."),
+                            Data::Error(error) => show_dialog(app_ui.window, false, error),
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
+                    }
+                }
+            }
+        )),
 
-            // Connect the action to the slot.
-            unsafe { open_mod_action.as_ref().unwrap().signals().triggered().connect(open_from_slots.last().unwrap()); }  
-        }
-    }
+        // This slot is used for the "Find Non-ASCII Keys" action.
+        find_non_ascii_keys: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                sender_qt.send(Commands::FindNonAsciiKeys).unwrap();
+                sender_qt_data.send(Data::VecString(selected_paths[0].to_vec())).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecNonAsciiKey(keys) => {
+                        if keys.is_empty() { show_dialog(app_ui.window, true, "All the keys of this PackedFile are ASCII.") }
+                        else {
+                            let rows = keys.iter().map(|x| format!(
+                                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                x.row + 1,
+                                x.column,
+                                x.key,
+                                x.positions.iter().map(|x| (x + 1).to_string()).collect::<Vec<String>>().join(", ")
+                            )).collect::<String>();
+                            show_dialog(app_ui.window, false, format!("<p>The following keys contain non-ASCII characters:</p><table><tr><th>Row</th><th>Column</th><th>Key</th><th>Positions</th></tr>{}</table>", rows));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
 
-    // Get the path of every PackFile in the data folder (if it's configured) and make an action for each one of them.
-    if let Some(ref mut paths) = get_game_selected_data_packfiles_paths() {
-        paths.sort_unstable_by_key(|x| x.file_name().unwrap().to_string_lossy().as_ref().to_owned());
-        for path in paths {
+        // This slot is used for the "Split Table by Column" action.
+        split_table_by_column: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
 
-            // That means our file is a valid PackFile and it needs to be added to the menu.
-            let mod_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
 
-            // Create the action for it.
-            let open_mod_action;
-            unsafe { open_mod_action = submenu_open_from_data.as_mut().unwrap().add_action(&QString::from_std_str(mod_name)); }
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::DBTableIsNotADBTable); }
 
-            // Create the slot for that action.
-            let slot_open_mod = SlotBool::new(clone!(
-                mode,
-                mymod_stuff,
-                path,
-                packedfiles_open_in_packedfile_view,
-                close_global_search_action,
-                table_state_data,
-                sender_qt,
-                sender_qt_data,
-                receiver_qt => move |_| {
+                if let Some(values) = create_text_input_dialog(&app_ui, "Split Table by Column", &["Column to split the table by (category)."]) {
+                    if values[0].is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput); }
+                    let keep_original = ask_question(&app_ui, "<p>Do you want to keep the original table?</p>");
 
-                    // Check first if there has been changes in the PackFile.
-                    if are_you_sure(&app_ui, false) {
+                    sender_qt.send(Commands::SplitTableByColumn).unwrap();
+                    sender_qt_data.send(Data::VecStringStringBool((selected_paths[0].to_vec(), values[0].to_owned(), keep_original))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecVecString(paths) => {
+                            if !keep_original {
+                                update_treeview(
+                                    &sender_qt,
+                                    &sender_qt_data,
+                                    &receiver_qt,
+                                    &app_ui,
+                                    app_ui.folder_tree_view,
+                                    Some(app_ui.folder_tree_filter),
+                                    app_ui.folder_tree_model,
+                                    TreeViewOperation::Delete(vec![TreePathType::File(selected_paths[0].to_vec())]),
+                                );
+                            }
 
-                        // Try to open it, and report it case of error.
-                        if let Err(error) = open_packfile(
-                            &sender_qt,
-                            &sender_qt_data,
-                            &receiver_qt,
-                            &[path.to_path_buf()],
-                            &app_ui,
-                            &mymod_stuff,
-                            &mode,
-                            "",
-                            &packedfiles_open_in_packedfile_view,
-                            close_global_search_action,
-                            &table_state_data,
-                        ) { show_dialog(app_ui.window, false, error); }
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Add(paths.iter().map(|x| TreePathType::File(x.to_vec())).collect()),
+                            );
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
                     }
                 }
-            ));
-
-            // Add the slot to the list.
-            open_from_slots.push(slot_open_mod);
-
-            // Connect the action to the slot.
-            unsafe { open_mod_action.as_ref().unwrap().signals().triggered().connect(open_from_slots.last().unwrap()); }  
-        }
-    }
-    
-    // Only if the submenu has items, we enable it.
-    unsafe { submenu_open_from_content.as_mut().unwrap().menu_action().as_mut().unwrap().set_visible(!submenu_open_from_content.as_mut().unwrap().actions().is_empty()); }
-    unsafe { submenu_open_from_data.as_mut().unwrap().menu_action().as_mut().unwrap().set_visible(!submenu_open_from_data.as_mut().unwrap().actions().is_empty()); }
-
-    // Return the slots.
-    open_from_slots
-}
+            }
+        )),
 
-/// This function is the one that takes care of the creation of different PackedFiles.
-pub fn create_packed_files(
-    sender_qt: &Sender<Commands>,
-    sender_qt_data: &Sender<Data>,
-    receiver_qt: &Rc<RefCell<Receiver<Data>>>,
-    table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
-    app_ui: &AppUI,
-    packed_file_type: &PackedFileType,
-) {
-    // Create the "New PackedFile" dialog and wait for his data (or a cancelation). If we receive None, we do nothing. If we receive Some,
-    // we still have to check if it has been any error during the creation of the PackedFile (for example, no definition for DB Tables).
-    if let Some(packed_file_type) = create_new_packed_file_dialog(&app_ui, &sender_qt, &sender_qt_data, &receiver_qt, packed_file_type) {
-        match packed_file_type {
-            Ok(packed_file_type) => {
+        // This slot is used for the "Restore Deleted File" action.
+        restore_deleted_file: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
 
-                // If we reach this place, we got all alright. Now act depending on the type of PackedFile we want to create.
-                match packed_file_type.clone() {
-                    PackedFileType::Loc(ref mut name) | PackedFileType::Text(ref mut name) | PackedFileType::DB(ref mut name, _, _) => {
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
 
-                        // If the name is_empty, stop.
-                        if name.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput) }
+                if let Some(values) = create_text_input_dialog(&app_ui, "Restore Deleted File", &["Path of the deleted file (db/units_tables/my_units)."]) {
+                    let path = values[0].split('/').map(|x| x.to_owned()).filter(|x| !x.is_empty()).collect::<Vec<String>>();
+                    if path.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput); }
 
-                        // Fix their name termination if needed.
-                        if let PackedFileType::Loc(_) = packed_file_type {
-                            if !name.ends_with(".loc") { name.push_str(".loc"); }
+                    // If the path has been reused since the file was deleted, ask before overwriting it.
+                    let mut overwrite = false;
+                    loop {
+                        sender_qt.send(Commands::RestoreDeletedFile).unwrap();
+                        sender_qt_data.send(Data::VecStringBool((path.to_vec(), overwrite))).unwrap();
+                        match check_message_validity_tryrecv(&receiver_qt) {
+                            Data::Success => {
+                                let operation = if overwrite { TreeViewOperation::Modify(vec![TreePathType::File(path.to_vec())]) }
+                                else { TreeViewOperation::Add(vec![TreePathType::File(path.to_vec())]) };
+                                update_treeview(
+                                    &sender_qt,
+                                    &sender_qt_data,
+                                    &receiver_qt,
+                                    &app_ui,
+                                    app_ui.folder_tree_view,
+                                    Some(app_ui.folder_tree_filter),
+                                    app_ui.folder_tree_model,
+                                    operation,
+                                );
+                                break;
+                            }
+                            Data::Error(error) => {
+                                if error.kind() == ErrorKind::FileAlreadyInPackFile && !overwrite &&
+                                    ask_question(&app_ui, "<p>There is already a file in that path.</p><p>Do you want to overwrite it with the deleted one?</p>") {
+                                    overwrite = true;
+                                }
+                                else {
+                                    if error.kind() != ErrorKind::FileAlreadyInPackFile { show_dialog(app_ui.window, false, error); }
+                                    break;
+                                }
+                            }
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Find Unannotated Tables" action.
+        find_unannotated_tables: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::FindUnannotatedTables).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecString(tables) => {
+                        if tables.is_empty() { show_dialog(app_ui.window, true, "All the tables of the schema have reference data."); }
+                        else {
+                            let tables = tables.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<p>The following tables of the schema have no reference data:</p><ul>{}</ul>", tables));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Translation Matrix" action.
+        export_translation_matrix: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::LocPackedFileIsNotALocPackedFile); }
+
+                if let Some(path) = get_save_file_path(&app_ui, "Export Translation Matrix", "TSV Files (*.tsv)", "tsv") {
+                    sender_qt.send(Commands::ExportTranslationMatrix).unwrap();
+                    sender_qt_data.send(Data::VecVecStringPathBuf((selected_paths, path))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Translation matrix exported."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Trim Trailing Nulls from Text Files" action.
+        trim_trailing_nulls: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
+
+                sender_qt.send(Commands::TrimTrailingNulls).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecVecString(paths) => {
+                        if paths.is_empty() { show_dialog(app_ui.window, true, "No Text PackedFile with trailing null bytes has been found."); }
+                        else {
+                            let message = format!("<p>The trailing null bytes have been removed from the following PackedFiles:</p><ul>{}</ul>", paths.iter().map(|x| format!("<li>{}</li>", x.join("/"))).collect::<String>());
+                            let paths = paths.iter().map(|x| TreePathType::File(x.to_vec())).collect::<Vec<TreePathType>>();
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Modify(paths),
+                            );
+                            show_dialog(app_ui.window, true, message);
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Validate PackFile for Game" action.
+        validate_pack_for_game: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(game) = create_game_selection_dialog(&app_ui, "Validate PackFile for Game") {
+                    let game_name = SUPPORTED_GAMES.get(&*game).unwrap().display_name.to_owned();
+                    sender_qt.send(Commands::ValidatePackForGame).unwrap();
+                    sender_qt_data.send(Data::String(game)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecVecStringString(failures) => {
+                            if failures.is_empty() { show_dialog(app_ui.window, true, format!("<p>Everything in this PackFile decodes fine under <i>{}</i>.</p>", game_name)); }
+                            else {
+                                let failures = failures.iter().map(|(path, error)| format!("<li><b>{}</b>: {}</li>", path.join("/"), error)).collect::<String>();
+                                show_dialog(app_ui.window, false, format!("<p>The following PackedFiles fail to decode under <i>{}</i>:</p><ul>{}</ul>", game_name, failures));
+                            }
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Check Composite Uniqueness" action.
+        check_composite_uniqueness: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                if let Some(values) = create_text_input_dialog(&app_ui, "Check Composite Uniqueness", &["Columns that should be unique together, separated by commas (unit, faction)."]) {
+                    let columns = values[0].split(',').map(|x| x.trim().to_owned()).filter(|x| !x.is_empty()).collect::<Vec<String>>();
+                    sender_qt.send(Commands::CheckCompositeUniqueness).unwrap();
+                    sender_qt_data.send(Data::VecStringVecString((selected_paths[0].to_vec(), columns))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecVecUsize(conflicts) => {
+                            if conflicts.is_empty() { show_dialog(app_ui.window, true, "No rows with the same values in these columns have been found."); }
+                            else {
+                                let conflicts = conflicts.iter().map(|x| format!("<li>{}</li>", x.iter().map(|x| (x + 1).to_string()).collect::<Vec<String>>().join(", "))).collect::<String>();
+                                show_dialog(app_ui.window, false, format!("<p>The following groups of rows have the same values in these columns:</p><ul>{}</ul>", conflicts));
+                            }
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Append Row Count Snapshot" action.
+        append_row_count_snapshot: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+
+                // We don't use the usual save dialog, as the log is appended to, not overwritten.
+                let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Append Row Count Snapshot"),
+                )) };
+                file_dialog.set_accept_mode(qt_widgets::file_dialog::AcceptMode::Save);
+                file_dialog.set_confirm_overwrite(false);
+                file_dialog.set_name_filter(&QString::from_std_str("Log Files (*.log)"));
+                file_dialog.set_default_suffix(&QString::from_std_str("log"));
+
+                if file_dialog.exec() == 1 {
+                    let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+                    sender_qt.send(Commands::AppendRowCountSnapshot).unwrap();
+                    sender_qt_data.send(Data::PathBuf(path)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Row count snapshot appended to the log."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Find Redundant Overrides" action.
+        find_redundant_overrides: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::FindRedundantOverrides).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecVecString(paths) => {
+                        if paths.is_empty() { show_dialog(app_ui.window, true, "No PackedFile identical to its vanilla version has been found."); }
+                        else {
+                            let paths = paths.iter().map(|x| format!("<li>{}</li>", x.join("/"))).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<p>The following PackedFiles are identical to their vanilla version, so they can be removed:</p><ul>{}</ul>", paths));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Suggest Unique Key" action.
+        suggest_unique_key: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                if let Some(values) = create_text_input_dialog(&app_ui, "Suggest Unique Key", &["Base for the new key (my_unit)."]) {
+                    sender_qt.send(Commands::SuggestUniqueKey).unwrap();
+                    sender_qt_data.send(Data::VecStringString((selected_paths[0].to_vec(), values[0].trim().to_owned()))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::String(key) => {
+                            unsafe { GuiApplication::clipboard().as_mut().unwrap().set_text(&QString::from_std_str(&key)); }
+                            show_dialog(app_ui.window, true, format!("<p>Suggested key: <b>{}</b></p><p>It has been copied to the clipboard.</p>", key));
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Vanilla/Mod Comparison" action.
+        export_vanilla_mod_comparison: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                if let Some(out) = get_save_file_path(&app_ui, "Export Vanilla/Mod Comparison", "TSV Files (*.tsv)", "tsv") {
+                    sender_qt.send(Commands::ExportVanillaModComparisonTsv).unwrap();
+                    sender_qt_data.send(Data::VecStringPathBuf((selected_paths[0].to_vec(), out))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Vanilla/Mod comparison exported."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Find Unreferenced Assets" action.
+        find_unreferenced_assets: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::FindUnreferencedAssets).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecVecString(paths) => {
+                        if paths.is_empty() { show_dialog(app_ui.window, true, "All the assets of the PackFile are referenced by at least one table."); }
+                        else {
+                            let paths = paths.iter().map(|x| format!("<li>{}</li>", x.join("/"))).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<p>The following assets are not referenced by any table. Check them before removing them, as they may be used by something else:</p><ul>{}</ul>", paths));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Run Edit Script" action.
+        run_edit_script: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
+
+                let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select Edit Script..."),
+                )) };
+                file_dialog.set_name_filter(&QString::from_std_str("JSON Files (*.json)"));
+
+                if file_dialog.exec() == 1 {
+                    let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+                    let operations: Vec<EditOperation> = match File::open(&path).map_err(Error::from).and_then(|file| serde_json::from_reader(BufReader::new(file)).map_err(Error::from)) {
+                        Ok(operations) => operations,
+                        Err(error) => return show_dialog(app_ui.window, false, error),
+                    };
+
+                    // Get the tables the script touches, so we can mark them as modified if it works.
+                    let mut paths: Vec<Vec<String>> = vec![];
+                    for operation in &operations {
+                        let path = match operation {
+                            EditOperation::EditCell { path, .. } |
+                            EditOperation::AddRow { path } |
+                            EditOperation::DeleteRow { path, .. } => path,
+                        };
+                        if !paths.contains(path) { paths.push(path.to_vec()); }
+                    }
+                    let paths = paths.iter().map(|x| TreePathType::File(x.to_vec())).collect::<Vec<TreePathType>>();
+
+                    sender_qt.send(Commands::RunEditScript).unwrap();
+                    sender_qt_data.send(Data::VecEditOperation(operations)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecString(report) => {
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Modify(paths),
+                            );
+                            let report = report.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<p>Edit script applied:</p><ul>{}</ul>", report));
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Size by Table Type" action.
+        size_by_table_type: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::SizeByTableType).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::BTreeMapStringU64(sizes) => {
+                        if sizes.is_empty() { show_dialog(app_ui.window, true, "There are no tables in this PackFile."); }
+                        else {
+
+                            // Biggest ones first, as these are the ones worth optimizing.
+                            let total: u64 = sizes.values().sum();
+                            let mut sizes = sizes.into_iter().collect::<Vec<(String, u64)>>();
+                            sizes.sort_by(|x, y| y.1.cmp(&x.1));
+                            let sizes = sizes.iter().map(|(table_type, size)| format!("<tr><td>{}</td><td>{} bytes</td></tr>", table_type, size)).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<p>Size of the tables by type (total: {} bytes):</p><table>{}</table>", total, sizes));
+                        }
+                    }
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Lock Schema" action.
+        lock_schema: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data => move |state| {
+                sender_qt.send(Commands::SetSchemaLocked).unwrap();
+                sender_qt_data.send(Data::Bool(state)).unwrap();
+            }
+        )),
+
+        // This slot is used for the "Find Oversized Cells" action.
+        find_oversized_cells: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(values) = create_text_input_dialog(&app_ui, "Find Oversized Cells", &["Maximum amount of characters of a cell (250)."]) {
+                    let threshold = match values[0].trim().parse::<usize>() {
+                        Ok(threshold) => threshold,
+                        Err(_) => return show_dialog(app_ui.window, false, ErrorKind::InvalidNumber(values[0].to_owned())),
+                    };
+
+                    sender_qt.send(Commands::FindOversizedCells).unwrap();
+                    sender_qt_data.send(Data::Usize(threshold)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecOversizedCell(cells) => {
+                            if cells.is_empty() { show_dialog(app_ui.window, true, "No cell longer than that has been found.") }
+                            else {
+                                let rows = cells.iter().map(|x| format!(
+                                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                    x.path.join("/"),
+                                    x.row + 1,
+                                    x.column,
+                                    x.length
+                                )).collect::<String>();
+                                show_dialog(app_ui.window, false, format!("<p>The following cells are longer than expected:</p><table><tr><th>PackedFile</th><th>Row</th><th>Column</th><th>Length</th></tr>{}</table>", rows));
+                            }
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Tree as HTML" action.
+        export_tree_as_html: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(out) = get_save_file_path(&app_ui, "Export Tree as HTML", "HTML Files (*.html)", "html") {
+                    sender_qt.send(Commands::ExportTreeAsHtml).unwrap();
+                    sender_qt_data.send(Data::PathBuf(out)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Tree exported."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Verify Loc Column Order" action.
+        verify_loc_column_order: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::LocPackedFileIsNotALocPackedFile); }
+
+                sender_qt.send(Commands::VerifyLocColumnOrder).unwrap();
+                sender_qt_data.send(Data::VecString(selected_paths[0].to_vec())).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecString(mismatches) => {
+                        if mismatches.is_empty() { show_dialog(app_ui.window, true, "The columns of this Loc PackedFile are in the right order."); }
+                        else {
+                            let mismatches = mismatches.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
+                            show_dialog(app_ui.window, false, format!("<p>The columns of this Loc PackedFile don't follow the Loc layout:</p><ul>{}</ul>", mismatches));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Session Edit Stats" action.
+        session_edit_stats: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::GetSessionEditStats).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::SessionEditStats(stats) => show_dialog(app_ui.window, true, format!(
+                        "<p>Edits done since the PackFile was opened:</p><table>
+                            <tr><td>Rows added:</td><td>{}</td></tr>
+                            <tr><td>Rows removed:</td><td>{}</td></tr>
+                            <tr><td>Rows edited:</td><td>{}</td></tr>
+                            <tr><td>Files added:</td><td>{}</td></tr>
+                            <tr><td>Files deleted:</td><td>{}</td></tr>
+                            <tr><td>Files renamed:</td><td>{}</td></tr>
+                        </table>",
+                        stats.rows_added,
+                        stats.rows_removed,
+                        stats.rows_edited,
+                        stats.files_added,
+                        stats.files_deleted,
+                        stats.files_renamed,
+                    )),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Find Case Collisions" action.
+        find_case_collisions: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::FindCaseCollisions).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecVecVecString(collisions) => {
+                        if collisions.is_empty() { show_dialog(app_ui.window, true, "No PackedFiles with paths differing only by case have been found."); }
+                        else {
+                            let collisions = collisions.iter().map(|x| format!("<li>{}</li>", x.iter().map(|x| x.join("/")).collect::<Vec<String>>().join(", "))).collect::<String>();
+                            show_dialog(app_ui.window, false, format!("<p>The following PackedFiles have paths differing only by case, so they will overwrite each other when extracted on case-insensitive filesystems:</p><ul>{}</ul>", collisions));
+                        }
+                    }
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Optimization Report" action.
+        export_optimization_report: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(out) = get_save_file_path(&app_ui, "Export Optimization Report", "TSV Files (*.tsv)", "tsv") {
+                    sender_qt.send(Commands::OptimizationReport).unwrap();
+                    sender_qt_data.send(Data::PathBuf(out)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Optimization report exported."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Replace in Column" action.
+        replace_in_column: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
+
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                if let Some(values) = create_text_input_dialog(&app_ui, "Replace in Column", &["Column (unit_class).", "Value to replace (inf_mel).", "New value (inf_spr)."]) {
+                    sender_qt.send(Commands::ReplaceInColumn).unwrap();
+                    sender_qt_data.send(Data::VecStringStringStringString((selected_paths[0].to_vec(), values[0].trim().to_owned(), values[1].to_owned(), values[2].to_owned()))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Usize(replaced) => {
+                            if replaced > 0 {
+                                update_treeview(
+                                    &sender_qt,
+                                    &sender_qt_data,
+                                    &receiver_qt,
+                                    &app_ui,
+                                    app_ui.folder_tree_view,
+                                    Some(app_ui.folder_tree_filter),
+                                    app_ui.folder_tree_model,
+                                    TreeViewOperation::Modify(vec![TreePathType::File(selected_paths[0].to_vec())]),
+                                );
+                            }
+                            show_dialog(app_ui.window, true, format!("{} cells replaced.", replaced));
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Validate Key Column" action.
+        validate_key_column: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                sender_qt.send(Commands::ValidateKeyColumn).unwrap();
+                sender_qt_data.send(Data::VecString(selected_paths[0].to_vec())).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecString(violations) => {
+                        if violations.is_empty() { show_dialog(app_ui.window, true, "The keys of this table are unique and not empty."); }
+                        else {
+                            let violations = violations.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
+                            show_dialog(app_ui.window, false, format!("<p>The key of this table is not valid:</p><ul>{}</ul>", violations));
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Loc Matches" action.
+        export_loc_matches: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(values) = create_text_input_dialog(&app_ui, "Export Loc Matches", &["Text to search in the Loc PackedFiles (Karl Franz)."]) {
+                    let case_sensitive = ask_question(&app_ui, "<p>Should the search be case sensitive?</p>");
+                    if let Some(out) = get_save_file_path(&app_ui, "Export Loc Matches", "TSV Files (*.tsv)", "tsv") {
+                        sender_qt.send(Commands::ExportLocMatches).unwrap();
+                        sender_qt_data.send(Data::StringBoolPathBuf((values[0].to_owned(), case_sensitive, out))).unwrap();
+                        match check_message_validity_tryrecv(&receiver_qt) {
+                            Data::Success => show_dialog(app_ui.window, true, "Loc matches exported."),
+                            Data::Error(error) => show_dialog(app_ui.window, false, error),
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Merge Identical Adjacent Rows" action.
+        merge_identical_adjacent_rows: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
+
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                sender_qt.send(Commands::MergeIdenticalAdjacentRows).unwrap();
+                sender_qt_data.send(Data::VecString(selected_paths[0].to_vec())).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::Usize(removed) => {
+                        if removed > 0 {
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Modify(vec![TreePathType::File(selected_paths[0].to_vec())]),
+                            );
+                        }
+                        show_dialog(app_ui.window, true, format!("{} repeated rows removed.", removed));
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Table Checksums" action.
+        export_table_checksums: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(out) = get_save_file_path(&app_ui, "Export Table Checksums", "TSV Files (*.tsv)", "tsv") {
+                    sender_qt.send(Commands::ExportTableChecksums).unwrap();
+                    sender_qt_data.send(Data::PathBuf(out)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Table checksums exported."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Command Log" action.
+        export_command_log: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                if let Some(out) = get_save_file_path(&app_ui, "Export Command Log", "JSON Files (*.json)", "json") {
+                    sender_qt.send(Commands::ExportCommandLog).unwrap();
+                    sender_qt_data.send(Data::PathBuf(out)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Command log exported."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Replay Command Log" action.
+        replay_command_log: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
+
+                let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select Command Log..."),
+                )) };
+                file_dialog.set_name_filter(&QString::from_std_str("JSON Files (*.json)"));
+
+                if file_dialog.exec() == 1 {
+                    let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+                    sender_qt.send(Commands::ReplayCommandLog).unwrap();
+                    sender_qt_data.send(Data::PathBuf(path)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecString(report) => {
+
+                            // The log can touch anything in the PackFile, so we rebuild the TreeView from it.
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Build(false),
+                            );
+                            show_dialog(app_ui.window, true, format!("<p>Command log replayed:</p><ul>{}</ul>", report.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>()));
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Export to SQLite" action.
+        export_to_sqlite: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let item_types = get_item_types_from_main_treeview_selection(&app_ui).iter().map(From::from).collect::<Vec<PathType>>();
+                if item_types.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable); }
+
+                if let Some(out) = get_save_file_path(&app_ui, "Export to SQLite", "SQLite Databases (*.sqlite *.db)", "sqlite") {
+                    unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                    sender_qt.send(Commands::ExportToSQLite).unwrap();
+                    sender_qt_data.send(Data::VecPathTypePathBuf((item_types, out))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::UsizeUsize((tables, rows)) => show_dialog(app_ui.window, true, format!("{} tables with {} rows exported to SQLite.", tables, rows)),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                    unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                }
+            }
+        )),
+
+        // This slot is used for the "Import from SQLite" action.
+        import_from_sqlite: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
+
+                let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select SQLite Database..."),
+                )) };
+                file_dialog.set_name_filter(&QString::from_std_str("SQLite Databases (*.sqlite *.db)"));
+
+                if file_dialog.exec() == 1 {
+                    let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+                    unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                    sender_qt.send(Commands::ImportFromSQLite).unwrap();
+                    sender_qt_data.send(Data::PathBuf(path)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecVecStringVecVecStringVecString((added_paths, modified_paths, errors)) => {
+                            for (paths, added) in &[(added_paths.to_vec(), true), (modified_paths.to_vec(), false)] {
+                                if !paths.is_empty() {
+                                    let paths = paths.iter().map(|x| TreePathType::File(x.to_vec())).collect::<Vec<TreePathType>>();
+                                    update_treeview(
+                                        &sender_qt,
+                                        &sender_qt_data,
+                                        &receiver_qt,
+                                        &app_ui,
+                                        app_ui.folder_tree_view,
+                                        Some(app_ui.folder_tree_filter),
+                                        app_ui.folder_tree_model,
+                                        if *added { TreeViewOperation::Add(paths) } else { TreeViewOperation::Modify(paths) },
+                                    );
+                                }
+                            }
+
+                            let message = format!("<p>{} tables imported from SQLite.</p>", added_paths.len() + modified_paths.len());
+                            if errors.is_empty() { show_dialog(app_ui.window, true, message); }
+                            else { show_dialog(app_ui.window, false, format!("{}<p>The following tables couldn't be imported:</p><ul>{}</ul>", message, errors.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>())); }
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                    unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                }
+            }
+        )),
+
+        // This slot is used for the "Find Missing Loc Keys" action.
+        find_missing_loc_keys: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+                sender_qt.send(Commands::FindMissingLocKeys).unwrap();
+                let missing_keys = match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecMissingLocKey(missing_keys) => missing_keys,
+                    Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                };
+
+                if missing_keys.is_empty() { return show_dialog(app_ui.window, true, "No missing loc key has been found."); }
+                let text = format!("{} loc keys are missing:", missing_keys.len());
+                let headers = vec!["Table".to_owned(), "Row Key".to_owned(), "Missing Loc Key".to_owned()];
+                let rows = missing_keys.iter().map(|x| vec![x.path.join("/"), x.row_key.to_owned(), x.loc_key.to_owned()]).collect::<Vec<Vec<String>>>();
+                create_results_dialog(&app_ui, "Missing Loc Keys", &text, &headers, &rows);
+
+                // If the user wants, add blank entries for the missing keys to the Loc PackedFile of his choice.
+                if ask_question(&app_ui, &format!("Do you want to add blank entries for these {} loc keys to a Loc PackedFile?", missing_keys.len())) {
+
+                    // This cannot be done if there is a PackedFile open, as we may overwrite the changes when closing it.
+                    if !packedfiles_open_in_packedfile_view.borrow().is_empty() { return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen); }
+
+                    // The Loc PackedFile is picked from the ones we have in the TreeView.
+                    sender_qt.send(Commands::GetPackFileDataForTreeView).unwrap();
+                    let loc_paths = match check_message_validity_recv2(&receiver_qt) {
+                        Data::StringI64VecVecString(data) => data.2.into_iter().filter(|x| !x.is_empty() && x.last().unwrap().ends_with(".loc")).collect::<Vec<Vec<String>>>(),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    };
+
+                    if loc_paths.is_empty() { return show_dialog(app_ui.window, false, "There are no Loc PackedFiles in this PackFile. Create one first and try again."); }
+                    let items = loc_paths.iter().map(|x| x.join("/")).collect::<Vec<String>>();
+                    if let Some(index) = create_item_selection_dialog(&app_ui, "Add Blank Loc Entries", "Loc PackedFile to add the blank entries to:", &items) {
+                        let path = loc_paths[index].to_vec();
+                        let loc_keys = missing_keys.iter().map(|x| x.loc_key.to_owned()).collect::<Vec<String>>();
+                        sender_qt.send(Commands::AddLocStubs).unwrap();
+                        sender_qt_data.send(Data::VecStringVecString((path.to_vec(), loc_keys))).unwrap();
+                        match check_message_validity_tryrecv(&receiver_qt) {
+                            Data::UsizeBool((added, created)) => {
+                                update_treeview(
+                                    &sender_qt,
+                                    &sender_qt_data,
+                                    &receiver_qt,
+                                    &app_ui,
+                                    app_ui.folder_tree_view,
+                                    Some(app_ui.folder_tree_filter),
+                                    app_ui.folder_tree_model,
+                                    if created { TreeViewOperation::Add(vec![TreePathType::File(path.to_vec())]) } else { TreeViewOperation::Modify(vec![TreePathType::File(path.to_vec())]) },
+                                );
+                                show_dialog(app_ui.window, true, format!("{} blank entries added to {}.", added, path.join("/")));
+                            }
+                            Data::Error(error) => show_dialog(app_ui.window, false, error),
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Find Duplicated Loc Keys" action.
+        find_duplicated_loc_keys: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+                sender_qt.send(Commands::FindDuplicatedLocKeys).unwrap();
+                let duplicated_keys = match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecDuplicatedLocKey(duplicated_keys) => duplicated_keys,
+                    Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                };
+
+                if duplicated_keys.is_empty() { return show_dialog(app_ui.window, true, "No duplicated loc key has been found."); }
+                let text = format!("{} loc keys are duplicated:", duplicated_keys.len());
+                let headers = vec!["Key".to_owned(), "Loc PackedFile".to_owned(), "Text".to_owned()];
+                let rows = duplicated_keys.iter().flat_map(|x| x.entries.iter().map(move |(path, text)| vec![x.key.to_owned(), path.join("/"), text.to_owned()])).collect::<Vec<Vec<String>>>();
+                create_results_dialog(&app_ui, "Duplicated Loc Keys", &text, &headers, &rows);
+
+                if ask_question(&app_ui, "Do you want to pick the entry to keep for each one of these keys? The rest of them will be removed.") {
+
+                    // This cannot be done if any of the affected Loc PackedFiles is open, as we may overwrite the changes when closing it.
+                    let affected_paths = duplicated_keys.iter().flat_map(|x| x.entries.iter().map(|(path, _)| path.to_vec())).collect::<BTreeSet<Vec<String>>>();
+                    if packedfiles_open_in_packedfile_view.borrow().values().any(|x| affected_paths.contains(&*x.borrow())) {
+                        return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen);
+                    }
+
+                    // Ask for the winner of each key. If any of the dialogs gets closed, we stop without changing anything.
+                    let mut resolutions = vec![];
+                    for duplicated_key in &duplicated_keys {
+                        let items = duplicated_key.entries.iter().map(|(path, text)| format!("{}: {}", path.join("/"), text)).collect::<Vec<String>>();
+                        match create_item_selection_dialog(&app_ui, "Resolve Duplicated Loc Key", &format!("Entry to keep for the key \"{}\":", duplicated_key.key), &items) {
+                            Some(index) => resolutions.push((duplicated_key.key.to_owned(), index)),
+                            None => return,
+                        }
+                    }
+
+                    sender_qt.send(Commands::ResolveDuplicatedLocKeys).unwrap();
+                    sender_qt_data.send(Data::VecStringUsize(resolutions)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecVecString(paths) => {
+                            let message = format!("<p>The duplicated entries have been removed from the following Loc PackedFiles:</p><ul>{}</ul>", paths.iter().map(|x| format!("<li>{}</li>", x.join("/"))).collect::<String>());
+                            let paths = paths.iter().map(|x| TreePathType::File(x.to_vec())).collect::<Vec<TreePathType>>();
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Modify(paths),
+                            );
+                            show_dialog(app_ui.window, true, message);
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Export Bilingual TSV" action.
+        export_bilingual_tsv: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::LocPackedFileIsNotALocPackedFile); }
+
+                let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select PackFile with the Target Texts..."),
+                )) };
+                file_dialog.set_name_filter(&QString::from_std_str("PackFiles (*.pack)"));
+                if file_dialog.exec() != 1 { return }
+                let target_pack_file_path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+
+                if let Some(path) = get_save_file_path(&app_ui, "Export Bilingual TSV", "TSV Files (*.tsv)", "tsv") {
+                    unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                    sender_qt.send(Commands::ExportBilingualTSV).unwrap();
+                    sender_qt_data.send(Data::VecVecStringPathBufPathBuf((selected_paths, target_pack_file_path, path))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecString(missing_keys) => {
+                            if missing_keys.is_empty() { show_dialog(app_ui.window, true, "Bilingual TSV exported."); }
+                            else { show_dialog(app_ui.window, true, format!("<p>Bilingual TSV exported. The following keys are missing in the target PackFile, so their target text is blank:</p><ul>{}</ul>", missing_keys.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>())); }
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                    unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                }
+            }
+        )),
+
+        // This slot is used for the "Import Bilingual TSV" action.
+        import_bilingual_tsv: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+                let selected_paths = get_path_from_main_treeview_selection(&app_ui);
+                if selected_paths.len() != 1 { return show_dialog(app_ui.window, false, ErrorKind::LocPackedFileIsNotALocPackedFile); }
+                let loc_path = selected_paths[0].to_vec();
+
+                // This cannot be done if the Loc PackedFile is open, as we may overwrite the changes when closing it.
+                if packedfiles_open_in_packedfile_view.borrow().values().any(|x| *x.borrow() == loc_path) {
+                    return show_dialog(app_ui.window, false, ErrorKind::OperationNotAllowedWithPackedFileOpen);
+                }
+
+                let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select Bilingual TSV File..."),
+                )) };
+                file_dialog.set_name_filter(&QString::from_std_str("TSV Files (*.tsv)"));
+                if file_dialog.exec() == 1 {
+                    let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+                    sender_qt.send(Commands::ImportBilingualTSV).unwrap();
+                    sender_qt_data.send(Data::VecStringPathBuf((loc_path.to_vec(), path))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::UsizeVecString((updated, added_keys)) => {
+                            if updated > 0 || !added_keys.is_empty() {
+                                update_treeview(
+                                    &sender_qt,
+                                    &sender_qt_data,
+                                    &receiver_qt,
+                                    &app_ui,
+                                    app_ui.folder_tree_view,
+                                    Some(app_ui.folder_tree_filter),
+                                    app_ui.folder_tree_model,
+                                    TreeViewOperation::Modify(vec![TreePathType::File(loc_path.to_vec())]),
+                                );
+                            }
+
+                            let message = format!("<p>{} entries updated.</p>", updated);
+                            if added_keys.is_empty() { show_dialog(app_ui.window, true, message); }
+                            else { show_dialog(app_ui.window, true, format!("{}<p>The following keys were missing in the Loc PackedFile, so they have been added to it:</p><ul>{}</ul>", message, added_keys.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>())); }
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // The results are shown in the "Global Search" view, so we can jump to each one of them from there.
+        find_untranslated_loc_entries: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data => move |_| {
+                if let Some(ignored_patterns) = create_untranslated_loc_entries_dialog(&app_ui) {
+                    sender_qt.send(Commands::FindUntranslatedLocEntries).unwrap();
+                    sender_qt_data.send(Data::VecString(ignored_patterns)).unwrap();
+                    unsafe { app_ui.global_search_show_untranslated.as_mut().unwrap().trigger(); }
+                }
+            }
+        )),
+
+        // This slot is used for the "Rename Key Prefix" action. The changes are previewed first, and only applied if the user accepts them.
+        rename_key_prefix: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+                if let Some(values) = create_text_input_dialog(&app_ui, "Rename Key Prefix", &["Old prefix (mymod_)", "New prefix (wh2_mymod_)"]) {
+                    let old_prefix = values[0].to_owned();
+                    let new_prefix = values[1].to_owned();
+                    if old_prefix.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput); }
+                    let rename_db_cells = ask_question(&app_ui, "Do you also want to rename the keys of the DB Tables with localisable fields, and the cells referencing them?");
+
+                    sender_qt.send(Commands::RenameKeyPrefix).unwrap();
+                    sender_qt_data.send(Data::StringStringBoolBool((old_prefix.to_owned(), new_prefix.to_owned(), rename_db_cells, false))).unwrap();
+                    let changes = match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecKeyPrefixChange(changes) => changes,
+                        Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    };
+
+                    if changes.is_empty() { return show_dialog(app_ui.window, true, "No key starts with that prefix."); }
+
+                    let text = format!("Do you want to apply these {} changes?", changes.len());
+                    let headers = vec!["PackedFile".to_owned(), "Row".to_owned(), "Column".to_owned(), "Old Value".to_owned(), "New Value".to_owned()];
+                    let rows = changes.iter().map(|x| vec![x.path.join("/"), (x.row + 1).to_string(), x.column.to_owned(), x.old_value.to_owned(), x.new_value.to_owned()]).collect::<Vec<Vec<String>>>();
+                    if !ask_results_question(&app_ui, "Rename Key Prefix", &text, &headers, &rows, "Apply") { return }
+
+                    sender_qt.send(Commands::RenameKeyPrefix).unwrap();
+                    sender_qt_data.send(Data::StringStringBoolBool((old_prefix, new_prefix, rename_db_cells, true))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::VecKeyPrefixChange(changes) => {
+                            let paths = changes.iter().map(|x| x.path.to_vec()).collect::<BTreeSet<Vec<String>>>();
+                            let message = format!("<p>{} keys renamed in the following PackedFiles:</p><ul>{}</ul>", changes.len(), paths.iter().map(|x| format!("<li>{}</li>", x.join("/"))).collect::<String>());
+                            update_treeview(
+                                &sender_qt,
+                                &sender_qt_data,
+                                &receiver_qt,
+                                &app_ui,
+                                app_ui.folder_tree_view,
+                                Some(app_ui.folder_tree_filter),
+                                app_ui.folder_tree_model,
+                                TreeViewOperation::Modify(paths.iter().map(|x| TreePathType::File(x.to_vec())).collect()),
+                            );
+
+                            // Reload the open views, so the changed PackedFiles show their new keys.
+                            let open_packedfiles = packedfiles_open_in_packedfile_view.borrow().iter().map(|(view, path)| (*view, path.borrow().to_vec())).collect::<BTreeMap<i32, Vec<String>>>();
+                            if open_packedfiles.values().any(|x| paths.contains(x)) {
+                                reopen_packedfiles(&app_ui, &open_packedfiles, &packedfiles_open_in_packedfile_view);
+                            }
+                            show_dialog(app_ui.window, true, message);
+                        }
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Diff Table with PackFile" action.
+        diff_table_with_packfile: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt,
+            packedfiles_open_in_packedfile_view => move |_| {
+
+                // Any of the open PackedFiles can be compared. If there is more than one, we ask which one.
+                let mut open_paths = packedfiles_open_in_packedfile_view.borrow().values().map(|x| x.borrow().to_vec()).filter(|x| !x.is_empty()).collect::<Vec<Vec<String>>>();
+                open_paths.dedup();
+                let path = match open_paths.len() {
+                    0 => return show_dialog(app_ui.window, false, ErrorKind::PackedFileIsNotATable),
+                    1 => open_paths.remove(0),
+                    _ => {
+                        let items = open_paths.iter().map(|x| x.join("/")).collect::<Vec<String>>();
+                        match create_item_selection_dialog(&app_ui, "Diff Table with PackFile", "Select the open table to compare:", &items) {
+                            Some(index) => open_paths.remove(index),
+                            None => return,
+                        }
+                    }
+                };
+
+                // The other PackFile is opened like the one of "Add from PackFile", and we pick the table to compare from its TreeView.
+                let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                    app_ui.window as *mut Widget,
+                    &QString::from_std_str("Select PackFile to Compare With..."),
+                )) };
+                file_dialog.set_name_filter(&QString::from_std_str("PackFiles (*.pack)"));
+                if file_dialog.exec() != 1 { return }
+                let pack_file_path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+
+                unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                sender_qt.send(Commands::OpenPackFileExtra).unwrap();
+                sender_qt_data.send(Data::PathBuf(pack_file_path)).unwrap();
+                let response = check_message_validity_tryrecv(&receiver_qt);
+                unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                match response {
+                    Data::Success => {},
+                    Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+
+                // Once we have the table to compare with, we don't need the other PackFile anymore.
+                let other_path = match create_packed_file_extra_selection_dialog(&sender_qt, &sender_qt_data, &receiver_qt, &app_ui, "Select Table to Compare With...", &path) {
+                    Some(other_path) => other_path,
+                    None => return sender_qt.send(Commands::ResetPackFileExtra).unwrap(),
+                };
+
+                unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                sender_qt.send(Commands::DiffTableWithPackFile).unwrap();
+                sender_qt_data.send(Data::VecStringVecString((path.to_vec(), other_path))).unwrap();
+                let response = check_message_validity_tryrecv(&receiver_qt);
+                sender_qt.send(Commands::ResetPackFileExtra).unwrap();
+                unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                match response {
+                    Data::TableDiff(diff) => {
+                        if diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty() { show_dialog(app_ui.window, true, "Both tables have the same rows."); }
+                        else { create_table_diff_dialog(&app_ui, &path, &diff); }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+
+        // This slot is used for the "Schema Patches" action.
+        schema_patches: SlotBool::new(clone!(
+            sender_qt,
+            sender_qt_data,
+            receiver_qt => move |_| {
+                sender_qt.send(Commands::GetSchemaPatches).unwrap();
+                let patches = match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::VecString(patches) => patches,
+                    Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                };
+
+                if patches.is_empty() { return show_dialog(app_ui.window, true, "There are no local patches for the schema of the game selected."); }
+                if let Some(index) = create_item_selection_dialog(&app_ui, "Schema Patches", "These patches are applied, in order, on top of the schema of the game selected. Select one to remove it:", &patches) {
+                    if !ask_question(&app_ui, &format!("Are you sure you want to remove the patch \"{}\"?", patches[index])) { return }
+
+                    sender_qt.send(Commands::RemoveSchemaPatch).unwrap();
+                    sender_qt_data.send(Data::Usize(index)).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => show_dialog(app_ui.window, true, "Patch removed. Tables already open keep using the old definitions until they're reopened."),
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            }
+        )),
+
+        // This slot is used for the "Import Assembly Kit Definitions" action.
+        import_assembly_kit_definitions: SlotBool::new(clone!(
+            sender_qt,
+            receiver_qt => move |_| {
+                unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                sender_qt.send(Commands::ImportAssemblyKitDefinitions).unwrap();
+                let response = check_message_validity_tryrecv(&receiver_qt);
+                unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                match response {
+                    Data::UsizeVecString((definitions_found, added)) => {
+                        if added.is_empty() { show_dialog(app_ui.window, true, format!("{} definitions found in the Assembly Kit, but none of them was for a version of a table the schema doesn't have.", definitions_found)); }
+                        else {
+                            let text = format!("{} definitions found in the Assembly Kit. {} definitions added to the schema:", definitions_found, added.len());
+                            let rows = added.into_iter().map(|x| vec![x]).collect::<Vec<Vec<String>>>();
+                            create_results_dialog(&app_ui, "Import Assembly Kit Definitions", &text, &["Definition".to_owned()], &rows);
+                        }
+                    }
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
+            }
+        )),
+    };
+
+    // Connect the actions to their slots.
+    unsafe { tools_stuff.strip_boms_from_text.as_ref().unwrap().signals().triggered().connect(&tools_slots.strip_boms_from_text); }
+    unsafe { tools_stuff.count_localisable_characters.as_ref().unwrap().signals().triggered().connect(&tools_slots.count_localisable_characters); }
+    unsafe { tools_stuff.dry_run_mode.as_ref().unwrap().signals().triggered().connect(&tools_slots.dry_run_mode); }
+    unsafe { tools_stuff.find_reference_cycles.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_reference_cycles); }
+    unsafe { tools_stuff.loc_coverage_report.as_ref().unwrap().signals().triggered().connect(&tools_slots.loc_coverage_report); }
+    unsafe { tools_stuff.set_table_alias.as_ref().unwrap().signals().triggered().connect(&tools_slots.set_table_alias); }
+    unsafe { tools_stuff.export_column_enums.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_column_enums); }
+    unsafe { tools_stuff.find_non_ascii_keys.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_non_ascii_keys); }
+    unsafe { tools_stuff.split_table_by_column.as_ref().unwrap().signals().triggered().connect(&tools_slots.split_table_by_column); }
+    unsafe { tools_stuff.restore_deleted_file.as_ref().unwrap().signals().triggered().connect(&tools_slots.restore_deleted_file); }
+    unsafe { tools_stuff.find_unannotated_tables.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_unannotated_tables); }
+    unsafe { tools_stuff.export_translation_matrix.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_translation_matrix); }
+    unsafe { tools_stuff.trim_trailing_nulls.as_ref().unwrap().signals().triggered().connect(&tools_slots.trim_trailing_nulls); }
+    unsafe { tools_stuff.validate_pack_for_game.as_ref().unwrap().signals().triggered().connect(&tools_slots.validate_pack_for_game); }
+    unsafe { tools_stuff.check_composite_uniqueness.as_ref().unwrap().signals().triggered().connect(&tools_slots.check_composite_uniqueness); }
+    unsafe { tools_stuff.append_row_count_snapshot.as_ref().unwrap().signals().triggered().connect(&tools_slots.append_row_count_snapshot); }
+    unsafe { tools_stuff.find_redundant_overrides.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_redundant_overrides); }
+    unsafe { tools_stuff.suggest_unique_key.as_ref().unwrap().signals().triggered().connect(&tools_slots.suggest_unique_key); }
+    unsafe { tools_stuff.export_vanilla_mod_comparison.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_vanilla_mod_comparison); }
+    unsafe { tools_stuff.find_unreferenced_assets.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_unreferenced_assets); }
+    unsafe { tools_stuff.run_edit_script.as_ref().unwrap().signals().triggered().connect(&tools_slots.run_edit_script); }
+    unsafe { tools_stuff.size_by_table_type.as_ref().unwrap().signals().triggered().connect(&tools_slots.size_by_table_type); }
+    unsafe { tools_stuff.lock_schema.as_ref().unwrap().signals().triggered().connect(&tools_slots.lock_schema); }
+    unsafe { tools_stuff.find_oversized_cells.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_oversized_cells); }
+    unsafe { tools_stuff.export_tree_as_html.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_tree_as_html); }
+    unsafe { tools_stuff.verify_loc_column_order.as_ref().unwrap().signals().triggered().connect(&tools_slots.verify_loc_column_order); }
+    unsafe { tools_stuff.session_edit_stats.as_ref().unwrap().signals().triggered().connect(&tools_slots.session_edit_stats); }
+    unsafe { tools_stuff.find_case_collisions.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_case_collisions); }
+    unsafe { tools_stuff.export_optimization_report.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_optimization_report); }
+    unsafe { tools_stuff.replace_in_column.as_ref().unwrap().signals().triggered().connect(&tools_slots.replace_in_column); }
+    unsafe { tools_stuff.validate_key_column.as_ref().unwrap().signals().triggered().connect(&tools_slots.validate_key_column); }
+    unsafe { tools_stuff.export_loc_matches.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_loc_matches); }
+    unsafe { tools_stuff.merge_identical_adjacent_rows.as_ref().unwrap().signals().triggered().connect(&tools_slots.merge_identical_adjacent_rows); }
+    unsafe { tools_stuff.export_table_checksums.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_table_checksums); }
+    unsafe { tools_stuff.export_command_log.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_command_log); }
+    unsafe { tools_stuff.replay_command_log.as_ref().unwrap().signals().triggered().connect(&tools_slots.replay_command_log); }
+    unsafe { tools_stuff.export_to_sqlite.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_to_sqlite); }
+    unsafe { tools_stuff.import_from_sqlite.as_ref().unwrap().signals().triggered().connect(&tools_slots.import_from_sqlite); }
+    unsafe { tools_stuff.find_missing_loc_keys.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_missing_loc_keys); }
+    unsafe { tools_stuff.find_duplicated_loc_keys.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_duplicated_loc_keys); }
+    unsafe { tools_stuff.export_bilingual_tsv.as_ref().unwrap().signals().triggered().connect(&tools_slots.export_bilingual_tsv); }
+    unsafe { tools_stuff.import_bilingual_tsv.as_ref().unwrap().signals().triggered().connect(&tools_slots.import_bilingual_tsv); }
+    unsafe { tools_stuff.find_untranslated_loc_entries.as_ref().unwrap().signals().triggered().connect(&tools_slots.find_untranslated_loc_entries); }
+    unsafe { tools_stuff.rename_key_prefix.as_ref().unwrap().signals().triggered().connect(&tools_slots.rename_key_prefix); }
+    unsafe { tools_stuff.diff_table_with_packfile.as_ref().unwrap().signals().triggered().connect(&tools_slots.diff_table_with_packfile); }
+    unsafe { tools_stuff.schema_patches.as_ref().unwrap().signals().triggered().connect(&tools_slots.schema_patches); }
+    unsafe { tools_stuff.import_assembly_kit_definitions.as_ref().unwrap().signals().triggered().connect(&tools_slots.import_assembly_kit_definitions); }
+
+    // Return the ToolsStuff with all the new actions.
+    (tools_stuff, tools_slots)
+}
+
+/// This function takes care of the re-creation of the "Open From Content" and "Open From Data" submenus.
+/// This has to be executed every time we change the Game Selected.
+pub fn build_open_from_submenus(
+    sender_qt: &Sender<Commands>,
+    sender_qt_data: &Sender<Data>,
+    receiver_qt: &Rc<RefCell<Receiver<Data>>>,
+    app_ui: AppUI,
+    submenu_open_from_content: *mut Menu,
+    submenu_open_from_data: *mut Menu,
+    mode: &Rc<RefCell<Mode>>,
+    packedfiles_open_in_packedfile_view: &Rc<RefCell<BTreeMap<i32, Rc<RefCell<Vec<String>>>>>>,
+    mymod_stuff: &Rc<RefCell<MyModStuff>>,
+    close_global_search_action: *mut Action,
+    table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
+) -> Vec<SlotBool<'static>> {
+
+    // First, we clear the list, just in case this is a "Rebuild" of the menu.
+    unsafe { submenu_open_from_content.as_mut().unwrap().clear(); }
+    unsafe { submenu_open_from_data.as_mut().unwrap().clear(); }
+
+    // And we create the slots.
+    let mut open_from_slots = vec![];
+
+    //---------------------------------------------------------------------------------------//
+    // Build the menus...
+    //---------------------------------------------------------------------------------------//
+
+    // Get the path of every PackFile in the data folder (if it's configured) and make an action for each one of them.
+    if let Some(ref mut paths) = get_game_selected_content_packfiles_paths() {
+        paths.sort_unstable_by_key(|x| x.file_name().unwrap().to_string_lossy().as_ref().to_owned());
+        for path in paths {
+
+            // That means our file is a valid PackFile and it needs to be added to the menu.
+            let mod_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
+
+            // Create the action for it.
+            let open_mod_action;
+            unsafe { open_mod_action = submenu_open_from_content.as_mut().unwrap().add_action(&QString::from_std_str(mod_name)); }
+
+            // Create the slot for that action.
+            let slot_open_mod = SlotBool::new(clone!(
+                mode,
+                mymod_stuff,
+                path,
+                packedfiles_open_in_packedfile_view,
+                close_global_search_action,
+                table_state_data,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt => move |_| {
+
+                    // Check first if there has been changes in the PackFile.
+                    if are_you_sure(&app_ui, false) {
+
+                        // Try to open it, and report it case of error.
+                        if let Err(error) = open_packfile(
+                            &sender_qt,
+                            &sender_qt_data,
+                            &receiver_qt,
+                            &[path.to_path_buf()],
+                            &app_ui,
+                            &mymod_stuff,
+                            &mode,
+                            "",
+                            &packedfiles_open_in_packedfile_view,
+                            close_global_search_action,
+                            &table_state_data,
+                        ) { show_dialog(app_ui.window, false, error); }
+                    }
+                }
+            ));
+
+            // Add the slot to the list.
+            open_from_slots.push(slot_open_mod);
+
+            // Connect the action to the slot.
+            unsafe { open_mod_action.as_ref().unwrap().signals().triggered().connect(open_from_slots.last().unwrap()); }  
+        }
+    }
+
+    // Get the path of every PackFile in the data folder (if it's configured) and make an action for each one of them.
+    if let Some(ref mut paths) = get_game_selected_data_packfiles_paths() {
+        paths.sort_unstable_by_key(|x| x.file_name().unwrap().to_string_lossy().as_ref().to_owned());
+        for path in paths {
+
+            // That means our file is a valid PackFile and it needs to be added to the menu.
+            let mod_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
+
+            // Create the action for it.
+            let open_mod_action;
+            unsafe { open_mod_action = submenu_open_from_data.as_mut().unwrap().add_action(&QString::from_std_str(mod_name)); }
+
+            // Create the slot for that action.
+            let slot_open_mod = SlotBool::new(clone!(
+                mode,
+                mymod_stuff,
+                path,
+                packedfiles_open_in_packedfile_view,
+                close_global_search_action,
+                table_state_data,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt => move |_| {
+
+                    // Check first if there has been changes in the PackFile.
+                    if are_you_sure(&app_ui, false) {
+
+                        // Try to open it, and report it case of error.
+                        if let Err(error) = open_packfile(
+                            &sender_qt,
+                            &sender_qt_data,
+                            &receiver_qt,
+                            &[path.to_path_buf()],
+                            &app_ui,
+                            &mymod_stuff,
+                            &mode,
+                            "",
+                            &packedfiles_open_in_packedfile_view,
+                            close_global_search_action,
+                            &table_state_data,
+                        ) { show_dialog(app_ui.window, false, error); }
+                    }
+                }
+            ));
+
+            // Add the slot to the list.
+            open_from_slots.push(slot_open_mod);
+
+            // Connect the action to the slot.
+            unsafe { open_mod_action.as_ref().unwrap().signals().triggered().connect(open_from_slots.last().unwrap()); }  
+        }
+    }
+    
+    // Only if the submenu has items, we enable it.
+    unsafe { submenu_open_from_content.as_mut().unwrap().menu_action().as_mut().unwrap().set_visible(!submenu_open_from_content.as_mut().unwrap().actions().is_empty()); }
+    unsafe { submenu_open_from_data.as_mut().unwrap().menu_action().as_mut().unwrap().set_visible(!submenu_open_from_data.as_mut().unwrap().actions().is_empty()); }
+
+    // Return the slots.
+    open_from_slots
+}
+
+/// This function is the one that takes care of the creation of different PackedFiles.
+pub fn create_packed_files(
+    sender_qt: &Sender<Commands>,
+    sender_qt_data: &Sender<Data>,
+    receiver_qt: &Rc<RefCell<Receiver<Data>>>,
+    table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
+    app_ui: &AppUI,
+    packed_file_type: &PackedFileType,
+) {
+    // Create the "New PackedFile" dialog and wait for his data (or a cancelation). If we receive None, we do nothing. If we receive Some,
+    // we still have to check if it has been any error during the creation of the PackedFile (for example, no definition for DB Tables).
+    if let Some(packed_file_type) = create_new_packed_file_dialog(&app_ui, &sender_qt, &sender_qt_data, &receiver_qt, packed_file_type) {
+        match packed_file_type {
+            Ok(packed_file_type) => {
+
+                // If we reach this place, we got all alright. Now act depending on the type of PackedFile we want to create.
+                match packed_file_type.clone() {
+                    PackedFileType::Loc(ref mut name) | PackedFileType::Text(ref mut name) | PackedFileType::DB(ref mut name, _, _) => {
+
+                        // If the name is_empty, stop.
+                        if name.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput) }
+
+                        // Fix their name termination if needed.
+                        if let PackedFileType::Loc(_) = packed_file_type {
+                            if !name.ends_with(".loc") { name.push_str(".loc"); }
                         }
                         if let PackedFileType::Text(_) = packed_file_type {
                             if !name.ends_with(".lua") &&
@@ -1523,6 +3154,70 @@ pub fn set_my_mod_mode(
     }
 }
 
+/// Function to load the provided matches into the "Global Search" tables, replacing the ones they had before.
+/// DB matches go to the DB table, and Loc matches to the Loc one.
+pub fn load_global_search_matches(
+    model_matches_db: *mut StandardItemModel,
+    model_matches_loc: *mut StandardItemModel,
+    table_view_matches_db: *mut TableView,
+    table_view_matches_loc: *mut TableView,
+    matches: &[GlobalMatch],
+) {
+
+    // Make sure both tables are empty.
+    unsafe { model_matches_db.as_mut().unwrap().clear(); }
+    unsafe { model_matches_loc.as_mut().unwrap().clear(); }
+
+    // For each match, generate an entry in their respective table.
+    for match_found in matches {
+        let (model, path, matches) = match match_found {
+            GlobalMatch::DB((path, matches)) => (model_matches_db, path, matches),
+            GlobalMatch::Loc((path, matches)) => (model_matches_loc, path, matches),
+        };
+
+        for match_found in matches.iter() {
+
+            // Create a new list of StandardItem.
+            let mut qlist = ListStandardItemMutPtr::new(());
+
+            // Create an empty row.
+            let clean_path: PathBuf = path.iter().collect();
+            let clean_path = clean_path.to_string_lossy();
+            let mut file = StandardItem::new(&QString::from_std_str(clean_path));
+            let mut column = StandardItem::new(&QString::from_std_str(&match_found.0));
+            let mut column_number = StandardItem::new(&QString::from_std_str(&format!("{:?}", match_found.1)));
+            let mut row = StandardItem::new(&QString::from_std_str(format!("{:?}", match_found.2 + 1)));
+            let mut text = StandardItem::new(&QString::from_std_str(&match_found.3));
+            file.set_editable(false);
+            column.set_editable(false);
+            column_number.set_editable(false);
+            row.set_editable(false);
+            text.set_editable(false);
+
+            // Add an empty row to the list.
+            unsafe { qlist.append_unsafe(&file.into_raw()); }
+            unsafe { qlist.append_unsafe(&column.into_raw()); }
+            unsafe { qlist.append_unsafe(&row.into_raw()); }
+            unsafe { qlist.append_unsafe(&text.into_raw()); }
+            unsafe { qlist.append_unsafe(&column_number.into_raw()); }
+
+            // Append the new row.
+            unsafe { model.as_mut().unwrap().append_row(&qlist); }
+        }
+    }
+
+    // Hide the column number column for tables, and reconfigure the columns.
+    for &(model, table_view) in &[(model_matches_db, table_view_matches_db), (model_matches_loc, table_view_matches_loc)] {
+        unsafe { table_view.as_mut().unwrap().hide_column(4); }
+        unsafe { model.as_mut().unwrap().set_header_data((0, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("PackedFile")))); }
+        unsafe { model.as_mut().unwrap().set_header_data((1, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Column")))); }
+        unsafe { model.as_mut().unwrap().set_header_data((2, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Row")))); }
+        unsafe { model.as_mut().unwrap().set_header_data((3, Orientation::Horizontal, &Variant::new0(&QString::from_std_str("Match")))); }
+        unsafe { table_view.as_mut().unwrap().horizontal_header().as_mut().unwrap().resize_sections(ResizeMode::ResizeToContents); }
+        unsafe { table_view.as_mut().unwrap().sort_by_column((0, SortOrder::Ascending)); }
+    }
+}
+
 /// Function to filter the results of a global search, in any of the result tables.
 /// If a value is not provided by a slot, we get it from the widget itself.
 pub fn filter_matches_result(
@@ -19,19 +19,43 @@
 // 1 misteryous byte
 // 4 bytes for the entry count, in u32 reversed.
 
+use byteorder::{LittleEndian, WriteBytesExt};
 use serde_derive::{Serialize, Deserialize};
 use uuid::Uuid;
 
-use super::DecodedData;
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::{DecodedData, TableDiff, decode_table_entries, diff_table_entries, export_json, get_row_identity_columns, parse_tsv_field, remove_duplicated_rows};
 use crate::GAME_SELECTED;
 use crate::common::coding_helpers::*;
 use crate::error::{ErrorKind, Result};
+use crate::packfile::packedfile::PackedFile;
 use crate::schema::*;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 /// These two const are the markers we need to check in the header of every DB file.
 const GUID_MARKER: &[u8] = &[253, 254, 252, 255];
 const VERSION_MARKER: &[u8] = &[252, 253, 254, 255];
 
+/// Limits for the search of `DB::guess_fields()`: max amount of fields in a row, and max amount of field types we try before giving up.
+const GUESS_MAX_FIELDS: usize = 64;
+const GUESS_MAX_STEPS: usize = 20_000;
+
+/// Amount of rows `DB::guess_fields()` decodes to discard the candidates. Only the fields decoding them are checked against the entire table.
+const GUESS_SAMPLE_ROWS: u32 = 16;
+
+/// Range of absolute values we consider plausible for a float field. Anything outside it (except 0) is probably not a float.
+const GUESS_FLOAT_RANGE: (f32, f32) = (0.0001, 1_000_000.0);
+
+/// Max absolute value we consider plausible for an integer field. Floats read as integers usually go way over it.
+const GUESS_INTEGER_MAX: i32 = 10_000_000;
+
 /// `DB`: This stores the data of a decoded DB PackedFile in memory.
 /// It stores the PackedFile divided in multiple parts:
 /// - db_type: the name of the table's definition (usually, db/"this_name"/yourtable).
@@ -48,6 +72,32 @@ pub struct DB {
     pub entries: Vec<Vec<DecodedData>>,
 }
 
+/// `FieldGuess`: This stores the type guessed by `DB::guess_fields()` for an undecoded field, and how sure we are about it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldGuess {
+    pub field_type: FieldType,
+    pub confidence: GuessConfidence,
+}
+
+/// `GuessConfidence`: This enum represents how much the values of a guessed field look like his type, across all the rows.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum GuessConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// `DefinitionTestResult`: This stores the result of decoding a DB Table with a definition in `DB::test_definition()`.
+/// - path: the path of the table.
+/// - is_dependency: if the table is from the dependency PackFiles instead of the open PackFile.
+/// - error: why it failed to decode, if it failed.
+#[derive(Clone, Debug)]
+pub struct DefinitionTestResult {
+    pub path: Vec<String>,
+    pub is_dependency: bool,
+    pub error: Option<String>,
+}
+
 /// Implementation of "DB".
 impl DB {
 
@@ -117,66 +167,7 @@ impl DB {
 
         // Try to get the table_definition for this table, if exists.
         if let Some(table_definition) = Self::get_schema(db_type, version, master_schema) {
-            let mut entries = vec![];
-            for row in 0..entry_count {
-
-                let mut decoded_row = vec![];
-                for column in 0..table_definition.fields.len() {
-
-                    let decoded_cell = match table_definition.fields[column].field_type {
-                        FieldType::Boolean => {
-                            if packed_file_data.get(index).is_some() { 
-                                if let Ok(data) = decode_packedfile_bool(packed_file_data[index], &mut index) { DecodedData::Boolean(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>Boolean</b></i> value: the value is not a boolean.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>Boolean</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                        FieldType::Float => {
-                            if packed_file_data.get(index + 3).is_some() {
-                                if let Ok(data) = decode_packedfile_float_f32(&packed_file_data[index..(index + 4)], &mut index) { DecodedData::Float(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>F32</b></i> value: the value is not a valid F32.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>F32</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                        FieldType::Integer => {
-                            if packed_file_data.get(index + 3).is_some() {
-                                if let Ok(data) = decode_packedfile_integer_i32(&packed_file_data[index..(index + 4)], &mut index) { DecodedData::Integer(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>I32</b></i> value: the value is not a valid I32.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>I32</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                        FieldType::LongInteger => {
-                            if packed_file_data.get(index + 7).is_some() {
-                                if let Ok(data) = decode_packedfile_integer_i64(&packed_file_data[index..(index + 8)], &mut index) { DecodedData::LongInteger(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>I64</b></i> value: the value is not a valid I64.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>I64</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                        FieldType::StringU8 => {
-                            if packed_file_data.get(index + 1).is_some() { 
-                                if let Ok(data) = decode_packedfile_string_u8(&packed_file_data[index..], &mut index) { DecodedData::StringU8(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>UTF-8 String</b></i> value: the value is not a valid UTF-8 String.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>UTF-8 String</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                        FieldType::StringU16 => {
-                            if packed_file_data.get(index + 1).is_some() { 
-                                if let Ok(data) = decode_packedfile_string_u16(&packed_file_data[index..], &mut index) { DecodedData::StringU16(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>UTF-16 String</b></i> value: the value is not a valid UTF-16 String.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>UTF-16 String</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                        FieldType::OptionalStringU8 => {
-                            if packed_file_data.get(index).is_some() { 
-                                if let Ok(data) = decode_packedfile_optional_string_u8(&packed_file_data[index..], &mut index) { DecodedData::OptionalStringU8(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>Optional UTF-8 String</b></i> value: the value is not a valid Optional UTF-8 String.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>Optional UTF-8 String</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                        FieldType::OptionalStringU16 => {
-                            if packed_file_data.get(index).is_some() { 
-                                if let Ok(data) = decode_packedfile_optional_string_u16(&packed_file_data[index..], &mut index) { DecodedData::OptionalStringU16(data) }
-                                else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>Optional UTF-16 String</b></i> value: the value is not a valid Optional UTF-16 String.</p>", row + 1, column + 1)))? }}
-                            else { return Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>Optional UTF-16 String</b></i> value: insufficient bytes to decode.</p>", row + 1, column + 1)))? }
-                        }
-                    };
-                    decoded_row.push(decoded_cell);
-                }
-                entries.push(decoded_row);
-            }
+            let entries = decode_table_entries(packed_file_data, &mut index, entry_count, &table_definition.fields)?;
 
             // If we are not in the last byte, it means we didn't parse the entire file, which means this file is corrupt.
             if index != packed_file_data.len() { return Err(ErrorKind::PackedFileSizeIsNotWhatWeExpect(packed_file_data.len(), index))? }
@@ -196,42 +187,176 @@ impl DB {
         else { Err(ErrorKind::SchemaTableDefinitionNotFound)? }
     }
 
-    /// This function takes an entire DB and encode it to Vec<u8>, so it can be written in the disk.
-    /// It returns a Vec<u8> with the entire DB encoded in it.
-    pub fn save(&self) -> Vec<u8> {
-        let mut packed_file: Vec<u8> = vec![];
+    /// This function returns the size of the DB once encoded, so we can allocate the buffer to encode it at once.
+    /// It always counts the GUID, so for the games without it, this is 78 bytes bigger than the real size.
+    pub fn get_encoded_size(&self) -> usize {
+        78 + 13 + self.entries.iter().flat_map(|x| x.iter()).map(|x| x.get_encoded_size()).sum::<usize>()
+    }
+
+    /// This function encodes the entire DB into the provided writer, without building the encoded DB in memory first.
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
 
         // Napoleon and Empire do not have GUID, and adding it to their tables crash both games.
         // So for those two games, we ignore the GUID_MARKER and the GUID itself.
         let game_selected = GAME_SELECTED.lock().unwrap().to_owned();
         if game_selected != "empire" && game_selected != "napoleon" {
-            packed_file.extend_from_slice(GUID_MARKER);
-            packed_file.extend_from_slice(&encode_packedfile_string_u16(&format!("{}", Uuid::new_v4())));
+            writer.write_all(GUID_MARKER)?;
+            write_packedfile_string_u16(writer, &format!("{}", Uuid::new_v4()))?;
         }
-        packed_file.extend_from_slice(VERSION_MARKER);
-        packed_file.extend_from_slice(&encode_integer_i32(self.version));
-        packed_file.push(self.mysterious_byte);
-        packed_file.extend_from_slice(&encode_integer_u32(self.entries.len() as u32));
+        writer.write_all(VERSION_MARKER)?;
+        writer.write_i32::<LittleEndian>(self.version)?;
+        writer.write_u8(self.mysterious_byte)?;
+        writer.write_u32::<LittleEndian>(self.entries.len() as u32)?;
 
-        for row in &self.entries {        
+        for row in &self.entries {
             for cell in row {
                 match *cell {
-                    DecodedData::Boolean(data) => packed_file.push(encode_bool(data)),
-                    DecodedData::Float(data) => packed_file.extend_from_slice(&encode_float_f32(data)),
-                    DecodedData::Integer(data) => packed_file.extend_from_slice(&encode_integer_i32(data)),
-                    DecodedData::LongInteger(data) => packed_file.extend_from_slice(&encode_integer_i64(data)),
-                    DecodedData::StringU8(ref data) => packed_file.extend_from_slice(&encode_packedfile_string_u8(data)),
-                    DecodedData::StringU16(ref data) => packed_file.extend_from_slice(&encode_packedfile_string_u16(data)),
-                    DecodedData::OptionalStringU8(ref data) => packed_file.extend_from_slice(&encode_packedfile_optional_string_u8(data)),
-                    DecodedData::OptionalStringU16(ref data) => packed_file.extend_from_slice(&encode_packedfile_optional_string_u16(data)),
+                    DecodedData::Boolean(data) => writer.write_u8(encode_bool(data))?,
+                    DecodedData::Float(data) => writer.write_f32::<LittleEndian>(data)?,
+                    DecodedData::Integer(data) => writer.write_i32::<LittleEndian>(data)?,
+                    DecodedData::LongInteger(data) => writer.write_i64::<LittleEndian>(data)?,
+                    DecodedData::Double(data) => writer.write_f64::<LittleEndian>(data)?,
+                    DecodedData::Colour(data) => writer.write_u32::<LittleEndian>(data)?,
+                    DecodedData::StringU8(ref data) => write_packedfile_string_u8(writer, data)?,
+                    DecodedData::StringU16(ref data) => write_packedfile_string_u16(writer, data)?,
+                    DecodedData::OptionalStringU8(ref data) => write_packedfile_optional_string_u8(writer, data)?,
+                    DecodedData::OptionalStringU16(ref data) => write_packedfile_optional_string_u16(writer, data)?,
                 }
             }
         }
 
-        // Return the encoded PackedFile.
+        Ok(())
+    }
+
+    /// This function takes an entire DB and encode it to Vec<u8>, so it can be written in the disk.
+    /// It returns a Vec<u8> with the entire DB encoded in it.
+    pub fn save(&self) -> Vec<u8> {
+        let mut packed_file = Vec::with_capacity(self.get_encoded_size());
+
+        // Writing to a Vec<u8> cannot fail.
+        self.encode(&mut packed_file).unwrap();
         packed_file
     }
 
+    /// This function exports the DB Table to a JSON file. See `export_json()` for the format.
+    pub fn export_json(&self, path: &PathBuf) -> Result<()> {
+        export_json(&self.entries, path, &self.table_definition, (&self.db_type, self.version))
+    }
+
+    /// This function removes from the DB Table the entries that are also in any of the provided vanilla tables
+    /// of his same type and version, so only the custom ones are left. Tables of other types or versions are ignored.
+    ///
+    /// It returns true if the table has been left empty.
+    pub fn optimize_table(&mut self, vanilla_tables: &[DB]) -> bool {
+        let vanilla_entries = vanilla_tables.iter()
+            .filter(|x| x.db_type == self.db_type && x.version == self.version)
+            .flat_map(|x| x.entries.iter())
+            .collect::<HashSet<&Vec<DecodedData>>>();
+        self.entries.retain(|entry| !vanilla_entries.contains(entry));
+        self.entries.is_empty()
+    }
+
+    /// This function changes the definition (and version) of the DB Table to the provided one, converting his entries to it.
+    /// Fields are matched by name and their values converted to their new type. Fields that are new, or whose values cannot
+    /// be converted, get their default value. Fields that are no longer in the definition are lost.
+    pub fn set_definition(&mut self, new_definition: &TableDefinition) {
+        let mapping = new_definition.fields.iter()
+            .map(|new_field| self.table_definition.fields.iter().position(|x| x.field_name == new_field.field_name))
+            .collect::<Vec<Option<usize>>>();
+
+        self.entries = self.entries.iter().map(|entry| {
+            new_definition.fields.iter().zip(&mapping).map(|(field, old_column)| match old_column.and_then(|x| entry.get(x)) {
+                Some(value) => parse_tsv_field(field.field_type, &value.data_to_string(), 0, 0).unwrap_or_else(|_| DecodedData::default_from_field(field)),
+                None => DecodedData::default_from_field(field),
+            }).collect()
+        }).collect();
+
+        self.version = new_definition.version;
+        self.table_definition = new_definition.clone();
+    }
+
+    /// This function merges the provided DB Tables into a new one with the provided definition. Tables with another definition are converted
+    /// to it first (see `set_definition()`), so tables of different versions can be merged. If `remove_duplicates` is true, only the last
+    /// row of each key is kept. Tables without key columns only lose the rows that are exactly the same as a later one.
+    ///
+    /// It fails if there are no tables to merge, or if they're not all of the same type.
+    pub fn merge(tables: &[&DB], table_definition: &TableDefinition, remove_duplicates: bool) -> Result<Self> {
+        let db_type = match tables.first() {
+            Some(table) => &table.db_type,
+            None => return Err(ErrorKind::InvalidFilesForMerging)?,
+        };
+        if tables.iter().any(|x| &x.db_type != db_type) { return Err(ErrorKind::InvalidFilesForMerging)? }
+
+        let mut merged_table = Self::new(db_type, table_definition.version, table_definition.clone());
+        for table in tables {
+            if table.table_definition == *table_definition { merged_table.entries.extend_from_slice(&table.entries); }
+            else {
+                let mut table = (*table).clone();
+                table.set_definition(table_definition);
+                merged_table.entries.append(&mut table.entries);
+            }
+        }
+
+        if remove_duplicates { remove_duplicated_rows(&mut merged_table.entries, &get_row_identity_columns(table_definition)); }
+        Ok(merged_table)
+    }
+
+    /// This function compares this DB Table (the old one) with the provided one (the new one), matching their rows by their key columns.
+    /// If their definitions are different, the table with the older version is converted to the definition of the other one first.
+    ///
+    /// It fails if the tables are not of the same type.
+    pub fn diff(&self, other: &DB) -> Result<TableDiff> {
+        if self.db_type != other.db_type { return Err(ErrorKind::InvalidFilesForDiffing)? }
+        if self.table_definition == other.table_definition { return Ok(diff_table_entries(&self.entries, &other.entries, &self.table_definition)) }
+
+        if self.version < other.version {
+            let mut table = self.clone();
+            table.set_definition(&other.table_definition);
+            Ok(diff_table_entries(&table.entries, &other.entries, &other.table_definition))
+        }
+        else {
+            let mut other = other.clone();
+            other.set_definition(&self.table_definition);
+            Ok(diff_table_entries(&self.entries, &other.entries, &self.table_definition))
+        }
+    }
+
+    /// This function decodes the entries of a DB Table with the provided definition, instead of the one in the schema.
+    /// It returns None if the table has a different version than the definition.
+    pub fn read_entries_with_definition(packed_file_data: &[u8], table_definition: &TableDefinition) -> Result<Option<Vec<Vec<DecodedData>>>> {
+        let (version, entry_count, mut index) = Self::get_header_data(packed_file_data)?;
+        if version != table_definition.version { return Ok(None) }
+
+        let entries = decode_table_entries(packed_file_data, &mut index, entry_count, &table_definition.fields)?;
+        if index != packed_file_data.len() { return Err(ErrorKind::PackedFileSizeIsNotWhatWeExpect(packed_file_data.len(), index))? }
+        Ok(Some(entries))
+    }
+
+    /// This function decodes with the provided definition every DB Table in the `table_folder` folder of the provided PackedFiles with
+    /// the same version as the definition, and returns the result for each one of them, so we can check a definition before saving it.
+    pub fn test_definition(
+        table_folder: &str,
+        table_definition: &TableDefinition,
+        packed_files: &[PackedFile],
+        is_dependency: bool,
+    ) -> Vec<DefinitionTestResult> {
+        packed_files.iter()
+            .filter(|x| x.path.len() == 3 && x.path[0] == "db" && x.path[1] == table_folder)
+            .filter_map(|x| {
+                let error = match x.get_data().and_then(|data| Self::read_entries_with_definition(&data, table_definition)) {
+                    Ok(Some(_)) => None,
+                    Ok(None) => return None,
+                    Err(error) => Some(error.to_string()),
+                };
+                Some(DefinitionTestResult {
+                    path: x.path.to_vec(),
+                    is_dependency,
+                    error,
+                })
+            })
+            .collect()
+    }
+
     /// This functions returns the version and entry count of a DB Table, without decoding the entire table. It just emulates what the `read` function does.
     pub fn get_header_data(packed_file_data: &[u8]) -> Result<(i32, u32, usize)> {
 
@@ -261,6 +386,45 @@ impl DB {
         Ok((version, entry_count, index))
     }
 
+    /// This function tries to guess the types of the fields of a table after the already decoded ones, so the decoder has
+    /// something to start from. `initial_index` is the byte where the first row starts.
+    ///
+    /// It looks for a sequence of fields that decodes every row of the table and ends exactly at the end of the data, trying first the
+    /// types that fit better the bytes of the first row (valid strings, plausible floats, 0/1 bytes, small integers). The candidates are
+    /// discarded by decoding only the first rows, and the entire table is only decoded for the ones passing that. Once found, each
+    /// guessed field gets a confidence level depending on how its values look in all the rows. If it can't find one, it returns nothing.
+    pub fn guess_fields(
+        packed_file_data: &[u8],
+        initial_index: usize,
+        entry_count: u32,
+        decoded_fields: &[Field],
+    ) -> Vec<FieldGuess> {
+        if entry_count == 0 { return vec![] }
+
+        // Get where the undecoded part of the first row starts.
+        let mut index = initial_index;
+        if decoded_fields.iter().any(|x| DecodedData::decode(packed_file_data, &mut index, x.field_type).is_err()) { return vec![] }
+
+        let mut fields = decoded_fields.to_vec();
+        let mut steps = 0;
+        if !guess_next_fields(packed_file_data, initial_index, entry_count, index, &mut fields, &mut steps) { return vec![] }
+
+        // Now that we know the entire row, we can check each guessed field against all the rows.
+        let mut index = initial_index;
+        let entries = match decode_table_entries(packed_file_data, &mut index, entry_count, &fields) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        fields.iter().enumerate().skip(decoded_fields.len()).map(|(column, field)| {
+            let values = entries.iter().map(|x| &x[column]).collect::<Vec<&DecodedData>>();
+            FieldGuess {
+                field_type: field.field_type,
+                confidence: get_guess_confidence(&values),
+            }
+        }).collect()
+    }
+
     /// This function gets the schema corresponding to the table we passed it, if it exists.
     pub fn get_schema(db_name: &str, version: i32, schema: &Schema) -> Option<TableDefinition> {
         if let Some(index_table_definitions) = schema.get_table_definitions(db_name) {
@@ -295,3 +459,166 @@ impl DB {
         unreachable!();
     }
 }
+
+/// Display implementation of `GuessConfidence`.
+impl Display for GuessConfidence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessConfidence::Low => write!(f, "Low"),
+            GuessConfidence::Medium => write!(f, "Medium"),
+            GuessConfidence::High => write!(f, "High"),
+        }
+    }
+}
+
+/// This function adds to `fields` the fields needed to decode the entire table, trying first the more plausible types for the bytes
+/// of the first row starting at `index`. It returns false if it couldn't find them, leaving `fields` as it was.
+///
+/// Before going deeper, we check if any of the candidates completes the row, so the end of a row is not confused with the start of the next one.
+fn guess_next_fields(
+    data: &[u8],
+    initial_index: usize,
+    entry_count: u32,
+    index: usize,
+    fields: &mut Vec<Field>,
+    steps: &mut usize,
+) -> bool {
+    if fields.len() >= GUESS_MAX_FIELDS || *steps >= GUESS_MAX_STEPS { return false }
+
+    // If the rest of the rows cannot fit in the remaining data, there is no point in trying that type.
+    let min_row_size = fields.iter().map(|x| get_min_encoded_size(x.field_type)).sum::<usize>();
+    let candidates = get_field_type_candidates(data, index).into_iter()
+        .filter(|(field_type, next_index)| next_index + (entry_count as usize - 1) * (min_row_size + get_min_encoded_size(*field_type)) <= data.len())
+        .collect::<Vec<(FieldType, usize)>>();
+
+    // The first rows discard most of the candidates, so we only decode the rest of the table for the ones that decode them.
+    let sample_rows = entry_count.min(GUESS_SAMPLE_ROWS);
+    for (field_type, _) in &candidates {
+        *steps += 1;
+        fields.push(Field::new("new_field".to_owned(), *field_type, false, None, String::new()));
+        if let Some(index) = decode_rows(data, initial_index, sample_rows, fields) {
+            if decode_rows(data, index, entry_count - sample_rows, fields) == Some(data.len()) { return true }
+        }
+        fields.pop();
+    }
+
+    for (field_type, next_index) in &candidates {
+        fields.push(Field::new("new_field".to_owned(), *field_type, false, None, String::new()));
+        if guess_next_fields(data, initial_index, entry_count, *next_index, fields, steps) { return true }
+        fields.pop();
+    }
+
+    false
+}
+
+/// This function returns the field types that can be decoded from the data at `index` and the index after decoding them, sorted from
+/// the most plausible to the least plausible one. Types whose data doesn't make sense (strings with control characters, floats out
+/// of range,...) are not returned at all, except for integers, as any 4 bytes can be one.
+fn get_field_type_candidates(data: &[u8], index: usize) -> Vec<(FieldType, usize)> {
+    let field_types = [
+        FieldType::StringU8,
+        FieldType::StringU16,
+        FieldType::OptionalStringU8,
+        FieldType::OptionalStringU16,
+        FieldType::Integer,
+        FieldType::Float,
+        FieldType::Boolean,
+        FieldType::LongInteger,
+    ];
+
+    let mut candidates = field_types.iter().filter_map(|field_type| {
+        let mut next_index = index;
+        let score = match DecodedData::decode(data, &mut next_index, *field_type).ok()? {
+            DecodedData::StringU8(ref data) |
+            DecodedData::OptionalStringU8(ref data) => if !is_plausible_string(data) { return None } else if data.is_empty() { 2 } else { 6 },
+            DecodedData::StringU16(ref data) |
+            DecodedData::OptionalStringU16(ref data) => if !is_plausible_string(data) { return None } else if data.is_empty() { 1 } else { 5 },
+            DecodedData::Float(data) => if data == 0.0 { 2 } else if is_plausible_float(data) { 4 } else { return None },
+            DecodedData::Integer(data) => if is_plausible_integer(data) { 3 } else { 1 },
+            DecodedData::Boolean(_) => 3,
+            _ => 0,
+        };
+
+        // Empty optional strings are just a 0, so they're less likely than booleans.
+        let score = if (*field_type == FieldType::OptionalStringU8 || *field_type == FieldType::OptionalStringU16) && next_index == index + 1 { 1 } else { score };
+        Some((*field_type, next_index, score))
+    }).collect::<Vec<(FieldType, usize, i32)>>();
+
+    candidates.sort_by(|a, b| b.2.cmp(&a.2));
+    candidates.into_iter().map(|(field_type, next_index, _)| (field_type, next_index)).collect()
+}
+
+/// This function returns how much the values of a guessed field look like his type.
+fn get_guess_confidence(values: &[&DecodedData]) -> GuessConfidence {
+    let strings = values.iter().filter_map(|x| match x {
+        DecodedData::StringU8(data) |
+        DecodedData::StringU16(data) |
+        DecodedData::OptionalStringU8(data) |
+        DecodedData::OptionalStringU16(data) => Some(data),
+        _ => None,
+    }).collect::<Vec<&String>>();
+
+    // Empty strings are just zeros, so they could be anything.
+    if !strings.is_empty() {
+        if strings.iter().any(|x| !is_plausible_string(x)) || strings.iter().all(|x| x.is_empty()) { GuessConfidence::Low }
+        else { GuessConfidence::High }
+    }
+
+    else {
+        match values.first() {
+            Some(DecodedData::Boolean(_)) => {
+                let trues = values.iter().filter(|x| ***x == DecodedData::Boolean(true)).count();
+                if trues == 0 { GuessConfidence::Low }
+                else if trues == values.len() { GuessConfidence::Medium }
+                else { GuessConfidence::High }
+            }
+            Some(DecodedData::Float(_)) => {
+                let floats = values.iter().filter_map(|x| if let DecodedData::Float(data) = x { Some(*data) } else { None }).collect::<Vec<f32>>();
+                if floats.iter().all(|x| *x == 0.0) || floats.iter().any(|x| *x != 0.0 && !is_plausible_float(*x)) { GuessConfidence::Low }
+                else { GuessConfidence::High }
+            }
+            Some(DecodedData::Integer(_)) => {
+                let integers = values.iter().filter_map(|x| if let DecodedData::Integer(data) = x { Some(*data) } else { None }).collect::<Vec<i32>>();
+                if integers.iter().all(|x| *x == 0) { GuessConfidence::Low }
+                else if integers.iter().all(|x| is_plausible_integer(*x)) { GuessConfidence::High }
+                else { GuessConfidence::Medium }
+            }
+            _ => GuessConfidence::Low,
+        }
+    }
+}
+
+/// This function decodes the provided amount of rows with the provided fields, starting at `initial_index`. It returns the index
+/// after the last row, or None if any of them cannot be decoded.
+fn decode_rows(data: &[u8], initial_index: usize, row_count: u32, fields: &[Field]) -> Option<usize> {
+    let mut index = initial_index;
+    for _ in 0..row_count {
+        if fields.iter().any(|x| DecodedData::decode(data, &mut index, x.field_type).is_err()) { return None }
+    }
+    Some(index)
+}
+
+/// This function returns the minimum amount of bytes a field of the provided type takes once encoded.
+fn get_min_encoded_size(field_type: FieldType) -> usize {
+    match field_type {
+        FieldType::Boolean | FieldType::OptionalStringU8 | FieldType::OptionalStringU16 => 1,
+        FieldType::StringU8 | FieldType::StringU16 => 2,
+        FieldType::Float | FieldType::Integer | FieldType::Colour => 4,
+        FieldType::LongInteger | FieldType::Double => 8,
+    }
+}
+
+/// This function checks if a decoded string looks like text, instead of random bytes that happen to be a valid string.
+fn is_plausible_string(string: &str) -> bool {
+    !string.chars().any(|x| x.is_control() && !x.is_whitespace())
+}
+
+/// This function checks if a non-zero float is in the range of values we usually find in the tables.
+fn is_plausible_float(float: f32) -> bool {
+    float.is_finite() && float.abs() >= GUESS_FLOAT_RANGE.0 && float.abs() <= GUESS_FLOAT_RANGE.1
+}
+
+/// This function checks if an integer is in the range of values we usually find in the tables.
+fn is_plausible_integer(integer: i32) -> bool {
+    integer.checked_abs().map_or(false, |x| x <= GUESS_INTEGER_MAX)
+}
@@ -0,0 +1,335 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the DB PackedFiles, to make sure we don't break their encoding/decoding or any of the functions working over their data.
+
+use crate::common::coding_helpers::*;
+use crate::common::tests::{new_db_packed_file, new_table_definitions_with_references};
+use crate::error::{Error, ErrorKind};
+use crate::packedfile::{ChangedRow, DecodedData};
+use crate::packedfile::db::{DB, FieldGuess, GuessConfidence};
+use crate::schema::{Field, FieldType, Schema, TableDefinition, TableDefinitions};
+use crate::ui::packedfile_table::packedfile_table_extras::is_valid_for_field_type;
+
+/// Test to make sure encoding a DB Table gives the same bytes as encoding it field by field, and that the size
+/// we allocate for it is enough to hold it.
+#[test]
+fn test_db_encoding() {
+    let mut table_definition = TableDefinition::new(1);
+    table_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    table_definition.fields.push(Field::new("name".to_owned(), FieldType::StringU16, false, None, String::new()));
+    table_definition.fields.push(Field::new("optional".to_owned(), FieldType::OptionalStringU8, false, None, String::new()));
+    table_definition.fields.push(Field::new("optional_u16".to_owned(), FieldType::OptionalStringU16, false, None, String::new()));
+    table_definition.fields.push(Field::new("enabled".to_owned(), FieldType::Boolean, false, None, String::new()));
+    table_definition.fields.push(Field::new("cost".to_owned(), FieldType::Float, false, None, String::new()));
+    table_definition.fields.push(Field::new("amount".to_owned(), FieldType::Integer, false, None, String::new()));
+    table_definition.fields.push(Field::new("big_amount".to_owned(), FieldType::LongInteger, false, None, String::new()));
+
+    let mut db = DB::new("units_tables", 1, table_definition);
+    db.entries.push(vec![
+        DecodedData::StringU8("unit_1".to_owned()),
+        DecodedData::StringU16("Únit ✓".to_owned()),
+        DecodedData::OptionalStringU8("a".to_owned()),
+        DecodedData::OptionalStringU16(String::new()),
+        DecodedData::Boolean(true),
+        DecodedData::Float(1.5),
+        DecodedData::Integer(-3),
+        DecodedData::LongInteger(1 << 40),
+    ]);
+
+    let mut expected = vec![];
+    expected.extend_from_slice(&[252, 253, 254, 255]);
+    expected.extend_from_slice(&encode_integer_i32(1));
+    expected.push(1);
+    expected.extend_from_slice(&encode_integer_u32(1));
+    expected.extend_from_slice(&encode_packedfile_string_u8("unit_1"));
+    expected.extend_from_slice(&encode_packedfile_string_u16("Únit ✓"));
+    expected.extend_from_slice(&encode_packedfile_optional_string_u8("a"));
+    expected.extend_from_slice(&encode_packedfile_optional_string_u16(""));
+    expected.push(1);
+    expected.extend_from_slice(&encode_float_f32(1.5));
+    expected.extend_from_slice(&encode_integer_i32(-3));
+    expected.extend_from_slice(&encode_integer_i64(1 << 40));
+
+    // The GUID is random (and not there for some games), so we skip it together with his marker.
+    let data = db.save();
+    let guid_size = if data.starts_with(&[253, 254, 252, 255]) { 78 } else { 0 };
+    assert_eq!(&data[guid_size..], &expected[..]);
+    assert!(db.get_encoded_size() >= data.len());
+}
+
+/// Test to make sure DB Tables with `Double` and `Colour` fields get decoded from their raw bytes, and encoded back to the same bytes.
+#[test]
+fn test_db_double_and_colour_fields() {
+    let mut table_definition = TableDefinition::new(1);
+    table_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    table_definition.fields.push(Field::new("scale".to_owned(), FieldType::Double, false, None, String::new()));
+    table_definition.fields.push(Field::new("banner_colour".to_owned(), FieldType::Colour, false, None, String::new()));
+    table_definition.fields.push(Field::new("big_amount".to_owned(), FieldType::LongInteger, false, None, String::new()));
+
+    let mut table_definitions = TableDefinitions::new("units_tables");
+    table_definitions.add_table_definition(table_definition);
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let fixture: Vec<u8> = vec![
+        252, 253, 254, 255,                         // Version marker.
+        1, 0, 0, 0,                                 // Version.
+        1,                                          // Mysterious byte.
+        1, 0, 0, 0,                                 // Entry count.
+        6, 0, b'u', b'n', b'i', b't', b'_', b'1',   // "unit_1".
+        0, 0, 0, 0, 0, 0, 248, 63,                  // 1.5 as f64.
+        0, 128, 255, 0,                             // 0xFF8000.
+        0, 0, 0, 0, 0, 1, 0, 0,                     // 1 << 40.
+    ];
+
+    let db = DB::read(&fixture, "units_tables", &schema).unwrap();
+    assert_eq!(db.entries, vec![vec![
+        DecodedData::StringU8("unit_1".to_owned()),
+        DecodedData::Double(1.5),
+        DecodedData::Colour(0xFF_8000),
+        DecodedData::LongInteger(1 << 40),
+    ]]);
+    assert_eq!(db.entries[0][2].data_to_string(), "FF8000");
+    assert!(is_valid_for_field_type("FF8000", FieldType::Colour));
+    assert!(!is_valid_for_field_type("orange", FieldType::Colour));
+
+    // The GUID is random (and not there for some games), so we skip it together with his marker.
+    let data = db.save();
+    let guid_size = if data.starts_with(&[253, 254, 252, 255]) { 78 } else { 0 };
+    assert_eq!(&data[guid_size..], &fixture[..]);
+    assert!(db.get_encoded_size() >= data.len());
+
+    // Truncating the colour of the row gives us the field that failed.
+    match DB::read(&fixture[..32], "units_tables", &schema) {
+        Ok(_) => panic!("Truncated DB decoded successfully."),
+        Err(error) => assert_eq!(error.kind(), ErrorKind::TableFieldDecode(0, "banner_colour".to_owned(), "Colour".to_owned(), 29, "insufficient bytes to decode".to_owned())),
+    }
+}
+
+/// Test to make sure changing the definition of a table (`DB::set_definition()`) keeps the fields by name, converting their type,
+/// and gives default values to the new ones.
+#[test]
+fn test_db_set_definition() {
+    let mut old_definition = TableDefinition::new(1);
+    old_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    old_definition.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    old_definition.fields.push(Field::new("removed".to_owned(), FieldType::Boolean, false, None, String::new()));
+
+    let mut new_definition = TableDefinition::new(2);
+    new_definition.fields.push(Field::new("cost".to_owned(), FieldType::Float, false, None, String::new()));
+    new_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    new_definition.fields.push(Field::new("added".to_owned(), FieldType::Integer, false, None, String::new()));
+
+    let mut db = DB::new("units_tables", 1, old_definition);
+    db.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(5), DecodedData::Boolean(true)]);
+    db.set_definition(&new_definition);
+
+    assert_eq!(db.version, 2);
+    assert_eq!(db.table_definition, new_definition);
+    assert_eq!(db.entries, vec![vec![DecodedData::Float(5.0), DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(0)]]);
+}
+
+/// Test to make sure merging DB Tables (`DB::merge()`) converts the tables of other versions to the target definition,
+/// and only removes the duplicated keys if asked, keeping the last row of each key.
+#[test]
+fn test_db_merge() {
+    let mut old_definition = TableDefinition::new(1);
+    old_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    old_definition.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+
+    let mut new_definition = TableDefinition::new(2);
+    new_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    new_definition.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    new_definition.fields.push(Field::new("added".to_owned(), FieldType::Boolean, false, None, String::new()));
+
+    let mut old_table = DB::new("units_tables", 1, old_definition);
+    old_table.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1)]);
+    old_table.entries.push(vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(2)]);
+
+    let mut new_table = DB::new("units_tables", 2, new_definition.clone());
+    new_table.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(10), DecodedData::Boolean(true)]);
+
+    let merged = DB::merge(&[&old_table, &new_table], &new_definition, false).unwrap();
+    assert_eq!(merged.version, 2);
+    assert_eq!(merged.table_definition, new_definition);
+    assert_eq!(merged.entries, vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1), DecodedData::Boolean(false)],
+        vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(2), DecodedData::Boolean(false)],
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(10), DecodedData::Boolean(true)],
+    ]);
+
+    let merged = DB::merge(&[&old_table, &new_table], &new_definition, true).unwrap();
+    assert_eq!(merged.entries, vec![
+        vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(2), DecodedData::Boolean(false)],
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(10), DecodedData::Boolean(true)],
+    ]);
+
+    // Tables without key columns only lose the rows that are repeated entirely.
+    let mut keyless_definition = TableDefinition::new(1);
+    keyless_definition.fields.push(Field::new("unit".to_owned(), FieldType::StringU8, false, None, String::new()));
+    keyless_definition.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+
+    let mut keyless_table = DB::new("units_to_groupings_tables", 1, keyless_definition.clone());
+    keyless_table.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1)]);
+    keyless_table.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(2)]);
+    keyless_table.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1)]);
+
+    let merged = DB::merge(&[&keyless_table], &keyless_definition, true).unwrap();
+    assert_eq!(merged.entries, vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(2)],
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1)],
+    ]);
+
+    // Tables of different types, or no tables at all, cannot be merged.
+    let other_table = DB::new("land_units_tables", 2, new_definition.clone());
+    assert!(DB::merge(&[&new_table, &other_table], &new_definition, false).is_err());
+    assert!(DB::merge(&[], &new_definition, false).is_err());
+}
+
+/// Test to make sure diffing DB Tables (`DB::diff()`) finds the added, removed and changed rows, converting the older table first.
+#[test]
+fn test_db_diff() {
+    let mut old_definition = TableDefinition::new(1);
+    old_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    old_definition.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+
+    let mut new_definition = old_definition.clone();
+    new_definition.version = 2;
+    new_definition.fields.push(Field::new("added".to_owned(), FieldType::Boolean, false, None, String::new()));
+
+    let mut old_table = DB::new("units_tables", 1, old_definition);
+    old_table.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1)]);
+    old_table.entries.push(vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(2)]);
+    old_table.entries.push(vec![DecodedData::StringU8("unit_3".to_owned()), DecodedData::Integer(3)]);
+
+    let mut new_table = DB::new("units_tables", 2, new_definition.clone());
+    new_table.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1), DecodedData::Boolean(false)]);
+    new_table.entries.push(vec![DecodedData::StringU8("unit_3".to_owned()), DecodedData::Integer(30), DecodedData::Boolean(true)]);
+    new_table.entries.push(vec![DecodedData::StringU8("unit_4".to_owned()), DecodedData::Integer(4), DecodedData::Boolean(false)]);
+
+    // The old table is converted to the new definition, so the new field is only changed in the rows not using his default value.
+    let diff = old_table.diff(&new_table).unwrap();
+    assert_eq!(diff.table_definition, new_definition);
+    assert_eq!(diff.added, vec![vec![DecodedData::StringU8("unit_4".to_owned()), DecodedData::Integer(4), DecodedData::Boolean(false)]]);
+    assert_eq!(diff.removed, vec![vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(2), DecodedData::Boolean(false)]]);
+    assert_eq!(diff.changed, vec![ChangedRow {
+        key: vec![DecodedData::StringU8("unit_3".to_owned())],
+        fields: vec![
+            ("cost".to_owned(), DecodedData::Integer(3), DecodedData::Integer(30)),
+            ("added".to_owned(), DecodedData::Boolean(false), DecodedData::Boolean(true)),
+        ],
+    }]);
+
+    // It works the same way in the other direction, converting the new table to the old definition.
+    let diff = new_table.diff(&old_table).unwrap();
+    assert_eq!(diff.table_definition.version, 1);
+    assert_eq!(diff.added, vec![vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(2)]]);
+    assert_eq!(diff.removed, vec![vec![DecodedData::StringU8("unit_4".to_owned()), DecodedData::Integer(4)]]);
+    assert_eq!(diff.changed.len(), 1);
+
+    // Tables of different types cannot be compared.
+    let other_table = DB::new("land_units_tables", 2, new_definition);
+    assert_eq!(new_table.diff(&other_table).unwrap_err().kind(), ErrorKind::InvalidFilesForDiffing);
+}
+
+/// Test to make sure optimizing a DB Table (`DB::optimize_table()`) only uses the vanilla tables of his same type and version.
+#[test]
+fn test_db_optimize_table() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let table = |name: &str, version: i32, keys: &[&str]| {
+        let mut db = DB::new(name, version, table_definitions.versions[0].clone());
+        for key in keys { db.entries.push(vec![DecodedData::StringU8(key.to_string())]); }
+        db
+    };
+
+    let vanilla_tables = vec![table("units_tables", 1, &["unit_1"]), table("units_tables", 2, &["unit_2"]), table("land_units_tables", 1, &["unit_3"])];
+    let mut db = table("units_tables", 1, &["unit_1", "unit_2", "unit_3", "unit_4"]);
+    assert!(!db.optimize_table(&vanilla_tables));
+    assert_eq!(db.entries, table("units_tables", 1, &["unit_2", "unit_3", "unit_4"]).entries);
+
+    // A table whose version doesn't match any vanilla table is left untouched.
+    let mut db = table("units_tables", 3, &["unit_1", "unit_2"]);
+    assert!(!db.optimize_table(&vanilla_tables));
+    assert_eq!(db.entries, table("units_tables", 3, &["unit_1", "unit_2"]).entries);
+}
+
+/// Test to make sure the field guesser (`DB::guess_fields()`) finds the fields of a table with strings, numbers and booleans,
+/// without confusing the end of a row with the start of the next one, and that it respects the already decoded fields.
+#[test]
+fn test_guess_fields() {
+    let mut table_definition = TableDefinition::new(1);
+    table_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    table_definition.fields.push(Field::new("amount".to_owned(), FieldType::Integer, false, None, String::new()));
+    table_definition.fields.push(Field::new("cost".to_owned(), FieldType::Float, false, None, String::new()));
+    table_definition.fields.push(Field::new("enabled".to_owned(), FieldType::Boolean, false, None, String::new()));
+
+    let mut db = DB::new("units_tables", 1, table_definition.clone());
+    db.entries.push(vec![DecodedData::StringU8("unit_a".to_owned()), DecodedData::Integer(10), DecodedData::Float(1.5), DecodedData::Boolean(true)]);
+    db.entries.push(vec![DecodedData::StringU8("unit_b".to_owned()), DecodedData::Integer(20), DecodedData::Float(2.25), DecodedData::Boolean(false)]);
+    db.entries.push(vec![DecodedData::StringU8("unit_c".to_owned()), DecodedData::Integer(30), DecodedData::Float(0.5), DecodedData::Boolean(true)]);
+    let data = db.save();
+    let (_, entry_count, initial_index) = DB::get_header_data(&data).unwrap();
+
+    let expected = table_definition.fields.iter().map(|x| FieldGuess { field_type: x.field_type, confidence: GuessConfidence::High }).collect::<Vec<FieldGuess>>();
+    assert_eq!(DB::guess_fields(&data, initial_index, entry_count, &[]), expected);
+    assert_eq!(DB::guess_fields(&data, initial_index, entry_count, &table_definition.fields[..1]), &expected[1..]);
+    assert!(DB::guess_fields(&data, initial_index, 0, &[]).is_empty());
+
+    // Tables with more rows than the ones used to discard the candidates still need to be decoded entirely.
+    for row in 0..40 {
+        db.entries.push(vec![DecodedData::StringU8(format!("unit_{}", row)), DecodedData::Integer(row + 1), DecodedData::Float(row as f32 + 0.5), DecodedData::Boolean(row % 2 == 0)]);
+    }
+    let data = db.save();
+    let (_, entry_count, initial_index) = DB::get_header_data(&data).unwrap();
+    assert_eq!(DB::guess_fields(&data, initial_index, entry_count, &[]), expected);
+}
+
+/// Test to make sure the field guesser (`DB::guess_fields()`) recognises optional and UTF-16 strings, and gives a low confidence
+/// to fields that are only zeros.
+#[test]
+fn test_guess_fields_strings() {
+    let mut db = DB::new("units_tables", 1, TableDefinition::new(1));
+    db.entries.push(vec![DecodedData::OptionalStringU8("abc".to_owned()), DecodedData::StringU16("Ünit".to_owned()), DecodedData::Integer(0)]);
+    let data = db.save();
+    let (_, entry_count, initial_index) = DB::get_header_data(&data).unwrap();
+
+    assert_eq!(DB::guess_fields(&data, initial_index, entry_count, &[]), vec![
+        FieldGuess { field_type: FieldType::OptionalStringU8, confidence: GuessConfidence::High },
+        FieldGuess { field_type: FieldType::StringU16, confidence: GuessConfidence::High },
+        FieldGuess { field_type: FieldType::Integer, confidence: GuessConfidence::Low },
+    ]);
+}
+
+/// Test to make sure testing a definition (`DB::test_definition()`) decodes only the tables of the same folder and version,
+/// and reports the ones that fail.
+#[test]
+fn test_test_definition() {
+    let table_definition = new_table_definitions_with_references("units_tables", &[]).versions[0].clone();
+    let mut other_version = table_definition.clone();
+    other_version.version = 2;
+    let path = |folder: &str, name: &str| vec!["db".to_owned(), folder.to_owned(), name.to_owned()];
+
+    let packed_files = vec![
+        new_db_packed_file(&path("units_tables", "good"), &table_definition, vec![vec![DecodedData::StringU8("unit_1".to_owned())]]),
+        new_db_packed_file(&path("units_tables", "bad"), &table_definition, vec![vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(5)]]),
+        new_db_packed_file(&path("units_tables", "other_version"), &other_version, vec![vec![DecodedData::Integer(5)]]),
+        new_db_packed_file(&path("factions_tables", "other_table"), &table_definition, vec![vec![DecodedData::Integer(5)]]),
+    ];
+
+    let results = DB::test_definition("units_tables", &table_definition, &packed_files, true);
+    assert_eq!(results.iter().map(|x| x.path[2].as_str()).collect::<Vec<&str>>(), vec!["good", "bad"]);
+    assert!(results.iter().all(|x| x.is_dependency));
+    assert!(results[0].error.is_none());
+
+    // The extra integer of the bad table is left undecoded.
+    let bad_table_size = packed_files[1].get_data().unwrap().len();
+    assert_eq!(results[1].error, Some(Error::from(ErrorKind::PackedFileSizeIsNotWhatWeExpect(bad_table_size, bad_table_size - 4)).to_string()));
+}
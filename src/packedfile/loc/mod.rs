@@ -11,9 +11,24 @@
 // In this file we define the PackedFile type Loc for decoding and encoding it.
 // This is the type used by localisation files.
 
+use byteorder::{LittleEndian, WriteBytesExt};
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_json::Value;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::PathBuf;
+
 use crate::common::coding_helpers::*;
 use crate::error::{ErrorKind, Result};
-use super::DecodedData;
+use crate::schema::{Field, TableDefinition};
+use super::{DecodedData, TSV_HEADER_LOC_PACKEDFILE, TableDiff, decode_table_entries, diff_table_entries, export_json, remove_duplicated_rows};
+
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
 
 /// This const represents the value that every LOC PackedFile has in their first 2 bytes.
 const BYTEORDER_MARK: u16 = 65279; // FF FE
@@ -31,6 +46,10 @@ pub struct Loc {
     pub entries: Vec<Vec<DecodedData>>,
 }
 
+/// `OrderedJsonObject`: This stores the members of a JSON object in the same order they have in the file, as the objects
+/// of `serde_json::Value` are sorted by key.
+struct OrderedJsonObject(Vec<(String, Value)>);
+
 /// Implementation of "Loc".
 impl Loc {
 
@@ -51,31 +70,15 @@ impl Loc {
         if PACKED_FILE_VERSION != decode_integer_u32(&packed_file_data[6..10])? { return Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
         let entry_count = decode_integer_u32(&packed_file_data[10..14])?;
 
-        // Get all the entries and return the Loc.
-        let mut entries = vec![];
+        // Get all the entries, escaping \t and \n in the key and the text to avoid weird behavior.
         let mut index = 14 as usize;
-        for _ in 0..entry_count {
-
-            // Decode the three fields escaping \t and \n to avoid weird behavior.
-            let mut entry = vec![];
-            if index < packed_file_data.len() { 
-                let mut key = decode_packedfile_string_u16(&packed_file_data[index..], &mut index)?;
-                key = key.replace("\t", "\\t").replace("\n", "\\n");
-                entry.push(DecodedData::StringU16(key));
-            } else { return Err(ErrorKind::LocPackedFileCorrupted)? };
-
-            if index < packed_file_data.len() { 
-                let mut text = decode_packedfile_string_u16(&packed_file_data[index..], &mut index)?;
-                text = text.replace("\t", "\\t").replace("\n", "\\n");
-                entry.push(DecodedData::StringU16(text));
-            } else { return Err(ErrorKind::LocPackedFileCorrupted)? };
-            
-            if index < packed_file_data.len() { 
-                let tooltip = decode_packedfile_bool(packed_file_data[index], &mut index)?;
-                entry.push(DecodedData::Boolean(tooltip));
-            } else { return Err(ErrorKind::LocPackedFileCorrupted)? };
-            
-            entries.push(entry);
+        let mut entries = decode_table_entries(packed_file_data, &mut index, entry_count, &TableDefinition::new_loc_definition().fields)?;
+        for entry in &mut entries {
+            for field in entry.iter_mut().take(2) {
+                if let DecodedData::StringU16(data) = field {
+                    *data = data.replace("\t", "\\t").replace("\n", "\\n");
+                }
+            }
         }
 
         // If we are not in the last byte, it means we didn't parse the entire file, which means this file is corrupt.
@@ -85,32 +88,152 @@ impl Loc {
 
     }
 
-    /// This function takes a LocHeader and a LocData and put them together in a Vec<u8>, encoding an
-    /// entire LocFile ready to write on disk.
-    pub fn save(&self) -> Vec<u8> {
+    /// This function returns the definition the provided Loc PackedFile has been written with: the version in his header, and his
+    /// columns in the order they're in the data. To find the order, the layouts with the key/text/tooltip columns in any order are
+    /// tried, in that order, and the first one decoding the full PackedFile is returned.
+    ///
+    /// It fails if the data is not a Loc PackedFile, or no layout can decode it.
+    pub fn read_definition(packed_file_data: &[u8]) -> Result<TableDefinition> {
+        if packed_file_data.len() < 14 { return Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
+        if BYTEORDER_MARK != decode_integer_u16(&packed_file_data[0..2])? { return Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
+        if PACKED_FILE_TYPE != decode_string_u8(&packed_file_data[2..5])? { return Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
+        let version = decode_integer_u32(&packed_file_data[6..10])?;
+        let entry_count = decode_integer_u32(&packed_file_data[10..14])?;
+
+        // Both string columns have the same type, so only the position of the tooltip flag matters.
+        let loc_fields = TableDefinition::new_loc_definition().fields;
+        for layout in &[[0, 1, 2], [0, 2, 1], [2, 0, 1]] {
+            let fields = layout.iter().map(|x| loc_fields[*x].clone()).collect::<Vec<Field>>();
+            let mut index = 14;
+            if decode_table_entries(packed_file_data, &mut index, entry_count, &fields).is_ok() && index == packed_file_data.len() {
+                let mut definition = TableDefinition::new(version as i32);
+                definition.fields = fields;
+                return Ok(definition);
+            }
+        }
+
+        Err(ErrorKind::LocPackedFileCorrupted)?
+    }
+
+    /// This function creates a new decoded Loc from a JSON file with a flat object of keys and texts, like `{ "key": "text" }`.
+    /// The columns are filled following the provided definition: the `key` and `text` columns get the key and the text,
+    /// and any other column (like the tooltip flag) gets its default value. The rows keep the order of the keys in the file.
+    ///
+    /// If any of the values of the object is not a string, it fails.
+    pub fn import_json(definition: &TableDefinition, path: &PathBuf) -> Result<Self> {
+        let object: OrderedJsonObject = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+
+        // The rows keep the order of the keys in the file.
+        let mut entries = vec![];
+        for (key, value) in object.0 {
+            let text = match value {
+                Value::String(text) => text,
+                _ => return Err(ErrorKind::ImportJSONInvalidValue(key))?,
+            };
+
+            // Same escaping as when reading a Loc PackedFile, so they get encoded back properly.
+            let entry = definition.fields.iter().map(|field| match &*field.field_name {
+                "key" => DecodedData::StringU16(key.replace("\t", "\\t").replace("\n", "\\n")),
+                "text" => DecodedData::StringU16(text.replace("\t", "\\t").replace("\n", "\\n")),
+                _ => DecodedData::default_from_field(field),
+            }).collect::<Vec<DecodedData>>();
+            entries.push(entry);
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// This function exports the Loc to a JSON file. See `export_json()` for the format.
+    pub fn export_json(&self, path: &PathBuf) -> Result<()> {
+        export_json(&self.entries, path, &TableDefinition::new_loc_definition(), (TSV_HEADER_LOC_PACKEDFILE, 1))
+    }
 
-        // Create the vector to hold them all.
-        let mut packed_file: Vec<u8> = vec![];
+    /// This function removes from the Loc the entries that are also in any of the provided vanilla Locs, so only the custom ones are left.
+    ///
+    /// It returns true if the Loc has been left empty.
+    pub fn optimize_table(&mut self, vanilla_locs: &[Loc]) -> bool {
+        let vanilla_entries = vanilla_locs.iter().flat_map(|x| x.entries.iter()).collect::<HashSet<&Vec<DecodedData>>>();
+        self.entries.retain(|entry| !vanilla_entries.contains(entry));
+        self.entries.is_empty()
+    }
+
+    /// This function merges the provided Locs into a new one. If `remove_duplicates` is true, only the last entry of each key is kept.
+    pub fn merge(tables: &[&Loc], remove_duplicates: bool) -> Self {
+        let mut merged_table = Self::new();
+        tables.iter().for_each(|table| merged_table.entries.extend_from_slice(&table.entries));
+
+        // The key of a Loc entry is its first column.
+        if remove_duplicates { remove_duplicated_rows(&mut merged_table.entries, &[0]); }
+        merged_table
+    }
+
+    /// This function compares this Loc (the old one) with the provided one (the new one), matching their entries by their keys.
+    pub fn diff(&self, other: &Loc) -> TableDiff {
+        diff_table_entries(&self.entries, &other.entries, &TableDefinition::new_loc_definition())
+    }
+
+    /// This function returns the size of the Loc once encoded, so we can allocate the buffer to encode it at once. If any of the
+    /// fields has an escaped `\t` or `\n`, this is a few bytes bigger than the real size, as they take only one character once encoded.
+    pub fn get_encoded_size(&self) -> usize {
+        14 + self.entries.iter().flat_map(|x| x.iter()).map(|x| x.get_encoded_size()).sum::<usize>()
+    }
+
+    /// This function encodes the entire Loc into the provided writer, without building the encoded Loc in memory first.
+    pub fn encode<W: Write>(&self, writer: &mut W) -> Result<()> {
 
         // Encode the header.
-        packed_file.extend_from_slice(&encode_integer_u16(BYTEORDER_MARK));
-        packed_file.extend_from_slice(&encode_string_u8(PACKED_FILE_TYPE));
-        packed_file.push(0);
-        packed_file.extend_from_slice(&encode_integer_u32(PACKED_FILE_VERSION));
-        packed_file.extend_from_slice(&encode_integer_u32(self.entries.len() as u32));
+        writer.write_u16::<LittleEndian>(BYTEORDER_MARK)?;
+        writer.write_all(PACKED_FILE_TYPE.as_bytes())?;
+        writer.write_u8(0)?;
+        writer.write_u32::<LittleEndian>(PACKED_FILE_VERSION)?;
+        writer.write_u32::<LittleEndian>(self.entries.len() as u32)?;
 
         // Encode the data. In Locs we only have StringU16 and Booleans, so we can safetly ignore the rest.
-        for row in &self.entries {        
+        // Only the fields with something to unescape get copied.
+        for row in &self.entries {
             for cell in row {
                 match *cell {
-                    DecodedData::Boolean(data) => packed_file.push(encode_bool(data)),
-                    DecodedData::StringU16(ref data) => packed_file.extend_from_slice(&encode_packedfile_string_u16(&data.replace("\\t", "\t").replace("\\n", "\n"))),
+                    DecodedData::Boolean(data) => writer.write_u8(encode_bool(data))?,
+                    DecodedData::StringU16(ref data) => {
+                        if data.contains('\\') { write_packedfile_string_u16(writer, &data.replace("\\t", "\t").replace("\\n", "\n"))? }
+                        else { write_packedfile_string_u16(writer, data)? }
+                    }
                     _ => unreachable!()
                 }
             }
         }
 
-        // And return the encoded PackedFile.
+        Ok(())
+    }
+
+    /// This function encodes the entire Loc to a Vec<u8>, ready to write on disk.
+    pub fn save(&self) -> Vec<u8> {
+        let mut packed_file = Vec::with_capacity(self.get_encoded_size());
+
+        // Writing to a Vec<u8> cannot fail.
+        self.encode(&mut packed_file).unwrap();
         packed_file
     }
 }
+
+/// Implementation of `Deserialize` for `OrderedJsonObject`. Anything that's not a JSON object fails as a data error.
+impl<'de> Deserialize<'de> for OrderedJsonObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct OrderedJsonObjectVisitor;
+        impl<'de> Visitor<'de> for OrderedJsonObjectVisitor {
+            type Value = OrderedJsonObject;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Self::Value, A::Error> {
+                let mut members = vec![];
+                while let Some(member) = map.next_entry::<String, Value>()? { members.push(member); }
+                Ok(OrderedJsonObject(members))
+            }
+        }
+
+        deserializer.deserialize_map(OrderedJsonObjectVisitor)
+    }
+}
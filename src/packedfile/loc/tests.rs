@@ -0,0 +1,168 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the Loc PackedFiles, to make sure we don't break their encoding/decoding or any of the functions working over their data.
+
+use crate::common::coding_helpers::*;
+use crate::error::ErrorKind;
+use crate::packedfile::{ChangedRow, DecodedData};
+use crate::packedfile::loc::Loc;
+use crate::schema::TableDefinition;
+
+use std::env::temp_dir;
+use std::fs::{File, remove_file};
+use std::io::Write;
+
+/// Test to make sure encoding a Loc PackedFile gives the same bytes as encoding it field by field, and that the size
+/// we allocate for it is enough to hold it.
+#[test]
+fn test_loc_encoding() {
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Line\\nTab\\t✓".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16(String::new()), DecodedData::Boolean(false)]);
+
+    let mut expected = vec![255, 254];
+    expected.extend_from_slice(b"LOC");
+    expected.push(0);
+    expected.extend_from_slice(&encode_integer_u32(1));
+    expected.extend_from_slice(&encode_integer_u32(2));
+    expected.extend_from_slice(&encode_packedfile_string_u16("key_1"));
+    expected.extend_from_slice(&encode_packedfile_string_u16("Line\nTab\t✓"));
+    expected.push(1);
+    expected.extend_from_slice(&encode_packedfile_string_u16("key_2"));
+    expected.extend_from_slice(&encode_packedfile_string_u16(""));
+    expected.push(0);
+
+    let data = loc.save();
+    assert_eq!(data, expected);
+    assert!(loc.get_encoded_size() >= data.len());
+    assert_eq!(Loc::read(&data).unwrap().entries, loc.entries);
+}
+
+/// Test to make sure decoding a corrupted Loc PackedFile reports the row, field, type and offset that failed.
+#[test]
+fn test_loc_decoding_errors() {
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("One".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Two".to_owned()), DecodedData::Boolean(false)]);
+    let data = loc.save();
+    assert_eq!(data.len(), 56);
+
+    // Invalid boolean in the tooltip of the second row.
+    let mut corrupted = data.clone();
+    corrupted[55] = 2;
+    match Loc::read(&corrupted) {
+        Ok(_) => panic!("Corrupted Loc decoded successfully."),
+        Err(error) => assert_eq!(error.kind(), ErrorKind::TableFieldDecode(1, "tooltip".to_owned(), "Boolean".to_owned(), 55, "the value (2) is not a boolean".to_owned())),
+    }
+
+    // Truncated text of the second row.
+    match Loc::read(&data[..50]) {
+        Ok(_) => panic!("Truncated Loc decoded successfully."),
+        Err(error) => assert_eq!(error.kind(), ErrorKind::TableFieldDecode(1, "text".to_owned(), "StringU16".to_owned(), 47, "the size of the string (6 bytes) is bigger than the remaining bytes (1)".to_owned())),
+    }
+
+    // Size of the key of the first row bigger than the entire file.
+    let mut corrupted = data.clone();
+    corrupted[14] = 255;
+    match Loc::read(&corrupted) {
+        Ok(_) => panic!("Corrupted Loc decoded successfully."),
+        Err(error) => assert_eq!(error.kind(), ErrorKind::TableFieldDecode(0, "key".to_owned(), "StringU16".to_owned(), 14, "the size of the string (510 bytes) is bigger than the remaining bytes (40)".to_owned())),
+    }
+
+    assert!(Loc::read(&data).is_ok());
+}
+
+/// Test to make sure merging Locs (`Loc::merge()`) keeps all the entries, or only the last one of each key if asked.
+#[test]
+fn test_loc_merge() {
+    let mut loc_1 = Loc::new();
+    loc_1.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Old".to_owned()), DecodedData::Boolean(true)]);
+    loc_1.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Two".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut loc_2 = Loc::new();
+    loc_2.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("New".to_owned()), DecodedData::Boolean(false)]);
+
+    assert_eq!(Loc::merge(&[&loc_1, &loc_2], false).entries.len(), 3);
+    assert_eq!(Loc::merge(&[&loc_1, &loc_2], true).entries, vec![
+        vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Two".to_owned()), DecodedData::Boolean(true)],
+        vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("New".to_owned()), DecodedData::Boolean(false)],
+    ]);
+}
+
+/// Test to make sure diffing Locs (`Loc::diff()`) matches the entries by key, including the repeated ones.
+#[test]
+fn test_loc_diff() {
+    let mut old_loc = Loc::new();
+    old_loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("One".to_owned()), DecodedData::Boolean(true)]);
+    old_loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Two".to_owned()), DecodedData::Boolean(true)]);
+    old_loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Dos".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut new_loc = Loc::new();
+    new_loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Two".to_owned()), DecodedData::Boolean(true)]);
+    new_loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Uno".to_owned()), DecodedData::Boolean(true)]);
+
+    let diff = old_loc.diff(&new_loc);
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed, vec![vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Dos".to_owned()), DecodedData::Boolean(true)]]);
+    assert_eq!(diff.changed, vec![ChangedRow {
+        key: vec![DecodedData::StringU16("key_1".to_owned())],
+        fields: vec![("text".to_owned(), DecodedData::StringU16("One".to_owned()), DecodedData::StringU16("Uno".to_owned()))],
+    }]);
+
+    assert!(old_loc.diff(&old_loc).changed.is_empty());
+}
+
+/// Test to make sure importing a JSON file into a Loc PackedFile (`Loc::import_json()`) works, and fails with non-string values.
+#[test]
+fn test_loc_import_json() {
+    let path = temp_dir().join("rpfm_test_loc_import_json.json");
+    let definition = TableDefinition::new_loc_definition();
+
+    File::create(&path).unwrap().write_all(br#"{ "key_2": "Line 1\nLine 2", "key_1": "Text 1" }"#).unwrap();
+    let loc = Loc::import_json(&definition, &path).unwrap();
+    assert_eq!(loc.entries, vec![
+        vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Line 1\\nLine 2".to_owned()), DecodedData::Boolean(false)],
+        vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Text 1".to_owned()), DecodedData::Boolean(false)],
+    ]);
+
+    File::create(&path).unwrap().write_all(br#"{ "key_1": "Text 1", "key_2": { "nested": "Text 2" } }"#).unwrap();
+    match Loc::import_json(&definition, &path) {
+        Err(error) => assert_eq!(error.kind(), ErrorKind::ImportJSONInvalidValue("key_2".to_owned())),
+        Ok(_) => panic!("Importing a JSON with a nested value should fail."),
+    }
+
+    File::create(&path).unwrap().write_all(br#"[ "key_1", "Text 1" ]"#).unwrap();
+    match Loc::import_json(&definition, &path) {
+        Err(error) => assert_eq!(error.kind(), ErrorKind::JsonErrorData),
+        Ok(_) => panic!("Importing a JSON that's not an object should fail."),
+    }
+    remove_file(&path).unwrap();
+}
+
+/// Test to make sure optimizing a Loc PackedFile (`Loc::optimize_table()`) removes only the entries found in any of the vanilla Locs.
+#[test]
+fn test_loc_optimize_table() {
+    let entry = |key: &str, text: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(true)];
+    let vanilla_locs = vec![
+        Loc { entries: vec![entry("unit_1", "Swordsmen"), entry("unit_2", "Spearmen")] },
+        Loc { entries: vec![entry("unit_3", "Archers")] },
+    ];
+
+    // Custom and edited lines must survive, and lines from any of the vanilla Locs must go.
+    let mut loc = Loc { entries: vec![entry("unit_1", "Swordsmen"), entry("unit_2", "Better Spearmen"), entry("unit_3", "Archers"), entry("unit_4", "Knights")] };
+    assert!(!loc.optimize_table(&vanilla_locs));
+    assert_eq!(loc.entries, vec![entry("unit_2", "Better Spearmen"), entry("unit_4", "Knights")]);
+
+    // A Loc with only vanilla lines is left empty.
+    let mut loc = Loc { entries: vec![entry("unit_3", "Archers"), entry("unit_1", "Swordsmen")] };
+    assert!(loc.optimize_table(&vanilla_locs));
+    assert!(loc.entries.is_empty());
+}
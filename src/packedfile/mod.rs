@@ -10,15 +10,23 @@
 
 // In this file are all the Fn, Structs and Impls common to at least 2 PackedFile types.
 
-use csv::{ReaderBuilder, WriterBuilder, QuoteStyle};
+use csv::{ReaderBuilder, Writer, WriterBuilder, QuoteStyle};
+use regex::Regex;
+use rusqlite::{Connection, NO_PARAMS, OpenFlags};
+use rusqlite::types::Value as SQLiteValue;
 use serde_derive::{Serialize, Deserialize};
-
-use std::collections::BTreeMap;
-use std::io::{BufReader, Read, Write};
-use std::fs::File;
+use serde_json::{Number, Value};
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::fs::{File, OpenOptions, remove_file};
 use std::path::PathBuf;
 
 use crate::DEPENDENCY_DATABASE;
+use crate::GlobalMatch;
 use crate::FAKE_DEPENDENCY_DATABASE;
 use crate::common::*;
 use crate::common::coding_helpers::*;
@@ -27,13 +35,17 @@ use crate::packfile::{PackFile, PathType};
 use crate::packfile::packedfile::PackedFile;
 use crate::packedfile::loc::*;
 use crate::packedfile::db::*;
-use crate::schema::{FieldType, Schema, TableDefinition};
+use crate::schema::{Field, FieldType, Schema, TableDefinition};
 
 use crate::SCHEMA;
 pub mod loc;
 pub mod db;
 pub mod rigidmodel;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 /// This enum specifies the PackedFile types we can create.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PackedFileType {
@@ -68,16 +80,353 @@ pub enum DecodedData {
     Float(f32),
     Integer(i32),
     LongInteger(i64),
+    Double(f64),
+    Colour(u32),
     StringU8(String),
     StringU16(String),
     OptionalStringU8(String),
     OptionalStringU16(String),
 }
 
+/// Implementation of `DecodedData`.
+impl DecodedData {
+
+    /// This function returns the default value of a field of the provided type, used when we need to fill a field we don't have data for.
+    pub fn default(field_type: FieldType) -> Self {
+        match field_type {
+            FieldType::Boolean => DecodedData::Boolean(false),
+            FieldType::Float => DecodedData::Float(0.0),
+            FieldType::Integer => DecodedData::Integer(0),
+            FieldType::LongInteger => DecodedData::LongInteger(0),
+            FieldType::Double => DecodedData::Double(0.0),
+            FieldType::Colour => DecodedData::Colour(0),
+            FieldType::StringU8 => DecodedData::StringU8(String::new()),
+            FieldType::StringU16 => DecodedData::StringU16(String::new()),
+            FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(String::new()),
+            FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(String::new()),
+        }
+    }
+
+    /// This function returns the default value of the provided field: his default value from the schema if it has a valid one,
+    /// or the default value of his type otherwise.
+    pub fn default_from_field(field: &Field) -> Self {
+        field.field_default_value.as_ref()
+            .and_then(|x| parse_tsv_field(field.field_type, x, 0, 0).ok())
+            .unwrap_or_else(|| Self::default(field.field_type))
+    }
+
+    /// This function returns the data of the field as a String, the same way it's shown in the tables.
+    pub fn data_to_string(&self) -> String {
+        match self {
+            DecodedData::Boolean(data) => data.to_string(),
+            DecodedData::Float(data) => data.to_string(),
+            DecodedData::Integer(data) => data.to_string(),
+            DecodedData::LongInteger(data) => data.to_string(),
+            DecodedData::Double(data) => data.to_string(),
+            DecodedData::Colour(data) => format!("{:06X}", data),
+            DecodedData::StringU8(data) |
+            DecodedData::StringU16(data) |
+            DecodedData::OptionalStringU8(data) |
+            DecodedData::OptionalStringU16(data) => data.to_owned(),
+        }
+    }
+
+    /// This function returns the amount of bytes the field takes once encoded in a DB Table or Loc PackedFile.
+    pub fn get_encoded_size(&self) -> usize {
+        match self {
+            DecodedData::Boolean(_) => 1,
+            DecodedData::Float(_) => 4,
+            DecodedData::Integer(_) => 4,
+            DecodedData::LongInteger(_) => 8,
+            DecodedData::Double(_) => 8,
+            DecodedData::Colour(_) => 4,
+            DecodedData::StringU8(data) => 2 + data.len(),
+            DecodedData::StringU16(data) => 2 + data.encode_utf16().count() * 2,
+            DecodedData::OptionalStringU8(data) => if data.is_empty() { 1 } else { 3 + data.len() },
+            DecodedData::OptionalStringU16(data) => if data.is_empty() { 1 } else { 3 + data.encode_utf16().count() * 2 },
+        }
+    }
+
+    /// This function decodes a field of the provided type from the data, starting at `index` and moving it to the byte where the next field starts.
+    ///
+    /// If it fails, the index is left untouched and we return the reason why it failed, so the caller can report it with the position of the field.
+    pub fn decode(data: &[u8], index: &mut usize, field_type: FieldType) -> std::result::Result<Self, String> {
+        let insufficient_bytes = || "insufficient bytes to decode".to_owned();
+        match field_type {
+            FieldType::Boolean => match data.get(*index) {
+                Some(byte) => decode_packedfile_bool(*byte, index).map(DecodedData::Boolean).map_err(|_| format!("the value ({}) is not a boolean", byte)),
+                None => Err(insufficient_bytes()),
+            },
+            FieldType::Float => if data.get(*index + 3).is_some() {
+                decode_packedfile_float_f32(&data[*index..(*index + 4)], index).map(DecodedData::Float).map_err(|_| "the value is not a valid F32".to_owned())
+            } else { Err(insufficient_bytes()) },
+            FieldType::Integer => if data.get(*index + 3).is_some() {
+                decode_packedfile_integer_i32(&data[*index..(*index + 4)], index).map(DecodedData::Integer).map_err(|_| "the value is not a valid I32".to_owned())
+            } else { Err(insufficient_bytes()) },
+            FieldType::LongInteger => if data.get(*index + 7).is_some() {
+                decode_packedfile_integer_i64(&data[*index..(*index + 8)], index).map(DecodedData::LongInteger).map_err(|_| "the value is not a valid I64".to_owned())
+            } else { Err(insufficient_bytes()) },
+            FieldType::Double => if data.get(*index + 7).is_some() {
+                decode_packedfile_float_f64(&data[*index..(*index + 8)], index).map(DecodedData::Double).map_err(|_| "the value is not a valid F64".to_owned())
+            } else { Err(insufficient_bytes()) },
+            FieldType::Colour => if data.get(*index + 3).is_some() {
+                decode_packedfile_integer_u32(&data[*index..(*index + 4)], index).map(DecodedData::Colour).map_err(|_| "the value is not a valid Colour".to_owned())
+            } else { Err(insufficient_bytes()) },
+            FieldType::StringU8 => if data.get(*index + 1).is_some() {
+                let start = *index;
+                decode_packedfile_string_u8(&data[start..], index).map(DecodedData::StringU8).map_err(|_| get_string_decoding_error(data, start, 1, "UTF-8 String"))
+            } else { Err(insufficient_bytes()) },
+            FieldType::StringU16 => if data.get(*index + 1).is_some() {
+                let start = *index;
+                decode_packedfile_string_u16(&data[start..], index).map(DecodedData::StringU16).map_err(|_| get_string_decoding_error(data, start, 2, "UTF-16 String"))
+            } else { Err(insufficient_bytes()) },
+            FieldType::OptionalStringU8 => if data.get(*index).is_some() {
+                let start = *index;
+                decode_packedfile_optional_string_u8(&data[start..], index).map(DecodedData::OptionalStringU8).map_err(|_| match data[start] {
+                    0 | 1 => get_string_decoding_error(data, start + 1, 1, "Optional UTF-8 String"),
+                    byte => format!("the value ({}) before the Optional UTF-8 String is not a boolean", byte),
+                })
+            } else { Err(insufficient_bytes()) },
+            FieldType::OptionalStringU16 => if data.get(*index).is_some() {
+                let start = *index;
+                decode_packedfile_optional_string_u16(&data[start..], index).map(DecodedData::OptionalStringU16).map_err(|_| match data[start] {
+                    0 | 1 => get_string_decoding_error(data, start + 1, 2, "Optional UTF-16 String"),
+                    byte => format!("the value ({}) before the Optional UTF-16 String is not a boolean", byte),
+                })
+            } else { Err(insufficient_bytes()) },
+        }
+    }
+
+    /// This function returns the data of the field as a JSON value of his type. Floats JSON cannot hold (NaN and infinites) become null.
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            DecodedData::Boolean(data) => Value::Bool(*data),
+            DecodedData::Float(data) => data.to_string().parse::<f64>().ok().and_then(Number::from_f64).map_or(Value::Null, Value::Number),
+            DecodedData::Integer(data) => Value::from(*data),
+            DecodedData::LongInteger(data) => Value::from(*data),
+            DecodedData::Double(data) => Number::from_f64(*data).map_or(Value::Null, Value::Number),
+            DecodedData::Colour(_) => Value::String(self.data_to_string()),
+            DecodedData::StringU8(data) |
+            DecodedData::StringU16(data) |
+            DecodedData::OptionalStringU8(data) |
+            DecodedData::OptionalStringU16(data) => Value::String(data.to_owned()),
+        }
+    }
+}
+
+/// Implementation of `Eq` for `DecodedData`, so rows can be used as keys of HashSets and HashMaps.
+///
+/// This is not true for floats with a NaN, but as a NaN is never equal to anything, the rows with one are just never found.
+impl Eq for DecodedData {}
+
+/// Implementation of `Hash` for `DecodedData`. Floats are hashed by their bits, except the zeros, as `0.0 == -0.0`.
+impl Hash for DecodedData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            DecodedData::Boolean(data) => data.hash(state),
+            DecodedData::Float(data) => if *data == 0.0 { 0u32.hash(state) } else { data.to_bits().hash(state) },
+            DecodedData::Integer(data) => data.hash(state),
+            DecodedData::LongInteger(data) => data.hash(state),
+            DecodedData::Double(data) => if *data == 0.0 { 0u64.hash(state) } else { data.to_bits().hash(state) },
+            DecodedData::Colour(data) => data.hash(state),
+            DecodedData::StringU8(data) |
+            DecodedData::StringU16(data) |
+            DecodedData::OptionalStringU8(data) |
+            DecodedData::OptionalStringU16(data) => data.hash(state),
+        }
+    }
+}
+
+/// This struct holds the loc coverage of a DB Table: how many of the loc keys his rows need exist
+/// in the PackFile with text, and how many of them exist but are blank or don't exist at all.
+#[derive(Clone, Debug)]
+pub struct LocCoverage {
+    pub path: Vec<String>,
+    pub expected: usize,
+    pub present: usize,
+    pub blank: usize,
+    pub missing: usize,
+}
+
+/// Implementation of `LocCoverage`.
+impl LocCoverage {
+
+    /// This function returns the percentage of the expected loc keys that exist and have text.
+    pub fn get_coverage(&self) -> f32 {
+        if self.expected == 0 { 100.0 }
+        else { self.present as f32 * 100.0 / self.expected as f32 }
+    }
+}
+
+/// This struct holds a loc key needed by a row of a DB Table that doesn't exist in any Loc PackedFile,
+/// neither in the PackFile nor in the dependency database.
+#[derive(Clone, Debug)]
+pub struct MissingLocKey {
+    pub path: Vec<String>,
+    pub row_key: String,
+    pub loc_key: String,
+}
+
+/// This struct holds a loc key defined more than once in the Loc PackedFiles of a PackFile,
+/// with the path of the Loc PackedFile and the text of each one of his entries.
+#[derive(Clone, Debug)]
+pub struct DuplicatedLocKey {
+    pub key: String,
+    pub entries: Vec<(Vec<String>, String)>,
+}
+
+/// This struct holds a value changed (or to be changed) when renaming the prefix of the keys of a PackFile,
+/// with the path of his PackedFile, his row and the name of his column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyPrefixChange {
+    pub path: Vec<String>,
+    pub row: usize,
+    pub column: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// This struct holds a key of a DB Table or Loc PackedFile containing non-ASCII characters. The positions
+/// are the indexes (in characters, not bytes) of the non-ASCII characters within the key.
+#[derive(Clone, Debug)]
+pub struct NonAsciiKey {
+    pub row: usize,
+    pub column: String,
+    pub key: String,
+    pub positions: Vec<usize>,
+}
+
+/// This struct holds the differences between two versions of the same table, with the definition used to compare them.
+/// Rows are matched by their key columns: rows only in the new table are added, rows only in the old one are removed,
+/// and rows in both with different values are changed.
+#[derive(Clone, Debug)]
+pub struct TableDiff {
+    pub table_definition: TableDefinition,
+    pub added: Vec<Vec<DecodedData>>,
+    pub removed: Vec<Vec<DecodedData>>,
+    pub changed: Vec<ChangedRow>,
+}
+
+/// This struct holds a row present in both versions of a table, with the values of his key columns
+/// and the old and new values of each one of his changed fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangedRow {
+    pub key: Vec<DecodedData>,
+    pub fields: Vec<(String, DecodedData, DecodedData)>,
+}
+
+/// This enum holds an operation of an edit script (see `run_edit_script()`). Rows start at 0, and columns are referenced by name.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum EditOperation {
+    EditCell {
+        path: Vec<String>,
+        row: usize,
+        column: String,
+        value: String,
+    },
+    AddRow {
+        path: Vec<String>,
+    },
+    DeleteRow {
+        path: Vec<String>,
+        row: usize,
+    },
+}
+
+/// This struct holds a string cell of a DB Table or Loc PackedFile longer than expected, with his length in characters.
+#[derive(Clone, Debug)]
+pub struct OversizedCell {
+    pub path: Vec<String>,
+    pub row: usize,
+    pub column: String,
+    pub length: usize,
+}
+
+/// This enum holds where the row referenced by a cell has been found (see `find_referenced_row()`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReferencedRow {
+
+    /// The row is in a table of the open PackFile. It contains the path of the table, and the row and column of the referenced cell.
+    PackFile(Vec<String>, usize, usize),
+
+    /// The row is only in the dependency database. It contains the path of the table, the names of his columns and the row.
+    Dependency(Vec<String>, Vec<String>, Vec<String>),
+}
+
+/// This struct holds a DB Table or Loc PackedFile exported to JSON (see `export_json()`). It contains the name and version
+/// of the table and his fields, so it can be understood (and read back) without the schema.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TableJSON {
+    pub table_name: String,
+    pub version: i32,
+    pub fields: Vec<FieldJSON>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// This struct holds a field of a table exported to JSON.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FieldJSON {
+    pub name: String,
+    pub field_type: FieldType,
+}
+
+/// This struct holds the counters of the edits done to the open PackFile since it was opened.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SessionEditStats {
+    pub rows_added: u64,
+    pub rows_removed: u64,
+    pub rows_edited: u64,
+    pub files_added: u64,
+    pub files_deleted: u64,
+    pub files_renamed: u64,
+}
+
+/// Implementation of `SessionEditStats`.
+impl SessionEditStats {
+
+    /// This function updates the row counters comparing the entries of a table before and after saving it.
+    ///
+    /// Rows are compared by content, not by position, so deleting a row in the middle of the table doesn't count
+    /// every row after it as edited. Each pair of a removed and an added row is counted as an edited row.
+    pub fn record_table_edit(&mut self, old_entries: &[Vec<DecodedData>], new_entries: &[Vec<DecodedData>]) {
+        let mut rows: BTreeMap<Vec<String>, i64> = BTreeMap::new();
+        for row in old_entries { *rows.entry(row.iter().map(|x| x.data_to_string()).collect()).or_insert(0) -= 1; }
+        for row in new_entries { *rows.entry(row.iter().map(|x| x.data_to_string()).collect()).or_insert(0) += 1; }
+
+        let added = rows.values().filter(|x| **x > 0).sum::<i64>() as u64;
+        let removed = rows.values().filter(|x| **x < 0).map(|x| -x).sum::<i64>() as u64;
+        let edited = added.min(removed);
+        self.rows_edited += edited;
+        self.rows_added += added - edited;
+        self.rows_removed += removed - edited;
+    }
+
+    /// This function updates the file counters comparing the paths of the PackedFiles of the PackFile before and after an operation.
+    pub fn record_files_change(&mut self, old_paths: &[Vec<String>], new_paths: &[Vec<String>]) {
+        let old_paths = old_paths.iter().collect::<BTreeSet<&Vec<String>>>();
+        let new_paths = new_paths.iter().collect::<BTreeSet<&Vec<String>>>();
+        self.files_added += new_paths.difference(&old_paths).count() as u64;
+        self.files_deleted += old_paths.difference(&new_paths).count() as u64;
+    }
+
+    /// This function updates the renamed files counter comparing the paths of the PackedFiles of the PackFile before and after renaming them.
+    pub fn record_files_rename(&mut self, old_paths: &[Vec<String>], new_paths: &[Vec<String>]) {
+        let old_paths = old_paths.iter().collect::<BTreeSet<&Vec<String>>>();
+        self.files_renamed += new_paths.iter().filter(|x| !old_paths.contains(x)).count() as u64;
+    }
+}
+
 /// Const to use in the header of TSV PackedFiles.
 pub const TSV_HEADER_PACKFILE_LIST: &str = "PackFile List";
 pub const TSV_HEADER_LOC_PACKEDFILE: &str = "Loc PackedFile";
 
+/// Const with the bytes of the UTF-8 BOM, as some text PackedFiles start with it.
+pub const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
+/// Const with the bytes of the UTF-16 (Little Endian) BOM, used to detect UTF-16 text PackedFiles.
+pub const UTF16_LE_BOM: [u8; 2] = [0xFF, 0xFE];
+
 //----------------------------------------------------------------//
 // Generic Functions for PackedFiles.
 //----------------------------------------------------------------//
@@ -126,6 +475,17 @@ pub fn get_packed_file_type(path: &[String]) -> DecodeablePackedFileType {
     else { DecodeablePackedFileType::None }
 }
 
+/// This function loads the data of the PackedFiles of the provided PackFile whose path passes the provided filter.
+///
+/// Due to how mutability works, we cannot load the data of a PackedFile while decoding it, as we need the PackFile to get the
+/// definition of the tables. So we have first to load the data of every PackedFile we want to decode, then decode them.
+fn load_packed_files_data<F: Fn(&[String]) -> bool>(pack_file: &mut PackFile, filter: F) -> Result<()> {
+    for packed_file in pack_file.packed_files.iter_mut().filter(|x| filter(&x.path)) {
+        packed_file.load_data()?;
+    }
+    Ok(())
+}
+
 /// This function is used to create a PackedFile outtanowhere. It returns his new path.
 pub fn create_packed_file(
     pack_file: &mut PackFile,
@@ -170,13 +530,18 @@ pub fn create_packed_file(
 
 /// This function merges (if it's possible) the provided DB and LOC tables into one with the name and, if asked,
 /// it deletes the source files. Table_type means true: DB, false: LOC.
+///
+/// DB Tables of different versions are converted to the newest one before merging them. If `remove_duplicates` is true,
+/// only the last row of each key is kept (see `DB::merge()` and `Loc::merge()`).
 pub fn merge_tables( 
     pack_file: &mut PackFile,
     source_paths: &[Vec<String>],
     name: &str,
     delete_source_paths: bool,
     table_type: bool,
+    remove_duplicates: bool,
 ) -> Result<(Vec<String>, Vec<PathType>)> {
+    if source_paths.is_empty() { return Err(ErrorKind::InvalidFilesForMerging)? }
     
     let mut db_files = vec![];
     let mut loc_files = vec![];
@@ -188,7 +553,7 @@ pub fn merge_tables(
         
         if table_type { 
             if let Some(ref schema) = *SCHEMA.lock().unwrap() {
-                db_files.push(DB::read(&packed_file_data, &path[1], &schema)?); 
+                db_files.push(DB::read(&packed_file_data, pack_file.get_table_definition_name(&path[1]), &schema)?); 
             }
             else { return Err(ErrorKind::SchemaNotFound)? }
         }
@@ -197,35 +562,10 @@ pub fn merge_tables(
 
     // Merge them all into one, and return error if any problem arise.
     let packed_file_data = if table_type {
-        let mut final_entries_list = vec![];
-        let mut version = -2;
-        let mut table_definition = TableDefinition::new(0);
-
-        for table in &mut db_files {
-            if version == -2 { 
-                version = table.version; 
-                table_definition = table.table_definition.clone();
-            }
-            else if table.version != version { return Err(ErrorKind::InvalidFilesForMerging)? }
-
-            final_entries_list.append(&mut table.entries);
-        }
-
-        let mut new_table = DB::new(&db_files[0].db_type, version, table_definition);
-        new_table.entries = final_entries_list;
-        new_table.save()
+        let table_definition = db_files.iter().max_by_key(|x| x.version).unwrap().table_definition.clone();
+        DB::merge(&db_files.iter().collect::<Vec<&DB>>(), &table_definition, remove_duplicates)?.save()
     }
-
-    else {
-        let mut final_entries_list = vec![];
-        for table in &mut loc_files {
-            final_entries_list.append(&mut table.entries);
-        }
-
-        let mut new_table = Loc::new();
-        new_table.entries = final_entries_list;
-        new_table.save()
-    };
+    else { Loc::merge(&loc_files.iter().collect::<Vec<&Loc>>(), remove_duplicates).save() };
 
     // And then, we reach the part where we have to do the "saving to PackFile" stuff.
     let mut path = source_paths[0].to_vec();
@@ -254,6 +594,18 @@ pub fn merge_tables(
     Ok((added_path, tree_paths))
 }
 
+/// This function returns the names (`xxx_tables`) of the tables referenced by the fields of the provided definition, without duplicates.
+pub fn get_referenced_tables(table_definition: &TableDefinition) -> Vec<String> {
+    let mut tables = table_definition.fields.iter()
+        .filter_map(|x| x.field_is_reference.as_ref())
+        .filter(|(table, column)| !table.is_empty() && !column.is_empty())
+        .map(|(table, _)| format!("{}_tables", table))
+        .collect::<Vec<String>>();
+    tables.sort();
+    tables.dedup();
+    tables
+}
+
 /// This function retrieves the entire Dependency Data for a given table definition.
 ///
 /// NOTE: It's here and not in DB because we may get an use for this in LOC PackedFiles.
@@ -315,7 +667,7 @@ pub fn get_dependency_data(
 
                 // The same for our own PackFile.
                 let mut iter = pack_file.packed_files.iter();
-                while let Some(packed_file) = iter.find(|x| x.path.starts_with(&["db".to_owned(), format!("{}_tables", dependency_data.0)])) {
+                while let Some(packed_file) = iter.find(|x| x.path.len() == 3 && x.path[0] == "db" && pack_file.get_table_definition_name(&x.path[1]) == format!("{}_tables", dependency_data.0)) {
                     if let Ok(packed_file_data) = packed_file.get_data() {
                         if let Ok(table) = DB::read(&packed_file_data, &format!("{}_tables", dependency_data.0), &schema) {
                             if let Some(column_index) = table.table_definition.fields.iter().position(|x| x.field_name == dependency_data.1) {
@@ -348,6 +700,85 @@ pub fn get_dependency_data(
     dep_data
 }
 
+/// This function returns the position of the first row of the provided table with the provided value in the provided column,
+/// and the position of that column. If the column doesn't exist or no row has the value, it returns None.
+pub fn find_row_by_value(table: &DB, column_name: &str, value: &str) -> Option<(usize, usize)> {
+    let column = table.table_definition.fields.iter().position(|x| x.field_name == column_name)?;
+    let row = table.entries.iter().position(|x| x[column].data_to_string() == value)?;
+    Some((row, column))
+}
+
+/// This function searches for the row a cell references, using the table and column of the reference (as they're in the schema)
+/// and the value of the cell. The open PackFile is checked first and, if the row is not there, the dependency database.
+///
+/// Like with `get_dependency_data()`, we get the LazyStatics as arguments instead of locking them here.
+pub fn find_referenced_row(
+    table_name: &str,
+    column_name: &str,
+    value: &str,
+    schema: &Schema,
+    dep_db: &mut Vec<PackedFile>,
+    pack_file: &PackFile
+) -> Option<ReferencedRow> {
+    let table_folder = format!("{}_tables", table_name);
+    for packed_file in pack_file.packed_files.iter().filter(|x| x.path.len() == 3 && x.path[0] == "db" && pack_file.get_table_definition_name(&x.path[1]) == table_folder) {
+        if let Ok(packed_file_data) = packed_file.get_data() {
+            if let Ok(table) = DB::read(&packed_file_data, &table_folder, &schema) {
+                if let Some((row, column)) = find_row_by_value(&table, column_name, value) {
+                    return Some(ReferencedRow::PackFile(packed_file.path.to_vec(), row, column));
+                }
+            }
+        }
+    }
+
+    for packed_file in dep_db.iter_mut().filter(|x| x.path.len() == 3 && x.path[0] == "db" && x.path[1] == table_folder) {
+        if let Ok(packed_file_data) = packed_file.get_data_and_keep_it() {
+            if let Ok(table) = DB::read(&packed_file_data, &table_folder, &schema) {
+                if let Some((row, _)) = find_row_by_value(&table, column_name, value) {
+                    let columns = table.table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect();
+                    let row = table.entries[row].iter().map(|x| x.data_to_string()).collect();
+                    return Some(ReferencedRow::Dependency(packed_file.path.to_vec(), columns, row));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// This function searches all the DB Tables of the provided PackFile for cells referencing the provided value of the provided
+/// column. Only columns referencing that column (as they're in the schema) are checked, so a value like `1` only matches real usages.
+pub fn find_usages(
+    table_name: &str,
+    column_name: &str,
+    value: &str,
+    schema: &Schema,
+    pack_file: &PackFile
+) -> Vec<GlobalMatch> {
+    let mut matches = vec![];
+    for packed_file in pack_file.packed_files.iter().filter(|x| x.path.len() == 3 && x.path[0] == "db") {
+        if let Ok(packed_file_data) = packed_file.get_data() {
+            if let Ok(table) = DB::read(&packed_file_data, pack_file.get_table_definition_name(&packed_file.path[1]), &schema) {
+                let mut matches_in_file = vec![];
+                for (column, field) in table.table_definition.fields.iter().enumerate() {
+                    if let Some((ref ref_table, ref ref_column)) = field.field_is_reference {
+                        if ref_table == table_name && ref_column == column_name {
+                            for (row, entry) in table.entries.iter().enumerate() {
+                                if entry[column].data_to_string() == value {
+                                    matches_in_file.push((field.field_name.to_owned(), column as i32, row as i64, value.to_owned()));
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !matches_in_file.is_empty() { matches.push(GlobalMatch::DB((packed_file.path.to_vec(), matches_in_file))); }
+            }
+        }
+    }
+    matches
+}
+
 /// This function checks all the DB Tables of the provided PackFile for dependency errors.
 pub fn check_tables( 
     pack_file: &mut PackFile,
@@ -371,7 +802,7 @@ pub fn check_tables(
 
             for packed_file in pack_file.packed_files.iter() {
                 if packed_file.path.starts_with(&["db".to_owned()]) {
-                    if let Ok(db_data) = db::DB::read(&(packed_file.get_data().unwrap()), &packed_file.path[1], &schema) {
+                    if let Ok(db_data) = db::DB::read(&(packed_file.get_data().unwrap()), pack_file.get_table_definition_name(&packed_file.path[1]), &schema) {
                         let dep_data = get_dependency_data(&db_data.table_definition, &schema, &mut dep_db, &fake_dep_db, &pack_file);
 
                         // If we got some dependency data (the referenced tables actually exists), check every
@@ -416,227 +847,2574 @@ pub fn check_tables(
     }
 }
 
-//----------------------------------------------------------------//
-// TSV Functions for PackedFiles.
-//----------------------------------------------------------------//
+/// This function tries to decode every DB Table and Loc PackedFile of the provided PackFile with the provided schema,
+/// to check if the PackFile is compatible with the game that schema belongs to.
+///
+/// It returns the paths of the PackedFiles that failed to decode, with the reason. If it's empty, everything decoded fine.
+pub fn validate_pack_for_schema(
+    pack_file: &mut PackFile,
+    schema: &Schema,
+) -> Result<Vec<(Vec<String>, String)>> {
 
-/// This function imports a TSV file and loads his contents into a DB Table.
-pub fn import_tsv(
-    definition: &TableDefinition,
-    path: &PathBuf,
-    name: &str,
-    version: i32,
-) -> Result<Vec<Vec<DecodedData>>> {
+    load_packed_files_data(pack_file, |path| !path.is_empty() && match get_packed_file_type(path) {
+        DecodeablePackedFileType::DB | DecodeablePackedFileType::Loc => true,
+        _ => false,
+    })?;
 
-    // We want the reader to have no quotes, tab as delimiter and custom headers, because otherwise
-    // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
-    let mut reader = ReaderBuilder::new()
-        .delimiter(b'\t')
-        .quoting(false)
-        .has_headers(false)
-        .flexible(true)
-        .from_path(&path)?;
+    let mut failures = vec![];
+    for packed_file in pack_file.packed_files.iter() {
+        if !packed_file.path.is_empty() {
+            let result = match get_packed_file_type(&packed_file.path) {
+                DecodeablePackedFileType::DB if packed_file.path.len() == 3 => DB::read(&packed_file.get_data()?, pack_file.get_table_definition_name(&packed_file.path[1]), schema).map(|_| ()),
+                DecodeablePackedFileType::DB => Err(Error::from(ErrorKind::DBTableIsNotADBTable)),
+                DecodeablePackedFileType::Loc => Loc::read(&packed_file.get_data()?).map(|_| ()),
+                _ => Ok(()),
+            };
 
-    // If we succesfully load the TSV file into a reader, check the first two lines to ensure 
-    // it's a valid TSV for our specific DB/Loc.
-    let mut entries = vec![];
-    for (row, record) in reader.records().enumerate() {
-        if let Ok(record) = record {
+            if let Err(error) = result {
+                failures.push((packed_file.path.to_vec(), error.to_string()));
+            }
+        }
+    }
 
-            // The first line should contain the "table_folder_name"/"Loc PackedFile/PackFile List", and the version (1 for Locs).
-            if row == 0 { 
-                if record.get(0).unwrap_or("error") != name { return Err(ErrorKind::ImportTSVWrongTypeTable)?; }
-                if record.get(1).unwrap_or("-1").parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVInvalidVersion))? != version { 
-                    return Err(ErrorKind::ImportTSVWrongVersion)?;
-                }
+    Ok(failures)
+}
+
+/// This function finds the PackedFiles of the provided PackFile that are byte-identical to the vanilla PackedFile
+/// with the same path in the provided dependency database. These overrides do nothing, so they can be removed.
+///
+/// It returns the paths of the redundant PackedFiles.
+pub fn find_redundant_overrides(
+    pack_file: &mut PackFile,
+    dependency_database: &mut [PackedFile],
+) -> Result<Vec<Vec<String>>> {
+
+    let mut redundant_paths = vec![];
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if let Some(vanilla_packed_file) = dependency_database.iter_mut().find(|x| x.path == packed_file.path) {
+            if packed_file.get_data_and_keep_it()? == vanilla_packed_file.get_data_and_keep_it()? {
+                redundant_paths.push(packed_file.path.to_vec());
             }
+        }
+    }
 
-            // The second line contains the column headers. Is just to help people in other programs,
-            // not needed to be check.
-            else if row == 1 { continue }
+    Ok(redundant_paths)
+}
 
-            // Then read the rest of the rows as a normal TSV.
-            else if record.len() == definition.fields.len() {
-                let mut entry = vec![];
-                for (column, field) in record.iter().enumerate() {
-                    match definition.fields[column].field_type {
-                        FieldType::Boolean => {
-                            let value = field.to_lowercase();
-                            if value == "true" || value == "1" { entry.push(DecodedData::Boolean(true)); }
-                            else if value == "false" || value == "0" { entry.push(DecodedData::Boolean(false)); }
-                            else { return Err(ErrorKind::ImportTSVIncorrectRow(row, column))?; }
+/// This function finds the assets (images and models) of the provided PackFile that are not referenced by any table,
+/// neither from the PackFile or from the provided dependency database, so they can be removed. The columns with paths
+/// to files are taken from the schema. Paths are compared case-insensitively, with or without extension.
+///
+/// It returns the paths of the unreferenced assets.
+pub fn find_unreferenced_assets(
+    pack_file: &mut PackFile,
+    dependency_database: &mut [PackedFile],
+    schema: &Schema,
+) -> Result<Vec<Vec<String>>> {
+
+    let normalize = |path: &str| path.trim().replace('\\', "/").trim_start_matches('/').to_lowercase();
+    let mut referenced_paths = BTreeSet::new();
+    let table_aliases = pack_file.table_aliases.clone();
+    for packed_file in pack_file.packed_files.iter_mut().chain(dependency_database.iter_mut()) {
+        if packed_file.path.len() == 3 && packed_file.path[0] == "db" {
+            let table_name = table_aliases.get(&packed_file.path[1]).unwrap_or(&packed_file.path[1]).to_owned();
+            if let Ok(db) = DB::read(&packed_file.get_data_and_keep_it()?, &table_name, schema) {
+                for (column, field) in db.table_definition.fields.iter().enumerate() {
+                    if let Some(ref relative_path) = field.field_is_filename {
+                        for entry in &db.entries {
+                            let value = entry[column].data_to_string();
+                            if value.trim().is_empty() { continue; }
+                            referenced_paths.insert(normalize(&value));
+                            if !relative_path.is_empty() {
+                                referenced_paths.insert(normalize(&format!("{}/{}", relative_path, value.trim())));
+                            }
                         }
-                        FieldType::Float => entry.push(DecodedData::Float(field.parse::<f32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
-                        FieldType::Integer => entry.push(DecodedData::Integer(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
-                        FieldType::LongInteger => entry.push(DecodedData::LongInteger(field.parse::<i64>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
-                        FieldType::StringU8 => entry.push(DecodedData::StringU8(field.to_owned())),
-                        FieldType::StringU16 => entry.push(DecodedData::StringU16(field.to_owned())),
-                        FieldType::OptionalStringU8 => entry.push(DecodedData::OptionalStringU8(field.to_owned())),
-                        FieldType::OptionalStringU16 => entry.push(DecodedData::OptionalStringU16(field.to_owned())),
                     }
                 }
-                entries.push(entry);
             }
-
-            // If it fails here, return an error with the len of the record instead a field.
-            else { return Err(ErrorKind::ImportTSVIncorrectRow(row, record.len()))?; }
         }
+    }
+
+    let mut unreferenced_paths = vec![];
+    for packed_file in &pack_file.packed_files {
+        match get_packed_file_type(&packed_file.path) {
+            DecodeablePackedFileType::Image | DecodeablePackedFileType::RigidModel => {
+                let path = normalize(&packed_file.path.join("/"));
+                let path_no_extension = match path.rfind('.') {
+                    Some(index) => path[..index].to_owned(),
+                    None => path.to_owned(),
+                };
 
-        else { return Err(ErrorKind::ImportTSVIncorrectRow(row, 0))?; }
+                if !referenced_paths.contains(&path) && !referenced_paths.contains(&path_no_extension) {
+                    unreferenced_paths.push(packed_file.path.to_vec());
+                }
+            }
+            _ => {}
+        }
     }
 
-    // If we reached this point without errors, we replace the old data with the new one and return success.
-    Ok(entries)
+    Ok(unreferenced_paths)
 }
 
-/// This function creates a TSV file with the contents of the DB/Loc PackedFile.
-pub fn export_tsv(
-    data: &[Vec<DecodedData>], 
-    path: &PathBuf,
-    headers: &[String], 
-    first_row_data: (&str, i32)
-) -> Result<()> {
+/// This struct represents a folder of the tree of a PackFile, used to export it as HTML (see `get_tree_as_html()`).
+#[derive(Default)]
+struct HtmlTreeFolder {
+    folders: BTreeMap<String, HtmlTreeFolder>,
+    files: Vec<(String, String, u32)>,
+}
 
-    // We want the writer to have no quotes, tab as delimiter and custom headers, because otherwise
-    // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
-    let mut writer = WriterBuilder::new()
-        .delimiter(b'\t')
-        .quote_style(QuoteStyle::Never)
-        .has_headers(false)
-        .flexible(true)
-        .from_writer(vec![]);
+/// This function escapes the characters of the provided text that have a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
 
-    // We serialize the info of the table (name and version) in the first line, and the column names in the second one.
-    writer.serialize(first_row_data)?;
-    writer.serialize(headers)?;
+/// This function generates a HTML page with the tree of the provided PackFile, with the type and size of each PackedFile.
+/// Folders are collapsible `<details>` elements, with their subfolders first and their files after them, both sorted by name.
+pub fn get_tree_as_html(pack_file: &PackFile) -> String {
 
-    // Then we serialize each entry in the DB Table.
-    for entry in data { writer.serialize(&entry)?; }
+    let mut tree = HtmlTreeFolder::default();
+    for packed_file in &pack_file.packed_files {
+        if packed_file.path.is_empty() { continue; }
+        let mut folder = &mut tree;
+        for name in &packed_file.path[..packed_file.path.len() - 1] {
+            folder = folder.folders.entry(name.to_owned()).or_insert_with(HtmlTreeFolder::default);
+        }
 
-    // Then, we try to write it on disk. If there is an error, report it.
-    let mut file = File::create(&path)?;
-    file.write_all(String::from_utf8(writer.into_inner().unwrap())?.as_bytes())?;
+        let packed_file_type = match get_packed_file_type(&packed_file.path) {
+            DecodeablePackedFileType::DB => "DB Table",
+            DecodeablePackedFileType::Loc => "Loc PackedFile",
+            DecodeablePackedFileType::Text => "Text",
+            DecodeablePackedFileType::Image => "Image",
+            DecodeablePackedFileType::RigidModel => "RigidModel",
+            DecodeablePackedFileType::None => "Unknown",
+        };
+        folder.files.push((packed_file.path.last().unwrap().to_owned(), packed_file_type.to_owned(), packed_file.get_size()));
+    }
 
-    Ok(())
-}
+    fn folder_to_html(folder: &HtmlTreeFolder, html: &mut String) {
+        html.push_str("<ul>\n");
+        for (name, subfolder) in &folder.folders {
+            html.push_str(&format!("<li><details><summary>{}</summary>\n", escape_html(name)));
+            folder_to_html(subfolder, html);
+            html.push_str("</details></li>\n");
+        }
 
-//----------------------------------------------------------------//
-// Mass-TSV Functions for PackedFiles.
-//----------------------------------------------------------------//
+        let mut files = folder.files.iter().collect::<Vec<&(String, String, u32)>>();
+        files.sort_by(|x, y| x.0.cmp(&y.0));
+        for (name, packed_file_type, size) in files {
+            html.push_str(&format!("<li>{} <i>({}, {} bytes)</i></li>\n", escape_html(name), packed_file_type, size));
+        }
+        html.push_str("</ul>\n");
+    }
 
-/// This function is used to Mass-Import TSV files into a PackFile. Note that this will OVERWRITE any
-/// existing PackedFile that has a name conflict with the TSV files provided.
-pub fn tsv_mass_import(
-    tsv_paths: &[PathBuf],
-    name: Option<String>,
-    pack_file: &mut PackFile
-) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>)> {
+    let name = escape_html(&pack_file.get_file_name());
+    let mut html = format!("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n<body>\n<details open><summary>{}</summary>\n", name, name);
+    folder_to_html(&tree, &mut html);
+    html.push_str("</details>\n</body>\n</html>\n");
+    html
+}
 
-    // Create a list of PackedFiles succesfully imported, and another for the ones that didn't work.
-    // The a third one to return the PackedFiles that were overwritten, so the UI can have an easy time updating his TreeView.
-    let mut packed_files: Vec<PackedFile> = vec![];
-    let mut packed_files_to_remove = vec![];
-    let mut error_files = vec![];
+/// This function exports the tree of the provided PackFile as a HTML page (see `get_tree_as_html()`) to the provided path.
+pub fn export_tree_as_html(
+    pack_file: &PackFile,
+    out: &PathBuf,
+) -> Result<()> {
+    let mut file = File::create(&out)?;
+    file.write_all(get_tree_as_html(pack_file).as_bytes())?;
+    Ok(())
+}
 
-    for path in tsv_paths {
+/// This function removes the UTF-8 BOM from the start of every Text PackedFile of the provided PackFile,
+/// as some of the game's parsers break when they find one. Tables are not touched.
+///
+/// It returns the paths of the PackedFiles that have been changed.
+pub fn strip_boms_from_text(
+    pack_file: &mut PackFile,
+) -> Result<Vec<Vec<String>>> {
 
-        // We open it and read it to a string. We use the first row to check what kind of TSV is, and the second one we ignore it.
-        let mut tsv = String::new();
-        BufReader::new(File::open(&path)?).read_to_string(&mut tsv)?;
+    let mut changed_paths = vec![];
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if !packed_file.path.is_empty() {
+            if let DecodeablePackedFileType::Text = get_packed_file_type(&packed_file.path) {
+                let data = packed_file.get_data_and_keep_it()?;
+                if data.starts_with(&UTF8_BOM) {
+                    packed_file.set_data(data[UTF8_BOM.len()..].to_vec());
+                    changed_paths.push(packed_file.path.to_vec());
+                }
+            }
+        }
+    }
 
-        // We get his first line, if it have it. Otherwise, we return an error in this file.
-        if let Some(line) = tsv.lines().next() {
+    Ok(changed_paths)
+}
 
-            // Split the first line by \t so we can get the info of the table. Only if we have 2 items, continue.
-            let tsv_info = line.split('\t').collect::<Vec<&str>>();
-            if tsv_info.len() == 2 {
+/// This function removes the trailing null bytes from the end of every Text PackedFile of the provided PackFile,
+/// as some of the game's parsers break when they find them. Tables are not touched.
+///
+/// It returns the paths of the PackedFiles that have been changed.
+pub fn trim_trailing_nulls(
+    pack_file: &mut PackFile,
+) -> Result<Vec<Vec<String>>> {
 
-                // Get the type and the version of the table, and with that, get his definition.
-                let table_type = tsv_info[0];
-                let table_version = match tsv_info[1].parse::<i32>() {
-                    Ok(version) => version,
-                    Err(_) => {
-                        error_files.push(path.to_string_lossy().to_string()); 
-                        continue
-                    }
-                };
-                
-                let table_definition = match table_type {
-                    "Loc PackedFile" => TableDefinition::new_loc_definition(),
-                    _ => {
-                        if let Some(ref schema) = *SCHEMA.lock().unwrap() {
-                            if let Some(table_definition) = DB::get_schema(&table_type, table_version, &schema) { table_definition }
-                            else { error_files.push(path.to_string_lossy().to_string()); continue }
-                        } else { error_files.push(path.to_string_lossy().to_string()); continue }
-                    }
-                };
+    let mut changed_paths = vec![];
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if !packed_file.path.is_empty() {
+            if let DecodeablePackedFileType::Text = get_packed_file_type(&packed_file.path) {
+                let data = packed_file.get_data_and_keep_it()?;
+                if let Some(length) = get_length_without_trailing_nulls(&data) {
+                    packed_file.set_data(data[..length].to_vec());
+                    changed_paths.push(packed_file.path.to_vec());
+                }
+            }
+        }
+    }
 
-                // Then, import whatever we have and, depending on what we have, save it.
-                match import_tsv(&table_definition, &path, &table_type, table_version) {
-                    Ok(data) => {
-                        match table_type {
-
-                            // Loc Tables.
-                            "Loc PackedFile" => {
-                                let mut loc = Loc::new();
-                                loc.entries = data;
-                                let raw_data = loc.save();
-
-                                // Depending on the name received, call it one thing or another.
-                                let name = match name {
-                                    Some(ref name) => name.to_string(),
-                                    None => path.file_stem().unwrap().to_str().unwrap().to_string(),
-                                };
+    Ok(changed_paths)
+}
 
-                                let mut path = vec!["text".to_owned(), "db".to_owned(), format!("{}.loc", name)];
+/// This function returns the length the provided text data has without its trailing nulls, or None if it doesn't end in nulls.
+///
+/// UTF-16 data (the one starting with its BOM) is checked by code units, so only full `0x0000` units are trimmed and
+/// the last character never gets cut in half.
+pub fn get_length_without_trailing_nulls(data: &[u8]) -> Option<usize> {
+    let length = if data.starts_with(&UTF16_LE_BOM) {
+        data.chunks(2).rposition(|x| x != [0, 0]).map_or(0, |x| (x + 1) * 2).min(data.len())
+    } else {
+        data.iter().rposition(|x| *x != 0).map_or(0, |x| x + 1)
+    };
 
-                                // If that path already exists in the list of new PackedFiles to add, change it using the index.
-                                let mut index = 1;
-                                while packed_files.iter().any(|x| x.path == path) {
-                                    path[2] = format!("{}_{}.loc", name, index);
-                                    index += 1;
-                                }
+    if length < data.len() { Some(length) } else { None }
+}
 
-                                // If that path already exist in the PackFile, add it to the "remove" list.
-                                if pack_file.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
+/// This function counts the characters of all the localisable text in the provided PackFile, to help estimating
+/// how much work a translation will take. That's the text column of the Loc PackedFiles, and the localisable columns
+/// of the DB Tables (see `TableDefinition::is_field_localisable()`), with escaped characters (like `\n`) counting as one character.
+/// DB Tables without localisable columns are skipped.
+///
+/// It returns the count of each PackedFile, the total count, and the paths of the PackedFiles that couldn't be decoded.
+pub fn count_localisable_characters(
+    pack_file: &mut PackFile,
+    schema: &Schema,
+) -> Result<(Vec<(Vec<String>, usize)>, usize, Vec<Vec<String>>)> {
+
+    let count_characters = |text: &str| text.replace("\\t", "\t").replace("\\n", "\n").chars().count();
+    let is_loc = |path: &[String]| !path.is_empty() && path.last().unwrap().ends_with(".loc");
+    let is_db = |path: &[String]| path.len() == 3 && path[0] == "db";
+
+    load_packed_files_data(pack_file, |path| is_loc(path) || is_db(path))?;
+
+    let mut counts = vec![];
+    let mut failed = vec![];
+    for packed_file in pack_file.packed_files.iter() {
+        if is_loc(&packed_file.path) {
+            match Loc::read(&packed_file.get_data()?) {
+                Ok(loc) => {
+                    let count = loc.entries.iter().map(|x| count_characters(&x[1].data_to_string())).sum();
+                    counts.push((packed_file.path.to_vec(), count));
+                }
+                Err(_) => failed.push(packed_file.path.to_vec()),
+            }
+        }
 
-                                // Create and add the new PackedFile to the list of PackedFiles to add.
-                                packed_files.push(PackedFile::read_from_vec(path, get_current_time(), false, raw_data));
+        else if is_db(&packed_file.path) {
+            match DB::read(&packed_file.get_data()?, pack_file.get_table_definition_name(&packed_file.path[1]), schema) {
+                Ok(db) => {
+                    let columns = db.table_definition.fields.iter()
+                        .enumerate()
+                        .filter(|(_, x)| db.table_definition.is_field_localisable(x))
+                        .map(|(x, _)| x)
+                        .collect::<Vec<usize>>();
+                    if columns.is_empty() { continue; }
+
+                    let count = db.entries.iter().map(|row| columns.iter().map(|x| count_characters(&row[*x].data_to_string())).sum::<usize>()).sum();
+                    counts.push((packed_file.path.to_vec(), count));
+                }
+                Err(_) => failed.push(packed_file.path.to_vec()),
+            }
+        }
+    }
+
+    let total = counts.iter().map(|x| x.1).sum();
+    Ok((counts, total, failed))
+}
+
+/// This function calculates the loc coverage of every DB Table in the provided PackFile.
+///
+/// Loc keys for tables are formed like `{table name without "_tables"}_{column}_{key of the row}`. As the localisable
+/// columns are not in the tables, we get them from his definition or, if it doesn't have them, from the loc keys of the PackFile
+/// that match a row of the table. Then we expect each row of the table to have a loc key for each one of these columns.
+/// Tables without localisable columns are skipped.
+pub fn get_loc_coverage(
+    pack_file: &mut PackFile,
+    schema: &Schema,
+) -> Result<Vec<LocCoverage>> {
+
+    // First, get all the loc entries of the PackFile.
+    let mut loc_entries = BTreeMap::new();
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                for entry in &loc.entries {
+                    loc_entries.insert(entry[0].data_to_string(), entry[1].data_to_string());
+                }
+            }
+        }
+    }
+
+    load_packed_files_data(pack_file, |path| path.len() == 3 && path[0] == "db")?;
+
+    let mut coverages = vec![];
+    for packed_file in pack_file.packed_files.iter() {
+        if packed_file.path.len() == 3 && packed_file.path[0] == "db" {
+            if let Ok(db) = DB::read(&packed_file.get_data()?, pack_file.get_table_definition_name(&packed_file.path[1]), schema) {
+
+                // The key of the row is in his first key column. If there is none, we cannot build the loc keys.
+                let key_column = match db.table_definition.fields.iter().position(|x| x.field_is_key) {
+                    Some(key_column) => key_column,
+                    None => continue,
+                };
+
+                let table_name = if packed_file.path[1].ends_with("_tables") { packed_file.path[1].split_at(packed_file.path[1].len() - 7).0 } else { &packed_file.path[1] };
+                let prefix = format!("{}_", table_name);
+                let row_keys = db.entries.iter().map(|x| x[key_column].data_to_string()).collect::<BTreeSet<String>>();
+
+                // The localisable columns are the ones of the definition, or the ones we find by splitting the loc keys
+                // of this table into column + row key, if the definition doesn't have them.
+                let mut columns = db.table_definition.localised_fields.iter().map(|x| x.field_name.to_owned()).collect::<BTreeSet<String>>();
+                if columns.is_empty() {
+                    for loc_key in loc_entries.keys().filter(|x| x.starts_with(&prefix)) {
+                        let loc_key = &loc_key[prefix.len()..];
+                        for (index, _) in loc_key.match_indices('_') {
+                            if row_keys.contains(&loc_key[index + 1..]) {
+                                columns.insert(loc_key[..index].to_owned());
+                                break;
+                            }
+                        }
+                    }
+                }
+                if columns.is_empty() { continue; }
+
+                let mut coverage = LocCoverage {
+                    path: packed_file.path.to_vec(),
+                    expected: row_keys.len() * columns.len(),
+                    present: 0,
+                    blank: 0,
+                    missing: 0,
+                };
+
+                // Rows with an empty key cannot have loc entries, so all their loc keys are missing.
+                for row_key in &row_keys {
+                    for column in &columns {
+                        match loc_entries.get(&format!("{}{}_{}", prefix, column, row_key)) {
+                            Some(text) if !row_key.is_empty() && !text.is_empty() => coverage.present += 1,
+                            Some(_) if !row_key.is_empty() => coverage.blank += 1,
+                            _ => coverage.missing += 1,
+                        }
+                    }
+                }
+                coverages.push(coverage);
+            }
+        }
+    }
+
+    Ok(coverages)
+}
+
+/// This function finds the loc keys needed by the rows of the DB Tables of the provided PackFile that don't exist, neither in the
+/// Loc PackedFiles of the PackFile nor in the ones of the provided dependency database.
+///
+/// The localisable columns of each table are taken from his definition. If it doesn't have them, we get them from the loc keys
+/// matching a row of the table, or a row of the vanilla table of the same type, like in `get_loc_coverage()`. Tables without
+/// localisable columns are skipped.
+pub fn find_missing_loc_keys(
+    pack_file: &mut PackFile,
+    dependency_database: &mut [PackedFile],
+    schema: &Schema,
+) -> Result<Vec<MissingLocKey>> {
+
+    // First, get all the loc keys of the PackFile and the dependency database.
+    let mut loc_keys = BTreeSet::new();
+    for packed_file in pack_file.packed_files.iter_mut().chain(dependency_database.iter_mut()) {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                loc_keys.extend(loc.entries.iter().map(|x| x[0].data_to_string()));
+            }
+        }
+    }
+
+    load_packed_files_data(pack_file, |path| path.len() == 3 && path[0] == "db")?;
+
+    // Get the keys of the vanilla rows of the types of tables we have, so we can find the localisable columns of tables with only new rows.
+    let table_names = pack_file.packed_files.iter()
+        .filter(|x| x.path.len() == 3 && x.path[0] == "db")
+        .map(|x| pack_file.get_table_definition_name(&x.path[1]).to_owned())
+        .collect::<BTreeSet<String>>();
+
+    let mut vanilla_row_keys: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for packed_file in dependency_database.iter_mut() {
+        if packed_file.path.len() == 3 && packed_file.path[0] == "db" && table_names.contains(&packed_file.path[1]) {
+            if let Ok(db) = DB::read(&packed_file.get_data_and_keep_it()?, &packed_file.path[1], schema) {
+                if let Some(key_column) = db.table_definition.fields.iter().position(|x| x.field_is_key) {
+                    vanilla_row_keys.entry(packed_file.path[1].to_owned()).or_insert_with(BTreeSet::new).extend(db.entries.iter().map(|x| x[key_column].data_to_string()));
+                }
+            }
+        }
+    }
+
+    let mut missing_keys = vec![];
+    for packed_file in pack_file.packed_files.iter() {
+        if packed_file.path.len() == 3 && packed_file.path[0] == "db" {
+            let table_name = pack_file.get_table_definition_name(&packed_file.path[1]);
+            if let Ok(db) = DB::read(&packed_file.get_data()?, table_name, schema) {
+
+                // The key of the row is in his first key column. If there is none, we cannot build the loc keys.
+                let key_column = match db.table_definition.fields.iter().position(|x| x.field_is_key) {
+                    Some(key_column) => key_column,
+                    None => continue,
+                };
+
+                let prefix = format!("{}_", if table_name.ends_with("_tables") { table_name.split_at(table_name.len() - 7).0 } else { table_name });
+                let row_keys = db.entries.iter().map(|x| x[key_column].data_to_string()).filter(|x| !x.is_empty()).collect::<BTreeSet<String>>();
+
+                let mut columns = db.table_definition.localised_fields.iter().map(|x| x.field_name.to_owned()).collect::<BTreeSet<String>>();
+                if columns.is_empty() {
+                    let vanilla_row_keys = vanilla_row_keys.get(table_name);
+                    for loc_key in loc_keys.range(prefix.to_owned()..).take_while(|x| x.starts_with(&prefix)) {
+                        let loc_key = &loc_key[prefix.len()..];
+                        for (index, _) in loc_key.match_indices('_') {
+                            let row_key = &loc_key[index + 1..];
+                            if row_keys.contains(row_key) || vanilla_row_keys.map_or(false, |x| x.contains(row_key)) {
+                                columns.insert(loc_key[..index].to_owned());
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                for row_key in &row_keys {
+                    for column in &columns {
+                        let loc_key = format!("{}{}_{}", prefix, column, row_key);
+                        if !loc_keys.contains(&loc_key) {
+                            missing_keys.push(MissingLocKey {
+                                path: packed_file.path.to_vec(),
+                                row_key: row_key.to_owned(),
+                                loc_key,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(missing_keys)
+}
+
+/// This function finds the loc keys defined more than once in the Loc PackedFiles of the provided PackFile, either
+/// in different Loc PackedFiles or in the same one. The game only uses one of them, so the rest are just noise.
+///
+/// It returns the duplicated keys sorted, with their entries in the order they are in the PackFile.
+pub fn find_duplicated_loc_keys(
+    pack_file: &mut PackFile,
+) -> Result<Vec<DuplicatedLocKey>> {
+
+    let mut entries: BTreeMap<String, Vec<(Vec<String>, String)>> = BTreeMap::new();
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                for entry in &loc.entries {
+                    entries.entry(entry[0].data_to_string()).or_insert_with(Vec::new).push((packed_file.path.to_vec(), entry[1].data_to_string()));
+                }
+            }
+        }
+    }
+
+    Ok(entries.into_iter().filter(|(_, entries)| entries.len() > 1).map(|(key, entries)| DuplicatedLocKey { key, entries }).collect())
+}
+
+/// This function resolves duplicated loc keys (see `find_duplicated_loc_keys()`) of the provided PackFile. For each key, only
+/// the entry with the provided index in his entries is kept. The rest are removed from their Loc PackedFiles.
+///
+/// It returns the paths of the edited Loc PackedFiles.
+pub fn resolve_duplicated_loc_keys(
+    pack_file: &mut PackFile,
+    resolutions: &[(String, usize)],
+) -> Result<Vec<Vec<String>>> {
+
+    let resolutions = resolutions.iter().cloned().collect::<BTreeMap<String, usize>>();
+    let mut found_entries: BTreeMap<String, usize> = BTreeMap::new();
+    let mut edited_paths = vec![];
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(mut loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                let entry_count = loc.entries.len();
+                loc.entries.retain(|entry| {
+                    let key = entry[0].data_to_string();
+                    match resolutions.get(&key) {
+                        Some(winner) => {
+                            let index = found_entries.entry(key).or_insert(0);
+                            *index += 1;
+                            *index - 1 == *winner
+                        }
+                        None => true,
+                    }
+                });
+
+                if loc.entries.len() != entry_count {
+                    packed_file.set_data(loc.save());
+                    edited_paths.push(packed_file.path.to_vec());
+                }
+            }
+        }
+    }
+
+    Ok(edited_paths)
+}
+
+/// This function returns the entries of the Loc PackedFiles of the provided PackFile with the exact same text as the entry with the same
+/// key in the provided english Loc PackedFiles, so we can find what's left to translate in a translation. Keys matching any of the
+/// provided patterns (regex, or plain text if they're not valid regex) are ignored, as some texts (like names) are meant to be the same.
+///
+/// The matches are returned as `GlobalMatch::Loc`, so they can be shown in the "Global Search" view.
+pub fn find_untranslated_loc_entries(
+    pack_file: &mut PackFile,
+    english_locs: &mut [PackedFile],
+    ignored_patterns: &[String],
+) -> Result<Vec<GlobalMatch>> {
+
+    let mut english_texts: BTreeMap<String, String> = BTreeMap::new();
+    for packed_file in english_locs.iter_mut() {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                for entry in &loc.entries {
+                    english_texts.insert(entry[0].data_to_string(), entry[1].data_to_string());
+                }
+            }
+        }
+    }
+
+    let ignored_patterns = ignored_patterns.iter()
+        .filter(|x| !x.is_empty())
+        .map(|x| Regex::new(x).unwrap_or_else(|_| Regex::new(&regex::escape(x)).unwrap()))
+        .collect::<Vec<Regex>>();
+
+    let mut matches = vec![];
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                let mut matches_in_file = vec![];
+                for (row, entry) in loc.entries.iter().enumerate() {
+                    let key = entry[0].data_to_string();
+                    let text = entry[1].data_to_string();
+                    if text.is_empty() || ignored_patterns.iter().any(|x| x.is_match(&key)) { continue; }
+                    if let Some(english_text) = english_texts.get(&key) {
+                        if *english_text == text {
+                            matches_in_file.push(("text".to_owned(), 1, row as i64, text));
+                        }
+                    }
+                }
+
+                if !matches_in_file.is_empty() { matches.push(GlobalMatch::Loc((packed_file.path.to_vec(), matches_in_file))); }
+            }
+        }
+    }
+
+    Ok(matches)
+}
+
+/// This function replaces the provided prefix of the loc keys of all the Loc PackedFiles of the provided PackFile with another one.
+///
+/// If `rename_db_cells` is true, it also replaces it in the key columns of the DB Tables with localisable fields (as their keys are part
+/// of their loc keys) and in the columns referencing them. The loc keys built from these keys (`{table}_{field}_{key}`) are renamed too.
+///
+/// It returns every change, in order. If `apply` is false, nothing is changed in the PackFile, so the changes can be previewed.
+pub fn rename_key_prefix(
+    pack_file: &mut PackFile,
+    old_prefix: &str,
+    new_prefix: &str,
+    rename_db_cells: bool,
+    apply: bool,
+    schema: &Schema,
+) -> Result<Vec<KeyPrefixChange>> {
+
+    if old_prefix.is_empty() { Err(ErrorKind::EmptyInput)? }
+    let mut changes = vec![];
+    let mut renamed_loc_keys: BTreeMap<String, String> = BTreeMap::new();
+
+    if rename_db_cells {
+        let mut tables = vec![];
+        let paths = pack_file.packed_files.iter().filter(|x| x.path.len() == 3 && x.path[0] == "db").map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+        for path in &paths {
+            if let Ok((table_definition, entries)) = get_table_data(pack_file, path, schema) {
+                let table_name = pack_file.get_table_definition_name(&path[1]).trim_end_matches("_tables").to_owned();
+                tables.push((path.to_vec(), table_name, table_definition, entries));
+            }
+        }
+
+        // Only the keys of tables with localisable fields are part of loc keys.
+        let localisable_keys = tables.iter()
+            .filter(|(_, _, table_definition, _)| !table_definition.localised_fields.is_empty())
+            .flat_map(|(_, table_name, table_definition, _)| get_key_columns(table_definition).iter().map(|x| (table_name.to_owned(), table_definition.fields[*x].field_name.to_owned())).collect::<Vec<(String, String)>>())
+            .collect::<BTreeSet<(String, String)>>();
+
+        for (path, table_name, table_definition, mut entries) in tables {
+            let columns = table_definition.fields.iter().enumerate().filter(|(_, field)| {
+                match field.field_type {
+                    FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16 => {
+                        localisable_keys.contains(&(table_name.to_owned(), field.field_name.to_owned())) ||
+                        field.field_is_reference.as_ref().map_or(false, |x| localisable_keys.contains(x))
+                    }
+                    _ => false,
+                }
+            }).map(|(x, _)| x).collect::<Vec<usize>>();
+
+            let mut changed = false;
+            for (row, entry) in entries.iter_mut().enumerate() {
+                for column in &columns {
+                    let field = &table_definition.fields[*column];
+                    let old_value = entry[*column].data_to_string();
+                    if !old_value.starts_with(old_prefix) { continue; }
+                    let new_value = format!("{}{}", new_prefix, &old_value[old_prefix.len()..]);
+                    entry[*column] = parse_tsv_field(field.field_type, &new_value, row, *column)?;
+
+                    if localisable_keys.contains(&(table_name.to_owned(), field.field_name.to_owned())) {
+                        for localised_field in &table_definition.localised_fields {
+                            renamed_loc_keys.insert(
+                                format!("{}_{}_{}", table_name, localised_field.field_name, old_value),
+                                format!("{}_{}_{}", table_name, localised_field.field_name, new_value)
+                            );
+                        }
+                    }
+
+                    changes.push(KeyPrefixChange {
+                        path: path.to_vec(),
+                        row,
+                        column: field.field_name.to_owned(),
+                        old_value,
+                        new_value,
+                    });
+                    changed = true;
+                }
+            }
+
+            if changed && apply {
+                let data = encode_table_data(pack_file, &path, entries, schema)?;
+                pack_file.packed_files.iter_mut().find(|x| x.path == path).unwrap().set_data(data);
+            }
+        }
+    }
+
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(mut loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                let mut changed = false;
+                for (row, entry) in loc.entries.iter_mut().enumerate() {
+                    let old_value = entry[0].data_to_string();
+                    let new_value = match renamed_loc_keys.get(&old_value) {
+                        Some(new_value) => new_value.to_owned(),
+                        None if old_value.starts_with(old_prefix) => format!("{}{}", new_prefix, &old_value[old_prefix.len()..]),
+                        None => continue,
+                    };
+
+                    entry[0] = DecodedData::StringU16(new_value.to_owned());
+                    changes.push(KeyPrefixChange {
+                        path: packed_file.path.to_vec(),
+                        row,
+                        column: "key".to_owned(),
+                        old_value,
+                        new_value,
+                    });
+                    changed = true;
+                }
+
+                if changed && apply { packed_file.set_data(loc.save()); }
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+/// This function generates the loc keys of the rows of the DB Table in the provided path, and adds an entry with blank text for each
+/// one of them to the Loc PackedFile in the other provided path (see `add_loc_stubs()`). Each row gets one loc key per template, with
+/// `{table}` replaced by the name of the table without the `_tables` suffix, and `{key}` replaced by the key of the row. If there are
+/// no templates, we use the localisable fields of the table (`{table}_{field}_{key}`) instead.
+///
+/// It returns the amount of entries created, the amount of them skipped because they already existed, and if the Loc PackedFile has been created.
+pub fn generate_loc_entries(
+    pack_file: &mut PackFile,
+    table_path: &[String],
+    loc_path: &[String],
+    templates: &[String],
+    schema: &Schema,
+) -> Result<(usize, usize, bool)> {
+
+    if table_path.len() != 3 || table_path[0] != "db" { Err(ErrorKind::DBTableIsNotADBTable)? }
+    let (table_definition, entries) = get_table_data(pack_file, table_path, schema)?;
+    let templates = if templates.is_empty() {
+        table_definition.localised_fields.iter().map(|x| format!("{{table}}_{}_{{key}}", x.field_name)).collect::<Vec<String>>()
+    } else { templates.to_vec() };
+    if templates.is_empty() { Err(ErrorKind::LocKeyTemplatesNotFound)? }
+
+    let table_name = pack_file.get_table_definition_name(&table_path[1]);
+    let table_name = if table_name.ends_with("_tables") { table_name.split_at(table_name.len() - 7).0.to_owned() } else { table_name.to_owned() };
+    let key_column = match table_definition.fields.iter().position(|x| x.field_is_key) {
+        Some(column) => column,
+        None => Err(ErrorKind::LocKeyTableWithoutKeyColumns)?,
+    };
+
+    let mut loc_keys = vec![];
+    let mut generated_keys = BTreeSet::new();
+    for entry in &entries {
+        let key = entry[key_column].data_to_string();
+        if key.is_empty() { continue; }
+        for template in &templates {
+            let loc_key = template.replace("{table}", &table_name).replace("{key}", &key);
+            if generated_keys.insert(loc_key.to_owned()) { loc_keys.push(loc_key); }
+        }
+    }
+
+    let (created, is_new) = add_loc_stubs(pack_file, loc_path, &loc_keys)?;
+    Ok((created, loc_keys.len() - created, is_new))
+}
+
+/// This function adds an entry with blank text for each one of the provided loc keys to the Loc PackedFile in the provided path,
+/// creating it if it doesn't exist. Keys already in that Loc PackedFile are not added again.
+///
+/// It returns the amount of entries added, and if the Loc PackedFile has been created.
+pub fn add_loc_stubs(
+    pack_file: &mut PackFile,
+    path: &[String],
+    loc_keys: &[String],
+) -> Result<(usize, bool)> {
+    if path.last().map_or(true, |x| !x.ends_with(".loc")) { Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
+
+    let (mut loc, exists) = match pack_file.packed_files.iter_mut().find(|x| x.path == path) {
+        Some(packed_file) => (Loc::read(&packed_file.get_data_and_keep_it()?)?, true),
+        None => (Loc::new(), false),
+    };
+
+    let mut existing_keys = loc.entries.iter().map(|x| x[0].data_to_string()).collect::<BTreeSet<String>>();
+    let mut added = 0;
+    for loc_key in loc_keys {
+        if existing_keys.insert(loc_key.to_owned()) {
+            loc.entries.push(vec![DecodedData::StringU16(loc_key.to_owned()), DecodedData::StringU16(String::new()), DecodedData::Boolean(true)]);
+            added += 1;
+        }
+    }
+
+    if exists { pack_file.packed_files.iter_mut().find(|x| x.path == path).unwrap().set_data(loc.save()); }
+    else {
+        let added_paths = pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), get_current_time(), false, loc.save())]);
+        if added_paths.is_empty() { Err(ErrorKind::ReservedFiles)? }
+    }
+
+    Ok((added, !exists))
+}
+
+/// This function gets the distinct values of the provided columns, searching in every table of the provided PackFile
+/// and the dependency database. Column references must be like `table_name.column_name`. The `_tables` suffix of the
+/// table name is optional.
+///
+/// It returns, for each column reference, the sorted list of distinct values found. Empty values are ignored.
+pub fn get_column_enums(
+    pack_file: &mut PackFile,
+    dependency_database: &mut [PackedFile],
+    schema: &Schema,
+    column_refs: &[String],
+) -> Result<BTreeMap<String, BTreeSet<String>>> {
+
+    // First, check all the references are valid, so we don't waste time decoding tables if one of them is wrong.
+    let mut columns = vec![];
+    for column_ref in column_refs {
+        let column_ref = column_ref.trim();
+        match column_ref.find('.') {
+            Some(index) if index > 0 && index < column_ref.len() - 1 => {
+                let table_name = &column_ref[..index];
+                let table_name = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{}_tables", table_name) };
+                columns.push((column_ref.to_owned(), table_name, column_ref[index + 1..].to_owned()));
+            }
+            _ => Err(ErrorKind::InvalidColumnReference(column_ref.to_owned()))?,
+        }
+    }
+
+    load_packed_files_data(pack_file, |path| path.len() == 3 && path[0] == "db")?;
+
+    let mut enums = BTreeMap::new();
+    for (column_ref, table_name, column_name) in &columns {
+        let mut values = BTreeSet::new();
+        let mut tables = vec![];
+        for packed_file in pack_file.packed_files.iter() {
+            if packed_file.path.len() == 3 && packed_file.path[0] == "db" && &packed_file.path[1] == table_name {
+                tables.push((packed_file.get_data()?, pack_file.get_table_definition_name(table_name).to_owned()));
+            }
+        }
+
+        for packed_file in dependency_database.iter_mut() {
+            if packed_file.path.len() == 3 && packed_file.path[0] == "db" && &packed_file.path[1] == table_name {
+                tables.push((packed_file.get_data_and_keep_it()?, table_name.to_owned()));
+            }
+        }
+
+        for (data, definition_name) in &tables {
+            if let Ok(db) = DB::read(data, definition_name, schema) {
+                if let Some(column) = db.table_definition.fields.iter().position(|x| &x.field_name == column_name) {
+                    values.extend(db.entries.iter().map(|x| x[column].data_to_string()).filter(|x| !x.is_empty()));
+                }
+            }
+        }
+        enums.insert(column_ref.to_owned(), values);
+    }
+
+    Ok(enums)
+}
+
+/// This function exports the distinct values of the provided columns to a TSV file, one value per line, like `column_ref  value`.
+pub fn export_column_enums(
+    pack_file: &mut PackFile,
+    dependency_database: &mut [PackedFile],
+    schema: &Schema,
+    column_refs: &[String],
+    path: &PathBuf,
+) -> Result<()> {
+
+    let enums = get_column_enums(pack_file, dependency_database, schema, column_refs)?;
+    let mut writer = new_tsv_writer();
+
+    for (column_ref, values) in &enums {
+        for value in values {
+            writer.serialize((column_ref, value))?;
+        }
+    }
+
+    save_tsv_writer(writer, path)?;
+    Ok(())
+}
+
+/// This function decodes `entry_count` rows of a table following the provided fields, starting at `index` and moving it to the end of the last row.
+///
+/// If a field fails to decode, the error contains the row and field that failed, his type and the offset of the byte where it starts.
+pub fn decode_table_entries(
+    data: &[u8],
+    index: &mut usize,
+    entry_count: u32,
+    fields: &[Field],
+) -> Result<Vec<Vec<DecodedData>>> {
+    let mut entries = vec![];
+    for row in 0..entry_count as usize {
+        let mut decoded_row = Vec::with_capacity(fields.len());
+        for field in fields {
+            match DecodedData::decode(data, index, field.field_type) {
+                Ok(decoded_cell) => decoded_row.push(decoded_cell),
+                Err(cause) => return Err(ErrorKind::TableFieldDecode(row, field.field_name.to_owned(), field.field_type.to_string(), *index, cause))?,
+            }
+        }
+        entries.push(decoded_row);
+    }
+    Ok(entries)
+}
+
+/// This function returns why a string starting (his size, not the Optional marker) at `index` failed to decode.
+/// `unit_size` is the amount of bytes of each character: 1 for UTF-8 Strings and 2 for UTF-16 ones.
+fn get_string_decoding_error(data: &[u8], index: usize, unit_size: usize, type_name: &str) -> String {
+    if data.get(index + 1).is_none() { return "insufficient bytes to decode the size of the string".to_owned() }
+    let size = decode_integer_u16(&data[index..(index + 2)]).unwrap() as usize * unit_size;
+    let remaining = data.len() - index - 2;
+    if size > remaining { format!("the size of the string ({} bytes) is bigger than the remaining bytes ({})", size, remaining) }
+    else { format!("the value is not a valid {}", type_name) }
+}
+
+/// This function decodes the DB Table or Loc PackedFile in the provided path, returning his definition and his entries.
+/// Loc PackedFiles use the fake definition from `TableDefinition::new_loc_definition()`.
+pub fn get_table_data(
+    pack_file: &mut PackFile,
+    path: &[String],
+    schema: &Schema,
+) -> Result<(TableDefinition, Vec<Vec<DecodedData>>)> {
+
+    if path.is_empty() { Err(ErrorKind::PackedFileNotFound)? }
+    let table_name = if path.len() == 3 { pack_file.get_table_definition_name(&path[1]).to_owned() } else { String::new() };
+    let packed_file = match pack_file.packed_files.iter_mut().find(|x| x.path == path) {
+        Some(packed_file) => packed_file,
+        None => Err(ErrorKind::PackedFileNotFound)?,
+    };
+
+    match get_packed_file_type(path) {
+        DecodeablePackedFileType::DB if path.len() == 3 => {
+            let db = DB::read(&packed_file.get_data_and_keep_it()?, &table_name, schema)?;
+            Ok((db.table_definition, db.entries))
+        }
+        DecodeablePackedFileType::Loc => {
+            let loc = Loc::read(&packed_file.get_data_and_keep_it()?)?;
+            Ok((TableDefinition::new_loc_definition(), loc.entries))
+        }
+        _ => Err(ErrorKind::PackedFileIsNotATable)?,
+    }
+}
+
+/// This function replaces the entries of the DB Table or Loc PackedFile in the provided path with the provided ones.
+pub fn set_table_data(
+    pack_file: &mut PackFile,
+    path: &[String],
+    entries: Vec<Vec<DecodedData>>,
+    schema: &Schema,
+) -> Result<()> {
+    get_table_data(pack_file, path, schema)?;
+    let data = encode_table_data(pack_file, path, entries, schema)?;
+    pack_file.packed_files.iter_mut().find(|x| x.path == path).unwrap().set_data(data);
+    Ok(())
+}
+
+/// This function checks that the provided Loc definition (usually, the one read from the Loc PackedFile with `Loc::read_definition()`)
+/// follows the Loc layout: the known version, and the key, text and tooltip flag columns in that order and with their types,
+/// as Loc PackedFiles written with anything else are shown and exported wrong.
+///
+/// It returns a line for each mismatch found. If it's empty, the definition is correct.
+pub fn verify_loc_column_order(table_definition: &TableDefinition) -> Vec<String> {
+    let expected = TableDefinition::new_loc_definition();
+    let mut mismatches = vec![];
+    if table_definition.version != expected.version {
+        mismatches.push(format!("Expected version {}, found {}.", expected.version, table_definition.version));
+    }
+
+    if table_definition.fields.len() != expected.fields.len() {
+        mismatches.push(format!("Expected {} columns, found {}.", expected.fields.len(), table_definition.fields.len()));
+    }
+
+    for (index, (field, expected_field)) in table_definition.fields.iter().zip(expected.fields.iter()).enumerate() {
+        if field.field_name != expected_field.field_name || field.field_type != expected_field.field_type {
+            mismatches.push(format!(
+                "Column {}: expected \"{}\" ({}), found \"{}\" ({}).",
+                index + 1,
+                expected_field.field_name,
+                expected_field.field_type,
+                field.field_name,
+                field.field_type
+            ));
+        }
+    }
+    mismatches
+}
+
+/// This function returns a new row for a table with the provided definition, with the default value of each one of its fields.
+pub fn get_default_row(table_definition: &TableDefinition) -> Vec<DecodedData> {
+    table_definition.fields.iter().map(DecodedData::default_from_field).collect()
+}
+
+/// This function returns the indexes of the key columns of the provided definition. Loc PackedFiles
+/// (and tables without key columns) use their first column as key.
+pub fn get_key_columns(table_definition: &TableDefinition) -> Vec<usize> {
+    let key_columns = table_definition.fields.iter().enumerate().filter(|(_, x)| x.field_is_key).map(|(x, _)| x).collect::<Vec<usize>>();
+    if key_columns.is_empty() && !table_definition.fields.is_empty() { vec![0] } else { key_columns }
+}
+
+/// This function returns the indexes of the columns that identify a row of a DB Table with the provided definition. That's its key
+/// columns or, for tables without key columns, all of them, so two rows of these tables are only the same row if they're equal.
+pub fn get_row_identity_columns(table_definition: &TableDefinition) -> Vec<usize> {
+    let key_columns = table_definition.fields.iter().enumerate().filter(|(_, x)| x.field_is_key).map(|(x, _)| x).collect::<Vec<usize>>();
+    if key_columns.is_empty() { (0..table_definition.fields.len()).collect() } else { key_columns }
+}
+
+/// This function removes from the provided rows the ones whose key is repeated later, so only the last row of each key is left.
+/// The rows left keep their order.
+pub fn remove_duplicated_rows(entries: &mut Vec<Vec<DecodedData>>, key_columns: &[usize]) {
+    let is_last = {
+        let mut keys = HashSet::new();
+        let mut is_last = entries.iter().rev()
+            .map(|row| keys.insert(key_columns.iter().map(|x| row.get(*x)).collect::<Vec<Option<&DecodedData>>>()))
+            .collect::<Vec<bool>>();
+        is_last.reverse();
+        is_last
+    };
+
+    let mut is_last = is_last.into_iter();
+    entries.retain(|_| is_last.next().unwrap());
+}
+
+/// This function compares two versions of the same table, using the provided definition for both of them. Rows are matched by
+/// their key columns and, if a key is repeated, in the order they appear in each table.
+pub fn diff_table_entries(
+    old_entries: &[Vec<DecodedData>],
+    new_entries: &[Vec<DecodedData>],
+    table_definition: &TableDefinition,
+) -> TableDiff {
+    let key_columns = get_key_columns(table_definition);
+    let get_key = |row: &[DecodedData]| key_columns.iter().filter_map(|x| row.get(*x)).cloned().collect::<Vec<DecodedData>>();
+
+    let mut old_rows_by_key: HashMap<Vec<DecodedData>, VecDeque<usize>> = HashMap::new();
+    for (index, row) in old_entries.iter().enumerate() {
+        old_rows_by_key.entry(get_key(row)).or_insert_with(VecDeque::new).push_back(index);
+    }
+
+    let mut diff = TableDiff {
+        table_definition: table_definition.clone(),
+        added: vec![],
+        removed: vec![],
+        changed: vec![],
+    };
+
+    let mut matched = vec![false; old_entries.len()];
+    for new_row in new_entries {
+        let key = get_key(new_row);
+        match old_rows_by_key.get_mut(&key).and_then(|x| x.pop_front()) {
+            Some(old_index) => {
+                matched[old_index] = true;
+                let fields = table_definition.fields.iter()
+                    .zip(old_entries[old_index].iter().zip(new_row))
+                    .filter(|(_, (old_value, new_value))| old_value != new_value)
+                    .map(|(field, (old_value, new_value))| (field.field_name.to_owned(), old_value.clone(), new_value.clone()))
+                    .collect::<Vec<(String, DecodedData, DecodedData)>>();
+                if !fields.is_empty() { diff.changed.push(ChangedRow { key, fields }); }
+            }
+            None => diff.added.push(new_row.to_vec()),
+        }
+    }
+
+    diff.removed = old_entries.iter().zip(&matched).filter(|(_, x)| !**x).map(|(row, _)| row.to_vec()).collect();
+    diff
+}
+
+/// This function compares the DB Table or Loc PackedFile in the provided path with the one in the other provided path of another PackFile.
+/// The table of our PackFile is the old one, and the one of the other PackFile the new one.
+pub fn diff_table_with_packfile(
+    pack_file: &PackFile,
+    other_pack_file: &PackFile,
+    path: &[String],
+    other_path: &[String],
+) -> Result<TableDiff> {
+    let packed_file = pack_file.packed_files.iter().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
+    let other_packed_file = other_pack_file.packed_files.iter().find(|x| x.path == other_path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
+    match (get_packed_file_type(path), get_packed_file_type(other_path)) {
+        (DecodeablePackedFileType::DB, DecodeablePackedFileType::DB) => {
+            if let Some(ref schema) = *SCHEMA.lock().unwrap() {
+                let table = DB::read(&packed_file.get_data()?, pack_file.get_table_definition_name(&path[1]), schema)?;
+                let other_table = DB::read(&other_packed_file.get_data()?, other_pack_file.get_table_definition_name(&other_path[1]), schema)?;
+                table.diff(&other_table)
+            }
+            else { Err(ErrorKind::SchemaNotFound)? }
+        }
+        (DecodeablePackedFileType::Loc, DecodeablePackedFileType::Loc) => Ok(Loc::read(&packed_file.get_data()?)?.diff(&Loc::read(&other_packed_file.get_data()?)?)),
+        (DecodeablePackedFileType::DB, _) |
+        (DecodeablePackedFileType::Loc, _) => Err(ErrorKind::InvalidFilesForDiffing)?,
+        _ => Err(ErrorKind::PackedFileIsNotATable)?,
+    }
+}
+
+/// This function finds the keys containing non-ASCII characters in the DB Table or Loc PackedFile in the provided path,
+/// as some parts of the games only support ASCII keys.
+pub fn find_non_ascii_keys(
+    pack_file: &mut PackFile,
+    path: &[String],
+    schema: &Schema,
+) -> Result<Vec<NonAsciiKey>> {
+
+    let (table_definition, entries) = get_table_data(pack_file, path, schema)?;
+    let key_columns = get_key_columns(&table_definition);
+
+    let mut keys = vec![];
+    for (row, entry) in entries.iter().enumerate() {
+        for column in &key_columns {
+            let key = entry[*column].data_to_string();
+            let positions = key.chars().enumerate().filter(|(_, x)| !x.is_ascii()).map(|(x, _)| x).collect::<Vec<usize>>();
+            if !positions.is_empty() {
+                keys.push(NonAsciiKey {
+                    row,
+                    column: table_definition.fields[*column].field_name.to_owned(),
+                    key,
+                    positions,
+                });
+            }
+        }
+    }
+
+    Ok(keys)
+}
+
+/// This function finds the string cells longer than the provided threshold (in characters) in every DB Table and Loc PackedFile
+/// of the provided PackFile, as they're usually text pasted in the wrong column. Tables that cannot be decoded are skipped.
+pub fn find_oversized_cells(
+    pack_file: &mut PackFile,
+    threshold: usize,
+    schema: &Schema,
+) -> Result<Vec<OversizedCell>> {
+
+    let paths = pack_file.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+    let mut cells = vec![];
+    for path in &paths {
+        if let Ok((table_definition, entries)) = get_table_data(pack_file, path, schema) {
+            for (row, entry) in entries.iter().enumerate() {
+                for (column, field) in entry.iter().enumerate() {
+                    match field {
+                        DecodedData::StringU8(ref data) |
+                        DecodedData::StringU16(ref data) |
+                        DecodedData::OptionalStringU8(ref data) |
+                        DecodedData::OptionalStringU16(ref data) => {
+                            let length = data.chars().count();
+                            if length > threshold {
+                                cells.push(OversizedCell {
+                                    path: path.to_vec(),
+                                    row,
+                                    column: table_definition.fields[column].field_name.to_owned(),
+                                    length,
+                                });
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(cells)
+}
+
+/// This function checks that no two rows of the DB Table or Loc PackedFile in the provided path have the same values in all
+/// the provided columns, as if they were a composite key.
+///
+/// It returns the groups of rows (their indexes) sharing the same values. If it's empty, there are no conflicts.
+pub fn check_composite_uniqueness(
+    pack_file: &mut PackFile,
+    path: &[String],
+    columns: &[String],
+    schema: &Schema,
+) -> Result<Vec<Vec<usize>>> {
+
+    if columns.is_empty() { Err(ErrorKind::EmptyInput)? }
+    let (table_definition, entries) = get_table_data(pack_file, path, schema)?;
+    let mut column_indexes = vec![];
+    for column in columns {
+        match table_definition.fields.iter().position(|x| &x.field_name == column) {
+            Some(index) => column_indexes.push(index),
+            None => Err(ErrorKind::ColumnNotFound(column.to_owned()))?,
+        }
+    }
+
+    let mut groups: BTreeMap<Vec<String>, Vec<usize>> = BTreeMap::new();
+    for (row, entry) in entries.iter().enumerate() {
+        let values = column_indexes.iter().map(|x| entry[*x].data_to_string()).collect::<Vec<String>>();
+        groups.entry(values).or_insert_with(|| vec![]).push(row);
+    }
+
+    let mut conflicts = groups.into_iter().map(|(_, rows)| rows).filter(|x| x.len() > 1).collect::<Vec<Vec<usize>>>();
+    conflicts.sort();
+    Ok(conflicts)
+}
+
+/// This function checks that the key columns of the DB Table or Loc PackedFile in the provided path (see `get_key_columns()`)
+/// hold non-empty values, unique between rows, as a broken key breaks the lookups and references of the game.
+///
+/// It returns a line for each violation found. If it's empty, the key of the table is valid.
+pub fn validate_key_column(
+    pack_file: &mut PackFile,
+    path: &[String],
+    schema: &Schema,
+) -> Result<Vec<String>> {
+
+    let (table_definition, entries) = get_table_data(pack_file, path, schema)?;
+    let key_columns = get_key_columns(&table_definition);
+
+    let mut violations = vec![];
+    let mut keys: BTreeMap<Vec<String>, usize> = BTreeMap::new();
+    for (row, entry) in entries.iter().enumerate() {
+        let key = key_columns.iter().map(|x| entry[*x].data_to_string()).collect::<Vec<String>>();
+        let empty_columns = key_columns.iter().zip(&key).filter(|(_, value)| value.is_empty()).map(|(column, _)| &table_definition.fields[*column].field_name).collect::<Vec<&String>>();
+        if !empty_columns.is_empty() {
+            for column in empty_columns {
+                violations.push(format!("Row {}: the key column \"{}\" is empty.", row + 1, column));
+            }
+        }
+        else if let Some(first_row) = keys.get(&key) {
+            violations.push(format!("Row {}: the key \"{}\" is already used in row {}.", row + 1, key.join(", "), first_row + 1));
+        }
+        else { keys.insert(key, row); }
+    }
+
+    Ok(violations)
+}
+
+/// This function suggests a key for a new row of the DB Table or Loc PackedFile in the provided path, based on the provided one.
+/// If the base key is not used in the first key column of the table, it's returned as it is. Otherwise, a numeric suffix
+/// (`base_1`, `base_2`,...) is added until we find one that's not in use.
+pub fn suggest_unique_key(
+    pack_file: &mut PackFile,
+    path: &[String],
+    base: &str,
+    schema: &Schema,
+) -> Result<String> {
+
+    if base.is_empty() { Err(ErrorKind::EmptyInput)? }
+    let (table_definition, entries) = get_table_data(pack_file, path, schema)?;
+    let key_column = match get_key_columns(&table_definition).first() {
+        Some(column) => *column,
+        None => return Ok(base.to_owned()),
+    };
+
+    let keys = entries.iter().map(|x| x[key_column].data_to_string()).collect::<BTreeSet<String>>();
+    let mut key = base.to_owned();
+    let mut index = 1;
+    while keys.contains(&key) {
+        key = format!("{}_{}", base, index);
+        index += 1;
+    }
+    Ok(key)
+}
+
+/// This function splits the DB Table in the provided path into multiple tables, one for each distinct value of the provided column.
+/// The new tables are created in the same folder, named like `{original name}_{value}`, with any character that's not valid in a
+/// name replaced by `_`. If `keep_original` is false, the original table is removed from the PackFile.
+///
+/// It returns the paths of the new tables.
+pub fn split_table_by_column(
+    pack_file: &mut PackFile,
+    path: &[String],
+    column: &str,
+    keep_original: bool,
+    schema: &Schema,
+) -> Result<Vec<Vec<String>>> {
+
+    if path.len() != 3 || path[0] != "db" { Err(ErrorKind::DBTableIsNotADBTable)? }
+    let table_name = pack_file.get_table_definition_name(&path[1]).to_owned();
+    let index = match pack_file.packed_files.iter().position(|x| x.path == path) {
+        Some(index) => index,
+        None => Err(ErrorKind::PackedFileNotFound)?,
+    };
+
+    let db = DB::read(&pack_file.packed_files[index].get_data_and_keep_it()?, &table_name, schema)?;
+    let column = match db.table_definition.fields.iter().position(|x| x.field_name == column) {
+        Some(column) => column,
+        None => Err(ErrorKind::ColumnNotFound(column.to_owned()))?,
+    };
+
+    // Group the rows by the value of the column, keeping their original order.
+    let mut groups: BTreeMap<String, Vec<Vec<DecodedData>>> = BTreeMap::new();
+    for row in &db.entries {
+        groups.entry(row[column].data_to_string()).or_insert_with(|| vec![]).push(row.to_vec());
+    }
+
+    let mut packed_files = vec![];
+    for (value, entries) in groups {
+        let value = if value.is_empty() { "empty".to_owned() } else { value.chars().map(|x| if x.is_alphanumeric() || x == '-' { x } else { '_' }).collect() };
+        let mut new_db = DB::new(&db.db_type, db.version, db.table_definition.clone());
+        new_db.mysterious_byte = db.mysterious_byte;
+        new_db.entries = entries;
+
+        let new_path = vec![path[0].to_owned(), path[1].to_owned(), format!("{}_{}", path[2], value)];
+        packed_files.push(PackedFile::read_from_vec(new_path, get_current_time(), false, new_db.save()));
+    }
+
+    if !keep_original { pack_file.remove_packedfile(index); }
+    Ok(pack_file.add_packed_files(&packed_files))
+}
+
+/// This function builds a matrix with the texts of the provided Loc PackedFiles, with one row per key and one column per Loc PackedFile,
+/// so translations to different languages can be compared side by side. Keys missing in a Loc PackedFile have their text blank.
+///
+/// It returns the rows sorted by key, each one like `[key, text of the first loc, text of the second loc,...]`.
+pub fn get_translation_matrix(
+    pack_file: &mut PackFile,
+    locs: &[Vec<String>],
+) -> Result<Vec<Vec<String>>> {
+
+    let mut matrix: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (column, path) in locs.iter().enumerate() {
+        if path.last().map_or(true, |x| !x.ends_with(".loc")) { Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
+        let (_, entries) = get_table_data(pack_file, path, &Schema::new())?;
+        for entry in &entries {
+            let texts = matrix.entry(entry[0].data_to_string()).or_insert_with(|| vec![String::new(); locs.len()]);
+            texts[column] = entry[1].data_to_string();
+        }
+    }
+
+    Ok(matrix.into_iter().map(|(key, mut texts)| { texts.insert(0, key); texts }).collect())
+}
+
+/// This function exports the translation matrix of the provided Loc PackedFiles (see `get_translation_matrix()`) to a TSV file,
+/// with the paths of the Loc PackedFiles as header.
+pub fn export_translation_matrix(
+    pack_file: &mut PackFile,
+    locs: &[Vec<String>],
+    path: &PathBuf,
+) -> Result<()> {
+
+    let matrix = get_translation_matrix(pack_file, locs)?;
+    let mut writer = new_tsv_writer();
+
+    let mut header = vec!["key".to_owned()];
+    header.extend(locs.iter().map(|x| x.join("/")));
+    writer.serialize(header)?;
+    for row in &matrix {
+        writer.serialize(row)?;
+    }
+
+    save_tsv_writer(writer, path)?;
+    Ok(())
+}
+
+/// This function exports the texts of the provided Loc PackedFiles to a bilingual TSV file, for translators. Each row has the key,
+/// the text of the key in the provided Loc PackedFiles (source) and the text of the key in the Loc PackedFiles of the PackFile
+/// in the provided path (target), like a translation pack or the vanilla loc pack of another language.
+///
+/// It returns the keys of the source missing in the target. Their target text is left blank.
+pub fn export_bilingual_tsv(
+    pack_file: &mut PackFile,
+    locs: &[Vec<String>],
+    target_pack_file_path: &PathBuf,
+    path: &PathBuf,
+) -> Result<Vec<String>> {
+
+    let mut source_texts = BTreeMap::new();
+    for loc_path in locs {
+        if loc_path.last().map_or(true, |x| !x.ends_with(".loc")) { Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
+        let packed_file = match pack_file.packed_files.iter_mut().find(|x| &x.path == loc_path) {
+            Some(packed_file) => packed_file,
+            None => Err(ErrorKind::PackedFileNotFound)?,
+        };
+
+        for entry in Loc::read(&packed_file.get_data_and_keep_it()?)?.entries {
+            source_texts.entry(entry[0].data_to_string()).or_insert_with(|| entry[1].data_to_string());
+        }
+    }
+
+    let mut target_texts = BTreeMap::new();
+    let target_pack_file = PackFile::read(target_pack_file_path.to_path_buf(), true)?;
+    for packed_file in &target_pack_file.packed_files {
+        if !packed_file.path.is_empty() && packed_file.path.last().unwrap().ends_with(".loc") {
+            if let Ok(loc) = Loc::read(&packed_file.get_data()?) {
+                for entry in loc.entries {
+                    target_texts.entry(entry[0].data_to_string()).or_insert_with(|| entry[1].data_to_string());
+                }
+            }
+        }
+    }
+
+    let mut writer = new_tsv_writer();
+
+    let mut missing_keys = vec![];
+    writer.serialize(("key", "source", "target"))?;
+    for (key, source_text) in &source_texts {
+        let target_text = match target_texts.get(key) {
+            Some(text) => text.to_owned(),
+            None => {
+                missing_keys.push(key.to_owned());
+                String::new()
+            }
+        };
+        writer.serialize((key, source_text, target_text))?;
+    }
+
+    save_tsv_writer(writer, path)?;
+    Ok(missing_keys)
+}
+
+/// This function imports a bilingual TSV file made by `export_bilingual_tsv()` into the Loc PackedFile in the provided path. Only
+/// the text of the keys of the TSV is changed, with their target text. Keys of the TSV missing in the Loc PackedFile are added to it.
+///
+/// It returns how many entries have been updated, and the keys that have been added.
+pub fn import_bilingual_tsv(
+    pack_file: &mut PackFile,
+    loc_path: &[String],
+    path: &PathBuf,
+) -> Result<(usize, Vec<String>)> {
+
+    if loc_path.last().map_or(true, |x| !x.ends_with(".loc")) { Err(ErrorKind::LocPackedFileIsNotALocPackedFile)? }
+    let packed_file = match pack_file.packed_files.iter_mut().find(|x| x.path == loc_path) {
+        Some(packed_file) => packed_file,
+        None => Err(ErrorKind::PackedFileNotFound)?,
+    };
+    let mut loc = Loc::read(&packed_file.get_data_and_keep_it()?)?;
+
+    let mut reader = ReaderBuilder::new()
+        .delimiter(b'\t')
+        .quoting(false)
+        .has_headers(false)
+        .flexible(true)
+        .from_path(&path)?;
+
+    let mut rows = loc.entries.iter().enumerate().map(|(row, entry)| (entry[0].data_to_string(), row)).collect::<BTreeMap<String, usize>>();
+    let mut updated = 0;
+    let mut added_keys = vec![];
+    for (index, record) in reader.records().enumerate() {
+        let record = record?;
+        let key = record.get(0).unwrap_or("");
+        if key.is_empty() || (index == 0 && key == "key") { continue; }
+        let text = record.get(2).unwrap_or("");
+
+        match rows.get(key) {
+            Some(row) => {
+                if loc.entries[*row][1].data_to_string() != text {
+                    loc.entries[*row][1] = DecodedData::StringU16(text.to_owned());
+                    updated += 1;
+                }
+            }
+            None => {
+                rows.insert(key.to_owned(), loc.entries.len());
+                loc.entries.push(vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(true)]);
+                added_keys.push(key.to_owned());
+            }
+        }
+    }
+
+    if updated > 0 || !added_keys.is_empty() { packed_file.set_data(loc.save()); }
+    Ok((updated, added_keys))
+}
+
+/// This function pairs each row of the DB Table or Loc PackedFile in the provided path with the vanilla row with the same key,
+/// searching for it in the tables of the same type in the provided dependency database.
+///
+/// It returns a header row, with the mod columns prefixed with `mod:` and the vanilla ones with `vanilla:`, followed by the paired
+/// rows. Rows that only exist in the mod have their vanilla columns blank. Rows of tables without key columns are only paired
+/// with vanilla rows equal to them.
+pub fn get_vanilla_mod_comparison(
+    pack_file: &mut PackFile,
+    dependency_database: &mut [PackedFile],
+    path: &[String],
+    schema: &Schema,
+) -> Result<Vec<Vec<String>>> {
+
+    let (table_definition, entries) = get_table_data(pack_file, path, schema)?;
+    let is_loc = path.last().unwrap().ends_with(".loc");
+    let table_name = if is_loc { String::new() } else { pack_file.get_table_definition_name(&path[1]).to_owned() };
+
+    // Get the vanilla rows by key. If more than one vanilla table has the same key, the first one wins.
+    let mut vanilla_definition = None;
+    let mut vanilla_rows: BTreeMap<Vec<String>, Vec<String>> = BTreeMap::new();
+    for packed_file in dependency_database.iter_mut() {
+        let vanilla_data = if is_loc && packed_file.path.last().map_or(false, |x| x.ends_with(".loc")) {
+            Loc::read(&packed_file.get_data_and_keep_it()?).ok().map(|x| (TableDefinition::new_loc_definition(), x.entries))
+        } else if !is_loc && packed_file.path.len() == 3 && packed_file.path[1] == table_name {
+            DB::read(&packed_file.get_data_and_keep_it()?, &table_name, schema).ok().map(|x| (x.table_definition, x.entries))
+        } else { None };
+
+        if let Some((definition, vanilla_entries)) = vanilla_data {
+            let key_columns = if is_loc { get_key_columns(&definition) } else { get_row_identity_columns(&definition) };
+            for entry in &vanilla_entries {
+                let key = key_columns.iter().map(|x| entry[*x].data_to_string()).collect::<Vec<String>>();
+                vanilla_rows.entry(key).or_insert_with(|| entry.iter().map(|x| x.data_to_string()).collect());
+            }
+            if vanilla_definition.is_none() { vanilla_definition = Some(definition); }
+        }
+    }
+
+    let vanilla_definition = vanilla_definition.unwrap_or_else(|| table_definition.clone());
+    let vanilla_columns = vanilla_definition.fields.len();
+    let mut header = table_definition.fields.iter().map(|x| format!("mod:{}", x.field_name)).collect::<Vec<String>>();
+    header.extend(vanilla_definition.fields.iter().map(|x| format!("vanilla:{}", x.field_name)));
+
+    let mut rows = vec![header];
+    let key_columns = if is_loc { get_key_columns(&table_definition) } else { get_row_identity_columns(&table_definition) };
+    for entry in &entries {
+        let key = key_columns.iter().map(|x| entry[*x].data_to_string()).collect::<Vec<String>>();
+        let mut row = entry.iter().map(|x| x.data_to_string()).collect::<Vec<String>>();
+        let mut vanilla_row = vanilla_rows.get(&key).cloned().unwrap_or_else(|| vec![]);
+        vanilla_row.resize(vanilla_columns, String::new());
+        row.append(&mut vanilla_row);
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// This function exports the comparison between the DB Table or Loc PackedFile in the provided path and his vanilla version
+/// (see `get_vanilla_mod_comparison()`) to a TSV file.
+pub fn export_vanilla_mod_comparison(
+    pack_file: &mut PackFile,
+    dependency_database: &mut [PackedFile],
+    path: &[String],
+    schema: &Schema,
+    out: &PathBuf,
+) -> Result<()> {
+
+    let rows = get_vanilla_mod_comparison(pack_file, dependency_database, path, schema)?;
+    let mut writer = new_tsv_writer();
+
+    for row in &rows {
+        writer.serialize(row)?;
+    }
+
+    save_tsv_writer(writer, out)?;
+    Ok(())
+}
+
+/// This function returns the rows of the vanilla versions of the DB Table in the provided path by the values of the identity columns
+/// of the provided definition (see `get_row_identity_columns()`), with their columns in the same order as in the provided definition.
+/// Columns the vanilla versions don't have are None. If more than one vanilla table has the same key, the first one wins.
+/// If the table has no vanilla versions, or they lack any of the identity columns, the map is empty.
+pub fn get_vanilla_rows(
+    pack_file: &PackFile,
+    dependency_database: &mut [PackedFile],
+    path: &[String],
+    table_definition: &TableDefinition,
+    schema: &Schema,
+) -> Result<BTreeMap<Vec<String>, Vec<Option<String>>>> {
+
+    let table_name = pack_file.get_table_definition_name(&path[1]);
+    let identity_columns = get_row_identity_columns(table_definition);
+    let mut vanilla_rows = BTreeMap::new();
+    for packed_file in dependency_database.iter_mut().filter(|x| x.path.len() == 3 && x.path[0] == "db" && x.path[1] == table_name) {
+        if let Ok(table) = DB::read(&packed_file.get_data_and_keep_it()?, table_name, schema) {
+
+            // Vanilla may use another version of the table, so his columns are mapped to ours by name. The key is built from our
+            // identity columns, so both sides are matched the same way. If vanilla lacks any of them, none of his rows can match ours.
+            let columns = table_definition.fields.iter().map(|x| table.table_definition.fields.iter().position(|y| y.field_name == x.field_name)).collect::<Vec<Option<usize>>>();
+            let key_columns = match identity_columns.iter().map(|x| columns[*x]).collect::<Option<Vec<usize>>>() {
+                Some(key_columns) => key_columns,
+                None => continue,
+            };
+
+            for entry in &table.entries {
+                let key = key_columns.iter().map(|x| entry[*x].data_to_string()).collect::<Vec<String>>();
+                vanilla_rows.entry(key).or_insert_with(|| columns.iter().map(|x| x.map(|x| entry[x].data_to_string())).collect());
+            }
+        }
+    }
+
+    Ok(vanilla_rows)
+}
+
+/// This function applies the provided operations to the DB Tables and Loc PackedFiles of the provided PackFile, as a transaction:
+/// all the operations are done over decoded copies of the tables, and these are only saved back if every operation worked.
+/// If any of them fails, the PackFile is left untouched.
+///
+/// It returns a line of report for each operation.
+pub fn run_edit_script(
+    pack_file: &mut PackFile,
+    operations: &[EditOperation],
+    schema: &Schema,
+) -> Result<Vec<String>> {
+
+    let mut tables: BTreeMap<Vec<String>, (TableDefinition, Vec<Vec<DecodedData>>)> = BTreeMap::new();
+    let mut report = vec![];
+    for (index, operation) in operations.iter().enumerate() {
+        match apply_edit_operation(pack_file, &mut tables, operation, schema) {
+            Ok(line) => report.push(line),
+            Err(error) => Err(ErrorKind::EditScriptOperationFailed(index + 1, error.to_string()))?,
+        }
+    }
+
+    // Encode everything before saving anything, so we don't leave the PackFile half-edited if something goes wrong.
+    let mut encoded_tables = vec![];
+    for (path, (_, entries)) in tables {
+        let data = encode_table_data(pack_file, &path, entries, schema)?;
+        encoded_tables.push((path, data));
+    }
+
+    for (path, data) in encoded_tables {
+        pack_file.packed_files.iter_mut().find(|x| x.path == path).unwrap().set_data(data);
+    }
+
+    Ok(report)
+}
+
+/// This function encodes the provided entries with the header of the DB Table or Loc PackedFile in the provided path, without saving them.
+fn encode_table_data(
+    pack_file: &mut PackFile,
+    path: &[String],
+    entries: Vec<Vec<DecodedData>>,
+    schema: &Schema,
+) -> Result<Vec<u8>> {
+    let packed_file = pack_file.packed_files.iter_mut().find(|x| x.path == path).ok_or_else(|| Error::from(ErrorKind::PackedFileNotFound))?;
+    let data = packed_file.get_data_and_keep_it()?;
+    if path.last().unwrap().ends_with(".loc") {
+        let mut loc = Loc::read(&data)?;
+        loc.entries = entries;
+        Ok(loc.save())
+    } else {
+        let mut db = DB::read(&data, pack_file.get_table_definition_name(&path[1]), schema)?;
+        db.entries = entries;
+        Ok(db.save())
+    }
+}
+
+/// This function applies an operation of an edit script over the decoded tables provided, decoding the table it needs if it's not there yet.
+fn apply_edit_operation(
+    pack_file: &mut PackFile,
+    tables: &mut BTreeMap<Vec<String>, (TableDefinition, Vec<Vec<DecodedData>>)>,
+    operation: &EditOperation,
+    schema: &Schema,
+) -> Result<String> {
+
+    let path = match operation {
+        EditOperation::EditCell { path, .. } |
+        EditOperation::AddRow { path } |
+        EditOperation::DeleteRow { path, .. } => path,
+    };
+
+    if !tables.contains_key(path) {
+        let table_data = get_table_data(pack_file, path, schema)?;
+        tables.insert(path.to_vec(), table_data);
+    }
+
+    let (table_definition, entries) = tables.get_mut(path).unwrap();
+    match operation {
+        EditOperation::EditCell { row, column, value, .. } => {
+            let column_index = match table_definition.fields.iter().position(|x| &x.field_name == column) {
+                Some(index) => index,
+                None => Err(ErrorKind::ColumnNotFound(column.to_owned()))?,
+            };
+
+            let entry = match entries.get_mut(*row) {
+                Some(entry) => entry,
+                None => Err(ErrorKind::RowNotFound(*row))?,
+            };
+
+            let new_value = parse_tsv_field(table_definition.fields[column_index].field_type, value, *row, column_index).map_err(|_| Error::from(ErrorKind::DBTableReplaceInvalidData))?;
+            let old_value = entry[column_index].data_to_string();
+            entry[column_index] = new_value;
+            Ok(format!("{}: row {}, column \"{}\" changed from \"{}\" to \"{}\".", path.join("/"), row, column, old_value, value))
+        }
+        EditOperation::AddRow { .. } => {
+            entries.push(get_default_row(table_definition));
+            Ok(format!("{}: row {} added.", path.join("/"), entries.len() - 1))
+        }
+        EditOperation::DeleteRow { row, .. } => {
+            if *row >= entries.len() { Err(ErrorKind::RowNotFound(*row))? }
+            entries.remove(*row);
+            Ok(format!("{}: row {} deleted.", path.join("/"), row))
+        }
+    }
+}
+
+/// This function replaces, in one go, every cell of the provided column of a DB Table or Loc PackedFile that's equal to `from` with `to`.
+/// Both values have to be valid for the type of the column.
+///
+/// It returns how many cells have been replaced.
+pub fn replace_in_column(
+    pack_file: &mut PackFile,
+    path: &[String],
+    column: &str,
+    from: &str,
+    to: &str,
+    schema: &Schema,
+) -> Result<usize> {
+
+    let (table_definition, mut entries) = get_table_data(pack_file, path, schema)?;
+    let column_index = match table_definition.fields.iter().position(|x| x.field_name == column) {
+        Some(index) => index,
+        None => Err(ErrorKind::ColumnNotFound(column.to_owned()))?,
+    };
+
+    let field_type = table_definition.fields[column_index].field_type;
+    let from = parse_tsv_field(field_type, from, 0, column_index).map_err(|_| Error::from(ErrorKind::DBTableReplaceInvalidData))?;
+    let to = parse_tsv_field(field_type, to, 0, column_index).map_err(|_| Error::from(ErrorKind::DBTableReplaceInvalidData))?;
+
+    let mut replaced = 0;
+    for entry in entries.iter_mut().filter(|x| x[column_index] == from) {
+        entry[column_index] = to.clone();
+        replaced += 1;
+    }
+
+    if replaced > 0 {
+        let data = encode_table_data(pack_file, path, entries, schema)?;
+        pack_file.packed_files.iter_mut().find(|x| x.path == path).unwrap().set_data(data);
+    }
+
+    Ok(replaced)
+}
+
+/// This function returns the rows of every Loc PackedFile in the provided PackFile whose text contains the provided substring.
+///
+/// The first row is the header. Then there is a row for each match, with the path of his Loc PackedFile, his key and his text.
+pub fn get_loc_matches(
+    pack_file: &mut PackFile,
+    substring: &str,
+    case_sensitive: bool,
+) -> Result<Vec<Vec<String>>> {
+
+    if substring.is_empty() { Err(ErrorKind::EmptyInput)? }
+    let substring = if case_sensitive { substring.to_owned() } else { substring.to_lowercase() };
+
+    let mut matches = vec![vec!["File".to_owned(), "Key".to_owned(), "Text".to_owned()]];
+    for packed_file in pack_file.packed_files.iter_mut() {
+        if let DecodeablePackedFileType::Loc = get_packed_file_type(&packed_file.path) {
+            if let Ok(loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                for entry in &loc.entries {
+                    let text = entry[1].data_to_string();
+                    let is_match = if case_sensitive { text.contains(&substring) } else { text.to_lowercase().contains(&substring) };
+                    if is_match { matches.push(vec![packed_file.path.join("/"), entry[0].data_to_string(), text]); }
+                }
+            }
+        }
+    }
+
+    Ok(matches)
+}
+
+/// This function exports the Loc rows of the provided PackFile whose text contains the provided substring (see `get_loc_matches()`) to a TSV file.
+pub fn export_loc_matches(
+    pack_file: &mut PackFile,
+    substring: &str,
+    case_sensitive: bool,
+    out: &PathBuf,
+) -> Result<()> {
+
+    let matches = get_loc_matches(pack_file, substring, case_sensitive)?;
+    let mut writer = new_tsv_writer();
+
+    for row in &matches {
+        writer.serialize(row)?;
+    }
+
+    save_tsv_writer(writer, out)?;
+    Ok(())
+}
+
+/// This function collapses every run of consecutive identical rows of the DB Table or Loc PackedFile in the provided path
+/// into a single row. Identical rows that are not next to each other are left alone.
+///
+/// It returns how many rows have been removed.
+pub fn merge_identical_adjacent_rows(
+    pack_file: &mut PackFile,
+    path: &[String],
+    schema: &Schema,
+) -> Result<usize> {
+
+    let (_, mut entries) = get_table_data(pack_file, path, schema)?;
+    let old_len = entries.len();
+    entries.dedup();
+
+    let removed = old_len - entries.len();
+    if removed > 0 {
+        let data = encode_table_data(pack_file, path, entries, schema)?;
+        pack_file.packed_files.iter_mut().find(|x| x.path == path).unwrap().set_data(data);
+    }
+
+    Ok(removed)
+}
+
+/// This function returns a fingerprint of the provided rows that doesn't depend on their order, so two tables with the same
+/// rows in a different order get the same fingerprint.
+///
+/// It uses FNV-1a instead of the hasher of the std, as that one can change between Rust versions, and these fingerprints are
+/// meant to be compared between builds.
+pub fn get_table_fingerprint(entries: &[Vec<DecodedData>]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    let fnv1a = |data: &[u8], mut hash: u64| {
+        for byte in data {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        hash
+    };
+
+    // Hash each row on its own, then hash the sorted list of row hashes.
+    let mut row_hashes = entries.iter().map(|row| {
+        let row = row.iter().map(|x| x.data_to_string()).collect::<Vec<String>>().join("\u{0}");
+        fnv1a(row.as_bytes(), FNV_OFFSET_BASIS)
+    }).collect::<Vec<u64>>();
+    row_hashes.sort_unstable();
+    row_hashes.iter().fold(FNV_OFFSET_BASIS, |hash, row_hash| fnv1a(&row_hash.to_le_bytes(), hash))
+}
+
+/// This function returns the fingerprint (see `get_table_fingerprint()`) of every DB Table and Loc PackedFile in the provided PackFile.
+/// Tables that cannot be decoded are skipped.
+pub fn get_table_checksums(
+    pack_file: &mut PackFile,
+    schema: &Schema,
+) -> Result<Vec<(Vec<String>, u64)>> {
+    let paths = pack_file.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+    let mut checksums = vec![];
+    for path in &paths {
+        if let Ok((_, entries)) = get_table_data(pack_file, path, schema) {
+            checksums.push((path.to_vec(), get_table_fingerprint(&entries)));
+        }
+    }
+    Ok(checksums)
+}
+
+/// This function exports the fingerprints of the tables of the provided PackFile (see `get_table_checksums()`) to a TSV file, with a line
+/// for each table, like `path/of/the/table\tchecksum`.
+pub fn export_table_checksums(
+    pack_file: &mut PackFile,
+    schema: &Schema,
+    out: &PathBuf,
+) -> Result<()> {
+    let checksums = get_table_checksums(pack_file, schema)?;
+    let mut file = File::create(&out)?;
+    for (path, checksum) in &checksums {
+        file.write_all(format!("{}\t{:016x}\n", path.join("/"), checksum).as_bytes())?;
+    }
+    Ok(())
+}
+
+/// This function turns the provided rows into a GitHub-flavored Markdown table, with the provided headers.
+/// Pipes are escaped and line jumps are turned into `<br>`, so they don't break the table.
+pub fn to_markdown_table(headers: &[String], rows: &[Vec<String>]) -> String {
+    let escape = |text: &str| text.replace('|', "\\|").replace("\r\n", "<br>").replace('\n', "<br>");
+    let mut table = format!("| {} |\n", headers.iter().map(|x| escape(x)).collect::<Vec<String>>().join(" | "));
+    table.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+    for row in rows {
+        table.push_str(&format!("| {} |\n", row.iter().map(|x| escape(x)).collect::<Vec<String>>().join(" | ")));
+    }
+    table
+}
+
+/// This function quotes a cell for the clipboard the same way Excel and LibreOffice do, if it has tabs or newlines
+/// (or starts with a quote), so it can be pasted back as a single cell. Quotes inside quoted cells are doubled.
+pub fn quote_clipboard_cell(cell: &str) -> String {
+    if cell.contains(|x| x == '\t' || x == '\n' || x == '\r') || cell.starts_with('"') {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else { cell.to_owned() }
+}
+
+/// This function parses the text of the clipboard as a TSV, with the quoting rules of Excel and LibreOffice: cells between
+/// double quotes can contain tabs and newlines, and doubled quotes inside them are single quotes. The last line jump is ignored.
+pub fn parse_clipboard_tsv(text: &str) -> Vec<Vec<String>> {
+    let text = if text.ends_with("\r\n") { &text[..text.len() - 2] } else if text.ends_with('\n') { &text[..text.len() - 1] } else { text };
+
+    let mut rows = vec![vec![]];
+    let mut cell = String::new();
+    let mut cell_start = true;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(character) = chars.next() {
+        if in_quotes {
+            if character == '"' {
+                if chars.peek() == Some(&'"') { cell.push('"'); chars.next(); }
+                else { in_quotes = false; }
+            } else { cell.push(character); }
+            continue;
+        }
+
+        match character {
+            '"' if cell_start => in_quotes = true,
+            '\t' => rows.last_mut().unwrap().push(cell.split_off(0)),
+            '\r' if chars.peek() == Some(&'\n') => {},
+            '\n' => {
+                rows.last_mut().unwrap().push(cell.split_off(0));
+                rows.push(vec![]);
+            }
+            _ => cell.push(character),
+        }
+        cell_start = character == '\t' || character == '\n';
+    }
+    rows.last_mut().unwrap().push(cell);
+    rows
+}
+
+/// This function turns the provided rows into tab-separated lines, like the normal copy, with the provided headers as first line.
+pub fn to_tsv_with_headers(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut lines = vec![headers.iter().map(|x| quote_clipboard_cell(x)).collect::<Vec<String>>().join("\t")];
+    lines.extend(rows.iter().map(|row| row.iter().map(|x| quote_clipboard_cell(x)).collect::<Vec<String>>().join("\t")));
+    lines.join("\n")
+}
+
+/// This function turns the provided rows into a JSON array of objects, using the names of the provided fields as keys.
+/// Cells set to None are not included in their object, and values that cannot be parsed as the type of their field are kept as strings.
+///
+/// The members of each object are written in the same order as the provided fields. `serde_json` objects are sorted by key,
+/// so the objects are written by hand, with the same format as `serde_json::to_string_pretty()`.
+pub fn to_json_objects(fields: &[&Field], rows: &[Vec<Option<String>>]) -> String {
+    let objects = rows.iter().map(|row| {
+        let members = row.iter().zip(fields).filter_map(|(cell, field)| cell.as_ref().map(|cell| {
+            let value = parse_tsv_field(field.field_type, cell, 0, 0).map_or_else(|_| Value::String(cell.to_owned()), |x| x.to_json_value());
+            format!("    {}: {}", Value::String(field.field_name.to_owned()), value)
+        })).collect::<Vec<String>>();
+        if members.is_empty() { "  {}".to_owned() } else { format!("  {{\n{}\n  }}", members.join(",\n")) }
+    }).collect::<Vec<String>>();
+    if objects.is_empty() { "[]".to_owned() } else { format!("[\n{}\n]", objects.join(",\n")) }
+}
+
+/// This function returns the row count of every DB Table and Loc PackedFile in the provided PackFile. For DB Tables,
+/// the count is read from their header, so it works even for tables we cannot decode. Broken tables are skipped.
+pub fn get_row_counts(
+    pack_file: &mut PackFile,
+) -> Result<Vec<(Vec<String>, u32)>> {
+
+    let mut counts = vec![];
+    for packed_file in pack_file.packed_files.iter_mut() {
+        match get_packed_file_type(&packed_file.path) {
+            DecodeablePackedFileType::DB if packed_file.path.len() == 3 => {
+                if let Ok((_, entry_count, _)) = DB::get_header_data(&packed_file.get_data_and_keep_it()?) {
+                    counts.push((packed_file.path.to_vec(), entry_count));
+                }
+            }
+            DecodeablePackedFileType::Loc => {
+                if let Ok(loc) = Loc::read(&packed_file.get_data_and_keep_it()?) {
+                    counts.push((packed_file.path.to_vec(), loc.entries.len() as u32));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(counts)
+}
+
+/// This function returns the size in bytes of the DB Tables and Loc PackedFiles of the provided PackFile, aggregated by
+/// table type (the table folder for DB Tables, `loc` for Loc PackedFiles). Edited tables are encoded when the edit
+/// is done, so we use the size of their current data instead of encoding them again.
+pub fn get_size_by_table_type(pack_file: &PackFile) -> BTreeMap<String, u64> {
+    let mut sizes = BTreeMap::new();
+    for packed_file in &pack_file.packed_files {
+        let table_type = match get_packed_file_type(&packed_file.path) {
+            DecodeablePackedFileType::DB if packed_file.path.len() == 3 => packed_file.path[1].to_owned(),
+            DecodeablePackedFileType::Loc => "loc".to_owned(),
+            _ => continue,
+        };
+        *sizes.entry(table_type).or_insert(0) += u64::from(packed_file.get_size());
+    }
+    sizes
+}
+
+/// This function appends a snapshot of the row counts of every table in the provided PackFile to the log file in the provided path,
+/// creating it if it doesn't exist. Each line of the log is a table, like `timestamp\tpath/of/the/table\trow count`,
+/// with the timestamp being the same for all the tables of the snapshot.
+pub fn append_row_count_snapshot(
+    pack_file: &mut PackFile,
+    log_path: &PathBuf,
+) -> Result<()> {
+
+    let timestamp = get_current_time();
+    let snapshot = get_row_counts(pack_file)?.iter().map(|(path, count)| format!("{}\t{}\t{}\n", timestamp, path.join("/"), count)).collect::<String>();
+
+    let mut file = OpenOptions::new().create(true).append(true).open(log_path)?;
+    file.write_all(snapshot.as_bytes())?;
+    Ok(())
+}
+
+//----------------------------------------------------------------//
+// TSV Functions for PackedFiles.
+//----------------------------------------------------------------//
+
+/// This enum represents the policies for quoting the values of the delimited files (see `export_delimited()`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DelimitedQuoting {
+
+    /// Quote every value.
+    Always,
+
+    /// Quote only the values that need it, because they have the delimiter, quotes or newlines.
+    Minimal,
+
+    /// Never quote values.
+    Never,
+}
+
+/// This enum represents a problem found in a row of a TSV file while importing it. Rows and columns start at 0.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TSVError {
+
+    /// The value in the cell at this row and column cannot be parsed as this type.
+    InvalidCell(usize, usize, FieldType),
+
+    /// This row has this amount of columns, which is more than the amount of columns the header has.
+    InvalidRowLength(usize, usize),
+
+    /// This row cannot be read at all.
+    InvalidRow(usize),
+}
+
+/// This function imports a TSV file and loads his contents into a DB Table or a Loc PackedFile. Instead of assuming the
+/// columns are in the same order as in the definition, it uses the header row (the second one) to know what column goes
+/// where, so TSVs from older versions of the table or reordered in other programs still work. Missing columns get their
+/// default value.
+///
+/// Problems in the rows don't stop the import. The rows with them are skipped, and their problems returned, so the user
+/// can decide what to do. Only problems with the file itself, like being for another table, make it fail.
+///
+/// It returns the entries, the list of columns of the TSV that are not in the definition, which are ignored, and the problems in the rows.
+pub fn import_tsv(
+    definition: &TableDefinition,
+    path: &PathBuf,
+    name: &str,
+    version: i32,
+) -> Result<(Vec<Vec<DecodedData>>, Vec<String>, Vec<TSVError>)> {
+    read_tsv(definition, path, Some((name, version)))
+}
+
+/// This function imports a TSV file made for another version of a DB Table, like `import_tsv()` does with the ones made for his version.
+/// The TSV is read using the definition of his own version, and then his entries are converted to the provided definition (see `DB::set_definition()`).
+pub fn import_tsv_from_other_version(
+    definition: &TableDefinition,
+    path: &PathBuf,
+    name: &str,
+) -> Result<(Vec<Vec<DecodedData>>, Vec<String>, Vec<TSVError>)> {
+    let (table_type, table_version, table_definition) = get_tsv_table_info(path).ok_or_else(|| Error::from(ErrorKind::SchemaTableDefinitionNotFound))?;
+    if table_type != name { return Err(ErrorKind::ImportTSVWrongTypeTable)? }
+
+    let (entries, unknown_columns, errors) = import_tsv(&table_definition, path, name, table_version)?;
+    let mut table = DB::new(name, table_version, table_definition);
+    table.entries = entries;
+    table.set_definition(definition);
+    Ok((table.entries, unknown_columns, errors))
+}
+
+/// This function imports a delimited file (like a CSV) made by `export_delimited()`, with the same delimiter and quoting policy.
+/// These files don't have the line with the type and version of the table, so the columns are mapped only by the header.
+///
+/// It returns the same as `import_tsv()`.
+pub fn import_delimited(
+    definition: &TableDefinition,
+    path: &PathBuf,
+    delimiter: u8,
+    quoting: DelimitedQuoting,
+) -> Result<(Vec<Vec<DecodedData>>, Vec<String>, Vec<TSVError>)> {
+    read_delimited(definition, path, None, delimiter, quoting)
+}
+
+/// This function reads a TSV file like `import_tsv()`. If `metadata` is None, the file doesn't have the line with
+/// the type and version of the table, and his first line is the header.
+fn read_tsv(
+    definition: &TableDefinition,
+    path: &PathBuf,
+    metadata: Option<(&str, i32)>,
+) -> Result<(Vec<Vec<DecodedData>>, Vec<String>, Vec<TSVError>)> {
+
+    // We want the reader to have no quotes, tab as delimiter and custom headers, because otherwise
+    // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
+    read_delimited(definition, path, metadata, b'\t', DelimitedQuoting::Never)
+}
+
+/// This function reads a delimited file (TSV, CSV,...) like `read_tsv()`, but with the provided delimiter and quoting policy.
+fn read_delimited(
+    definition: &TableDefinition,
+    path: &PathBuf,
+    metadata: Option<(&str, i32)>,
+    delimiter: u8,
+    quoting: DelimitedQuoting,
+) -> Result<(Vec<Vec<DecodedData>>, Vec<String>, Vec<TSVError>)> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .quoting(quoting != DelimitedQuoting::Never)
+        .has_headers(false)
+        .flexible(true)
+        .from_path(&path)?;
+
+    // For each column of the TSV, the index of his field in the definition, if it has one.
+    let mut columns_mapping: Vec<Option<usize>> = vec![];
+    let mut unknown_columns = vec![];
+    let mut entries = vec![];
+    let mut errors = vec![];
+    let header_row = if metadata.is_some() { 1 } else { 0 };
+    for (row, record) in reader.records().enumerate() {
+        if let Ok(record) = record {
+
+            // The first line should contain the "table_folder_name"/"Loc PackedFile/PackFile List", and the version (1 for Locs).
+            if let (0, Some((name, version))) = (row, metadata) {
+                if record.get(0).unwrap_or("error") != name { return Err(ErrorKind::ImportTSVWrongTypeTable)?; }
+                if record.get(1).unwrap_or("-1").parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVInvalidVersion))? != version { 
+                    return Err(ErrorKind::ImportTSVWrongVersion)?;
+                }
+            }
+
+            // The next line contains the column headers. We map each one of them to his field in the definition.
+            // If a column is not in the definition, or it's repeated, we ignore it and report it.
+            else if row == header_row {
+                for header in record.iter() {
+                    match definition.fields.iter().position(|x| x.field_name == header) {
+                        Some(index) if !columns_mapping.contains(&Some(index)) => columns_mapping.push(Some(index)),
+                        _ => {
+                            columns_mapping.push(None);
+                            unknown_columns.push(header.to_owned());
+                        }
+                    }
+                }
+            }
+
+            // Then read the rest of the rows, putting each field in his place. Fields not in the TSV, or missing at the end of the row, get their default value.
+            else if record.len() <= columns_mapping.len() {
+                let mut entry = get_default_row(definition);
+                let mut is_valid = true;
+                for (column, field) in record.iter().enumerate() {
+                    if let Some(index) = columns_mapping[column] {
+                        let field_type = definition.fields[index].field_type;
+                        match parse_tsv_field(field_type, field, row, column) {
+                            Ok(value) => entry[index] = value,
+                            Err(_) => {
+                                errors.push(TSVError::InvalidCell(row, column, field_type));
+                                is_valid = false;
                             }
-        
-                            // DB Tables.
-                            _ => {
-                                let mut db = DB::new(table_type, table_version, table_definition);
-                                db.entries = data;
-                                let raw_data = db.save();
-
-                                // Depending on the name received, call it one thing or another.
-                                let name = match name {
-                                    Some(ref name) => name.to_string(),
-                                    None => path.file_stem().unwrap().to_str().unwrap().to_string(),
-                                };
+                        }
+                    }
+                }
+                if is_valid { entries.push(entry); }
+            }
 
-                                let mut path = vec!["db".to_owned(), table_type.to_owned(), name.to_owned()];
-                        
-                                // If that path already exists in the list of new PackedFiles to add, change it using the index.
-                                let mut index = 1;
-                                while packed_files.iter().any(|x| x.path == path) {
-                                    path[2] = format!("{}_{}", name, index);
-                                    index += 1;
-                                }
-                                
-                                // If that path already exists in the PackFile, add it to the "remove" list.
-                                if pack_file.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
+            // If it fails here, report the len of the record instead a field.
+            else { errors.push(TSVError::InvalidRowLength(row, record.len())); }
+        }
+
+        else { errors.push(TSVError::InvalidRow(row)); }
+    }
+
+    Ok((entries, unknown_columns, errors))
+}
+
+/// Display implementation of `TSVError`. Rows and columns are shown starting at 1, like in any other program.
+impl Display for TSVError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TSVError::InvalidCell(row, column, field_type) => write!(f, "Row {}, column {}: the value is not a valid {:?}.", row + 1, column + 1, field_type),
+            TSVError::InvalidRowLength(row, length) => write!(f, "Row {}: it has {} columns, more than the header.", row + 1, length),
+            TSVError::InvalidRow(row) => write!(f, "Row {}: it cannot be read.", row + 1),
+        }
+    }
+}
+
+/// This function parses a field of a TSV file into the `DecodedData` of the type provided.
+/// The row and column are only used for the error, in case it fails.
+fn parse_tsv_field(
+    field_type: FieldType,
+    field: &str,
+    row: usize,
+    column: usize,
+) -> Result<DecodedData> {
+    match field_type {
+        FieldType::Boolean => {
+            let value = field.to_lowercase();
+            if value == "true" || value == "1" { Ok(DecodedData::Boolean(true)) }
+            else if value == "false" || value == "0" { Ok(DecodedData::Boolean(false)) }
+            else { Err(ErrorKind::ImportTSVIncorrectRow(row, column))? }
+        }
+        FieldType::Float => Ok(DecodedData::Float(field.parse::<f32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+        FieldType::Integer => Ok(DecodedData::Integer(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+        FieldType::LongInteger => Ok(DecodedData::LongInteger(field.parse::<i64>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+        FieldType::Double => Ok(DecodedData::Double(field.parse::<f64>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+        FieldType::Colour => Ok(DecodedData::Colour(u32::from_str_radix(field.trim_start_matches('#'), 16).map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?)),
+        FieldType::StringU8 => Ok(DecodedData::StringU8(field.to_owned())),
+        FieldType::StringU16 => Ok(DecodedData::StringU16(field.to_owned())),
+        FieldType::OptionalStringU8 => Ok(DecodedData::OptionalStringU8(field.to_owned())),
+        FieldType::OptionalStringU16 => Ok(DecodedData::OptionalStringU16(field.to_owned())),
+    }
+}
+
+/// This function creates a TSV file with the contents of the DB/Loc PackedFile.
+pub fn export_tsv(
+    data: &[Vec<DecodedData>], 
+    path: &PathBuf,
+    headers: &[String], 
+    first_row_data: (&str, i32)
+) -> Result<()> {
+
+    let mut writer = new_tsv_writer();
+
+    // We serialize the info of the table (name and version) in the first line, and the column names in the second one.
+    writer.serialize(first_row_data)?;
+    writer.serialize(headers)?;
+
+    // Then we serialize each entry in the DB Table.
+    for entry in data { writer.serialize(&entry)?; }
+
+    // Then, we try to write it on disk. If there is an error, report it.
+    save_tsv_writer(writer, path)?;
+
+    Ok(())
+}
+
+/// This function creates the writer used to create TSV files. We want the writer to have no quotes, tab as delimiter and custom headers,
+/// because otherwise Excel, Libreoffice and all the programs that edit this kind of files break them on save.
+pub fn new_tsv_writer() -> Writer<Vec<u8>> {
+    WriterBuilder::new()
+        .delimiter(b'\t')
+        .quote_style(QuoteStyle::Never)
+        .has_headers(false)
+        .flexible(true)
+        .from_writer(vec![])
+}
+
+/// This function writes the contents of a writer created with `new_tsv_writer()` to a TSV file in the provided path.
+pub fn save_tsv_writer(writer: Writer<Vec<u8>>, path: &PathBuf) -> Result<()> {
+    let mut file = File::create(&path)?;
+    file.write_all(String::from_utf8(writer.into_inner().unwrap())?.as_bytes())?;
+    Ok(())
+}
+
+/// This function creates a delimited file (like a CSV) with the contents of the DB/Loc PackedFile, for the programs
+/// that don't like TSVs. Unlike TSVs, these files don't have the line with the type and version of the table, just the
+/// column names in the first line.
+///
+/// With `DelimitedQuoting::Never`, values with the delimiter, quotes or newlines cannot be exported, as they will not import back correctly.
+pub fn export_delimited(
+    data: &[Vec<DecodedData>],
+    path: &PathBuf,
+    headers: &[String],
+    delimiter: u8,
+    quoting: DelimitedQuoting,
+) -> Result<()> {
+    let quote_style = match quoting {
+        DelimitedQuoting::Always => QuoteStyle::Always,
+        DelimitedQuoting::Minimal => QuoteStyle::Necessary,
+        DelimitedQuoting::Never => {
+            for (row, entry) in data.iter().enumerate() {
+                if let Some(column) = entry.iter().position(|x| x.data_to_string().bytes().any(|x| x == delimiter || x == b'"' || x == b'\n' || x == b'\r')) {
+                    return Err(ErrorKind::ExportDelimitedUnquotedValue(row, column))?
+                }
+            }
+            QuoteStyle::Never
+        }
+    };
+
+    let mut writer = WriterBuilder::new()
+        .delimiter(delimiter)
+        .quote_style(quote_style)
+        .has_headers(false)
+        .flexible(true)
+        .from_writer(vec![]);
+
+    writer.serialize(headers)?;
+    for entry in data { writer.serialize(&entry)?; }
+
+    let mut file = File::create(&path)?;
+    file.write_all(&writer.into_inner().unwrap())?;
+    Ok(())
+}
+
+/// This function exports the provided rows of a DB Table or Loc PackedFile to a JSON file (see `TableJSON`), keeping the type of
+/// every value: booleans as booleans, numbers as numbers and strings as strings.
+pub fn export_json(
+    data: &[Vec<DecodedData>],
+    path: &PathBuf,
+    table_definition: &TableDefinition,
+    first_row_data: (&str, i32)
+) -> Result<()> {
+    let table = TableJSON {
+        table_name: first_row_data.0.to_owned(),
+        version: first_row_data.1,
+        fields: table_definition.fields.iter().map(|x| FieldJSON { name: x.field_name.to_owned(), field_type: x.field_type }).collect(),
+        rows: data.iter().map(|row| row.iter().map(|x| x.to_json_value()).collect()).collect(),
+    };
+
+    let mut file = BufWriter::new(File::create(&path)?);
+    file.write_all(serde_json::to_string_pretty(&table)?.as_bytes())?;
+    Ok(())
+}
+
+/// Name of the SQLite table where `export_to_sqlite()` keeps the path and version of the PackedFile of each exported table.
+const SQLITE_TABLES_TABLE: &str = "rpfm_tables";
+
+/// This function exports the DB Tables and Loc PackedFiles of the provided selection to a new SQLite database. Any file already in `out` is replaced.
+///
+/// There is a SQLite table for each PackedFile, named like his path (`db/units_tables/my_mod`), with a column for each field of his
+/// definition, typed as INTEGER, REAL or TEXT. If a name is already taken (SQLite doesn't care about the case), it gets an index.
+/// The path and version of the PackedFile of each table are kept in the `rpfm_tables` table, so `import_from_sqlite()` knows where
+/// each table goes back to. Tables that cannot be decoded are skipped.
+///
+/// It returns how many tables and rows have been written.
+pub fn export_to_sqlite(
+    pack_file: &mut PackFile,
+    item_types: &[PathType],
+    schema: &Schema,
+    out: &PathBuf,
+) -> Result<(usize, usize)> {
+
+    // Get the selected tables.
+    let paths = pack_file.packed_files.iter().map(|x| x.path.to_vec()).filter(|path| item_types.iter().any(|item_type| match item_type {
+        PathType::File(item_path) => path == item_path,
+        PathType::Folder(item_path) => path.starts_with(item_path),
+        PathType::PackFile => true,
+        PathType::None => false,
+    })).collect::<Vec<Vec<String>>>();
+
+    if out.is_file() { remove_file(out)?; }
+    let mut connection = Connection::open(out)?;
+    let transaction = connection.transaction()?;
+    transaction.execute(&format!("CREATE TABLE {} (name TEXT, path TEXT, version INTEGER)", quote_sqlite_name(SQLITE_TABLES_TABLE)), NO_PARAMS)?;
+
+    let mut table_names = vec![SQLITE_TABLES_TABLE.to_owned()];
+    let mut row_count = 0;
+    for path in &paths {
+        let (table_definition, entries) = match get_table_data(pack_file, path, schema) {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+
+        let base_name = path.join("/");
+        let mut table_name = base_name.to_owned();
+        let mut index = 2;
+        while table_names.iter().any(|x| x.eq_ignore_ascii_case(&table_name)) {
+            table_name = format!("{} ({})", base_name, index);
+            index += 1;
+        }
+
+        let columns = table_definition.fields.iter().map(|field| format!("{} {}", quote_sqlite_name(&field.field_name), get_sqlite_column_type(field.field_type))).collect::<Vec<String>>();
+        transaction.execute(&format!("CREATE TABLE {} ({})", quote_sqlite_name(&table_name), columns.join(", ")), NO_PARAMS)?;
+        transaction.execute(
+            &format!("INSERT INTO {} VALUES (?, ?, ?)", quote_sqlite_name(SQLITE_TABLES_TABLE)),
+            vec![SQLiteValue::Text(table_name.to_owned()), SQLiteValue::Text(base_name), SQLiteValue::Integer(i64::from(table_definition.version))]
+        )?;
+
+        let placeholders = vec!["?"; table_definition.fields.len()].join(", ");
+        let mut statement = transaction.prepare(&format!("INSERT INTO {} VALUES ({})", quote_sqlite_name(&table_name), placeholders))?;
+        for row in &entries {
+            statement.execute(row.iter().map(|cell| match cell {
+                DecodedData::Boolean(data) => SQLiteValue::Integer(i64::from(*data)),
+                DecodedData::Float(data) => data.to_string().parse::<f64>().map(SQLiteValue::Real).unwrap_or(SQLiteValue::Null),
+                DecodedData::Integer(data) => SQLiteValue::Integer(i64::from(*data)),
+                DecodedData::LongInteger(data) => SQLiteValue::Integer(*data),
+                DecodedData::Double(data) => SQLiteValue::Real(*data),
+                DecodedData::Colour(data) => SQLiteValue::Integer(i64::from(*data)),
+                DecodedData::StringU8(data) |
+                DecodedData::StringU16(data) |
+                DecodedData::OptionalStringU8(data) |
+                DecodedData::OptionalStringU16(data) => SQLiteValue::Text(data.to_owned()),
+            }))?;
+        }
 
-                                // Create and add the new PackedFile to the list of PackedFiles to add.
-                                packed_files.push(PackedFile::read_from_vec(path, get_current_time(), false, raw_data));
+        row_count += entries.len();
+        table_names.push(table_name);
+    }
+    transaction.commit()?;
+
+    Ok((table_names.len() - 1, row_count))
+}
+
+/// This function imports the tables of a SQLite database made by `export_to_sqlite()` into the provided PackFile.
+///
+/// Each SQLite table goes back to the PackFile in the path the `rpfm_tables` table has for it, using the definition of the
+/// version it has there. His columns must be the fields of that definition, with their types. Its rows replace the ones of the
+/// PackedFile in that path or, if there is none, go to a new one. Tables with problems are not imported, and get an error instead.
+///
+/// It returns the paths of the PackedFiles created, the ones replaced, and the errors of the tables that couldn't be imported.
+pub fn import_from_sqlite(
+    pack_file: &mut PackFile,
+    schema: &Schema,
+    path: &PathBuf,
+) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>, Vec<String>)> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let table_names = connection.prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")?
+        .query_map(NO_PARAMS, |row| row.get(0))?
+        .collect::<std::result::Result<Vec<String>, _>>()?;
+
+    // Databases without the table with the paths are not made by us, so we don't know where their tables go.
+    let exported_tables = if table_names.iter().any(|x| x == SQLITE_TABLES_TABLE) {
+        connection.prepare(&format!("SELECT name, path, version FROM {}", quote_sqlite_name(SQLITE_TABLES_TABLE)))?
+            .query_map(NO_PARAMS, |row| Ok((row.get::<_, String>(0)?, (row.get::<_, String>(1)?, row.get::<_, i64>(2)?))))?
+            .collect::<std::result::Result<BTreeMap<String, (String, i64)>, _>>()?
+    } else { BTreeMap::new() };
+
+    let (mut added_paths, mut modified_paths, mut errors) = (vec![], vec![], vec![]);
+    for table_name in table_names.iter().filter(|x| *x != SQLITE_TABLES_TABLE) {
+        let result = match exported_tables.get(table_name) {
+            Some((path, version)) => {
+                let path = path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>();
+                i32::try_from(*version).map_err(|_| Error::from(ErrorKind::ImportSQLiteUnknownTable(table_name.to_owned())))
+                    .and_then(|version| import_sqlite_table(pack_file, schema, &connection, table_name, &path, version).map(|is_new| (path, is_new)))
+            }
+            None => Err(Error::from(ErrorKind::ImportSQLiteUnknownTable(table_name.to_owned()))),
+        };
+
+        match result {
+            Ok((path, true)) => added_paths.push(path),
+            Ok((path, false)) => modified_paths.push(path),
+            Err(error) => errors.push(error.to_string()),
+        }
+    }
+
+    Ok((added_paths, modified_paths, errors))
+}
+
+/// This function imports a table of a SQLite database into the PackedFile with the provided path and version (see `import_from_sqlite()`).
+///
+/// It returns if the PackedFile is a new one.
+fn import_sqlite_table(
+    pack_file: &mut PackFile,
+    schema: &Schema,
+    connection: &Connection,
+    table_name: &str,
+    path: &[String],
+    version: i32,
+) -> Result<bool> {
+
+    // Find the definition of the table.
+    let table_definition = match get_packed_file_type(path) {
+        DecodeablePackedFileType::Loc => Some(TableDefinition::new_loc_definition()).filter(|x| x.version == version),
+        DecodeablePackedFileType::DB if path.len() == 3 => DB::get_schema(&path[1], version, schema),
+        _ => None,
+    };
+    let table_definition = table_definition.ok_or_else(|| Error::from(ErrorKind::ImportSQLiteUnknownTable(table_name.to_owned())))?;
+
+    // Check the columns are the ones of the definition, with the same types.
+    let columns = connection.prepare(&format!("PRAGMA table_info({})", quote_sqlite_name(table_name)))?
+        .query_map(NO_PARAMS, |row| Ok((row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<std::result::Result<Vec<(String, String)>, _>>()?;
+
+    let mut invalid_columns = columns.iter().filter(|(name, _)| table_definition.fields.iter().all(|x| &x.field_name != name)).map(|(name, _)| name.to_owned()).collect::<Vec<String>>();
+    for field in &table_definition.fields {
+        match columns.iter().find(|(name, _)| name == &field.field_name) {
+            Some((_, column_type)) if column_type.eq_ignore_ascii_case(get_sqlite_column_type(field.field_type)) => {}
+            _ => invalid_columns.push(field.field_name.to_owned()),
+        }
+    }
+    if !invalid_columns.is_empty() { Err(ErrorKind::ImportSQLiteInvalidColumns(table_name.to_owned(), invalid_columns))? }
+
+    // Get the rows. SQLite doesn't enforce the types of the columns, so we have to check every value.
+    let column_names = table_definition.fields.iter().map(|x| quote_sqlite_name(&x.field_name)).collect::<Vec<String>>().join(", ");
+    let mut statement = connection.prepare(&format!("SELECT {} FROM {}", column_names, quote_sqlite_name(table_name)))?;
+    let mut rows = statement.query(NO_PARAMS)?;
+    let mut entries = vec![];
+    while let Some(row) = rows.next()? {
+        let mut entry = vec![];
+        for (column, field) in table_definition.fields.iter().enumerate() {
+            let value = match (field.field_type, row.get::<_, SQLiteValue>(column)?) {
+                (FieldType::Boolean, SQLiteValue::Integer(value)) if value == 0 || value == 1 => Some(DecodedData::Boolean(value == 1)),
+                (FieldType::Float, SQLiteValue::Real(value)) => Some(DecodedData::Float(value as f32)),
+                (FieldType::Float, SQLiteValue::Integer(value)) => Some(DecodedData::Float(value as f32)),
+                (FieldType::Integer, SQLiteValue::Integer(value)) => i32::try_from(value).ok().map(DecodedData::Integer),
+                (FieldType::LongInteger, SQLiteValue::Integer(value)) => Some(DecodedData::LongInteger(value)),
+                (FieldType::Double, SQLiteValue::Real(value)) => Some(DecodedData::Double(value)),
+                (FieldType::Double, SQLiteValue::Integer(value)) => Some(DecodedData::Double(value as f64)),
+                (FieldType::Colour, SQLiteValue::Integer(value)) => u32::try_from(value).ok().map(DecodedData::Colour),
+                (FieldType::StringU8, SQLiteValue::Text(value)) => Some(DecodedData::StringU8(value)),
+                (FieldType::StringU16, SQLiteValue::Text(value)) => Some(DecodedData::StringU16(value)),
+                (FieldType::OptionalStringU8, SQLiteValue::Text(value)) => Some(DecodedData::OptionalStringU8(value)),
+                (FieldType::OptionalStringU16, SQLiteValue::Text(value)) => Some(DecodedData::OptionalStringU16(value)),
+                _ => None,
+            };
+            match value {
+                Some(value) => entry.push(value),
+                None => if !invalid_columns.contains(&field.field_name) { invalid_columns.push(field.field_name.to_owned()) },
+            }
+        }
+        entries.push(entry);
+    }
+    if !invalid_columns.is_empty() { Err(ErrorKind::ImportSQLiteInvalidColumns(table_name.to_owned(), invalid_columns))? }
+
+    let data = if path[0] == "db" {
+        let mut db = DB::new(&path[1], version, table_definition);
+        db.entries = entries;
+        db.save()
+    } else { Loc { entries }.save() };
+
+    // Put the rows in the PackedFile of the table, or in a new one if there is none.
+    match pack_file.packed_files.iter_mut().find(|x| x.path == path) {
+        Some(packed_file) => {
+            packed_file.set_data(data);
+            Ok(false)
+        }
+        None => {
+            let added_paths = pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), get_current_time(), false, data)]);
+            if added_paths.is_empty() { Err(ErrorKind::ReservedFiles)? }
+            Ok(true)
+        }
+    }
+}
+
+/// This function returns the type of the SQLite columns used for the fields of the provided type.
+fn get_sqlite_column_type(field_type: FieldType) -> &'static str {
+    match field_type {
+        FieldType::Boolean | FieldType::Integer | FieldType::LongInteger | FieldType::Colour => "INTEGER",
+        FieldType::Float | FieldType::Double => "REAL",
+        FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16 => "TEXT",
+    }
+}
+
+/// This function quotes a name so it can be used as a table or column name in SQLite.
+fn quote_sqlite_name(name: &str) -> String {
+    format!("\"{}\"", name.replace("\"", "\"\""))
+}
+
+//----------------------------------------------------------------//
+// Mass-TSV Functions for PackedFiles.
+//----------------------------------------------------------------//
+
+/// This function checks the TSV files we want to Mass-Import, returning the errors in their rows, with the path of their file.
+/// Files we cannot import at all are not checked here, as `tsv_mass_import()` already reports them.
+pub fn tsv_mass_import_preview(tsv_paths: &[PathBuf]) -> Vec<String> {
+    let mut errors = vec![];
+    for path in tsv_paths {
+        if let Some((table_type, table_version, table_definition)) = get_tsv_table_info(path) {
+            if let Ok((_, _, tsv_errors)) = import_tsv(&table_definition, &path, &table_type, table_version) {
+                errors.extend(tsv_errors.iter().map(|x| format!("{}: {}", path.to_string_lossy(), x)));
+            }
+        }
+    }
+    errors
+}
+
+/// This function is used to Mass-Import TSV files into a PackFile. Note that this will OVERWRITE any
+/// existing PackedFile that has a name conflict with the TSV files provided.
+///
+/// If `skip_bad_rows` is true, the rows with errors (see `tsv_mass_import_preview()`) are skipped. Otherwise, the files with them fail.
+///
+/// It returns the paths of the PackedFiles overwritten, the ones of the PackedFiles added, and a warning
+/// for each column of the TSV files that is not in the definition of his table, as those are ignored.
+pub fn tsv_mass_import(
+    tsv_paths: &[PathBuf],
+    name: Option<String>,
+    pack_file: &mut PackFile,
+    skip_bad_rows: bool,
+) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>, Vec<String>)> {
+
+    // Create a list of PackedFiles succesfully imported, and another for the ones that didn't work.
+    // The a third one to return the PackedFiles that were overwritten, so the UI can have an easy time updating his TreeView.
+    let mut packed_files: Vec<PackedFile> = vec![];
+    let mut packed_files_to_remove = vec![];
+    let mut error_files = vec![];
+    let mut warnings = vec![];
+
+    for path in tsv_paths {
+
+        // Get the type and the version of the table from the first line of the file and, with that, get his definition.
+        if let Some((table_type, table_version, table_definition)) = get_tsv_table_info(path) {
+
+            // Then, import whatever we have and, depending on what we have, save it. If any row has errors,
+            // we skip it if we've been told to. Otherwise, the entire file fails.
+            match import_tsv(&table_definition, &path, &table_type, table_version) {
+                Ok((_, _, ref errors)) if !errors.is_empty() && !skip_bad_rows => error_files.push(path.to_string_lossy().to_string()),
+                Ok((data, unknown_columns, _)) => {
+                    warnings.extend(unknown_columns.iter().map(|x| format!("{}: column \"{}\" ignored.", path.to_string_lossy(), x)));
+                    match &*table_type {
+
+                        // Loc Tables.
+                        "Loc PackedFile" => {
+                            let mut loc = Loc::new();
+                            loc.entries = data;
+                            let raw_data = loc.save();
+
+                            // Depending on the name received, call it one thing or another.
+                            let name = match name {
+                                Some(ref name) => name.to_string(),
+                                None => path.file_stem().unwrap().to_str().unwrap().to_string(),
+                            };
+
+                            let mut path = vec!["text".to_owned(), "db".to_owned(), format!("{}.loc", name)];
+
+                            // If that path already exists in the list of new PackedFiles to add, change it using the index.
+                            let mut index = 1;
+                            while packed_files.iter().any(|x| x.path == path) {
+                                path[2] = format!("{}_{}.loc", name, index);
+                                index += 1;
+                            }
+
+                            // If that path already exist in the PackFile, add it to the "remove" list.
+                            if pack_file.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
+
+                            // Create and add the new PackedFile to the list of PackedFiles to add.
+                            packed_files.push(PackedFile::read_from_vec(path, get_current_time(), false, raw_data));
+                        }
+    
+                        // DB Tables.
+                        _ => {
+                            let mut db = DB::new(&table_type, table_version, table_definition);
+                            db.entries = data;
+                            let raw_data = db.save();
+
+                            // Depending on the name received, call it one thing or another.
+                            let name = match name {
+                                Some(ref name) => name.to_string(),
+                                None => path.file_stem().unwrap().to_str().unwrap().to_string(),
+                            };
+
+                            let mut path = vec!["db".to_owned(), table_type.to_owned(), name.to_owned()];
+                    
+                            // If that path already exists in the list of new PackedFiles to add, change it using the index.
+                            let mut index = 1;
+                            while packed_files.iter().any(|x| x.path == path) {
+                                path[2] = format!("{}_{}", name, index);
+                                index += 1;
                             }
+                            
+                            // If that path already exists in the PackFile, add it to the "remove" list.
+                            if pack_file.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
+
+                            // Create and add the new PackedFile to the list of PackedFiles to add.
+                            packed_files.push(PackedFile::read_from_vec(path, get_current_time(), false, raw_data));
                         }
                     }
-                    Err(_) => error_files.push(path.to_string_lossy().to_string()),
                 }
+                Err(_) => error_files.push(path.to_string_lossy().to_string()),
             }
-            else { error_files.push(path.to_string_lossy().to_string()) }
         }
         else { error_files.push(path.to_string_lossy().to_string()) }
     }
@@ -665,47 +3443,202 @@ pub fn tsv_mass_import(
     // We add all the files to the PackFile, and return success.
     let added_paths = pack_file.add_packed_files(&packed_files);
     if added_paths.len() < packed_files.len() { Err(ErrorKind::ReservedFiles)? }
-    Ok((packed_files_to_remove, tree_path))
+    Ok((packed_files_to_remove, tree_path, warnings))
+}
+
+/// This function is used to Mass-Import the TSV files of a folder into a PackFile, using the structure of the folder instead of the
+/// metadata line of the files to know where each one goes: `db/units_tables/units.tsv` goes to `db/units_tables/units`, and any TSV
+/// in `text` (like `text/db/mymod.tsv`) becomes a Loc PackedFile (`text/db/mymod.loc`). The metadata line is optional in these files.
+/// If it's not there, the newest definition of the table in the schema is used.
+///
+/// If any of the new PackedFiles already exists in the PackFile, nothing is imported and the conflicts are returned as an error.
+///
+/// It returns the paths of the PackedFiles added, and a warning for each file and column ignored.
+pub fn tsv_mass_import_from_folder(
+    folder_path: &PathBuf,
+    pack_file: &mut PackFile,
+    schema: &Schema,
+) -> Result<(Vec<Vec<String>>, Vec<String>)> {
+    let mut packed_files = vec![];
+    let mut error_files = vec![];
+    let mut warnings = vec![];
+
+    for path in get_files_from_subdir(folder_path)? {
+        if path.extension().map_or(true, |x| x != "tsv") { continue }
+        let relative_path = path.strip_prefix(folder_path).unwrap().iter().map(|x| x.to_string_lossy().to_string()).collect::<Vec<String>>();
+        let file_stem = path.file_stem().unwrap().to_string_lossy().to_string();
+
+        // Get the type of the table and the path of his PackedFile from where the TSV is.
+        let mut packed_file_path = relative_path.to_vec();
+        let table_type = if relative_path.len() == 3 && relative_path[0] == "db" {
+            *packed_file_path.last_mut().unwrap() = file_stem;
+            relative_path[1].to_owned()
+        }
+        else if relative_path.len() > 1 && relative_path[0] == "text" {
+            *packed_file_path.last_mut().unwrap() = format!("{}.loc", file_stem);
+            TSV_HEADER_LOC_PACKEDFILE.to_owned()
+        }
+        else {
+            warnings.push(format!("{}: it's not in a table folder, so it has been ignored.", relative_path.join("/")));
+            continue
+        };
+
+        // If the file has the metadata line, we use his version. Otherwise, we use the newest definition of the table.
+        let metadata = get_tsv_metadata(&path).filter(|(x, _)| x == &table_type);
+        let table_definition = if table_type == TSV_HEADER_LOC_PACKEDFILE { Some(TableDefinition::new_loc_definition()) }
+            else if let Some((_, version)) = metadata { DB::get_schema(&table_type, version, schema) }
+            else { schema.get_table_definitions(&table_type).and_then(|x| schema.tables_definitions[x].versions.iter().max_by_key(|x| x.version).cloned()) };
+        let table_definition = match table_definition {
+            Some(table_definition) => table_definition,
+            None => {
+                error_files.push(relative_path.join("/"));
+                continue
+            }
+        };
+
+        match read_tsv(&table_definition, &path, metadata.as_ref().map(|(x, y)| (&**x, *y))) {
+            Ok((entries, unknown_columns, errors)) => {
+                if !errors.is_empty() {
+                    error_files.push(relative_path.join("/"));
+                    continue
+                }
+
+                warnings.extend(unknown_columns.iter().map(|x| format!("{}: column \"{}\" ignored.", relative_path.join("/"), x)));
+                let data = if table_type == TSV_HEADER_LOC_PACKEDFILE { Loc { entries }.save() } else {
+                    let mut db = DB::new(&table_type, table_definition.version, table_definition);
+                    db.entries = entries;
+                    db.save()
+                };
+                packed_files.push(PackedFile::read_from_vec(packed_file_path, get_current_time(), false, data));
+            }
+            Err(_) => error_files.push(relative_path.join("/")),
+        }
+    }
+
+    // If any of the files returned error, return error.
+    if !error_files.is_empty() {
+        let error_files_string = error_files.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
+        return Err(ErrorKind::MassImport(error_files_string))?
+    }
+
+    // Before adding anything, make sure we're not going to overwrite anything.
+    let conflicts = packed_files.iter().filter(|x| pack_file.packedfile_exists(&x.path)).map(|x| format!("<li>{}</li>", x.path.join("/"))).collect::<Vec<String>>();
+    if !conflicts.is_empty() { return Err(ErrorKind::MassImportConflicts(conflicts.concat()))? }
+
+    let added_paths = pack_file.add_packed_files(&packed_files);
+    if added_paths.len() < packed_files.len() { Err(ErrorKind::ReservedFiles)? }
+    Ok((added_paths, warnings))
+}
+
+/// This function gets the type and version of the table of a TSV file from his first line, and the definition of that table.
+/// If the file cannot be read, or we don't have a definition for his table, it returns None.
+fn get_tsv_table_info(path: &PathBuf) -> Option<(String, i32, TableDefinition)> {
+    let (table_type, table_version) = get_tsv_metadata(path)?;
+    let table_definition = match &*table_type {
+        TSV_HEADER_LOC_PACKEDFILE => TableDefinition::new_loc_definition(),
+        _ => match *SCHEMA.lock().unwrap() {
+            Some(ref schema) => DB::get_schema(&table_type, table_version, &schema)?,
+            None => return None,
+        }
+    };
+
+    Some((table_type, table_version, table_definition))
+}
+
+/// This function gets the type and version of the table of a TSV file from his first line. If the file cannot be read,
+/// or his first line is not the one with the type and version, it returns None.
+fn get_tsv_metadata(path: &PathBuf) -> Option<(String, i32)> {
+    let mut tsv = String::new();
+    BufReader::new(File::open(&path).ok()?).read_to_string(&mut tsv).ok()?;
+
+    // Split the first line by \t so we can get the info of the table. Only if we have 2 items, continue.
+    let tsv_info = tsv.lines().next()?.split('\t').collect::<Vec<&str>>();
+    if tsv_info.len() != 2 { return None }
+    Some((tsv_info[0].to_owned(), tsv_info[1].parse::<i32>().ok()?))
 }
 
 /// This function is used to Mass-Export TSV files from a PackFile. Note that this will OVERWRITE any
 /// existing file that has a name conflict with the TSV files provided.
+///
+/// If `only_modified` is true, only the tables whose data is different from the one in the PackFile on disk are exported.
 pub fn tsv_mass_export(
+    export_path: &PathBuf,
+    pack_file: &mut PackFile,
+    only_modified: bool,
+) -> Result<String> {
+    mass_export(export_path, pack_file, false, only_modified)
+}
+
+/// This function is used to Mass-Export JSON files (see `export_json()`) from a PackFile. Note that this will OVERWRITE any
+/// existing file that has a name conflict with the JSON files provided.
+pub fn json_mass_export(
     export_path: &PathBuf,
     pack_file: &mut PackFile
 ) -> Result<String> {
+    mass_export(export_path, pack_file, true, false)
+}
+
+/// This function exports every DB Table and Loc PackedFile of a PackFile to the provided folder, as JSON or TSV files.
+fn mass_export(
+    export_path: &PathBuf,
+    pack_file: &mut PackFile,
+    as_json: bool,
+    only_modified: bool,
+) -> Result<String> {
+    let extension = if as_json { "json" } else { "tsv" };
 
     // Lists of PackedFiles that couldn't be exported for one thing or another and exported PackedFile names,
     // so we make sure we don't overwrite those with the following ones.
     let mut error_list = vec![];
     let mut exported_files = vec![];
+    let mut skipped_files = vec![];
+
+    // If we only want the modified tables, we need the PackFile as it's on disk to compare them. If it has never been saved, everything is modified.
+    let pack_file_on_disk = if only_modified && pack_file.file_path.is_file() { Some(PackFile::read(pack_file.file_path.to_path_buf(), true)?) } else { None };
 
+    let table_aliases = pack_file.table_aliases.clone();
     for packed_file in &mut pack_file.packed_files {
 
         // We check if his path is empty first to avoid false positives related with "starts_with" function.
         if !packed_file.path.is_empty() {
 
+            // Skip the tables that are the same as on disk.
+            if let Some(ref pack_file_on_disk) = pack_file_on_disk {
+                let is_table = (packed_file.path.starts_with(&["db".to_owned()]) && packed_file.path.len() == 3) || packed_file.path.last().unwrap().ends_with(".loc");
+                if is_table {
+                    if let Some(packed_file_on_disk) = pack_file_on_disk.packed_files.iter().find(|x| x.path == packed_file.path) {
+                        if packed_file_on_disk.get_data()? == packed_file.get_data_and_keep_it()? {
+                            skipped_files.push(packed_file.path.join("/"));
+                            continue;
+                        }
+                    }
+                }
+            }
+
             // If the PackedFile is a DB Table and we have an schema, try to decode it and export it.
             if packed_file.path.starts_with(&["db".to_owned()]) && packed_file.path.len() == 3 {
                 match *SCHEMA.lock().unwrap() {
                     Some(ref schema) => {
-                        match DB::read(&(packed_file.get_data_and_keep_it()?), &packed_file.path[1], &schema) {
+                        match DB::read(&(packed_file.get_data_and_keep_it()?), table_aliases.get(&packed_file.path[1]).unwrap_or(&packed_file.path[1]), &schema) {
                             Ok(db) => {
 
-                                // His name will be "db_name_file_name.tsv". If that's taken, we'll add an index until we find one available.
-                                let mut name = format!("{}_{}.tsv", packed_file.path[1], packed_file.path.last().unwrap().to_owned());
+                                // His name will be "db_name_file_name.tsv" (or ".json"). If that's taken, we'll add an index until we find one available.
+                                let mut name = format!("{}_{}.{}", packed_file.path[1], packed_file.path.last().unwrap().to_owned(), extension);
                                 let mut export_path = export_path.to_path_buf();
 
                                 // Checks to avoid overwriting exported files go here, in an infinite loop of life and death.
                                 let mut index = 1;
                                 while exported_files.contains(&name) {
-                                    name = format!("{}_{}_{}.tsv", packed_file.path[1], packed_file.path.last().unwrap().to_owned(), index);
+                                    name = format!("{}_{}_{}.{}", packed_file.path[1], packed_file.path.last().unwrap().to_owned(), index, extension);
                                     index += 1;
                                 }
 
                                 export_path.push(name.to_owned());
-                                let headers = db.table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
-                                match export_tsv(&db.entries, &export_path, &headers, (&packed_file.path[1], db.version)) {
+                                let result = if as_json { db.export_json(&export_path) } else {
+                                    let headers = db.table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
+                                    export_tsv(&db.entries, &export_path, &headers, (&packed_file.path[1], db.version))
+                                };
+                                match result {
                                     Ok(_) => exported_files.push(name.to_owned()),
                                     Err(error) => error_list.push((packed_file.path.to_vec().join("\\"), error)),
                                 }
@@ -722,20 +3655,23 @@ pub fn tsv_mass_export(
                 match Loc::read(&(packed_file.get_data_and_keep_it()?)) {
                     Ok(loc) => {
 
-                        // His name will be "file_name.tsv". If that's taken, we'll add an index until we find one available.
-                        let mut name = format!("{}.tsv", packed_file.path.last().unwrap().to_owned());
+                        // His name will be "file_name.tsv" (or ".json"). If that's taken, we'll add an index until we find one available.
+                        let mut name = format!("{}.{}", packed_file.path.last().unwrap().to_owned(), extension);
                         let mut export_path = export_path.to_path_buf();
 
                         // Checks to avoid overwriting exported files go here, in an infinite loop of life and death.
                         let mut index = 1;
                         while exported_files.contains(&name) {
-                            name = format!("{}_{}.tsv", packed_file.path.last().unwrap().to_owned(), index);
+                            name = format!("{}_{}.{}", packed_file.path.last().unwrap().to_owned(), index, extension);
                             index += 1;
                         }
 
                         export_path.push(name.to_owned());
-                        let headers = TableDefinition::new_loc_definition().fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
-                        match export_tsv(&loc.entries, &export_path, &headers, ("Loc PackedFile", 1)) {
+                        let result = if as_json { loc.export_json(&export_path) } else {
+                            let headers = TableDefinition::new_loc_definition().fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
+                            export_tsv(&loc.entries, &export_path, &headers, ("Loc PackedFile", 1))
+                        };
+                        match result {
                             Ok(_) => exported_files.push(name.to_owned()),
                             Err(error) => error_list.push((packed_file.path.to_vec().join("\\"), error)),
                         }
@@ -747,11 +3683,18 @@ pub fn tsv_mass_export(
     }
 
     // If there has been errors, return ok with the list of errors.
-    if !error_list.is_empty() {
+    let mut message = if !error_list.is_empty() {
         let error_files_string = error_list.iter().map(|x| format!("<li>{}</li>", x.0)).collect::<String>();
-        Ok(format!("<p>All exportable files have been exported, except the following ones:</p><ul>{}</ul>", error_files_string))
+        format!("<p>All exportable files have been exported, except the following ones:</p><ul>{}</ul>", error_files_string)
     }
 
     // Otherwise, just return success and an empty error list.
-    else { Ok("<p>All exportable files have been exported.</p>".to_owned()) }
+    else { "<p>All exportable files have been exported.</p>".to_owned() };
+
+    // If we only exported the modified tables, report what we exported and what we skipped.
+    if only_modified {
+        let to_list = |files: &[String]| if files.is_empty() { "<p>None.</p>".to_owned() } else { format!("<ul>{}</ul>", files.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>()) };
+        message.push_str(&format!("<p>Exported files:</p>{}<p>Tables skipped as unchanged:</p>{}", to_list(&exported_files), to_list(&skipped_files)));
+    }
+    Ok(message)
 }
@@ -0,0 +1,1497 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the PackedFile logic functions, like the ones to check, fix, import and export the tables of a PackFile.
+
+use super::*;
+use crate::background_thread_extra::{delete_from_packfile, rename_packed_files};
+use crate::common::tests::{new_db_packed_file, new_table_definitions_with_references};
+use crate::packfile::PFHVersion;
+
+use std::env::temp_dir;
+use std::fs::{create_dir_all, read_to_string, remove_dir_all};
+
+//-----------------------------------------------------//
+//                PackFile maintenance
+//-----------------------------------------------------//
+
+/// Test to make sure the BOM stripper (`strip_boms_from_text()`) only touches Text PackedFiles starting with a BOM.
+#[test]
+fn test_strip_boms_from_text() {
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let with_bom = vec!["script".to_owned(), "with_bom.lua".to_owned()];
+    let without_bom = vec!["script".to_owned(), "without_bom.lua".to_owned()];
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(with_bom.to_vec(), 0, false, vec![0xEF, 0xBB, 0xBF, 97, 98]),
+        PackedFile::read_from_vec(without_bom.to_vec(), 0, false, vec![97, 98]),
+    ]);
+
+    // Check that only the file with the BOM is reported as changed.
+    assert_eq!(strip_boms_from_text(&mut pack_file).unwrap(), vec![with_bom]);
+
+    // Check that both files end up with the same BOM-less data.
+    assert_eq!(pack_file.packed_files[0].get_data().unwrap(), vec![97, 98]);
+    assert_eq!(pack_file.packed_files[1].get_data().unwrap(), vec![97, 98]);
+}
+
+/// Test to make sure the null trimmer (`trim_trailing_nulls()`) only touches Text PackedFiles ending with null bytes.
+#[test]
+fn test_trim_trailing_nulls() {
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let with_nulls = vec!["script".to_owned(), "with_nulls.lua".to_owned()];
+    let without_nulls = vec!["script".to_owned(), "without_nulls.lua".to_owned()];
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(with_nulls.to_vec(), 0, false, vec![97, 0, 98, 0, 0, 0]),
+        PackedFile::read_from_vec(without_nulls.to_vec(), 0, false, vec![97, 98]),
+    ]);
+
+    // Check that only the file with the nulls is reported as changed, and only the trailing ones are removed.
+    assert_eq!(trim_trailing_nulls(&mut pack_file).unwrap(), vec![with_nulls]);
+    assert_eq!(pack_file.packed_files[0].get_data().unwrap(), vec![97, 0, 98]);
+    assert_eq!(pack_file.packed_files[1].get_data().unwrap(), vec![97, 98]);
+
+    // UTF-16 files are trimmed by code units, so the high byte of their last character is kept.
+    let utf16 = vec!["script".to_owned(), "utf16.lua".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(utf16.to_vec(), 0, false, vec![0xFF, 0xFE, 0x00, 0x01, 0, 0, 0, 0])]);
+    assert_eq!(trim_trailing_nulls(&mut pack_file).unwrap(), vec![utf16]);
+    assert_eq!(pack_file.packed_files[2].get_data().unwrap(), vec![0xFF, 0xFE, 0x00, 0x01]);
+}
+
+/// Test to make sure the character counter (`count_localisable_characters()`) counts the text of every Loc PackedFile and
+/// the localisable columns of the DB Tables, and reports the PackedFiles it cannot decode.
+#[test]
+fn test_count_localisable_characters() {
+
+    let mut loc_1 = Loc::new();
+    loc_1.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Hello".to_owned()), DecodedData::Boolean(true)]);
+    loc_1.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("World!".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut loc_2 = Loc::new();
+    loc_2.entries.push(vec![DecodedData::StringU16("key_3".to_owned()), DecodedData::StringU16("Año".to_owned()), DecodedData::Boolean(false)]);
+
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("onscreen_name".to_owned(), FieldType::StringU16, false, None, String::new()));
+    let db = new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "test".to_owned()], &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::StringU16("Spears".to_owned())],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["text".to_owned(), "db".to_owned(), "first.loc".to_owned()], 0, false, loc_1.save()),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "db".to_owned(), "second.loc".to_owned()], 0, false, loc_2.save()),
+        db,
+        PackedFile::read_from_vec(vec!["text".to_owned(), "db".to_owned(), "broken.loc".to_owned()], 0, false, vec![1, 2, 3]),
+    ]);
+
+    let (counts, total, failed) = count_localisable_characters(&mut pack_file, &schema).unwrap();
+    assert_eq!(counts.iter().find(|x| x.0.last().unwrap() == "first.loc").unwrap().1, 11);
+    assert_eq!(counts.iter().find(|x| x.0.last().unwrap() == "second.loc").unwrap().1, 3);
+    assert_eq!(counts.iter().find(|x| x.0.last().unwrap() == "test").unwrap().1, 6);
+    assert_eq!(total, 20);
+    assert_eq!(failed, vec![vec!["text".to_owned(), "db".to_owned(), "broken.loc".to_owned()]]);
+}
+
+/// Test to make sure the loc coverage (`get_loc_coverage()`) of a table with half of his loc keys with text is 50%,
+/// with blank and missing keys counted separately.
+#[test]
+fn test_get_loc_coverage() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let entries = ["unit_1", "unit_2", "unit_3", "unit_4"].iter().map(|x| vec![DecodedData::StringU8(x.to_string())]).collect();
+    let db = new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "test".to_owned()], &table_definitions.versions[0], entries);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_unit_1".to_owned()), DecodedData::StringU16("Unit 1".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_unit_2".to_owned()), DecodedData::StringU16(String::new()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_unit_3".to_owned()), DecodedData::StringU16("Unit 3".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db, PackedFile::read_from_vec(vec!["text".to_owned(), "test.loc".to_owned()], 0, false, loc.save())]);
+
+    let coverages = get_loc_coverage(&mut pack_file, &schema).unwrap();
+    assert_eq!(coverages.len(), 1);
+    assert_eq!(coverages[0].expected, 4);
+    assert_eq!(coverages[0].present, 2);
+    assert_eq!(coverages[0].blank, 1);
+    assert_eq!(coverages[0].missing, 1);
+    assert_eq!(coverages[0].get_coverage(), 50.0);
+}
+
+/// Test to make sure the loc coverage (`get_loc_coverage()`) uses the localisable fields of the definition, so tables without
+/// any of their loc keys are reported as fully missing, and rows with an empty key count as missing instead of being skipped.
+#[test]
+fn test_get_loc_coverage_from_definition() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].localised_fields.push(Field::new("onscreen_name".to_owned(), FieldType::StringU16, false, None, String::new()));
+    let entries = ["unit_1", ""].iter().map(|x| vec![DecodedData::StringU8(x.to_string())]).collect();
+    let db = new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "test".to_owned()], &table_definitions.versions[0], entries);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db]);
+
+    let coverages = get_loc_coverage(&mut pack_file, &schema).unwrap();
+    assert_eq!(coverages.len(), 1);
+    assert_eq!(coverages[0].expected, 2);
+    assert_eq!(coverages[0].present, 0);
+    assert_eq!(coverages[0].missing, 2);
+}
+
+/// Test to make sure the missing loc key finder (`find_missing_loc_keys()`) gets the localisable columns from the PackFile and
+/// the dependency database, and that the missing keys can be added to a new Loc PackedFile (`add_loc_stubs()`) only once.
+#[test]
+fn test_find_missing_loc_keys() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let db = new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "test".to_owned()], &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned())],
+        vec![DecodedData::StringU8("unit_2".to_owned())],
+    ]);
+    let vanilla_db = new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "data__".to_owned()], &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_3".to_owned())],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_unit_1".to_owned()), DecodedData::StringU16("Unit 1".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut vanilla_loc = Loc::new();
+    vanilla_loc.entries.push(vec![DecodedData::StringU16("units_tooltip_unit_3".to_owned()), DecodedData::StringU16("Unit 3".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db, PackedFile::read_from_vec(vec!["text".to_owned(), "test.loc".to_owned()], 0, false, loc.save())]);
+
+    let mut dependency_database = vec![
+        vanilla_db,
+        PackedFile::read_from_vec(vec!["text".to_owned(), "db".to_owned(), "units.loc".to_owned()], 0, false, vanilla_loc.save()),
+    ];
+
+    let missing_keys = find_missing_loc_keys(&mut pack_file, &mut dependency_database, &schema).unwrap();
+    assert_eq!(missing_keys.iter().map(|x| x.loc_key.to_owned()).collect::<Vec<String>>(), vec![
+        "units_tooltip_unit_1".to_owned(),
+        "units_onscreen_name_unit_2".to_owned(),
+        "units_tooltip_unit_2".to_owned(),
+    ]);
+    assert_eq!(missing_keys[1].row_key, "unit_2");
+
+    let path = vec!["text".to_owned(), "db".to_owned(), "stubs.loc".to_owned()];
+    let loc_keys = missing_keys.iter().map(|x| x.loc_key.to_owned()).collect::<Vec<String>>();
+    assert_eq!(add_loc_stubs(&mut pack_file, &path, &loc_keys).unwrap(), (3, true));
+    assert_eq!(add_loc_stubs(&mut pack_file, &path, &loc_keys).unwrap(), (0, false));
+    assert!(find_missing_loc_keys(&mut pack_file, &mut dependency_database, &schema).unwrap().is_empty());
+}
+
+/// Test to make sure generating the loc entries of a table (`generate_loc_entries()`) uses the provided templates, or the localisable
+/// fields of the table if there are none, and never overwrites existing entries.
+#[test]
+fn test_generate_loc_entries() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].localised_fields.push(Field::new("onscreen_name".to_owned(), FieldType::StringU16, false, None, String::new()));
+    let table_path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let db = new_db_packed_file(&table_path, &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned())],
+        vec![DecodedData::StringU8("unit_2".to_owned())],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_unit_1".to_owned()), DecodedData::StringU16("Unit 1".to_owned()), DecodedData::Boolean(true)]);
+
+    let loc_path = vec!["text".to_owned(), "db".to_owned(), "test.loc".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        db,
+        PackedFile::read_from_vec(loc_path.to_vec(), 0, false, loc.save()),
+    ]);
+
+    assert_eq!(generate_loc_entries(&mut pack_file, &table_path, &loc_path, &[], &schema).unwrap(), (1, 1, false));
+    assert_eq!(generate_loc_entries(&mut pack_file, &table_path, &loc_path, &["{table}_description_{key}".to_owned()], &schema).unwrap(), (2, 0, false));
+
+    let loc = Loc::read(&pack_file.packed_files.iter().find(|x| x.path == loc_path).unwrap().get_data().unwrap()).unwrap();
+    assert_eq!(loc.entries.iter().map(|x| x[0].data_to_string()).collect::<Vec<String>>(), vec![
+        "units_onscreen_name_unit_1".to_owned(),
+        "units_onscreen_name_unit_2".to_owned(),
+        "units_description_unit_1".to_owned(),
+        "units_description_unit_2".to_owned(),
+    ]);
+    assert_eq!(loc.entries[0][1].data_to_string(), "Unit 1");
+
+    // Keyless tables cannot have loc keys generated.
+    let mut table_definitions = new_table_definitions_with_references("keyless_tables", &[]);
+    table_definitions.versions[0].fields[0].field_is_key = false;
+    let table_path = vec!["db".to_owned(), "keyless_tables".to_owned(), "test".to_owned()];
+    pack_file.add_packed_files(&[new_db_packed_file(&table_path, &table_definitions.versions[0], vec![])]);
+    schema.add_table_definitions(table_definitions);
+    assert!(generate_loc_entries(&mut pack_file, &table_path, &loc_path, &["{table}_name_{key}".to_owned()], &schema).is_err());
+}
+
+/// Test to make sure the duplicated loc key finder (`find_duplicated_loc_keys()`) finds keys repeated across Loc PackedFiles,
+/// and that resolving them (`resolve_duplicated_loc_keys()`) only keeps the chosen entry.
+#[test]
+fn test_resolve_duplicated_loc_keys() {
+    let mut loc_a = Loc::new();
+    loc_a.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("A".to_owned()), DecodedData::Boolean(true)]);
+    loc_a.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Unique".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut loc_b = Loc::new();
+    loc_b.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("B".to_owned()), DecodedData::Boolean(true)]);
+
+    let path_a = vec!["text".to_owned(), "a.loc".to_owned()];
+    let path_b = vec!["text".to_owned(), "b.loc".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(path_a.to_vec(), 0, false, loc_a.save()),
+        PackedFile::read_from_vec(path_b.to_vec(), 0, false, loc_b.save()),
+    ]);
+
+    let duplicated_keys = find_duplicated_loc_keys(&mut pack_file).unwrap();
+    assert_eq!(duplicated_keys.len(), 1);
+    assert_eq!(duplicated_keys[0].key, "key_1");
+    assert_eq!(duplicated_keys[0].entries, vec![(path_a.to_vec(), "A".to_owned()), (path_b.to_vec(), "B".to_owned())]);
+
+    assert_eq!(resolve_duplicated_loc_keys(&mut pack_file, &[("key_1".to_owned(), 1)]).unwrap(), vec![path_a.to_vec()]);
+    assert!(find_duplicated_loc_keys(&mut pack_file).unwrap().is_empty());
+
+    let loc_a = Loc::read(&pack_file.packed_files.iter().find(|x| x.path == path_a).unwrap().get_data().unwrap()).unwrap();
+    assert_eq!(loc_a.entries.len(), 1);
+    assert_eq!(loc_a.entries[0][0].data_to_string(), "key_2");
+}
+
+/// Test to make sure `find_untranslated_loc_entries()` only reports entries with the english text, ignoring the keys matching the patterns.
+#[test]
+fn test_find_untranslated_loc_entries() {
+    let mut english_loc = Loc::new();
+    english_loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Sword".to_owned()), DecodedData::Boolean(true)]);
+    english_loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Shield".to_owned()), DecodedData::Boolean(true)]);
+    english_loc.entries.push(vec![DecodedData::StringU16("names_name_1".to_owned()), DecodedData::StringU16("Karl Franz".to_owned()), DecodedData::Boolean(true)]);
+    let mut english_locs = vec![PackedFile::read_from_vec(vec!["text".to_owned(), "db".to_owned(), "english.loc".to_owned()], 0, false, english_loc.save())];
+
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Espada".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Shield".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("names_name_1".to_owned()), DecodedData::StringU16("Karl Franz".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("key_3".to_owned()), DecodedData::StringU16("Shield".to_owned()), DecodedData::Boolean(true)]);
+
+    let path = vec!["text".to_owned(), "translation.loc".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, loc.save())]);
+
+    let matches = find_untranslated_loc_entries(&mut pack_file, &mut english_locs, &[]).unwrap();
+    assert_eq!(matches, vec![GlobalMatch::Loc((path.to_vec(), vec![
+        ("text".to_owned(), 1, 1, "Shield".to_owned()),
+        ("text".to_owned(), 1, 2, "Karl Franz".to_owned()),
+    ]))]);
+
+    let matches = find_untranslated_loc_entries(&mut pack_file, &mut english_locs, &["^names_name_".to_owned()]).unwrap();
+    assert_eq!(matches, vec![GlobalMatch::Loc((path.to_vec(), vec![("text".to_owned(), 1, 1, "Shield".to_owned())]))]);
+}
+
+/// Test to make sure `rename_key_prefix()` renames the loc keys, the keys of localisable tables, their references and the loc keys built from them.
+#[test]
+fn test_rename_key_prefix() {
+    let mut units_definitions = new_table_definitions_with_references("units_tables", &[]);
+    units_definitions.versions[0].localised_fields.push(Field::new("onscreen_name".to_owned(), FieldType::StringU16, false, None, String::new()));
+    let armies_definitions = new_table_definitions_with_references("armies_tables", &["units"]);
+
+    let units_path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let armies_path = vec!["db".to_owned(), "armies_tables".to_owned(), "test".to_owned()];
+    let units = new_db_packed_file(&units_path, &units_definitions.versions[0], vec![vec![DecodedData::StringU8("mymod_unit".to_owned())]]);
+    let armies = new_db_packed_file(&armies_path, &armies_definitions.versions[0], vec![vec![DecodedData::StringU8("mymod_army".to_owned()), DecodedData::StringU8("mymod_unit".to_owned())]]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(units_definitions);
+    schema.add_table_definitions(armies_definitions);
+
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_mymod_unit".to_owned()), DecodedData::StringU16("Unit".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("mymod_custom_text".to_owned()), DecodedData::StringU16("Text".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("other_key".to_owned()), DecodedData::StringU16("Other".to_owned()), DecodedData::Boolean(true)]);
+
+    let loc_path = vec!["text".to_owned(), "db".to_owned(), "test.loc".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[units, armies, PackedFile::read_from_vec(loc_path.to_vec(), 0, false, loc.save())]);
+
+    // Without DB cells, only the loc keys starting with the prefix are renamed.
+    let changes = rename_key_prefix(&mut pack_file, "mymod_", "wh2_mymod_", false, false, &schema).unwrap();
+    assert_eq!(changes, vec![KeyPrefixChange {
+        path: loc_path.to_vec(),
+        row: 1,
+        column: "key".to_owned(),
+        old_value: "mymod_custom_text".to_owned(),
+        new_value: "wh2_mymod_custom_text".to_owned(),
+    }]);
+
+    // The preview must not change anything.
+    let changes = rename_key_prefix(&mut pack_file, "mymod_", "wh2_mymod_", true, false, &schema).unwrap();
+    assert_eq!(changes.len(), 4);
+    assert_eq!(get_table_data(&mut pack_file, &units_path, &schema).unwrap().1[0][0].data_to_string(), "mymod_unit");
+
+    assert_eq!(rename_key_prefix(&mut pack_file, "mymod_", "wh2_mymod_", true, true, &schema).unwrap(), changes);
+    assert_eq!(get_table_data(&mut pack_file, &units_path, &schema).unwrap().1, vec![vec![DecodedData::StringU8("wh2_mymod_unit".to_owned())]]);
+    assert_eq!(get_table_data(&mut pack_file, &armies_path, &schema).unwrap().1, vec![vec![DecodedData::StringU8("mymod_army".to_owned()), DecodedData::StringU8("wh2_mymod_unit".to_owned())]]);
+
+    let loc = Loc::read(&pack_file.packed_files.iter().find(|x| x.path == loc_path).unwrap().get_data().unwrap()).unwrap();
+    assert_eq!(loc.entries.iter().map(|x| x[0].data_to_string()).collect::<Vec<String>>(), vec![
+        "units_onscreen_name_wh2_mymod_unit".to_owned(),
+        "wh2_mymod_custom_text".to_owned(),
+        "other_key".to_owned(),
+    ]);
+}
+
+/// Test to make sure the distinct values of a column (`get_column_enums()`) are collected from both, the PackFile and the dependency database.
+#[test]
+fn test_get_column_enums() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let rows = |keys: &[&str]| -> Vec<Vec<DecodedData>> { keys.iter().map(|x| vec![DecodedData::StringU8(x.to_string())]).collect() };
+    let db_pack = new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "test".to_owned()], &table_definitions.versions[0], rows(&["unit_1", "unit_2", ""]));
+    let mut dependency_database = vec![new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "data__".to_owned()], &table_definitions.versions[0], rows(&["unit_2", "unit_3"]))];
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db_pack]);
+
+    let enums = get_column_enums(&mut pack_file, &mut dependency_database, &schema, &["units.key".to_owned()]).unwrap();
+    assert_eq!(enums["units.key"].iter().cloned().collect::<Vec<String>>(), vec!["unit_1".to_owned(), "unit_2".to_owned(), "unit_3".to_owned()]);
+
+    // Invalid references should fail.
+    assert!(get_column_enums(&mut pack_file, &mut dependency_database, &schema, &["units".to_owned()]).is_err());
+}
+
+/// Test to make sure referenced rows (`find_referenced_row()`) are searched first in the PackFile, then in the dependency database.
+#[test]
+fn test_find_referenced_row() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut db_pack = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+    let mut db_dependency = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+    for key in &["unit_1", "unit_2"] { db_pack.entries.push(vec![DecodedData::StringU8(key.to_string())]); }
+    for key in &["unit_2", "unit_3"] { db_dependency.entries.push(vec![DecodedData::StringU8(key.to_string())]); }
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let pack_path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let dependency_path = vec!["db".to_owned(), "units_tables".to_owned(), "data__".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(pack_path.to_vec(), 0, false, db_pack.save())]);
+    let mut dependency_database = vec![PackedFile::read_from_vec(dependency_path.to_vec(), 0, false, db_dependency.save())];
+
+    assert_eq!(find_row_by_value(&db_pack, "key", "unit_2"), Some((1, 0)));
+    assert_eq!(find_row_by_value(&db_pack, "tier", "unit_2"), None);
+    assert_eq!(find_referenced_row("units", "key", "unit_2", &schema, &mut dependency_database, &pack_file), Some(ReferencedRow::PackFile(pack_path, 1, 0)));
+    assert_eq!(find_referenced_row("units", "key", "unit_3", &schema, &mut dependency_database, &pack_file), Some(ReferencedRow::Dependency(dependency_path, vec!["key".to_owned()], vec!["unit_3".to_owned()])));
+    assert_eq!(find_referenced_row("units", "key", "unit_4", &schema, &mut dependency_database, &pack_file), None);
+
+    // Tables in aliased folders are searched as tables of the aliased type.
+    let aliased_path = vec!["db".to_owned(), "my_custom_units".to_owned(), "test".to_owned()];
+    pack_file.packed_files[0].path = aliased_path.to_vec();
+    assert_eq!(find_referenced_row("units", "key", "unit_1", &schema, &mut dependency_database, &pack_file), None);
+    pack_file.table_aliases.insert("my_custom_units".to_owned(), "units_tables".to_owned());
+    assert_eq!(find_referenced_row("units", "key", "unit_1", &schema, &mut dependency_database, &pack_file), Some(ReferencedRow::PackFile(aliased_path, 0, 0)));
+}
+
+/// Test to make sure the tables referenced by a definition (`get_referenced_tables()`) are returned once, in alphabetical order.
+#[test]
+fn test_get_referenced_tables() {
+    let table_definitions = new_table_definitions_with_references("main_units_tables", &["units", "factions", "units"]);
+    assert_eq!(get_referenced_tables(&table_definitions.versions[0]), vec!["factions_tables".to_owned(), "units_tables".to_owned()]);
+
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    assert!(get_referenced_tables(&table_definitions.versions[0]).is_empty());
+}
+
+/// Test to make sure the usages of a key (`find_usages()`) are only searched in the columns referencing it.
+#[test]
+fn test_find_usages() {
+    let table_definitions = new_table_definitions_with_references("land_units_tables", &["units"]);
+    let path = vec!["db".to_owned(), "land_units_tables".to_owned(), "test".to_owned()];
+    let db = new_db_packed_file(&path, &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::StringU8("unit_2".to_owned())],
+        vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::StringU8("unit_1".to_owned())],
+        vec![DecodedData::StringU8("unit_3".to_owned()), DecodedData::StringU8("unit_2".to_owned())],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db]);
+
+    assert_eq!(find_usages("units", "key", "unit_2", &schema, &pack_file), vec![GlobalMatch::DB((path, vec![
+        ("units_ref".to_owned(), 1, 0, "unit_2".to_owned()),
+        ("units_ref".to_owned(), 1, 2, "unit_2".to_owned()),
+    ]))]);
+    assert!(find_usages("units", "key", "unit_3", &schema, &pack_file).is_empty());
+    assert!(find_usages("land_units", "key", "unit_2", &schema, &pack_file).is_empty());
+}
+
+/// Test to make sure keys with non-ASCII characters (`find_non_ascii_keys()`) are reported with the position of the offending characters.
+#[test]
+fn test_find_non_ascii_keys() {
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_unit".to_owned()), DecodedData::StringU16("Unit".to_owned()), DecodedData::Boolean(true)]);
+    loc.entries.push(vec![DecodedData::StringU16("units_onscreen_name_unité".to_owned()), DecodedData::StringU16("Unité".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["text".to_owned(), "test.loc".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, loc.save())]);
+
+    let keys = find_non_ascii_keys(&mut pack_file, &path, &Schema::new()).unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].row, 1);
+    assert_eq!(keys[0].column, "key");
+    assert_eq!(keys[0].positions, vec![24]);
+}
+
+/// Test to make sure splitting a table (`split_table_by_column()`) creates one table per distinct value of the column.
+#[test]
+fn test_split_table_by_column() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("category".to_owned(), FieldType::StringU8, false, None, String::new()));
+    let mut db = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+    db.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::StringU8("infantry".to_owned())]);
+    db.entries.push(vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::StringU8("cavalry".to_owned())]);
+    db.entries.push(vec![DecodedData::StringU8("unit_3".to_owned()), DecodedData::StringU8("infantry".to_owned())]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, db.save())]);
+
+    let new_paths = split_table_by_column(&mut pack_file, &path, "category", false, &schema).unwrap();
+    assert_eq!(new_paths, vec![
+        vec!["db".to_owned(), "units_tables".to_owned(), "test_cavalry".to_owned()],
+        vec!["db".to_owned(), "units_tables".to_owned(), "test_infantry".to_owned()],
+    ]);
+    assert!(!pack_file.packedfile_exists(&path));
+
+    let (_, cavalry) = get_table_data(&mut pack_file, &new_paths[0], &schema).unwrap();
+    let (_, infantry) = get_table_data(&mut pack_file, &new_paths[1], &schema).unwrap();
+    assert_eq!(cavalry, vec![db.entries[1].to_vec()]);
+    assert_eq!(infantry, vec![db.entries[0].to_vec(), db.entries[2].to_vec()]);
+    assert!(split_table_by_column(&mut pack_file, &new_paths[0], "tier", true, &schema).is_err());
+}
+
+/// Test to make sure the bilingual TSV export (`export_bilingual_tsv()`) pairs the texts of both PackFiles by key, and that
+/// importing it back (`import_bilingual_tsv()`) only changes the text of the keys in the file, adding the missing ones.
+#[test]
+fn test_bilingual_tsv() {
+    let loc_path = vec!["text".to_owned(), "db".to_owned(), "test.loc".to_owned()];
+
+    let mut source_loc = Loc::new();
+    source_loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Hello".to_owned()), DecodedData::Boolean(true)]);
+    source_loc.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Bye".to_owned()), DecodedData::Boolean(true)]);
+    let mut source_pack_file = PackFile::new_with_name("source.pack".to_owned(), PFHVersion::PFH5);
+    source_pack_file.add_packed_files(&[PackedFile::read_from_vec(loc_path.to_vec(), 0, false, source_loc.save())]);
+
+    let mut target_loc = Loc::new();
+    target_loc.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Hola".to_owned()), DecodedData::Boolean(true)]);
+    target_loc.entries.push(vec![DecodedData::StringU16("key_3".to_owned()), DecodedData::StringU16("Otro".to_owned()), DecodedData::Boolean(true)]);
+    let mut target_pack_file = PackFile::new_with_name("target.pack".to_owned(), PFHVersion::PFH5);
+    target_pack_file.file_path = temp_dir().join("rpfm_test_bilingual_tsv.pack");
+    target_pack_file.add_packed_files(&[PackedFile::read_from_vec(loc_path.to_vec(), 0, false, target_loc.save())]);
+    target_pack_file.save().unwrap();
+
+    let path = temp_dir().join("rpfm_test_bilingual_tsv.tsv");
+    assert_eq!(export_bilingual_tsv(&mut source_pack_file, &[loc_path.to_vec()], &target_pack_file.file_path, &path).unwrap(), vec!["key_2".to_owned()]);
+    assert_eq!(read_to_string(&path).unwrap(), "key\tsource\ttarget\nkey_1\tHello\tHola\nkey_2\tBye\t\n");
+
+    File::create(&path).unwrap().write_all("key\tsource\ttarget\nkey_1\tHello\tHola!\nkey_2\tBye\t\n".as_bytes()).unwrap();
+    assert_eq!(import_bilingual_tsv(&mut target_pack_file, &loc_path, &path).unwrap(), (1, vec!["key_2".to_owned()]));
+
+    let loc = Loc::read(&target_pack_file.packed_files[0].get_data().unwrap()).unwrap();
+    assert_eq!(loc.entries.iter().map(|x| (x[0].data_to_string(), x[1].data_to_string())).collect::<Vec<(String, String)>>(), vec![
+        ("key_1".to_owned(), "Hola!".to_owned()),
+        ("key_3".to_owned(), "Otro".to_owned()),
+        ("key_2".to_owned(), String::new()),
+    ]);
+
+    remove_file(&path).unwrap();
+    remove_file(&target_pack_file.file_path).unwrap();
+}
+
+/// Test to make sure the Loc column order check (`verify_loc_column_order()`) reports the columns out of place.
+#[test]
+fn test_verify_loc_column_order() {
+    assert!(verify_loc_column_order(&TableDefinition::new_loc_definition()).is_empty());
+
+    let mut table_definition = TableDefinition::new_loc_definition();
+    table_definition.fields.swap(0, 1);
+    let mismatches = verify_loc_column_order(&table_definition);
+    assert_eq!(mismatches, vec![
+        "Column 1: expected \"key\" (StringU16), found \"text\" (StringU16).".to_owned(),
+        "Column 2: expected \"text\" (StringU16), found \"key\" (StringU16).".to_owned(),
+    ]);
+
+    table_definition.fields.pop();
+    assert_eq!(verify_loc_column_order(&table_definition).len(), 3);
+
+    // The definition has to be the one the Loc PackedFile has been written with, not the one we use to decode them.
+    let mut loc = Loc::new();
+    loc.entries.push(vec![DecodedData::StringU16("key".to_owned()), DecodedData::StringU16("text".to_owned()), DecodedData::Boolean(true)]);
+    assert!(verify_loc_column_order(&Loc::read_definition(&loc.save()).unwrap()).is_empty());
+
+    let mut data = Loc::new().save();
+    data[10..14].copy_from_slice(&encode_integer_u32(1));
+    data.extend_from_slice(&encode_packedfile_string_u16("k"));
+    data.push(encode_bool(true));
+    data.extend_from_slice(&encode_packedfile_string_u16("t"));
+    assert_eq!(verify_loc_column_order(&Loc::read_definition(&data).unwrap()), vec![
+        "Column 2: expected \"text\" (StringU16), found \"tooltip\" (Boolean).".to_owned(),
+        "Column 3: expected \"tooltip\" (Boolean), found \"text\" (StringU16).".to_owned(),
+    ]);
+}
+
+/// Test to make sure the translation matrix (`get_translation_matrix()`) has a row for every key, leaving blank the missing ones.
+#[test]
+fn test_get_translation_matrix() {
+    let mut loc_en = Loc::new();
+    loc_en.entries.push(vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Sword".to_owned()), DecodedData::Boolean(true)]);
+    loc_en.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Shield".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut loc_es = Loc::new();
+    loc_es.entries.push(vec![DecodedData::StringU16("key_2".to_owned()), DecodedData::StringU16("Escudo".to_owned()), DecodedData::Boolean(true)]);
+    loc_es.entries.push(vec![DecodedData::StringU16("key_3".to_owned()), DecodedData::StringU16("Lanza".to_owned()), DecodedData::Boolean(true)]);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path_en = vec!["text".to_owned(), "en.loc".to_owned()];
+    let path_es = vec!["text".to_owned(), "es.loc".to_owned()];
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(path_en.to_vec(), 0, false, loc_en.save()),
+        PackedFile::read_from_vec(path_es.to_vec(), 0, false, loc_es.save()),
+    ]);
+
+    let matrix = get_translation_matrix(&mut pack_file, &[path_en, path_es]).unwrap();
+    assert_eq!(matrix, vec![
+        vec!["key_1".to_owned(), "Sword".to_owned(), String::new()],
+        vec!["key_2".to_owned(), "Shield".to_owned(), "Escudo".to_owned()],
+        vec!["key_3".to_owned(), String::new(), "Lanza".to_owned()],
+    ]);
+}
+
+/// Test to make sure the PackFile validator (`validate_pack_for_schema()`) reports the tables without definition in the schema used.
+#[test]
+fn test_validate_pack_for_schema() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let db = new_db_packed_file(&path, &table_definitions.versions[0], vec![vec![DecodedData::StringU8("unit_1".to_owned())]]);
+
+    let mut schema_supported = Schema::new();
+    schema_supported.add_table_definitions(table_definitions);
+    let schema_unsupported = Schema::new();
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        db,
+        PackedFile::read_from_vec(vec!["text".to_owned(), "test.loc".to_owned()], 0, false, Loc::new().save()),
+    ]);
+
+    assert!(validate_pack_for_schema(&mut pack_file, &schema_supported).unwrap().is_empty());
+
+    let failures = validate_pack_for_schema(&mut pack_file, &schema_unsupported).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, path);
+}
+
+/// Test to make sure the oversized cell finder (`find_oversized_cells()`) only reports string cells longer than the threshold.
+#[test]
+fn test_find_oversized_cells() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let db = new_db_packed_file(&path, &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(1_000_000_000)],
+        vec![DecodedData::StringU8("this key is way too long".to_owned()), DecodedData::Integer(100)],
+        vec![DecodedData::StringU8("ñññññññññ".to_owned()), DecodedData::Integer(100)],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db]);
+
+    // Lengths are in characters, so the 9 "ñ" (18 bytes) must pass.
+    let cells = find_oversized_cells(&mut pack_file, 10, &schema).unwrap();
+    assert_eq!(cells.len(), 1);
+    assert_eq!(cells[0].path, path);
+    assert_eq!(cells[0].row, 1);
+    assert_eq!(cells[0].column, "key");
+    assert_eq!(cells[0].length, 24);
+}
+
+/// Test to make sure the composite uniqueness check (`check_composite_uniqueness()`) only reports rows with the same values in all the columns.
+#[test]
+fn test_check_composite_uniqueness() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("faction".to_owned(), FieldType::StringU8, false, None, String::new()));
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let db = new_db_packed_file(&path, &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::StringU8("faction_1".to_owned())],
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::StringU8("faction_2".to_owned())],
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::StringU8("faction_1".to_owned())],
+        vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::StringU8("faction_1".to_owned())],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db]);
+
+    let columns = vec!["key".to_owned(), "faction".to_owned()];
+    assert_eq!(check_composite_uniqueness(&mut pack_file, &path, &columns, &schema).unwrap(), vec![vec![0, 2]]);
+    assert!(check_composite_uniqueness(&mut pack_file, &path, &[], &schema).is_err());
+}
+
+/// Test to make sure the vanilla/mod comparison (`get_vanilla_mod_comparison()`) pairs the rows by key, leaving blank the mod-only ones.
+#[test]
+fn test_get_vanilla_mod_comparison() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let db_mod = new_db_packed_file(&path, &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_3".to_owned()), DecodedData::Integer(300)],
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(150)],
+    ]);
+
+    let mut dependency_database = vec![new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "data__".to_owned()], &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(100)],
+        vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(200)],
+    ])];
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db_mod]);
+
+    let rows = get_vanilla_mod_comparison(&mut pack_file, &mut dependency_database, &path, &schema).unwrap();
+    assert_eq!(rows, vec![
+        vec!["mod:key".to_owned(), "mod:cost".to_owned(), "vanilla:key".to_owned(), "vanilla:cost".to_owned()],
+        vec!["unit_3".to_owned(), "300".to_owned(), String::new(), String::new()],
+        vec!["unit_1".to_owned(), "150".to_owned(), "unit_1".to_owned(), "100".to_owned()],
+    ]);
+
+    // Without key columns, rows are only paired with the vanilla rows equal to them.
+    let mut schema = Schema::new();
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields[0].field_is_key = false;
+    table_definitions.versions[0].fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    schema.add_table_definitions(table_definitions);
+
+    let rows = get_vanilla_mod_comparison(&mut pack_file, &mut dependency_database, &path, &schema).unwrap();
+    assert_eq!(rows[1..].to_vec(), vec![
+        vec!["unit_3".to_owned(), "300".to_owned(), String::new(), String::new()],
+        vec!["unit_1".to_owned(), "150".to_owned(), String::new(), String::new()],
+    ]);
+}
+
+/// Test to make sure the key suggestions (`suggest_unique_key()`) never collide with the keys of the table.
+#[test]
+fn test_suggest_unique_key() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let db = new_db_packed_file(&path, &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("base".to_owned())],
+        vec![DecodedData::StringU8("base_1".to_owned())],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db]);
+
+    assert_eq!(suggest_unique_key(&mut pack_file, &path, "base", &schema).unwrap(), "base_2");
+    assert_eq!(suggest_unique_key(&mut pack_file, &path, "other", &schema).unwrap(), "other");
+    assert!(suggest_unique_key(&mut pack_file, &path, "", &schema).is_err());
+}
+
+/// Test to make sure the row count snapshots (`append_row_count_snapshot()`) are appended to the log, not overwriting it.
+#[test]
+fn test_append_row_count_snapshot() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut db = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+    db.entries.push(vec![DecodedData::StringU8("unit_1".to_owned())]);
+    db.entries.push(vec![DecodedData::StringU8("unit_2".to_owned())]);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, db.save())]);
+
+    let log_path = temp_dir().join("rpfm_test_append_row_count_snapshot.log");
+    if log_path.is_file() { remove_file(&log_path).unwrap(); }
+    append_row_count_snapshot(&mut pack_file, &log_path).unwrap();
+
+    // Add a row between snapshots, so we can tell them apart.
+    db.entries.push(vec![DecodedData::StringU8("unit_3".to_owned())]);
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, db.save())]);
+    append_row_count_snapshot(&mut pack_file, &log_path).unwrap();
+
+    let log = read_to_string(&log_path).unwrap();
+    remove_file(&log_path).unwrap();
+
+    let lines = log.lines().map(|x| x.split('\t').collect::<Vec<&str>>()).collect::<Vec<Vec<&str>>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0][1..], ["db/units_tables/test", "2"]);
+    assert_eq!(lines[1][1..], ["db/units_tables/test", "3"]);
+    assert!(lines[0][0].parse::<i64>().is_ok());
+}
+
+/// Test to make sure the redundant override finder (`find_redundant_overrides()`) only reports PackedFiles identical to their vanilla version.
+#[test]
+fn test_find_redundant_overrides() {
+    let path_identical = vec!["db".to_owned(), "units_tables".to_owned(), "data__".to_owned()];
+    let path_modified = vec!["db".to_owned(), "factions_tables".to_owned(), "data__".to_owned()];
+    let path_new = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+
+    let mut dependency_database = vec![
+        PackedFile::read_from_vec(path_identical.to_vec(), 0, false, vec![1, 2, 3]),
+        PackedFile::read_from_vec(path_modified.to_vec(), 0, false, vec![1, 2, 3]),
+    ];
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(path_identical.to_vec(), 0, false, vec![1, 2, 3]),
+        PackedFile::read_from_vec(path_modified.to_vec(), 0, false, vec![1, 2, 4]),
+        PackedFile::read_from_vec(path_new.to_vec(), 0, false, vec![1, 2, 3]),
+    ]);
+
+    assert_eq!(find_redundant_overrides(&mut pack_file, &mut dependency_database).unwrap(), vec![path_identical]);
+}
+
+/// Test to make sure the unreferenced asset finder (`find_unreferenced_assets()`) only reports assets no file path column points to.
+#[test]
+fn test_find_unreferenced_assets() {
+    let mut table_definitions = new_table_definitions_with_references("unit_variants_tables", &[]);
+    let mut field = Field::new("texture".to_owned(), FieldType::StringU8, false, None, String::new());
+    field.field_is_filename = Some("ui/units/icons".to_owned());
+    table_definitions.versions[0].fields.push(field);
+    let db = new_db_packed_file(&["db".to_owned(), "unit_variants_tables".to_owned(), "test".to_owned()], &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::StringU8("Unit_1.png".to_owned())],
+    ]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let path_referenced = vec!["ui".to_owned(), "units".to_owned(), "icons".to_owned(), "unit_1.png".to_owned()];
+    let path_unreferenced = vec!["ui".to_owned(), "units".to_owned(), "icons".to_owned(), "unit_2.png".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        db,
+        PackedFile::read_from_vec(path_referenced, 0, false, vec![1, 2, 3]),
+        PackedFile::read_from_vec(path_unreferenced.to_vec(), 0, false, vec![1, 2, 3]),
+        PackedFile::read_from_vec(vec!["script".to_owned(), "test.lua".to_owned()], 0, false, vec![97, 98]),
+    ]);
+
+    assert_eq!(find_unreferenced_assets(&mut pack_file, &mut [], &schema).unwrap(), vec![path_unreferenced]);
+}
+
+/// Test to make sure the edit scripts (`run_edit_script()`) apply all their operations, or none of them if one fails.
+#[test]
+fn test_run_edit_script() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    let mut db = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+    db.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(100)]);
+    db.entries.push(vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(200)]);
+    let original_data = db.save();
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, original_data.to_vec())]);
+
+    // The third operation fails, so the first two must not be applied.
+    let operations = vec![
+        EditOperation::EditCell { path: path.to_vec(), row: 0, column: "cost".to_owned(), value: "150".to_owned() },
+        EditOperation::DeleteRow { path: path.to_vec(), row: 1 },
+        EditOperation::EditCell { path: path.to_vec(), row: 0, column: "cost".to_owned(), value: "not_a_number".to_owned() },
+    ];
+    match run_edit_script(&mut pack_file, &operations, &schema) {
+        Err(error) => assert_eq!(error.kind(), ErrorKind::EditScriptOperationFailed(3, Error::from(ErrorKind::DBTableReplaceInvalidData).to_string())),
+        Ok(_) => panic!("Edit script with a broken operation worked."),
+    }
+    assert_eq!(pack_file.packed_files[0].get_data_and_keep_it().unwrap(), original_data);
+
+    // Without the broken operation, everything must be applied.
+    let report = run_edit_script(&mut pack_file, &operations[..2], &schema).unwrap();
+    assert_eq!(report.len(), 2);
+    let (_, entries) = get_table_data(&mut pack_file, &path, &schema).unwrap();
+    assert_eq!(entries, vec![vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(150)]]);
+}
+
+/// Test to make sure the table sizes (`get_size_by_table_type()`) are aggregated by table type, ignoring everything that's not a table.
+#[test]
+fn test_get_size_by_table_type() {
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["db".to_owned(), "units_tables".to_owned(), "test_1".to_owned()], 0, false, vec![0; 10]),
+        PackedFile::read_from_vec(vec!["db".to_owned(), "units_tables".to_owned(), "test_2".to_owned()], 0, false, vec![0; 5]),
+        PackedFile::read_from_vec(vec!["db".to_owned(), "factions_tables".to_owned(), "test".to_owned()], 0, false, vec![0; 7]),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "test.loc".to_owned()], 0, false, vec![0; 3]),
+        PackedFile::read_from_vec(vec!["script".to_owned(), "test.lua".to_owned()], 0, false, vec![0; 100]),
+    ]);
+
+    let sizes = get_size_by_table_type(&pack_file);
+    assert_eq!(sizes.len(), 3);
+    assert_eq!(sizes["units_tables"], 15);
+    assert_eq!(sizes["factions_tables"], 7);
+    assert_eq!(sizes["loc"], 3);
+}
+
+/// Test to make sure the HTML tree (`get_tree_as_html()`) nests the folders properly and escapes the names.
+#[test]
+fn test_get_tree_as_html() {
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()], 0, false, vec![0; 10]),
+        PackedFile::read_from_vec(vec!["script".to_owned(), "<b>&.lua".to_owned()], 0, false, vec![0; 3]),
+    ]);
+
+    let html = get_tree_as_html(&pack_file);
+    assert_eq!(html.matches("<details").count(), 4);
+    assert_eq!(html.matches("<details").count(), html.matches("</details>").count());
+    assert_eq!(html.matches("<ul>").count(), html.matches("</ul>").count());
+    assert!(html.contains("<li><details><summary>db</summary>\n<ul>\n<li><details><summary>units_tables</summary>\n<ul>\n<li>test <i>(DB Table, 10 bytes)</i></li>"));
+    assert!(html.contains("<li>&lt;b&gt;&amp;.lua <i>(Text, 3 bytes)</i></li>"));
+    assert!(!html.contains("<b>"));
+}
+
+/// Test to make sure the session counters (`SessionEditStats`) count properly the edits done over a sequence of operations.
+#[test]
+fn test_session_edit_stats() {
+    let mut stats = SessionEditStats::default();
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let get_paths = |pack_file: &PackFile| pack_file.packed_files.iter().map(|x| x.path.to_vec()).collect::<Vec<Vec<String>>>();
+
+    // Add two files.
+    let old_paths = get_paths(&pack_file);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["script".to_owned(), "a.lua".to_owned()], 0, false, vec![]),
+        PackedFile::read_from_vec(vec!["script".to_owned(), "b.lua".to_owned()], 0, false, vec![]),
+    ]);
+    stats.record_files_change(&old_paths, &get_paths(&pack_file));
+
+    // Rename one of them, and delete the other one.
+    let old_paths = get_paths(&pack_file);
+    rename_packed_files(&mut pack_file, &[(PathType::File(vec!["script".to_owned(), "a.lua".to_owned()]), "c.lua".to_owned())]);
+    stats.record_files_rename(&old_paths, &get_paths(&pack_file));
+
+    let old_paths = get_paths(&pack_file);
+    delete_from_packfile(&mut pack_file, &[PathType::File(vec!["script".to_owned(), "b.lua".to_owned()])]);
+    stats.record_files_change(&old_paths, &get_paths(&pack_file));
+
+    // Edit a table: the first row is edited, and a new one is added at the end.
+    let row = |key: &str| vec![DecodedData::StringU8(key.to_owned())];
+    stats.record_table_edit(&[row("a"), row("b"), row("c")], &[row("a2"), row("b"), row("c"), row("d")]);
+
+    assert_eq!(stats, SessionEditStats {
+        rows_added: 1,
+        rows_removed: 0,
+        rows_edited: 1,
+        files_added: 2,
+        files_deleted: 1,
+        files_renamed: 1,
+    });
+
+    // Deleting a row in the middle shouldn't count the rows after it as edited.
+    stats = SessionEditStats::default();
+    stats.record_table_edit(&[row("a"), row("b"), row("c")], &[row("a"), row("c")]);
+    assert_eq!(stats.rows_removed, 1);
+    assert_eq!(stats.rows_edited, 0);
+}
+
+/// Test to make sure replacing a value in a column (`replace_in_column()`) replaces only the matching cells, and fails with values of the wrong type.
+#[test]
+fn test_replace_in_column() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("class".to_owned(), FieldType::StringU8, false, None, String::new()));
+    table_definitions.versions[0].fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let entries = [("unit_1", "inf_mel"), ("unit_2", "inf_spr"), ("unit_3", "inf_mel"), ("unit_4", "inf_mel")].iter()
+        .map(|(key, class)| vec![DecodedData::StringU8(key.to_string()), DecodedData::StringU8(class.to_string()), DecodedData::Integer(100)])
+        .collect();
+    let db = new_db_packed_file(&path, &table_definitions.versions[0], entries);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[db]);
+
+    assert_eq!(replace_in_column(&mut pack_file, &path, "class", "inf_mel", "inf_sword", &schema).unwrap(), 3);
+    let (_, entries) = get_table_data(&mut pack_file, &path, &schema).unwrap();
+    assert_eq!(entries.iter().map(|x| x[1].data_to_string()).collect::<Vec<String>>(), vec!["inf_sword", "inf_spr", "inf_sword", "inf_sword"]);
+
+    match replace_in_column(&mut pack_file, &path, "cost", "100", "cheap", &schema) {
+        Err(error) => assert_eq!(error.kind(), ErrorKind::DBTableReplaceInvalidData),
+        Ok(_) => panic!("Replacing a number with text worked."),
+    }
+    match replace_in_column(&mut pack_file, &path, "not_a_column", "100", "200", &schema) {
+        Err(error) => assert_eq!(error.kind(), ErrorKind::ColumnNotFound("not_a_column".to_owned())),
+        Ok(_) => panic!("Replacing in a column that doesn't exist worked."),
+    }
+}
+
+/// Test to make sure the key validator (`validate_key_column()`) flags empty and duplicated keys, and passes valid ones.
+#[test]
+fn test_validate_key_column() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    let rows = |keys: &[&str]| -> Vec<Vec<DecodedData>> { keys.iter().map(|x| vec![DecodedData::StringU8(x.to_string())]).collect() };
+    let broken_path = vec!["db".to_owned(), "units_tables".to_owned(), "broken".to_owned()];
+    let valid_path = vec!["db".to_owned(), "units_tables".to_owned(), "valid".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        new_db_packed_file(&broken_path, &table_definitions.versions[0], rows(&["unit_1", "", "unit_2", "unit_1"])),
+        new_db_packed_file(&valid_path, &table_definitions.versions[0], rows(&["unit_1", "unit_2", "unit_3"])),
+    ]);
+
+    assert_eq!(validate_key_column(&mut pack_file, &broken_path, &schema).unwrap(), vec![
+        "Row 2: the key column \"key\" is empty.".to_owned(),
+        "Row 4: the key \"unit_1\" is already used in row 1.".to_owned(),
+    ]);
+    assert!(validate_key_column(&mut pack_file, &valid_path, &schema).unwrap().is_empty());
+}
+
+/// Test to make sure the Loc matches exporter (`export_loc_matches()`) exports the matching rows of every Loc PackedFile, respecting the case flag.
+#[test]
+fn test_export_loc_matches() {
+    let row = |key: &str, text: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(true)];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["text".to_owned(), "units.loc".to_owned()], 0, false, Loc { entries: vec![row("unit_1", "Empire Swordsmen"), row("unit_2", "Greatswords")] }.save()),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "lords.loc".to_owned()], 0, false, Loc { entries: vec![row("lord_1", "Karl Franz, the sword of the Empire")] }.save()),
+    ]);
+
+    let out = temp_dir().join("rpfm_test_export_loc_matches.tsv");
+    export_loc_matches(&mut pack_file, "sword", false, &out).unwrap();
+    assert_eq!(read_to_string(&out).unwrap(), "File\tKey\tText\ntext/units.loc\tunit_1\tEmpire Swordsmen\ntext/units.loc\tunit_2\tGreatswords\ntext/lords.loc\tlord_1\tKarl Franz, the sword of the Empire\n");
+
+    export_loc_matches(&mut pack_file, "Sword", true, &out).unwrap();
+    assert_eq!(read_to_string(&out).unwrap(), "File\tKey\tText\ntext/units.loc\tunit_1\tEmpire Swordsmen\n");
+    remove_file(&out).unwrap();
+}
+
+/// Test to make sure merging identical adjacent rows (`merge_identical_adjacent_rows()`) only collapses consecutive repeated rows.
+#[test]
+fn test_merge_identical_adjacent_rows() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    let entries = ["unit_1", "unit_2", "unit_2", "unit_2", "unit_3", "unit_1"].iter().map(|x| vec![DecodedData::StringU8(x.to_string())]).collect();
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    pack_file.add_packed_files(&[new_db_packed_file(&path, &table_definitions.versions[0], entries)]);
+
+    assert_eq!(merge_identical_adjacent_rows(&mut pack_file, &path, &schema).unwrap(), 2);
+    let (_, entries) = get_table_data(&mut pack_file, &path, &schema).unwrap();
+    assert_eq!(entries.iter().map(|x| x[0].data_to_string()).collect::<Vec<String>>(), vec!["unit_1", "unit_2", "unit_3", "unit_1"]);
+    assert_eq!(merge_identical_adjacent_rows(&mut pack_file, &path, &schema).unwrap(), 0);
+}
+
+/// Test to make sure the table fingerprints (`get_table_checksums()`) only depend on the content of the tables, not on the order of their rows.
+#[test]
+fn test_get_table_checksums() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    let table = |name: &str, keys: &[&str]| {
+        let path = vec!["db".to_owned(), "units_tables".to_owned(), name.to_owned()];
+        new_db_packed_file(&path, &table_definitions.versions[0], keys.iter().map(|x| vec![DecodedData::StringU8(x.to_string())]).collect())
+    };
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        table("a", &["unit_1", "unit_2", "unit_2"]),
+        table("b", &["unit_2", "unit_1", "unit_2"]),
+        table("c", &["unit_1", "unit_1", "unit_2"]),
+        PackedFile::read_from_vec(vec!["script".to_owned(), "test.lua".to_owned()], 0, false, vec![]),
+    ]);
+
+    let checksums = get_table_checksums(&mut pack_file, &schema).unwrap();
+    assert_eq!(checksums.len(), 3);
+    assert_eq!(checksums[0].1, checksums[1].1);
+    assert_ne!(checksums[0].1, checksums[2].1);
+}
+
+/// Test to make sure optimizing big tables (`Loc::optimize_table()` and `DB::optimize_table()`) is fast enough. With a linear search
+/// per row this takes minutes, so if it gets slow again, it shows.
+#[test]
+fn test_optimize_table_big_tables() {
+    let entry = |index: usize| vec![DecodedData::StringU16(format!("key_{}", index)), DecodedData::StringU16(format!("Text {}", index)), DecodedData::Boolean(false)];
+
+    // Every even row of ours is vanilla.
+    let vanilla_loc = Loc { entries: (0..100_000).map(|x| entry(x * 2)).collect() };
+    let mut loc = Loc { entries: (0..10_000).map(entry).collect() };
+    assert!(!loc.optimize_table(&[vanilla_loc]));
+    assert_eq!(loc.entries, (0..5_000).map(|x| entry(x * 2 + 1)).collect::<Vec<Vec<DecodedData>>>());
+
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let table = |keys: Vec<usize>| {
+        let mut db = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+        db.entries = keys.iter().map(|x| vec![DecodedData::StringU8(format!("unit_{}", x))]).collect();
+        db
+    };
+    let mut db = table((0..10_000).collect());
+    assert!(!db.optimize_table(&[table((0..100_000).map(|x| x * 2).collect())]));
+    assert_eq!(db.entries, table((0..5_000).map(|x| x * 2 + 1).collect()).entries);
+
+    // Floats that are equal must be found, even if they're different zeros.
+    let mut loc = Loc { entries: vec![vec![DecodedData::Float(-0.0)], vec![DecodedData::Float(1.5)]] };
+    assert!(loc.optimize_table(&[Loc { entries: vec![vec![DecodedData::Float(0.0)], vec![DecodedData::Float(1.5)]] }]));
+}
+
+/// Test to make sure exporting tables to JSON (`DB::export_json()` and `Loc::export_json()`) keeps the definition and the type of every value.
+#[test]
+fn test_export_json() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    table_definitions.versions[0].fields.push(Field::new("speed".to_owned(), FieldType::Float, false, None, String::new()));
+    table_definitions.versions[0].fields.push(Field::new("is_naval".to_owned(), FieldType::Boolean, false, None, String::new()));
+    let mut db = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+    db.entries.push(vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(100), DecodedData::Float(1.1), DecodedData::Boolean(true)]);
+
+    let path = temp_dir().join("rpfm_test_export_json.json");
+    db.export_json(&path).unwrap();
+    let table: TableJSON = serde_json::from_str(&read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(table.table_name, "units_tables");
+    assert_eq!(table.version, 1);
+    assert_eq!(table.fields.iter().map(|x| (x.name.to_owned(), x.field_type)).collect::<Vec<(String, FieldType)>>(), vec![
+        ("key".to_owned(), FieldType::StringU8),
+        ("cost".to_owned(), FieldType::Integer),
+        ("speed".to_owned(), FieldType::Float),
+        ("is_naval".to_owned(), FieldType::Boolean),
+    ]);
+    assert_eq!(table.rows, vec![vec![serde_json::json!("unit_1"), serde_json::json!(100), serde_json::json!(1.1), serde_json::json!(true)]]);
+
+    let loc = Loc { entries: vec![vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Text 1".to_owned()), DecodedData::Boolean(false)]] };
+    loc.export_json(&path).unwrap();
+    let table: TableJSON = serde_json::from_str(&read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(table.table_name, TSV_HEADER_LOC_PACKEDFILE);
+    assert_eq!(table.fields.len(), 3);
+    assert_eq!(table.rows, vec![vec![serde_json::json!("key_1"), serde_json::json!("Text 1"), serde_json::json!(false)]]);
+    remove_file(&path).unwrap();
+}
+
+/// Test to make sure exporting tables to SQLite (`export_to_sqlite()`) makes a table for each PackedFile, with typed columns.
+#[test]
+fn test_export_to_sqlite() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("speed".to_owned(), FieldType::Float, false, None, String::new()));
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    let table = |name: &str, keys: &[&str]| {
+        let path = vec!["db".to_owned(), "units_tables".to_owned(), name.to_owned()];
+        new_db_packed_file(&path, &table_definitions.versions[0], keys.iter().map(|x| vec![DecodedData::StringU8(x.to_string()), DecodedData::Float(1.5)]).collect())
+    };
+    let loc = Loc { entries: vec![vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Text 1".to_owned()), DecodedData::Boolean(true)]] };
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        table("a", &["unit_1", "unit_2"]),
+        table("b", &["unit_3"]),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "test.loc".to_owned()], 0, false, loc.save()),
+        PackedFile::read_from_vec(vec!["script".to_owned(), "test.lua".to_owned()], 0, false, vec![]),
+    ]);
+
+    let path = temp_dir().join("rpfm_test_export_to_sqlite.sqlite");
+    assert_eq!(export_to_sqlite(&mut pack_file, &[PathType::PackFile], &schema, &path).unwrap(), (3, 4));
+
+    {
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        let count: i64 = connection.query_row("SELECT COUNT(*) FROM \"db/units_tables/a\" WHERE speed = 1.5", rusqlite::NO_PARAMS, |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+        let count: i64 = connection.query_row("SELECT COUNT(*) FROM \"db/units_tables/b\"", rusqlite::NO_PARAMS, |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+        let tooltip: i64 = connection.query_row("SELECT tooltip FROM \"text/test.loc\" WHERE key = 'key_1'", rusqlite::NO_PARAMS, |row| row.get(0)).unwrap();
+        assert_eq!(tooltip, 1);
+        let version: i64 = connection.query_row("SELECT version FROM rpfm_tables WHERE path = 'db/units_tables/b'", rusqlite::NO_PARAMS, |row| row.get(0)).unwrap();
+        assert_eq!(version, 1);
+    }
+
+    // Exporting only a folder overwrites the database with just the tables in it.
+    assert_eq!(export_to_sqlite(&mut pack_file, &[PathType::Folder(vec!["text".to_owned()])], &schema, &path).unwrap(), (1, 1));
+    remove_file(&path).unwrap();
+}
+
+/// Test to make sure importing tables from SQLite (`import_from_sqlite()`) replaces or creates the tables in the paths they were exported from,
+/// and reports the ones with wrong columns or that were not exported by us.
+#[test]
+fn test_import_from_sqlite() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("speed".to_owned(), FieldType::Float, false, None, String::new()));
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    let entries = vec![vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Float(1.5)]];
+    let loc = Loc { entries: vec![vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Text 1".to_owned()), DecodedData::Boolean(true)]] };
+
+    // Two tables of the same type and version, which go back to their own PackedFiles.
+    let db_path = vec!["db".to_owned(), "units_tables".to_owned(), "a".to_owned()];
+    let other_db_path = vec!["db".to_owned(), "units_tables".to_owned(), "b".to_owned()];
+    let loc_path = vec!["text".to_owned(), "test.loc".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        new_db_packed_file(&db_path, &table_definitions.versions[0], entries.to_vec()),
+        new_db_packed_file(&other_db_path, &table_definitions.versions[0], entries),
+        PackedFile::read_from_vec(loc_path.to_vec(), 0, false, loc.save()),
+    ]);
+
+    let path = temp_dir().join("rpfm_test_import_from_sqlite.sqlite");
+    export_to_sqlite(&mut pack_file, &[PathType::PackFile], &schema, &path).unwrap();
+    {
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        connection.execute("UPDATE \"db/units_tables/a\" SET speed = 2.5", rusqlite::NO_PARAMS).unwrap();
+        connection.execute("CREATE TABLE broken (key TEXT, speed TEXT, extra INTEGER)", rusqlite::NO_PARAMS).unwrap();
+        connection.execute("INSERT INTO rpfm_tables VALUES ('broken', 'db/units_tables/c', 1)", rusqlite::NO_PARAMS).unwrap();
+        connection.execute("CREATE TABLE land_units_v1 (key TEXT)", rusqlite::NO_PARAMS).unwrap();
+    }
+
+    // Importing into the same PackFile replaces the tables.
+    let (added_paths, modified_paths, errors) = import_from_sqlite(&mut pack_file, &schema, &path).unwrap();
+    assert!(added_paths.is_empty());
+    assert_eq!(modified_paths, vec![db_path.to_vec(), other_db_path.to_vec(), loc_path.to_vec()]);
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("broken") && errors[0].contains("<li>speed</li>") && errors[0].contains("<li>extra</li>") && !errors[0].contains("<li>key</li>"));
+    assert!(errors[1].contains("land_units_v1"));
+
+    let data = pack_file.packed_files.iter().find(|x| x.path == db_path).unwrap().get_data().unwrap();
+    assert_eq!(DB::read(&data, "units_tables", &schema).unwrap().entries[0][1], DecodedData::Float(2.5));
+    let data = pack_file.packed_files.iter().find(|x| x.path == other_db_path).unwrap().get_data().unwrap();
+    assert_eq!(DB::read(&data, "units_tables", &schema).unwrap().entries[0][1], DecodedData::Float(1.5));
+
+    // Importing into a PackFile without the tables creates them.
+    let mut pack_file = PackFile::new_with_name("other.pack".to_owned(), PFHVersion::PFH5);
+    let (added_paths, modified_paths, _) = import_from_sqlite(&mut pack_file, &schema, &path).unwrap();
+    assert!(modified_paths.is_empty());
+    assert_eq!(added_paths, vec![db_path.to_vec(), other_db_path.to_vec(), loc_path.to_vec()]);
+    remove_file(&path).unwrap();
+}
+
+/// Test to make sure the Mass-Export TSV (`tsv_mass_export()`) can skip the tables that are the same as in the PackFile on disk.
+#[test]
+fn test_tsv_mass_export_only_modified() {
+    let loc = |text: &str| Loc { entries: vec![vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(true)]] }.save();
+    let path_1 = vec!["text".to_owned(), "db".to_owned(), "first.loc".to_owned()];
+    let path_2 = vec!["text".to_owned(), "db".to_owned(), "second.loc".to_owned()];
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.file_path = temp_dir().join("rpfm_test_tsv_mass_export_only_modified.pack");
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(path_1.to_vec(), 0, false, loc("Text 1")),
+        PackedFile::read_from_vec(path_2.to_vec(), 0, false, loc("Text 2")),
+    ]);
+    pack_file.save().unwrap();
+    pack_file.packed_files.iter_mut().find(|x| x.path == path_2).unwrap().set_data(loc("Text 2 edited"));
+
+    let export_path = temp_dir().join("rpfm_test_tsv_mass_export_only_modified");
+    std::fs::create_dir_all(&export_path).unwrap();
+    let message = tsv_mass_export(&export_path, &mut pack_file, true).unwrap();
+    assert!(message.contains("<li>text/db/first.loc</li>"));
+    assert!(!export_path.join("first.loc.tsv").is_file());
+    assert!(read_to_string(export_path.join("second.loc.tsv")).unwrap().contains("Text 2 edited"));
+
+    remove_dir_all(&export_path).unwrap();
+    remove_file(&pack_file.file_path).unwrap();
+}
+
+/// Test to make sure the Mass-Import TSV from a folder (`tsv_mass_import_from_folder()`) uses the structure of the folder to know where
+/// each file goes, with or without metadata line, and doesn't import anything if any of the files already exists.
+#[test]
+fn test_tsv_mass_import_from_folder() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("speed".to_owned(), FieldType::Float, false, None, String::new()));
+    let mut newer_definition = table_definitions.versions[0].clone();
+    newer_definition.version = 2;
+    newer_definition.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    table_definitions.add_table_definition(newer_definition);
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let folder_path = temp_dir().join("rpfm_test_tsv_mass_import_from_folder");
+    std::fs::create_dir_all(folder_path.join("db").join("units_tables")).unwrap();
+    std::fs::create_dir_all(folder_path.join("text").join("db")).unwrap();
+    File::create(folder_path.join("db").join("units_tables").join("units.tsv")).unwrap().write_all(b"key\tspeed\tcost\nunit_1\t1.5\t100\n").unwrap();
+    File::create(folder_path.join("db").join("units_tables").join("old_units.tsv")).unwrap().write_all(b"units_tables\t1\nkey\tspeed\nunit_2\t2.5\n").unwrap();
+    File::create(folder_path.join("text").join("db").join("mymod.tsv")).unwrap().write_all(b"Loc PackedFile\t1\nkey\ttext\ttooltip\nkey_1\tText 1\ttrue\n").unwrap();
+    File::create(folder_path.join("readme.tsv")).unwrap().write_all(b"whatever\n").unwrap();
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let (mut paths, warnings) = tsv_mass_import_from_folder(&folder_path, &mut pack_file, &schema).unwrap();
+    paths.sort();
+    assert_eq!(paths, vec![
+        vec!["db".to_owned(), "units_tables".to_owned(), "old_units".to_owned()],
+        vec!["db".to_owned(), "units_tables".to_owned(), "units".to_owned()],
+        vec!["text".to_owned(), "db".to_owned(), "mymod.loc".to_owned()],
+    ]);
+    assert_eq!(warnings.len(), 1);
+
+    let get_db = |pack_file: &PackFile, name: &str| {
+        let data = pack_file.packed_files.iter().find(|x| x.path.last().unwrap() == name).unwrap().get_data().unwrap();
+        DB::read(&data, "units_tables", &schema).unwrap()
+    };
+    let units = get_db(&pack_file, "units");
+    assert_eq!(units.version, 2);
+    assert_eq!(units.entries[0][2], DecodedData::Integer(100));
+    assert_eq!(get_db(&pack_file, "old_units").version, 1);
+
+    // Importing it again conflicts with the files already in the PackFile.
+    match tsv_mass_import_from_folder(&folder_path, &mut pack_file, &schema).unwrap_err().kind() {
+        ErrorKind::MassImportConflicts(_) => {},
+        _ => panic!("Wrong error returned."),
+    }
+    assert_eq!(pack_file.packed_files.len(), 3);
+    remove_dir_all(&folder_path).unwrap();
+}
+
+//-----------------------------------------------------//
+//                  TSV Import/Export
+//-----------------------------------------------------//
+
+/// Test to make sure the TSV importer (`import_tsv()`) puts every column in his place,
+/// defaults the missing ones and reports the unknown ones.
+#[test]
+fn test_import_tsv_by_header() {
+
+    let mut definition = TableDefinition::new(3);
+    definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, "".to_owned()));
+    definition.fields.push(Field::new("value".to_owned(), FieldType::Integer, false, None, "".to_owned()));
+    definition.fields.push(Field::new("enabled".to_owned(), FieldType::Boolean, false, None, "".to_owned()));
+
+    // Columns reordered, "enabled" missing, and an extra column the definition doesn't have.
+    let path = temp_dir().join("rpfm_test_import_tsv_by_header.tsv");
+    let mut file = File::create(&path).unwrap();
+    file.write_all(b"test_tables\t3\nvalue\tcomment\tkey\n5\tignore me\tfirst\n7\t\tsecond\n").unwrap();
+
+    let (entries, unknown_columns, errors) = import_tsv(&definition, &path, "test_tables", 3).unwrap();
+    remove_file(&path).unwrap();
+    assert!(errors.is_empty());
+
+    assert_eq!(entries, vec![
+        vec![DecodedData::StringU8("first".to_owned()), DecodedData::Integer(5), DecodedData::Boolean(false)],
+        vec![DecodedData::StringU8("second".to_owned()), DecodedData::Integer(7), DecodedData::Boolean(false)],
+    ]);
+    assert_eq!(unknown_columns, vec!["comment".to_owned()]);
+}
+
+/// Test to make sure the TSV importer (`import_tsv()`) imports a TSV with the columns shuffled exactly like one with them in order.
+#[test]
+fn test_import_tsv_shuffled_columns() {
+
+    let mut definition = TableDefinition::new(3);
+    definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, "".to_owned()));
+    definition.fields.push(Field::new("value".to_owned(), FieldType::Integer, false, None, "".to_owned()));
+    definition.fields.push(Field::new("enabled".to_owned(), FieldType::Boolean, false, None, "".to_owned()));
+
+    let import = |name: &str, tsv: &[u8]| {
+        let path = temp_dir().join(name);
+        File::create(&path).unwrap().write_all(tsv).unwrap();
+        let (entries, unknown_columns, errors) = import_tsv(&definition, &path, "test_tables", 3).unwrap();
+        remove_file(&path).unwrap();
+        assert!(errors.is_empty());
+        (entries, unknown_columns)
+    };
+
+    let (canonical, unknown_columns) = import("rpfm_test_import_tsv_canonical.tsv", b"test_tables\t3\nkey\tvalue\tenabled\nfirst\t5\ttrue\nsecond\t7\tfalse\n");
+    let (shuffled, unknown_columns_shuffled) = import("rpfm_test_import_tsv_shuffled.tsv", b"test_tables\t3\nenabled\tkey\tvalue\ntrue\tfirst\t5\nfalse\tsecond\t7\n");
+    assert_eq!(canonical, shuffled);
+    assert!(unknown_columns.is_empty() && unknown_columns_shuffled.is_empty());
+    assert_eq!(canonical[0], vec![DecodedData::StringU8("first".to_owned()), DecodedData::Integer(5), DecodedData::Boolean(true)]);
+
+    // Missing columns get their default value, and the extra ones are reported.
+    let (entries, unknown_columns) = import("rpfm_test_import_tsv_missing.tsv", b"test_tables\t3\nkey\textra_1\textra_2\nfirst\ta\tb\n");
+    assert_eq!(entries, vec![vec![DecodedData::StringU8("first".to_owned()), DecodedData::Integer(0), DecodedData::Boolean(false)]]);
+    assert_eq!(unknown_columns, vec!["extra_1".to_owned(), "extra_2".to_owned()]);
+}
+
+/// Test to make sure new rows (`get_default_row()`) get the default values of the schema, falling back to the defaults
+/// of their type when a field has no default value, or it's not valid for his type.
+#[test]
+fn test_get_default_row() {
+    let mut definition = TableDefinition::new(1);
+    definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, "".to_owned()));
+    definition.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, "".to_owned()));
+    definition.fields.push(Field::new("speed".to_owned(), FieldType::Float, false, None, "".to_owned()));
+    definition.fields.push(Field::new("enabled".to_owned(), FieldType::Boolean, false, None, "".to_owned()));
+    definition.fields.push(Field::new("description".to_owned(), FieldType::OptionalStringU8, false, None, "".to_owned()));
+    assert_eq!(get_default_row(&definition), vec![
+        DecodedData::StringU8(String::new()),
+        DecodedData::Integer(0),
+        DecodedData::Float(0.0),
+        DecodedData::Boolean(false),
+        DecodedData::OptionalStringU8(String::new()),
+    ]);
+
+    definition.fields[1].field_default_value = Some("100".to_owned());
+    definition.fields[2].field_default_value = Some("fast".to_owned());
+    definition.fields[3].field_default_value = Some("true".to_owned());
+    definition.fields[4].field_default_value = Some("placeholder".to_owned());
+    assert_eq!(get_default_row(&definition), vec![
+        DecodedData::StringU8(String::new()),
+        DecodedData::Integer(100),
+        DecodedData::Float(0.0),
+        DecodedData::Boolean(true),
+        DecodedData::OptionalStringU8("placeholder".to_owned()),
+    ]);
+}
+
+/// Test to make sure the TSV importer (`import_tsv()`) skips the rows with errors, and reports every bad cell of them.
+/// Rows missing trailing cells are not errors: they get the default values of their fields.
+#[test]
+fn test_import_tsv_with_errors() {
+
+    let mut definition = TableDefinition::new(3);
+    definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, "".to_owned()));
+    definition.fields.push(Field::new("value".to_owned(), FieldType::Integer, false, None, "".to_owned()));
+    definition.fields.push(Field::new("enabled".to_owned(), FieldType::Boolean, false, None, "".to_owned()));
+    definition.fields[2].field_default_value = Some("true".to_owned());
+
+    let path = temp_dir().join("rpfm_test_import_tsv_with_errors.tsv");
+    File::create(&path).unwrap().write_all(b"test_tables\t3\nkey\tvalue\tenabled\nfirst\t5\ttrue\nsecond\tfive\tmaybe\nthird\t7\nfourth\t8\tfalse\tfifth\n").unwrap();
+    let (entries, _, errors) = import_tsv(&definition, &path, "test_tables", 3).unwrap();
+    remove_file(&path).unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1], vec![DecodedData::StringU8("third".to_owned()), DecodedData::Integer(7), DecodedData::Boolean(true)]);
+    assert_eq!(errors, vec![
+        TSVError::InvalidCell(3, 1, FieldType::Integer),
+        TSVError::InvalidCell(3, 2, FieldType::Boolean),
+        TSVError::InvalidRowLength(5, 4),
+    ]);
+    assert_eq!(errors[0].to_string(), "Row 4, column 2: the value is not a valid Integer.");
+}
+
+/// Test to make sure CSVs (`export_delimited()` and `import_delimited()`) keep values with delimiters and newlines on round-trip,
+/// and that we refuse to export them without quotes.
+#[test]
+fn test_export_delimited_round_trip() {
+
+    let mut definition = TableDefinition::new(1);
+    definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, "".to_owned()));
+    definition.fields.push(Field::new("text".to_owned(), FieldType::StringU16, false, None, "".to_owned()));
+    definition.fields.push(Field::new("tooltip".to_owned(), FieldType::Boolean, false, None, "".to_owned()));
+
+    let entries = vec![
+        vec![DecodedData::StringU8("first".to_owned()), DecodedData::StringU16("one; two\nthree".to_owned()), DecodedData::Boolean(true)],
+        vec![DecodedData::StringU8("second".to_owned()), DecodedData::StringU16("\"quoted\"".to_owned()), DecodedData::Boolean(false)],
+    ];
+    let headers = definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
+
+    let path = temp_dir().join("rpfm_test_export_delimited_round_trip.csv");
+    for quoting in &[DelimitedQuoting::Minimal, DelimitedQuoting::Always] {
+        export_delimited(&entries, &path, &headers, b';', *quoting).unwrap();
+        let (imported, unknown_columns, errors) = import_delimited(&definition, &path, b';', *quoting).unwrap();
+        assert_eq!(imported, entries);
+        assert!(unknown_columns.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    let error = export_delimited(&entries, &path, &headers, b';', DelimitedQuoting::Never).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ExportDelimitedUnquotedValue(0, 1));
+    let _ = remove_file(&path);
+}
+
+//-----------------------------------------------------//
+//                    Table Views
+//-----------------------------------------------------//
+
+/// Test to make sure the Markdown tables (`to_markdown_table()`) escape the characters that would break them.
+#[test]
+fn test_to_markdown_table() {
+    let headers = vec!["key".to_owned(), "text".to_owned()];
+    let rows = vec![
+        vec!["key_1".to_owned(), "Sword | Shield".to_owned()],
+        vec!["key_2".to_owned(), "Line 1\nLine 2".to_owned()],
+    ];
+
+    assert_eq!(to_markdown_table(&headers, &rows), "| key | text |\n| --- | --- |\n| key_1 | Sword \\| Shield |\n| key_2 | Line 1<br>Line 2 |\n");
+}
+
+/// Test to make sure the clipboard parser (`parse_clipboard_tsv()`) keeps quoted cells with tabs and newlines in one piece,
+/// as Excel and LibreOffice copy them, and that our quoted copies (`quote_clipboard_cell()`) paste back without changes.
+#[test]
+fn test_parse_clipboard_tsv() {
+    let text = "key_1\t\"Line 1\r\nLine 2\"\r\nkey_2\t\"Say \"\"hi\"\"\tnow\"\r\n";
+    assert_eq!(parse_clipboard_tsv(text), vec![
+        vec!["key_1".to_owned(), "Line 1\r\nLine 2".to_owned()],
+        vec!["key_2".to_owned(), "Say \"hi\"\tnow".to_owned()],
+    ]);
+
+    assert_eq!(parse_clipboard_tsv("a\t\tb\n"), vec![vec!["a".to_owned(), "".to_owned(), "b".to_owned()]]);
+    assert_eq!(parse_clipboard_tsv("He said \"no\""), vec![vec!["He said \"no\"".to_owned()]]);
+
+    let cells = vec!["plain".to_owned(), "multi\nline".to_owned(), "\"quoted\" start".to_owned(), "tab\there".to_owned()];
+    let copy = cells.iter().map(|x| quote_clipboard_cell(x)).collect::<Vec<String>>().join("\t");
+    assert_eq!(parse_clipboard_tsv(&copy), vec![cells]);
+}
+
+/// Test to make sure the copies with headers (`to_tsv_with_headers()`) put the headers in the first line.
+#[test]
+fn test_to_tsv_with_headers() {
+    let headers = vec!["key".to_owned(), "value".to_owned()];
+    let rows = vec![
+        vec!["key_1".to_owned(), "1".to_owned()],
+        vec!["key_2".to_owned(), "".to_owned()],
+    ];
+
+    assert_eq!(to_tsv_with_headers(&headers, &rows), "key\tvalue\nkey_1\t1\nkey_2\t");
+}
+
+/// Test to make sure the JSON copies (`to_json_objects()`) keep the type of every value, and skip the unselected cells.
+#[test]
+fn test_to_json_objects() {
+    let key = Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new());
+    let value = Field::new("value".to_owned(), FieldType::Float, false, None, String::new());
+    let enabled = Field::new("enabled".to_owned(), FieldType::Boolean, false, None, String::new());
+    let fields = vec![&key, &value, &enabled];
+    let rows = vec![
+        vec![Some("say \"hi\"".to_owned()), Some("1.5".to_owned()), Some("true".to_owned())],
+        vec![None, Some("0.25".to_owned()), Some("false".to_owned())],
+        vec![Some("key_3".to_owned()), None, None],
+    ];
+
+    let objects: serde_json::Value = serde_json::from_str(&to_json_objects(&fields, &rows)).unwrap();
+    assert_eq!(objects, serde_json::json!([
+        { "key": "say \"hi\"", "value": 1.5, "enabled": true },
+        { "value": 0.25, "enabled": false },
+        { "key": "key_3" },
+    ]));
+
+    // The members have to be in the same order as the columns, not sorted by name.
+    let fields = vec![&value, &key];
+    let rows = vec![vec![Some("1.5".to_owned()), Some("key_1".to_owned())]];
+    assert_eq!(to_json_objects(&fields, &rows), "[\n  {\n    \"value\": 1.5,\n    \"key\": \"key_1\"\n  }\n]");
+}
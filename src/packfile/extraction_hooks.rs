@@ -0,0 +1,102 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// In this file are the hooks we run over the files extracted from a PackFile, for games that need their files converted after extracting them.
+
+use std::collections::BTreeMap;
+use std::fs::{File, read};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::Result;
+use crate::packedfile::{DecodeablePackedFileType, UTF16_LE_BOM, get_length_without_trailing_nulls, get_packed_file_type};
+
+/// This trait represents a transformation done over the extracted files it matches.
+pub trait ExtractionHook {
+
+    /// This function returns the name of the hook, used when reporting what it did.
+    fn get_name(&self) -> &str;
+
+    /// This function returns true if the hook has to be run over the PackedFile with the provided path.
+    fn matches(&self, path: &[String]) -> bool;
+
+    /// This function transforms the data of an extracted file. If there is nothing to change, it returns None.
+    fn transform(&self, data: &[u8]) -> Result<Option<Vec<u8>>>;
+}
+
+/// This struct holds the hooks registered for each game, using their `folder_name` as key.
+pub struct ExtractionHooks {
+    hooks: BTreeMap<String, Vec<Box<dyn ExtractionHook>>>,
+}
+
+/// This hook removes the trailing null bytes from the end of the extracted Text PackedFiles, as they show up as garbage in most text editors.
+/// UTF-16 Text PackedFiles are not touched, as their nulls are part of their characters.
+pub struct TrimTrailingNullsHook;
+
+/// Implementation of `ExtractionHooks`.
+impl ExtractionHooks {
+
+    /// This function creates a new registry, with the `TrimTrailingNullsHook` registered only for the provided games (by `folder_name`).
+    pub fn new(trim_nulls_games: &[String]) -> Self {
+        let mut extraction_hooks = Self::new_empty();
+        for game in trim_nulls_games {
+            extraction_hooks.register(game, Box::new(TrimTrailingNullsHook));
+        }
+        extraction_hooks
+    }
+
+    /// This function creates a new registry without hooks.
+    pub fn new_empty() -> Self {
+        Self {
+            hooks: BTreeMap::new(),
+        }
+    }
+
+    /// This function registers a new hook for the provided game. Hooks are run in the same order they're registered.
+    pub fn register(&mut self, game: &str, hook: Box<dyn ExtractionHook>) {
+        self.hooks.entry(game.to_owned()).or_insert_with(|| vec![]).push(hook);
+    }
+
+    /// This function runs the hooks of the provided game over the extracted files. Each file comes with the path it had in the PackFile.
+    ///
+    /// It returns a line of report for each file changed by a hook.
+    pub fn run(&self, game: &str, extracted_files: &[(Vec<String>, PathBuf)]) -> Result<Vec<String>> {
+        let mut report = vec![];
+        if let Some(hooks) = self.hooks.get(game) {
+            for (path, extracted_path) in extracted_files {
+                for hook in hooks {
+                    if hook.matches(path) {
+                        if let Some(data) = hook.transform(&read(extracted_path)?)? {
+                            File::create(extracted_path)?.write_all(&data)?;
+                            report.push(format!("{}: {}", hook.get_name(), path.join("/")));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Implementation of `ExtractionHook` for `TrimTrailingNullsHook`.
+impl ExtractionHook for TrimTrailingNullsHook {
+    fn get_name(&self) -> &str {
+        "Trim Trailing Nulls"
+    }
+
+    fn matches(&self, path: &[String]) -> bool {
+        if let DecodeablePackedFileType::Text = get_packed_file_type(path) { true } else { false }
+    }
+
+    fn transform(&self, data: &[u8]) -> Result<Option<Vec<u8>>> {
+        if data.starts_with(&UTF16_LE_BOM) { Ok(None) }
+        else { Ok(get_length_without_trailing_nulls(data).map(|x| data[..x].to_vec())) }
+    }
+}
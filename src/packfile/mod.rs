@@ -11,11 +11,13 @@
 // In this file are all the Structs and Impls required to decode and encode the PackFiles.
 
 use bitflags::bitflags;
+use serde_derive::{Serialize, Deserialize};
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::io::prelude::*;
 use std::io::{ BufReader, BufWriter, Read, Write, SeekFrom };
-use std::fs::File;
+use std::fs::{File, remove_file, rename};
 use std::sync::{Arc, Mutex};
 
 use crate::common::*;
@@ -28,12 +30,20 @@ use crate::ui::packfile_treeview::TreePathType;
 
 mod compression;
 mod crypto;
+pub mod extraction_hooks;
 pub mod packedfile;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 /// These consts are used for dealing with Time-related operations.
 const WINDOWS_TICK: i64 = 10_000_000;
 const SEC_TO_UNIX_EPOCH: i64 = 11_644_473_600;
 
+/// Size of the buffer used to copy the data of the PackedFiles not loaded to memory when saving.
+const SAVE_BUFFER_SIZE: usize = 64 * 1024;
+
 /// These are the different Preamble/Id the PackFiles can have.
 const PFH5_PREAMBLE: &str = "PFH5"; // PFH5
 const PFH4_PREAMBLE: &str = "PFH4"; // PFH4
@@ -46,6 +56,10 @@ const FILE_TYPE_RELEASE: u32 = 1;
 const FILE_TYPE_PATCH: u32 = 2;
 const FILE_TYPE_MOD: u32 = 3;
 const FILE_TYPE_MOVIE: u32 = 4;
+
+/// This is the name of the reserved PackedFile where we store the table aliases of the PackFile.
+const TABLE_ALIASES_PACKED_FILE: &str = "frodos_biggest_secret.rpfm-table-aliases";
+
 bitflags! {
 
     /// This represents the bitmasks a PackFile can have applied to his type.
@@ -99,7 +113,7 @@ pub enum PFHFileType {
 
 /// This `Enum` is the background equivalent to the `TreePathType` Enum from the UI.
 /// We keep them separated so we have a version of it to use to only background stuff.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PathType {
     File(Vec<String>),
     Folder(Vec<String>),
@@ -130,7 +144,9 @@ pub enum CompressionState {
 ///
 /// And about the custom stuff (exclusive of RPFM).
 /// - `notes`: a String to store all the notes you have on the same Packfile.
-#[derive(Debug)]
+/// - `table_aliases`: a map of table folders (`my_custom_units`) to the table definitions used to decode them (`units_tables`).
+/// - `deleted_packed_files`: the PackedFiles deleted from this PackFile since it was open, in the order they were deleted. Not saved.
+#[derive(Clone, Debug)]
 pub struct PackFile {
     pub file_path: PathBuf,
     pub pfh_version: PFHVersion,
@@ -143,6 +159,8 @@ pub struct PackFile {
 
     // Custom Stuff goes here.
     pub notes: Option<String>,
+    pub table_aliases: BTreeMap<String, String>,
+    pub deleted_packed_files: Vec<PackedFile>,
 }
 
 /// This `Struct` is a reduced version of the `PackFile` Struct, used to pass data to the UI.
@@ -237,7 +255,9 @@ impl PackFile {
             pack_files: vec![],
             packed_files: vec![],
 
-            notes: None
+            notes: None,
+            table_aliases: BTreeMap::new(),
+            deleted_packed_files: vec![],
         }
     }
 
@@ -256,6 +276,8 @@ impl PackFile {
             packed_files: vec![],
 
             notes: None,
+            table_aliases: BTreeMap::new(),
+            deleted_packed_files: vec![],
         }
     }
 
@@ -357,6 +379,15 @@ impl PackFile {
         else { false }
     }
 
+    /// This function returns the name of the table definition we have to use to decode the tables in the provided
+    /// table folder. That's the one in the table aliases if the folder has an alias, or the folder itself if it doesn't.
+    pub fn get_table_definition_name<'a>(&'a self, table_folder: &'a str) -> &'a str {
+        match self.table_aliases.get(table_folder) {
+            Some(table_definition_name) => table_definition_name,
+            None => table_folder,
+        }
+    }
+
     /// This function returns a list of reserved PackedFile names, used by RPFM to keep
     /// some per-PackFile data stored inside the PackFiles.
     ///
@@ -364,6 +395,7 @@ impl PackFile {
     pub fn get_reserved_packed_file_list() -> Vec<Vec<String>> {
         let mut packed_file_list = vec![];
         packed_file_list.push(vec!["frodos_biggest_secret.rpfm-notes".to_owned()]);    // This one is the notes file.
+        packed_file_list.push(vec![TABLE_ALIASES_PACKED_FILE.to_owned()]);              // This one is the table aliases file.
         packed_file_list
     }
 
@@ -385,12 +417,56 @@ impl PackFile {
         self.packed_files.iter_mut().for_each(|x| x.should_be_compressed = enable);
     }
 
-    /// This function remove all PackedFiles from a PackFile.
+    /// This function removes a PackedFile from a PackFile, keeping a copy of it in the deleted PackedFiles history,
+    /// so it can be restored later with `restore_deleted_packedfile()`. His data is loaded first, so the copy stays
+    /// valid even if the PackFile on disk gets overwritten.
     ///
     /// It requires:
     /// - `&mut self`: the PackFile we are going to manipulate.
-    pub fn remove_all_packedfiles(&mut self) {
-        self.packed_files = vec![];
+    /// - `index`: the index of the PackedFile we want to delete from the PackFile.
+    pub fn delete_packedfile(&mut self, index: usize) {
+        let mut packed_file = self.packed_files.remove(index);
+        if packed_file.load_data().is_ok() { self.deleted_packed_files.push(packed_file); }
+    }
+
+    /// This function restores the last deleted PackedFile with the provided path from the deleted PackedFiles history,
+    /// without undoing anything done after deleting it. If there is already a PackedFile in that path, it's only
+    /// replaced if `overwrite` is true.
+    ///
+    /// It requires:
+    /// - `&mut self`: the PackFile we are going to manipulate.
+    /// - `path`: the path of the PackedFile we want to restore.
+    /// - `overwrite`: if we should replace the PackedFile currently in that path, if any.
+    pub fn restore_deleted_packedfile(&mut self, path: &[String], overwrite: bool) -> Result<()> {
+        let index = match self.deleted_packed_files.iter().rposition(|x| x.path == path) {
+            Some(index) => index,
+            None => Err(ErrorKind::PackedFileNotFound)?,
+        };
+
+        if !overwrite && self.packedfile_exists(path) { Err(ErrorKind::FileAlreadyInPackFile)? }
+        let packed_file = self.deleted_packed_files.remove(index);
+        self.add_packed_files(&[packed_file]);
+        Ok(())
+    }
+
+    /// This function replaces the data of the PackedFile with the provided path with the data it has in the PackFile on disk,
+    /// discarding any change done to it since the PackFile was last saved.
+    ///
+    /// It requires:
+    /// - `&mut self`: the PackFile we are going to manipulate.
+    /// - `path`: the path of the PackedFile we want to reload.
+    pub fn reload_packedfile_from_disk(&mut self, path: &[String]) -> Result<()> {
+        let pack_file_on_disk = Self::read(self.file_path.to_path_buf(), true)?;
+        let data = match pack_file_on_disk.packed_files.iter().find(|x| x.path == path) {
+            Some(packed_file) => packed_file.get_data()?,
+            None => Err(ErrorKind::PackedFileNotFound)?,
+        };
+
+        match self.packed_files.iter_mut().find(|x| x.path == path) {
+            Some(packed_file) => packed_file.set_data(data),
+            None => Err(ErrorKind::PackedFileNotFound)?,
+        }
+        Ok(())
     }
 
     /// This function checks if a `PackedFile` exists in a `PackFile`.
@@ -427,6 +503,19 @@ impl PackFile {
         }
     }
 
+    /// This function returns the groups of `PackedFiles` whose paths are the same if we ignore the case, as they
+    /// collide when extracted on case-insensitive filesystems.
+    ///
+    /// Each group contains the paths of the colliding `PackedFiles`, in the same order they are in the `PackFile`.
+    pub fn get_case_collisions(&self) -> Vec<Vec<Vec<String>>> {
+        let mut paths: BTreeMap<Vec<String>, Vec<Vec<String>>> = BTreeMap::new();
+        for packed_file in &self.packed_files {
+            let path_lowercase = packed_file.path.iter().map(|x| x.to_lowercase()).collect::<Vec<String>>();
+            paths.entry(path_lowercase).or_insert_with(|| vec![]).push(packed_file.path.to_vec());
+        }
+        paths.into_iter().map(|(_, paths)| paths).filter(|x| x.len() > 1).collect()
+    }
+
     /// This function reads the content of a PackFile and returns a `PackFile` with all the contents of the PackFile decoded.
     ///
     /// It requires:
@@ -608,6 +697,15 @@ impl PackFile {
                     }
                 }
             }
+
+            // Same with the table aliases PackedFile.
+            else if packed_file.path == &[TABLE_ALIASES_PACKED_FILE] {
+                if let Ok(data) = packed_file.get_data() {
+                    if let Ok(table_aliases) = serde_json::from_slice(&data) {
+                        pack_file_decoded.table_aliases = table_aliases;
+                    }
+                }
+            }
             else {
                 pack_file_decoded.packed_files.push(packed_file);
             }
@@ -648,14 +746,20 @@ impl PackFile {
             self.packed_files.push(PackedFile::read_from_vec(vec!["frodos_biggest_secret.rpfm-notes".to_owned()], 0, false, encode_string_u8(&data)));
         }
 
+        // And the one for the table aliases.
+        if !self.table_aliases.is_empty() {
+            self.packed_files.push(PackedFile::read_from_vec(vec![TABLE_ALIASES_PACKED_FILE.to_owned()], 0, false, serde_json::to_vec(&self.table_aliases)?));
+        }
+
         // For some bizarre reason, if the PackedFiles are not alphabetically sorted they may or may not crash the game for particular people.
         // So, to fix it, we have to sort all the PackedFiles here by path.
         // NOTE: This sorting has to be CASE INSENSITIVE. This means for "ac", "Ab" and "aa" it'll be "aa", "Ab", "ac".
         self.packed_files.sort_unstable_by(|a, b| a.path.join("\\").to_lowercase().cmp(&b.path.join("\\").to_lowercase()));
         
-        // We ensure that all the data is loaded and in his right form (compressed/encrypted) before attempting to save.
-        // We need to do this here because we need later on their compressed size.
-        for packed_file in &mut self.packed_files { 
+        // We ensure that all the data is in his right form (compressed/encrypted) before attempting to save. We need to do this here
+        // because we need later on their compressed size. Only the PackedFiles that need to change are loaded to memory for this.
+        for packed_file in &mut self.packed_files {
+            if packed_file.get_compression_state() == packed_file.should_be_compressed && packed_file.get_encryption_state().is_none() { continue; }
             packed_file.load_data()?;
 
             // Remember: first compress (only PFH5), then encrypt.
@@ -679,9 +783,10 @@ impl PackFile {
             }
         }
 
-        // First we encode the indexes and the data (just in case we compressed it).
-        let mut pack_file_index = vec![];
-        let mut packed_file_index = vec![];
+        // First we encode the indexes and the data (just in case we compressed it). Both indexes are allocated at once
+        // with their full size (or a bit more), as they can get quite big in PackFiles with a lot of PackedFiles.
+        let mut pack_file_index = Vec::with_capacity(self.pack_files.iter().map(|x| x.len() + 1).sum());
+        let mut packed_file_index = Vec::with_capacity(self.packed_files.iter().map(|x| 13 + x.path.iter().map(|x| x.len() + 1).sum::<usize>()).sum());
 
         for pack_file in &self.pack_files {
             pack_file_index.extend_from_slice(pack_file.as_bytes());
@@ -709,43 +814,96 @@ impl PackFile {
                 PFHVersion::PFH0 => {}
             }
 
-            packed_file_index.append(&mut packed_file.path.join("\\").as_bytes().to_vec());
+            packed_file_index.extend_from_slice(packed_file.path.join("\\").as_bytes());
             packed_file_index.push(0);
         }
 
-        // Create the file to save to, and save the header and the indexes.
-        let mut file = BufWriter::new(File::create(&self.file_path)?);
+        // Update the creation time, so it gets saved in the header.
+        self.timestamp = get_current_time();
+
+        // We save to a temporal file next to the real one, and replace the real one with it at the end, as the data of the PackedFiles
+        // not loaded to memory may be in the file we're replacing. If anything fails, the temporal file is removed.
+        let mut temp_path = self.file_path.clone().into_os_string();
+        temp_path.push(".temp");
+        let temp_path = PathBuf::from(temp_path);
+        let data_positions = match self.write_to_file(&temp_path, &pack_file_index, &packed_file_index) {
+            Ok(data_positions) => data_positions,
+            Err(error) => {
+                let _ = remove_file(&temp_path);
+                return Err(error);
+            }
+        };
+
+        // Make the PackedFiles not loaded to memory read their data from the temporal file before replacing the real one, so they no
+        // longer keep the real one open. Otherwise, the replacement fails on Windows. Once it's replaced, the handle follows the file.
+        let new_file = match File::open(&temp_path) {
+            Ok(file) => Arc::new(Mutex::new(BufReader::new(file))),
+            Err(error) => {
+                let _ = remove_file(&temp_path);
+                return Err(error.into());
+            }
+        };
 
-        // Write the entire header.
+        for (packed_file, data_position) in self.packed_files.iter_mut().zip(data_positions.iter()) {
+            packed_file.move_data_on_disk(&new_file, *data_position);
+        }
+        drop(new_file);
+
+        // If we cannot replace it, load to memory the data that's in the temporal file, so we can remove it.
+        if let Err(error) = rename(&temp_path, &self.file_path) {
+            for packed_file in &mut self.packed_files { packed_file.load_data()?; }
+            let _ = remove_file(&temp_path);
+            return Err(error.into());
+        }
+
+        // Remove again the notes PackedFile.
+        if let Some(pos) = self.packed_files.iter().position(|x| x.path == vec!["frodos_biggest_secret.rpfm-notes".to_owned()]) {
+            self.remove_packedfile(pos);
+        }
+
+        // And the table aliases one.
+        if let Some(pos) = self.packed_files.iter().position(|x| x.path == vec![TABLE_ALIASES_PACKED_FILE.to_owned()]) {
+            self.remove_packedfile(pos);
+        }
+
+        // If nothing has failed, return success.
+        Ok(())
+    }
+
+    /// This function writes the header, the provided indexes and the data of the PackedFiles of this `PackFile` to the provided path,
+    /// returning the position in the file where the data of each PackedFile starts.
+    ///
+    /// The data is streamed into the file, one PackedFile at a time, so we never have the data of the entire PackFile in memory.
+    /// The data not loaded to memory is copied through a single buffer, reused for every PackedFile.
+    fn write_to_file(&self, path: &Path, pack_file_index: &[u8], packed_file_index: &[u8]) -> Result<Vec<u64>> {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        // Write the entire header. PFH0 files don't have timestamp in the headers.
         file.write_all(&encode_string_u8(&self.pfh_version.get_value()))?;
         file.write_all(&encode_integer_u32(self.bitmask.bits | self.pfh_file_type.get_value()))?;
         file.write_all(&encode_integer_u32(self.pack_files.len() as u32))?;
         file.write_all(&encode_integer_u32(pack_file_index.len() as u32))?;
         file.write_all(&encode_integer_u32(self.packed_files.len() as u32))?;
         file.write_all(&encode_integer_u32(packed_file_index.len() as u32))?;
-
-        // Update the creation time, then save it. PFH0 files don't have timestamp in the headers.
-        self.timestamp = get_current_time();
         match self.pfh_version {
             PFHVersion::PFH5 | PFHVersion::PFH4 => file.write_all(&encode_integer_u32(self.timestamp as u32))?,
             PFHVersion::PFH3 => file.write_all(&encode_integer_i64((self.timestamp + SEC_TO_UNIX_EPOCH) * WINDOWS_TICK))?,
             PFHVersion::PFH0 => {}
         };
 
-        // Write the indexes and the data of the PackedFiles. No need to keep the data, as it has been preloaded before.
-        file.write_all(&pack_file_index)?;
-        file.write_all(&packed_file_index)?;
-        for packed_file in &mut self.packed_files { 
-            let (data,_,_,_,_) = packed_file.get_data_and_info_from_memory()?;
-            file.write_all(&data)?;
-        }
-
-        // Remove again the notes PackedFile.
-        if let Some(pos) = self.packed_files.iter().position(|x| x.path == vec!["frodos_biggest_secret.rpfm-notes".to_owned()]) {
-            self.remove_packedfile(pos);
+        // Write the indexes and the data of the PackedFiles.
+        file.write_all(pack_file_index)?;
+        file.write_all(packed_file_index)?;
+        let mut buffer = vec![0; SAVE_BUFFER_SIZE];
+        let mut data_positions = Vec::with_capacity(self.packed_files.len());
+        let mut data_position = file.seek(SeekFrom::Current(0))?;
+        for packed_file in &self.packed_files {
+            packed_file.write_raw_data(&mut file, &mut buffer)?;
+            data_positions.push(data_position);
+            data_position += u64::from(packed_file.get_size());
         }
 
-        // If nothing has failed, return success.
-        Ok(())
+        file.flush()?;
+        Ok(data_positions)
     }
 }
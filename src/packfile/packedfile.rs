@@ -38,11 +38,12 @@ pub struct PackedFile {
 /// This enum represents the data of a PackedFile.
 ///
 /// - `OnMemory`: the data is loaded to memory and the variant holds the data and info about the current state of the data (is_compressed, is_encrypted).
+///   The data is shared between clones of the PackedFile until one of them changes it, so cloning a PackFile doesn't copy the data of all his PackedFiles.
 /// - `OnDisk`: the data is not loaded to memory and the variant holds the file, position and size of the data on the disk, and info about the current 
 ///   state of the data (is_compressed, is_encrypted).
 #[derive(Clone, Debug)]
 pub enum PackedFileData {
-    OnMemory(Vec<u8>, bool, Option<PFHVersion>),
+    OnMemory(Arc<Vec<u8>>, bool, Option<PFHVersion>),
     OnDisk(Arc<Mutex<BufReader<File>>>, u64, u32, bool, Option<PFHVersion>),
 } 
 
@@ -56,7 +57,7 @@ impl PackedFile {
             timestamp,
             should_be_compressed,
             should_be_encrypted: None,
-            data: PackedFileData::OnMemory(data, should_be_compressed, None),
+            data: PackedFileData::OnMemory(Arc::new(data), should_be_compressed, None),
         }
     }
 
@@ -78,7 +79,7 @@ impl PackedFile {
             let mut data = vec![0; size as usize];
             file.lock().unwrap().seek(SeekFrom::Start(position))?;
             file.lock().unwrap().read_exact(&mut data)?;
-            PackedFileData::OnMemory(data, is_compressed, is_encrypted)
+            PackedFileData::OnMemory(Arc::new(data), is_compressed, is_encrypted)
         } else { return Ok(()) };
         
         self.data = data_on_memory;
@@ -109,8 +110,8 @@ impl PackedFile {
     pub fn get_data_and_keep_it(&mut self) -> Result<Vec<u8>> {
         let data = match self.data {
             PackedFileData::OnMemory(ref mut data, ref mut is_compressed, ref mut is_encrypted) => {
-                if is_encrypted.is_some() { *data = decrypt_packed_file(&data); }
-                if *is_compressed { *data = decompress_data(&data)?; }
+                if is_encrypted.is_some() { *data = Arc::new(decrypt_packed_file(&data)); }
+                if *is_compressed { *data = Arc::new(decompress_data(&data)?); }
                 *is_compressed = false;
                 *is_encrypted = None;
                 return Ok(data.to_vec())
@@ -125,7 +126,7 @@ impl PackedFile {
             }
         };
 
-        self.data = PackedFileData::OnMemory(data.to_vec(), false, None);
+        self.data = PackedFileData::OnMemory(Arc::new(data.to_vec()), false, None);
         Ok(data)
     }
 
@@ -134,7 +135,7 @@ impl PackedFile {
     pub fn get_data_and_info_from_memory(&mut self) -> Result<(&mut Vec<u8>, &mut bool, &mut Option<PFHVersion>, &mut bool, &mut Option<PFHVersion>)> {
         match self.data {
             PackedFileData::OnMemory(ref mut data, ref mut is_compressed, ref mut is_encrypted) => {
-                Ok((data, is_compressed, is_encrypted, &mut self.should_be_compressed, &mut self.should_be_encrypted))
+                Ok((Arc::make_mut(data), is_compressed, is_encrypted, &mut self.should_be_compressed, &mut self.should_be_encrypted))
             },
             PackedFileData::OnDisk(_, _, _, _, _) => {
                 Err(ErrorKind::PackedFileDataIsNotInMemory)?
@@ -144,7 +145,7 @@ impl PackedFile {
 
     /// This function loads the data from the disk if it's not loaded yet.
     pub fn set_data(&mut self, data: Vec<u8>) {
-        self.data = PackedFileData::OnMemory(data, false, None);
+        self.data = PackedFileData::OnMemory(Arc::new(data), false, None);
     }
 
     /// This function returns the size of the data of the PackedFile.
@@ -162,4 +163,42 @@ impl PackedFile {
             PackedFileData::OnDisk(_, _, _, state, _) => state,
         }
     }
+
+    /// This function returns the encryption state of a PackedFile.
+    pub fn get_encryption_state(&self) -> Option<PFHVersion> {
+        match self.data {
+            PackedFileData::OnMemory(_, _, state) => state,
+            PackedFileData::OnDisk(_, _, _, _, state) => state,
+        }
+    }
+
+    /// This function writes the data of the PackedFile, as it's stored (compressed and/or encrypted), into the provided writer.
+    /// If the data is on disk, it's copied to the writer in chunks through the provided buffer, so it's never entirely loaded to memory.
+    pub fn write_raw_data<W: Write>(&self, writer: &mut W, buffer: &mut [u8]) -> Result<()> {
+        match self.data {
+            PackedFileData::OnMemory(ref data, _, _) => writer.write_all(data)?,
+            PackedFileData::OnDisk(ref file, position, size, _, _) => {
+                let mut file = file.lock().unwrap();
+                file.seek(SeekFrom::Start(position))?;
+
+                let mut remaining = size as usize;
+                while remaining > 0 {
+                    let chunk_size = remaining.min(buffer.len());
+                    file.read_exact(&mut buffer[..chunk_size])?;
+                    writer.write_all(&buffer[..chunk_size])?;
+                    remaining -= chunk_size;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// This function makes a PackedFile with his data on disk read it from the provided file and position, like when
+    /// his PackFile has been saved to another file. PackedFiles with their data in memory are left as they are.
+    pub fn move_data_on_disk(&mut self, new_file: &Arc<Mutex<BufReader<File>>>, new_position: u64) {
+        if let PackedFileData::OnDisk(ref mut file, ref mut position, _, _, _) = self.data {
+            *file = new_file.clone();
+            *position = new_position;
+        }
+    }
 }
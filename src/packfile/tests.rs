@@ -0,0 +1,213 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the PackFile logic functions, like the ones to load, save, restore or extract his PackedFiles.
+
+use crate::background_thread_extra::{delete_from_packfile, extract_from_packfile};
+use crate::common::tests::new_table_definitions_with_references;
+use crate::error::Result;
+use crate::packedfile::DecodedData;
+use crate::packedfile::db::DB;
+use crate::packfile::{PackFile, PathType, PFHVersion};
+use crate::packfile::extraction_hooks::{ExtractionHook, ExtractionHooks, TrimTrailingNullsHook};
+use crate::packfile::packedfile::PackedFile;
+use crate::schema::Schema;
+
+use std::env::temp_dir;
+use std::fs::{File, create_dir_all, read, read_to_string, remove_dir_all, remove_file};
+use std::io::Write;
+
+/// Hook that matches every file, but never changes anything.
+struct NoOpHook;
+impl ExtractionHook for NoOpHook {
+    fn get_name(&self) -> &str { "No-Op" }
+    fn matches(&self, _path: &[String]) -> bool { true }
+    fn transform(&self, _data: &[u8]) -> Result<Option<Vec<u8>>> { Ok(None) }
+}
+
+/// Hook that turns the contents of the Lua files into uppercase.
+struct UppercaseLuaHook;
+impl ExtractionHook for UppercaseLuaHook {
+    fn get_name(&self) -> &str { "Uppercase Lua" }
+    fn matches(&self, path: &[String]) -> bool { path.last().map_or(false, |x| x.ends_with(".lua")) }
+    fn transform(&self, data: &[u8]) -> Result<Option<Vec<u8>>> { Ok(Some(data.to_ascii_uppercase())) }
+}
+
+/// Test to make sure tables in an aliased folder (`get_table_definition_name()`) get decoded with the definition of the aliased table.
+#[test]
+fn test_decode_aliased_table() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut db = DB::new("units_tables", 1, table_definitions.versions[0].clone());
+    db.entries.push(vec![DecodedData::StringU8("unit_1".to_owned())]);
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["db".to_owned(), "my_custom_units".to_owned(), "test".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, db.save())]);
+
+    // Without the alias, there is no definition for the table.
+    let data = pack_file.packed_files[0].get_data().unwrap();
+    assert!(DB::read(&data, pack_file.get_table_definition_name(&path[1]), &schema).is_err());
+
+    // With it, it gets decoded as an "units_tables" table.
+    pack_file.table_aliases.insert("my_custom_units".to_owned(), "units_tables".to_owned());
+    let decoded = DB::read(&data, pack_file.get_table_definition_name(&path[1]), &schema).unwrap();
+    assert_eq!(decoded.db_type, "units_tables");
+    assert_eq!(decoded.entries, db.entries);
+}
+
+/// Test to make sure restoring a deleted PackedFile (`restore_deleted_packedfile()`) only restores that PackedFile,
+/// keeping everything done after deleting it.
+#[test]
+fn test_restore_deleted_packedfile() {
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path_1 = vec!["script".to_owned(), "test_1.lua".to_owned()];
+    let path_2 = vec!["script".to_owned(), "test_2.lua".to_owned()];
+    let path_3 = vec!["script".to_owned(), "test_3.lua".to_owned()];
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(path_1.to_vec(), 0, false, vec![1]),
+        PackedFile::read_from_vec(path_2.to_vec(), 0, false, vec![2]),
+    ]);
+
+    delete_from_packfile(&mut pack_file, &[PathType::File(path_1.to_vec()), PathType::File(path_2.to_vec())]);
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path_3.to_vec(), 0, false, vec![3])]);
+
+    pack_file.restore_deleted_packedfile(&path_1, false).unwrap();
+    assert!(pack_file.packedfile_exists(&path_1));
+    assert!(!pack_file.packedfile_exists(&path_2));
+    assert!(pack_file.packedfile_exists(&path_3));
+    assert_eq!(pack_file.deleted_packed_files.len(), 1);
+
+    // If the path has been reused, it should only be restored when overwriting.
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path_2.to_vec(), 0, false, vec![4])]);
+    assert!(pack_file.restore_deleted_packedfile(&path_2, false).is_err());
+    pack_file.restore_deleted_packedfile(&path_2, true).unwrap();
+    let index = pack_file.packed_files.iter().position(|x| x.path == path_2).unwrap();
+    assert_eq!(pack_file.packed_files[index].get_data().unwrap(), vec![2]);
+}
+
+/// Test to make sure reloading a PackedFile (`reload_packedfile_from_disk()`) discards only his changes, and fails with PackedFiles not on disk.
+#[test]
+fn test_reload_packedfile_from_disk() {
+    let path_1 = vec!["script".to_owned(), "test_1.lua".to_owned()];
+    let path_2 = vec!["script".to_owned(), "test_2.lua".to_owned()];
+    let path_3 = vec!["script".to_owned(), "test_3.lua".to_owned()];
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.file_path = temp_dir().join("rpfm_test_reload_packedfile_from_disk.pack");
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(path_1.to_vec(), 0, false, vec![1]),
+        PackedFile::read_from_vec(path_2.to_vec(), 0, false, vec![2]),
+    ]);
+    pack_file.save().unwrap();
+
+    pack_file.packed_files.iter_mut().find(|x| x.path == path_1).unwrap().set_data(vec![3]);
+    pack_file.packed_files.iter_mut().find(|x| x.path == path_2).unwrap().set_data(vec![4]);
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path_3.to_vec(), 0, false, vec![5])]);
+
+    pack_file.reload_packedfile_from_disk(&path_1).unwrap();
+    assert!(pack_file.reload_packedfile_from_disk(&path_3).is_err());
+    assert_eq!(pack_file.packed_files.iter().find(|x| x.path == path_1).unwrap().get_data().unwrap(), vec![1]);
+    assert_eq!(pack_file.packed_files.iter().find(|x| x.path == path_2).unwrap().get_data().unwrap(), vec![4]);
+
+    remove_file(&pack_file.file_path).unwrap();
+}
+
+/// Test to make sure saving a PackFile (`PackFile::save()`) over the file his PackedFiles not loaded to memory are read from
+/// keeps their data, and that they can still be read after saving.
+#[test]
+fn test_save_packfile_over_lazy_loaded_data() {
+    let path_1 = vec!["script".to_owned(), "test_1.lua".to_owned()];
+    let path_2 = vec!["script".to_owned(), "test_2.lua".to_owned()];
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.file_path = temp_dir().join("rpfm_test_save_packfile_over_lazy_loaded_data.pack");
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(path_1.to_vec(), 0, false, vec![1, 2, 3]),
+        PackedFile::read_from_vec(path_2.to_vec(), 0, false, vec![4, 5]),
+    ]);
+    pack_file.save().unwrap();
+
+    let mut pack_file = PackFile::read(pack_file.file_path.to_path_buf(), true).unwrap();
+    pack_file.packed_files.iter_mut().find(|x| x.path == path_2).unwrap().set_data(vec![6]);
+    pack_file.save().unwrap();
+    assert_eq!(pack_file.packed_files.iter().find(|x| x.path == path_1).unwrap().get_data().unwrap(), vec![1, 2, 3]);
+
+    let pack_file = PackFile::read(pack_file.file_path.to_path_buf(), false).unwrap();
+    assert_eq!(pack_file.packed_files.iter().find(|x| x.path == path_1).unwrap().get_data().unwrap(), vec![1, 2, 3]);
+    assert_eq!(pack_file.packed_files.iter().find(|x| x.path == path_2).unwrap().get_data().unwrap(), vec![6]);
+
+    remove_file(&pack_file.file_path).unwrap();
+}
+
+/// Test to make sure the extraction hooks (`ExtractionHooks::run()`) are run only over the extracted files they match, and only for their game.
+#[test]
+fn test_extraction_hooks() {
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["script".to_owned(), "test.lua".to_owned()], 0, false, b"print(1)".to_vec()),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "test.txt".to_owned()], 0, false, b"print(1)".to_vec()),
+    ]);
+
+    let mut extraction_hooks = ExtractionHooks::new_empty();
+    extraction_hooks.register("test_game", Box::new(NoOpHook));
+    extraction_hooks.register("test_game", Box::new(UppercaseLuaHook));
+
+    let extracted_path = temp_dir().join("rpfm_test_extraction_hooks");
+    let result = extract_from_packfile(&pack_file, &[PathType::PackFile], &extracted_path, &extraction_hooks, "test_game").unwrap();
+    assert!(result.contains("<li>Uppercase Lua: script/test.lua</li>"));
+    assert!(!result.contains("No-Op"));
+    assert_eq!(read_to_string(extracted_path.join("script").join("test.lua")).unwrap(), "PRINT(1)");
+    assert_eq!(read_to_string(extracted_path.join("text").join("test.txt")).unwrap(), "print(1)");
+
+    // Hooks from other games should not be run.
+    let result = extract_from_packfile(&pack_file, &[PathType::PackFile], &extracted_path, &extraction_hooks, "other_game").unwrap();
+    assert_eq!(result, "2 files extracted. No errors detected.");
+    assert_eq!(read_to_string(extracted_path.join("script").join("test.lua")).unwrap(), "print(1)");
+    remove_dir_all(&extracted_path).unwrap();
+}
+
+/// Test to make sure the null trimming hook (`TrimTrailingNullsHook`) is only registered for the games provided, and skips UTF-16 files.
+#[test]
+fn test_trim_trailing_nulls_hook() {
+    assert_eq!(TrimTrailingNullsHook.transform(&[97, 98, 0, 0]).unwrap(), Some(vec![97, 98]));
+    assert_eq!(TrimTrailingNullsHook.transform(&[97, 98]).unwrap(), None);
+    assert_eq!(TrimTrailingNullsHook.transform(&[0xFF, 0xFE, 97, 0, 0, 0]).unwrap(), None);
+
+    let extraction_hooks = ExtractionHooks::new(&["warhammer_2".to_owned()]);
+    let extracted_path = temp_dir().join("rpfm_test_trim_trailing_nulls_hook");
+    create_dir_all(&extracted_path).unwrap();
+    let file_path = extracted_path.join("test.lua");
+    let extracted_files = vec![(vec!["script".to_owned(), "test.lua".to_owned()], file_path.to_path_buf())];
+
+    File::create(&file_path).unwrap().write_all(&[97, 0]).unwrap();
+    assert!(extraction_hooks.run("attila", &extracted_files).unwrap().is_empty());
+    assert_eq!(extraction_hooks.run("warhammer_2", &extracted_files).unwrap(), vec!["Trim Trailing Nulls: script/test.lua".to_owned()]);
+    assert_eq!(read(&file_path).unwrap(), vec![97]);
+    remove_dir_all(&extracted_path).unwrap();
+}
+
+/// Test to make sure the case collision checker (`PackFile::get_case_collisions()`) reports paths differing only by case.
+#[test]
+fn test_get_case_collisions() {
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["text".to_owned(), "Foo.loc".to_owned()], 0, false, vec![]),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "foo.loc".to_owned()], 0, false, vec![]),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "bar.loc".to_owned()], 0, false, vec![]),
+    ]);
+
+    assert_eq!(pack_file.get_case_collisions(), vec![vec![
+        vec!["text".to_owned(), "Foo.loc".to_owned()],
+        vec!["text".to_owned(), "foo.loc".to_owned()],
+    ]]);
+}
@@ -23,20 +23,26 @@ use serde_derive::Deserialize;
 use serde_xml_rs::from_reader;
 use bincode;
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{File, DirBuilder};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::common::*;
 use crate::error::{Result, Error, ErrorKind};
 use crate::packedfile::db::DB;
 use crate::packedfile::DecodedData;
+use crate::packfile::packedfile::PackedFile;
 use crate::schema::*;
 use crate::DEPENDENCY_DATABASE;
 use crate::RPFM_PATH;
 use crate::GAME_SELECTED;
 use crate::SUPPORTED_GAMES;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 //---------------------------------------------------------------------------//
 // Types for parsing the Assembly Kit Schema Files into.
 //---------------------------------------------------------------------------//
@@ -64,6 +70,8 @@ pub struct field {
     pub column_source_column: Option<Vec<String>>,
     pub column_source_table: Option<String>,
     pub field_description: Option<String>,
+    pub is_filename: Option<String>,
+    pub filename_relative_path: Option<String>,
 }
 
 //---------------------------------------------------------------------------//
@@ -207,6 +215,8 @@ pub fn process_raw_tables(
                                         FieldType::Float => DecodedData::Float(if let Ok(data) = field.field_data.parse::<f32>() { data } else { 0.0 }),
                                         FieldType::Integer => DecodedData::Integer(if let Ok(data) = field.field_data.parse::<i32>() { data } else { 0 }),
                                         FieldType::LongInteger => DecodedData::LongInteger(if let Ok(data) = field.field_data.parse::<i64>() { data } else { 0 }),
+                                        FieldType::Double => DecodedData::Double(if let Ok(data) = field.field_data.parse::<f64>() { data } else { 0.0 }),
+                                        FieldType::Colour => DecodedData::Colour(if let Ok(data) = u32::from_str_radix(field.field_data.trim_start_matches('#'), 16) { data } else { 0 }),
                                         FieldType::StringU8 => DecodedData::StringU8(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
                                         FieldType::StringU16 => DecodedData::StringU16(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
                                         FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
@@ -259,6 +269,75 @@ pub fn process_raw_tables(
     Ok(())
 }
 
+//---------------------------------------------------------------------------//
+// Functions to import the Assembly Kit definitions into the Schema.
+//---------------------------------------------------------------------------//
+
+/// This function returns the name of the table (`units_tables`) of an Assembly Kit definition file (`TWaD_units.xml`).
+pub fn get_table_name_from_definition_path(path: &Path) -> Option<String> {
+    let file_stem = path.file_stem()?.to_str()?;
+    if file_stem.starts_with("TWaD_") { Some(format!("{}_tables", &file_stem[5..])) } else { None }
+}
+
+/// This function reads the Assembly Kit definition (`TWaD_*.xml` file) of the provided table from the raw DB folder of the Assembly Kit.
+pub fn get_assembly_kit_definition(raw_db_path: &Path, table_name: &str) -> Result<root> {
+    let file_name = format!("TWaD_{}.xml", table_name.trim_end_matches("_tables"));
+    let file = File::open(raw_db_path.join(file_name)).map_err(|_| Error::from(ErrorKind::AssemblyKitDefinitionNotFound(table_name.to_owned())))?;
+    from_reader(file).map_err(From::from)
+}
+
+/// This function reads all the Assembly Kit definitions (`TWaD_*.xml` files) from the raw DB folder of the Assembly Kit, by table name.
+/// Files that cannot be parsed are ignored.
+pub fn get_assembly_kit_definitions(raw_db_path: &Path) -> Result<BTreeMap<String, root>> {
+    if !raw_db_path.is_dir() { return Err(ErrorKind::AssemblyKitNotFound)? }
+
+    let mut definitions = BTreeMap::new();
+    for path in get_raw_definitions(raw_db_path, 2)? {
+        if let Some(table_name) = get_table_name_from_definition_path(&path) {
+            if let Ok(file) = File::open(&path) {
+                if let Ok(definition) = from_reader(file) {
+                    definitions.insert(table_name, definition);
+                }
+            }
+        }
+    }
+    Ok(definitions)
+}
+
+/// This function returns the name and version of each one of the DB Tables in the provided PackedFiles.
+/// Tables whose header cannot be read are ignored.
+pub fn get_table_versions(packed_files: &[PackedFile]) -> BTreeSet<(String, i32)> {
+    packed_files.iter()
+        .filter(|x| x.path.len() == 3 && x.path[0] == "db")
+        .filter_map(|x| Some((x.path[1].to_owned(), DB::get_header_data(&x.get_data().ok()?).ok()?.0)))
+        .collect()
+}
+
+/// This function adds to the schema the definitions of the provided versions of the tables, converted from their Assembly Kit definitions,
+/// for the versions the schema doesn't have yet. Versions of tables the Assembly Kit doesn't have a definition for are ignored.
+///
+/// It returns the added definitions as patches, so they can be saved in the local patch of the schema.
+pub fn import_assembly_kit_definitions(
+    schema: &mut Schema,
+    definitions: &BTreeMap<String, root>,
+    table_versions: &BTreeSet<(String, i32)>,
+) -> Vec<SchemaPatch> {
+    let mut patches = vec![];
+    for (table_name, version) in table_versions {
+        if let Some(definition) = definitions.get(table_name) {
+            if DB::get_schema(table_name, *version, schema).is_none() {
+                let patch = SchemaPatch::Definition {
+                    table_name: table_name.to_owned(),
+                    definition: TableDefinition::new_from_assembly_kit(definition, *version, table_name),
+                };
+                patch.apply(schema);
+                patches.push(patch);
+            }
+        }
+    }
+    patches
+}
+
 /// This function is the response to our prayers. It takes the Assembly Kit's DB Files to create basic definitions of each 
 /// undecoded table from the folder you provide it.
 /// 
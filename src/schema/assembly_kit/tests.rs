@@ -0,0 +1,77 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the import of definitions from the Assembly Kit.
+
+use crate::common::tests::new_table_definitions_with_references;
+use crate::packedfile::db::DB;
+use crate::schema::{FieldType, Schema};
+use crate::schema::assembly_kit::{field as AssemblyKitField, get_table_name_from_definition_path, import_assembly_kit_definitions, root as AssemblyKitRoot};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Helper to create a field of an Assembly Kit definition.
+fn new_assembly_kit_field(name: &str, field_type: &str, primary_key: bool, reference: Option<(&str, &str)>) -> AssemblyKitField {
+    AssemblyKitField {
+        primary_key: if primary_key { "1".to_owned() } else { "0".to_owned() },
+        name: name.to_owned(),
+        field_type: field_type.to_owned(),
+        required: "1".to_owned(),
+        max_length: None,
+        column_source_column: reference.map(|x| vec![x.1.to_owned()]),
+        column_source_table: reference.map(|x| x.0.to_owned()),
+        field_description: None,
+        is_filename: None,
+        filename_relative_path: None,
+    }
+}
+
+/// Test to make sure we get the right table name from the name of an Assembly Kit definition file.
+#[test]
+fn test_get_table_name_from_definition_path() {
+    assert_eq!(get_table_name_from_definition_path(Path::new("raw_data/db/TWaD_units.xml")), Some("units_tables".to_owned()));
+    assert_eq!(get_table_name_from_definition_path(Path::new("raw_data/db/units.xml")), None);
+}
+
+/// Test to make sure the Assembly Kit importer (`import_assembly_kit_definitions()`) only adds the versions we don't have,
+/// with their types and references, and returns them as patches.
+#[test]
+fn test_import_assembly_kit_definitions() {
+    let mut schema = Schema::new();
+    schema.add_table_definitions(new_table_definitions_with_references("units_tables", &[]));
+
+    let mut definitions = BTreeMap::new();
+    definitions.insert("units_tables".to_owned(), AssemblyKitRoot { field: vec![
+        new_assembly_kit_field("key", "text", true, None),
+        new_assembly_kit_field("cost", "integer", false, None),
+        new_assembly_kit_field("is_naval", "yesno", false, None),
+        new_assembly_kit_field("faction", "text", false, Some(("factions", "key"))),
+    ]});
+
+    let mut table_versions = BTreeSet::new();
+    table_versions.insert(("units_tables".to_owned(), 1));
+    table_versions.insert(("units_tables".to_owned(), 2));
+    table_versions.insert(("factions_tables".to_owned(), 1));
+
+    let patches = import_assembly_kit_definitions(&mut schema, &definitions, &table_versions);
+    assert_eq!(patches.len(), 1);
+    assert_eq!(patches[0].get_target(), ("units_tables", 2));
+
+    // The version we already had must be untouched, and the new one must be in the schema.
+    assert_eq!(DB::get_schema("units_tables", 1, &schema).unwrap().fields.len(), 1);
+    let definition = DB::get_schema("units_tables", 2, &schema).unwrap();
+    assert_eq!(definition.fields.iter().map(|x| x.field_type.clone()).collect::<Vec<FieldType>>(), vec![FieldType::StringU8, FieldType::Integer, FieldType::Boolean, FieldType::StringU8]);
+    assert!(definition.fields[0].field_is_key);
+    assert_eq!(definition.fields[3].field_is_reference, Some(("factions".to_owned(), "key".to_owned())));
+
+    // Importing it again must not add anything.
+    assert!(import_assembly_kit_definitions(&mut schema, &definitions, &table_versions).is_empty());
+}
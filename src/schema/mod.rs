@@ -12,21 +12,49 @@
 
 use serde_derive::{Serialize, Deserialize};
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
-use std::fs::File;
+use std::fs::{DirBuilder, File};
 use std::io::{BufReader, Read, Write};
 use std::{fmt, fmt::Display};
 
 use crate::RPFM_PATH;
 use crate::SUPPORTED_GAMES;
 use crate::updater::Versions;
-use crate::error::Result;
+use crate::error::{ErrorKind, Result};
 
 pub mod assembly_kit;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 /// Name of the schemas versions file.
 const SCHEMA_VERSIONS_FILE: &'static str = "versions.json";
 
+/// Name of the folder, within the schemas one, where the local patches of the schemas are stored.
+const SCHEMA_PATCHES_FOLDER: &'static str = "patches";
+
+/// Names of the fields of the Assembly Kit tables whose text is localisable. In most games they're not in the final tables, but in the Loc PackedFiles.
+const LOCALISABLE_FIELD_NAMES: [&str; 16] = [
+    "localised_text",
+    "localised_name",
+    "localised_tooltip",
+    "description",
+    "objectives_team_1",
+    "objectives_team_2",
+    "short_description_text",
+    "historical_description_text",
+    "strengths_weaknesses_text",
+    "onscreen",
+    "onscreen_text",
+    "onscreen_name",
+    "onscreen_description",
+    "on_screen_name",
+    "on_screen_description",
+    "on_screen_target",
+];
+
 /// URL used to download new schemas.
 pub const SCHEMA_UPDATE_URL_MASTER: &'static str = "https://raw.githubusercontent.com/Frodo45127/rpfm/master/schemas/";
 
@@ -53,6 +81,7 @@ pub struct TableDefinitions {
 /// This struct holds the definitions for a version of a table. It has:
 /// - version: the version of the table these definitions are for.
 /// - fields: the different fields this table has.
+/// - localised_fields: the fields of this table that are not in the table, but in the Loc PackedFiles, with keys like `{table}_{field}_{row key}`.
 ///
 /// NOTE: the versions are:
 /// - 0: for unversioned tables.
@@ -63,6 +92,9 @@ pub struct TableDefinitions {
 pub struct TableDefinition {
     pub version: i32,
     pub fields: Vec<Field>,
+
+    #[serde(default)]
+    pub localised_fields: Vec<Field>,
 }
 
 /// This struct holds the type of a field of a table. It has:
@@ -70,6 +102,8 @@ pub struct TableDefinition {
 /// - field_is_key: true if the field is a key field and his column needs to be put in the beginning of the TreeView.
 /// - field_is_reference: if this field is a reference of another, this has (table name, field name).
 /// - field_type: the type of the field.
+/// - field_is_filename: if this field contains paths to files (models, textures,...), this has the path they're relative to. It can be empty.
+/// - field_default_value: the value new rows get in this field, if it has one. Otherwise, they get the default of the field type.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Field {
     pub field_name: String,
@@ -77,6 +111,12 @@ pub struct Field {
     pub field_is_key: bool,
     pub field_is_reference: Option<(String, String)>,
     pub field_description: String,
+
+    #[serde(default)]
+    pub field_is_filename: Option<String>,
+
+    #[serde(default)]
+    pub field_default_value: Option<String>,
 }
 
 /// Enum FieldType: This enum is used to define the possible types of a field in the schema.
@@ -86,12 +126,45 @@ pub enum FieldType {
     Float,
     Integer,
     LongInteger,
+    Double,
+    Colour,
     StringU8,
     StringU16,
     OptionalStringU8,
     OptionalStringU16
 }
 
+/// This enum holds a change made by the user to the schema of a game, stored in a local patch file so it survives schema updates:
+/// - FieldRenamed: the field `old_name` of a version of a table has been renamed to `new_name`.
+/// - FieldDescription: the description of a field of a version of a table has been changed.
+/// - FieldReference: the reference of a field of a version of a table has been added, changed or removed.
+/// - Definition: a version of a table has been added, or changed in a way the other patches cannot represent.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum SchemaPatch {
+    FieldRenamed {
+        table_name: String,
+        version: i32,
+        old_name: String,
+        new_name: String,
+    },
+    FieldDescription {
+        table_name: String,
+        version: i32,
+        field_name: String,
+        description: String,
+    },
+    FieldReference {
+        table_name: String,
+        version: i32,
+        field_name: String,
+        reference: Option<(String, String)>,
+    },
+    Definition {
+        table_name: String,
+        definition: TableDefinition,
+    },
+}
+
 /// Implementation of "Schema"
 impl Schema {
 
@@ -117,6 +190,82 @@ impl Schema {
         self.tables_definitions.iter().position(|x| x.name == table_name)
     }
 
+    /// This function finds the circular references between the tables of the schema. Each cycle is returned once, as
+    /// the path of tables that forms it, starting and ending with the first table of the cycle in alphabetical order
+    /// (`a_tables -> b_tables -> a_tables`).
+    pub fn find_reference_cycles(&self) -> Vec<Vec<String>> {
+
+        // First, we build the graph of references. References use the table name without the "_tables" suffix, so we add it back.
+        let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for table_definitions in &self.tables_definitions {
+            let references = graph.entry(table_definitions.name.to_owned()).or_insert_with(BTreeSet::new);
+            for table_definition in &table_definitions.versions {
+                for field in &table_definition.fields {
+                    if let Some((ref table, _)) = field.field_is_reference {
+                        references.insert(format!("{}_tables", table));
+                    }
+                }
+            }
+        }
+
+        // Then we walk the graph from every table we haven't visited yet, reporting every cycle we find in the way.
+        // The same cycle can be reached from different tables, so we normalise them before removing the duplicates.
+        let mut cycles = vec![];
+        let mut visited = BTreeSet::new();
+        for table in graph.keys() {
+            Self::find_reference_cycles_from_table(table, &graph, &mut visited, &mut vec![], &mut cycles);
+        }
+        cycles.into_iter().map(|x| Self::normalise_reference_cycle(&x)).collect::<BTreeSet<Vec<String>>>().into_iter().collect()
+    }
+
+    /// This function rotates a cycle (`b_tables -> a_tables -> b_tables`) so it starts and ends with his first table
+    /// in alphabetical order (`a_tables -> b_tables -> a_tables`), so the same cycle is always reported the same way.
+    fn normalise_reference_cycle(cycle: &[String]) -> Vec<String> {
+        let tables = &cycle[..cycle.len() - 1];
+        let start = tables.iter().enumerate().min_by_key(|(_, x)| *x).map_or(0, |(x, _)| x);
+        let mut normalised = tables[start..].iter().chain(tables[..start].iter()).cloned().collect::<Vec<String>>();
+        normalised.push(normalised[0].to_owned());
+        normalised
+    }
+
+    /// This function walks the reference graph in depth from the provided table. If we reach a table that's already
+    /// in the current path, the part of the path from that table is a cycle.
+    fn find_reference_cycles_from_table(
+        table: &str,
+        graph: &BTreeMap<String, BTreeSet<String>>,
+        visited: &mut BTreeSet<String>,
+        path: &mut Vec<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if let Some(position) = path.iter().position(|x| x == table) {
+            let mut cycle = path[position..].to_vec();
+            cycle.push(table.to_owned());
+            cycles.push(cycle);
+            return;
+        }
+
+        // Tables already walked have already reported their cycles.
+        if visited.contains(table) { return; }
+
+        path.push(table.to_owned());
+        if let Some(references) = graph.get(table) {
+            for reference in references {
+                Self::find_reference_cycles_from_table(reference, graph, visited, path, cycles);
+            }
+        }
+        path.pop();
+        visited.insert(table.to_owned());
+    }
+
+    /// This function returns the names of the tables of the schema without reference data in any of their versions.
+    /// These tables cannot be checked for missing references, so they're the ones that need more work on the schema.
+    pub fn find_unannotated_tables(&self) -> Vec<String> {
+        self.tables_definitions.iter()
+            .filter(|x| !x.versions.iter().any(|x| x.fields.iter().any(|x| x.field_is_reference.is_some())))
+            .map(|x| x.name.to_owned())
+            .collect()
+    }
+
     /// This function takes an schema file and reads it into a "Schema" object.
     pub fn load(schema_file: &str) -> Result<Self> {
 
@@ -128,6 +277,18 @@ impl Schema {
         serde_json::from_reader(file).map_err(|x| From::from(x))
     }
 
+    /// This function takes an schema file and reads it into a "Schema" object, applying on top of it the local patches of that schema.
+    pub fn load_with_patches(schema_file: &str) -> Result<Self> {
+        let mut schema = Self::load(schema_file)?;
+        schema.apply_patches(&SchemaPatch::load(schema_file)?);
+        Ok(schema)
+    }
+
+    /// This function applies the provided patches to the schema, in order. Patches for fields or versions that don't exist are ignored.
+    pub fn apply_patches(&mut self, patches: &[SchemaPatch]) {
+        patches.iter().for_each(|x| { x.apply(self); });
+    }
+
     /// This function takes an "Schema" object and saves it into a schema file.
     pub fn save(&self, schema_file: &str) -> Result<()> {
 
@@ -301,6 +462,208 @@ impl Schema {
     }
 }
 
+/// This function is used to run an operation that changes the provided schema. If `locked` is true, the operation
+/// is not run and it returns an error instead, so the schema is left untouched.
+pub fn edit_schema<T, F>(
+    schema: &mut Option<Schema>,
+    locked: bool,
+    operation: F,
+) -> Result<T> where F: FnOnce(&mut Option<Schema>) -> Result<T> {
+    if locked { Err(ErrorKind::SchemaLocked)? }
+    else { operation(schema) }
+}
+
+/// Implementation of `SchemaPatch`.
+impl SchemaPatch {
+
+    /// This function returns the patches needed to turn the old definition of a version of a table into the new one. Renames, descriptions
+    /// and references are stored as their own patches. Anything else (or a version we didn't have) means we need to store the entire definition.
+    pub fn get_patches(table_name: &str, old_definition: Option<&TableDefinition>, new_definition: &TableDefinition) -> Vec<Self> {
+        let old_definition = match old_definition {
+            Some(old_definition) => old_definition,
+            None => return vec![SchemaPatch::Definition { table_name: table_name.to_owned(), definition: new_definition.clone() }],
+        };
+
+        let same_structure = old_definition.version == new_definition.version &&
+            old_definition.localised_fields == new_definition.localised_fields &&
+            old_definition.fields.len() == new_definition.fields.len() &&
+            old_definition.fields.iter().zip(&new_definition.fields).all(|(old, new)|
+                old.field_type == new.field_type &&
+                old.field_is_key == new.field_is_key &&
+                old.field_is_filename == new.field_is_filename &&
+                old.field_default_value == new.field_default_value
+            );
+        if !same_structure { return vec![SchemaPatch::Definition { table_name: table_name.to_owned(), definition: new_definition.clone() }]; }
+
+        let version = new_definition.version;
+        let mut patches = vec![];
+        for (old, new) in old_definition.fields.iter().zip(&new_definition.fields) {
+            if old.field_name != new.field_name {
+                patches.push(SchemaPatch::FieldRenamed { table_name: table_name.to_owned(), version, old_name: old.field_name.to_owned(), new_name: new.field_name.to_owned() });
+            }
+            if old.field_description != new.field_description {
+                patches.push(SchemaPatch::FieldDescription { table_name: table_name.to_owned(), version, field_name: new.field_name.to_owned(), description: new.field_description.to_owned() });
+            }
+            if old.field_is_reference != new.field_is_reference {
+                patches.push(SchemaPatch::FieldReference { table_name: table_name.to_owned(), version, field_name: new.field_name.to_owned(), reference: new.field_is_reference.clone() });
+            }
+        }
+        patches
+    }
+
+    /// This function returns the name of the table and the version this patch is for.
+    pub fn get_target(&self) -> (&str, i32) {
+        match self {
+            SchemaPatch::FieldRenamed { table_name, version, .. } |
+            SchemaPatch::FieldDescription { table_name, version, .. } |
+            SchemaPatch::FieldReference { table_name, version, .. } => (table_name.as_str(), *version),
+            SchemaPatch::Definition { table_name, definition } => (table_name.as_str(), definition.version),
+        }
+    }
+
+    /// This function applies the patch to the provided schema. It returns false if the field or version it's for doesn't exist.
+    pub fn apply(&self, schema: &mut Schema) -> bool {
+        if let SchemaPatch::Definition { table_name, definition } = self {
+            let index = match schema.get_table_definitions(table_name) {
+                Some(index) => index,
+                None => {
+                    schema.add_table_definitions(TableDefinitions::new(table_name));
+                    schema.tables_definitions.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+                    schema.get_table_definitions(table_name).unwrap()
+                }
+            };
+            schema.tables_definitions[index].add_table_definition(definition.clone());
+            schema.tables_definitions[index].versions.sort_unstable_by(|a, b| b.version.cmp(&a.version));
+            return true;
+        }
+
+        let (table_name, version) = self.get_target();
+        let table_definitions = match schema.tables_definitions.iter_mut().find(|x| x.name == table_name) {
+            Some(table_definitions) => table_definitions,
+            None => return false,
+        };
+        let definition = match table_definitions.versions.iter_mut().find(|x| x.version == version) {
+            Some(definition) => definition,
+            None => return false,
+        };
+
+        match self {
+            SchemaPatch::FieldRenamed { old_name, new_name, .. } => match definition.fields.iter_mut().find(|x| &x.field_name == old_name) {
+                Some(field) => { field.field_name = new_name.to_owned(); true }
+                None => false,
+            }
+            SchemaPatch::FieldDescription { field_name, description, .. } => match definition.fields.iter_mut().find(|x| &x.field_name == field_name) {
+                Some(field) => { field.field_description = description.to_owned(); true }
+                None => false,
+            }
+            SchemaPatch::FieldReference { field_name, reference, .. } => match definition.fields.iter_mut().find(|x| &x.field_name == field_name) {
+                Some(field) => { field.field_is_reference = reference.clone(); true }
+                None => false,
+            }
+            SchemaPatch::Definition { .. } => unreachable!(),
+        }
+    }
+
+    /// This function adds the provided patches to a list of patches, removing the ones they make redundant: a `Definition`
+    /// replaces all the previous patches of his version, descriptions and references replace the previous ones of their field,
+    /// and renames are folded into the previous rename of their field.
+    pub fn add_patches(patches: &mut Vec<Self>, new_patches: Vec<Self>) {
+        for new_patch in new_patches {
+            match new_patch {
+                SchemaPatch::Definition { .. } => {
+                    let target = new_patch.get_target();
+                    patches.retain(|x| x.get_target() != target);
+                }
+                SchemaPatch::FieldDescription { .. } | SchemaPatch::FieldReference { .. } => patches.retain(|x| !x.is_same_field_change(&new_patch)),
+                SchemaPatch::FieldRenamed { ref table_name, version, ref old_name, ref new_name } => {
+                    if Self::fold_field_rename(patches, table_name, version, old_name, new_name) { continue; }
+                }
+            }
+            patches.push(new_patch);
+        }
+    }
+
+    /// This function folds a rename of a field into the previous rename of that field, if any, so renaming a field several times leaves
+    /// a single rename from his original name, or none if he gets his original name back. As the folded rename is applied before the
+    /// patches added after the previous one, these are changed to use the new name of the field. It returns true if the rename was folded.
+    fn fold_field_rename(patches: &mut Vec<Self>, table_name: &str, version: i32, old_name: &str, new_name: &str) -> bool {
+        let index = match patches.iter().position(|x| match x {
+            SchemaPatch::FieldRenamed { new_name: previous_new_name, .. } => previous_new_name == old_name && x.get_target() == (table_name, version),
+            _ => false,
+        }) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        for patch in &mut patches[index + 1..] {
+            if patch.get_target() != (table_name, version) { continue; }
+            match patch {
+                SchemaPatch::FieldDescription { field_name, .. } |
+                SchemaPatch::FieldReference { field_name, .. } => if *field_name == old_name { *field_name = new_name.to_owned(); },
+                _ => {}
+            }
+        }
+
+        let gets_original_name = if let SchemaPatch::FieldRenamed { old_name: original_name, .. } = &patches[index] { original_name == new_name } else { false };
+        if gets_original_name { patches.remove(index); }
+        else if let SchemaPatch::FieldRenamed { new_name: previous_new_name, .. } = &mut patches[index] { *previous_new_name = new_name.to_owned(); }
+        true
+    }
+
+    /// This function returns true if both patches are descriptions (or references) of the same field.
+    fn is_same_field_change(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SchemaPatch::FieldDescription { field_name, .. }, SchemaPatch::FieldDescription { field_name: other_field_name, .. }) |
+            (SchemaPatch::FieldReference { field_name, .. }, SchemaPatch::FieldReference { field_name: other_field_name, .. }) => field_name == other_field_name && self.get_target() == other.get_target(),
+            _ => false,
+        }
+    }
+
+    /// This function returns the path of the patch file of the provided schema file (`schemas/patches/wh2.json` for `schema_wh2.json`).
+    pub fn get_patch_file_path(schema_file: &str) -> PathBuf {
+        let mut path = RPFM_PATH.to_path_buf();
+        path.push("schemas");
+        path.push(SCHEMA_PATCHES_FOLDER);
+        path.push(schema_file.trim_start_matches("schema_"));
+        path
+    }
+
+    /// This function loads the local patches of the provided schema file. If there is no patch file, there are no patches.
+    pub fn load(schema_file: &str) -> Result<Vec<Self>> {
+        let path = Self::get_patch_file_path(schema_file);
+        if !path.is_file() { return Ok(vec![]) }
+
+        let file = BufReader::new(File::open(&path)?);
+        serde_json::from_reader(file).map_err(From::from)
+    }
+
+    /// This function saves the provided patches as the local patches of the provided schema file.
+    pub fn save(patches: &[Self], schema_file: &str) -> Result<()> {
+        let path = Self::get_patch_file_path(schema_file);
+        DirBuilder::new().recursive(true).create(path.parent().unwrap())?;
+
+        let json = serde_json::to_string_pretty(&patches)?;
+        let mut file = File::create(&path)?;
+        file.write_all(json.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Implementation of Display for `SchemaPatch`, to list the patches in the UI.
+impl Display for SchemaPatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaPatch::FieldRenamed { table_name, version, old_name, new_name } => write!(f, "{} v{}: field \"{}\" renamed to \"{}\"", table_name, version, old_name, new_name),
+            SchemaPatch::FieldDescription { table_name, version, field_name, .. } => write!(f, "{} v{}: description of the field \"{}\" changed", table_name, version, field_name),
+            SchemaPatch::FieldReference { table_name, version, field_name, reference } => match reference {
+                Some((ref_table, ref_column)) => write!(f, "{} v{}: field \"{}\" references {}/{}", table_name, version, field_name, ref_table, ref_column),
+                None => write!(f, "{} v{}: reference of the field \"{}\" removed", table_name, version, field_name),
+            }
+            SchemaPatch::Definition { table_name, definition } => write!(f, "{} v{}: definition with {} fields", table_name, definition.version, definition.fields.len()),
+        }
+    }
+}
+
 /// Implementation of "TableDefinitions"
 impl TableDefinitions {
 
@@ -358,6 +721,19 @@ impl TableDefinition {
         TableDefinition {
             version,
             fields: vec![],
+            localised_fields: vec![],
+        }
+    }
+
+    /// This function returns if the provided field of this definition holds localisable text: a string field that's
+    /// either one of the localised fields of the definition, or one of the fields known to be localisable.
+    pub fn is_field_localisable(&self, field: &Field) -> bool {
+        match field.field_type {
+            FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16 => {
+                LOCALISABLE_FIELD_NAMES.contains(&&*field.field_name) ||
+                self.localised_fields.iter().any(|x| x.field_name == field.field_name)
+            }
+            _ => false,
         }
     }
 
@@ -366,27 +742,16 @@ impl TableDefinition {
     /// import the version (this... I think I can do some trick for it).
     pub fn new_from_assembly_kit(imported_table_definition: &assembly_kit::root, version: i32, table_name: &str) -> TableDefinition {
         let mut fields = vec![];
+        let mut localised_fields = vec![];
         for field in &imported_table_definition.field {
 
             // First, we need to disable a number of known fields that are not in the final tables. We
             // check if the current field is one of them, and ignore it if it's.
-            if field.name == "game_expansion_key" || // This one exists in one of the advices tables.
-                field.name == "localised_text" ||
-                field.name == "localised_name" ||
-                field.name == "localised_tooltip" ||
-                field.name == "description" ||
-                field.name == "objectives_team_1" ||
-                field.name == "objectives_team_2" ||
-                field.name == "short_description_text" ||
-                field.name == "historical_description_text" ||
-                field.name == "strengths_weaknesses_text" ||
-                field.name == "onscreen" ||
-                field.name == "onscreen_text" ||
-                field.name == "onscreen_name" ||
-                field.name == "onscreen_description" ||
-                field.name == "on_screen_name" ||
-                field.name == "on_screen_description" ||
-                field.name == "on_screen_target" {
+            if field.name == "game_expansion_key" { continue; } // This one exists in one of the advices tables.
+
+            // The localisable fields are not in the final tables either, but we keep them apart, as their text is in the Loc PackedFiles.
+            if LOCALISABLE_FIELD_NAMES.contains(&&*field.name) {
+                localised_fields.push(Field::new(field.name.to_owned(), FieldType::StringU16, false, None, String::new()));
                 continue;
             }
             let field_name = field.name.to_owned();
@@ -448,19 +813,25 @@ impl TableDefinition {
                 None => String::new(),
             };
 
-            let new_field = Field::new(
+            let mut new_field = Field::new(
                 field_name,
                 field_type,
                 field_is_key,
                 field_is_reference,
                 field_description
             );
+
+            // If the field contains paths to files, keep the path they're relative to.
+            if field.is_filename.as_ref().map_or(false, |x| x == "1") {
+                new_field.field_is_filename = Some(field.filename_relative_path.clone().unwrap_or_else(String::new));
+            }
             fields.push(new_field);
         }
 
         TableDefinition {
             version,
             fields,
+            localised_fields,
         }
     }
         
@@ -529,19 +900,25 @@ impl TableDefinition {
                 None => String::new(),
             };
 
-            let new_field = Field::new(
+            let mut new_field = Field::new(
                 field_name,
                 field_type,
                 field_is_key,
                 field_is_reference,
                 field_description
             );
+
+            // If the field contains paths to files, keep the path they're relative to.
+            if field.is_filename.as_ref().map_or(false, |x| x == "1") {
+                new_field.field_is_filename = Some(field.filename_relative_path.clone().unwrap_or_else(String::new));
+            }
             fields.push(new_field);
         }
 
         TableDefinition {
             version,
             fields,
+            localised_fields: vec![],
         }
     }
 
@@ -555,6 +932,7 @@ impl TableDefinition {
         Self {
             version,
             fields,
+            localised_fields: vec![],
         }
     }
 
@@ -563,6 +941,7 @@ impl TableDefinition {
         Self {
             version: 1,
             fields: vec![Field::new("PackFile's List".to_owned(), FieldType::StringU8, false, None, "".to_owned())],
+            localised_fields: vec![],
         }
     }
 
@@ -676,7 +1055,9 @@ impl Field {
             field_type,
             field_is_key,
             field_is_reference,
-            field_description
+            field_description,
+            field_is_filename: None,
+            field_default_value: None,
         }
     }
 }
@@ -689,6 +1070,8 @@ impl Display for FieldType {
             FieldType::Float => write!(f, "Float"),
             FieldType::Integer => write!(f, "Integer"),
             FieldType::LongInteger => write!(f, "Long Integer"),
+            FieldType::Double => write!(f, "Double"),
+            FieldType::Colour => write!(f, "Colour"),
             FieldType::StringU8 => write!(f, "StringU8"),
             FieldType::StringU16 => write!(f, "StringU16"),
             FieldType::OptionalStringU8 => write!(f, "OptionalStringU8"),
@@ -0,0 +1,140 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the Schema logic functions, like the ones to edit it, patch it or check his references.
+
+use crate::common::tests::new_table_definitions_with_references;
+use crate::error::ErrorKind;
+use crate::packedfile::db::DB;
+use crate::schema::{Field, FieldType, Schema, SchemaPatch, TableDefinition, edit_schema};
+
+/// Test to make sure the operations that change the schema (`edit_schema()`) are blocked while it's locked.
+#[test]
+fn test_edit_schema_locked() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.add_table_definition(TableDefinition::new(2));
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+    let mut schema = Some(schema);
+
+    let result = edit_schema(&mut schema, true, |schema| DB::remove_table_version("units_tables", 2, schema.as_mut().unwrap()));
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::SchemaLocked);
+    assert_eq!(schema.as_ref().unwrap().tables_definitions[0].versions.len(), 2);
+
+    edit_schema(&mut schema, false, |schema| DB::remove_table_version("units_tables", 2, schema.as_mut().unwrap())).unwrap();
+    assert_eq!(schema.as_ref().unwrap().tables_definitions[0].versions.len(), 1);
+}
+
+/// Test to make sure the changes to a definition are turned into the right schema patches (`SchemaPatch::get_patches()`),
+/// and that applying them to an updated schema keeps the changes.
+#[test]
+fn test_schema_patches() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &["land_units_tables"]);
+    let old_definition = table_definitions.versions[0].clone();
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions);
+
+    // Renames, descriptions and references are stored as deltas.
+    let mut new_definition = old_definition.clone();
+    new_definition.fields[0].field_name = "unit".to_owned();
+    new_definition.fields[0].field_description = "The unit's key.".to_owned();
+    new_definition.fields[1].field_is_reference = None;
+    let patches = SchemaPatch::get_patches("units_tables", Some(&old_definition), &new_definition);
+    assert_eq!(patches, vec![
+        SchemaPatch::FieldRenamed { table_name: "units_tables".to_owned(), version: 1, old_name: "key".to_owned(), new_name: "unit".to_owned() },
+        SchemaPatch::FieldDescription { table_name: "units_tables".to_owned(), version: 1, field_name: "unit".to_owned(), description: "The unit's key.".to_owned() },
+        SchemaPatch::FieldReference { table_name: "units_tables".to_owned(), version: 1, field_name: "land_units_tables_ref".to_owned(), reference: None },
+    ]);
+    assert!(SchemaPatch::get_patches("units_tables", Some(&old_definition), &old_definition).is_empty());
+
+    schema.apply_patches(&patches);
+    assert_eq!(schema.tables_definitions[0].versions[0], new_definition);
+
+    // New versions, or changes to the structure of a version, store the entire definition.
+    let mut new_version = new_definition.clone();
+    new_version.version = 2;
+    new_version.fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    let version_patches = SchemaPatch::get_patches("units_tables", None, &new_version);
+    assert_eq!(version_patches, vec![SchemaPatch::Definition { table_name: "units_tables".to_owned(), definition: new_version.clone() }]);
+
+    schema.apply_patches(&version_patches);
+    assert_eq!(schema.tables_definitions[0].versions, vec![new_version.clone(), new_definition.clone()]);
+
+    // Patches for fields that no longer exist are ignored.
+    assert!(!patches[0].apply(&mut schema));
+
+    // A definition replaces the previous patches of his version, and a description replaces the previous one of his field.
+    let mut stored_patches = patches.to_vec();
+    SchemaPatch::add_patches(&mut stored_patches, vec![SchemaPatch::FieldDescription { table_name: "units_tables".to_owned(), version: 1, field_name: "unit".to_owned(), description: "Key.".to_owned() }]);
+    assert_eq!(stored_patches.len(), 3);
+    assert_eq!(stored_patches[2], SchemaPatch::FieldDescription { table_name: "units_tables".to_owned(), version: 1, field_name: "unit".to_owned(), description: "Key.".to_owned() });
+
+    // Renaming a field again folds the rename into the previous one, and the later patches of the field follow his new name.
+    SchemaPatch::add_patches(&mut stored_patches, vec![SchemaPatch::FieldRenamed { table_name: "units_tables".to_owned(), version: 1, old_name: "unit".to_owned(), new_name: "unit_key".to_owned() }]);
+    assert_eq!(stored_patches, vec![
+        SchemaPatch::FieldRenamed { table_name: "units_tables".to_owned(), version: 1, old_name: "key".to_owned(), new_name: "unit_key".to_owned() },
+        SchemaPatch::FieldReference { table_name: "units_tables".to_owned(), version: 1, field_name: "land_units_tables_ref".to_owned(), reference: None },
+        SchemaPatch::FieldDescription { table_name: "units_tables".to_owned(), version: 1, field_name: "unit_key".to_owned(), description: "Key.".to_owned() },
+    ]);
+
+    // And giving him his original name back removes the rename.
+    SchemaPatch::add_patches(&mut stored_patches, vec![SchemaPatch::FieldRenamed { table_name: "units_tables".to_owned(), version: 1, old_name: "unit_key".to_owned(), new_name: "key".to_owned() }]);
+    assert_eq!(stored_patches, vec![
+        SchemaPatch::FieldReference { table_name: "units_tables".to_owned(), version: 1, field_name: "land_units_tables_ref".to_owned(), reference: None },
+        SchemaPatch::FieldDescription { table_name: "units_tables".to_owned(), version: 1, field_name: "key".to_owned(), description: "Key.".to_owned() },
+    ]);
+
+    SchemaPatch::add_patches(&mut stored_patches, vec![SchemaPatch::Definition { table_name: "units_tables".to_owned(), definition: new_definition.clone() }]);
+    assert_eq!(stored_patches, vec![SchemaPatch::Definition { table_name: "units_tables".to_owned(), definition: new_definition }]);
+}
+
+/// Test to make sure the reference cycle finder (`find_reference_cycles()`) finds a cycle between two tables.
+#[test]
+fn test_find_reference_cycles() {
+    let mut schema = Schema::new();
+    schema.add_table_definitions(new_table_definitions_with_references("a_tables", &["b"]));
+    schema.add_table_definitions(new_table_definitions_with_references("b_tables", &["a"]));
+
+    assert_eq!(schema.find_reference_cycles(), vec![vec!["a_tables".to_owned(), "b_tables".to_owned(), "a_tables".to_owned()]]);
+}
+
+/// Test to make sure the reference cycle finder (`find_reference_cycles()`) reports a cycle reachable from more than one table
+/// only once, starting with his first table in alphabetical order.
+#[test]
+fn test_find_reference_cycles_normalised() {
+    let mut schema = Schema::new();
+    schema.add_table_definitions(new_table_definitions_with_references("a_tables", &["c"]));
+    schema.add_table_definitions(new_table_definitions_with_references("b_tables", &["c"]));
+    schema.add_table_definitions(new_table_definitions_with_references("c_tables", &["b"]));
+
+    assert_eq!(schema.find_reference_cycles(), vec![vec!["b_tables".to_owned(), "c_tables".to_owned(), "b_tables".to_owned()]]);
+}
+
+/// Test to make sure the reference cycle finder (`find_reference_cycles()`) finds nothing in a schema without cycles,
+/// even when a table is referenced from more than one path.
+#[test]
+fn test_find_reference_cycles_without_cycles() {
+    let mut schema = Schema::new();
+    schema.add_table_definitions(new_table_definitions_with_references("a_tables", &["b", "c"]));
+    schema.add_table_definitions(new_table_definitions_with_references("b_tables", &["c"]));
+    schema.add_table_definitions(new_table_definitions_with_references("c_tables", &[]));
+
+    assert!(schema.find_reference_cycles().is_empty());
+}
+
+/// Test to make sure only the tables without reference data (`find_unannotated_tables()`) are reported.
+#[test]
+fn test_find_unannotated_tables() {
+    let mut schema = Schema::new();
+    schema.add_table_definitions(new_table_definitions_with_references("a_tables", &["b"]));
+    schema.add_table_definitions(new_table_definitions_with_references("b_tables", &[]));
+
+    assert_eq!(schema.find_unannotated_tables(), vec!["b_tables".to_owned()]);
+}
@@ -25,8 +25,15 @@ use crate::packfile::PFHVersion;
 
 pub mod shortcuts;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 const SETTINGS_FILE: &str = "settings.json";
 
+/// Game selected on start if the user hasn't set a default game, or the one set is no longer supported.
+pub const DEFAULT_GAME: &str = "three_kingdoms";
+
 /// `GameInfo`: This struct holds all the info needed for a game to be "supported" by RPFM features.
 /// It's stores the following data:
 /// - `display_name`: This is the name it'll show up in the UI. For example, in a dropdown (Warhammer 2).
@@ -55,12 +62,25 @@ pub struct GameInfo {
     pub game_selected_icon: String,
 }
 
+/// This struct holds the view-only settings of the DB Tables and Loc PackedFiles, parsed from the settings.
+/// Anything set to None uses the default of the system.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableViewStyle {
+    pub font_family: Option<String>,
+    pub font_size: Option<i32>,
+    pub row_height: Option<i32>,
+}
+
 /// This struct hold every setting of the program, and it's the one that we are going to serialize.
+/// - `table_column_layouts`: the column layouts saved for each table name, with the name of the layout as key and the columns hidden in it as value.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Settings {
     pub paths: BTreeMap<String, Option<PathBuf>>,
     pub settings_string: BTreeMap<String, String>,
     pub settings_bool: BTreeMap<String, bool>,
+
+    #[serde(default)]
+    pub table_column_layouts: BTreeMap<String, BTreeMap<String, Vec<String>>>,
 }
 
 /// Implementation of `Settings`.
@@ -78,13 +98,36 @@ impl Settings {
 
         // Populate the maps with the default shortcuts. New settings MUST BE ADDED HERE.
         paths.insert("mymods_base_path".to_owned(), None);
+
+        // Raw DB folder of the Assembly Kit, for importing his definitions. None means "use the one in the game's folder".
+        paths.insert("assembly_kit_raw_db_path".to_owned(), None);
         
         for (folder_name, _) in SUPPORTED_GAMES.iter() {
             paths.insert(folder_name.to_string(), None);
         }
 
         // Default Game.
-        settings_string.insert("default_game".to_owned(), "three_kingdoms".to_owned());
+        settings_string.insert("default_game".to_owned(), DEFAULT_GAME.to_owned());
+
+        // TableView Style Settings. Empty means "use the default".
+        settings_string.insert("table_font_family".to_owned(), String::new());
+        settings_string.insert("table_font_size".to_owned(), String::new());
+        settings_string.insert("table_row_height".to_owned(), String::new());
+
+        // Suffix added to the keys of cloned rows. Empty means "don't add anything".
+        settings_string.insert("cloned_key_suffix".to_owned(), String::new());
+
+        // Templates used to generate the loc keys of DB Tables, separated by commas. Empty means "use the localisable fields of the table".
+        settings_string.insert("loc_key_templates".to_owned(), String::new());
+
+        // Patterns of the loc keys to ignore when searching for untranslated loc entries, separated by commas. Empty means "ignore nothing".
+        settings_string.insert("untranslated_ignored_keys".to_owned(), String::new());
+
+        // Minutes between autosaves of the open PackFile. Empty or 0 means "don't autosave".
+        settings_string.insert("autosave_interval".to_owned(), "5".to_owned());
+
+        // Games (by `folder_name`) whose extracted Text PackedFiles get their trailing nulls trimmed, separated by commas. Empty means "none".
+        settings_string.insert("trim_nulls_on_extraction_games".to_owned(), String::new());
 
         // UI Settings.
         settings_bool.insert("adjust_columns_to_content".to_owned(), true);
@@ -101,6 +144,7 @@ impl Settings {
         settings_bool.insert("use_dependency_checker".to_owned(), false);
         settings_bool.insert("use_lazy_loading".to_owned(), true);
         settings_bool.insert("optimize_not_renamed_packedfiles".to_owned(), false);
+        settings_bool.insert("autosave_recovery_prompt".to_owned(), true);
 
         // Debug Settings.
         settings_bool.insert("check_for_missing_table_definitions".to_owned(), false);
@@ -108,12 +152,14 @@ impl Settings {
         // TableView Specific Settings.
         settings_bool.insert("remember_column_sorting".to_owned(), true);
         settings_bool.insert("remember_column_visual_order".to_owned(), true);
+        settings_bool.insert("tint_column_headers_by_type".to_owned(), false);
 
         // Return it.
         Self {
             paths,
             settings_string,
             settings_bool,
+            table_column_layouts: BTreeMap::new(),
         }
     }
 
@@ -151,6 +197,62 @@ impl Settings {
         Ok(settings)
     }
 
+    /// This function returns the game that should be selected on start: the default game of the settings if it's
+    /// set and supported, or `DEFAULT_GAME` otherwise.
+    pub fn get_default_game(&self) -> String {
+        match self.settings_string.get("default_game") {
+            Some(game) if SUPPORTED_GAMES.contains_key(&**game) => game.to_owned(),
+            _ => DEFAULT_GAME.to_owned(),
+        }
+    }
+
+    /// This function returns the font and row height the tables should use, ignoring the values that are empty or not valid.
+    pub fn get_table_view_style(&self) -> TableViewStyle {
+        let get_string = |key: &str| self.settings_string.get(key).map(|x| x.trim().to_owned()).filter(|x| !x.is_empty());
+        let get_number = |key: &str| get_string(key).and_then(|x| x.parse::<i32>().ok()).filter(|x| *x > 0);
+        TableViewStyle {
+            font_family: get_string("table_font_family"),
+            font_size: get_number("table_font_size"),
+            row_height: get_number("table_row_height"),
+        }
+    }
+
+    /// This function returns the minutes between autosaves of the open PackFile, or None if autosaving is disabled.
+    pub fn get_autosave_interval(&self) -> Option<u32> {
+        self.settings_string.get("autosave_interval").and_then(|x| x.trim().parse::<u32>().ok()).filter(|x| *x > 0)
+    }
+
+    /// This function returns the games (by `folder_name`) whose extracted Text PackedFiles get their trailing nulls trimmed.
+    pub fn get_trim_nulls_on_extraction_games(&self) -> Vec<String> {
+        self.settings_string.get("trim_nulls_on_extraction_games").map(|x| x.split(',').map(|x| x.trim().to_owned()).filter(|x| !x.is_empty()).collect()).unwrap_or_else(|| vec![])
+    }
+
+    /// This function returns the names of the column layouts saved for the provided table.
+    pub fn get_table_column_layouts(&self, table_name: &str) -> Vec<String> {
+        self.table_column_layouts.get(table_name).map(|x| x.keys().cloned().collect()).unwrap_or_else(|| vec![])
+    }
+
+    /// This function returns, for each one of the provided columns, if it's hidden in the provided layout of the table.
+    /// Columns the layout doesn't know about (like new columns after a schema update) are visible. If the layout doesn't exist, it returns None.
+    pub fn get_table_column_layout(&self, table_name: &str, layout_name: &str, columns: &[String]) -> Option<Vec<bool>> {
+        self.table_column_layouts.get(table_name)
+            .and_then(|x| x.get(layout_name))
+            .map(|hidden| columns.iter().map(|x| hidden.contains(x)).collect())
+    }
+
+    /// This function saves a column layout for the provided table, replacing any layout with the same name.
+    pub fn set_table_column_layout(&mut self, table_name: &str, layout_name: &str, hidden_columns: Vec<String>) {
+        self.table_column_layouts.entry(table_name.to_owned()).or_insert_with(BTreeMap::new).insert(layout_name.to_owned(), hidden_columns);
+    }
+
+    /// This function removes a column layout from the provided table, if it exists.
+    pub fn remove_table_column_layout(&mut self, table_name: &str, layout_name: &str) {
+        if let Some(layouts) = self.table_column_layouts.get_mut(table_name) {
+            layouts.remove(layout_name);
+            if layouts.is_empty() { self.table_column_layouts.remove(table_name); }
+        }
+    }
+
     /// This function takes the Settings object and saves it into a settings.json file.
     pub fn save(&self) -> Result<()> {
 
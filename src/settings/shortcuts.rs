@@ -83,6 +83,7 @@ impl Shortcuts {
         tree_view.insert("mass_import_tsv".to_owned(), "Ctrl+.".to_owned());
         tree_view.insert("mass_export_tsv".to_owned(), "Ctrl+,".to_owned());
         tree_view.insert("merge_tables".to_owned(), "Ctrl+M".to_owned());
+        tree_view.insert("generate_loc_entries".to_owned(), "Ctrl+Shift+L".to_owned());
         tree_view.insert("delete".to_owned(), "Del".to_owned());
         tree_view.insert("extract".to_owned(), "Ctrl+E".to_owned());
         tree_view.insert("rename".to_owned(), "Ctrl+R".to_owned());
@@ -110,16 +111,35 @@ impl Shortcuts {
         packed_files_table.insert("clone_and_append_row".to_owned(), "Ctrl+Shift+D".to_owned());
         packed_files_table.insert("copy".to_owned(), "Ctrl+C".to_owned());
         packed_files_table.insert("copy_as_lua_table".to_owned(), "Ctrl+Shift+C".to_owned());
+        packed_files_table.insert("copy_as_json".to_owned(), "Ctrl+Alt+C".to_owned());
+        packed_files_table.insert("copy_with_headers".to_owned(), "Ctrl+Alt+Shift+C".to_owned());
+        packed_files_table.insert("copy_as_markdown_table".to_owned(), "Ctrl+Alt+M".to_owned());
         packed_files_table.insert("paste".to_owned(), "Ctrl+V".to_owned());
         packed_files_table.insert("paste_as_new_row".to_owned(), "Ctrl+Shift+V".to_owned());
         packed_files_table.insert("paste_to_fill_selection".to_owned(), "Ctrl+Alt+V".to_owned());
         packed_files_table.insert("apply_maths_to_selection".to_owned(), "Ctrl+B".to_owned());
         packed_files_table.insert("rewrite_selection".to_owned(), "Ctrl+Y".to_owned());
+        packed_files_table.insert("toggle_booleans".to_owned(), "Ctrl+Space".to_owned());
+        packed_files_table.insert("pick_colour".to_owned(), "Ctrl+Alt+P".to_owned());
+        packed_files_table.insert("pick_reference".to_owned(), "Ctrl+Alt+L".to_owned());
+        packed_files_table.insert("edit_in_window".to_owned(), "Ctrl+Alt+W".to_owned());
+        packed_files_table.insert("go_to_definition".to_owned(), "Ctrl+Alt+G".to_owned());
+        packed_files_table.insert("find_usages".to_owned(), "Ctrl+Alt+U".to_owned());
         packed_files_table.insert("selection_invert".to_owned(), "Ctrl+-".to_owned());
         packed_files_table.insert("search".to_owned(), "Ctrl+F".to_owned());
+        packed_files_table.insert("go_to_row".to_owned(), "Ctrl+G".to_owned());
         packed_files_table.insert("sidebar".to_owned(), "Ctrl+A".to_owned());
+        packed_files_table.insert("history".to_owned(), "Ctrl+H".to_owned());
+        packed_files_table.insert("column_filters".to_owned(), "Ctrl+Alt+F".to_owned());
+        packed_files_table.insert("freeze_key_columns".to_owned(), "Ctrl+Alt+K".to_owned());
+        packed_files_table.insert("vanilla_diff".to_owned(), "Ctrl+Alt+D".to_owned());
+        packed_files_table.insert("revert_cell_to_vanilla".to_owned(), "Ctrl+Alt+R".to_owned());
+        packed_files_table.insert("revert_row_to_vanilla".to_owned(), "Ctrl+Alt+Shift+R".to_owned());
         packed_files_table.insert("import_tsv".to_owned(), "Ctrl+W".to_owned());
         packed_files_table.insert("export_tsv".to_owned(), "Ctrl+E".to_owned());
+        packed_files_table.insert("export_json".to_owned(), "Ctrl+Alt+E".to_owned());
+        packed_files_table.insert("export_csv".to_owned(), "Ctrl+Shift+E".to_owned());
+        packed_files_table.insert("reload_packed_file".to_owned(), "Ctrl+Shift+R".to_owned());
         packed_files_table.insert("smart_delete".to_owned(), "Del".to_owned());
         packed_files_table.insert("undo".to_owned(), "Ctrl+Z".to_owned());
         packed_files_table.insert("redo".to_owned(), "Ctrl+Shift+Z".to_owned());
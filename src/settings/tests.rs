@@ -0,0 +1,76 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the Settings logic functions.
+
+use crate::settings::{DEFAULT_GAME, Settings, TableViewStyle};
+
+/// Test to make sure the game selected on start (`get_default_game()`) is the one in the settings, if it's set and supported.
+#[test]
+fn test_get_default_game() {
+    let mut settings = Settings::new();
+    assert_eq!(settings.get_default_game(), DEFAULT_GAME);
+
+    settings.settings_string.insert("default_game".to_owned(), "warhammer_2".to_owned());
+    assert_eq!(settings.get_default_game(), "warhammer_2");
+
+    settings.settings_string.insert("default_game".to_owned(), "unsupported_game".to_owned());
+    assert_eq!(settings.get_default_game(), DEFAULT_GAME);
+
+    settings.settings_string.remove("default_game");
+    assert_eq!(settings.get_default_game(), DEFAULT_GAME);
+}
+
+/// Test to make sure the column layouts of the tables are saved per table, and columns unknown to a layout are visible.
+#[test]
+fn test_table_column_layouts() {
+    let mut settings = Settings::new();
+    let columns = vec!["key".to_owned(), "tier".to_owned(), "cost".to_owned()];
+    assert!(settings.get_table_column_layout("land_units_tables", "stats", &columns).is_none());
+
+    settings.set_table_column_layout("land_units_tables", "stats", vec!["key".to_owned(), "removed_column".to_owned()]);
+    settings.set_table_column_layout("land_units_tables", "keys", vec!["tier".to_owned(), "cost".to_owned()]);
+    assert_eq!(settings.get_table_column_layouts("land_units_tables"), vec!["keys".to_owned(), "stats".to_owned()]);
+    assert_eq!(settings.get_table_column_layouts("units_tables"), Vec::<String>::new());
+    assert_eq!(settings.get_table_column_layout("land_units_tables", "stats", &columns), Some(vec![true, false, false]));
+    assert!(settings.get_table_column_layout("units_tables", "stats", &columns).is_none());
+
+    settings.remove_table_column_layout("land_units_tables", "stats");
+    settings.remove_table_column_layout("land_units_tables", "keys");
+    assert!(settings.table_column_layouts.is_empty());
+
+    // Settings saved before the layouts existed must still load.
+    let old_settings: Settings = serde_json::from_str(r#"{ "paths": {}, "settings_string": {}, "settings_bool": {} }"#).unwrap();
+    assert!(old_settings.table_column_layouts.is_empty());
+}
+
+/// Test to make sure the style of the tables (`get_table_view_style()`) is read from the settings, ignoring empty and invalid values.
+#[test]
+fn test_get_table_view_style() {
+    let mut settings = Settings::new();
+    assert_eq!(settings.get_table_view_style(), TableViewStyle::default());
+
+    settings.settings_string.insert("table_font_family".to_owned(), " Consolas ".to_owned());
+    settings.settings_string.insert("table_font_size".to_owned(), "12".to_owned());
+    settings.settings_string.insert("table_row_height".to_owned(), "28".to_owned());
+    assert_eq!(settings.get_table_view_style(), TableViewStyle {
+        font_family: Some("Consolas".to_owned()),
+        font_size: Some(12),
+        row_height: Some(28),
+    });
+
+    settings.settings_string.insert("table_font_size".to_owned(), "big".to_owned());
+    settings.settings_string.insert("table_row_height".to_owned(), "-5".to_owned());
+    assert_eq!(settings.get_table_view_style(), TableViewStyle {
+        font_family: Some("Consolas".to_owned()),
+        font_size: None,
+        row_height: None,
+    });
+}
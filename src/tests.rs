@@ -0,0 +1,247 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the functions of the background thread, like the ones to edit, save and optimize a PackFile, or to replay a log of commands.
+
+use crate::background_thread_extra::{LoggedCommand, delete_from_packfile, edit_packfile, export_command_log, get_optimization_report, load_command_log, rename_packed_files, replay_command_log, save_packfile};
+use crate::background_thread_extra::{update_packed_file_data_db, update_packed_file_data_text};
+use crate::common::tests::{new_db_packed_file, new_table_definitions_with_references};
+use crate::error::{Error, ErrorKind};
+use crate::packedfile::{DecodedData, merge_identical_adjacent_rows, replace_in_column};
+use crate::packedfile::db::DB;
+use crate::packedfile::loc::Loc;
+use crate::packfile::{PackFile, PathType, PFHVersion};
+use crate::packfile::packedfile::PackedFile;
+use crate::schema::Schema;
+
+use std::env::temp_dir;
+use std::fs::remove_file;
+use std::path::PathBuf;
+
+/// Test to make sure the optimization report (`get_optimization_report()`) reports what the optimizer would remove, without changing the PackFile.
+#[test]
+fn test_get_optimization_report() {
+    let row = |key: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(key.to_uppercase()), DecodedData::Boolean(true)];
+    let vanilla_loc = Loc { entries: vec![row("a")] };
+    let dependency_database = vec![PackedFile::read_from_vec(vec!["text".to_owned(), "db".to_owned(), "vanilla.loc".to_owned()], 0, false, vanilla_loc.save())];
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[
+        PackedFile::read_from_vec(vec!["text".to_owned(), "mod.loc".to_owned()], 0, false, Loc { entries: vec![row("a"), row("b")] }.save()),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "vanilla_only.loc".to_owned()], 0, false, Loc { entries: vec![row("a")] }.save()),
+        PackedFile::read_from_vec(vec!["text".to_owned(), "new.loc".to_owned()], 0, false, Loc { entries: vec![row("c")] }.save()),
+    ]);
+
+    let report = get_optimization_report(&mut pack_file, &dependency_database).unwrap();
+
+    assert_eq!(report, vec![
+        vec!["Path".to_owned(), "Rows".to_owned(), "Rows Removed".to_owned(), "Bytes Saved".to_owned(), "Emptied".to_owned()],
+        vec!["text/mod.loc".to_owned(), "2".to_owned(), "1".to_owned(), "9".to_owned(), "false".to_owned()],
+        vec!["text/vanilla_only.loc".to_owned(), "1".to_owned(), "1".to_owned(), "23".to_owned(), "true".to_owned()],
+    ]);
+
+    // The PackFile itself must not be optimized.
+    assert_eq!(pack_file.packed_files.len(), 3);
+}
+
+/// Test to make sure a command log (`replay_command_log()`) turns a fresh PackFile into the one it was recorded from, and that it's not applied at all if a command fails.
+#[test]
+fn test_replay_command_log() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    let table_path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let new_pack_file = || {
+        let entries = ["unit_1", "unit_1", "unit_2"].iter().map(|x| vec![DecodedData::StringU8(x.to_string())]).collect();
+        let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+        pack_file.add_packed_files(&[
+            new_db_packed_file(&table_path, &table_definitions.versions[0], entries),
+            PackedFile::read_from_vec(vec!["script".to_owned(), "a.lua".to_owned()], 0, false, vec![]),
+        ]);
+        pack_file
+    };
+
+    // Record a few commands while editing one PackFile.
+    let mut pack_file = new_pack_file();
+    let renaming_data = vec![(PathType::File(vec!["script".to_owned(), "a.lua".to_owned()]), "b.lua".to_owned())];
+    rename_packed_files(&mut pack_file, &renaming_data);
+    merge_identical_adjacent_rows(&mut pack_file, &table_path, &schema).unwrap();
+    replace_in_column(&mut pack_file, &table_path, "key", "unit_2", "unit_3", &schema).unwrap();
+    let command_log = vec![
+        LoggedCommand::RenamePackedFiles { renaming_data },
+        LoggedCommand::MergeIdenticalAdjacentRows { path: table_path.to_vec() },
+        LoggedCommand::ReplaceInColumn { path: table_path.to_vec(), column: "key".to_owned(), from: "unit_2".to_owned(), to: "unit_3".to_owned() },
+    ];
+
+    // Save it and load it back, so we check the log survives the trip to disk.
+    let log_path = temp_dir().join("rpfm_test_command_log.json");
+    export_command_log(&command_log, &log_path).unwrap();
+    let command_log = load_command_log(&log_path).unwrap();
+    remove_file(&log_path).unwrap();
+
+    // Replay it over a fresh PackFile.
+    let mut replayed_pack_file = new_pack_file();
+    assert_eq!(replay_command_log(&mut replayed_pack_file, &command_log, Some(&schema), &[], false).unwrap().len(), 3);
+    let get_contents = |pack_file: &PackFile| pack_file.packed_files.iter().map(|x| (x.path.to_vec(), x.get_data().unwrap())).collect::<Vec<(Vec<String>, Vec<u8>)>>();
+    assert_eq!(get_contents(&replayed_pack_file), get_contents(&pack_file));
+
+    // A replay done through `edit_packfile()` is logged as one command, which can be replayed too.
+    let mut replayed_pack_file = new_pack_file();
+    let mut replay_log = vec![];
+    let command = LoggedCommand::ReplayCommandLog { commands: command_log.to_vec() };
+    edit_packfile(&mut replayed_pack_file, false, &mut replay_log, command, |pack_file| replay_command_log(pack_file, &command_log, Some(&schema), &[], false)).unwrap();
+    assert_eq!(replay_log.len(), 1);
+
+    let mut nested_pack_file = new_pack_file();
+    assert_eq!(replay_command_log(&mut nested_pack_file, &replay_log, Some(&schema), &[], false).unwrap().len(), 1);
+    assert_eq!(get_contents(&nested_pack_file), get_contents(&pack_file));
+
+    // A failing command stops the replay, without applying any of the commands before it.
+    let mut untouched_pack_file = new_pack_file();
+    let broken_command_log = vec![
+        LoggedCommand::RenamePackedFiles { renaming_data: vec![(PathType::File(vec!["script".to_owned(), "a.lua".to_owned()]), "b.lua".to_owned())] },
+        LoggedCommand::ReplaceInColumn { path: table_path.to_vec(), column: "missing".to_owned(), from: String::new(), to: String::new() },
+    ];
+    match replay_command_log(&mut untouched_pack_file, &broken_command_log, Some(&schema), &[], false) {
+        Err(error) => assert_eq!(error.kind(), ErrorKind::CommandLogReplayFailed(2, Error::from(ErrorKind::ColumnNotFound("missing".to_owned())).to_string())),
+        Ok(_) => panic!("The replay of a broken command log worked."),
+    }
+    assert_eq!(get_contents(&untouched_pack_file), get_contents(&new_pack_file()));
+
+    // Commands over tables need the schema.
+    assert!(replay_command_log(&mut new_pack_file(), &command_log[1..], None, &[], false).is_err());
+}
+
+/// Test to make sure the edits saved from the table and text views are logged, and replaying them leaves the PackedFiles as they were saved.
+#[test]
+fn test_replay_view_saves() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    let table_path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let text_path = vec!["script".to_owned(), "a.lua".to_owned()];
+    let new_pack_file = || {
+        let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+        pack_file.add_packed_files(&[
+            new_db_packed_file(&table_path, &table_definitions.versions[0], vec![vec![DecodedData::StringU8("unit_1".to_owned())]]),
+            PackedFile::read_from_vec(text_path.to_vec(), 0, false, vec![]),
+        ]);
+        pack_file
+    };
+
+    // Save a table and a text like their views do.
+    let mut pack_file = new_pack_file();
+    let mut command_log = vec![];
+    let data = pack_file.packed_files[0].get_data().unwrap();
+    let mut db = DB::read(&data, "units_tables", &schema).unwrap();
+    db.entries.push(vec![DecodedData::StringU8("unit_2".to_owned())]);
+    let command = LoggedCommand::SetTableData { path: table_path.to_vec(), entries: db.entries.to_vec() };
+    edit_packfile(&mut pack_file, false, &mut command_log, command, |pack_file| update_packed_file_data_db(&db, pack_file, &table_path)).unwrap();
+
+    let text = "print(\"test\")".to_owned();
+    let command = LoggedCommand::SetTextData { path: text_path.to_vec(), text: text.to_owned() };
+    edit_packfile(&mut pack_file, false, &mut command_log, command, |pack_file| update_packed_file_data_text(text.as_bytes(), pack_file, &text_path)).unwrap();
+    assert_eq!(command_log.len(), 2);
+
+    // Replay them over a fresh PackFile.
+    let mut replayed_pack_file = new_pack_file();
+    assert_eq!(replay_command_log(&mut replayed_pack_file, &command_log, Some(&schema), &[], false).unwrap().len(), 2);
+    let get_contents = |pack_file: &PackFile| pack_file.packed_files.iter().map(|x| (x.path.to_vec(), x.get_data().unwrap())).collect::<Vec<(Vec<String>, Vec<u8>)>>();
+    assert_eq!(get_contents(&replayed_pack_file), get_contents(&pack_file));
+}
+
+/// Test to make sure deleting in "Dry Run" mode (`edit_packfile()`) reports what would be deleted without deleting it.
+#[test]
+fn test_dry_run_delete() {
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["script".to_owned(), "test.lua".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, vec![97, 98])]);
+
+    // Check that the deletion is reported, but not logged.
+    let mut command_log = vec![];
+    let command = LoggedCommand::DeletePackedFiles { item_types: vec![PathType::File(path.to_vec())] };
+    let deleted = edit_packfile(&mut pack_file, true, &mut command_log, command, |pack_file| delete_from_packfile(pack_file, &[PathType::File(path.to_vec())]));
+    assert!(command_log.is_empty());
+    assert_eq!(deleted.len(), 1);
+    match deleted[0] {
+        PathType::File(ref deleted_path) => assert_eq!(deleted_path, &path),
+        _ => panic!("Wrong PathType returned."),
+    }
+
+    // Check that the PackedFile is still there.
+    assert!(pack_file.packedfile_exists(&path));
+    assert_eq!(pack_file.packed_files.len(), 1);
+}
+
+/// Test to make sure renaming in "Dry Run" mode (`edit_packfile()`) reports the new name without renaming anything.
+#[test]
+fn test_dry_run_rename() {
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["script".to_owned(), "test.lua".to_owned()];
+    let new_path = vec!["script".to_owned(), "renamed.lua".to_owned()];
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(path.to_vec(), 0, false, vec![97, 98])]);
+
+    // Check that the rename is reported.
+    let renaming_data = vec![(PathType::File(path.to_vec()), "renamed.lua".to_owned())];
+    let mut command_log = vec![];
+    let renamed = edit_packfile(&mut pack_file, true, &mut command_log, LoggedCommand::RenamePackedFiles { renaming_data: renaming_data.to_vec() }, |pack_file| rename_packed_files(pack_file, &renaming_data));
+    assert_eq!(renamed.len(), 1);
+    assert_eq!(renamed[0].1, "renamed.lua");
+
+    // Check that the PackedFile keeps his old name.
+    assert!(pack_file.packedfile_exists(&path));
+    assert!(!pack_file.packedfile_exists(&new_path));
+
+    // And that without "Dry Run" the rename actually happens, and gets logged.
+    assert!(command_log.is_empty());
+    edit_packfile(&mut pack_file, false, &mut command_log, LoggedCommand::RenamePackedFiles { renaming_data: renaming_data.to_vec() }, |pack_file| rename_packed_files(pack_file, &renaming_data));
+    assert!(pack_file.packedfile_exists(&new_path));
+    assert_eq!(command_log.len(), 1);
+}
+
+/// Test to make sure editing the data of a PackedFile in "Dry Run" mode (`edit_packfile()`) doesn't change the data of the PackedFile,
+/// even when the copy shares it.
+#[test]
+fn test_dry_run_edit_data() {
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(vec!["script".to_owned(), "test.lua".to_owned()], 0, false, vec![97, 98])]);
+
+    let data = edit_packfile(&mut pack_file, true, &mut vec![], LoggedCommand::TrimTrailingNulls, |pack_file| {
+        pack_file.packed_files[0].get_data_and_info_from_memory().unwrap().0.push(99);
+        pack_file.packed_files[0].get_data().unwrap()
+    });
+    assert_eq!(data, vec![97, 98, 99]);
+    assert_eq!(pack_file.packed_files[0].get_data().unwrap(), vec![97, 98]);
+}
+
+/// Test to make sure saving in "Dry Run" mode (`save_packfile()`) reports where the PackFile would be saved without saving it.
+#[test]
+fn test_dry_run_save() {
+
+    let mut pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    pack_file.add_packed_files(&[PackedFile::read_from_vec(vec!["script".to_owned(), "test.lua".to_owned()], 0, false, vec![97, 98])]);
+
+    let path = temp_dir().join("rpfm_test_dry_run_save.pack");
+    match save_packfile(&mut pack_file, Some(path.to_path_buf()), false, true) {
+        Err(error) => match error.kind() {
+            ErrorKind::SavePackFileGeneric(message) => assert!(message.contains("1 PackedFiles")),
+            _ => panic!("Wrong error returned."),
+        }
+        Ok(_) => panic!("The PackFile has been saved in \"Dry Run\" mode."),
+    }
+
+    assert!(!path.exists());
+    assert_eq!(pack_file.file_path, PathBuf::from("test.pack"));
+}
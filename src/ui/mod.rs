@@ -10,6 +10,7 @@
 
 // In this file are all the helper functions used by the UI (mainly Qt here)
 
+use qt_widgets::abstract_item_view::EditTrigger;
 use qt_widgets::action::Action;
 use qt_widgets::check_box::CheckBox;
 use qt_widgets::combo_box::ComboBox;
@@ -17,29 +18,35 @@ use qt_widgets::dialog::Dialog;
 use qt_widgets::file_dialog::{FileDialog, FileMode};
 use qt_widgets::grid_layout::GridLayout;
 use qt_widgets::group_box::GroupBox;
+use qt_widgets::header_view::ResizeMode;
 use qt_widgets::label::Label;
 use qt_widgets::layout::Layout;
 use qt_widgets::line_edit::LineEdit;
 use qt_widgets::main_window::MainWindow;
 use qt_widgets::message_box::{MessageBox, Icon};
 use qt_widgets::push_button::PushButton;
+use qt_widgets::table_view::TableView;
 use qt_widgets::tree_view::TreeView;
 use qt_widgets::widget::Widget;
 
 use qt_gui::brush::Brush;
 use qt_gui::icon;
 use qt_gui::key_sequence::KeySequence;
+use qt_gui::list::ListStandardItemMutPtr;
+use qt_gui::standard_item::StandardItem;
 use qt_gui::standard_item_model::StandardItemModel;
 
 use qt_core::abstract_item_model::AbstractItemModel;
 use qt_core::connection::Signal;
 use qt_core::flags::Flags;
+use qt_core::item_selection_model::SelectionFlag;
 use qt_core::model_index::ModelIndex;
 use qt_core::object::Object;
-use qt_core::qt::ShortcutContext;
+use qt_core::qt::{Orientation, ShortcutContext};
 use qt_core::reg_exp::RegExp;
 use qt_core::slots::{SlotBool, SlotNoArgs, SlotStringRef, SlotModelIndexRef};
 use qt_core::sort_filter_proxy_model::SortFilterProxyModel;
+use qt_core::variant::Variant;
 
 use cpp_utils::{CppBox, StaticCast};
 
@@ -57,6 +64,7 @@ use crate::RPFM_PATH;
 use crate::SHORTCUTS;
 use crate::SETTINGS;
 use crate::SCHEMA;
+use crate::SUPPORTED_GAMES;
 use crate::IS_MODIFIED;
 use crate::IS_FOLDER_TREE_VIEW_LOCKED;
 use crate::ORANGE;
@@ -93,6 +101,13 @@ pub mod table_state;
 pub mod updater;
 pub mod qt_custom_stuff;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
+/// Amount of errors shown when asking the user if he wants to import TSV files with errors anyway.
+const TSV_ERRORS_TO_SHOW: usize = 20;
+
 //----------------------------------------------------------------------------//
 //             UI Structs (to hold slots, actions and what not)
 //----------------------------------------------------------------------------//
@@ -128,6 +143,110 @@ pub struct MyModSlots {
     pub open_mymod: Vec<SlotBool<'static>>,
 }
 
+/// This struct holds all the "Tools" actions from the Menu Bar.
+#[derive(Copy, Clone)]
+pub struct ToolsStuff {
+    pub strip_boms_from_text: *mut Action,
+    pub count_localisable_characters: *mut Action,
+    pub dry_run_mode: *mut Action,
+    pub find_reference_cycles: *mut Action,
+    pub loc_coverage_report: *mut Action,
+    pub set_table_alias: *mut Action,
+    pub export_column_enums: *mut Action,
+    pub find_non_ascii_keys: *mut Action,
+    pub split_table_by_column: *mut Action,
+    pub restore_deleted_file: *mut Action,
+    pub find_unannotated_tables: *mut Action,
+    pub export_translation_matrix: *mut Action,
+    pub trim_trailing_nulls: *mut Action,
+    pub validate_pack_for_game: *mut Action,
+    pub check_composite_uniqueness: *mut Action,
+    pub append_row_count_snapshot: *mut Action,
+    pub find_redundant_overrides: *mut Action,
+    pub suggest_unique_key: *mut Action,
+    pub export_vanilla_mod_comparison: *mut Action,
+    pub find_unreferenced_assets: *mut Action,
+    pub run_edit_script: *mut Action,
+    pub size_by_table_type: *mut Action,
+    pub lock_schema: *mut Action,
+    pub find_oversized_cells: *mut Action,
+    pub export_tree_as_html: *mut Action,
+    pub verify_loc_column_order: *mut Action,
+    pub session_edit_stats: *mut Action,
+    pub find_case_collisions: *mut Action,
+    pub export_optimization_report: *mut Action,
+    pub replace_in_column: *mut Action,
+    pub validate_key_column: *mut Action,
+    pub export_loc_matches: *mut Action,
+    pub merge_identical_adjacent_rows: *mut Action,
+    pub export_table_checksums: *mut Action,
+    pub export_command_log: *mut Action,
+    pub replay_command_log: *mut Action,
+    pub export_to_sqlite: *mut Action,
+    pub import_from_sqlite: *mut Action,
+    pub find_missing_loc_keys: *mut Action,
+    pub find_duplicated_loc_keys: *mut Action,
+    pub export_bilingual_tsv: *mut Action,
+    pub import_bilingual_tsv: *mut Action,
+    pub find_untranslated_loc_entries: *mut Action,
+    pub rename_key_prefix: *mut Action,
+    pub diff_table_with_packfile: *mut Action,
+    pub schema_patches: *mut Action,
+    pub import_assembly_kit_definitions: *mut Action,
+}
+
+/// This struct holds all the Slots related to the "Tools" Menu, as otherwise they'll die before we
+/// press their buttons and do nothing.
+pub struct ToolsSlots {
+    pub strip_boms_from_text: SlotBool<'static>,
+    pub count_localisable_characters: SlotBool<'static>,
+    pub dry_run_mode: SlotBool<'static>,
+    pub find_reference_cycles: SlotBool<'static>,
+    pub loc_coverage_report: SlotBool<'static>,
+    pub set_table_alias: SlotBool<'static>,
+    pub export_column_enums: SlotBool<'static>,
+    pub find_non_ascii_keys: SlotBool<'static>,
+    pub split_table_by_column: SlotBool<'static>,
+    pub restore_deleted_file: SlotBool<'static>,
+    pub find_unannotated_tables: SlotBool<'static>,
+    pub export_translation_matrix: SlotBool<'static>,
+    pub trim_trailing_nulls: SlotBool<'static>,
+    pub validate_pack_for_game: SlotBool<'static>,
+    pub check_composite_uniqueness: SlotBool<'static>,
+    pub append_row_count_snapshot: SlotBool<'static>,
+    pub find_redundant_overrides: SlotBool<'static>,
+    pub suggest_unique_key: SlotBool<'static>,
+    pub export_vanilla_mod_comparison: SlotBool<'static>,
+    pub find_unreferenced_assets: SlotBool<'static>,
+    pub run_edit_script: SlotBool<'static>,
+    pub size_by_table_type: SlotBool<'static>,
+    pub lock_schema: SlotBool<'static>,
+    pub find_oversized_cells: SlotBool<'static>,
+    pub export_tree_as_html: SlotBool<'static>,
+    pub verify_loc_column_order: SlotBool<'static>,
+    pub session_edit_stats: SlotBool<'static>,
+    pub find_case_collisions: SlotBool<'static>,
+    pub export_optimization_report: SlotBool<'static>,
+    pub replace_in_column: SlotBool<'static>,
+    pub validate_key_column: SlotBool<'static>,
+    pub export_loc_matches: SlotBool<'static>,
+    pub merge_identical_adjacent_rows: SlotBool<'static>,
+    pub export_table_checksums: SlotBool<'static>,
+    pub export_command_log: SlotBool<'static>,
+    pub replay_command_log: SlotBool<'static>,
+    pub export_to_sqlite: SlotBool<'static>,
+    pub import_from_sqlite: SlotBool<'static>,
+    pub find_missing_loc_keys: SlotBool<'static>,
+    pub find_duplicated_loc_keys: SlotBool<'static>,
+    pub export_bilingual_tsv: SlotBool<'static>,
+    pub import_bilingual_tsv: SlotBool<'static>,
+    pub find_untranslated_loc_entries: SlotBool<'static>,
+    pub rename_key_prefix: SlotBool<'static>,
+    pub diff_table_with_packfile: SlotBool<'static>,
+    pub schema_patches: SlotBool<'static>,
+    pub import_assembly_kit_definitions: SlotBool<'static>,
+}
+
 /// This struct holds all the Slots related to the "Add from PackFile" View, as otherwise they'll
 /// die before we press their buttons and do nothing.
 pub struct AddFromPackFileSlots {
@@ -631,8 +750,39 @@ pub fn create_global_search_dialog(app_ui: &AppUI) -> Option<String> {
     else { None }
 }
 
-/// This function creates the entire "Merge Tables" dialog. It returns the stuff set in it.
-pub fn create_merge_tables_dialog(app_ui: &AppUI) -> Option<(String, bool)> {
+/// This function creates the dialog of the "Find Untranslated Loc Entries" tool, with the patterns of the loc keys to ignore. The
+/// patterns in the settings are loaded by default. It returns the patterns to use, or None if the dialog is closed.
+pub fn create_untranslated_loc_entries_dialog(app_ui: &AppUI) -> Option<Vec<String>> {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str("Find Untranslated Loc Entries"));
+    dialog.set_modal(true);
+
+    // Create the main Grid.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let mut ignored_keys_label = Label::new(&QString::from_std_str("Patterns (regex) of the loc keys to ignore, separated by commas:"));
+    let mut ignored_keys = LineEdit::new(());
+    ignored_keys.set_placeholder_text(&QString::from_std_str("None"));
+    ignored_keys.set_text(&QString::from_std_str(&SETTINGS.lock().unwrap().settings_string["untranslated_ignored_keys"]));
+
+    let search_button = PushButton::new(&QString::from_std_str("Search")).into_raw();
+    unsafe { main_grid.as_mut().unwrap().add_widget((ignored_keys_label.static_cast_mut() as *mut Widget, 0, 0, 1, 2)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((ignored_keys.static_cast_mut() as *mut Widget, 1, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((search_button as *mut Widget, 1, 1, 1, 1)); }
+
+    // What happens when we hit the "Search" button.
+    unsafe { search_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+
+    // Execute the dialog.
+    if dialog.exec() == 1 { Some(ignored_keys.text().to_std_string().split(',').map(|x| x.trim().to_owned()).filter(|x| !x.is_empty()).collect()) }
+
+    // Otherwise, return None.
+    else { None }
+}
+
+/// This function creates the entire "Merge Tables" dialog. It returns the name of the new file, and if we want
+/// to delete the source tables and to remove the duplicated rows.
+pub fn create_merge_tables_dialog(app_ui: &AppUI) -> Option<(String, bool, bool)> {
 
     let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
     dialog.set_window_title(&QString::from_std_str("Merge Tables"));
@@ -644,11 +794,13 @@ pub fn create_merge_tables_dialog(app_ui: &AppUI) -> Option<(String, bool)> {
     name.set_placeholder_text(&QString::from_std_str("Write the name of the new file here."));
 
     let mut delete_source_tables = CheckBox::new(&QString::from_std_str("Delete original tables"));
+    let mut remove_duplicates = CheckBox::new(&QString::from_std_str("Remove duplicated rows (keeps the last one of each key)"));
 
     let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
     unsafe { main_grid.as_mut().unwrap().add_widget((name.static_cast_mut() as *mut Widget, 0, 0, 1, 1)); }
     unsafe { main_grid.as_mut().unwrap().add_widget((delete_source_tables.static_cast_mut() as *mut Widget, 1, 0, 1, 1)); }
-    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 2, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((remove_duplicates.static_cast_mut() as *mut Widget, 2, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 3, 0, 1, 1)); }
 
     // What happens when we hit the "Search" button.
     unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
@@ -657,7 +809,8 @@ pub fn create_merge_tables_dialog(app_ui: &AppUI) -> Option<(String, bool)> {
     if dialog.exec() == 1 { 
         let text = name.text().to_std_string();
         let delete_source_tables = delete_source_tables.is_checked();
-        if !text.is_empty() { Some((text, delete_source_tables)) }
+        let remove_duplicates = remove_duplicates.is_checked();
+        if !text.is_empty() { Some((text, delete_source_tables, remove_duplicates)) }
         else { None }
     }
     
@@ -665,6 +818,291 @@ pub fn create_merge_tables_dialog(app_ui: &AppUI) -> Option<(String, bool)> {
     else { None }
 }
 
+/// This function creates a simple dialog with one LineEdit for each one of the provided placeholder texts,
+/// for the tools that need a few values from the user. It returns the text of each LineEdit, in the same order.
+pub fn create_text_input_dialog(app_ui: &AppUI, title: &str, placeholders: &[&str]) -> Option<Vec<String>> {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(title));
+    dialog.set_modal(true);
+
+    // Create the main Grid, with a LineEdit per value and the button at the end.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let mut line_edits = vec![];
+    for (index, placeholder) in placeholders.iter().enumerate() {
+        let line_edit = LineEdit::new(()).into_raw();
+        unsafe { line_edit.as_mut().unwrap().set_placeholder_text(&QString::from_std_str(placeholder)); }
+        unsafe { main_grid.as_mut().unwrap().add_widget((line_edit as *mut Widget, index as i32, 0, 1, 1)); }
+        line_edits.push(line_edit);
+    }
+
+    let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, placeholders.len() as i32, 0, 1, 1)); }
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+
+    // Execute the dialog.
+    if dialog.exec() == 1 { Some(line_edits.iter().map(|x| unsafe { x.as_mut().unwrap().text().to_std_string() }).collect()) }
+
+    // Otherwise, return None.
+    else { None }
+}
+
+/// This function creates a dialog to ask the user for one of the supported games. It returns the folder name
+/// of the game (`warhammer_2`), or None if the dialog is closed.
+pub fn create_game_selection_dialog(app_ui: &AppUI, title: &str) -> Option<String> {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(title));
+    dialog.set_modal(true);
+
+    // Create the main Grid, with the ComboBox and the button below it.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let game_combobox = ComboBox::new().into_raw();
+    for (_, game) in SUPPORTED_GAMES.iter() { unsafe { game_combobox.as_mut().unwrap().add_item(&QString::from_std_str(&game.display_name)); } }
+
+    let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
+    unsafe { main_grid.as_mut().unwrap().add_widget((game_combobox as *mut Widget, 0, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 1, 0, 1, 1)); }
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+
+    if dialog.exec() == 1 {
+        let index = unsafe { game_combobox.as_mut().unwrap().current_index() };
+        SUPPORTED_GAMES.get_index(index as usize).map(|(folder_name, _)| folder_name.to_string())
+    }
+    else { None }
+}
+
+/// This function creates a dialog to ask the user to pick one of the provided items, with the provided text
+/// above them. It returns the index of the chosen item, or None if the dialog is closed.
+pub fn create_item_selection_dialog(app_ui: &AppUI, title: &str, text: &str, items: &[String]) -> Option<usize> {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(title));
+    dialog.set_modal(true);
+
+    // Create the main Grid, with the text, the ComboBox and the button below them.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let text_label = Label::new(&QString::from_std_str(text)).into_raw();
+    let item_combobox = ComboBox::new().into_raw();
+    for item in items { unsafe { item_combobox.as_mut().unwrap().add_item(&QString::from_std_str(item)); } }
+
+    let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
+    unsafe { main_grid.as_mut().unwrap().add_widget((text_label as *mut Widget, 0, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((item_combobox as *mut Widget, 1, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 2, 0, 1, 1)); }
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+
+    if dialog.exec() == 1 { Some(unsafe { item_combobox.as_mut().unwrap().current_index() } as usize) }
+    else { None }
+}
+
+/// This function creates a dialog with a TreeView of the PackFile opened for "Add from PackFile", to ask the user to pick one of
+/// its PackedFiles. The PackedFile in the provided path is selected by default, if it's in that PackFile. It returns the path of
+/// the chosen PackedFile, or None if the dialog is closed or what's selected is not a PackedFile.
+pub fn create_packed_file_extra_selection_dialog(
+    sender_qt: &Sender<Commands>,
+    sender_qt_data: &Sender<Data>,
+    receiver_qt: &Rc<RefCell<Receiver<Data>>>,
+    app_ui: &AppUI,
+    title: &str,
+    default_path: &[String],
+) -> Option<Vec<String>> {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(title));
+    dialog.set_modal(true);
+    dialog.resize((400, 600));
+
+    // Create the main Grid, with the TreeView and the button below it. The TreeView is configured like the "Add from PackFile" one.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let tree_view = TreeView::new().into_raw();
+    let tree_model = StandardItemModel::new(()).into_raw();
+    unsafe { tree_view.as_mut().unwrap().set_model(tree_model as *mut AbstractItemModel); }
+    unsafe { tree_view.as_mut().unwrap().set_header_hidden(true); }
+    unsafe { tree_view.as_mut().unwrap().set_expands_on_double_click(false); }
+    unsafe { tree_view.as_mut().unwrap().set_animated(true); }
+
+    let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
+    unsafe { main_grid.as_mut().unwrap().add_widget((tree_view as *mut Widget, 0, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 1, 0, 1, 1)); }
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+
+    update_treeview(sender_qt, sender_qt_data, receiver_qt, app_ui, tree_view, None, tree_model, TreeViewOperation::Build(true));
+
+    // If the PackFile has a PackedFile in the default path, select it. Scrolling to it expands his parents.
+    if !default_path.is_empty() {
+        let item = get_item_from_type(tree_model, &TreePathType::File(default_path.to_vec()));
+        if let TreePathType::File(ref path) = get_type_of_item(item, tree_model) {
+            if path == default_path {
+                let model_index = unsafe { tree_model.as_mut().unwrap().index_from_item(item) };
+                let selection_model = unsafe { tree_view.as_mut().unwrap().selection_model() };
+                unsafe { selection_model.as_mut().unwrap().select((&model_index, Flags::from_enum(SelectionFlag::ClearAndSelect))); }
+                unsafe { tree_view.as_mut().unwrap().scroll_to(&model_index); }
+            }
+        }
+    }
+
+    if dialog.exec() == 1 {
+        match get_item_types_from_selection(tree_view, None, tree_model).first() {
+            Some(TreePathType::File(path)) => Some(path.to_vec()),
+            _ => None,
+        }
+    }
+    else { None }
+}
+
+/// This function creates a read-only dialog to show the differences between two versions of a table (see `TableDiff`),
+/// with a section for the added rows, another for the removed ones, and another for the changed fields of each row.
+pub fn create_table_diff_dialog(app_ui: &AppUI, path: &[String], diff: &TableDiff) {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(&format!("Diff of {}", path.join("/"))));
+    dialog.set_modal(true);
+    dialog.resize((900, 700));
+
+    let headers = diff.table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
+    let added = diff.added.iter().map(|row| row.iter().map(|x| x.data_to_string()).collect()).collect::<Vec<Vec<String>>>();
+    let removed = diff.removed.iter().map(|row| row.iter().map(|x| x.data_to_string()).collect()).collect::<Vec<Vec<String>>>();
+    let changed = diff.changed.iter().flat_map(|row| {
+        let key = row.key.iter().map(|x| x.data_to_string()).collect::<Vec<String>>().join("|");
+        row.fields.iter().map(move |(field_name, old_value, new_value)| vec![key.to_owned(), field_name.to_owned(), old_value.data_to_string(), new_value.data_to_string()])
+    }).collect::<Vec<Vec<String>>>();
+    let changed_headers = ["Key", "Field", "Old Value", "New Value"].iter().map(|x| (*x).to_owned()).collect::<Vec<String>>();
+
+    // Create the main Grid, with one section for each kind of difference.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let sections = [
+        (format!("Added Rows ({})", added.len()), &headers, &added),
+        (format!("Removed Rows ({})", removed.len()), &headers, &removed),
+        (format!("Changed Rows ({})", diff.changed.len()), &changed_headers, &changed),
+    ];
+
+    for (index, (title, headers, rows)) in sections.iter().enumerate() {
+        let frame = GroupBox::new(&QString::from_std_str(title)).into_raw();
+        let grid = create_grid_layout_unsafe(frame as *mut Widget);
+        let table_view = create_read_only_table_view(headers, rows);
+        unsafe { grid.as_mut().unwrap().add_widget((table_view as *mut Widget, 0, 0, 1, 1)); }
+        unsafe { main_grid.as_mut().unwrap().add_widget((frame as *mut Widget, index as i32, 0, 1, 1)); }
+    }
+
+    dialog.exec();
+}
+
+/// This function creates a read-only dialog to show the results of a tool when they're too many for a MessageBox,
+/// with the provided text above a scrollable table with the provided headers and rows.
+pub fn create_results_dialog(app_ui: &AppUI, title: &str, text: &str, headers: &[String], rows: &[Vec<String>]) {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(title));
+    dialog.set_modal(true);
+    dialog.resize((700, 500));
+
+    // Create the main Grid, with the text and the results below it.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let mut text_label = Label::new(&QString::from_std_str(text));
+    text_label.set_word_wrap(true);
+    let table_view = create_read_only_table_view(headers, rows);
+    unsafe { main_grid.as_mut().unwrap().add_widget((text_label.into_raw() as *mut Widget, 0, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((table_view as *mut Widget, 1, 0, 1, 1)); }
+
+    dialog.exec();
+}
+
+/// This function creates a dialog like the one of `create_results_dialog()`, with a button to accept what the results show
+/// (like the changes a tool is going to do). It returns true if the user accepts them.
+pub fn ask_results_question(app_ui: &AppUI, title: &str, text: &str, headers: &[String], rows: &[Vec<String>], accept_text: &str) -> bool {
+
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(title));
+    dialog.set_modal(true);
+    dialog.resize((700, 500));
+
+    // Create the main Grid, with the text, the results below it and the buttons at the end.
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+    let mut text_label = Label::new(&QString::from_std_str(text));
+    text_label.set_word_wrap(true);
+    let table_view = create_read_only_table_view(headers, rows);
+    let accept_button = PushButton::new(&QString::from_std_str(accept_text)).into_raw();
+    let cancel_button = PushButton::new(&QString::from_std_str("Cancel")).into_raw();
+    unsafe { main_grid.as_mut().unwrap().add_widget((text_label.into_raw() as *mut Widget, 0, 0, 1, 2)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((table_view as *mut Widget, 1, 0, 1, 2)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 2, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((cancel_button as *mut Widget, 2, 1, 1, 1)); }
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+    unsafe { cancel_button.as_mut().unwrap().signals().released().connect(&dialog.slots().reject()); }
+
+    dialog.exec() == 1
+}
+
+/// This function creates a read-only TableView with the provided headers and rows, for the dialogs that show lists of results.
+fn create_read_only_table_view(headers: &[String], rows: &[Vec<String>]) -> *mut TableView {
+    let table_view = TableView::new().into_raw();
+    let model = StandardItemModel::new(()).into_raw();
+    unsafe { table_view.as_mut().unwrap().set_model(model as *mut AbstractItemModel); }
+    unsafe { table_view.as_mut().unwrap().set_edit_triggers(Flags::from_enum(EditTrigger::NoEditTriggers)); }
+    unsafe { table_view.as_mut().unwrap().set_alternating_row_colors(true); }
+
+    for row in rows {
+        let mut qlist = ListStandardItemMutPtr::new(());
+        for cell in row {
+            let mut item = StandardItem::new(&QString::from_std_str(cell));
+            item.set_editable(false);
+            unsafe { qlist.append_unsafe(&item.into_raw()); }
+        }
+        unsafe { model.as_mut().unwrap().append_row(&qlist); }
+    }
+
+    for (column, header) in headers.iter().enumerate() {
+        unsafe { model.as_mut().unwrap().set_header_data((column as i32, Orientation::Horizontal, &Variant::new0(&QString::from_std_str(header)))); }
+    }
+    unsafe { table_view.as_mut().unwrap().horizontal_header().as_mut().unwrap().resize_sections(ResizeMode::ResizeToContents); }
+    unsafe { table_view.as_mut().unwrap().horizontal_header().as_mut().unwrap().set_stretch_last_section(true); }
+    table_view
+}
+
+/// This function creates a FileDialog to ask the user for the path of a file to save, with the provided filter (`TSV Files (*.tsv)`)
+/// and default extension (`tsv`). If the user cancels the dialog, it returns None.
+pub fn get_save_file_path(app_ui: &AppUI, title: &str, name_filter: &str, default_suffix: &str) -> Option<PathBuf> {
+
+    let mut file_dialog = unsafe { FileDialog::new_unsafe((
+        app_ui.window as *mut Widget,
+        &QString::from_std_str(title),
+    )) };
+
+    file_dialog.set_accept_mode(qt_widgets::file_dialog::AcceptMode::Save);
+    file_dialog.set_confirm_overwrite(true);
+    file_dialog.set_name_filter(&QString::from_std_str(name_filter));
+    file_dialog.set_default_suffix(&QString::from_std_str(default_suffix));
+
+    if file_dialog.exec() == 1 { Some(PathBuf::from(file_dialog.selected_files().at(0).to_std_string())) }
+    else { None }
+}
+
+/// This function asks the user a Yes/No question, returning true if the answer is yes.
+pub fn ask_question(app_ui: &AppUI, question: &str) -> bool {
+    let mut dialog = unsafe { MessageBox::new_unsafe((
+        &QString::from_std_str("Rusted PackFile Manager"),
+        &QString::from_std_str(question),
+        Icon::Question,
+        65536, // No
+        16384, // Yes
+        1, // By default, select yes.
+        app_ui.window as *mut Widget,
+    )) };
+
+    // Run the dialog and get the response. Yes => 3, No => 4.
+    dialog.exec() == 3
+}
+
+/// This function shows the first errors found in the rows of the TSV files we're importing, and asks the user
+/// if he wants to import them anyway, skipping those rows. It returns true if the answer is yes.
+pub fn ask_import_tsv_anyway(app_ui: &AppUI, errors: &[String]) -> bool {
+    let mut message = format!("<p>{} errors have been found in the TSV files:</p><ul>{}</ul>", errors.len(), errors.iter().take(TSV_ERRORS_TO_SHOW).map(|x| format!("<li>{}</li>", x)).collect::<String>());
+    if errors.len() > TSV_ERRORS_TO_SHOW { message.push_str(&format!("<p>And {} more.</p>", errors.len() - TSV_ERRORS_TO_SHOW)); }
+    message.push_str("<p>Do you want to import them anyway, skipping the rows with errors?</p>");
+    ask_question(app_ui, &message)
+}
+
 //----------------------------------------------------------------------------//
 //                    Enums & Structs needed for the UI
 //----------------------------------------------------------------------------//
@@ -715,6 +1153,12 @@ pub fn show_dialog<T: Display>(
     dialog.exec();
 }
 
+/// This function shows in the status bar the report of a change not done because we are in "Dry Run" mode.
+/// It's used for the changes done while editing a PackedFile, where a dialog would pop up on every edit.
+pub fn show_dry_run_report(window: *mut MainWindow, report: &str) {
+    unsafe { window.as_mut().unwrap().status_bar().as_mut().unwrap().show_message(&QString::from_std_str(report)); }
+}
+
 /// This function deletes whatever it's in the right side of the screen, leaving it empty.
 /// Also, each time this triggers we consider there is no PackedFile open.
 pub fn purge_them_all(app_ui: &AppUI, packedfiles_open_in_packedfile_view: &Rc<RefCell<BTreeMap<i32, Rc<RefCell<Vec<String>>>>>>) {
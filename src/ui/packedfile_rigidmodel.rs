@@ -295,6 +295,12 @@ impl PackedFileRigidModelDataView {
                     // Tell the background thread to start saving the PackedFile.
                     sender_qt.send(Commands::EncodePackedFileRigidModel).unwrap();
                     sender_qt_data.send(Data::RigidModelVecString((packed_file.borrow().clone(), packed_file_path.borrow().to_vec()))).unwrap();
+                    match check_message_validity_tryrecv(&receiver_qt) {
+                        Data::Success => {},
+                        Data::String(report) => return show_dry_run_report(app_ui.window, &report),
+                        Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
 
                     update_treeview(
                         &sender_qt,
@@ -79,6 +79,9 @@ pub struct PackedFileDBDecoder {
     pub slot_generate_pretty_diff: SlotNoArgs<'static>,
     pub slot_remove_all_fields: SlotNoArgs<'static>,
     pub slot_save_definition: SlotNoArgs<'static>,
+    pub slot_load_from_assembly_kit: SlotNoArgs<'static>,
+    pub slot_guess_fields: SlotNoArgs<'static>,
+    pub slot_test_definition: SlotNoArgs<'static>,
     pub slot_table_view_old_versions_context_menu_enabler: SlotItemSelectionRefItemSelectionRef<'static>,
     pub slot_table_view_old_versions_context_menu: SlotQtCorePointRef<'static>,
     pub slot_table_view_old_versions_context_menu_load: SlotBool<'static>,
@@ -124,6 +127,7 @@ pub struct PackedFileDBDecoderStuff {
     pub table_info_type_decoded_label: *mut Label,
     pub table_info_version_decoded_label: *mut Label,
     pub table_info_entry_count_decoded_label: *mut Label,
+    pub table_info_decoding_status_decoded_label: *mut Label,
 
     pub table_view_old_versions: *mut TableView,
     pub table_model_old_versions: *mut StandardItemModel,
@@ -131,6 +135,9 @@ pub struct PackedFileDBDecoderStuff {
     pub generate_pretty_diff_button: *mut PushButton,
     pub clear_definition_button: *mut PushButton,
     pub save_button: *mut PushButton,
+    pub load_from_assembly_kit_button: *mut PushButton,
+    pub guess_fields_button: *mut PushButton,
+    pub test_definition_button: *mut PushButton,
 
     pub table_view_context_menu: *mut Menu,
     pub table_view_context_menu_move_up: *mut Action,
@@ -146,6 +153,7 @@ pub struct PackedFileDBDecoderStuff {
 #[derive(Clone)]
 pub struct PackedFileDBDecoderStuffNonUI {
     pub packed_file_path: Vec<String>,
+    pub table_name: String,
     pub packed_file_data: Vec<u8>,
     pub initial_index: usize,
     pub version: i32,
@@ -349,18 +357,23 @@ impl PackedFileDBDecoder {
         let table_info_type_label = Label::new(&QString::from_std_str("Table type:")).into_raw();
         let table_info_version_label = Label::new(&QString::from_std_str("Table version:")).into_raw();
         let table_info_entry_count_label = Label::new(&QString::from_std_str("Table entry count:")).into_raw();
+        let table_info_decoding_status_label = Label::new(&QString::from_std_str("Decoding status:")).into_raw();
 
         let table_info_type_decoded_label = Label::new(()).into_raw();
         let table_info_version_decoded_label = Label::new(()).into_raw();
         let table_info_entry_count_decoded_label = Label::new(()).into_raw();
+        let table_info_decoding_status_decoded_label = Label::new(()).into_raw();
+        unsafe { table_info_decoding_status_decoded_label.as_mut().unwrap().set_word_wrap(true); }
 
         unsafe { info_layout.as_mut().unwrap().add_widget((table_info_type_label as *mut Widget, 0, 0, 1, 1)); }
         unsafe { info_layout.as_mut().unwrap().add_widget((table_info_version_label as *mut Widget, 1, 0, 1, 1)); }
         unsafe { info_layout.as_mut().unwrap().add_widget((table_info_entry_count_label as *mut Widget, 2, 0, 1, 1)); }
+        unsafe { info_layout.as_mut().unwrap().add_widget((table_info_decoding_status_label as *mut Widget, 3, 0, 1, 1)); }
 
         unsafe { info_layout.as_mut().unwrap().add_widget((table_info_type_decoded_label as *mut Widget, 0, 1, 1, 1)); }
         unsafe { info_layout.as_mut().unwrap().add_widget((table_info_version_decoded_label as *mut Widget, 1, 1, 1, 1)); }
         unsafe { info_layout.as_mut().unwrap().add_widget((table_info_entry_count_decoded_label as *mut Widget, 2, 1, 1, 1)); }
+        unsafe { info_layout.as_mut().unwrap().add_widget((table_info_decoding_status_decoded_label as *mut Widget, 3, 1, 1, 1)); }
 
         // Create the TableView at the top.
         let table_view_old_versions = TableView::new().into_raw();
@@ -413,11 +426,17 @@ impl PackedFileDBDecoder {
         let generate_pretty_diff_button = PushButton::new(&QString::from_std_str("Generate Diff")).into_raw();
         let clear_definition_button = PushButton::new(&QString::from_std_str("Remove all fields")).into_raw();
         let save_button = PushButton::new(&QString::from_std_str("Finish it!")).into_raw();
+        let load_from_assembly_kit_button = PushButton::new(&QString::from_std_str("Load from Assembly Kit")).into_raw();
+        let guess_fields_button = PushButton::new(&QString::from_std_str("Auto-guess")).into_raw();
+        let test_definition_button = PushButton::new(&QString::from_std_str("Test against all")).into_raw();
 
         // Add them to the Dialog.
         unsafe { button_box_layout.as_mut().unwrap().add_widget((generate_pretty_diff_button as *mut Widget, 0, 0, 1, 1)); }
         unsafe { button_box_layout.as_mut().unwrap().add_widget((clear_definition_button as *mut Widget, 0, 1, 1, 1)); }
         unsafe { button_box_layout.as_mut().unwrap().add_widget((save_button as *mut Widget, 0, 2, 1, 1)); }
+        unsafe { button_box_layout.as_mut().unwrap().add_widget((guess_fields_button as *mut Widget, 1, 0, 1, 1)); }
+        unsafe { button_box_layout.as_mut().unwrap().add_widget((test_definition_button as *mut Widget, 1, 1, 1, 1)); }
+        unsafe { button_box_layout.as_mut().unwrap().add_widget((load_from_assembly_kit_button as *mut Widget, 1, 2, 1, 1)); }
 
         // Add everything to the main grid.
         unsafe { widget_layout.as_mut().unwrap().add_widget((hex_view_group as *mut Widget, 0, 0, 5, 1)); }
@@ -447,6 +466,14 @@ impl PackedFileDBDecoder {
         if packed_file.path.len() == 3 {
             if packed_file.path[0] == "db" {
 
+                // If the table's folder has an alias, we have to decode it with the definition of the aliased table.
+                sender_qt.send(Commands::GetTableDefinitionName).unwrap();
+                sender_qt_data.send(Data::String(packed_file.path[1].to_owned())).unwrap();
+                let table_name = match check_message_validity_recv2(&receiver_qt) {
+                    Data::String(data) => data,
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                };
+
                 // Put all together so we can pass it easely.
                 let stuff = PackedFileDBDecoderStuff {
                     hex_view_index,
@@ -481,11 +508,15 @@ impl PackedFileDBDecoder {
                     table_info_type_decoded_label,
                     table_info_version_decoded_label,
                     table_info_entry_count_decoded_label,
+                    table_info_decoding_status_decoded_label,
                     table_view_old_versions,
                     table_model_old_versions,
                     generate_pretty_diff_button,
                     clear_definition_button,
                     save_button,
+                    load_from_assembly_kit_button,
+                    guess_fields_button,
+                    test_definition_button,
                     table_view_context_menu: table_view_context_menu.into_raw(),
                     table_view_context_menu_move_up,
                     table_view_context_menu_move_down,
@@ -505,6 +536,7 @@ impl PackedFileDBDecoder {
                         // Put all the "Non UI" data we need to keep together.
                         let stuff_non_ui = PackedFileDBDecoderStuffNonUI {
                             packed_file_path: packed_file.path.to_vec(),
+                            table_name,
                             packed_file_data,
                             initial_index,
                             version,
@@ -522,7 +554,7 @@ impl PackedFileDBDecoder {
                             Some(schema) => {
 
                                 // Get the table definition for this table (or create a new one).
-                                let table_definition = match DB::get_schema(&stuff_non_ui.packed_file_path[1], stuff_non_ui.version, &schema) {
+                                let table_definition = match DB::get_schema(&stuff_non_ui.table_name, stuff_non_ui.version, &schema) {
                                     Some(table_definition) => Rc::new(RefCell::new(table_definition)),
                                     None => Rc::new(RefCell::new(TableDefinition::new(stuff_non_ui.version)))
                                 };
@@ -535,7 +567,7 @@ impl PackedFileDBDecoder {
                                 Self::load_data_to_decoder_view(&stuff, &stuff_non_ui);
 
                                 // Update the versions list.
-                                Self::update_versions_list(&stuff, &schema, &stuff_non_ui.packed_file_path[1]);
+                                Self::update_versions_list(&stuff, &schema, &stuff_non_ui.table_name);
 
                                 // Update the Decoder View's Dynamic Data (LineEdits, Table,...) and recalculate
                                 // the current "index_data" (position in the vector we are decoding).
@@ -954,7 +986,7 @@ impl PackedFileDBDecoder {
                                             // We get the index of our table's definitions. In case we find it, we just return it. If it's not
                                             // the case, then we create a new table's definitions and return his index. To know if we didn't found
                                             // an index, we just return -1 as index.
-                                            let mut table_definitions_index = match schema.borrow().get_table_definitions(&stuff_non_ui.packed_file_path[1]) {
+                                            let mut table_definitions_index = match schema.borrow().get_table_definitions(&stuff_non_ui.table_name) {
                                                 Some(table_definitions_index) => table_definitions_index as i32,
                                                 None => -1i32,
                                             };
@@ -963,10 +995,18 @@ impl PackedFileDBDecoder {
                                             if table_definitions_index == -1 {
 
                                                 // We create one.
-                                                schema.borrow_mut().add_table_definitions(TableDefinitions::new(&stuff_non_ui.packed_file_path[1]));
+                                                schema.borrow_mut().add_table_definitions(TableDefinitions::new(&stuff_non_ui.table_name));
 
                                                 // And get his index.
-                                                table_definitions_index = schema.borrow().get_table_definitions(&stuff_non_ui.packed_file_path[1]).unwrap() as i32;
+                                                table_definitions_index = schema.borrow().get_table_definitions(&stuff_non_ui.table_name).unwrap() as i32;
+                                            }
+
+                                            // Before saving it, make sure it decodes all the tables we have with the same version. If not, warn the user.
+                                            let failures = Self::test_definition(&sender_qt, &sender_qt_data, &receiver_qt, &stuff, &stuff_non_ui).into_iter().filter(|x| x.error.is_some()).collect::<Vec<DefinitionTestResult>>();
+                                            if !failures.is_empty() {
+                                                let text = format!("This definition fails to decode {} tables with the same version. Are you sure you want to save it?", failures.len());
+                                                let (headers, rows) = Self::get_test_failures_table(&failures);
+                                                if !ask_results_question(&app_ui, "Test Definition", &text, &headers, &rows, "Save") { return }
                                             }
 
                                             // We replace his fields with the ones from the TableView.
@@ -977,19 +1017,127 @@ impl PackedFileDBDecoder {
                                             schema.borrow_mut().tables_definitions.sort_unstable_by(|a, b| a.name.cmp(&b.name));
                                             schema.borrow_mut().tables_definitions.iter_mut().for_each(|x| x.versions.sort_unstable_by(|a, b| b.version.cmp(&a.version)));
 
-                                            // Send it back to the background thread for saving it.
-                                            sender_qt.send(Commands::SaveSchema).unwrap();
-                                            sender_qt_data.send(Data::Schema(schema.borrow().clone())).unwrap();
+                                            // Send it back to the background thread for saving it in the local patch of the schema, so it's not lost when the schema is updated.
+                                            sender_qt.send(Commands::SaveSchemaPatch).unwrap();
+                                            sender_qt_data.send(Data::VecStringTableDefinition((stuff_non_ui.packed_file_path.to_vec(), table_definition.borrow().clone()))).unwrap();
 
                                             // Report success while saving it, or an error.
                                             match check_message_validity_recv2(&receiver_qt) {
-                                                Data::Success => show_dialog(app_ui.window, true, "Schema successfully saved."),
+                                                Data::Success => show_dialog(app_ui.window, true, "Definition successfully saved in the local patch of the schema."),
                                                 Data::Error(error) => show_dialog(app_ui.window, false, error),
                                                 _ => panic!(THREADS_MESSAGE_ERROR),
                                             }
 
                                             // After all that, we need to update the version list, as this may have created a new version.
-                                            Self::update_versions_list(&stuff, &schema.borrow(), &stuff_non_ui.packed_file_path[1]);
+                                            Self::update_versions_list(&stuff, &schema.borrow(), &stuff_non_ui.table_name);
+                                        }
+                                    )),
+
+                                    // Slot for the "Load from Assembly Kit" button. It replaces the current fields with the ones of the
+                                    // Assembly Kit's definition of this table, so we only have to check them instead of decoding them by hand.
+                                    slot_load_from_assembly_kit: SlotNoArgs::new(clone!(
+                                        sender_qt,
+                                        sender_qt_data,
+                                        receiver_qt,
+                                        app_ui,
+                                        index,
+                                        stuff,
+                                        stuff_non_ui => move || {
+                                            sender_qt.send(Commands::GetAssemblyKitDefinition).unwrap();
+                                            sender_qt_data.send(Data::StringI32((stuff_non_ui.table_name.to_owned(), stuff_non_ui.version))).unwrap();
+                                            let table_definition = match check_message_validity_recv2(&receiver_qt) {
+                                                Data::TableDefinition(table_definition) => table_definition,
+                                                Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                                                _ => panic!(THREADS_MESSAGE_ERROR),
+                                            };
+
+                                            // Remove everything from the model.
+                                            unsafe { stuff.table_model.as_mut().unwrap().clear(); }
+
+                                            // Reset the index.
+                                            *index.borrow_mut() = stuff_non_ui.initial_index;
+
+                                            // Update the decoder view.
+                                            Self::update_decoder_view(&stuff, &stuff_non_ui, (true, &table_definition.fields), &mut index.borrow_mut());
+                                        }
+                                    )),
+
+                                    // Slot for the "Auto-guess" button. It adds after the current fields the ones we think are needed to decode the
+                                    // entire table, painting their types depending on how sure we are about them, so they can be checked by hand.
+                                    // The guess can take a while with big tables, so it's done in the background thread.
+                                    slot_guess_fields: SlotNoArgs::new(clone!(
+                                        sender_qt,
+                                        sender_qt_data,
+                                        receiver_qt,
+                                        app_ui,
+                                        index,
+                                        stuff,
+                                        stuff_non_ui => move || {
+                                            let mut table_definition = TableDefinition::new(stuff_non_ui.version);
+                                            table_definition.fields = Self::return_data_from_data_view(&stuff);
+
+                                            unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                                            sender_qt.send(Commands::GuessTableFields).unwrap();
+                                            sender_qt_data.send(Data::VecStringTableDefinition((stuff_non_ui.packed_file_path.to_vec(), table_definition))).unwrap();
+                                            let response = check_message_validity_tryrecv(&receiver_qt);
+                                            unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+                                            let guesses = match response {
+                                                Data::VecFieldGuess(guesses) => guesses,
+                                                Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                                                _ => panic!(THREADS_MESSAGE_ERROR),
+                                            };
+                                            if guesses.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::DBTableFieldsCannotBeGuessed); }
+
+                                            for guess in &guesses {
+                                                Self::add_field_to_data_view(
+                                                    &stuff,
+                                                    &stuff_non_ui,
+                                                    "new_field",
+                                                    guess.field_type,
+                                                    false,
+                                                    &None,
+                                                    "",
+                                                    &mut index.borrow_mut(),
+                                                );
+
+                                                // Green means the values of the field look like his type in all the rows, yellow that some of them
+                                                // are odd, and red that they could be anything (like all zeros).
+                                                let dark_theme = SETTINGS.lock().unwrap().settings_bool["use_dark_theme"];
+                                                let colour = match guess.confidence {
+                                                    GuessConfidence::High => if dark_theme { GlobalColor::DarkGreen } else { GlobalColor::Green },
+                                                    GuessConfidence::Medium => if dark_theme { GlobalColor::DarkYellow } else { GlobalColor::Yellow },
+                                                    GuessConfidence::Low => if dark_theme { GlobalColor::DarkRed } else { GlobalColor::Red },
+                                                };
+                                                let row = unsafe { stuff.table_model.as_ref().unwrap().row_count(()) - 1 };
+                                                let item = unsafe { stuff.table_model.as_mut().unwrap().item((row, 1)) };
+                                                unsafe { item.as_mut().unwrap().set_background(&Brush::new(colour)); }
+                                                unsafe { item.as_mut().unwrap().set_tool_tip(&QString::from_std_str(format!("Guessed with {} confidence.", guess.confidence))); }
+                                            }
+
+                                            // Update the decoder view.
+                                            Self::update_decoder_view(&stuff, &stuff_non_ui, (false, &[]), &mut index.borrow_mut());
+                                        }
+                                    )),
+
+                                    // Slot for the "Test against all" button.
+                                    slot_test_definition: SlotNoArgs::new(clone!(
+                                        sender_qt,
+                                        sender_qt_data,
+                                        receiver_qt,
+                                        app_ui,
+                                        stuff,
+                                        stuff_non_ui => move || {
+                                            unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(false); }
+                                            let results = Self::test_definition(&sender_qt, &sender_qt_data, &receiver_qt, &stuff, &stuff_non_ui);
+                                            unsafe { (app_ui.window.as_mut().unwrap() as &mut Widget).set_enabled(true); }
+
+                                            let failures = results.iter().filter(|x| x.error.is_some()).cloned().collect::<Vec<DefinitionTestResult>>();
+                                            if failures.is_empty() { show_dialog(app_ui.window, true, format!("This definition decodes the {} tables with the same version in the open PackFile and the dependency PackFiles. You can save it with <i>Finish it!</i>.", results.len())); }
+                                            else {
+                                                let text = format!("This definition fails to decode {} of the {} tables with the same version in the open PackFile and the dependency PackFiles:", failures.len(), results.len());
+                                                let (headers, rows) = Self::get_test_failures_table(&failures);
+                                                create_results_dialog(&app_ui, "Test Definition", &text, &headers, &rows);
+                                            }
                                         }
                                     )),
 
@@ -1039,7 +1187,7 @@ impl PackedFileDBDecoder {
                                                 let version = version.parse::<i32>().unwrap();
 
                                                 // Get the new definition.
-                                                let table_definition = DB::get_schema(&stuff_non_ui.packed_file_path[1], version, &*schema.borrow());
+                                                let table_definition = DB::get_schema(&stuff_non_ui.table_name, version, &*schema.borrow());
 
                                                 // Remove everything from the model.
                                                 unsafe { stuff.table_model.as_mut().unwrap().clear(); }
@@ -1077,12 +1225,12 @@ impl PackedFileDBDecoder {
                                                 let version = version.parse::<i32>().unwrap();
 
                                                 // Try to remove that version form the schema.
-                                                if let Err(error) = DB::remove_table_version(&stuff_non_ui.packed_file_path[1], version, &mut schema.borrow_mut()) {
+                                                if let Err(error) = DB::remove_table_version(&stuff_non_ui.table_name, version, &mut schema.borrow_mut()) {
                                                     return show_dialog(app_ui.window, false, error.kind());
                                                 }
 
                                                 // If it worked, update the list.
-                                                Self::update_versions_list(&stuff, &schema.borrow(), &stuff_non_ui.packed_file_path[1]);
+                                                Self::update_versions_list(&stuff, &schema.borrow(), &stuff_non_ui.table_name);
                                             }
                                         }
                                     )),
@@ -1129,6 +1277,9 @@ impl PackedFileDBDecoder {
                                 unsafe { stuff.generate_pretty_diff_button.as_mut().unwrap().signals().released().connect(&slots.slot_generate_pretty_diff); }
                                 unsafe { stuff.clear_definition_button.as_mut().unwrap().signals().released().connect(&slots.slot_remove_all_fields); }
                                 unsafe { stuff.save_button.as_mut().unwrap().signals().released().connect(&slots.slot_save_definition); }
+                                unsafe { stuff.load_from_assembly_kit_button.as_mut().unwrap().signals().released().connect(&slots.slot_load_from_assembly_kit); }
+                                unsafe { stuff.guess_fields_button.as_mut().unwrap().signals().released().connect(&slots.slot_guess_fields); }
+                                unsafe { stuff.test_definition_button.as_mut().unwrap().signals().released().connect(&slots.slot_test_definition); }
 
                                 // Actions for the Contextual Menu in the "Versions" table.
                                 unsafe { stuff.table_view_old_versions.as_mut().unwrap().selection_model().as_ref().unwrap().signals().selection_changed().connect(&slots.slot_table_view_old_versions_context_menu_enabler); }
@@ -1297,7 +1448,7 @@ impl PackedFileDBDecoder {
         }
 
         // Load the "Info" data to the view.
-        unsafe { stuff.table_info_type_decoded_label.as_mut().unwrap().set_text(&QString::from_std_str(&stuff_non_ui.packed_file_path[1])); }
+        unsafe { stuff.table_info_type_decoded_label.as_mut().unwrap().set_text(&QString::from_std_str(&stuff_non_ui.table_name)); }
         unsafe { stuff.table_info_version_decoded_label.as_mut().unwrap().set_text(&QString::from_std_str(format!("{}", stuff_non_ui.version))); }
         unsafe { stuff.table_info_entry_count_decoded_label.as_mut().unwrap().set_text(&QString::from_std_str(format!("{}", stuff_non_ui.entry_count))); }
     }
@@ -1460,6 +1611,17 @@ impl PackedFileDBDecoder {
         unsafe { stuff.optional_string_u8_line_edit.as_mut().unwrap().set_text(&QString::from_std_str(&format!("{:?}", decoded_optional_string_u8))); }
         unsafe { stuff.optional_string_u16_line_edit.as_mut().unwrap().set_text(&QString::from_std_str(&format!("{:?}", decoded_optional_string_u16))); }
 
+        // Try to decode the entire table with the current fields, so we know where the definition breaks, if it breaks.
+        let fields = Self::return_data_from_data_view(stuff);
+        let mut index_table = stuff_non_ui.initial_index;
+        let decoding_status = match decode_table_entries(&stuff_non_ui.packed_file_data, &mut index_table, stuff_non_ui.entry_count, &fields) {
+            Ok(_) if fields.is_empty() => "No fields to decode.".to_owned(),
+            Ok(_) if index_table == stuff_non_ui.packed_file_data.len() => format!("All {} rows decoded.", stuff_non_ui.entry_count),
+            Ok(_) => format!("All {} rows decoded, but there are {} bytes left.", stuff_non_ui.entry_count, stuff_non_ui.packed_file_data.len() - index_table),
+            Err(error) => error.to_string(),
+        };
+        unsafe { stuff.table_info_decoding_status_decoded_label.as_mut().unwrap().set_text(&QString::from_std_str(decoding_status)); }
+
         // Prepare the format for the cleaning.
         let mut neutral_format = TextCharFormat::new();
         neutral_format.set_background(&Brush::new(GlobalColor::Transparent));
@@ -1659,6 +1821,8 @@ impl PackedFileDBDecoder {
             FieldType::Float => "Float",
             FieldType::Integer => "Integer",
             FieldType::LongInteger => "LongInteger",
+            FieldType::Double => "Double",
+            FieldType::Colour => "Colour",
             FieldType::StringU8 => "StringU8",
             FieldType::StringU16 => "StringU16",
             FieldType::OptionalStringU8 => "OptionalStringU8",
@@ -1750,6 +1914,8 @@ impl PackedFileDBDecoder {
         list.append(&QString::from_std_str("Float"));
         list.append(&QString::from_std_str("Integer"));
         list.append(&QString::from_std_str("LongInteger"));
+        list.append(&QString::from_std_str("Double"));
+        list.append(&QString::from_std_str("Colour"));
         list.append(&QString::from_std_str("StringU8"));
         list.append(&QString::from_std_str("StringU16"));
         list.append(&QString::from_std_str("OptionalStringU8"));
@@ -1812,6 +1978,24 @@ impl PackedFileDBDecoder {
                 }
                 else { "Error".to_owned() }
             },
+            FieldType::Double => {
+                if field_data.get(*index_data + 7).is_some() {
+                    match coding_helpers::decode_packedfile_float_f64(&field_data[*index_data..(*index_data + 8)], &mut index_data) {
+                        Ok(result) => result.to_string(),
+                        Err(_) => "Error".to_owned(),
+                    }
+                }
+                else { "Error".to_owned() }
+            },
+            FieldType::Colour => {
+                if field_data.get(*index_data + 3).is_some() {
+                    match coding_helpers::decode_packedfile_integer_u32(&field_data[*index_data..(*index_data + 4)], &mut index_data) {
+                        Ok(result) => format!("{:06X}", result),
+                        Err(_) => "Error".to_owned(),
+                    }
+                }
+                else { "Error".to_owned() }
+            },
             FieldType::StringU8 => {
                 if field_data.get(*index_data + 1).is_some() {
                     match coding_helpers::decode_packedfile_string_u8(&field_data[*index_data..], &mut index_data) {
@@ -1918,6 +2102,8 @@ impl PackedFileDBDecoder {
                     "Float" => FieldType::Float,
                     "Integer" => FieldType::Integer,
                     "LongInteger" => FieldType::LongInteger,
+                    "Double" => FieldType::Double,
+                    "Colour" => FieldType::Colour,
                     "StringU8" => FieldType::StringU8,
                     "StringU16" => FieldType::StringU16,
                     "OptionalStringU8" => FieldType::OptionalStringU8,
@@ -1963,6 +2149,37 @@ impl PackedFileDBDecoder {
         invalid_types
     }
 
+    /// This function decodes with the fields in the decoder every table with the same folder and version in the open PackFile
+    /// and in the dependency PackFiles, and returns the result for each one of them.
+    fn test_definition(
+        sender_qt: &Sender<Commands>,
+        sender_qt_data: &Sender<Data>,
+        receiver_qt: &Rc<RefCell<Receiver<Data>>>,
+        stuff: &PackedFileDBDecoderStuff,
+        stuff_non_ui: &PackedFileDBDecoderStuffNonUI,
+    ) -> Vec<DefinitionTestResult> {
+        let mut table_definition = TableDefinition::new(stuff_non_ui.version);
+        table_definition.fields = Self::return_data_from_data_view(stuff);
+
+        sender_qt.send(Commands::TestTableDefinition).unwrap();
+        sender_qt_data.send(Data::VecStringTableDefinition((stuff_non_ui.packed_file_path.to_vec(), table_definition))).unwrap();
+        match check_message_validity_recv2(receiver_qt) {
+            Data::VecDefinitionTestResult(results) => results,
+            _ => panic!(THREADS_MESSAGE_ERROR),
+        }
+    }
+
+    /// This function returns the headers and rows to show the tables that failed to decode in a test of a definition, with the reason of each failure.
+    fn get_test_failures_table(failures: &[DefinitionTestResult]) -> (Vec<String>, Vec<Vec<String>>) {
+        let headers = vec!["PackedFile".to_owned(), "Source".to_owned(), "Error".to_owned()];
+        let rows = failures.iter().map(|x| vec![
+            x.path.join("/"),
+            if x.is_dependency { "Dependency PackFiles" } else { "Open PackFile" }.to_owned(),
+            x.error.to_owned().unwrap_or_default(),
+        ]).collect();
+        (headers, rows)
+    }
+
     /// This function gets the data from the decoder's table, and returns it, so we can save it in a TableDefinition.
     pub fn return_data_from_data_view(
         stuff: &PackedFileDBDecoderStuff
@@ -2005,6 +2222,8 @@ impl PackedFileDBDecoder {
                     "Float" => FieldType::Float,
                     "Integer" => FieldType::Integer,
                     "LongInteger" => FieldType::LongInteger,
+                    "Double" => FieldType::Double,
+                    "Colour" => FieldType::Colour,
                     "StringU8" => FieldType::StringU8,
                     "StringU16" => FieldType::StringU16,
                     "OptionalStringU8" => FieldType::OptionalStringU8,
@@ -12,16 +12,20 @@
 // to reduce duplicated code. It also houses the DB Decoder, because thatś 
 // related with the tables.
 
+use qt_widgets::abstract_item_view::ScrollHint;
 use qt_widgets::action::Action;
+use qt_widgets::color_dialog::ColorDialog;
 use qt_widgets::file_dialog::FileDialog;
 use qt_widgets::header_view::ResizeMode;
 use qt_widgets::menu::Menu;
 use qt_widgets::label::Label;
+use qt_widgets::list_view::ListView;
 use qt_widgets::slots::{SlotQtCorePointRef, SlotCIntQtCoreQtSortOrder};
 use qt_widgets::table_view::TableView;
 use qt_widgets::scroll_area::ScrollArea;
 use qt_widgets::widget::Widget;
 
+use qt_gui::color::Color;
 use qt_gui::cursor::Cursor;
 use qt_gui::gui_application::GuiApplication;
 use qt_gui::key_sequence::KeySequence;
@@ -40,22 +44,25 @@ use qt_core::item_selection::ItemSelection;
 use qt_core::item_selection_model::SelectionFlag;
 use qt_core::object::Object;
 use qt_core::reg_exp::RegExp;
-use qt_core::slots::{SlotBool, SlotCInt, SlotStringRef, SlotItemSelectionRefItemSelectionRef, SlotModelIndexRefModelIndexRefVectorVectorCIntRef};
+use qt_core::timer::Timer;
+use qt_core::slots::{SlotBool, SlotCInt, SlotModelIndexRef, SlotStringRef, SlotItemSelectionRefItemSelectionRef, SlotModelIndexRefCIntCInt, SlotModelIndexRefModelIndexRefVectorVectorCIntRef};
 use qt_core::string_list::StringList;
 use qt_core::qt::{AlignmentFlag, CaseSensitivity, CheckState, ShortcutContext, SortOrder, GlobalColor, MatchFlag};
 
 use regex::Regex;
-use meval;
 
 use std::collections::BTreeMap;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::FROZEN_KEY_COLUMNS;
+use crate::GAME_SELECTED;
 use crate::TABLE_STATES_UI;
 use crate::QString;
 use crate::ui::*;
 use crate::packedfile::db::DB;
 use crate::packedfile::loc::Loc;
+use crate::settings::Settings;
 use crate::ui::qt_custom_stuff::*;
 use crate::ui::table_state::*;
 use crate::ui::packedfile_table::packedfile_table_undo::*;
@@ -65,9 +72,13 @@ pub mod db_decoder;
 pub mod packedfile_db;
 pub mod packedfile_loc;
 pub mod dependency_manager;
-mod packedfile_table_extras;
+pub mod packedfile_table_extras;
 mod packedfile_table_undo;
 
+// This tells the compiler to only compile this mod when testing.
+#[cfg(test)]
+mod tests;
+
 //----------------------------------------------------------------//
 // Generic Enums and Structs for DB/LOC PackedFiles.
 //----------------------------------------------------------------//
@@ -111,26 +122,61 @@ pub struct PackedFileTableView {
     pub slot_context_menu_enabler: SlotItemSelectionRefItemSelectionRef<'static>,
     pub save_changes: SlotModelIndexRefModelIndexRefVectorVectorCIntRef<'static>,
     pub slot_item_changed: SlotStandardItemMutPtr<'static>,
+    pub slot_run_table_checks: SlotNoArgs<'static>,
+    pub slot_schedule_table_checks: SlotModelIndexRefCIntCInt<'static>,
+    pub slot_schedule_table_checks_on_edit: SlotModelIndexRefModelIndexRefVectorVectorCIntRef<'static>,
+    pub slot_update_selection_stats: SlotNoArgs<'static>,
+    pub slot_schedule_selection_stats: SlotItemSelectionRefItemSelectionRef<'static>,
+    pub slot_schedule_selection_stats_rows: SlotModelIndexRefCIntCInt<'static>,
     pub slot_row_filter_change_text: SlotStringRef<'static>,
     pub slot_row_filter_change_column: SlotCInt<'static>,
     pub slot_row_filter_change_case_sensitive: SlotBool<'static>,
+    pub slot_column_filter_change_text: SlotStringRef<'static>,
+    pub slot_apply_column_filters: SlotNoArgs<'static>,
+    pub slot_update_column_filters_geometry: SlotNoArgs<'static>,
+    pub slot_update_row_filter_column_selector: SlotNoArgs<'static>,
     pub slot_context_menu_add: SlotBool<'static>,
     pub slot_context_menu_insert: SlotBool<'static>,
     pub slot_context_menu_delete: SlotBool<'static>,
     pub slot_context_menu_apply_maths_to_selection: SlotBool<'static>,
     pub slot_context_menu_rewrite_selection: SlotBool<'static>,
+    pub slot_context_menu_toggle_booleans: SlotBool<'static>,
+    pub slot_context_menu_pick_colour: SlotBool<'static>,
+    pub slot_context_menu_pick_reference: SlotBool<'static>,
+    pub slot_context_menu_edit_in_window: SlotBool<'static>,
+    pub slot_context_menu_go_to_definition: SlotBool<'static>,
+    pub slot_context_menu_find_usages: SlotBool<'static>,
     pub slot_context_menu_clone: SlotBool<'static>,
     pub slot_context_menu_clone_and_append: SlotBool<'static>,
     pub slot_context_menu_copy: SlotBool<'static>,
     pub slot_context_menu_copy_as_lua_table: SlotBool<'static>,
+    pub slot_context_menu_copy_as_markdown_table: SlotBool<'static>,
+    pub slot_context_menu_copy_as_json: SlotBool<'static>,
+    pub slot_context_menu_copy_with_headers: SlotBool<'static>,
     pub slot_context_menu_paste: SlotBool<'static>,
     pub slot_context_menu_paste_as_new_lines: SlotBool<'static>,
     pub slot_context_menu_paste_to_fill_selection: SlotBool<'static>,
     pub slot_context_menu_selection_invert: SlotBool<'static>,
+    pub slot_context_menu_add_highlight_rule: SlotBool<'static>,
+    pub slot_context_menu_clear_highlight_rules: SlotBool<'static>,
     pub slot_context_menu_search: SlotBool<'static>,
+    pub slot_context_menu_go_to_row: SlotBool<'static>,
     pub slot_context_menu_sidebar: SlotBool<'static>,
+    pub slot_context_menu_history: SlotBool<'static>,
+    pub slot_history_go_to: SlotModelIndexRef<'static>,
+    pub slot_context_menu_column_filters: SlotBool<'static>,
+    pub slot_context_menu_freeze_key_columns: SlotBool<'static>,
+    pub slot_context_menu_vanilla_diff: SlotBool<'static>,
+    pub slot_context_menu_revert_cell_to_vanilla: SlotBool<'static>,
+    pub slot_context_menu_revert_row_to_vanilla: SlotBool<'static>,
+    pub slot_save_column_layout: SlotNoArgs<'static>,
+    pub slot_load_column_layout: SlotNoArgs<'static>,
+    pub slot_delete_column_layout: SlotNoArgs<'static>,
     pub slot_context_menu_import: SlotBool<'static>,
     pub slot_context_menu_export: SlotBool<'static>,
+    pub slot_context_menu_export_json: SlotBool<'static>,
+    pub slot_context_menu_export_csv: SlotBool<'static>,
+    pub slot_context_menu_reload: SlotBool<'static>,
     pub slot_smart_delete: SlotBool<'static>,
     pub slots_hide_show_column: Vec<SlotCInt<'static>>,
     pub slots_freeze_unfreeze_column: Vec<SlotCInt<'static>>,
@@ -166,6 +212,36 @@ impl Debug for TableOperations {
     }
 }
 
+/// Implementation of `TableOperations`.
+impl TableOperations {
+
+    /// This function returns a human-readable description of the operation, for the history of the table. As undoing an operation
+    /// puts his opposite in the redo history, operations from the redo history (`is_redo`) describe what redoing them does.
+    /// Undoing a paste also reverses the order of his operations, so the ones from the redo history are described in reverse.
+    pub fn get_description(&self, table_definition: &TableDefinition, is_redo: bool) -> String {
+        let count = |amount: usize, name: &str| format!("{} {}{}", amount, name, if amount == 1 { "" } else { "s" });
+        match self {
+            TableOperations::Editing(data) => {
+                let mut columns = data.iter().map(|x| (x.0).1).collect::<Vec<i32>>();
+                columns.sort();
+                columns.dedup();
+                if columns.len() == 1 { format!("Edited {} in column '{}'", count(data.len(), "cell"), PackedFileTableView::clean_column_names(&table_definition.fields[columns[0] as usize].field_name)) }
+                else { format!("Edited {} in {}", count(data.len(), "cell"), count(columns.len(), "column")) }
+            }
+            TableOperations::AddRows(rows) => format!("{} {}", if is_redo { "Deleted" } else { "Added" }, count(rows.len(), "row")),
+            TableOperations::RemoveRows(rows) => format!("{} {}", if is_redo { "Added" } else { "Deleted" }, count(rows.iter().map(|x| x.len()).sum(), "row")),
+            TableOperations::SmartDelete((edits, rows)) => format!("Deleted {} and {}", count(edits.len(), "cell"), count(rows.iter().map(|x| x.len()).sum(), "row")),
+            TableOperations::RevertSmartDelete((edits, rows)) => format!("Deleted {} and {}", count(edits.len(), "cell"), count(rows.len(), "row")),
+            TableOperations::ImportTSV(_) => "Imported a TSV file".to_owned(),
+            TableOperations::Carolina(operations) => {
+                let mut descriptions = operations.iter().map(|x| x.get_description(table_definition, is_redo)).collect::<Vec<String>>();
+                if is_redo { descriptions.reverse(); }
+                format!("Pasted: {}", descriptions.join(", "))
+            }
+        }
+    }
+}
+
 //----------------------------------------------------------------//
 // Implementation of `PackedFileTableView`.
 //----------------------------------------------------------------//
@@ -186,19 +262,23 @@ impl PackedFileTableView {
         global_search_explicit_paths: &Rc<RefCell<Vec<Vec<String>>>>,
         update_global_search_stuff: *mut Action,
         table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
+        open_table_views: &Rc<RefCell<BTreeMap<Vec<String>, *mut TableView>>>,
         table_definition: &Rc<TableDefinition>,
         enable_header_popups: Option<String>,
         table_type: &Rc<RefCell<TableType>>,
     ) -> Result<Self> {
 
-        // Get the entire dependency data for this table.
-        sender_qt.send(Commands::DecodeDependencyDB).unwrap();
+        // Get the data referenced by the reference columns of this table. It's only requested once per view.
+        sender_qt.send(Commands::GetReferenceData).unwrap();
         sender_qt_data.send(Data::TableDefinition((&**table_definition).clone())).unwrap();
         let dependency_data: Rc<BTreeMap<i32, Vec<String>>> = Rc::new(match check_message_validity_recv2(&receiver_qt) { 
             Data::BTreeMapI32VecString(data) => data,
             Data::Error(_) => BTreeMap::new(),
             _ => panic!(THREADS_MESSAGE_ERROR), 
         });
+
+        // Get the columns holding colours, so we can pick them.
+        let colour_columns = Rc::new(get_colour_columns(table_definition));
         
         // Create the "Undo" stuff needed for the Undo/Redo functions to work.
         let undo_lock = Rc::new(RefCell::new(false));
@@ -224,33 +304,64 @@ impl PackedFileTableView {
             unsafe { table_view.as_mut().unwrap().horizontal_header().as_mut().unwrap().set_stretch_last_section(true); }
         }
 
+        // Use the font and row height from the settings, if any. The frozen view needs them too, or his rows will not match.
+        let table_view_style = SETTINGS.lock().unwrap().get_table_view_style();
+        apply_table_view_style(table_view, &table_view_style);
+        apply_table_view_style(table_view_frozen, &table_view_style);
+
         // Create the filter's LineEdit.
         let row_filter_line_edit = LineEdit::new(()).into_raw();
         unsafe { row_filter_line_edit.as_mut().unwrap().set_placeholder_text(&QString::from_std_str("Type here to filter the rows in the table. Works with Regex too!")); }
 
-        // Create the filter's column selector.
-        // TODO: Make this follow the visual order of the columns, NOT THE LOGICAL ONE.
+        // Create the filter's column selector. Its items follow the visual order of the columns, with their logical index as data.
         let row_filter_column_selector = ComboBox::new().into_raw();
         let row_filter_column_list = StandardItemModel::new(()).into_raw();
         unsafe { row_filter_column_selector.as_mut().unwrap().set_model(row_filter_column_list as *mut AbstractItemModel); }
-        for column in &table_definition.fields {
-            let name = Self::clean_column_names(&column.field_name);
-            unsafe { row_filter_column_selector.as_mut().unwrap().add_item(&QString::from_std_str(&name)); }
-        }
 
         // Create the filter's "Case Sensitive" button.
         let row_filter_case_sensitive_button = PushButton::new(&QString::from_std_str("Case Sensitive")).into_raw();
         unsafe { row_filter_case_sensitive_button.as_mut().unwrap().set_checkable(true); }
 
+        // Create the column filters row, with a filter for each column. It's hidden until the user opens it.
+        // The filters are not in a layout, because they're placed over their columns, following the visual order and size of the header's sections.
+        let column_filters_widget = Widget::new().into_raw();
+        let column_filter_line_edits = Rc::new(table_definition.fields.iter().enumerate().map(|(index, column)| {
+            let line_edit = LineEdit::new(()).into_raw();
+            let tool_tip = match column.field_type {
+                FieldType::Float | FieldType::Integer | FieldType::LongInteger | FieldType::Double => "Filter for this column. Works with Regex, and with comparisons like '>100', '<=5', '=0' or '50..200'.",
+                _ => "Filter for this column. Works with Regex too!",
+            };
+            unsafe { line_edit.as_mut().unwrap().set_placeholder_text(&QString::from_std_str(&Self::clean_column_names(&column.field_name))); }
+            unsafe { line_edit.as_mut().unwrap().set_tool_tip(&QString::from_std_str(tool_tip)); }
+            unsafe { line_edit.as_mut().unwrap().set_parent(column_filters_widget); }
+            line_edit
+        }).collect::<Vec<*mut LineEdit>>());
+        if let Some(line_edit) = column_filter_line_edits.first() {
+            unsafe { column_filters_widget.as_mut().unwrap().set_fixed_height(line_edit.as_mut().unwrap().size_hint().height()); }
+        }
+        unsafe { column_filters_widget.as_mut().unwrap().hide(); }
+
         // Load the data to the Table. For some reason, if we do this after setting the titles of
         // the columns, the titles will be reseted to 1, 2, 3,... so we do this here.
         Self::load_data_to_table_view(table_view, model, &table_type.borrow(), table_definition, &dependency_data);
+        Self::update_row_filter_column_selector(table_view, row_filter_column_selector, table_definition);
+
+        // Add Table to the Grid. The TableView must be the first widget added, as other parts of the program expect it at the index 0 of the layout.
+        unsafe { layout.as_mut().unwrap().add_widget((table_view as *mut Widget, 1, 0, 1, 3)); }
+        unsafe { layout.as_mut().unwrap().add_widget((column_filters_widget as *mut Widget, 0, 0, 1, 3)); }
+        unsafe { layout.as_mut().unwrap().add_widget((row_filter_line_edit as *mut Widget, 3, 0, 1, 1)); }
+        unsafe { layout.as_mut().unwrap().add_widget((row_filter_case_sensitive_button as *mut Widget, 3, 1, 1, 1)); }
+        unsafe { layout.as_mut().unwrap().add_widget((row_filter_column_selector as *mut Widget, 3, 2, 1, 1)); }
+
+        // If we tint the column headers, add a legend for the colors under the filter.
+        if SETTINGS.lock().unwrap().settings_bool["tint_column_headers_by_type"] {
+            let legend_label = Label::new(&QString::from_std_str(get_field_type_colors_legend())).into_raw();
+            unsafe { layout.as_mut().unwrap().add_widget((legend_label as *mut Widget, 4, 0, 1, 3)); }
+        }
 
-        // Add Table to the Grid.
-        unsafe { layout.as_mut().unwrap().add_widget((table_view as *mut Widget, 0, 0, 1, 3)); }
-        unsafe { layout.as_mut().unwrap().add_widget((row_filter_line_edit as *mut Widget, 2, 0, 1, 1)); }
-        unsafe { layout.as_mut().unwrap().add_widget((row_filter_case_sensitive_button as *mut Widget, 2, 1, 1, 1)); }
-        unsafe { layout.as_mut().unwrap().add_widget((row_filter_column_selector as *mut Widget, 2, 2, 1, 1)); }
+        // Add the selection statistics under everything else.
+        let selection_stats_label = Label::new(()).into_raw();
+        unsafe { layout.as_mut().unwrap().add_widget((selection_stats_label as *mut Widget, 5, 0, 1, 3)); }
 
         // Create the search and hide/show/freeze widgets.
         let search_widget = Widget::new().into_raw();
@@ -285,6 +396,7 @@ impl PackedFileTableView {
 
         unsafe { column_selector.set_model(column_list.into_raw() as *mut AbstractItemModel); }
         column_selector.add_item(&QString::from_std_str("* (All Columns)"));
+        column_selector.add_item(&QString::from_std_str("* (Current Column)"));
         for column in &table_definition.fields {
             column_selector.add_item(&QString::from_std_str(&Self::clean_column_names(&column.field_name)));
         }
@@ -323,11 +435,18 @@ impl PackedFileTableView {
         unsafe { grid.as_mut().unwrap().add_widget((case_sensitive_button as *mut Widget, 2, 3, 1, 1)); }
 
         // Add all the stuff to the main grid and hide the search widget.
-        unsafe { layout.as_mut().unwrap().add_widget((sidebar_scroll_area as *mut Widget, 0, 3, 3, 1)); }
-        unsafe { layout.as_mut().unwrap().add_widget((search_widget as *mut Widget, 1, 0, 1, 3)); }
+        unsafe { layout.as_mut().unwrap().add_widget((sidebar_scroll_area as *mut Widget, 0, 3, 4, 1)); }
+        unsafe { layout.as_mut().unwrap().add_widget((search_widget as *mut Widget, 2, 0, 1, 3)); }
         unsafe { layout.as_mut().unwrap().set_column_stretch(0, 10); }
         unsafe { search_widget.as_mut().unwrap().hide(); }
         unsafe { sidebar_scroll_area.as_mut().unwrap().hide(); }
+
+        // Create the history panel, hidden until the user opens it.
+        let history_view = ListView::new().into_raw();
+        let history_model = StandardItemModel::new(()).into_raw();
+        unsafe { history_view.as_mut().unwrap().set_model(history_model as *mut AbstractItemModel); }
+        unsafe { layout.as_mut().unwrap().add_widget((history_view as *mut Widget, 0, 4, 4, 1)); }
+        unsafe { history_view.as_mut().unwrap().hide(); }
         unsafe { sidebar_grid.as_mut().unwrap().set_row_stretch(999, 10); }
 
         // Store the search results and the currently selected search item.
@@ -363,12 +482,18 @@ impl PackedFileTableView {
         // Create the Contextual Menu for the TableView.
         let mut context_menu = Menu::new(());
         let context_menu_add = context_menu.add_action(&QString::from_std_str("&Add Row"));
-        let context_menu_insert = context_menu.add_action(&QString::from_std_str("&Insert Row"));
+        let context_menu_insert = context_menu.add_action(&QString::from_std_str("&Insert Rows Above"));
         let context_menu_delete = context_menu.add_action(&QString::from_std_str("&Delete Row"));
+        let context_menu_edit_in_window = context_menu.add_action(&QString::from_std_str("&Edit in Window..."));
+        let context_menu_go_to_definition = context_menu.add_action(&QString::from_std_str("&Go to Definition"));
+        let context_menu_find_usages = context_menu.add_action(&QString::from_std_str("&Find Usages"));
 
         let mut context_menu_apply_submenu = Menu::new(&QString::from_std_str("A&pply..."));
         let context_menu_apply_maths_to_selection = context_menu_apply_submenu.add_action(&QString::from_std_str("&Apply Maths to Selection"));
         let context_menu_rewrite_selection = context_menu_apply_submenu.add_action(&QString::from_std_str("&Rewrite Selection"));
+        let context_menu_toggle_booleans = context_menu_apply_submenu.add_action(&QString::from_std_str("&Toggle Booleans"));
+        let context_menu_pick_colour = context_menu_apply_submenu.add_action(&QString::from_std_str("&Pick Colour..."));
+        let context_menu_pick_reference = context_menu_apply_submenu.add_action(&QString::from_std_str("Pick R&eference..."));
 
         let mut context_menu_clone_submenu = Menu::new(&QString::from_std_str("&Clone..."));
         let context_menu_clone = context_menu_clone_submenu.add_action(&QString::from_std_str("&Clone and Insert"));
@@ -377,17 +502,36 @@ impl PackedFileTableView {
         let mut context_menu_copy_submenu = Menu::new(&QString::from_std_str("&Copy..."));
         let context_menu_copy = context_menu_copy_submenu.add_action(&QString::from_std_str("&Copy"));
         let context_menu_copy_as_lua_table = context_menu_copy_submenu.add_action(&QString::from_std_str("&Copy as &LUA Table"));
+        let context_menu_copy_as_markdown_table = context_menu_copy_submenu.add_action(&QString::from_std_str("Copy as &Markdown Table"));
+        let context_menu_copy_as_json = context_menu_copy_submenu.add_action(&QString::from_std_str("Copy as &JSON"));
+        let context_menu_copy_with_headers = context_menu_copy_submenu.add_action(&QString::from_std_str("Copy with &Headers"));
 
         let mut context_menu_paste_submenu = Menu::new(&QString::from_std_str("&Paste..."));
         let context_menu_paste = context_menu_paste_submenu.add_action(&QString::from_std_str("&Paste"));
         let context_menu_paste_as_new_lines = context_menu_paste_submenu.add_action(&QString::from_std_str("&Paste as New Rows"));
         let context_menu_paste_to_fill_selection = context_menu_paste_submenu.add_action(&QString::from_std_str("&Paste to Fill Selection"));
 
+        let mut context_menu_highlight_submenu = Menu::new(&QString::from_std_str("&Highlight..."));
+        let context_menu_add_highlight_rule = context_menu_highlight_submenu.add_action(&QString::from_std_str("&Add Highlight Rule..."));
+        let context_menu_clear_highlight_rules = context_menu_highlight_submenu.add_action(&QString::from_std_str("&Clear Highlight Rules"));
+
         let context_menu_search = context_menu.add_action(&QString::from_std_str("&Search"));
+        let context_menu_go_to_row = context_menu.add_action(&QString::from_std_str("Go to &Row..."));
         let context_menu_sidebar = context_menu.add_action(&QString::from_std_str("Si&debar"));
+        let context_menu_history = context_menu.add_action(&QString::from_std_str("Histor&y"));
+        let context_menu_column_filters = context_menu.add_action(&QString::from_std_str("Column &Filters"));
+        let context_menu_freeze_key_columns = context_menu.add_action(&QString::from_std_str("Freeze &Key Columns"));
+        unsafe { context_menu_freeze_key_columns.as_mut().unwrap().set_checkable(true); }
+        let context_menu_vanilla_diff = context_menu.add_action(&QString::from_std_str("Highlight &Vanilla Differences"));
+        unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_checkable(true); }
+        let context_menu_revert_cell_to_vanilla = context_menu.add_action(&QString::from_std_str("Revert Cell to Vanilla"));
+        let context_menu_revert_row_to_vanilla = context_menu.add_action(&QString::from_std_str("Revert Row to Vanilla"));
 
         let context_menu_import = context_menu.add_action(&QString::from_std_str("&Import"));
         let context_menu_export = context_menu.add_action(&QString::from_std_str("&Export"));
+        let context_menu_export_json = context_menu.add_action(&QString::from_std_str("Export as &JSON"));
+        let context_menu_export_csv = context_menu.add_action(&QString::from_std_str("Export as &CSV"));
+        let context_menu_reload = context_menu.add_action(&QString::from_std_str("Re&load from PackFile"));
 
         let context_menu_selection_invert = context_menu.add_action(&QString::from_std_str("Inver&t Selection"));
         
@@ -400,18 +544,37 @@ impl PackedFileTableView {
         unsafe { context_menu_delete.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["delete_row"]))); }
         unsafe { context_menu_apply_maths_to_selection.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["apply_maths_to_selection"]))); }
         unsafe { context_menu_rewrite_selection.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["rewrite_selection"]))); }
+        unsafe { context_menu_toggle_booleans.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["toggle_booleans"]))); }
+        unsafe { context_menu_pick_colour.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["pick_colour"]))); }
+        unsafe { context_menu_pick_reference.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["pick_reference"]))); }
+        unsafe { context_menu_edit_in_window.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["edit_in_window"]))); }
+        unsafe { context_menu_go_to_definition.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["go_to_definition"]))); }
+        unsafe { context_menu_find_usages.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["find_usages"]))); }
         unsafe { context_menu_clone.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["clone_row"]))); }
         unsafe { context_menu_clone_and_append.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["clone_and_append_row"]))); }
         unsafe { context_menu_copy.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["copy"]))); }
         unsafe { context_menu_copy_as_lua_table.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["copy_as_lua_table"]))); }
+        unsafe { context_menu_copy_as_json.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["copy_as_json"]))); }
+        unsafe { context_menu_copy_with_headers.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["copy_with_headers"]))); }
+        unsafe { context_menu_copy_as_markdown_table.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["copy_as_markdown_table"]))); }
         unsafe { context_menu_paste.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["paste"]))); }
         unsafe { context_menu_paste_as_new_lines.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["paste_as_new_row"]))); }
         unsafe { context_menu_paste_to_fill_selection.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["paste_to_fill_selection"]))); }
         unsafe { context_menu_selection_invert.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["selection_invert"]))); }
         unsafe { context_menu_search.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["search"]))); }
+        unsafe { context_menu_go_to_row.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["go_to_row"]))); }
         unsafe { context_menu_sidebar.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["sidebar"]))); }
+        unsafe { context_menu_history.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["history"]))); }
+        unsafe { context_menu_column_filters.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["column_filters"]))); }
+        unsafe { context_menu_freeze_key_columns.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["freeze_key_columns"]))); }
+        unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["vanilla_diff"]))); }
+        unsafe { context_menu_revert_cell_to_vanilla.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["revert_cell_to_vanilla"]))); }
+        unsafe { context_menu_revert_row_to_vanilla.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["revert_row_to_vanilla"]))); }
         unsafe { context_menu_import.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["import_tsv"]))); }
         unsafe { context_menu_export.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["export_tsv"]))); }
+        unsafe { context_menu_export_json.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["export_json"]))); }
+        unsafe { context_menu_export_csv.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["export_csv"]))); }
+        unsafe { context_menu_reload.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["reload_packed_file"]))); }
         unsafe { smart_delete.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["smart_delete"]))); }
         unsafe { context_menu_undo.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["undo"]))); }
         unsafe { context_menu_redo.as_mut().unwrap().set_shortcut(&KeySequence::from_string(&QString::from_std_str(&SHORTCUTS.lock().unwrap().packed_files_table["redo"]))); }
@@ -422,18 +585,37 @@ impl PackedFileTableView {
         unsafe { context_menu_delete.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_apply_maths_to_selection.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_rewrite_selection.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_toggle_booleans.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_pick_colour.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_pick_reference.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_edit_in_window.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_go_to_definition.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_find_usages.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_clone.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_clone_and_append.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_copy.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_copy_as_lua_table.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_copy_as_json.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_copy_with_headers.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_copy_as_markdown_table.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_paste.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_paste_as_new_lines.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_paste_to_fill_selection.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_selection_invert.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_search.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_go_to_row.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_sidebar.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_history.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_column_filters.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_freeze_key_columns.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_revert_cell_to_vanilla.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_revert_row_to_vanilla.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_import.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_export.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_export_json.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_export_csv.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
+        unsafe { context_menu_reload.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { smart_delete.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_undo.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
         unsafe { context_menu_redo.as_mut().unwrap().set_shortcut_context(ShortcutContext::Widget); }
@@ -444,40 +626,80 @@ impl PackedFileTableView {
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_delete); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_apply_maths_to_selection); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_rewrite_selection); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_toggle_booleans); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_pick_colour); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_pick_reference); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_edit_in_window); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_go_to_definition); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_find_usages); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_clone); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_clone_and_append); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_copy); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_copy_as_lua_table); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_copy_as_markdown_table); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_copy_as_json); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_copy_with_headers); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_paste); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_paste_as_new_lines); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_paste_to_fill_selection); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_selection_invert); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_search); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_go_to_row); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_sidebar); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_history); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_column_filters); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_freeze_key_columns); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_vanilla_diff); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_revert_cell_to_vanilla); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_revert_row_to_vanilla); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_import); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_export); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_export_json); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_export_csv); }
+        unsafe { table_view.as_mut().unwrap().add_action(context_menu_reload); }
         unsafe { table_view.as_mut().unwrap().add_action(smart_delete); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_undo); }
         unsafe { table_view.as_mut().unwrap().add_action(context_menu_redo); }
 
         // Status Tips for the actions.
         unsafe { context_menu_add.as_mut().unwrap().set_status_tip(&QString::from_std_str("Add an empty row at the end of the table.")); }
-        unsafe { context_menu_insert.as_mut().unwrap().set_status_tip(&QString::from_std_str("Insert an empty row just above the one selected.")); }
+        unsafe { context_menu_insert.as_mut().unwrap().set_status_tip(&QString::from_std_str("Insert as many empty rows as rows are selected, just above the first selected row. If nothing is selected, insert one at the top of the table.")); }
         unsafe { context_menu_delete.as_mut().unwrap().set_status_tip(&QString::from_std_str("Delete all the selected rows.")); }
         unsafe { context_menu_apply_maths_to_selection.as_mut().unwrap().set_status_tip(&QString::from_std_str("Apply a simple mathematical operation to every cell in the selected cells.")); }
         unsafe { context_menu_rewrite_selection.as_mut().unwrap().set_status_tip(&QString::from_std_str("Rewrite the selected cells using a pattern.")); }
+        unsafe { context_menu_toggle_booleans.as_mut().unwrap().set_status_tip(&QString::from_std_str("Toggle all the selected boolean cells at once.")); }
+        unsafe { context_menu_pick_colour.as_mut().unwrap().set_status_tip(&QString::from_std_str("Pick the colour of the current cell from a colour dialog. Only works in columns holding a colour.")); }
+        unsafe { context_menu_pick_reference.as_mut().unwrap().set_status_tip(&QString::from_std_str("Pick the value of the current cell from a searchable list of the values it can reference. Only works in reference columns with referenced data.")); }
+        unsafe { context_menu_edit_in_window.as_mut().unwrap().set_status_tip(&QString::from_std_str("Edit the text of the current cell in a bigger window, with support for multiple lines. Only works in text columns.")); }
+        unsafe { context_menu_go_to_definition.as_mut().unwrap().set_status_tip(&QString::from_std_str("Open the table referenced by the current cell and select the referenced row. If the row is only in the game files, it's shown in a dialog instead.")); }
+        unsafe { context_menu_find_usages.as_mut().unwrap().set_status_tip(&QString::from_std_str("Search all the DB Tables of the open PackFile for cells referencing the current key cell. The results are shown in the Global Search view.")); }
         unsafe { context_menu_clone.as_mut().unwrap().set_status_tip(&QString::from_std_str("Duplicate the selected rows and insert the new rows under the original ones.")); }
         unsafe { context_menu_clone_and_append.as_mut().unwrap().set_status_tip(&QString::from_std_str("Duplicate the selected rows and append the new rows at the end of the table.")); }
         unsafe { context_menu_copy.as_mut().unwrap().set_status_tip(&QString::from_std_str("Copy whatever is selected to the Clipboard.")); }
         unsafe { context_menu_copy_as_lua_table.as_mut().unwrap().set_status_tip(&QString::from_std_str("Turns the entire DB Table into a LUA Table and copies it to the clipboard.")); }
+        unsafe { context_menu_copy_as_markdown_table.as_mut().unwrap().set_status_tip(&QString::from_std_str("Turns the selection into a Markdown Table, with the column names as header, and copies it to the clipboard.")); }
+        unsafe { context_menu_copy_with_headers.as_mut().unwrap().set_status_tip(&QString::from_std_str("Copies the selection like the normal copy, with the names of the visible columns in the first line.")); }
+        unsafe { context_menu_copy_as_json.as_mut().unwrap().set_status_tip(&QString::from_std_str("Turns the selected rows into a JSON array of objects, with the selected columns as keys, and copies it to the clipboard.")); }
         unsafe { context_menu_paste.as_mut().unwrap().set_status_tip(&QString::from_std_str("Try to paste whatever is in the Clipboard. If the data of a cell is incompatible with the content to paste, the cell is ignored.")); }
         unsafe { context_menu_paste_as_new_lines.as_mut().unwrap().set_status_tip(&QString::from_std_str("Try to paste whatever is in the Clipboard as new lines at the end of the table. Does nothing if the data is not compatible with the cell.")); }
-        unsafe { context_menu_paste_to_fill_selection.as_mut().unwrap().set_status_tip(&QString::from_std_str("Try to paste whatever is in the Clipboard in EVERY CELL selected. Does nothing if the data is not compatible with the cell.")); }
-        unsafe { context_menu_selection_invert.as_mut().unwrap().set_status_tip(&QString::from_std_str("Inverts the current selection.")); }
+        unsafe { context_menu_paste_to_fill_selection.as_mut().unwrap().set_status_tip(&QString::from_std_str("Try to paste whatever is in the Clipboard in EVERY CELL selected, repeating it if it's smaller than the selection. Cells not compatible with the data are skipped.")); }
+        unsafe { context_menu_selection_invert.as_mut().unwrap().set_status_tip(&QString::from_std_str("Inverts the current selection within the visible rows. If only some columns are selected, it only inverts the selection within them.")); }
+        unsafe { context_menu_add_highlight_rule.as_mut().unwrap().set_status_tip(&QString::from_std_str("Add a rule to highlight the cells of a column that match a condition, like 'tier > 3'. Rules are remembered for each table.")); }
+        unsafe { context_menu_clear_highlight_rules.as_mut().unwrap().set_status_tip(&QString::from_std_str("Remove all the highlight rules of this table.")); }
         unsafe { context_menu_search.as_mut().unwrap().set_status_tip(&QString::from_std_str("Search what you want in the table. Also allows you to replace coincidences.")); }
+        unsafe { context_menu_go_to_row.as_mut().unwrap().set_status_tip(&QString::from_std_str("Jump to a row by his number, as the table is shown right now, or by the value of one of his key columns.")); }
         unsafe { context_menu_sidebar.as_mut().unwrap().set_status_tip(&QString::from_std_str("Open/Close the sidebar with the controls to hide/show/freeze columns.")); }
+        unsafe { context_menu_history.as_mut().unwrap().set_status_tip(&QString::from_std_str("Open/Close the list with the undo history of the table. Click an entry to undo/redo everything up to it.")); }
+        unsafe { context_menu_column_filters.as_mut().unwrap().set_status_tip(&QString::from_std_str("Open/Close the row with a filter for each visible column. Closing it clears all the column filters.")); }
+        unsafe { context_menu_freeze_key_columns.as_mut().unwrap().set_status_tip(&QString::from_std_str("Freeze/Unfreeze the key columns of the table (or the first column, if it has no keys), so they're always visible when scrolling. Remembered for every table with the same name.")); }
+        unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_status_tip(&QString::from_std_str("Tint the cells that differ from the vanilla version of the table, and the rows vanilla doesn't have. Only available for tables with a vanilla version.")); }
+        unsafe { context_menu_revert_cell_to_vanilla.as_mut().unwrap().set_status_tip(&QString::from_std_str("Revert the selected cells to the values they have in the vanilla version of the table, as a single undo step.")); }
+        unsafe { context_menu_revert_row_to_vanilla.as_mut().unwrap().set_status_tip(&QString::from_std_str("Revert the selected rows to the values they have in the vanilla version of the table, as a single undo step.")); }
         unsafe { context_menu_import.as_mut().unwrap().set_status_tip(&QString::from_std_str("Import a TSV file into this table, replacing all the data.")); }
         unsafe { context_menu_export.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export this table's data into a TSV file.")); }
+        unsafe { context_menu_export_json.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export this table's data into a JSON file, with the type of every field.")); }
+        unsafe { context_menu_export_csv.as_mut().unwrap().set_status_tip(&QString::from_std_str("Export this table's data into a CSV file, with the delimiter and quoting you choose.")); }
+        unsafe { context_menu_reload.as_mut().unwrap().set_status_tip(&QString::from_std_str("Discard all the changes done to this table since the PackFile was last saved, reloading it from the PackFile on disk. This clears the undo history.")); }
         unsafe { context_menu_undo.as_mut().unwrap().set_status_tip(&QString::from_std_str("A classic.")); }
         unsafe { context_menu_redo.as_mut().unwrap().set_status_tip(&QString::from_std_str("Another classic.")); }
 
@@ -487,6 +709,7 @@ impl PackedFileTableView {
         unsafe { context_menu.insert_menu(context_menu_search, context_menu_clone_submenu.into_raw()); }
         unsafe { context_menu.insert_menu(context_menu_search, context_menu_copy_submenu.into_raw()); }
         unsafe { context_menu.insert_menu(context_menu_search, context_menu_paste_submenu.into_raw()); }
+        unsafe { context_menu.insert_menu(context_menu_search, context_menu_highlight_submenu.into_raw()); }
         unsafe { context_menu.insert_separator(context_menu_search); }
         unsafe { context_menu.insert_separator(context_menu_import); }
         unsafe { context_menu.insert_separator(context_menu_sidebar); }
@@ -518,6 +741,7 @@ impl PackedFileTableView {
             // Logic here: If we hide something, it cannot be frozen.
             let hide_show_slot = SlotCInt::new(clone!(
                 packed_file_path,
+                column_filter_line_edits,
                 actions_freeze_unfreeze_column => move |state| {
 
 
@@ -526,6 +750,14 @@ impl PackedFileTableView {
                     unsafe { table_view.as_mut().unwrap().set_column_hidden(index as i32, state); }
                     unsafe { actions_freeze_unfreeze_column.borrow()[index].as_mut().unwrap().set_enabled(!state); }
 
+                    // Hidden columns cannot be filtered, so we clear and hide their column filter.
+                    let line_edit = column_filter_line_edits[index];
+                    if state {
+                        unsafe { line_edit.as_mut().unwrap().clear(); }
+                        unsafe { line_edit.as_mut().unwrap().hide(); }
+                    }
+                    else { unsafe { line_edit.as_mut().unwrap().show(); }}
+
                     // Update the state of the column in the table history.
                     if let Some(state_ui) = TABLE_STATES_UI.lock().unwrap().get_mut(&*packed_file_path.borrow()) {
                         state_ui.columns_state.visual_history.push(VisualHistory::ColumnHidden(state, index as i32));
@@ -601,6 +833,51 @@ impl PackedFileTableView {
             actions_freeze_unfreeze_column.borrow_mut().push(freeze_unfreeze_checkbox);
         }
 
+        // Add the controls to save and load the hidden columns as layouts at the end of the sidebar.
+        let layouts_row = table_definition.fields.len() as i32 + 1;
+        let column_layout_selector = ComboBox::new().into_raw();
+        let column_layout_list = StandardItemModel::new(()).into_raw();
+        let column_layout_save_button = PushButton::new(&QString::from_std_str("Save Layout")).into_raw();
+        let column_layout_load_button = PushButton::new(&QString::from_std_str("Load Layout")).into_raw();
+        let column_layout_delete_button = PushButton::new(&QString::from_std_str("Delete Layout")).into_raw();
+        unsafe { column_layout_selector.as_mut().unwrap().set_model(column_layout_list as *mut AbstractItemModel); }
+        for layout_name in SETTINGS.lock().unwrap().get_table_column_layouts(&Self::get_table_name(&table_type.borrow())) {
+            unsafe { column_layout_selector.as_mut().unwrap().add_item(&QString::from_std_str(&layout_name)); }
+        }
+
+        unsafe { sidebar_grid.as_mut().unwrap().add_widget((column_layout_selector as *mut Widget, layouts_row, 0, 1, 3)); }
+        unsafe { sidebar_grid.as_mut().unwrap().add_widget((column_layout_save_button as *mut Widget, layouts_row + 1, 0, 1, 1)); }
+        unsafe { sidebar_grid.as_mut().unwrap().add_widget((column_layout_load_button as *mut Widget, layouts_row + 1, 1, 1, 1)); }
+        unsafe { sidebar_grid.as_mut().unwrap().add_widget((column_layout_delete_button as *mut Widget, layouts_row + 1, 2, 1, 1)); }
+
+        // Only DB Tables can be compared with vanilla. The vanilla rows are only loaded the first time we compare, and reloaded if
+        // the Game Selected changes, so opening a table doesn't wait for the dependency database. If the table turns out to have no
+        // vanilla version, the comparison gets disabled then.
+        let is_db = if let TableType::DB(_) = *table_type.borrow() { true } else { false };
+        unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_enabled(is_db); }
+        unsafe { context_menu_revert_cell_to_vanilla.as_mut().unwrap().set_enabled(false); }
+        unsafe { context_menu_revert_row_to_vanilla.as_mut().unwrap().set_enabled(false); }
+        let vanilla_rows: Rc<RefCell<Option<(String, BTreeMap<Vec<String>, Vec<Option<String>>>)>>> = Rc::new(RefCell::new(None));
+
+        // Timer to run the duplicated keys and vanilla checks a bit after the last edit, so we don't check big tables on every keystroke. Only DB Tables have keys.
+        let key_columns = match *table_type.borrow() {
+            TableType::DB(_) => table_definition.fields.iter().enumerate().filter(|(_, x)| x.field_is_key).map(|(x, _)| x).collect::<Vec<usize>>(),
+            _ => vec![],
+        };
+        let table_checks_timer = Timer::new().into_raw();
+        unsafe { table_checks_timer.as_mut().unwrap().set_single_shot(true); }
+        unsafe { table_checks_timer.as_mut().unwrap().set_interval(500); }
+
+        // Timer to update the selection statistics, so selecting with the mouse doesn't recalculate them for every cell we pass over.
+        let selection_stats_timer = Timer::new().into_raw();
+        unsafe { selection_stats_timer.as_mut().unwrap().set_single_shot(true); }
+        unsafe { selection_stats_timer.as_mut().unwrap().set_interval(100); }
+
+        // Timer to apply the column filters a bit after the last keystroke, so we don't filter big tables for every character typed.
+        let column_filters_timer = Timer::new().into_raw();
+        unsafe { column_filters_timer.as_mut().unwrap().set_single_shot(true); }
+        unsafe { column_filters_timer.as_mut().unwrap().set_interval(300); }
+
         // Slots for the TableView...
         let slots = Self {
             slot_column_moved: SlotCIntCIntCInt::new(clone!(
@@ -743,6 +1020,7 @@ impl PackedFileTableView {
                 app_ui,
                 table_type,
                 table_state_data,
+                table_definition,
                 packed_file_path => move || { 
                     let table_state_data = table_state_data.borrow_mut();
                     let table_state_data = table_state_data.get(&*packed_file_path.borrow()).unwrap();
@@ -769,11 +1047,17 @@ impl PackedFileTableView {
                         if table_state_data.redo_history.is_empty() { context_menu_redo.as_mut().unwrap().set_enabled(false); }
                         else { context_menu_redo.as_mut().unwrap().set_enabled(true); }
                     }
+
+                    update_history_model(history_view, history_model, &table_state_data, &table_definition);
                 }
             )),
 
             slot_context_menu: SlotQtCorePointRef::new(move |_| { context_menu.exec2(&Cursor::pos()); }),
             slot_context_menu_enabler: SlotItemSelectionRefItemSelectionRef::new(clone!(
+                colour_columns,
+                dependency_data,
+                table_type,
+                vanilla_rows,
                 table_definition => move |_,_| {
 
                     // Turns out that this slot doesn't give the the amount of selected items, so we have to get them ourselfs.
@@ -785,10 +1069,13 @@ impl PackedFileTableView {
                             context_menu_clone.as_mut().unwrap().set_enabled(true);
                             context_menu_clone_and_append.as_mut().unwrap().set_enabled(true);
                             context_menu_copy.as_mut().unwrap().set_enabled(true);
+                            context_menu_copy_as_markdown_table.as_mut().unwrap().set_enabled(true);
+                            context_menu_copy_as_json.as_mut().unwrap().set_enabled(true);
+                            context_menu_copy_with_headers.as_mut().unwrap().set_enabled(true);
                             context_menu_delete.as_mut().unwrap().set_enabled(true);
                             context_menu_rewrite_selection.as_mut().unwrap().set_enabled(true);
                         
-                            // The "Apply" actions have to be enabled only when all the indexes are valid for the operation. 
+                            // The "Apply" actions have to be enabled only when some of the indexes are valid for the operation.
                             let mut columns = vec![];
                             for index in 0..indexes.count(()) {
                                 let model_index = indexes.at(index);
@@ -797,14 +1084,54 @@ impl PackedFileTableView {
 
                             columns.sort();
                             columns.dedup();
-                            
-                            let mut can_apply = true;
-                            for column in &columns {
-                                let field_type = &table_definition.fields[*column as usize].field_type;
-                                if *field_type != FieldType::Boolean { continue }
-                                else { can_apply = false; break } 
-                            }
+
+                            // Non-numeric columns are skipped, so we only need one numeric column.
+                            let can_apply = columns.iter().any(|column| match table_definition.fields[*column as usize].field_type {
+                                FieldType::Float | FieldType::Integer | FieldType::LongInteger | FieldType::Double => true,
+                                _ => false,
+                            });
                             context_menu_apply_maths_to_selection.as_mut().unwrap().set_enabled(can_apply);
+
+                            // Same with the booleans.
+                            let can_toggle = columns.iter().any(|column| table_definition.fields[*column as usize].field_type == FieldType::Boolean);
+                            context_menu_toggle_booleans.as_mut().unwrap().set_enabled(can_toggle);
+
+                            // And with the colours.
+                            let can_pick = columns.iter().any(|column| colour_columns.contains(&(*column as usize)));
+                            context_menu_pick_colour.as_mut().unwrap().set_enabled(can_pick);
+
+                            // And with the references, if we have something to pick from.
+                            let can_pick_reference = columns.iter().any(|column| dependency_data.get(column).map_or(false, |data| !data.is_empty()));
+                            context_menu_pick_reference.as_mut().unwrap().set_enabled(can_pick_reference);
+
+                            // And with the texts.
+                            let can_edit_in_window = columns.iter().any(|column| match table_definition.fields[*column as usize].field_type {
+                                FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16 => true,
+                                _ => false,
+                            });
+                            context_menu_edit_in_window.as_mut().unwrap().set_enabled(can_edit_in_window);
+
+                            // And with the references.
+                            let can_go_to_definition = columns.iter().any(|column| table_definition.fields[*column as usize].field_is_reference.is_some());
+                            context_menu_go_to_definition.as_mut().unwrap().set_enabled(can_go_to_definition);
+
+                            // Only keys of DB Tables can be referenced.
+                            let is_db = if let TableType::DB(_) = *table_type.borrow() { true } else { false };
+                            let can_find_usages = is_db && columns.iter().any(|column| table_definition.fields[*column as usize].field_is_key);
+                            context_menu_find_usages.as_mut().unwrap().set_enabled(can_find_usages);
+
+                            // The reverts need the selected rows to be in vanilla. If we haven't loaded the vanilla rows yet, we assume they are.
+                            let can_revert = context_menu_vanilla_diff.as_ref().unwrap().is_enabled() && match *vanilla_rows.borrow() {
+                                Some((_, ref vanilla_rows)) => {
+                                    let identity_columns = get_row_identity_columns(&table_definition);
+                                    (0..indexes.count(())).map(|x| indexes.at(x)).filter(|x| x.is_valid()).any(|model_index| {
+                                        vanilla_rows.contains_key(&get_model_row(model, model_index.row(), &identity_columns))
+                                    })
+                                }
+                                None => true,
+                            };
+                            context_menu_revert_cell_to_vanilla.as_mut().unwrap().set_enabled(can_revert);
+                            context_menu_revert_row_to_vanilla.as_mut().unwrap().set_enabled(can_revert);
                         }
                     }
 
@@ -813,9 +1140,20 @@ impl PackedFileTableView {
                         unsafe {
                             context_menu_apply_maths_to_selection.as_mut().unwrap().set_enabled(false);
                             context_menu_rewrite_selection.as_mut().unwrap().set_enabled(false);
+                            context_menu_toggle_booleans.as_mut().unwrap().set_enabled(false);
+                            context_menu_pick_colour.as_mut().unwrap().set_enabled(false);
+                            context_menu_pick_reference.as_mut().unwrap().set_enabled(false);
+                            context_menu_edit_in_window.as_mut().unwrap().set_enabled(false);
+                            context_menu_go_to_definition.as_mut().unwrap().set_enabled(false);
+                            context_menu_find_usages.as_mut().unwrap().set_enabled(false);
+                            context_menu_revert_cell_to_vanilla.as_mut().unwrap().set_enabled(false);
+                            context_menu_revert_row_to_vanilla.as_mut().unwrap().set_enabled(false);
                             context_menu_clone.as_mut().unwrap().set_enabled(false);
                             context_menu_clone_and_append.as_mut().unwrap().set_enabled(false);
                             context_menu_copy.as_mut().unwrap().set_enabled(false);
+                            context_menu_copy_as_markdown_table.as_mut().unwrap().set_enabled(false);
+                            context_menu_copy_as_json.as_mut().unwrap().set_enabled(false);
+                            context_menu_copy_with_headers.as_mut().unwrap().set_enabled(false);
                             context_menu_delete.as_mut().unwrap().set_enabled(false);
                         }
                     }
@@ -868,6 +1206,7 @@ impl PackedFileTableView {
                 save_lock,
                 table_state_data,
                 dependency_data,
+                colour_columns,
                 table_definition => move |item| {
 
                     // If we are NOT UNDOING, paint the item as edited and add the edition to the undo list.
@@ -881,7 +1220,7 @@ impl PackedFileTableView {
 
                             // We block the saving for painting, so this doesn't get rettriggered again.
                             let mut blocker = unsafe { SignalBlocker::new(model.as_mut().unwrap().static_cast_mut() as &mut Object) };
-                            unsafe { item.as_mut().unwrap().set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkYellow } else { GlobalColor::Yellow })); }
+                            unsafe { set_cell_background(item.as_mut().unwrap(), &Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkYellow } else { GlobalColor::Yellow })); }
                             blocker.unblock();
 
                             // For pasting, only update the undo_model the last iteration of the paste.                        
@@ -901,51 +1240,176 @@ impl PackedFileTableView {
                         }
                     }
 
+                    // If the item holds a colour, update the swatch of the colour.
+                    if colour_columns.contains(&unsafe { item.as_mut().unwrap().column() as usize }) {
+                        let mut blocker = unsafe { SignalBlocker::new(model.as_mut().unwrap().static_cast_mut() as &mut Object) };
+                        update_colour_swatch(item);
+                        blocker.unblock();
+                    }
+
+                    // The edited item may be selected, so update the selection statistics.
+                    unsafe { selection_stats_timer.as_mut().unwrap().start(()); }
+
                     // If we are editing the Dependency Manager, check for PackFile errors too.
                     if let TableType::DependencyManager(_) = *table_type.borrow() { Self::check_dependency_packfile_errors(model); }
                 }
             )),
 
+            slot_run_table_checks: SlotNoArgs::new(clone!(
+                app_ui,
+                undo_lock,
+                packed_file_path,
+                key_columns,
+                vanilla_rows,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt,
+                table_definition => move || {
+                    paint_duplicated_keys(model, &key_columns, table_definition.fields.len(), &undo_lock);
+                    if unsafe { context_menu_vanilla_diff.as_ref().unwrap().is_checked() } {
+                        match Self::load_vanilla_rows(&sender_qt, &sender_qt_data, &receiver_qt, &packed_file_path.borrow(), &table_definition, &vanilla_rows) {
+                            Ok(_) => paint_vanilla_diff(model, &get_row_identity_columns(&table_definition), table_definition.fields.len(), vanilla_rows.borrow().as_ref().map(|(_, x)| x), &undo_lock),
+                            Err(error) => show_dialog(app_ui.window, false, error),
+                        }
+                    }
+                }
+            )),
+
+            slot_schedule_table_checks: SlotModelIndexRefCIntCInt::new(move |_,_,_| {
+                unsafe { table_checks_timer.as_mut().unwrap().start(()); }
+            }),
+
+            slot_schedule_table_checks_on_edit: SlotModelIndexRefModelIndexRefVectorVectorCIntRef::new(clone!(
+                key_columns => move |top_left, bottom_right, roles| {
+
+                    // Only edits of the values count, not the tints of the checks, or they'll retrigger themselves.
+                    // If the cells are part of the key or we're comparing with vanilla, recheck the table once the user stops editing.
+                    if roles.contains(&0) || roles.contains(&2) || roles.contains(&10) {
+                        let is_key = (top_left.column()..=bottom_right.column()).any(|column| key_columns.contains(&(column as usize)));
+                        if is_key || unsafe { context_menu_vanilla_diff.as_ref().unwrap().is_checked() } {
+                            unsafe { table_checks_timer.as_mut().unwrap().start(()); }
+                        }
+                    }
+                }
+            )),
+
+            slot_update_selection_stats: SlotNoArgs::new(clone!(
+                table_definition => move || {
+                    let visible_rows = unsafe { filter_model.as_mut().unwrap().row_count(()) as usize };
+                    let indexes = unsafe { filter_model.as_mut().unwrap().map_selection_to_source(&table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection()).indexes() };
+                    let selected_cells = indexes.count(()) as usize;
+
+                    // Only numeric cells count for the maths, and only if the selection is not too big.
+                    let mut has_floats = false;
+                    let values = if selected_cells <= SELECTION_STATS_MAX_CELLS {
+                        let mut values = vec![];
+                        for index in 0..indexes.count(()) {
+                            let model_index = indexes.at(index);
+                            if model_index.is_valid() {
+                                match table_definition.fields[model_index.column() as usize].field_type {
+                                    FieldType::Float | FieldType::Double => has_floats = true,
+                                    FieldType::Integer | FieldType::LongInteger => {},
+                                    _ => continue,
+                                }
+
+                                let value = unsafe { model.as_ref().unwrap().item_from_index(&model_index).as_ref().unwrap().data(2).to_string().to_std_string() };
+                                if let Ok(value) = value.parse::<f64>() { values.push(value); }
+                            }
+                        }
+                        Some(values)
+                    } else { None };
+
+                    let text = get_selection_stats_text(visible_rows, selected_cells, values.as_ref().map(|x| &**x), has_floats);
+                    unsafe { selection_stats_label.as_mut().unwrap().set_text(&QString::from_std_str(text)); }
+                }
+            )),
+
+            slot_schedule_selection_stats: SlotItemSelectionRefItemSelectionRef::new(move |_,_| {
+                unsafe { selection_stats_timer.as_mut().unwrap().start(()); }
+            }),
+
+            slot_schedule_selection_stats_rows: SlotModelIndexRefCIntCInt::new(move |_,_,_| {
+                unsafe { selection_stats_timer.as_mut().unwrap().start(()); }
+            }),
+
             slot_row_filter_change_text: SlotStringRef::new(clone!(
-                packed_file_path => move |filter_text| {
+                packed_file_path,
+                table_definition,
+                column_filter_line_edits => move |filter_text| {
                     Self::filter_table(
                         Some(QString::from_std_str(filter_text.to_std_string())),
                         None,
                         None,
+                        model,
                         filter_model,
+                        &table_definition,
                         row_filter_line_edit,
                         row_filter_column_selector,
                         row_filter_case_sensitive_button,
+                        &column_filter_line_edits,
                         update_search_stuff,
                         &packed_file_path,
                     ); 
                 }
             )),
             slot_row_filter_change_column: SlotCInt::new(clone!(
-                packed_file_path => move |index| {
+                packed_file_path,
+                table_definition,
+                column_filter_line_edits => move |_| {
                     Self::filter_table(
                         None,
-                        Some(index),
                         None,
+                        None,
+                        model,
                         filter_model,
+                        &table_definition,
                         row_filter_line_edit,
                         row_filter_column_selector,
                         row_filter_case_sensitive_button,
+                        &column_filter_line_edits,
                         update_search_stuff,
                         &packed_file_path,
                     ); 
                 }
             )),
             slot_row_filter_change_case_sensitive: SlotBool::new(clone!(
-                packed_file_path => move |case_sensitive| {
+                packed_file_path,
+                table_definition,
+                column_filter_line_edits => move |case_sensitive| {
                     Self::filter_table(
                         None,
                         None,
                         Some(case_sensitive),
+                        model,
+                        filter_model,
+                        &table_definition,
+                        row_filter_line_edit,
+                        row_filter_column_selector,
+                        row_filter_case_sensitive_button,
+                        &column_filter_line_edits,
+                        update_search_stuff,
+                        &packed_file_path,
+                    ); 
+                }
+            )),
+            slot_column_filter_change_text: SlotStringRef::new(move |_| {
+                unsafe { column_filters_timer.as_mut().unwrap().start(()); }
+            }),
+            slot_apply_column_filters: SlotNoArgs::new(clone!(
+                packed_file_path,
+                table_definition,
+                column_filter_line_edits => move || {
+                    Self::filter_table(
+                        None,
+                        None,
+                        None,
+                        model,
                         filter_model,
+                        &table_definition,
                         row_filter_line_edit,
                         row_filter_column_selector,
                         row_filter_case_sensitive_button,
+                        &column_filter_line_edits,
                         update_search_stuff,
                         &packed_file_path,
                     ); 
@@ -1006,26 +1470,28 @@ impl PackedFileTableView {
                 sender_qt_data,
                 table_definition => move |_| {
 
-                    // Get the indexes ready for battle.
+                    // Get the selected rows, both as they're shown and in the source model, as sorting and filtering can change their order.
                     let indexes = unsafe { table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection().indexes() };
-                    let indexes_sorted = (0..indexes.count(())).map(|x| indexes.at(x)).collect::<Vec<&ModelIndex>>();
-                    let mut indexes_sorted = get_real_indexes(&indexes_sorted, filter_model);
-                    sort_indexes_by_model(&mut indexes_sorted);
-                    dedup_indexes_per_row(&mut indexes_sorted);
-                    let mut row_numbers = vec![];
-
-                    // If nothing is selected, we just append the row at the end.
-                    if indexes_sorted.is_empty() {
-                        let rows = create_empty_rows(&table_definition, 1);
-                        unsafe { model.as_mut().unwrap().append_row(&rows[0]); } 
-                        row_numbers.push(unsafe { model.as_mut().unwrap().row_count(()) - 1 });
+                    let selected_rows = (0..indexes.count(())).map(|x| indexes.at(x))
+                        .filter(|x| x.is_valid())
+                        .map(|x| (x.row(), unsafe { filter_model.as_mut().unwrap().map_to_source(x).row() }))
+                        .collect::<Vec<(i32, i32)>>();
+
+                    // Insert all the new rows together, above the first selected row.
+                    let (position, row_numbers) = get_rows_to_insert_above(&selected_rows);
+                    let rows = create_empty_rows(&table_definition, row_numbers.len() as u32);
+                    for (offset, row) in rows.iter().enumerate() {
+                        unsafe { model.as_mut().unwrap().insert_row((position + offset as i32, row)); }
                     }
-                
-                    for index in indexes_sorted.iter().rev() {
-                        row_numbers.push(index.row());
-                        let rows = create_empty_rows(&table_definition, 1);
-                        unsafe { model.as_mut().unwrap().insert_row((index.row(), &rows[0])); }
+
+                    // Select the new rows that are visible, so the user can start editing them.
+                    let mut selection = ItemSelection::new(());
+                    for row in &row_numbers {
+                        let model_index_filtered = unsafe { filter_model.as_mut().unwrap().map_from_source(&model.as_mut().unwrap().index((*row, 0))) };
+                        if model_index_filtered.is_valid() { selection.select(&model_index_filtered, &model_index_filtered); }
                     }
+                    let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };
+                    unsafe { selection_model.as_mut().unwrap().select((&selection, Flags::from_enum(SelectionFlag::ClearAndSelect) | Flags::from_enum(SelectionFlag::Rows))); }
 
                     // Save, so there are no discrepances between the normal and undo models.
                     Self::save_to_packed_file(
@@ -1044,9 +1510,6 @@ impl PackedFileTableView {
                     // Update the search stuff, if needed.
                     unsafe { update_search_stuff.as_mut().unwrap().trigger(); }
                     {
-
-                        // The undo mode needs this reversed.
-                        row_numbers.reverse();
                         let mut table_state_data = table_state_data.borrow_mut();
                         let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
                         table_state_data.undo_history.push(TableOperations::AddRows(row_numbers));
@@ -1152,46 +1615,47 @@ impl PackedFileTableView {
                     // If we got an operation, get all the cells in the selection, try to apply the operation to them and,
                     // if the resulting value is valid in each of them, apply it.
                     if let Some(operation) = create_apply_maths_dialog(&app_ui) {
+                        let operation = match parse_maths_operation(&operation) {
+                            Ok(operation) => operation,
+                            Err(error) => return show_dialog(app_ui.window, false, error),
+                        };
 
                         let mut results = 0;
+                        let mut failed = 0;
                         let indexes_visual = unsafe { table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection().indexes() };
                         let indexes_visual = (0..indexes_visual.count(())).map(|x| indexes_visual.at(x)).collect::<Vec<&ModelIndex>>();
                         let indexes_real = get_real_indexes(&indexes_visual, filter_model);
                         for index in indexes_real {
-                            if index.is_valid() { 
-
-                                // First, we replace {x} with our current value. Then, we try to parse with meval.
-                                // And finally, we try to put the new value in the cell.
-                                let current_value = unsafe { model.as_ref().unwrap().item_from_index(&index).as_ref().unwrap().data(2).to_string().to_std_string() };
-                                let real_operation = operation.replace("{x}", &current_value);
+                            if index.is_valid() {
+
+                                // Non-numeric columns are skipped.
+                                let item = unsafe { model.as_mut().unwrap().item_from_index(&index) };
+                                let current_value = match table_definition.fields[index.column() as usize].field_type {
+                                    FieldType::Float => DecodedData::Float(unsafe { item.as_ref().unwrap().data(2).to_float() }),
+                                    FieldType::Integer => DecodedData::Integer(unsafe { item.as_ref().unwrap().data(2).to_int() }),
+                                    FieldType::LongInteger => DecodedData::LongInteger(unsafe { item.as_ref().unwrap().data(2).to_long_long() }),
+                                    FieldType::Double => DecodedData::Double(unsafe { item.as_ref().unwrap().data(2).to_double() }),
+                                    _ => continue,
+                                };
 
-                                // We only do this if the current value is a valid number.
-                                if let Ok(result) = meval::eval_str(&real_operation) {
-                                    let mut is_valid = false;
-                                    
-                                    // If we got a current value and it's different, it's a valid cell.
-                                    if let Ok(current_value) = current_value.parse::<f64>() {
-                                        if (result - current_value).abs() >= std::f64::EPSILON { 
-                                            is_valid = true;
-                                        }
+                                // If the result is not a valid number, we keep the current value and report it later.
+                                let result = match apply_maths_to_value(&operation, &current_value) {
+                                    Some(result) => result,
+                                    None => {
+                                        failed += 1;
+                                        continue;
                                     }
+                                };
 
-                                    // Otherwise, it's a change over a string. Allow it.
-                                    else { is_valid = true; }
-                                    if is_valid {    
-                                        match table_definition.fields[index.column() as usize].field_type {
-                                            FieldType::Float => unsafe { model.as_mut().unwrap().item_from_index(&index).as_mut().unwrap().set_data((&Variant::new2(result as f32), 2)) }
-                                            FieldType::Integer => unsafe { model.as_mut().unwrap().item_from_index(&index).as_mut().unwrap().set_data((&Variant::new0(result as i32), 2)) },
-                                            FieldType::LongInteger => unsafe { model.as_mut().unwrap().item_from_index(&index).as_mut().unwrap().set_data((&Variant::new2(result as i64), 2)) },
-                                            
-                                            FieldType::StringU8 |
-                                            FieldType::StringU16 |
-                                            FieldType::OptionalStringU8 |
-                                            FieldType::OptionalStringU16 => unsafe { model.as_mut().unwrap().item_from_index(&index).as_mut().unwrap().set_text(&QString::from_std_str(&format!("{}", result))) },
-                                            _ => continue,
-                                        }
-                                        results += 1;
+                                if result != current_value {
+                                    match result {
+                                        DecodedData::Float(result) => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(result), 2)) },
+                                        DecodedData::Integer(result) => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(result), 2)) },
+                                        DecodedData::LongInteger(result) => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(result), 2)) },
+                                        DecodedData::Double(result) => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(result), 2)) },
+                                        _ => unreachable!(),
                                     }
+                                    results += 1;
                                 }
                             }
                         }
@@ -1219,6 +1683,10 @@ impl PackedFileTableView {
 
                             unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
                         }
+
+                        if failed > 0 {
+                            show_dialog(app_ui.window, false, format!("<p>{} cells kept their value, because the result of the operation in them was not a valid number (like a division by zero or an overflow).</p>", failed));
+                        }
                     }
                 }
             )),
@@ -1230,15 +1698,24 @@ impl PackedFileTableView {
                 app_ui => move |_| {
 
                     // If we got a sequence, get all the cells in the selection, try to apply it to them.
-                    if let Some(mut sequence) = create_rewrite_selection_dialog(&app_ui) {
+                    if let Some((mut sequence, regex)) = create_rewrite_selection_dialog(&app_ui) {
 
                         // For some reason Qt adds & sometimes, so remove it if you found it.
                         if let Some(index) = sequence.find('&') { sequence.remove(index); }
 
+                        // If we got a regex, we're in regex mode.
+                        let regex = if regex.is_empty() { None } else {
+                            match Regex::new(&regex) {
+                                Ok(regex) => Some(regex),
+                                Err(_) => return show_dialog(app_ui.window, false, ErrorKind::InvalidRegex(regex)),
+                            }
+                        };
+
                         // Get all the selected cells. We can rewrite any kind of cell (except Booleans),
                         // so we have to do a first pass to ensure everything is valid before applying the data.
                         let indexes = unsafe { filter_model.as_mut().unwrap().map_selection_to_source(&table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection()).indexes() };
                         let mut results = vec![];
+                        let mut skipped_cells = 0;
                         for index in 0..indexes.count(()) {
                             let model_index = indexes.at(index);
 
@@ -1250,10 +1727,15 @@ impl PackedFileTableView {
                                 let text = match column_type {
 
                                     // As I said, we skip booleans.
-                                    FieldType::Boolean => continue,
+                                    FieldType::Boolean => {
+                                        skipped_cells += 1;
+                                        continue
+                                    },
                                     FieldType::Float |
                                     FieldType::Integer |
                                     FieldType::LongInteger |
+                                    FieldType::Double |
+                                    FieldType::Colour |
                                     FieldType::StringU8 |
                                     FieldType::StringU16 |
                                     FieldType::OptionalStringU8 |
@@ -1261,17 +1743,11 @@ impl PackedFileTableView {
                                 };
 
                                 // If any of the new texts is incompatible with his cells, skip it.
-                                let replaced_text = sequence.to_owned().replace("{x}", &text).replace("{X}", &text);
-                                match column_type {
-                                    FieldType::Boolean => continue,
-                                    FieldType::Float => if replaced_text.parse::<f32>().is_err() { continue; }
-                                    FieldType::Integer => if replaced_text.parse::<i32>().is_err() { continue; }
-                                    FieldType::LongInteger => if replaced_text.parse::<i64>().is_err() { continue; }
-                                    FieldType::StringU8 |
-                                    FieldType::StringU16 |
-                                    FieldType::OptionalStringU8 |
-                                    FieldType::OptionalStringU16 => {},
-                                };
+                                let replaced_text = rewrite_text(&text, &sequence, regex.as_ref());
+                                if !is_valid_for_field_type(&replaced_text, column_type) {
+                                    skipped_cells += 1;
+                                    continue;
+                                }
 
                                 results.push((model_index, replaced_text));
                             }
@@ -1312,6 +1788,15 @@ impl PackedFileTableView {
                                     }
                                 },
 
+                                FieldType::Double => {
+                                    let result = result.parse::<f64>().unwrap();
+                                    if item.data(2).to_double() != result {
+                                        item.set_data((&Variant::new0(result), 2));
+                                        changed_cells += 1;
+                                    }
+                                },
+
+                                FieldType::Colour |
                                 FieldType::StringU8 |
                                 FieldType::StringU16 |
                                 FieldType::OptionalStringU8 |
@@ -1325,15 +1810,64 @@ impl PackedFileTableView {
                             }
                         }
 
+                        if changed_cells > 0 {
+                            {
+                                let mut table_state_data = table_state_data.borrow_mut();
+                                let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
+
+                                // If we finished rewriting cells, fix the undo history to have all the previous changes merged into one.
+                                // Keep in mind that `None` results should be ignored here.
+                                let len = table_state_data.undo_history.len();
+                                let mut edits_data = vec![];
+
+                                {
+                                    let mut edits = table_state_data.undo_history.drain((len - changed_cells)..);
+                                    for edit in &mut edits { if let TableOperations::Editing(mut edit) = edit { edits_data.append(&mut edit); }}
+                                }
+
+                                table_state_data.undo_history.push(TableOperations::Editing(edits_data));
+                                table_state_data.redo_history.clear();
+                                update_undo_model(model, table_state_data.undo_model); 
+                            }
+
+                            unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
+                        }
+
+                        // Let the user know about the cells we couldn't rewrite.
+                        if skipped_cells > 0 {
+                            show_dialog(app_ui.window, true, format!("<p>{} cells changed. {} cells skipped, because the rewritten text is not valid for their column.</p>", changed_cells, skipped_cells));
+                        }
+                    }
+                }
+            )),
+
+            slot_context_menu_toggle_booleans: SlotBool::new(clone!(
+                packed_file_path,
+                table_state_data,
+                table_definition => move |_| {
+
+                    // Get all the selected boolean cells and toggle them. The copy/paste stuff keeps working with "true"/"false",
+                    // as it reads and writes the check state of the items.
+                    let indexes = unsafe { filter_model.as_mut().unwrap().map_selection_to_source(&table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection()).indexes() };
+                    let mut changed_cells = 0;
+                    for index in 0..indexes.count(()) {
+                        let model_index = indexes.at(index);
+                        if model_index.is_valid() && table_definition.fields[model_index.column() as usize].field_type == FieldType::Boolean {
+                            let item = unsafe { model.as_ref().unwrap().item_from_index(model_index).as_mut().unwrap() };
+                            let new_value = if item.check_state() == CheckState::Checked { CheckState::Unchecked } else { CheckState::Checked };
+                            item.set_check_state(new_value);
+                            changed_cells += 1;
+                        }
+                    }
+
+                    // Merge all the toggles into a single undo step.
+                    if changed_cells > 0 {
                         {
                             let mut table_state_data = table_state_data.borrow_mut();
                             let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
-
-                            // If we finished rewriting cells, fix the undo history to have all the previous changes merged into one.
-                            // Keep in mind that `None` results should be ignored here.
                             let len = table_state_data.undo_history.len();
                             let mut edits_data = vec![];
-                            
+
                             {
                                 let mut edits = table_state_data.undo_history.drain((len - changed_cells)..);
                                 for edit in &mut edits { if let TableOperations::Editing(mut edit) = edit { edits_data.append(&mut edit); }}
@@ -1349,6 +1883,164 @@ impl PackedFileTableView {
                 }
             )),
 
+            slot_context_menu_pick_colour: SlotBool::new(clone!(
+                app_ui,
+                colour_columns => move |_| {
+
+                    // The colour we edit is the one of the current cell, if it holds a colour. The change is a normal edit, so it's a single undo step.
+                    let model_index = unsafe { filter_model.as_mut().unwrap().map_to_source(&table_view.as_mut().unwrap().current_index()) };
+                    if !model_index.is_valid() || !colour_columns.contains(&(model_index.column() as usize)) { return }
+                    let item = unsafe { model.as_ref().unwrap().item_from_index(&model_index).as_mut().unwrap() };
+                    let text = item.text().to_std_string();
+                    let initial_colour = match get_colour_from_text(&text) {
+                        Some((red, green, blue)) => Color::new((i32::from(red), i32::from(green), i32::from(blue))),
+                        None => Color::new(GlobalColor::White),
+                    };
+
+                    // If the user cancels the dialog, the colour is invalid.
+                    let colour = unsafe { ColorDialog::get_color_unsafe((&initial_colour, app_ui.window as *mut Widget)) };
+                    if !colour.is_valid() { return }
+
+                    let new_text = get_text_from_colour((colour.red() as u8, colour.green() as u8, colour.blue() as u8));
+                    if new_text != text { item.set_text(&QString::from_std_str(new_text)); }
+                }
+            )),
+
+            slot_context_menu_pick_reference: SlotBool::new(clone!(
+                app_ui,
+                dependency_data => move |_| {
+
+                    // Only reference cells with referenced data can be picked. The change is a normal edit, so it's a single undo step.
+                    let model_index = unsafe { filter_model.as_mut().unwrap().map_to_source(&table_view.as_mut().unwrap().current_index()) };
+                    if !model_index.is_valid() { return }
+                    if let Some(data) = dependency_data.get(&model_index.column()) {
+                        if data.is_empty() { return }
+                        let item = unsafe { model.as_ref().unwrap().item_from_index(&model_index).as_mut().unwrap() };
+                        let text = item.text().to_std_string();
+                        if let Some(new_text) = create_reference_picker_dialog(&app_ui, data, &text) {
+                            if new_text != text { item.set_text(&QString::from_std_str(new_text)); }
+                        }
+                    }
+                }
+            )),
+
+            slot_context_menu_edit_in_window: SlotBool::new(clone!(
+                app_ui,
+                table_type,
+                table_definition => move |_| {
+
+                    // Only text cells can be edited in a window. The change is a normal edit, so it's a single undo step.
+                    let model_index = unsafe { filter_model.as_mut().unwrap().map_to_source(&table_view.as_mut().unwrap().current_index()) };
+                    if !model_index.is_valid() { return }
+                    match table_definition.fields[model_index.column() as usize].field_type {
+                        FieldType::StringU8 |
+                        FieldType::StringU16 |
+                        FieldType::OptionalStringU8 |
+                        FieldType::OptionalStringU16 => {
+                            let item = unsafe { model.as_ref().unwrap().item_from_index(&model_index).as_mut().unwrap() };
+                            let text = item.text().to_std_string();
+
+                            // Locs keep their tabs and newlines escaped in the table, so we unescape them for editing.
+                            let is_loc = if let TableType::LOC(_) = *table_type.borrow() { true } else { false };
+                            let text_to_edit = if is_loc { text.replace("\\t", "\t").replace("\\n", "\n") } else { text.to_owned() };
+                            if let Some(mut new_text) = create_text_edit_dialog(&app_ui, &text_to_edit) {
+                                if is_loc { new_text = new_text.replace("\t", "\\t").replace("\n", "\\n"); }
+                                if new_text != text { item.set_text(&QString::from_std_str(new_text)); }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            )),
+
+            slot_context_menu_go_to_definition: SlotBool::new(clone!(
+                app_ui,
+                open_table_views,
+                table_definition,
+                receiver_qt,
+                sender_qt,
+                sender_qt_data => move |_| {
+
+                    // Only cells of reference columns can go to their definition.
+                    let model_index = unsafe { filter_model.as_mut().unwrap().map_to_source(&table_view.as_mut().unwrap().current_index()) };
+                    if !model_index.is_valid() { return }
+                    let (ref_table, ref_column) = match table_definition.fields[model_index.column() as usize].field_is_reference {
+                        Some(ref reference) => reference.clone(),
+                        None => return,
+                    };
+
+                    let value = unsafe { model.as_ref().unwrap().item_from_index(&model_index).as_mut().unwrap().text().to_std_string() };
+                    sender_qt.send(Commands::FindReferencedRow).unwrap();
+                    sender_qt_data.send(Data::StringStringString((ref_table, ref_column, value))).unwrap();
+                    match check_message_validity_recv2(&receiver_qt) {
+
+                        // If the row is in our PackFile, open his table and select it. This closes the current table.
+                        Data::ReferencedRow(ReferencedRow::PackFile(path, row, column)) => {
+                            let item = get_item_from_type(app_ui.folder_tree_model, &TreePathType::File(path.to_vec()));
+                            let model_index = unsafe { app_ui.folder_tree_model.as_mut().unwrap().index_from_item(item) };
+                            let filtered_index = unsafe { app_ui.folder_tree_filter.as_ref().unwrap().map_from_source(&model_index) };
+
+                            // If it's not in the current TreeView Filter we CAN'T OPEN IT.
+                            if filtered_index.is_valid() {
+                                let selection_model = unsafe { app_ui.folder_tree_view.as_mut().unwrap().selection_model() };
+                                unsafe { selection_model.as_mut().unwrap().select((&filtered_index, Flags::from_enum(SelectionFlag::ClearAndSelect))); }
+                                unsafe { app_ui.folder_tree_view.as_mut().unwrap().scroll_to(&filtered_index); }
+                                expand_treeview_to_item(app_ui.folder_tree_view, app_ui.folder_tree_filter, app_ui.folder_tree_model, &path);
+
+                                // Forget any previous TableView of that table, so we only get one if the opening worked.
+                                open_table_views.borrow_mut().remove(&path);
+                                unsafe { app_ui.tree_view_open_packedfile.as_mut().unwrap().trigger(); }
+
+                                let packed_file_table = open_table_views.borrow().get(&path).cloned();
+                                if let Some(packed_file_table) = packed_file_table {
+                                    let packed_file_filter = unsafe { packed_file_table.as_mut().unwrap().model() as *mut SortFilterProxyModel };
+                                    let packed_file_model = unsafe { packed_file_filter.as_mut().unwrap().source_model() };
+                                    let model_index = unsafe { packed_file_filter.as_mut().unwrap().map_from_source(&packed_file_model.as_mut().unwrap().index((row as i32, column as i32))) };
+                                    select_search_matches(packed_file_table, &[&model_index], 0);
+                                }
+                            }
+                            else { show_dialog(app_ui.window, false, ErrorKind::PackedFileNotInFilter); }
+                        }
+
+                        // If it's only in the game files, we cannot edit it, so we just show it.
+                        Data::ReferencedRow(ReferencedRow::Dependency(path, columns, row)) => {
+                            let fields = columns.iter().zip(row.iter()).map(|(column, value)| format!("<li><b>{}</b>: {}</li>", column, value)).collect::<String>();
+                            show_dialog(app_ui.window, true, format!("<p>Row found in the game files, in <i>'{}'</i>:</p><ul>{}</ul>", path.join("/"), fields));
+                        }
+
+                        Data::Error(error) => show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+                }
+            )),
+
+            slot_context_menu_find_usages: SlotBool::new(clone!(
+                app_ui,
+                packed_file_path,
+                table_definition,
+                table_type,
+                sender_qt,
+                sender_qt_data => move |_| {
+
+                    // Only keys of DB Tables can be referenced by other tables.
+                    match *table_type.borrow() {
+                        TableType::DB(_) => {},
+                        _ => return,
+                    }
+
+                    let model_index = unsafe { filter_model.as_mut().unwrap().map_to_source(&table_view.as_mut().unwrap().current_index()) };
+                    if !model_index.is_valid() { return }
+                    let field = &table_definition.fields[model_index.column() as usize];
+                    if !field.field_is_key { return }
+
+                    // The results are received and shown by the "Global Search" view.
+                    let value = unsafe { model.as_ref().unwrap().item_from_index(&model_index).as_mut().unwrap().text().to_std_string() };
+                    sender_qt.send(Commands::FindUsages).unwrap();
+                    sender_qt_data.send(Data::VecStringStringString((packed_file_path.borrow().to_vec(), field.field_name.to_owned(), value))).unwrap();
+                    unsafe { app_ui.global_search_show_usages.as_mut().unwrap().trigger(); }
+                }
+            )),
+
             slot_context_menu_clone: SlotBool::new(clone!(
                 global_search_explicit_paths,
                 packed_file_path,
@@ -1374,16 +2066,9 @@ impl PackedFileTableView {
                     rows.reverse();
 
                     // For each row to clone, create a new one, duplicate the items and add the row under the old one.
+                    let key_suffix = SETTINGS.lock().unwrap().settings_string["cloned_key_suffix"].to_owned();
                     for row in &rows {
-                        let mut qlist = ListStandardItemMutPtr::new(());
-                        for column in 0..table_definition.fields.len() {
-
-                            // Get the original item and his clone.
-                            let original_item = unsafe { model.as_mut().unwrap().item((*row, column as i32)) };
-                            let item = unsafe { original_item.as_mut().unwrap().clone() };
-                            unsafe { item.as_mut().unwrap().set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkGreen } else { GlobalColor::Green })); }
-                            unsafe { qlist.append_unsafe(&item); }
-                        }
+                        let qlist = clone_row(model, *row, &table_definition, &key_suffix);
 
                         // Insert the new row after the original one.
                         unsafe { model.as_mut().unwrap().insert_row((row + 1, &qlist)); }
@@ -1446,16 +2131,9 @@ impl PackedFileTableView {
                     rows.dedup();
 
                     // For each row to clone, create a new one, duplicate the items and add the row under the old one.
+                    let key_suffix = SETTINGS.lock().unwrap().settings_string["cloned_key_suffix"].to_owned();
                     for row in &rows {
-                        let mut qlist = ListStandardItemMutPtr::new(());
-                        for column in 0..table_definition.fields.len() {
-
-                            // Get the original item and his clone.
-                            let original_item = unsafe { model.as_mut().unwrap().item((*row, column as i32)) };
-                            let item = unsafe { original_item.as_mut().unwrap().clone() };
-                            unsafe { item.as_mut().unwrap().set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkGreen } else { GlobalColor::Green })); }
-                            unsafe { qlist.append_unsafe(&item); }
-                        }
+                        let qlist = clone_row(model, *row, &table_definition, &key_suffix);
 
                         // Insert the new row after the original one.
                         unsafe { model.as_mut().unwrap().append_row(&qlist); }
@@ -1526,7 +2204,7 @@ impl PackedFileTableView {
                                 _ => return
                             }
                         }
-                        else { copy.push_str(&QString::to_std_string(unsafe { &item.as_mut().unwrap().text() })); }
+                        else { copy.push_str(&quote_clipboard_cell(&QString::to_std_string(unsafe { &item.as_mut().unwrap().text() }))); }
 
                         // Add a \t to separate fields except if it's the last field.
                         if cycle < (indexes_sorted.len() - 1) { copy.push('\t'); }
@@ -1537,6 +2215,34 @@ impl PackedFileTableView {
                 unsafe { GuiApplication::clipboard().as_mut().unwrap().set_text(&QString::from_std_str(copy)); }
             }),
 
+            slot_context_menu_copy_as_markdown_table: SlotBool::new(clone!(
+                table_definition => move |_| {
+                    let (columns, rows) = get_visual_selection(table_view, filter_model, model);
+                    let headers = columns.iter().map(|x| table_definition.fields[*x as usize].field_name.to_owned()).collect::<Vec<String>>();
+                    unsafe { GuiApplication::clipboard().as_mut().unwrap().set_text(&QString::from_std_str(to_markdown_table(&headers, &rows))); }
+                }
+            )),
+
+            slot_context_menu_copy_with_headers: SlotBool::new(clone!(
+                table_definition => move |_| {
+
+                    // Hidden columns are skipped, so the copy matches what the user sees.
+                    let (columns, rows) = get_visual_selection(table_view, filter_model, model);
+                    let visible = columns.iter().map(|x| unsafe { !table_view.as_ref().unwrap().is_column_hidden(*x) }).collect::<Vec<bool>>();
+                    let headers = columns.iter().zip(&visible).filter(|(_, x)| **x).map(|(x, _)| table_definition.fields[*x as usize].field_name.to_owned()).collect::<Vec<String>>();
+                    let rows = rows.iter().map(|row| row.iter().zip(&visible).filter(|(_, x)| **x).map(|(x, _)| x.to_owned()).collect()).collect::<Vec<Vec<String>>>();
+                    unsafe { GuiApplication::clipboard().as_mut().unwrap().set_text(&QString::from_std_str(to_tsv_with_headers(&headers, &rows))); }
+                }
+            )),
+
+            slot_context_menu_copy_as_json: SlotBool::new(clone!(
+                table_definition => move |_| {
+                    let (columns, rows) = get_visual_selection_cells(table_view, filter_model, model);
+                    let fields = columns.iter().map(|x| &table_definition.fields[*x as usize]).collect::<Vec<&Field>>();
+                    unsafe { GuiApplication::clipboard().as_mut().unwrap().set_text(&QString::from_std_str(to_json_objects(&fields, &rows))); }
+                }
+            )),
+
             slot_context_menu_copy_as_lua_table: SlotBool::new(clone!(
                 table_definition,
                 table_type => move |_| {
@@ -1572,6 +2278,8 @@ impl PackedFileTableView {
                                 },
                                 DecodedData::Integer(ref data) => format!("{}", data),
                                 DecodedData::LongInteger(ref data) => format!("{}", data),
+                                DecodedData::Double(ref data) => format!("{}", data),
+                                DecodedData::Colour(ref data) => format!("{}", data),
 
                                 // All these are Strings, so they need to escape certain chars and include commas in Lua.
                                 DecodedData::StringU8(ref data) |
@@ -1693,7 +2401,7 @@ impl PackedFileTableView {
 
                     // Get the current selection.
                     let clipboard = GuiApplication::clipboard();
-                    let mut text = unsafe { clipboard.as_mut().unwrap().text(()).to_std_string() };
+                    let text = unsafe { clipboard.as_mut().unwrap().text(()).to_std_string() };
 
                     // Get the current selection and his, visually speaking, first item (top-left).
                     let indexes = unsafe { table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection().indexes() };
@@ -1701,10 +2409,9 @@ impl PackedFileTableView {
                     sort_indexes_visually(&mut indexes_sorted_visual, table_view);
                     let base_index_visual = if !indexes_sorted_visual.is_empty() { &indexes_sorted_visual[0] } else { return };
 
-                    // If the text ends in \n, remove it. Excel things.
-                    if text.ends_with('\n') { text.pop(); }
-                    let rows = text.split('\n').collect::<Vec<&str>>();
-                    let rows = rows.iter().map(|x| x.split('\t').collect::<Vec<&str>>()).collect::<Vec<Vec<&str>>>();
+                    // Parse it as a TSV with quotes, so cells with newlines or tabs from Excel/LibreOffice stay in one piece.
+                    let rows = parse_clipboard_tsv(&text);
+                    let rows = rows.iter().map(|x| x.iter().map(|x| &**x).collect::<Vec<&str>>()).collect::<Vec<Vec<&str>>>();
 
                     // We're going to try and check in square mode. That means, start in the selected cell, then right
                     // until we reach a \n, then return to the initial column. Due to how sorting works, we have to do
@@ -1727,6 +2434,8 @@ impl PackedFileTableView {
                                     FieldType::Float => if text.parse::<f32>().is_err() { false } else { true },
                                     FieldType::Integer => if text.parse::<i32>().is_err() { false } else { true },
                                     FieldType::LongInteger => if text.parse::<i64>().is_err() { false } else { true },
+                                    FieldType::Double |
+                                    FieldType::Colour => is_valid_for_field_type(text, field.field_type),
 
                                     // All these are Strings, so we can skip their checks....
                                     FieldType::StringU8 |
@@ -1802,6 +2511,14 @@ impl PackedFileTableView {
                                 }
                             },
 
+                            FieldType::Double => {
+                                let new_value = text.parse::<f64>().unwrap();
+                                if unsafe { model.as_ref().unwrap().data(real_cell).to_double() } != new_value {
+                                    unsafe { model.as_mut().unwrap().set_data((real_cell, &Variant::new0(new_value), 2)); }
+                                    changed_cells += 1;
+                                }
+                            },
+
                             _ => {
                                 let current_value = unsafe { model.as_ref().unwrap().data(real_cell).to_string().to_std_string() };
                                 if &current_value != *text {
@@ -1867,14 +2584,9 @@ impl PackedFileTableView {
                     // If whatever it's in the Clipboard is pasteable in our selection...
                     if Self::check_clipboard_append_rows(table_view, &table_definition) {
 
-                        // Get the text from the clipboard.
-                        let clipboard = GuiApplication::clipboard();
-                        let mut text = unsafe { clipboard.as_mut().unwrap().text(()).to_std_string() };
-
-                        // If the text ends in \n, remove it. Excel things. We don't use newlines, so replace them with '\t'.
-                        if text.ends_with('\n') { text.pop(); }
-                        let text = text.replace('\n', "\t");
-                        let text = text.split('\t').collect::<Vec<&str>>();
+                        // Get the rows from the clipboard, already fitted to the columns of the table, in a single line.
+                        let (text, truncated) = Self::get_clipboard_rows_to_append(table_view, &table_definition);
+                        let text = text.iter().map(|x| &**x).collect::<Vec<&str>>();
 
                         // Create a new list of StandardItem, ready to be populated.
                         let mut column = 0;
@@ -1911,8 +2623,13 @@ impl PackedFileTableView {
                                     item.set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkGreen } else { GlobalColor::Green }));
                                 },
 
-                                // In any other case, we treat it as a string. Type-checking is done before this and while saving.
-                                _ => {
+                                FieldType::Double => {
+                                    item.set_data((&Variant::new0(cell.parse::<f64>().unwrap()), 2));
+                                    item.set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkGreen } else { GlobalColor::Green }));
+                                },
+
+                                // In any other case, we treat it as a string. Type-checking is done before this and while saving.
+                                _ => {
                                     item.set_text(&QString::from_std_str(cell));
                                     item.set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkGreen } else { GlobalColor::Green }));
                                 }
@@ -1941,63 +2658,6 @@ impl PackedFileTableView {
                             else { column += 1; }
                         }
 
-                        // If the last list was incomplete...
-                        if column != 0 {
-
-                            // For each columns we lack...
-                            for column in column..table_definition.fields.len() as i32 {
-
-                                // Get the new field.
-                                let column_logical_index = unsafe { table_view.as_ref().unwrap().horizontal_header().as_ref().unwrap().logical_index(column) };
-                                let field = &table_definition.fields[column_logical_index as usize];
-
-                                // Create a new Item.
-                                let mut item = match field.field_type {
-
-                                    // This one needs a couple of changes before turning it into an item in the table.
-                                    FieldType::Boolean => {
-                                        let mut item = StandardItem::new(());
-                                        item.set_editable(false);
-                                        item.set_checkable(true);
-                                        item.set_check_state(CheckState::Checked);
-                                        item
-                                    }
-
-                                    FieldType::Float => {
-                                        let mut item = StandardItem::new(());
-                                        item.set_data((&Variant::new2(0.0f32), 2));
-                                        item
-                                    },
-
-                                    FieldType::Integer => {
-                                        let mut item = StandardItem::new(());
-                                        item.set_data((&Variant::new0(0i32), 2));
-                                        item
-                                    },
-                                    
-                                    FieldType::LongInteger => {
-                                        let mut item = StandardItem::new(());
-                                        item.set_data((&Variant::new2(0i64), 2));
-                                        item
-                                    },
-
-                                    // All these are Strings, so it can be together.
-                                    FieldType::StringU8 |
-                                    FieldType::StringU16 |
-                                    FieldType::OptionalStringU8 |
-                                    FieldType::OptionalStringU16 => StandardItem::new(&QString::from_std_str("")),
-                                };
-
-                                item.set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkGreen } else { GlobalColor::Green }));
-                                qlist_unordered.push((column_logical_index, item.into_raw()));
-                            }
-
-                            // Append the list to the Table.
-                            qlist_unordered.sort_unstable_by_key(|x| x.0);
-                            for (_, item) in &qlist_unordered { unsafe { qlist.append_unsafe(&item.clone()); }}
-                            unsafe { model.as_mut().unwrap().append_row(&qlist); }
-                        }
-
                         // If we pasted something, try to save the PackedFile to the main PackFile.
                         if !text.is_empty() {
                             Self::save_to_packed_file(
@@ -2029,96 +2689,146 @@ impl PackedFileTableView {
 
                             unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
                         }
+
+                        if truncated {
+                            show_dialog(app_ui.window, true, "<p>Some of the pasted rows had more columns than the table. The extra cells have been ignored.</p>");
+                        }
                     }
                 }
             )),
 
             slot_context_menu_paste_to_fill_selection: SlotBool::new(clone!(
+                global_search_explicit_paths,
+                app_ui,
                 packed_file_path,
                 table_state_data,
+                table_type,
+                save_lock,
+                receiver_qt,
+                sender_qt,
+                sender_qt_data,
                 table_definition => move |_| {
 
-                    // If whatever it's in the Clipboard is pasteable in our selection...
-                    if Self::check_clipboard_to_fill_selection(&table_definition, table_view, model, filter_model) {
-
-                        // Get the text from the clipboard and the list of cells to paste to.
-                        let clipboard = GuiApplication::clipboard();
-                        let text = unsafe { clipboard.as_mut().unwrap().text(()).to_std_string() };
-                        let indexes = unsafe { filter_model.as_mut().unwrap().map_selection_to_source(&table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection()).indexes() };
+                    // Get the block of cells from the clipboard. If it's smaller than the selection, we repeat it to fill the selection.
+                    let clipboard = GuiApplication::clipboard();
+                    let block = parse_clipboard_tsv(&unsafe { clipboard.as_mut().unwrap().text(()).to_std_string() });
+                    let indexes = unsafe { table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection().indexes() };
+                    let indexes = (0..indexes.count(())).map(|x| indexes.at(x)).filter(|x| x.is_valid()).collect::<Vec<&ModelIndex>>();
+                    if indexes.is_empty() { return }
 
-                        let mut changed_cells = 0;
-                        for index in 0..indexes.count(()) {
-                            let model_index = indexes.at(index);
-                            if model_index.is_valid() {
+                    // The position of each cell in the block depends on his distance, visually speaking, to the top-left corner of the selection.
+                    let horizontal_header = unsafe { table_view.as_ref().unwrap().horizontal_header().as_ref().unwrap() };
+                    let vertical_header = unsafe { table_view.as_ref().unwrap().vertical_header().as_ref().unwrap() };
+                    let first_row = indexes.iter().map(|x| vertical_header.visual_index(x.row())).min().unwrap();
+                    let first_column = indexes.iter().map(|x| horizontal_header.visual_index(x.column())).min().unwrap();
+
+                    // The saving is locked meanwhile, as saving on each edit freezes the UI with big selections. We save once at the end.
+                    // Values equal to the current ones (like "05" in an Integer cell with 5) don't trigger an edit, so we count the edits
+                    // that reach the undo history instead of the cells we set.
+                    let undo_history_len = table_state_data.borrow().get(&*packed_file_path.borrow()).unwrap().undo_history.len();
+                    let mut skipped_cells = 0;
+                    *save_lock.borrow_mut() = true;
+                    for index in &indexes {
+                        let row = (vertical_header.visual_index(index.row()) - first_row) as usize;
+                        let column = (horizontal_header.visual_index(index.column()) - first_column) as usize;
+                        let text = get_tiled_cell(&block, row, column).to_owned();
+
+                        let model_index = unsafe { filter_model.as_mut().unwrap().map_to_source(index) };
+                        let field_type = table_definition.fields[model_index.column() as usize].field_type;
+                        if !is_valid_for_field_type(&text, field_type) {
+                            skipped_cells += 1;
+                            continue;
+                        }
 
-                                // Get the column of that cell.
-                                let column = model_index.column();
-                                let item = unsafe { model.as_mut().unwrap().item_from_index(&model_index) };
+                        // Depending on the column, we try to encode the data in one format or another.
+                        let item = unsafe { model.as_mut().unwrap().item_from_index(&model_index) };
+                        match field_type {
+                            FieldType::Boolean => {
+                                let current_value = unsafe { item.as_mut().unwrap().check_state() };
+                                let new_value = if text.to_lowercase() == "true" || text == "1" { CheckState::Checked } else { CheckState::Unchecked };
+                                if current_value != new_value {
+                                    unsafe { item.as_mut().unwrap().set_check_state(new_value); }
+                                }
+                            },
 
-                                // Depending on the column, we try to encode the data in one format or another.
-                                match table_definition.fields[column as usize].field_type {
-                                    FieldType::Boolean => {
-                                        let current_value = unsafe { item.as_mut().unwrap().check_state() };
-                                        let new_value = if text.to_lowercase() == "true" || text == "1" { CheckState::Checked } else { CheckState::Unchecked };
-                                        if current_value != new_value { 
-                                            unsafe { item.as_mut().unwrap().set_check_state(new_value); }
-                                            changed_cells += 1;
-                                        }
-                                    },
+                            FieldType::Float => {
+                                let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
+                                if *current_value != text {
+                                    unsafe { item.as_mut().unwrap().set_data((&Variant::new2(text.parse::<f32>().unwrap()), 2)); }
+                                }
+                            },
 
-                                    FieldType::Float => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if *current_value != text {
-                                            unsafe { item.as_mut().unwrap().set_data((&Variant::new2(text.parse::<f32>().unwrap()), 2)); }
-                                            changed_cells += 1;
-                                        }
-                                    },
+                            FieldType::Integer => {
+                                let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
+                                if *current_value != text {
+                                    unsafe { item.as_mut().unwrap().set_data((&Variant::new0(text.parse::<i32>().unwrap()), 2)); }
+                                }
+                            },
 
-                                    FieldType::Integer => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if *current_value != text {
-                                            unsafe { item.as_mut().unwrap().set_data((&Variant::new0(text.parse::<i32>().unwrap()), 2)); }
-                                            changed_cells += 1;
-                                        }
-                                    },
+                            FieldType::LongInteger => {
+                                let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
+                                if *current_value != text {
+                                    unsafe { item.as_mut().unwrap().set_data((&Variant::new2(text.parse::<i64>().unwrap()), 2)); }
+                                }
+                            },
 
-                                    FieldType::LongInteger => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if *current_value != text {
-                                            unsafe { item.as_mut().unwrap().set_data((&Variant::new2(text.parse::<i64>().unwrap()), 2)); }
-                                            changed_cells += 1;
-                                        }
-                                    },
+                            FieldType::Double => {
+                                let new_value = text.parse::<f64>().unwrap();
+                                if unsafe { item.as_mut().unwrap().data(2).to_double() } != new_value {
+                                    unsafe { item.as_mut().unwrap().set_data((&Variant::new0(new_value), 2)); }
+                                }
+                            },
 
-                                    _ => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if *current_value != text {
-                                            unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str(&text)); }
-                                            changed_cells += 1;
-                                        }
-                                    }
+                            _ => {
+                                let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
+                                if *current_value != text {
+                                    unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str(&text)); }
                                 }
                             }
                         }
+                    }
 
-                        // Fix the undo history to have all the previous changed merged into one.
-                        if changed_cells > 0 {
-                            {
-                                let mut table_state_data = table_state_data.borrow_mut();
-                                let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
-                                let len = table_state_data.undo_history.len();
-                                let mut edits_data = vec![];
-                                {
-                                    let mut edits = table_state_data.undo_history.drain((len - changed_cells)..);
-                                    for edit in &mut edits { if let TableOperations::Editing(mut edit) = edit { edits_data.append(&mut edit); }}
-                                }
+                    *save_lock.borrow_mut() = false;
+                    let changed_cells = table_state_data.borrow().get(&*packed_file_path.borrow()).unwrap().undo_history.len() - undo_history_len;
 
-                                table_state_data.undo_history.push(TableOperations::Editing(edits_data));
-                                table_state_data.redo_history.clear();
-                                update_undo_model(model, table_state_data.undo_model); 
+                    // Fix the undo history to have all the previous changed merged into one.
+                    if changed_cells > 0 {
+                        Self::save_to_packed_file(
+                            &sender_qt,
+                            &sender_qt_data,
+                            &receiver_qt,
+                            &app_ui,
+                            &packed_file_path,
+                            model,
+                            &global_search_explicit_paths,
+                            update_global_search_stuff,
+                            &table_definition,
+                            &mut table_type.borrow_mut(),
+                        );
+
+                        // Update the search stuff, if needed.
+                        unsafe { update_search_stuff.as_mut().unwrap().trigger(); }
+
+                        {
+                            let mut table_state_data = table_state_data.borrow_mut();
+                            let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
+                            let len = table_state_data.undo_history.len();
+                            let mut edits_data = vec![];
+                            {
+                                let mut edits = table_state_data.undo_history.drain((len - changed_cells)..);
+                                for edit in &mut edits { if let TableOperations::Editing(mut edit) = edit { edits_data.append(&mut edit); }}
                             }
-                            unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }                           
+
+                            table_state_data.undo_history.push(TableOperations::Editing(edits_data));
+                            table_state_data.redo_history.clear();
+                            update_undo_model(model, table_state_data.undo_model); 
                         }
+                        unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
+                    }
+
+                    // Let the user know about the cells that didn't accept the value.
+                    if skipped_cells > 0 {
+                        show_dialog(app_ui.window, true, format!("<p>{} cells changed. {} cells skipped, because their column doesn't accept the value pasted in them.</p>", changed_cells, skipped_cells));
                     }
                 }
             )),
@@ -2128,13 +2838,259 @@ impl PackedFileTableView {
                 let columns = unsafe { filter_model.as_mut().unwrap().column_count(()) };
                 if rows > 0 && columns > 0 {
                     let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };
-                    let first_item = unsafe { filter_model.as_mut().unwrap().index((0, 0)) };
-                    let last_item = unsafe { filter_model.as_mut().unwrap().index((rows - 1, columns - 1)) } ;
-                    let selection = ItemSelection::new((&first_item, &last_item));
+
+                    // Only invert within the selected columns if the selection is column-shaped.
+                    let indexes = unsafe { selection_model.as_mut().unwrap().selection().indexes() };
+                    let selected_cells = (0..indexes.count(())).map(|x| indexes.at(x)).filter(|x| x.is_valid()).map(|x| (x.row(), x.column())).collect::<Vec<(i32, i32)>>();
+                    let inverted_columns = get_inverted_selection_columns(&selected_cells, columns);
+
+                    // Toggle one range per block of consecutive columns, so this is fast even with huge tables.
+                    let mut selection = ItemSelection::new(());
+                    let mut first_column = inverted_columns[0];
+                    for (index, column) in inverted_columns.iter().enumerate() {
+                        if inverted_columns.get(index + 1) != Some(&(column + 1)) {
+                            let first_item = unsafe { filter_model.as_mut().unwrap().index((0, first_column)) };
+                            let last_item = unsafe { filter_model.as_mut().unwrap().index((rows - 1, *column)) };
+                            selection.select(&first_item, &last_item);
+                            if let Some(next_column) = inverted_columns.get(index + 1) { first_column = *next_column; }
+                        }
+                    }
                     unsafe { selection_model.as_mut().unwrap().select((&selection, Flags::from_enum(SelectionFlag::Toggle))); }
                 }
             }),
 
+            slot_context_menu_add_highlight_rule: SlotBool::new(clone!(
+                packed_file_path,
+                table_definition,
+                undo_lock,
+                app_ui => move |_| {
+                    if let Some(values) = create_text_input_dialog(&app_ui, "Add Highlight Rule", &["Condition, like 'tier > 3' or 'category == infantry'. Operators: ==, !=, >, <, contains.", "Color: red, green, blue, yellow, orange or purple."]) {
+                        match HighlightRule::new_from_text(&values[0], &values[1]) {
+                            Some(rule) => {
+                                if let Some(state) = TABLE_STATES_UI.lock().unwrap().get_mut(&*packed_file_path.borrow()) {
+                                    state.highlight_rules.push(rule.clone());
+                                }
+                                apply_highlight_rules(model, &table_definition, &[rule], &undo_lock);
+                            }
+                            None => show_dialog(app_ui.window, false, ErrorKind::InvalidHighlightRule),
+                        }
+                    }
+                }
+            )),
+
+            slot_context_menu_clear_highlight_rules: SlotBool::new(clone!(
+                packed_file_path,
+                app_ui => move |_| {
+                    if let Some(state) = TABLE_STATES_UI.lock().unwrap().get_mut(&*packed_file_path.borrow()) {
+                        state.highlight_rules.clear();
+                    }
+                    show_dialog(app_ui.window, true, "Highlight rules removed. The cells will stop being highlighted the next time you open this table.");
+                }
+            )),
+
+            slot_context_menu_vanilla_diff: SlotBool::new(clone!(
+                app_ui,
+                packed_file_path,
+                undo_lock,
+                vanilla_rows,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt,
+                table_definition => move |enable| {
+                    if enable {
+                        if let Err(error) = Self::load_vanilla_rows(&sender_qt, &sender_qt_data, &receiver_qt, &packed_file_path.borrow(), &table_definition, &vanilla_rows) {
+                            unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_checked(false); }
+                            return show_dialog(app_ui.window, false, error);
+                        }
+
+                        // If the Game Selected has changed, the table may no longer have a vanilla version.
+                        if vanilla_rows.borrow().as_ref().map_or(true, |(_, x)| x.is_empty()) {
+                            unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_checked(false); }
+                            unsafe { context_menu_vanilla_diff.as_mut().unwrap().set_enabled(false); }
+                            return show_dialog(app_ui.window, false, "This table has no vanilla version for the current Game Selected.");
+                        }
+
+                        paint_vanilla_diff(model, &get_row_identity_columns(&table_definition), table_definition.fields.len(), vanilla_rows.borrow().as_ref().map(|(_, x)| x), &undo_lock);
+                    }
+
+                    // When disabling it, just clean the tints.
+                    else {
+                        paint_vanilla_diff(model, &[], table_definition.fields.len(), None, &undo_lock);
+                    }
+                }
+            )),
+
+            slot_context_menu_revert_cell_to_vanilla: SlotBool::new(clone!(
+                app_ui,
+                packed_file_path,
+                table_state_data,
+                vanilla_rows,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt,
+                table_definition => move |_| {
+                    Self::revert_selection_to_vanilla(
+                        false,
+                        &app_ui,
+                        &sender_qt,
+                        &sender_qt_data,
+                        &receiver_qt,
+                        table_view,
+                        filter_model,
+                        model,
+                        undo_redo_enabler,
+                        &packed_file_path.borrow(),
+                        &table_definition,
+                        &table_state_data,
+                        &vanilla_rows,
+                    );
+                }
+            )),
+
+            slot_context_menu_revert_row_to_vanilla: SlotBool::new(clone!(
+                app_ui,
+                packed_file_path,
+                table_state_data,
+                vanilla_rows,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt,
+                table_definition => move |_| {
+                    Self::revert_selection_to_vanilla(
+                        true,
+                        &app_ui,
+                        &sender_qt,
+                        &sender_qt_data,
+                        &receiver_qt,
+                        table_view,
+                        filter_model,
+                        model,
+                        undo_redo_enabler,
+                        &packed_file_path.borrow(),
+                        &table_definition,
+                        &table_state_data,
+                        &vanilla_rows,
+                    );
+                }
+            )),
+
+            slot_context_menu_freeze_key_columns: SlotBool::new(clone!(
+                table_type,
+                table_definition,
+                actions_hide_show_column,
+                actions_freeze_unfreeze_column => move |freeze| {
+
+                    // If the table has no key columns, we use the first one.
+                    let mut columns = table_definition.fields.iter().enumerate().filter(|(_, x)| x.field_is_key).map(|(x, _)| x).collect::<Vec<usize>>();
+                    if columns.is_empty() && !table_definition.fields.is_empty() {
+                        columns.push(unsafe { table_view.as_ref().unwrap().horizontal_header().as_ref().unwrap().logical_index(0) } as usize);
+                    }
+
+                    // Freezing moves the column to the beginning of the table, so we freeze them from the last one to keep their order.
+                    // Hidden columns cannot be frozen, so we skip them.
+                    if freeze { columns.reverse(); }
+                    for column in &columns {
+                        let checkbox = actions_freeze_unfreeze_column.borrow()[*column];
+                        let is_hidden = unsafe { actions_hide_show_column.borrow()[*column].as_ref().unwrap().is_checked() };
+                        if !is_hidden && unsafe { checkbox.as_ref().unwrap().is_checked() } != freeze {
+                            unsafe { checkbox.as_mut().unwrap().set_checked(freeze); }
+                        }
+                    }
+
+                    // Remember it for the next time we open a table with the same name.
+                    let table_name = Self::get_table_name(&table_type.borrow());
+                    if freeze { FROZEN_KEY_COLUMNS.lock().unwrap().insert(table_name); }
+                    else { FROZEN_KEY_COLUMNS.lock().unwrap().remove(&table_name); }
+                }
+            )),
+
+            slot_save_column_layout: SlotNoArgs::new(clone!(
+                app_ui,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt,
+                table_type,
+                table_definition,
+                actions_hide_show_column => move || {
+                    if let Some(name) = create_text_input_dialog(&app_ui, "Save Column Layout", &["Name of the layout"]) {
+                        let name = name[0].trim().to_owned();
+                        if name.is_empty() { return show_dialog(app_ui.window, false, ErrorKind::EmptyInput); }
+
+                        // Layouts store the names of the hidden columns, so they keep working if the columns change.
+                        let hidden_columns = table_definition.fields.iter().enumerate()
+                            .filter(|(index, _)| unsafe { actions_hide_show_column.borrow()[*index].as_ref().unwrap().is_checked() })
+                            .map(|(_, field)| field.field_name.to_owned())
+                            .collect::<Vec<String>>();
+
+                        let mut settings = SETTINGS.lock().unwrap().clone();
+                        let already_exists = settings.get_table_column_layouts(&Self::get_table_name(&table_type.borrow())).contains(&name);
+                        settings.set_table_column_layout(&Self::get_table_name(&table_type.borrow()), &name, hidden_columns);
+                        if Self::save_settings(&sender_qt, &sender_qt_data, &receiver_qt, &app_ui, settings) && !already_exists {
+                            unsafe { column_layout_selector.as_mut().unwrap().add_item(&QString::from_std_str(&name)); }
+                            unsafe { column_layout_selector.as_mut().unwrap().set_current_text(&QString::from_std_str(&name)); }
+                        }
+                    }
+                }
+            )),
+
+            slot_load_column_layout: SlotNoArgs::new(clone!(
+                table_type,
+                table_definition,
+                actions_hide_show_column,
+                actions_freeze_unfreeze_column => move || {
+                    let name = unsafe { column_layout_selector.as_mut().unwrap().current_text().to_std_string() };
+                    let columns = table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
+                    let layout = SETTINGS.lock().unwrap().get_table_column_layout(&Self::get_table_name(&table_type.borrow()), &name, &columns);
+                    if let Some(layout) = layout {
+
+                        // Frozen columns cannot be hidden, so we leave them as they are.
+                        for (index, hidden) in layout.iter().enumerate() {
+                            let checkbox = actions_hide_show_column.borrow()[index];
+                            let is_frozen = unsafe { actions_freeze_unfreeze_column.borrow()[index].as_ref().unwrap().is_checked() };
+                            if !is_frozen && unsafe { checkbox.as_ref().unwrap().is_checked() } != *hidden {
+                                unsafe { checkbox.as_mut().unwrap().set_checked(*hidden); }
+                            }
+                        }
+                    }
+                }
+            )),
+
+            slot_delete_column_layout: SlotNoArgs::new(clone!(
+                app_ui,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt,
+                table_type => move || {
+                    let index = unsafe { column_layout_selector.as_mut().unwrap().current_index() };
+                    if index == -1 { return; }
+
+                    let name = unsafe { column_layout_selector.as_mut().unwrap().current_text().to_std_string() };
+                    let mut settings = SETTINGS.lock().unwrap().clone();
+                    settings.remove_table_column_layout(&Self::get_table_name(&table_type.borrow()), &name);
+                    if Self::save_settings(&sender_qt, &sender_qt_data, &receiver_qt, &app_ui, settings) {
+                        unsafe { column_layout_selector.as_mut().unwrap().remove_item(index); }
+                    }
+                }
+            )),
+
+            slot_context_menu_history: SlotBool::new(move |_| {
+                unsafe {
+                    if history_view.as_mut().unwrap().is_visible() { history_view.as_mut().unwrap().hide(); }
+                    else { history_view.as_mut().unwrap().show(); }
+                }
+            }),
+
+            // Undo or redo operations until we reach the clicked entry. Entry 0 is the table before any change.
+            slot_history_go_to: SlotModelIndexRef::new(clone!(
+                packed_file_path,
+                table_state_data => move |model_index| {
+                    let current = table_state_data.borrow().get(&*packed_file_path.borrow()).unwrap().undo_history.len() as i32;
+                    let target = model_index.row();
+                    for _ in target..current { unsafe { context_menu_undo.as_mut().unwrap().trigger(); }}
+                    for _ in current..target { unsafe { context_menu_redo.as_mut().unwrap().trigger(); }}
+                }
+            )),
+
             slot_context_menu_sidebar: SlotBool::new(move |_| {
                 unsafe {
                     if sidebar_scroll_area.as_mut().unwrap().is_visible() { sidebar_scroll_area.as_mut().unwrap().hide(); } 
@@ -2142,10 +3098,118 @@ impl PackedFileTableView {
                 }
             }),
 
+            slot_context_menu_column_filters: SlotBool::new(clone!(
+                packed_file_path,
+                table_definition,
+                column_filter_line_edits => move |_| {
+                    if unsafe { column_filters_widget.as_mut().unwrap().is_visible() } {
+
+                        // Clear all the filters at once, so we only filter the table one time.
+                        for line_edit in column_filter_line_edits.iter() {
+                            let mut blocker = unsafe { SignalBlocker::new(line_edit.as_mut().unwrap().static_cast_mut() as &mut Object) };
+                            unsafe { line_edit.as_mut().unwrap().clear(); }
+                            blocker.unblock();
+                        }
+                        unsafe { column_filters_widget.as_mut().unwrap().hide(); }
+                        unsafe { column_filters_timer.as_mut().unwrap().stop(); }
+
+                        Self::filter_table(
+                            None,
+                            None,
+                            None,
+                            model,
+                            filter_model,
+                            &table_definition,
+                            row_filter_line_edit,
+                            row_filter_column_selector,
+                            row_filter_case_sensitive_button,
+                            &column_filter_line_edits,
+                            update_search_stuff,
+                            &packed_file_path,
+                        ); 
+                    }
+                    else {
+                        unsafe { column_filters_widget.as_mut().unwrap().show(); }
+                        update_column_filters_geometry(table_view, &column_filter_line_edits);
+                    }
+                }
+            )),
+
+            slot_update_column_filters_geometry: SlotNoArgs::new(clone!(
+                column_filter_line_edits => move || {
+                    update_column_filters_geometry(table_view, &column_filter_line_edits);
+                }
+            )),
+
+            slot_update_row_filter_column_selector: SlotNoArgs::new(clone!(
+                table_definition => move || {
+                    Self::update_row_filter_column_selector(table_view, row_filter_column_selector, &table_definition);
+                }
+            )),
+
+            slot_context_menu_go_to_row: SlotBool::new(clone!(
+                app_ui,
+                packed_file_path,
+                table_definition,
+                column_filter_line_edits => move |_| {
+                    if let Some(text) = create_text_input_dialog(&app_ui, "Go to Row", &["Row number, or the value of a key column"]) {
+                        let visible_rows = unsafe { filter_model.as_mut().unwrap().row_count(()) as usize };
+                        let keys = get_model_rows(model, &get_key_columns(&table_definition));
+                        let model_index_filtered = match get_go_to_row_target(&text[0], visible_rows, &keys) {
+                            Some(GoToRow::Visible(row)) => unsafe { filter_model.as_mut().unwrap().index((row as i32, 0)) },
+
+                            // Rows found by key may be hidden by the filters. In that case, we need to clear them to show the row.
+                            Some(GoToRow::Source(row)) => {
+                                let model_index = unsafe { model.as_mut().unwrap().index((row as i32, 0)) };
+                                let model_index_filtered = unsafe { filter_model.as_mut().unwrap().map_from_source(&model_index) };
+                                if model_index_filtered.is_valid() { model_index_filtered }
+                                else if ask_question(&app_ui, "<p>The row with that key is hidden by the filters.</p><p>Do you want to clear them?</p>") {
+                                    let mut line_edits = vec![row_filter_line_edit];
+                                    line_edits.extend_from_slice(&column_filter_line_edits);
+                                    for line_edit in &line_edits {
+                                        let mut blocker = unsafe { SignalBlocker::new(line_edit.as_mut().unwrap().static_cast_mut() as &mut Object) };
+                                        unsafe { line_edit.as_mut().unwrap().clear(); }
+                                        blocker.unblock();
+                                    }
+
+                                    Self::filter_table(
+                                        None,
+                                        None,
+                                        None,
+                                        model,
+                                        filter_model,
+                                        &table_definition,
+                                        row_filter_line_edit,
+                                        row_filter_column_selector,
+                                        row_filter_case_sensitive_button,
+                                        &column_filter_line_edits,
+                                        update_search_stuff,
+                                        &packed_file_path,
+                                    );
+                                    unsafe { filter_model.as_mut().unwrap().map_from_source(&model_index) }
+                                }
+                                else { return }
+                            }
+                            None => return show_dialog(app_ui.window, false, "There is no row with that number or key in this table."),
+                        };
+
+                        // Select the entire row and put it in the center of the view.
+                        let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };
+                        unsafe { selection_model.as_mut().unwrap().select((&model_index_filtered, Flags::from_enum(SelectionFlag::ClearAndSelect) | Flags::from_enum(SelectionFlag::Rows))); }
+                        unsafe { selection_model.as_mut().unwrap().set_current_index(&model_index_filtered, Flags::from_enum(SelectionFlag::NoUpdate)); }
+                        unsafe { table_view.as_mut().unwrap().scroll_to((&model_index_filtered, ScrollHint::PositionAtCenter)); }
+                    }
+                }
+            )),
+
             slot_context_menu_search: SlotBool::new(move |_| {
                 unsafe {
                     if search_widget.as_mut().unwrap().is_visible() { search_widget.as_mut().unwrap().hide(); } 
-                    else { search_widget.as_mut().unwrap().show(); }
+                    else {
+                        search_widget.as_mut().unwrap().show();
+                        search_line_edit.as_mut().unwrap().set_focus(());
+                        search_line_edit.as_mut().unwrap().select_all();
+                    }
                 }
             }),
 
@@ -2169,7 +3233,12 @@ impl PackedFileTableView {
                         &QString::from_std_str("Select TSV File to Import..."),
                     )) };
 
-                    file_dialog.set_name_filter(&QString::from_std_str("TSV Files (*.tsv)"));
+                    // Loc PackedFiles can also be imported from JSON files, as that's what most web tools use for translations.
+                    if let TableType::LOC(_) = &*table_type.borrow() {
+                        file_dialog.set_name_filter(&QString::from_std_str("TSV Files (*.tsv);;CSV Files (*.csv);;JSON Files (*.json)"));
+                    } else {
+                        file_dialog.set_name_filter(&QString::from_std_str("TSV Files (*.tsv);;CSV Files (*.csv)"));
+                    }
 
                     // Run it and, if we receive 1 (Accept), try to import the TSV file.
                     if file_dialog.exec() == 1 {
@@ -2180,21 +3249,56 @@ impl PackedFileTableView {
                             TableType::LOC(data) => (TSV_HEADER_LOC_PACKEDFILE.to_owned(), 1, data.entries.to_vec()),
                         };
 
-                        sender_qt.send(Commands::ImportTSVPackedFile).unwrap();
-                        sender_qt_data.send(Data::TableDefinitionPathBufStringI32(((*table_definition).clone(), path, name, version))).unwrap();
+                        // CSV files can use any delimiter and quoting, so we need to ask for them first.
+                        if path.extension().map_or(false, |x| x == "csv") {
+                            let (delimiter, quoting) = match create_delimited_options_dialog(&app_ui, "CSV Import Options") {
+                                Some(Ok(options)) => options,
+                                Some(Err(error)) => return show_dialog(app_ui.window, false, error),
+                                None => return,
+                            };
+                            sender_qt.send(Commands::ImportCSVPackedFile).unwrap();
+                            sender_qt_data.send(Data::TableDefinitionPathBufU8DelimitedQuoting(((*table_definition).clone(), path.to_path_buf(), delimiter, quoting))).unwrap();
+                        } else if name == TSV_HEADER_LOC_PACKEDFILE && path.extension().map_or(false, |x| x == "json") {
+                            sender_qt.send(Commands::ImportJSONLocPackedFile).unwrap();
+                            sender_qt_data.send(Data::TableDefinitionPathBuf(((*table_definition).clone(), path.to_path_buf()))).unwrap();
+                        } else {
+                            sender_qt.send(Commands::ImportTSVPackedFile).unwrap();
+                            sender_qt_data.send(Data::TableDefinitionPathBufStringI32(((*table_definition).clone(), path.to_path_buf(), name.to_owned(), version))).unwrap();
+                        }
 
-                        match check_message_validity_recv2(&receiver_qt) {
-                            Data::VecVecDecodedData(new_data) => {
+                        // If the TSV is for another version of the DB Table, offer to convert it to the version of the table before importing it.
+                        let mut response = check_message_validity_recv2(&receiver_qt);
+                        let is_other_version = match (&response, &*table_type.borrow()) {
+                            (Data::Error(error), TableType::DB(_)) => error.kind() == ErrorKind::ImportTSVWrongVersion,
+                            _ => false,
+                        };
+
+                        if is_other_version {
+                            if !ask_question(&app_ui, &format!("This TSV file is for another version of this table. Do you want to convert it to the version of this table ({}) and import it? Columns that are not in this version will be lost.", version)) { return }
+                            sender_qt.send(Commands::ImportTSVPackedFileFromOtherVersion).unwrap();
+                            sender_qt_data.send(Data::TableDefinitionPathBufStringI32(((*table_definition).clone(), path, name, version))).unwrap();
+                            response = check_message_validity_recv2(&receiver_qt);
+                        }
+
+                        let old_rows = old_data.len();
+                        let new_rows;
+                        let unknown_columns = match response {
+                            Data::VecVecDecodedDataVecStringVecTSVError((new_data, unknown_columns, errors)) => {
+
+                                // If any row has errors, we ask the user if he wants to import the file anyway, without them.
+                                if !errors.is_empty() && !ask_import_tsv_anyway(&app_ui, &errors.iter().map(|x| x.to_string()).collect::<Vec<String>>()) { return }
                                 match &mut *table_type.borrow_mut() {
                                     TableType::DependencyManager(data) => *data = new_data.to_vec(),
                                     TableType::DB(data) => data.entries = new_data.to_vec(),
                                     TableType::LOC(data) => data.entries = new_data.to_vec(),
                                 };
-                                Self::load_data_to_table_view(table_view, model, &table_type.borrow(), &table_definition, &dependency_data)
+                                Self::load_data_to_table_view(table_view, model, &table_type.borrow(), &table_definition, &dependency_data);
+                                new_rows = new_data.len();
+                                unknown_columns
                             },
                             Data::Error(error) => return show_dialog(app_ui.window, false, error),
                             _ => panic!(THREADS_MESSAGE_ERROR),
-                        }
+                        };
 
                         // Build the Column's "Data".
                         Self::build_columns(table_view, table_view_frozen, model, &table_definition, enable_header_popups.clone());
@@ -2227,44 +3331,129 @@ impl PackedFileTableView {
                             table_state_data.redo_history.clear();
                             update_undo_model(model, table_state_data.undo_model); 
                         }
-                        unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
+                        unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
+
+                        // Let the user know how the table changed and, if the TSV had columns we don't know about, that they have been ignored.
+                        let mut message = format!("<p>TSV imported. The table had {} rows, and now it has {}.</p>", old_rows, new_rows);
+                        if !unknown_columns.is_empty() {
+                            let unknown_columns = unknown_columns.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
+                            message.push_str(&format!("<p>The following columns are not in the table's definition and have been ignored:</p><ul>{}</ul>", unknown_columns));
+                        }
+                        show_dialog(app_ui.window, true, message);
+                    }
+                }
+            )),
+            slot_context_menu_export: SlotBool::new(clone!(
+                table_definition,
+                table_type,
+                app_ui,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt => move |_| {
+
+                    // Create a File Chooser to get the destination path and configure it.
+                    let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                        app_ui.window as *mut Widget,
+                        &QString::from_std_str("Export TSV File..."),
+                    )) };
+
+                    file_dialog.set_accept_mode(qt_widgets::file_dialog::AcceptMode::Save);
+                    file_dialog.set_confirm_overwrite(true);
+                    file_dialog.set_name_filter(&QString::from_std_str("TSV Files (*.tsv)"));
+                    file_dialog.set_default_suffix(&QString::from_std_str("tsv"));
+
+                    // Run it and, if we receive 1 (Accept), export the DB Table.
+                    if file_dialog.exec() == 1 {
+
+                        let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+                        let headers = table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
+                        let (name, version, entries) = match &*table_type.borrow() {
+                            TableType::DependencyManager(data) => (TSV_HEADER_PACKFILE_LIST.to_owned(), 1, data.to_vec()),
+                            TableType::DB(data) => (data.db_type.to_owned(), data.version, data.entries.to_vec()),
+                            TableType::LOC(data) => (TSV_HEADER_LOC_PACKEDFILE.to_owned(), 1, data.entries.to_vec()),
+                        };
+
+                        sender_qt.send(Commands::ExportTSVPackedFile).unwrap();
+                        sender_qt_data.send(Data::VecVecDecodedDataPathBufVecStringTupleStrI32((entries.to_vec(), path, headers, (name, version)))).unwrap();
+
+                        // If there is an error, report it. Otherwise, we're done.
+                        match check_message_validity_recv2(&receiver_qt) {
+                            Data::Success => return,
+                            Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
+                    }
+                }
+            )),
+            slot_context_menu_export_json: SlotBool::new(clone!(
+                table_definition,
+                table_type,
+                app_ui,
+                sender_qt,
+                sender_qt_data,
+                receiver_qt => move |_| {
+                    let mut file_dialog = unsafe { FileDialog::new_unsafe((
+                        app_ui.window as *mut Widget,
+                        &QString::from_std_str("Export JSON File..."),
+                    )) };
+
+                    file_dialog.set_accept_mode(qt_widgets::file_dialog::AcceptMode::Save);
+                    file_dialog.set_confirm_overwrite(true);
+                    file_dialog.set_name_filter(&QString::from_std_str("JSON Files (*.json)"));
+                    file_dialog.set_default_suffix(&QString::from_std_str("json"));
+
+                    if file_dialog.exec() == 1 {
+                        let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+                        let (name, version, entries) = match &*table_type.borrow() {
+                            TableType::DependencyManager(data) => (TSV_HEADER_PACKFILE_LIST.to_owned(), 1, data.to_vec()),
+                            TableType::DB(data) => (data.db_type.to_owned(), data.version, data.entries.to_vec()),
+                            TableType::LOC(data) => (TSV_HEADER_LOC_PACKEDFILE.to_owned(), 1, data.entries.to_vec()),
+                        };
+
+                        sender_qt.send(Commands::ExportJSONPackedFile).unwrap();
+                        sender_qt_data.send(Data::VecVecDecodedDataPathBufTableDefinitionTupleStrI32((entries, path, (*table_definition).clone(), (name, version)))).unwrap();
+                        match check_message_validity_recv2(&receiver_qt) {
+                            Data::Success => return,
+                            Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                            _ => panic!(THREADS_MESSAGE_ERROR),
+                        }
                     }
                 }
             )),
-            slot_context_menu_export: SlotBool::new(clone!(
+            slot_context_menu_export_csv: SlotBool::new(clone!(
                 table_definition,
                 table_type,
                 app_ui,
                 sender_qt,
                 sender_qt_data,
                 receiver_qt => move |_| {
+                    let (delimiter, quoting) = match create_delimited_options_dialog(&app_ui, "CSV Export Options") {
+                        Some(Ok(options)) => options,
+                        Some(Err(error)) => return show_dialog(app_ui.window, false, error),
+                        None => return,
+                    };
 
-                    // Create a File Chooser to get the destination path and configure it.
                     let mut file_dialog = unsafe { FileDialog::new_unsafe((
                         app_ui.window as *mut Widget,
-                        &QString::from_std_str("Export TSV File..."),
+                        &QString::from_std_str("Export CSV File..."),
                     )) };
 
                     file_dialog.set_accept_mode(qt_widgets::file_dialog::AcceptMode::Save);
                     file_dialog.set_confirm_overwrite(true);
-                    file_dialog.set_name_filter(&QString::from_std_str("TSV Files (*.tsv)"));
-                    file_dialog.set_default_suffix(&QString::from_std_str("tsv"));
+                    file_dialog.set_name_filter(&QString::from_std_str("CSV Files (*.csv)"));
+                    file_dialog.set_default_suffix(&QString::from_std_str("csv"));
 
-                    // Run it and, if we receive 1 (Accept), export the DB Table.
                     if file_dialog.exec() == 1 {
-
                         let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
                         let headers = table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
-                        let (name, version, entries) = match &*table_type.borrow() {
-                            TableType::DependencyManager(data) => (TSV_HEADER_PACKFILE_LIST.to_owned(), 1, data.to_vec()),
-                            TableType::DB(data) => (data.db_type.to_owned(), data.version, data.entries.to_vec()),
-                            TableType::LOC(data) => (TSV_HEADER_LOC_PACKEDFILE.to_owned(), 1, data.entries.to_vec()),
+                        let entries = match &*table_type.borrow() {
+                            TableType::DependencyManager(data) => data.to_vec(),
+                            TableType::DB(data) => data.entries.to_vec(),
+                            TableType::LOC(data) => data.entries.to_vec(),
                         };
 
-                        sender_qt.send(Commands::ExportTSVPackedFile).unwrap();
-                        sender_qt_data.send(Data::VecVecDecodedDataPathBufVecStringTupleStrI32((entries.to_vec(), path, headers, (name, version)))).unwrap();
-
-                        // If there is an error, report it. Otherwise, we're done.
+                        sender_qt.send(Commands::ExportCSVPackedFile).unwrap();
+                        sender_qt_data.send(Data::VecVecDecodedDataPathBufVecStringU8DelimitedQuoting((entries, path, headers, delimiter, quoting))).unwrap();
                         match check_message_validity_recv2(&receiver_qt) {
                             Data::Success => return,
                             Data::Error(error) => return show_dialog(app_ui.window, false, error),
@@ -2274,8 +3463,71 @@ impl PackedFileTableView {
                 }
             )),
 
+            slot_context_menu_reload: SlotBool::new(clone!(
+                global_search_explicit_paths,
+                dependency_data,
+                enable_header_popups,
+                packed_file_path,
+                app_ui,
+                table_definition,
+                table_state_data,
+                table_type,
+                receiver_qt,
+                sender_qt,
+                sender_qt_data => move |_| {
+
+                    // If the table has changes, make sure the user really wants to lose them.
+                    let has_changes = !table_state_data.borrow().get(&*packed_file_path.borrow()).unwrap().undo_history.is_empty();
+                    if has_changes && !ask_question(&app_ui, "This table has changes that will be lost. Are you sure you want to reload it from the PackFile?") { return }
+
+                    // Restore the PackedFile from the PackFile on disk, then get it again with the same command we used to open it.
+                    sender_qt.send(Commands::ReloadPackedFileFromDisk).unwrap();
+                    sender_qt_data.send(Data::VecString(packed_file_path.borrow().to_vec())).unwrap();
+                    // In "Dry Run" mode nothing is reloaded, so we keep the view and his history as they are.
+                    match check_message_validity_recv2(&receiver_qt) {
+                        Data::Success => {},
+                        Data::String(report) => return show_dialog(app_ui.window, true, report),
+                        Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+
+                    let command = match *table_type.borrow() {
+                        TableType::DB(_) => Commands::DecodePackedFileDB,
+                        TableType::LOC(_) => Commands::DecodePackedFileLoc,
+                        TableType::DependencyManager(_) => return,
+                    };
+                    sender_qt.send(command).unwrap();
+                    sender_qt_data.send(Data::VecString(packed_file_path.borrow().to_vec())).unwrap();
+                    match check_message_validity_recv2(&receiver_qt) {
+                        Data::DB(data) => *table_type.borrow_mut() = TableType::DB(data),
+                        Data::Loc(data) => *table_type.borrow_mut() = TableType::LOC(data),
+                        Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+
+                    Self::load_data_to_table_view(table_view, model, &table_type.borrow(), &table_definition, &dependency_data);
+                    Self::build_columns(table_view, table_view_frozen, model, &table_definition, enable_header_popups.clone());
+
+                    // Update the search stuff, if needed.
+                    unsafe { update_search_stuff.as_mut().unwrap().trigger(); }
+                    global_search_explicit_paths.borrow_mut().push(packed_file_path.borrow().to_vec());
+                    unsafe { update_global_search_stuff.as_mut().unwrap().trigger(); }
+
+                    // The reloaded table has no changes, so forget about the old ones. This also removes the "modified" mark of the PackedFile.
+                    {
+                        let mut table_state_data = table_state_data.borrow_mut();
+                        let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
+                        table_state_data.undo_history.clear();
+                        table_state_data.redo_history.clear();
+                        update_undo_model(model, table_state_data.undo_model); 
+                    }
+                    unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
+                }
+            )),
+
             slot_smart_delete: SlotBool::new(clone!(
                 global_search_explicit_paths,
+                save_lock,
                 app_ui,
                 table_definition,
                 packed_file_path,
@@ -2306,57 +3558,27 @@ impl PackedFileTableView {
                         }
                     }
 
-                    // First, we do all the edits needed.
+                    // First, we clear the cells of the rows that are not fully selected, setting them to the default value of their field.
+                    // Cells that already have it are skipped, as setting them doesn't trigger an edit.
+                    // The saving is locked meanwhile, as saving on each edit freezes the UI with big selections. We save once at the end.
+                    let default_row = get_default_row(&table_definition);
                     let mut edits = vec![];
-                    for (key, values) in cells.iter() {
-                        if values.len() < unsafe { model.as_ref().unwrap().column_count(()) as usize } {
-                            for column in values {
-                                let item = unsafe { model.as_mut().unwrap().item((*key, *column)) };
-                                match table_definition.fields[*column as usize].field_type {
-                                    FieldType::Boolean => {
-                                        let current_value = unsafe { item.as_mut().unwrap().check_state() };
-                                        if current_value != CheckState::Unchecked { 
-                                            unsafe { edits.push(((*key, *column), (&*item).clone())); }
-                                            unsafe { item.as_mut().unwrap().set_check_state(CheckState::Unchecked); }
-                                        }
-                                    }
-
-                                    FieldType::Float => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if !current_value.is_empty() {
-                                            unsafe { edits.push(((*key, *column), (&*item).clone())); }
-                                            unsafe { item.as_mut().unwrap().set_data((&Variant::new2(0.0f32), 2)); }
-                                        }
-                                    }
-
-                                    FieldType::Integer => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if !current_value.is_empty() {
-                                            unsafe { edits.push(((*key, *column), (&*item).clone())); }
-                                            unsafe { item.as_mut().unwrap().set_data((&Variant::new0(0i32), 2)); }
-                                        }
-                                    }
-
-                                    FieldType::LongInteger => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if !current_value.is_empty() {
-                                            unsafe { edits.push(((*key, *column), (&*item).clone())); }
-                                            unsafe { item.as_mut().unwrap().set_data((&Variant::new2(0i64), 2)); }
-                                        }
-                                    }
-
-                                    _ => {
-                                        let current_value = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                        if !current_value.is_empty() {
-                                            unsafe { edits.push(((*key, *column), (&*item).clone())); }
-                                            unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str("")); }
-                                        }
-                                    }
+                    *save_lock.borrow_mut() = true;
+                    for (row, columns) in cells.iter() {
+                        if columns.len() < unsafe { model.as_ref().unwrap().column_count(()) as usize } {
+                            for column in columns {
+                                let item = unsafe { model.as_mut().unwrap().item((*row, *column)) };
+                                let default_value = &default_row[*column as usize];
+                                if !item_has_value(item, default_value) {
+                                    unsafe { edits.push(((*row, *column), (&*item).clone())); }
+                                    set_item_value(item, default_value);
                                 }
                             }
                         }
                     }
 
+                    *save_lock.borrow_mut() = false;
+
                     // Then, we delete all the fully selected rows. This time in reverse.
                     let mut removed_rows_splitted = vec![];
                     let mut current_row_pack = vec![];
@@ -2391,7 +3613,7 @@ impl PackedFileTableView {
                     }
 
                     // When you delete a row, the save has to be triggered manually. For cell edits it get's triggered automatically.
-                    if !cells.is_empty() {
+                    if !edits.is_empty() || !removed_rows_splitted.is_empty() {
                         Self::save_to_packed_file(
                             &sender_qt,
                             &sender_qt_data,
@@ -2574,38 +3796,38 @@ impl PackedFileTableView {
                     let case_sensitive = unsafe { case_sensitive_button.as_mut().unwrap().is_checked() };
                     if case_sensitive { flags = flags | Flags::from_enum(MatchFlag::CaseSensitive); }
                     
-                    // Get the column selected, and act depending on it.
+                    // Get the columns to search in. The current one is the one of the cell selected, if any.
                     let column = unsafe { column_selector.as_mut().unwrap().current_text().to_std_string().replace(' ', "_").to_lowercase() };
-                    match &*column {
-                        "*_(all_columns)" => {
-                            for index in 0..table_definition.fields.len() {
-                                
-                                // Get all the matches from all the columns. Once you got them, process them and get their ModelIndex.
-                                let matches_unprocessed = unsafe { model.as_mut().unwrap().find_items((&text, flags.clone(), index as i32)) };
-                                for index in 0..matches_unprocessed.count() {
-                                    let model_index = unsafe { matches_unprocessed.at(index).as_mut().unwrap().index() };
-                                    let filter_model_index = unsafe { filter_model.as_mut().unwrap().map_from_source(&model_index) };
-                                    matches.borrow_mut().insert(
-                                        ModelIndexWrapped::new(model_index),
-                                        if filter_model_index.is_valid() { Some(ModelIndexWrapped::new(filter_model_index)) } else { None }
-                                    );
-                                }
-                            }
-                        },
+                    let columns = match &*column {
+                        "*_(all_columns)" => (0..table_definition.fields.len() as i32).collect::<Vec<i32>>(),
+                        "*_(current_column)" => {
+                            let current_index = unsafe { table_view.as_mut().unwrap().current_index() };
+                            if current_index.is_valid() { vec![current_index.column()] } else { vec![] }
+                        }
+                        _ => vec![table_definition.fields.iter().position(|x| x.field_name == column).unwrap() as i32],
+                    };
 
-                        _ => {
-                            let column = table_definition.fields.iter().position(|x| x.field_name == column).unwrap();
+                    // If we're searching in the current column but there is no cell selected, there is nowhere to search.
+                    if columns.is_empty() {
+                        *search_data.borrow_mut() = (String::new(), flags, -1);
+                        unsafe { matches_label.as_mut().unwrap().set_text(&QString::from_std_str("No cell selected.")); }
+                        unsafe { prev_match_button.as_mut().unwrap().set_enabled(false); }
+                        unsafe { next_match_button.as_mut().unwrap().set_enabled(false); }
+                        unsafe { replace_current_button.as_mut().unwrap().set_enabled(false); }
+                        unsafe { replace_all_button.as_mut().unwrap().set_enabled(false); }
+                        return
+                    }
 
-                            // Once you got them, process them and get their ModelIndex.
-                            let matches_unprocessed = unsafe { model.as_mut().unwrap().find_items((&text, flags.clone(), column as i32)) };
-                            for index in 0..matches_unprocessed.count() {
-                                let model_index = unsafe { matches_unprocessed.at(index).as_mut().unwrap().index() };
-                                let filter_model_index = unsafe { filter_model.as_mut().unwrap().map_from_source(&model_index) };
-                                matches.borrow_mut().insert(
-                                    ModelIndexWrapped::new(model_index),
-                                    if filter_model_index.is_valid() { Some(ModelIndexWrapped::new(filter_model_index)) } else { None }
-                                );
-                            }
+                    // Get all the matches from all the columns. Once you got them, process them and get their ModelIndex.
+                    for column in &columns {
+                        let matches_unprocessed = unsafe { model.as_mut().unwrap().find_items((&text, flags.clone(), *column)) };
+                        for index in 0..matches_unprocessed.count() {
+                            let model_index = unsafe { matches_unprocessed.at(index).as_mut().unwrap().index() };
+                            let filter_model_index = unsafe { filter_model.as_mut().unwrap().map_from_source(&model_index) };
+                            matches.borrow_mut().insert(
+                                ModelIndexWrapped::new(model_index),
+                                if filter_model_index.is_valid() { Some(ModelIndexWrapped::new(filter_model_index)) } else { None }
+                            );
                         }
                     }
 
@@ -2646,16 +3868,15 @@ impl PackedFileTableView {
                             unsafe { replace_current_button.as_mut().unwrap().set_enabled(true); }
                             unsafe { replace_all_button.as_mut().unwrap().set_enabled(true); }
 
-                            let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };
-                            unsafe { selection_model.as_mut().unwrap().select((
-                                matches.borrow().iter().find(|x| x.1.is_some()).map(|x| x.1.as_ref().unwrap().get()).unwrap(),
-                                Flags::from_enum(SelectionFlag::ClearAndSelect)
-                            )); }
+                            // Highlight all the matches, starting by the first one.
+                            let matches = matches.borrow();
+                            let matches_in_filter = matches.iter().filter(|x| x.1.is_some()).map(|x| x.1.as_ref().unwrap().get()).collect::<Vec<&ModelIndex>>();
+                            select_search_matches(table_view, &matches_in_filter, 0);
                         }
                     }
 
                     // Add the new search data to the state history.
-                    *search_data.borrow_mut() = (text.to_std_string(), flags, table_definition.fields.iter().position(|x| x.field_name == column).map(|x| x as i32).unwrap_or(-1));
+                    *search_data.borrow_mut() = (text.to_std_string(), flags, if columns.len() == 1 { columns[0] } else { -1 });
                     if let Some(state) = TABLE_STATES_UI.lock().unwrap().get_mut(&*packed_file_path.borrow()) {
                         unsafe { state.search_state = SearchState::new(search_line_edit.as_mut().unwrap().text().to_std_string(), replace_line_edit.as_mut().unwrap().text().to_std_string(), column_selector.as_ref().unwrap().current_index(), case_sensitive_button.as_mut().unwrap().is_checked()); }
                     }
@@ -2680,12 +3901,8 @@ impl PackedFileTableView {
                             if *pos >= matches_in_filter.len() - 1 { unsafe { next_match_button.as_mut().unwrap().set_enabled(false); }}
                             else { unsafe { next_match_button.as_mut().unwrap().set_enabled(true); }}
 
-                            // Select the new cell.
-                            let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };
-                            unsafe { selection_model.as_mut().unwrap().select((
-                                matches_in_filter[*pos],
-                                Flags::from_enum(SelectionFlag::ClearAndSelect)
-                            )); }
+                            // Make the new match the current one.
+                            select_search_matches(table_view, &matches_in_filter, *pos);
 
                             unsafe { matches_label.as_mut().unwrap().set_text(&QString::from_std_str(&format!("{} of {} with current filter ({} in total)", *pos + 1, matches_in_filter.len(), matches.len()))); }
                         }
@@ -2710,12 +3927,8 @@ impl PackedFileTableView {
                             if *pos >= matches_in_filter.len() - 1 { unsafe { next_match_button.as_mut().unwrap().set_enabled(false); }}
                             else { unsafe { next_match_button.as_mut().unwrap().set_enabled(true); }}
 
-                            // Select the new cell.
-                            let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };                            
-                            unsafe { selection_model.as_mut().unwrap().select((
-                                matches_in_filter[*pos],
-                                Flags::from_enum(SelectionFlag::ClearAndSelect)
-                            )); }
+                            // Make the new match the current one.
+                            select_search_matches(table_view, &matches_in_filter, *pos);
 
                             unsafe { matches_label.as_mut().unwrap().set_text(&QString::from_std_str(&format!("{} of {} with current filter ({} in total)", *pos + 1, matches_in_filter.len(), matches.len()))); }
                         }
@@ -2758,14 +3971,17 @@ impl PackedFileTableView {
                             if model_index.is_valid() {
                                 unsafe { item = model.as_mut().unwrap().item_from_index(model_index); }
                                 let text = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                replaced_text = text.replace(&text_source, &text_replace);
+                                let case_sensitive = unsafe { case_sensitive_button.as_mut().unwrap().is_checked() };
+                                replaced_text = replace_search_text(&text, &text_source, &text_replace, case_sensitive);
 
                                 // We need to do an extra check to ensure the new text can be in the field. Return in bools, as we don't support those columns.
-                                match table_definition.fields[model_index.column() as usize].field_type {
+                                let field_type = table_definition.fields[model_index.column() as usize].field_type;
+                                match field_type {
                                     FieldType::Boolean => return,
                                     FieldType::Float => if replaced_text.parse::<f32>().is_err() { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
                                     FieldType::Integer => if replaced_text.parse::<i32>().is_err() { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
                                     FieldType::LongInteger => if replaced_text.parse::<i64>().is_err() { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
+                                    FieldType::Double | FieldType::Colour => if !is_valid_for_field_type(&replaced_text, field_type) { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
                                     _ =>  {}
                                 }
                             } else { return }
@@ -2775,6 +3991,7 @@ impl PackedFileTableView {
                             FieldType::Float => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(replaced_text.parse::<f32>().unwrap()), 2)); }
                             FieldType::Integer => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(replaced_text.parse::<i32>().unwrap()), 2)); }
                             FieldType::LongInteger => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(replaced_text.parse::<i64>().unwrap()), 2)); }
+                            FieldType::Double => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(replaced_text.parse::<f64>().unwrap()), 2)); }
                             _ => unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str(&replaced_text)); }
                         }
 
@@ -2782,18 +3999,13 @@ impl PackedFileTableView {
                         if let Some(pos) = *position.borrow() {
                             let matches = matches.borrow();
                             let matches_in_filter = matches.iter().filter(|x| x.1.is_some()).map(|x| x.1.as_ref().unwrap().get()).collect::<Vec<&ModelIndex>>();
-                           
-                            let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };
-                            unsafe { selection_model.as_mut().unwrap().select((
-                                matches_in_filter[pos],
-                                Flags::from_enum(SelectionFlag::ClearAndSelect)
-                            )); }
+                            select_search_matches(table_view, &matches_in_filter, pos);
                         }
                     }
                 }
             )),
 
-            // Slot for the "Replace All" button. This triggers the main save function, so we can let that one update the search stuff.
+            // Slot for the "Replace All" button. All the replaced cells are a single undo step.
             slot_replace_all: SlotNoArgs::new(clone!(
                 table_definition,
                 packed_file_path,
@@ -2804,79 +4016,62 @@ impl PackedFileTableView {
                     // Get the texts and only proceed if the source is not empty.
                     let text_source = unsafe { search_line_edit.as_mut().unwrap().text().to_std_string() };
                     let text_replace = unsafe { replace_line_edit.as_mut().unwrap().text().to_std_string() };
-                    if text_source == text_replace { return }
-                    if !text_source.is_empty() {
-
-                        // This is done like that because problems with borrowing matches and position. We cannot set the new text while
-                        // matches is borrowed, so we have to catch that into his own scope.
-                        let mut positions_and_texts: Vec<((i32, i32), String)> = vec![];
-                        { 
-                            // Get the list of all valid ModelIndex for the current filter and the current position.
-                            let matches = matches.borrow();
-                            let matches_original_from_filter = matches.iter().filter(|x| x.1.is_some()).map(|x| x.0.get()).collect::<Vec<&ModelIndex>>();
-                            for model_index in &matches_original_from_filter {
-                             
-                                // If the position is still valid (not required, but just in case)...
-                                if model_index.is_valid() {
-
-                                    // We need to do an extra check to ensure every new field is valid. If one fails, return.
-                                    let text = unsafe { model.as_ref().unwrap().item_from_index(model_index).as_mut().unwrap().text().to_std_string() };
-                                    let replaced_text = text.replace(&text_source, &text_replace);
-                                    match table_definition.fields[model_index.column() as usize].field_type {
-                                        FieldType::Boolean => return,
-                                        FieldType::Float => if replaced_text.parse::<f32>().is_err() { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
-                                        FieldType::Integer => if replaced_text.parse::<i32>().is_err() { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
-                                        FieldType::LongInteger => if replaced_text.parse::<i64>().is_err() { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
-                                        _ =>  {}
-                                    }
-                                } else { return }
-                            }
-                            let regex = Regex::new(&format!("(?i){}", text_source)).unwrap();
-                            for model_index in &matches_original_from_filter {
-                             
-                                // If the position is still valid (not required, but just in case)...
-                                if model_index.is_valid() {
-                                    let item = unsafe { model.as_mut().unwrap().item_from_index(model_index) };
-                                    let text = unsafe { item.as_mut().unwrap().text().to_std_string() };
-                                    positions_and_texts.push(((model_index.row(), model_index.column()), regex.replace_all(&text, &*text_replace).to_string()));
-                                } else { return }
-                            }
+                    let case_sensitive = unsafe { case_sensitive_button.as_mut().unwrap().is_checked() };
+                    if text_source == text_replace || text_source.is_empty() { return }
+
+                    // This is done like that because problems with borrowing matches and position. We cannot set the new text while
+                    // matches is borrowed, so we have to catch that into his own scope. We also check every new value is valid before
+                    // changing anything. Booleans are not supported, so we skip them.
+                    let mut positions_and_texts: Vec<((i32, i32), String)> = vec![];
+                    { 
+                        let matches = matches.borrow();
+                        for model_index in matches.iter().filter(|x| x.1.is_some()).map(|x| x.0.get()) {
+                            if !model_index.is_valid() { continue; }
+
+                            let field_type = table_definition.fields[model_index.column() as usize].field_type;
+                            if let FieldType::Boolean = field_type { continue; }
+
+                            let text = unsafe { model.as_ref().unwrap().item_from_index(model_index).as_mut().unwrap().text().to_std_string() };
+                            let replaced_text = replace_search_text(&text, &text_source, &text_replace, case_sensitive);
+                            if replaced_text == text { continue; }
+                            if !is_valid_for_field_type(&replaced_text, field_type) { return show_dialog(app_ui.window, false, ErrorKind::DBTableReplaceInvalidData) }
+                            positions_and_texts.push(((model_index.row(), model_index.column()), replaced_text));
                         }
+                    }
 
-                        // For each position, get his item and change his text.
-                        for (index, data) in positions_and_texts.iter().enumerate() {
-                            let item = unsafe { model.as_mut().unwrap().item(((data.0).0, (data.0).1)) };
-
-                            match table_definition.fields[unsafe { item.as_mut().unwrap().column() as usize }].field_type {
-                                FieldType::Float => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(data.1.parse::<f32>().unwrap()), 2)); }
-                                FieldType::Integer => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(data.1.parse::<i32>().unwrap()), 2)); }
-                                FieldType::LongInteger => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(data.1.parse::<i64>().unwrap()), 2)); }
-                                _ => unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str(&data.1)); }
-                            }
-
-                            // If we finished replacing, fix the undo history to have all the previous changed merged into one.
-                            if index == positions_and_texts.len() - 1 {
-
-                                {
-                                    let mut table_state_data = table_state_data.borrow_mut();
-                                    let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
-                                    let len = table_state_data.undo_history.len();
-                                    let mut edits_data = vec![];
-                                    
-                                    {
-                                        let mut edits = table_state_data.undo_history.drain((len - positions_and_texts.len())..);
-                                        for edit in &mut edits { if let TableOperations::Editing(mut edit) = edit { edits_data.append(&mut edit); }}
-                                    }
+                    if positions_and_texts.is_empty() { return show_dialog(app_ui.window, false, "No cells have been changed."); }
 
-                                    table_state_data.undo_history.push(TableOperations::Editing(edits_data));
-                                    table_state_data.redo_history.clear();
-                                    update_undo_model(model, table_state_data.undo_model); 
-                                }
+                    // For each position, get his item and change his text.
+                    for data in &positions_and_texts {
+                        let item = unsafe { model.as_mut().unwrap().item(((data.0).0, (data.0).1)) };
+                        match table_definition.fields[unsafe { item.as_mut().unwrap().column() as usize }].field_type {
+                            FieldType::Float => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(data.1.parse::<f32>().unwrap()), 2)); }
+                            FieldType::Integer => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(data.1.parse::<i32>().unwrap()), 2)); }
+                            FieldType::LongInteger => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(data.1.parse::<i64>().unwrap()), 2)); }
+                            FieldType::Double => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(data.1.parse::<f64>().unwrap()), 2)); }
+                            _ => unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str(&data.1)); }
+                        }
+                    }
 
-                                unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
-                            }
+                    // Fix the undo history to have all the previous changed merged into one.
+                    {
+                        let mut table_state_data = table_state_data.borrow_mut();
+                        let table_state_data = table_state_data.get_mut(&*packed_file_path.borrow()).unwrap();
+                        let len = table_state_data.undo_history.len();
+                        let mut edits_data = vec![];
+                        
+                        {
+                            let mut edits = table_state_data.undo_history.drain((len - positions_and_texts.len())..);
+                            for edit in &mut edits { if let TableOperations::Editing(mut edit) = edit { edits_data.append(&mut edit); }}
                         }
+
+                        table_state_data.undo_history.push(TableOperations::Editing(edits_data));
+                        table_state_data.redo_history.clear();
+                        update_undo_model(model, table_state_data.undo_model); 
                     }
+
+                    unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
+                    show_dialog(app_ui.window, true, format!("{} cells have been changed.", positions_and_texts.len()));
                 }
             )),
         };
@@ -2888,24 +4083,56 @@ impl PackedFileTableView {
         unsafe { table_view.as_mut().unwrap().horizontal_header().as_mut().unwrap().signals().sort_indicator_changed().connect(&slots.slot_sort_order_column_changed); }
         //unsafe { table_view_frozen.as_mut().unwrap().horizontal_header().as_mut().unwrap().signals().sort_indicator_changed().connect(&slots.slot_sort_order_column_changed); }
         unsafe { model.as_mut().unwrap().signals().data_changed().connect(&slots.save_changes); }
+        unsafe { model.as_mut().unwrap().signals().data_changed().connect(&slots.slot_schedule_table_checks_on_edit); }
         unsafe { model.as_mut().unwrap().signals().item_changed().connect(&slots.slot_item_changed); }
+        unsafe { model.as_mut().unwrap().signals().rows_inserted().connect(&slots.slot_schedule_table_checks); }
+        unsafe { model.as_mut().unwrap().signals().rows_removed().connect(&slots.slot_schedule_table_checks); }
+        unsafe { table_checks_timer.as_ref().unwrap().signals().timeout().connect(&slots.slot_run_table_checks); }
+        unsafe { (filter_model as *mut AbstractItemModel).as_ref().unwrap().signals().rows_inserted().connect(&slots.slot_schedule_selection_stats_rows); }
+        unsafe { (filter_model as *mut AbstractItemModel).as_ref().unwrap().signals().rows_removed().connect(&slots.slot_schedule_selection_stats_rows); }
+        unsafe { selection_stats_timer.as_ref().unwrap().signals().timeout().connect(&slots.slot_update_selection_stats); }
         unsafe { context_menu_add.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_add); }
         unsafe { context_menu_insert.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_insert); }
         unsafe { context_menu_delete.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_delete); }
         unsafe { context_menu_apply_maths_to_selection.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_apply_maths_to_selection); }
         unsafe { context_menu_rewrite_selection.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_rewrite_selection); }
+        unsafe { context_menu_toggle_booleans.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_toggle_booleans); }
+        unsafe { context_menu_pick_colour.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_pick_colour); }
+        unsafe { context_menu_pick_reference.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_pick_reference); }
+        unsafe { context_menu_edit_in_window.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_edit_in_window); }
+        unsafe { context_menu_go_to_definition.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_go_to_definition); }
+        unsafe { context_menu_find_usages.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_find_usages); }
         unsafe { context_menu_clone.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_clone); }
         unsafe { context_menu_clone_and_append.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_clone_and_append); }
         unsafe { context_menu_copy.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_copy); }
         unsafe { context_menu_copy_as_lua_table.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_copy_as_lua_table); }
+        unsafe { context_menu_copy_as_markdown_table.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_copy_as_markdown_table); }
+        unsafe { context_menu_copy_as_json.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_copy_as_json); }
+        unsafe { context_menu_copy_with_headers.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_copy_with_headers); }
         unsafe { context_menu_paste.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_paste); }
         unsafe { context_menu_paste_as_new_lines.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_paste_as_new_lines); }
         unsafe { context_menu_paste_to_fill_selection.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_paste_to_fill_selection); }
         unsafe { context_menu_selection_invert.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_selection_invert); }
         unsafe { context_menu_sidebar.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_sidebar); }
+        unsafe { context_menu_history.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_history); }
+        unsafe { history_view.as_mut().unwrap().signals().clicked().connect(&slots.slot_history_go_to); }
+        unsafe { context_menu_column_filters.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_column_filters); }
+        unsafe { context_menu_freeze_key_columns.as_mut().unwrap().signals().toggled().connect(&slots.slot_context_menu_freeze_key_columns); }
+        unsafe { context_menu_vanilla_diff.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_vanilla_diff); }
+        unsafe { context_menu_revert_cell_to_vanilla.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_revert_cell_to_vanilla); }
+        unsafe { context_menu_revert_row_to_vanilla.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_revert_row_to_vanilla); }
+        unsafe { column_layout_save_button.as_mut().unwrap().signals().released().connect(&slots.slot_save_column_layout); }
+        unsafe { column_layout_load_button.as_mut().unwrap().signals().released().connect(&slots.slot_load_column_layout); }
+        unsafe { column_layout_delete_button.as_mut().unwrap().signals().released().connect(&slots.slot_delete_column_layout); }
+        unsafe { context_menu_add_highlight_rule.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_add_highlight_rule); }
+        unsafe { context_menu_clear_highlight_rules.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_clear_highlight_rules); }
         unsafe { context_menu_search.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_search); }
+        unsafe { context_menu_go_to_row.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_go_to_row); }
         unsafe { context_menu_import.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_import); }
         unsafe { context_menu_export.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_export); }
+        unsafe { context_menu_export_json.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_export_json); }
+        unsafe { context_menu_export_csv.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_export_csv); }
+        unsafe { context_menu_reload.as_mut().unwrap().signals().triggered().connect(&slots.slot_context_menu_reload); }
 
         unsafe { smart_delete.as_mut().unwrap().signals().triggered().connect(&slots.slot_smart_delete); }
         unsafe { context_menu_undo.as_mut().unwrap().signals().triggered().connect(&slots.slot_undo); }
@@ -2924,6 +4151,17 @@ impl PackedFileTableView {
         unsafe { row_filter_line_edit.as_mut().unwrap().signals().text_changed().connect(&slots.slot_row_filter_change_text); }
         unsafe { row_filter_column_selector.as_mut().unwrap().signals().current_index_changed_c_int().connect(&slots.slot_row_filter_change_column); }
         unsafe { row_filter_case_sensitive_button.as_mut().unwrap().signals().toggled().connect(&slots.slot_row_filter_change_case_sensitive); }
+        for line_edit in column_filter_line_edits.iter() {
+            unsafe { line_edit.as_mut().unwrap().signals().text_changed().connect(&slots.slot_column_filter_change_text); }
+        }
+        unsafe { column_filters_timer.as_ref().unwrap().signals().timeout().connect(&slots.slot_apply_column_filters); }
+
+        // Keep the column filters over their columns when the columns are resized, moved or scrolled.
+        unsafe { table_view.as_mut().unwrap().horizontal_header().as_ref().unwrap().signals().section_resized().connect(&slots.slot_update_column_filters_geometry); }
+        unsafe { table_view.as_mut().unwrap().horizontal_header().as_ref().unwrap().signals().section_moved().connect(&slots.slot_update_column_filters_geometry); }
+        unsafe { table_view.as_mut().unwrap().horizontal_header().as_ref().unwrap().signals().section_moved().connect(&slots.slot_update_row_filter_column_selector); }
+        unsafe { table_view.as_mut().unwrap().horizontal_header().as_ref().unwrap().signals().geometries_changed().connect(&slots.slot_update_column_filters_geometry); }
+        unsafe { table_view.as_mut().unwrap().horizontal_scroll_bar().as_ref().unwrap().signals().value_changed().connect(&slots.slot_update_column_filters_geometry); }
 
         // Initial states for the Contextual Menu Actions.
         unsafe {
@@ -2932,21 +4170,37 @@ impl PackedFileTableView {
             context_menu_delete.as_mut().unwrap().set_enabled(false);
             context_menu_apply_maths_to_selection.as_mut().unwrap().set_enabled(false);
             context_menu_rewrite_selection.as_mut().unwrap().set_enabled(false);
+            context_menu_toggle_booleans.as_mut().unwrap().set_enabled(false);
+            context_menu_pick_colour.as_mut().unwrap().set_enabled(false);
+            context_menu_pick_reference.as_mut().unwrap().set_enabled(false);
+            context_menu_edit_in_window.as_mut().unwrap().set_enabled(false);
+            context_menu_go_to_definition.as_mut().unwrap().set_enabled(false);
+            context_menu_find_usages.as_mut().unwrap().set_enabled(false);
+            context_menu_revert_cell_to_vanilla.as_mut().unwrap().set_enabled(false);
+            context_menu_revert_row_to_vanilla.as_mut().unwrap().set_enabled(false);
             context_menu_clone.as_mut().unwrap().set_enabled(false);
             context_menu_clone_and_append.as_mut().unwrap().set_enabled(false);
             context_menu_copy.as_mut().unwrap().set_enabled(false);
             context_menu_copy_as_lua_table.as_mut().unwrap().set_enabled(true);
+            context_menu_copy_as_markdown_table.as_mut().unwrap().set_enabled(false);
+            context_menu_copy_as_json.as_mut().unwrap().set_enabled(false);
+            context_menu_copy_with_headers.as_mut().unwrap().set_enabled(false);
             context_menu_paste.as_mut().unwrap().set_enabled(true);
             context_menu_paste_as_new_lines.as_mut().unwrap().set_enabled(true);
             context_menu_paste_to_fill_selection.as_mut().unwrap().set_enabled(true);
             context_menu_selection_invert.as_mut().unwrap().set_enabled(true);
             context_menu_import.as_mut().unwrap().set_enabled(true);
             context_menu_export.as_mut().unwrap().set_enabled(true);
+            context_menu_export_json.as_mut().unwrap().set_enabled(true);
+            context_menu_export_csv.as_mut().unwrap().set_enabled(true);
+            context_menu_reload.as_mut().unwrap().set_enabled(if let TableType::DependencyManager(_) = *table_type.borrow() { false } else { true });
             undo_redo_enabler.as_mut().unwrap().trigger();
         }
 
         // Trigger the "Enable/Disable" slot every time we change the selection in the TreeView.
         unsafe { table_view.as_mut().unwrap().selection_model().as_ref().unwrap().signals().selection_changed().connect(&slots.slot_context_menu_enabler); }
+        unsafe { table_view.as_mut().unwrap().selection_model().as_ref().unwrap().signals().selection_changed().connect(&slots.slot_schedule_selection_stats); }
+        unsafe { selection_stats_timer.as_mut().unwrap().start(()); }
 
         // If we got an entry for this PackedFile in the state's history, use it.
         if TABLE_STATES_UI.lock().unwrap().get(&*packed_file_path.borrow()).is_some() {
@@ -2955,8 +4209,9 @@ impl PackedFileTableView {
                 let ts = TABLE_STATES_UI.lock().unwrap().clone();
                 state_data = ts.get(&*packed_file_path.borrow()).clone().unwrap().clone();
             }
-            // Ensure that the selected column actually exists in the table.
+            // Ensure that the selected column actually exists in the table. The filter state stores the logical index of the column.
             let column = if state_data.filter_state.column < table_definition.fields.len() as i32 { state_data.filter_state.column } else { 0 };
+            let column = unsafe { row_filter_column_selector.as_mut().unwrap().find_data(&Variant::new0(column)) };
 
             // Block the signals during this, so we don't trigger a borrow error.
             let mut blocker1 = unsafe { SignalBlocker::new(row_filter_line_edit.as_mut().unwrap().static_cast_mut() as &mut Object) };
@@ -3025,15 +4280,34 @@ impl PackedFileTableView {
             
             blocker1.unblock();
             blocker2.unblock();
+
+            // The header's signals were blocked, so we need to manually update the stuff that follows the visual order of the columns.
+            Self::update_row_filter_column_selector(table_view, row_filter_column_selector, &table_definition);
+            update_column_filters_geometry(table_view, &column_filter_line_edits);
         }
 
         // Otherwise, we create a basic state.
         else { TABLE_STATES_UI.lock().unwrap().insert(packed_file_path.borrow().to_vec(), TableStateUI::new_empty()); }
 
+        // Highlight the cells matching the highlight rules of the table, if any.
+        let highlight_rules = TABLE_STATES_UI.lock().unwrap().get(&*packed_file_path.borrow()).map(|x| x.highlight_rules.to_vec()).unwrap_or_else(|| vec![]);
+        apply_highlight_rules(model, &table_definition, &highlight_rules, &undo_lock);
+
+        // Mark the rows with duplicated keys, if any.
+        paint_duplicated_keys(model, &key_columns, table_definition.fields.len(), &undo_lock);
+
+        // If the key columns of tables with this name were frozen the last time, freeze them again.
+        if FROZEN_KEY_COLUMNS.lock().unwrap().contains(&Self::get_table_name(&table_type.borrow())) {
+            unsafe { context_menu_freeze_key_columns.as_mut().unwrap().set_checked(true); }
+        }
+
         // Retrigger the filter, so the table get's updated properly.
         unsafe { row_filter_case_sensitive_button.as_mut().unwrap().set_checked(!row_filter_case_sensitive_button.as_mut().unwrap().is_checked()); }
         unsafe { row_filter_case_sensitive_button.as_mut().unwrap().set_checked(!row_filter_case_sensitive_button.as_mut().unwrap().is_checked()); }
 
+        // Register the TableView, so other views can reach it once it's open.
+        open_table_views.borrow_mut().insert(packed_file_path.borrow().to_vec(), table_view);
+
         // Return the slots to keep them as hostages.
         Ok(slots)
     }
@@ -3100,6 +4374,20 @@ impl PackedFileTableView {
                         item.set_data((&Variant::new2(*data), 2));
                         item
                     },
+
+                    DecodedData::Double(ref data) => {
+                        let mut item = StandardItem::new(());
+                        item.set_data((&Variant::new0(*data), 2));
+                        item
+                    },
+
+                    // This one is stored as text, as it's shown in hex, with a swatch of the colour.
+                    DecodedData::Colour(_) => {
+                        let mut item = StandardItem::new(&QString::from_std_str(field.data_to_string()));
+                        update_colour_swatch(item.as_mut_ptr());
+                        item
+                    },
+
                     // All these are Strings, so it can be together,
                     DecodedData::StringU8(ref data) |
                     DecodedData::StringU16(ref data) |
@@ -3144,6 +4432,12 @@ impl PackedFileTableView {
                         item.set_data((&Variant::new2(0i64), 2));
                         item
                     },
+                    FieldType::Double => {
+                        let mut item = StandardItem::new(());
+                        item.set_data((&Variant::new0(0f64), 2));
+                        item
+                    },
+                    FieldType::Colour => StandardItem::new(&QString::from_std_str(DecodedData::default(field.field_type).data_to_string())),
                     FieldType::StringU8 |
                     FieldType::StringU16 |
                     FieldType::OptionalStringU8 |
@@ -3157,6 +4451,8 @@ impl PackedFileTableView {
 
         // Here we assing the ItemDelegates, so each type has his own widget with validation included.
         // LongInteger uses normal string controls due to QSpinBox being limited to i32.
+        // Colour uses normal string controls too, as it's shown in hex. Its values are validated when pasted or replaced,
+        // and they can be picked with a colour dialog through the "Pick Colour" action.
         // The rest don't need any kind of validation. For now.
         for (column, field) in table_definition.fields.iter().enumerate() {
             match field.field_type {
@@ -3164,6 +4460,8 @@ impl PackedFileTableView {
                 FieldType::Float => unsafe { qt_custom_stuff::new_doublespinbox_item_delegate(table_view as *mut Object, column as i32) },
                 FieldType::Integer => unsafe { qt_custom_stuff::new_spinbox_item_delegate(table_view as *mut Object, column as i32, 32) },
                 FieldType::LongInteger => unsafe { qt_custom_stuff::new_spinbox_item_delegate(table_view as *mut Object, column as i32, 64) },
+                FieldType::Double => unsafe { qt_custom_stuff::new_doublespinbox_item_delegate(table_view as *mut Object, column as i32) },
+                FieldType::Colour => {},
                 FieldType::StringU8 => {},
                 FieldType::StringU16 => {},
                 FieldType::OptionalStringU8 => {},
@@ -3172,8 +4470,9 @@ impl PackedFileTableView {
         }
 
         // We build the combos lists here, so it get's rebuilt if we import a TSV and clear the table.
+        // Reference columns without referenced data (no dependency database loaded) keep the normal editor.
         if !SETTINGS.lock().unwrap().settings_bool["disable_combos_on_tables"] {
-            for (column, data) in dependency_data {
+            for (column, data) in dependency_data.iter().filter(|(_, data)| !data.is_empty()) {
                 let mut list = StringList::new(());
                 data.iter().for_each(|x| list.append(&QString::from_std_str(x)));
                 let list: *mut StringList = &mut list;
@@ -3214,6 +4513,8 @@ impl PackedFileTableView {
                         FieldType::Float => DecodedData::Float(model.as_mut().unwrap().item((row as i32, column as i32)).as_mut().unwrap().data(2).to_float()),
                         FieldType::Integer => DecodedData::Integer(model.as_mut().unwrap().item((row as i32, column as i32)).as_mut().unwrap().data(2).to_int()),
                         FieldType::LongInteger => DecodedData::LongInteger(model.as_mut().unwrap().item((row as i32, column as i32)).as_mut().unwrap().data(2).to_long_long()),
+                        FieldType::Double => DecodedData::Double(model.as_mut().unwrap().item((row as i32, column as i32)).as_mut().unwrap().data(2).to_double()),
+                        FieldType::Colour => DecodedData::Colour(u32::from_str_radix(QString::to_std_string(&model.as_mut().unwrap().item((row as i32, column as i32)).as_mut().unwrap().text()).trim_start_matches('#'), 16).unwrap_or(0)),
 
                         // All these are just normal Strings.
                         FieldType::StringU8 => DecodedData::StringU8(QString::to_std_string(&model.as_mut().unwrap().item((row as i32, column as i32)).as_mut().unwrap().text())),
@@ -3256,11 +4557,23 @@ impl PackedFileTableView {
             TableType::DB(packed_file) => {
                 sender_qt.send(Commands::EncodePackedFileDB).unwrap();
                 sender_qt_data.send(Data::DBVecString((packed_file.clone(), packed_file_path.borrow().to_vec()))).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::Success => {},
+                    Data::String(report) => return show_dry_run_report(app_ui.window, &report),
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
             },
 
             TableType::LOC(packed_file) => {
                 sender_qt.send(Commands::EncodePackedFileLoc).unwrap();
                 sender_qt_data.send(Data::LocVecString((packed_file.clone(), packed_file_path.borrow().to_vec()))).unwrap();
+                match check_message_validity_tryrecv(&receiver_qt) {
+                    Data::Success => {},
+                    Data::String(report) => return show_dry_run_report(app_ui.window, &report),
+                    Data::Error(error) => show_dialog(app_ui.window, false, error),
+                    _ => panic!(THREADS_MESSAGE_ERROR),
+                }
             }
         }
 
@@ -3706,12 +5019,19 @@ impl PackedFileTableView {
             let item = StandardItem::new(&QString::from_std_str(&name)).into_raw();
             unsafe { model.as_mut().unwrap().set_horizontal_header_item(index as i32, item) };
 
+            // If we want it, tint the header depending on the type of the column.
+            if SETTINGS.lock().unwrap().settings_bool["tint_column_headers_by_type"] {
+                unsafe { item.as_mut().unwrap().set_background(&Brush::new(&Color::new(get_color_for_field_type(field.field_type)))); }
+            }
+
             // Depending on his type, set one width or another.
             match field.field_type {
                 FieldType::Boolean => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 100); }
                 FieldType::Float => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 140); }
                 FieldType::Integer => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 140); }
                 FieldType::LongInteger => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 140); }
+                FieldType::Double => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 140); }
+                FieldType::Colour => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 100); }
                 FieldType::StringU8 => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 350); }
                 FieldType::StringU16 => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 350); }
                 FieldType::OptionalStringU8 => unsafe { table_view.as_mut().unwrap().set_column_width(index as i32, 350); }
@@ -3784,6 +5104,130 @@ impl PackedFileTableView {
         }
     }
 
+    /// This function saves the provided settings, reporting any error to the user. It returns true if they were saved.
+    fn save_settings(
+        sender_qt: &Sender<Commands>,
+        sender_qt_data: &Sender<Data>,
+        receiver_qt: &Rc<RefCell<Receiver<Data>>>,
+        app_ui: &AppUI,
+        settings: Settings,
+    ) -> bool {
+        sender_qt.send(Commands::SetSettings).unwrap();
+        sender_qt_data.send(Data::Settings(settings)).unwrap();
+        match check_message_validity_recv2(receiver_qt) {
+            Data::Success => true,
+            Data::Error(error) => { show_dialog(app_ui.window, false, error); false },
+            _ => panic!(THREADS_MESSAGE_ERROR),
+        }
+    }
+
+    /// This function loads the vanilla rows of the table into the provided cache, if they're not there or were loaded for another Game Selected.
+    fn load_vanilla_rows(
+        sender_qt: &Sender<Commands>,
+        sender_qt_data: &Sender<Data>,
+        receiver_qt: &Rc<RefCell<Receiver<Data>>>,
+        packed_file_path: &[String],
+        table_definition: &TableDefinition,
+        vanilla_rows: &Rc<RefCell<Option<(String, BTreeMap<Vec<String>, Vec<Option<String>>>)>>>,
+    ) -> Result<()> {
+        let game_selected = GAME_SELECTED.lock().unwrap().to_owned();
+        if vanilla_rows.borrow().as_ref().map_or(true, |(game, _)| *game != game_selected) {
+            sender_qt.send(Commands::GetVanillaRows).unwrap();
+            sender_qt_data.send(Data::VecStringTableDefinition((packed_file_path.to_vec(), table_definition.clone()))).unwrap();
+            match check_message_validity_tryrecv(&receiver_qt) {
+                Data::BTreeMapVecStringVecOptionString(data) => *vanilla_rows.borrow_mut() = Some((game_selected, data)),
+                Data::Error(error) => return Err(error),
+                _ => panic!(THREADS_MESSAGE_ERROR),
+            }
+        }
+        Ok(())
+    }
+
+    /// This function reverts the selected cells (or the entire rows of the selected cells) to their vanilla values, as a single undo step.
+    fn revert_selection_to_vanilla(
+        entire_rows: bool,
+        app_ui: &AppUI,
+        sender_qt: &Sender<Commands>,
+        sender_qt_data: &Sender<Data>,
+        receiver_qt: &Rc<RefCell<Receiver<Data>>>,
+        table_view: *mut TableView,
+        filter_model: *mut SortFilterProxyModel,
+        model: *mut StandardItemModel,
+        undo_redo_enabler: *mut Action,
+        packed_file_path: &[String],
+        table_definition: &TableDefinition,
+        table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
+        vanilla_rows: &Rc<RefCell<Option<(String, BTreeMap<Vec<String>, Vec<Option<String>>>)>>>,
+    ) {
+        if let Err(error) = Self::load_vanilla_rows(sender_qt, sender_qt_data, receiver_qt, packed_file_path, table_definition, vanilla_rows) {
+            return show_dialog(app_ui.window, false, error);
+        }
+
+        // Get the selected cells in the source model, so this works with the table filtered or sorted.
+        let indexes = unsafe { filter_model.as_mut().unwrap().map_selection_to_source(&table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection()).indexes() };
+        let mut cells = vec![];
+        for index in 0..indexes.count(()) {
+            let model_index = indexes.at(index);
+            if model_index.is_valid() {
+                if entire_rows { cells.extend((0..table_definition.fields.len()).map(|column| (model_index.row() as usize, column))); }
+                else { cells.push((model_index.row() as usize, model_index.column() as usize)); }
+            }
+        }
+        cells.sort();
+        cells.dedup();
+
+        // Cells with values not valid for their column (like when a column changed his type) are skipped.
+        let rows = get_model_rows(model, &(0..table_definition.fields.len()).collect::<Vec<usize>>());
+        let cells_to_revert = match *vanilla_rows.borrow() {
+            Some((_, ref vanilla_rows)) => get_cells_to_revert(&rows, &cells, &get_row_identity_columns(table_definition), vanilla_rows)
+                .into_iter()
+                .filter(|((_, column), value)| is_valid_for_field_type(value, table_definition.fields[*column].field_type))
+                .collect::<Vec<((usize, usize), String)>>(),
+            None => vec![],
+        };
+        if cells_to_revert.is_empty() { return show_dialog(app_ui.window, false, "No cells have been changed. The selected cells already have their vanilla values, or their rows are not in vanilla."); }
+
+        for ((row, column), value) in &cells_to_revert {
+            let item = unsafe { model.as_mut().unwrap().item((*row as i32, *column as i32)) };
+            match table_definition.fields[*column].field_type {
+                FieldType::Boolean => unsafe { item.as_mut().unwrap().set_check_state(if value.to_lowercase() == "true" || value == "1" { CheckState::Checked } else { CheckState::Unchecked }); }
+                FieldType::Float => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(value.parse::<f32>().unwrap()), 2)); }
+                FieldType::Integer => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(value.parse::<i32>().unwrap()), 2)); }
+                FieldType::LongInteger => unsafe { item.as_mut().unwrap().set_data((&Variant::new2(value.parse::<i64>().unwrap()), 2)); }
+                FieldType::Double => unsafe { item.as_mut().unwrap().set_data((&Variant::new0(value.parse::<f64>().unwrap()), 2)); }
+                _ => unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str(value)); }
+            }
+        }
+
+        // Fix the undo history to have all the previous changed merged into one.
+        {
+            let mut table_state_data = table_state_data.borrow_mut();
+            let table_state_data = table_state_data.get_mut(packed_file_path).unwrap();
+            let len = table_state_data.undo_history.len();
+            let mut edits_data = vec![];
+
+            {
+                let mut edits = table_state_data.undo_history.drain((len - cells_to_revert.len())..);
+                for edit in &mut edits { if let TableOperations::Editing(mut edit) = edit { edits_data.append(&mut edit); }}
+            }
+
+            table_state_data.undo_history.push(TableOperations::Editing(edits_data));
+            table_state_data.redo_history.clear();
+            update_undo_model(model, table_state_data.undo_model);
+        }
+
+        unsafe { undo_redo_enabler.as_mut().unwrap().trigger(); }
+    }
+
+    /// This function returns the name used to remember stuff for all the tables of the same kind: the type of the table for DB Tables.
+    fn get_table_name(table_type: &TableType) -> String {
+        match table_type {
+            TableType::DB(data) => data.db_type.to_owned(),
+            TableType::LOC(_) => "loc".to_owned(),
+            TableType::DependencyManager(_) => "dependency_manager".to_owned(),
+        }
+    }
+
     // Function to check if an specific field's data is in their references.
     fn check_references(
         dependency_data: &BTreeMap<i32, Vec<String>>,
@@ -3800,47 +5244,18 @@ impl PackedFileTableView {
         }
     }
 
-    /// This function checks if the data in the clipboard is suitable for be pasted in all selected cells.
-    fn check_clipboard_to_fill_selection(
-        definition: &TableDefinition,
+    /// This function returns the rows in the clipboard, fitted to the columns of the table (in visual order) and with all their cells in a single line,
+    /// and if any of them had to be truncated.
+    fn get_clipboard_rows_to_append(
         table_view: *mut TableView,
-        model: *mut StandardItemModel,
-        filter_model: *mut SortFilterProxyModel,
-    ) -> bool {
-
-        // Get the current selection.
+        definition: &TableDefinition
+    ) -> (Vec<String>, bool) {
         let clipboard = GuiApplication::clipboard();
         let text = unsafe { clipboard.as_mut().unwrap().text(()).to_std_string() };
-        let indexes = unsafe { filter_model.as_mut().unwrap().map_selection_to_source(&table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection()).indexes() };
-
-        // If there is nothing selected, don't waste your time.
-        if indexes.count(()) == 0 { return false }
-
-        // For each selected index...
-        for index in 0..indexes.count(()) {
-            let model_index = indexes.at(index);
-            if model_index.is_valid() {
-
-                // Depending on the column, we try to encode the data in one format or another.
-                let item = unsafe { model.as_mut().unwrap().item_from_index(&model_index) };
-                let column = unsafe { item.as_mut().unwrap().index().column() };
-                match definition.fields[column as usize].field_type {
-                    FieldType::Boolean => if text.to_lowercase() != "true" && text.to_lowercase() != "false" && text != "1" && text != "0" { return false },
-                    FieldType::Float => if text.parse::<f32>().is_err() { return false },
-                    FieldType::Integer => if text.parse::<i32>().is_err() { return false },
-                    FieldType::LongInteger => if text.parse::<i64>().is_err() { return false },
-
-                    // All these are Strings, so we can skip their checks....
-                    FieldType::StringU8 |
-                    FieldType::StringU16 |
-                    FieldType::OptionalStringU8 |
-                    FieldType::OptionalStringU16 => {}
-                }
-            }
-        }
-
-        // If we reach this place, it means none of the cells was incorrect, so we can paste.
-        true
+        let horizontal_header = unsafe { table_view.as_ref().unwrap().horizontal_header().as_ref().unwrap() };
+        let fields = (0..definition.fields.len() as i32).map(|x| &definition.fields[horizontal_header.logical_index(x) as usize]).collect::<Vec<&Field>>();
+        let (rows, truncated) = fit_rows_to_columns(&parse_clipboard_tsv(&text), &fields);
+        (rows.into_iter().flatten().collect(), truncated)
     }
 
     /// This function checks if the data in the clipboard is suitable to be appended as rows at the end of the Table.
@@ -3849,18 +5264,12 @@ impl PackedFileTableView {
         definition: &TableDefinition
     ) -> bool {
 
-        // Get the text from the clipboard.
-        let clipboard = GuiApplication::clipboard();
-        let mut text = unsafe { clipboard.as_mut().unwrap().text(()).to_std_string() };
-
-        // If the text ends in \n, remove it. Excel things. We don't use newlines, so replace them with '\t'.
-        if text.ends_with('\n') { text.pop(); }
-        let text = text.replace('\n', "\t");
-        let text = text.split('\t').collect::<Vec<&str>>();
+        // Get the rows from the clipboard, fitted to the columns of the table.
+        let (text, _) = Self::get_clipboard_rows_to_append(table_view, definition);
 
         // Get the index for the column.
         let mut column = 0;
-        for cell in text {
+        for cell in &text {
 
             // Depending on the column, we try to encode the data in one format or another.
             let column_logical_index = unsafe { table_view.as_ref().unwrap().horizontal_header().as_ref().unwrap().logical_index(column) };
@@ -3869,6 +5278,8 @@ impl PackedFileTableView {
                 FieldType::Float => if cell.parse::<f32>().is_err() { return false },
                 FieldType::Integer => if cell.parse::<i32>().is_err() { return false },
                 FieldType::LongInteger => if cell.parse::<i64>().is_err() { return false },
+                FieldType::Double |
+                FieldType::Colour => if !is_valid_for_field_type(cell, definition.fields[column_logical_index as usize].field_type) { return false },
 
                 // All these are Strings, so we can skip their checks....
                 FieldType::StringU8 |
@@ -3885,48 +5296,95 @@ impl PackedFileTableView {
         true
     }
 
+    /// This function fills the column selector of the filter with the columns of the table, in their visual order, keeping the selected column.
+    ///
+    /// Each item stores the logical index of his column as data, so it can be used for filtering no matter where the column has been moved.
+    fn update_row_filter_column_selector(
+        table_view: *mut TableView,
+        column_selector: *mut ComboBox,
+        table_definition: &TableDefinition,
+    ) {
+        let current_column = Self::get_row_filter_column(column_selector).max(0);
+        let header = unsafe { table_view.as_mut().unwrap().horizontal_header() };
+
+        // Block the signals during this, so we don't trigger a filtering for every item we add.
+        let mut blocker = unsafe { SignalBlocker::new(column_selector.as_mut().unwrap().static_cast_mut() as &mut Object) };
+        unsafe { column_selector.as_mut().unwrap().clear(); }
+        for visual_index in 0..table_definition.fields.len() as i32 {
+            let logical_index = unsafe { header.as_mut().unwrap().logical_index(visual_index) };
+            let name = Self::clean_column_names(&table_definition.fields[logical_index as usize].field_name);
+            unsafe { column_selector.as_mut().unwrap().add_item((&QString::from_std_str(&name), &Variant::new0(logical_index))); }
+        }
+
+        let index = unsafe { column_selector.as_mut().unwrap().find_data(&Variant::new0(current_column)) };
+        unsafe { column_selector.as_mut().unwrap().set_current_index(index); }
+        blocker.unblock();
+    }
+
+    /// This function returns the logical index of the column selected in the column selector of the filter, or -1 if there is none selected.
+    fn get_row_filter_column(column_selector: *mut ComboBox) -> i32 {
+        let data = unsafe { column_selector.as_mut().unwrap().current_data(()) };
+        if data.is_valid() { data.to_int() } else { -1 }
+    }
+
     /// Function to filter the table. If a value is not provided by a slot, we get it from the widget itself.
+    ///
+    /// If any of the column filters is being used, the rows are checked against all of them (and the normal filter) here, and the filter
+    /// model only hides the rows we marked as filtered. Otherwise, the filter model filters the column with the normal filter.
     fn filter_table(
         pattern: Option<QString>,
         column: Option<i32>,
         case_sensitive: Option<bool>,
+        model: *mut StandardItemModel,
         filter_model: *mut SortFilterProxyModel,
+        table_definition: &TableDefinition,
         filter_line_edit: *mut LineEdit,
         column_selector: *mut ComboBox,
         case_sensitive_button: *mut PushButton,
+        column_filter_line_edits: &[*mut LineEdit],
         update_search_stuff: *mut Action,
         packed_file_path: &Rc<RefCell<Vec<String>>>,
     ) {
 
-        // Set the pattern to search.
-        let mut pattern = if let Some(pattern) = pattern { RegExp::new(&pattern) }
-        else { unsafe { RegExp::new(&filter_line_edit.as_mut().unwrap().text()) }};
+        // Get the pattern, the column and the case sensitivity of the filter.
+        let pattern = if let Some(pattern) = pattern { pattern } else { unsafe { filter_line_edit.as_mut().unwrap().text() }};
+        let column = if let Some(column) = column { column } else { Self::get_row_filter_column(column_selector) };
+        let case_sensitive = if let Some(case_sensitive) = case_sensitive { case_sensitive } else { unsafe { case_sensitive_button.as_mut().unwrap().is_checked() }};
 
-        // Set the column selected.
-        if let Some(column) = column { unsafe { filter_model.as_mut().unwrap().set_filter_key_column(column); }}
-        else { unsafe { filter_model.as_mut().unwrap().set_filter_key_column(column_selector.as_mut().unwrap().current_index()); }}
+        // Get the filters of the columns that have one.
+        let mut column_filters = column_filter_line_edits.iter().enumerate().filter_map(|(index, line_edit)| {
+            let text = unsafe { line_edit.as_mut().unwrap().text().to_std_string() };
+            ColumnFilter::new(&text, table_definition.fields[index].field_type, case_sensitive).map(|x| (index, x))
+        }).collect::<Vec<(usize, ColumnFilter)>>();
 
-        // Check if the filter should be "Case Sensitive".
-        if let Some(case_sensitive) = case_sensitive { 
+        // If there are no column filters, filter whatever it's in that column by the text we got.
+        if column_filters.is_empty() {
+            let mut pattern = RegExp::new(&pattern);
             if case_sensitive { pattern.set_case_sensitivity(CaseSensitivity::Sensitive); }
             else { pattern.set_case_sensitivity(CaseSensitivity::Insensitive); }
+
+            unsafe { filter_model.as_mut().unwrap().set_filter_role(0); }
+            unsafe { filter_model.as_mut().unwrap().set_filter_key_column(column); }
+            unsafe { filter_model.as_mut().unwrap().set_filter_reg_exp(&pattern); }
         }
 
+        // Otherwise, mark the rows that don't pass all the filters, and hide them.
         else {
-            let case_sensitive = unsafe { case_sensitive_button.as_mut().unwrap().is_checked() };
-            if case_sensitive { pattern.set_case_sensitivity(CaseSensitivity::Sensitive); }
-            else { pattern.set_case_sensitivity(CaseSensitivity::Insensitive); }
-        }
+            let pattern = pattern.to_std_string();
+            if !pattern.is_empty() && column >= 0 { column_filters.push((column as usize, ColumnFilter::new_text(&pattern, case_sensitive))); }
+            mark_filtered_rows(model, table_definition.fields.len(), &column_filters);
 
-        // Filter whatever it's in that column by the text we got.
-        unsafe { filter_model.as_mut().unwrap().set_filter_reg_exp(&pattern); }
+            unsafe { filter_model.as_mut().unwrap().set_filter_role(COLUMN_FILTER_ROLE); }
+            unsafe { filter_model.as_mut().unwrap().set_filter_key_column(0); }
+            unsafe { filter_model.as_mut().unwrap().set_filter_reg_exp(&RegExp::new(&QString::from_std_str("^$"))); }
+        }
 
         // Update the search stuff, if needed.
         unsafe { update_search_stuff.as_mut().unwrap().trigger(); }
 
         // Add the new filter data to the state history.
         if let Some(state) = TABLE_STATES_UI.lock().unwrap().get_mut(&*packed_file_path.borrow()) {
-            unsafe { state.filter_state = FilterState::new(filter_line_edit.as_mut().unwrap().text().to_std_string(), column_selector.as_mut().unwrap().current_index(), case_sensitive_button.as_mut().unwrap().is_checked()); }
+            unsafe { state.filter_state = FilterState::new(filter_line_edit.as_mut().unwrap().text().to_std_string(), Self::get_row_filter_column(column_selector), case_sensitive_button.as_mut().unwrap().is_checked()); }
         }
     }
 }
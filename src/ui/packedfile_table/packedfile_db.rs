@@ -39,6 +39,7 @@ pub fn create_db_view(
     global_search_explicit_paths: &Rc<RefCell<Vec<Vec<String>>>>,
     update_global_search_stuff: *mut Action,
     table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
+    open_table_views: &Rc<RefCell<BTreeMap<Vec<String>, *mut TableView>>>,
 ) -> Result<PackedFileTableView> {
 
     // Send the index back to the background thread, and wait until we get a response.
@@ -50,6 +51,7 @@ pub fn create_db_view(
         _ => panic!(THREADS_MESSAGE_ERROR), 
     };
     let table_definition = Rc::new(packed_file_data.table_definition.clone());
+    let table_name = packed_file_data.db_type.to_owned();
     
     PackedFileTableView::create_table_view(
         sender_qt,
@@ -61,8 +63,9 @@ pub fn create_db_view(
         global_search_explicit_paths,
         update_global_search_stuff,
         table_state_data,
+        open_table_views,
         &table_definition,
-        Some(packed_file_path.borrow()[1].to_owned()),
+        Some(table_name),
         &Rc::new(RefCell::new(TableType::DB(packed_file_data))),
     )
 }
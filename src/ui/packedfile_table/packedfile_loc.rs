@@ -40,6 +40,7 @@ pub fn create_loc_view(
     global_search_explicit_paths: &Rc<RefCell<Vec<Vec<String>>>>,
     update_global_search_stuff: *mut Action,
     table_state_data: &Rc<RefCell<BTreeMap<Vec<String>, TableStateData>>>,
+    open_table_views: &Rc<RefCell<BTreeMap<Vec<String>, *mut TableView>>>,
 ) -> Result<PackedFileTableView> {
 
     // Send the index back to the background thread, and wait until we get a response.
@@ -63,6 +64,7 @@ pub fn create_loc_view(
         global_search_explicit_paths,
         update_global_search_stuff,
         table_state_data,
+        open_table_views,
         &table_definition,
         None,
         &Rc::new(RefCell::new(TableType::LOC(packed_file_data))),
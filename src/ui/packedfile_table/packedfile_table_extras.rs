@@ -10,33 +10,98 @@
 
 // In this file are all the helper functions used by the PackedFile Tables.
 
+use qt_widgets::combo_box::ComboBox;
 use qt_widgets::dialog::Dialog;
 use qt_widgets::group_box::GroupBox;
 use qt_widgets::label::Label;
 use qt_widgets::line_edit::LineEdit;
+use qt_widgets::plain_text_edit::PlainTextEdit;
 use qt_widgets::push_button::PushButton;
 use qt_widgets::table_view::TableView;
 use qt_widgets::widget::Widget;
 
 use qt_gui::brush::Brush;
+use qt_gui::color::Color;
+use qt_gui::font::Font;
 use qt_gui::list::ListStandardItemMutPtr;
 use qt_gui::standard_item::StandardItem;
+use qt_gui::standard_item_model::StandardItemModel;
 
+use qt_core::abstract_item_model::AbstractItemModel;
 use qt_core::connection::Signal;
+use qt_core::flags::Flags;
+use qt_core::item_selection::ItemSelection;
+use qt_core::item_selection_model::SelectionFlag;
 use qt_core::model_index::ModelIndex;
-use qt_core::qt::{CheckState, GlobalColor};
+use qt_core::object::Object;
+use qt_core::qt::{CaseSensitivity, CheckState, GlobalColor};
+use qt_core::reg_exp::RegExp;
+use qt_core::signal_blocker::SignalBlocker;
+use qt_core::slots::{SlotNoArgs, SlotStringRef};
 use qt_core::sort_filter_proxy_model::SortFilterProxyModel;
 use qt_core::variant::Variant;
 
 use cpp_utils::StaticCast;
 
+use meval::tokenizer::{Operation, Token};
+use regex::{NoExpand, Regex};
+
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::rc::Rc;
 
 use crate::SETTINGS;
 use crate::QString;
 use crate::AppUI;
-use crate::schema::{FieldType, TableDefinition};
+use crate::error::{Error, ErrorKind, Result};
+use crate::packedfile::{DecodedData, DelimitedQuoting, get_default_row, get_key_columns};
+use crate::schema::{Field, FieldType, TableDefinition};
+use crate::settings::TableViewStyle;
 use crate::ui::create_grid_layout_unsafe;
+use crate::ui::table_state::{ColumnFilter, HighlightRule, get_highlighted_cells, row_matches_column_filters};
+
+/// Role used to mark the rows hidden by the column filters. It has to be over `Qt::UserRole` (256), so it doesn't collide with the roles Qt uses.
+pub const COLUMN_FILTER_ROLE: i32 = 264;
+
+/// Role holding the colour the cells of rows with a duplicated key are tinted with. Empty for the rest of the cells.
+pub const DUPLICATED_KEY_ROLE: i32 = 265;
+
+/// Role holding the colour the cells that differ from vanilla are tinted with. Empty for the rest of the cells.
+/// If a cell has both tints, the duplicated key one is the one painted.
+pub const VANILLA_DIFF_ROLE: i32 = 266;
+
+/// Role holding the tint of the table checks currently painted as background of a cell. Empty if the cell is not tinted.
+pub const TABLE_CHECKS_TINT_ROLE: i32 = 267;
+
+/// Role holding the background a cell had before being tinted by the table checks, so it can be restored once the tint is cleaned.
+pub const TABLE_CHECKS_BACKGROUND_ROLE: i32 = 268;
+
+/// Max amount of selected cells we calculate the statistics of. Bigger selections are just counted, so they don't lag the UI.
+pub const SELECTION_STATS_MAX_CELLS: usize = 100_000;
+
+/// This enum represents how a row of a table differs from his vanilla version (see `get_vanilla_diff()`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum VanillaDiff {
+
+    /// The row doesn't exist in vanilla.
+    Added,
+
+    /// The row exists in vanilla, but the cells in these columns have different values.
+    Changed(Vec<usize>),
+}
+
+/// This enum represents the row the text of the "Go to Row" dialog points to (see `get_go_to_row_target()`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum GoToRow {
+
+    /// Row in the order the table is shown right now, starting from 0.
+    Visible(usize),
+
+    /// Row of the source model, that may be hidden by the filters.
+    Source(usize),
+}
 
 /// This function creates the entire "Apply Maths" dialog for tables. It returns the operation to apply.
 pub fn create_apply_maths_dialog(app_ui: &AppUI) -> Option<String> {
@@ -55,16 +120,17 @@ pub fn create_apply_maths_dialog(app_ui: &AppUI) -> Option<String> {
     "\
 It's easy, but you'll not understand it without an example, so here it's one:
  - You selected a cell that says '5'.
- - Write '3 + {x}' in the box below.
+ - Write 'x + 3' in the box below.
  - Hit 'Accept'.
  - RPFM will turn that into '8' and put it in the cell.
+Only numeric cells are changed. Results are rounded in integer columns.
 Easy, isn't?
     "    
     ));
     unsafe { instructions_grid.as_mut().unwrap().add_widget((instructions_label.static_cast_mut() as *mut Widget, 0, 0, 1, 1)); }
 
     let mut maths_line_edit = LineEdit::new(());
-    maths_line_edit.set_placeholder_text(&QString::from_std_str("Write here a maths operation. {x} it's your current number."));
+    maths_line_edit.set_placeholder_text(&QString::from_std_str("Write here a maths operation. x it's your current number."));
     let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
 
     unsafe { main_grid.as_mut().unwrap().add_widget((instructions_frame as *mut Widget, 0, 0, 1, 2)); }
@@ -79,8 +145,152 @@ Easy, isn't?
     } else { None }
 }
 
-/// This function creates the entire "Rewrite selection" dialog for tables. It returns the rewriting sequence, or None.
-pub fn create_rewrite_selection_dialog(app_ui: &AppUI) -> Option<String> {
+/// This struct holds a maths operation from the "Apply Maths" dialog, over the current value of a cell (`x` or `{x}`).
+pub struct MathsOperation {
+    tokens: Vec<Token>,
+}
+
+/// This trait represents the numbers a maths operation can be computed with (see `evaluate_maths_operation()`).
+/// Every operation returns None if it cannot be done with this type of number.
+trait MathsNumber: Copy {
+    fn from_f64(value: f64) -> Option<Self>;
+    fn operate(operation: &Operation, left: Self, right: Self) -> Option<Self>;
+    fn call(function: &str, args: &[Self]) -> Option<Self>;
+}
+
+/// Floats get the same functions `meval` has, computed in their own precision.
+macro_rules! impl_maths_number_for_float {
+    ($float:ident) => {
+        impl MathsNumber for $float {
+            fn from_f64(value: f64) -> Option<Self> { Some(value as $float) }
+            fn operate(operation: &Operation, left: Self, right: Self) -> Option<Self> {
+                Some(match operation {
+                    Operation::Plus => left + right,
+                    Operation::Minus => left - right,
+                    Operation::Times => left * right,
+                    Operation::Div => left / right,
+                    Operation::Rem => left % right,
+                    Operation::Pow => left.powf(right),
+                })
+            }
+            fn call(function: &str, args: &[Self]) -> Option<Self> {
+                match (function, args) {
+                    ("sqrt", [x]) => Some(x.sqrt()),
+                    ("exp", [x]) => Some(x.exp()),
+                    ("ln", [x]) => Some(x.ln()),
+                    ("abs", [x]) => Some(x.abs()),
+                    ("sin", [x]) => Some(x.sin()),
+                    ("cos", [x]) => Some(x.cos()),
+                    ("tan", [x]) => Some(x.tan()),
+                    ("asin", [x]) => Some(x.asin()),
+                    ("acos", [x]) => Some(x.acos()),
+                    ("atan", [x]) => Some(x.atan()),
+                    ("sinh", [x]) => Some(x.sinh()),
+                    ("cosh", [x]) => Some(x.cosh()),
+                    ("tanh", [x]) => Some(x.tanh()),
+                    ("asinh", [x]) => Some(x.asinh()),
+                    ("acosh", [x]) => Some(x.acosh()),
+                    ("atanh", [x]) => Some(x.atanh()),
+                    ("floor", [x]) => Some(x.floor()),
+                    ("ceil", [x]) => Some(x.ceil()),
+                    ("round", [x]) => Some(x.round()),
+                    ("signum", [x]) => Some(x.signum()),
+                    ("atan2", [y, x]) => Some(y.atan2(*x)),
+                    ("max", [first, rest @ ..]) => Some(rest.iter().fold(*first, |max, x| max.max(*x))),
+                    ("min", [first, rest @ ..]) => Some(rest.iter().fold(*first, |min, x| min.min(*x))),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl_maths_number_for_float!(f32);
+impl_maths_number_for_float!(f64);
+
+/// Whole numbers are used for LongIntegers, which don't fit in a f64 without losing precision. They can only be
+/// used for operations with whole results, and overflows are not valid results.
+impl MathsNumber for i128 {
+    fn from_f64(value: f64) -> Option<Self> {
+        if value.fract() == 0.0 && value.abs() <= std::i64::MAX as f64 { Some(value as i128) } else { None }
+    }
+    fn operate(operation: &Operation, left: Self, right: Self) -> Option<Self> {
+        match operation {
+            Operation::Plus => left.checked_add(right),
+            Operation::Minus => left.checked_sub(right),
+            Operation::Times => left.checked_mul(right),
+            Operation::Rem => left.checked_rem(right),
+            Operation::Pow => u32::try_from(right).ok().and_then(|right| left.checked_pow(right)),
+            Operation::Div => None,
+        }
+    }
+    fn call(_: &str, _: &[Self]) -> Option<Self> { None }
+}
+
+/// This function parses a maths operation from the "Apply Maths" dialog, checking it only uses the current value of the cell,
+/// which can be written as `x` or `{x}`, and the constants and functions of `meval`.
+pub fn parse_maths_operation(operation: &str) -> Result<MathsOperation> {
+    let expression = operation.replace("{x}", "x").parse::<meval::Expr>().map_err(|_| Error::from(ErrorKind::InvalidMathsOperation))?;
+    expression.clone().bind("x").map_err(|_| Error::from(ErrorKind::InvalidMathsOperation))?;
+    Ok(MathsOperation { tokens: expression.to_vec() })
+}
+
+/// This function computes a maths operation with the provided type of number, from the current value of the cell.
+fn evaluate_maths_operation<T: MathsNumber>(operation: &MathsOperation, x: T) -> Option<T> {
+    let mut stack: Vec<T> = vec![];
+    for token in &operation.tokens {
+        let value = match token {
+            Token::Number(number) => T::from_f64(*number)?,
+            Token::Var(name) => match name.as_str() {
+                "x" => x,
+                "pi" => T::from_f64(std::f64::consts::PI)?,
+                "e" => T::from_f64(std::f64::consts::E)?,
+                _ => return None,
+            },
+            Token::Unary(Operation::Plus) => stack.pop()?,
+            Token::Unary(Operation::Minus) => T::operate(&Operation::Minus, T::from_f64(0.0)?, stack.pop()?)?,
+            Token::Binary(operation) => {
+                let right = stack.pop()?;
+                let left = stack.pop()?;
+                T::operate(operation, left, right)?
+            }
+            Token::Func(function, Some(args)) if *args <= stack.len() => {
+                let args = stack.split_off(stack.len() - args);
+                T::call(function, &args)?
+            }
+            _ => return None,
+        };
+        stack.push(value);
+    }
+    if stack.len() == 1 { stack.pop() } else { None }
+}
+
+/// This function applies a maths operation to the value of a numeric cell, computing it in the type of the cell, so the result is exactly what
+/// the cell can hold. Integers are computed as f64 (which holds every i32) and LongIntegers as whole numbers when possible (or as f64 otherwise),
+/// and their results are rounded and clamped to the range of the column.
+///
+/// It returns None if the result is not a number (like on divisions by zero or overflows) or the cell is not numeric.
+pub fn apply_maths_to_value(operation: &MathsOperation, value: &DecodedData) -> Option<DecodedData> {
+    let round_and_clamp = |result: f64, min: f64, max: f64| if result.is_finite() { Some(result.round().max(min).min(max)) } else { None };
+    match value {
+        DecodedData::Float(value) => evaluate_maths_operation(operation, *value).filter(|x| x.is_finite()).map(DecodedData::Float),
+        DecodedData::Double(value) => evaluate_maths_operation(operation, *value).filter(|x| x.is_finite()).map(DecodedData::Double),
+        DecodedData::Integer(value) => evaluate_maths_operation(operation, f64::from(*value))
+            .and_then(|x| round_and_clamp(x, f64::from(std::i32::MIN), f64::from(std::i32::MAX)))
+            .map(|x| DecodedData::Integer(x as i32)),
+        DecodedData::LongInteger(value) => match evaluate_maths_operation(operation, i128::from(*value)) {
+            Some(result) => Some(DecodedData::LongInteger(result.max(i128::from(std::i64::MIN)).min(i128::from(std::i64::MAX)) as i64)),
+            None => evaluate_maths_operation(operation, *value as f64)
+                .and_then(|x| round_and_clamp(x, std::i64::MIN as f64, std::i64::MAX as f64))
+                .map(|x| DecodedData::LongInteger(x as i64)),
+        },
+        _ => None,
+    }
+}
+
+/// This function creates the entire "Rewrite selection" dialog for tables. It returns the rewriting sequence and the regex
+/// to search in the cells (empty if we're not in regex mode), or None.
+pub fn create_rewrite_selection_dialog(app_ui: &AppUI) -> Option<(String, String)> {
 
     // Create and configure the dialog.
     let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
@@ -100,23 +310,177 @@ It's easy, but you'll not understand it without an example, so here it's one:
  - Hit 'Accept'.
  - RPFM will turn that into 'whatever you want' and put it in the cell.
 And, in case you ask, works with numeric cells too, as long as the resulting text is a valid number.
+If you write a regex in the second box, the text of the first box replaces its matches instead, with $1, $2,... being its capture groups.
     "    
     ));
     unsafe { instructions_grid.as_mut().unwrap().add_widget((instructions_label.static_cast_mut() as *mut Widget, 0, 0, 1, 1)); }
 
     let mut rewrite_sequence_line_edit = LineEdit::new(());
     rewrite_sequence_line_edit.set_placeholder_text(&QString::from_std_str("Write here whatever you want. {x} it's your current text."));
+    let mut regex_line_edit = LineEdit::new(());
+    regex_line_edit.set_placeholder_text(&QString::from_std_str("Optional. Write here a regex to replace only what matches it."));
     let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
 
     unsafe { main_grid.as_mut().unwrap().add_widget((instructions_frame as *mut Widget, 0, 0, 1, 2)); }
     unsafe { main_grid.as_mut().unwrap().add_widget((rewrite_sequence_line_edit.static_cast_mut() as *mut Widget, 1, 0, 1, 1)); }
-    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 1, 1, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((regex_line_edit.static_cast_mut() as *mut Widget, 2, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 1, 1, 2, 1)); }
 
     unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
 
+    // In regex mode, an empty sequence is valid. It just removes the matches.
     if dialog.exec() == 1 { 
         let new_text = rewrite_sequence_line_edit.text().to_std_string();
-        if new_text.is_empty() { None } else { Some(rewrite_sequence_line_edit.text().to_std_string()) } 
+        let regex = regex_line_edit.text().to_std_string();
+        if new_text.is_empty() && regex.is_empty() { None } else { Some((new_text, regex)) }
+    } else { None }
+}
+
+/// This function creates the "Edit in Window" dialog for tables, to edit long texts with more space and multiple lines.
+/// It returns the new text, or None if the dialog was cancelled.
+pub fn create_text_edit_dialog(app_ui: &AppUI, text: &str) -> Option<String> {
+
+    // Create and configure the dialog.
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str("Edit in Window"));
+    dialog.set_modal(true);
+    dialog.resize((600, 400));
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+
+    let plain_text_edit = PlainTextEdit::new(&QString::from_std_str(text)).into_raw();
+    let character_count_label = Label::new(&QString::from_std_str(format!("{} characters", text.chars().count()))).into_raw();
+    let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
+    let cancel_button = PushButton::new(&QString::from_std_str("Cancel")).into_raw();
+
+    unsafe { main_grid.as_mut().unwrap().add_widget((plain_text_edit as *mut Widget, 0, 0, 1, 3)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((character_count_label as *mut Widget, 1, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 1, 1, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((cancel_button as *mut Widget, 1, 2, 1, 1)); }
+
+    // Keep the character count updated while editing.
+    let slot_update_character_count = SlotNoArgs::new(move || {
+        let count = unsafe { plain_text_edit.as_mut().unwrap().to_plain_text().to_std_string().chars().count() };
+        unsafe { character_count_label.as_mut().unwrap().set_text(&QString::from_std_str(format!("{} characters", count))); }
+    });
+
+    unsafe { plain_text_edit.as_ref().unwrap().signals().text_changed().connect(&slot_update_character_count); }
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+    unsafe { cancel_button.as_mut().unwrap().signals().released().connect(&dialog.slots().reject()); }
+
+    if dialog.exec() == 1 { Some(unsafe { plain_text_edit.as_mut().unwrap().to_plain_text().to_std_string() }) } 
+    else { None }
+}
+
+/// This function creates the dialog to pick the value of a reference cell from the values it can reference, with a filter to search them.
+///
+/// It returns the value picked, or None if the dialog is canceled.
+pub fn create_reference_picker_dialog(app_ui: &AppUI, values: &[String], current_value: &str) -> Option<String> {
+
+    // Create and configure the dialog.
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str("Pick Reference"));
+    dialog.set_modal(true);
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+
+    let filter_line_edit = LineEdit::new(()).into_raw();
+    let values_combobox = ComboBox::new().into_raw();
+    let values_filter = SortFilterProxyModel::new().into_raw();
+    let values_model = StandardItemModel::new(()).into_raw();
+    let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
+    let cancel_button = PushButton::new(&QString::from_std_str("Cancel")).into_raw();
+
+    unsafe { filter_line_edit.as_mut().unwrap().set_placeholder_text(&QString::from_std_str("Type here to filter the values of the list. Works with Regex too!")); }
+    unsafe { values_combobox.as_mut().unwrap().set_model(values_model as *mut AbstractItemModel); }
+    for value in values { unsafe { values_combobox.as_mut().unwrap().add_item(&QString::from_std_str(value)); } }
+    unsafe { values_filter.as_mut().unwrap().set_source_model(values_model as *mut AbstractItemModel); }
+    unsafe { values_combobox.as_mut().unwrap().set_model(values_filter as *mut AbstractItemModel); }
+
+    // Start with the current value selected, if it's in the list.
+    let current_index = unsafe { values_combobox.as_mut().unwrap().find_text(&QString::from_std_str(current_value)) };
+    if current_index != -1 { unsafe { values_combobox.as_mut().unwrap().set_current_index(current_index); }}
+
+    unsafe { main_grid.as_mut().unwrap().add_widget((filter_line_edit as *mut Widget, 0, 0, 1, 2)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((values_combobox as *mut Widget, 1, 0, 1, 2)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 2, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((cancel_button as *mut Widget, 2, 1, 1, 1)); }
+
+    // Filter the values while typing.
+    let slot_filter_change_text = SlotStringRef::new(move |_| {
+        let mut pattern = unsafe { RegExp::new(&filter_line_edit.as_mut().unwrap().text()) };
+        pattern.set_case_sensitivity(CaseSensitivity::Insensitive);
+        unsafe { values_filter.as_mut().unwrap().set_filter_reg_exp(&pattern); }
+    });
+
+    unsafe { filter_line_edit.as_mut().unwrap().signals().text_changed().connect(&slot_filter_change_text); }
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+    unsafe { cancel_button.as_mut().unwrap().signals().released().connect(&dialog.slots().reject()); }
+
+    if dialog.exec() == 1 {
+        let value = unsafe { values_combobox.as_mut().unwrap().current_text().to_std_string() };
+        if value.is_empty() { None } else { Some(value) }
+    }
+    else { None }
+}
+
+/// This function returns the new text of a cell for the "Rewrite selection" action. Without regex, `{x}` in the sequence is replaced
+/// with the current text. With a regex, the sequence replaces every match of the regex in the current text, and can use his capture groups.
+pub fn rewrite_text(text: &str, sequence: &str, regex: Option<&Regex>) -> String {
+    match regex {
+        Some(regex) => regex.replace_all(text, sequence).to_string(),
+        None => sequence.replace("{x}", text).replace("{X}", text),
+    }
+}
+
+/// Delimiters offered in the dialog of the delimited files (see `create_delimited_options_dialog()`), with their label.
+/// Tab is here because it cannot be typed in the dialog.
+const DELIMITERS: [(&str, u8); 4] = [("Comma (,)", b','), ("Semicolon (;)", b';'), ("Tab", b'\t'), ("Pipe (|)", b'|')];
+
+/// Quoting policies offered in the dialog of the delimited files (see `create_delimited_options_dialog()`), with their label.
+const QUOTING_POLICIES: [(&str, DelimitedQuoting); 3] = [
+    ("Quote only when needed", DelimitedQuoting::Minimal),
+    ("Always quote", DelimitedQuoting::Always),
+    ("Never quote", DelimitedQuoting::Never),
+];
+
+/// This function creates the dialog with the options of the delimited files (CSV), in a single row: the delimiter and the quoting policy.
+/// The delimiter can be one of the usual ones, or any other ASCII character typed in his combo.
+///
+/// It returns them, None if the dialog is canceled, or an error if the delimiter typed is not a single ASCII character.
+pub fn create_delimited_options_dialog(app_ui: &AppUI, title: &str) -> Option<Result<(u8, DelimitedQuoting)>> {
+
+    // Create and configure the dialog.
+    let mut dialog = unsafe { Dialog::new_unsafe(app_ui.window as *mut Widget) };
+    dialog.set_window_title(&QString::from_std_str(title));
+    dialog.set_modal(true);
+    let main_grid = create_grid_layout_unsafe(dialog.static_cast_mut() as *mut Widget);
+
+    let mut delimiter_label = Label::new(&QString::from_std_str("Delimiter:"));
+    let mut delimiter_combobox = ComboBox::new();
+    delimiter_combobox.set_editable(true);
+    for (label, _) in &DELIMITERS { delimiter_combobox.add_item(&QString::from_std_str(label)); }
+
+    let mut quoting_label = Label::new(&QString::from_std_str("Quoting:"));
+    let mut quoting_combobox = ComboBox::new();
+    for (label, _) in &QUOTING_POLICIES { quoting_combobox.add_item(&QString::from_std_str(label)); }
+    let accept_button = PushButton::new(&QString::from_std_str("Accept")).into_raw();
+
+    unsafe { main_grid.as_mut().unwrap().add_widget((delimiter_label.static_cast_mut() as *mut Widget, 0, 0, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((delimiter_combobox.static_cast_mut() as *mut Widget, 0, 1, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((quoting_label.static_cast_mut() as *mut Widget, 0, 2, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((quoting_combobox.static_cast_mut() as *mut Widget, 0, 3, 1, 1)); }
+    unsafe { main_grid.as_mut().unwrap().add_widget((accept_button as *mut Widget, 0, 4, 1, 1)); }
+
+    unsafe { accept_button.as_mut().unwrap().signals().released().connect(&dialog.slots().accept()); }
+
+    if dialog.exec() == 1 {
+        let delimiter = delimiter_combobox.current_text().to_std_string();
+        let delimiter = match DELIMITERS.iter().find(|(label, _)| *label == delimiter) {
+            Some((_, delimiter)) => *delimiter,
+            None if delimiter.len() == 1 && delimiter.is_ascii() => delimiter.as_bytes()[0],
+            None => return Some(Err(Error::from(ErrorKind::InvalidDelimiter(delimiter)))),
+        };
+        let quoting = QUOTING_POLICIES.get(quoting_combobox.current_index() as usize).map_or(DelimitedQuoting::Minimal, |(_, quoting)| *quoting);
+        Some(Ok((delimiter, quoting)))
     } else { None }
 }
 
@@ -162,6 +526,55 @@ pub fn get_real_indexes(indexes_sorted: &Vec<&ModelIndex>, filter_model: *mut So
     unsafe { indexes_sorted.iter().map(|x| filter_model.as_mut().unwrap().map_to_source(x)).collect() }
 }
 
+/// This function returns the current selection of a TableView just as you see it on screen: the columns with something selected,
+/// in their visual order, and the text of every row with something selected in these columns. Unselected cells are left empty.
+pub fn get_visual_selection(
+    table_view: *mut TableView,
+    filter_model: *mut SortFilterProxyModel,
+    model: *mut StandardItemModel,
+) -> (Vec<i32>, Vec<Vec<String>>) {
+    let (columns, rows) = get_visual_selection_cells(table_view, filter_model, model);
+    (columns, rows.into_iter().map(|row| row.into_iter().map(|x| x.unwrap_or_default()).collect()).collect())
+}
+
+/// This function returns the same as `get_visual_selection()`, but with the unselected cells set to None.
+pub fn get_visual_selection_cells(
+    table_view: *mut TableView,
+    filter_model: *mut SortFilterProxyModel,
+    model: *mut StandardItemModel,
+) -> (Vec<i32>, Vec<Vec<Option<String>>>) {
+
+    let indexes = unsafe { table_view.as_mut().unwrap().selection_model().as_mut().unwrap().selection().indexes() };
+    let mut indexes_sorted = (0..indexes.count(())).map(|x| indexes.at(x)).collect::<Vec<&ModelIndex>>();
+    sort_indexes_visually(&mut indexes_sorted, table_view);
+
+    let horizontal_header = unsafe { table_view.as_ref().unwrap().horizontal_header().as_ref().unwrap() };
+    let mut columns = indexes_sorted.iter().filter(|x| x.is_valid()).map(|x| x.column()).collect::<Vec<i32>>();
+    columns.sort_unstable_by_key(|x| horizontal_header.visual_index(*x));
+    columns.dedup();
+
+    let mut rows: Vec<Vec<Option<String>>> = vec![];
+    let mut last_row = None;
+    for model_index in get_real_indexes(&indexes_sorted, filter_model).iter().filter(|x| x.is_valid()) {
+        if last_row != Some(model_index.row()) {
+            rows.push(vec![None; columns.len()]);
+            last_row = Some(model_index.row());
+        }
+
+        // If it's checkable, we need to get a bool. Otherwise it's a String.
+        let item = unsafe { model.as_mut().unwrap().item_from_index(&model_index) };
+        let text = if unsafe { item.as_mut().unwrap().is_checkable() } {
+            if let CheckState::Checked = unsafe { item.as_mut().unwrap().check_state() } { "true".to_owned() } else { "false".to_owned() }
+        } else { unsafe { item.as_mut().unwrap().text().to_std_string() } };
+
+        if let Some(column) = columns.iter().position(|x| *x == model_index.column()) {
+            rows.last_mut().unwrap()[column] = Some(text);
+        }
+    }
+
+    (columns, rows)
+}
+
 /// This function removes indexes with the same row from a list of indexes.
 pub fn dedup_indexes_per_row(indexes: &mut Vec<ModelIndex>) {
     let mut rows_done = vec![];
@@ -176,43 +589,180 @@ pub fn dedup_indexes_per_row(indexes: &mut Vec<ModelIndex>) {
     }
 }
 
-/// This function creates as many new empty rows with default values for each column type as you ask it for.
+/// This function returns the value of a block of cells (like the one in the clipboard) for the cell at the provided position
+/// in the selection, repeating the block as many times as needed to fill the selection.
+pub fn get_tiled_cell(block: &[Vec<String>], row: usize, column: usize) -> &str {
+    let block_row = &block[row % block.len()];
+    &block_row[column % block_row.len()]
+}
+
+/// This function returns true if the provided text is a valid value for a cell of the provided type.
+pub fn is_valid_for_field_type(text: &str, field_type: FieldType) -> bool {
+    match field_type {
+        FieldType::Boolean => { let text = text.to_lowercase(); text == "true" || text == "false" || text == "1" || text == "0" },
+        FieldType::Float => text.parse::<f32>().is_ok(),
+        FieldType::Integer => text.parse::<i32>().is_ok(),
+        FieldType::LongInteger => text.parse::<i64>().is_ok(),
+        FieldType::Double => text.parse::<f64>().is_ok(),
+        FieldType::Colour => u32::from_str_radix(text.trim_start_matches('#'), 16).is_ok(),
+
+        // All these are Strings, so we can skip their checks....
+        FieldType::StringU8 |
+        FieldType::StringU16 |
+        FieldType::OptionalStringU8 |
+        FieldType::OptionalStringU16 => true,
+    }
+}
+
+/// This function replaces every appearance of the searched text in the provided text. The searched text is not a regex.
+pub fn replace_search_text(text: &str, source: &str, replace: &str, case_sensitive: bool) -> String {
+    if source.is_empty() { return text.to_owned(); }
+    let flags = if case_sensitive { "" } else { "(?i)" };
+    let regex = Regex::new(&format!("{}{}", flags, regex::escape(source))).unwrap();
+    regex.replace_all(text, NoExpand(replace)).to_string()
+}
+
+/// This function highlights the provided search matches of a TableView by selecting all of them, and makes the one in the provided
+/// position the current one, scrolling to it.
+pub fn select_search_matches(table_view: *mut TableView, matches: &[&ModelIndex], position: usize) {
+    let mut selection = ItemSelection::new(());
+    for model_index in matches { selection.select(model_index, model_index); }
+
+    let selection_model = unsafe { table_view.as_mut().unwrap().selection_model() };
+    unsafe { selection_model.as_mut().unwrap().select((&selection, Flags::from_enum(SelectionFlag::ClearAndSelect))); }
+    if let Some(model_index) = matches.get(position) {
+        unsafe { selection_model.as_mut().unwrap().set_current_index(model_index, Flags::from_enum(SelectionFlag::NoUpdate)); }
+        unsafe { table_view.as_mut().unwrap().scroll_to(*model_index); }
+    }
+}
+
+/// This function returns the row the text of the "Go to Row" dialog points to: the row with that number (starting from 1) as the
+/// table is shown, or the first row with a key column exactly matching the text. `keys` are the key columns of every row of the source model.
+pub fn get_go_to_row_target(text: &str, visible_rows: usize, keys: &[Vec<String>]) -> Option<GoToRow> {
+    match text.trim().parse::<usize>() {
+        Ok(row) if row >= 1 && row <= visible_rows => Some(GoToRow::Visible(row - 1)),
+        _ => keys.iter().position(|key| key.iter().any(|x| x == text)).map(GoToRow::Source),
+    }
+}
+
+/// This function makes the provided rows fit in a table with the provided columns (in visual order): missing trailing
+/// cells are filled with the default value of their field, and extra cells are removed. It returns true if any row was truncated.
+pub fn fit_rows_to_columns(rows: &[Vec<String>], fields: &[&Field]) -> (Vec<Vec<String>>, bool) {
+    let mut truncated = false;
+    let rows = rows.iter().map(|row| {
+        if row.len() > fields.len() { truncated = true; }
+        fields.iter().enumerate().map(|(column, field)| match row.get(column) {
+            Some(cell) => cell.to_owned(),
+            None => DecodedData::default_from_field(field).data_to_string(),
+        }).collect()
+    }).collect();
+    (rows, truncated)
+}
+
+/// This function returns the text a key cell of a cloned row should have, with the provided suffix at the end, so the cloned row
+/// doesn't collide with the original one. It returns None if the cell should be cloned as it is (empty suffix or non-text key).
+pub fn get_cloned_key(field_type: &FieldType, key: &str, suffix: &str) -> Option<String> {
+    match field_type {
+        FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16 if !suffix.is_empty() => Some(format!("{}{}", key, suffix)),
+        _ => None,
+    }
+}
+
+/// This function returns where "Insert Rows Above" should insert its rows in the source model, and the rows the inserted ones will
+/// take (in 9->1 order, as the undo system expects them). `selected_rows` are the selected rows as (visual row, source row) pairs.
+/// We insert one row per selected row above the first visible one, or a single row at the top if nothing is selected.
+pub fn get_rows_to_insert_above(selected_rows: &[(i32, i32)]) -> (i32, Vec<i32>) {
+    let mut visual_rows = selected_rows.iter().map(|(visual_row, _)| *visual_row).collect::<Vec<i32>>();
+    visual_rows.sort();
+    visual_rows.dedup();
+
+    let position = selected_rows.iter().min_by_key(|(visual_row, _)| *visual_row).map(|(_, source_row)| *source_row).unwrap_or(0);
+    let amount = visual_rows.len().max(1) as i32;
+    (position, (position..position + amount).rev().collect())
+}
+
+/// This function returns the columns "Invert Selection" should work on. If every selected row has the same cells selected, and they're
+/// not the full row, the selection is column-shaped and we only invert it within those columns. Otherwise, we invert it in all the columns.
+pub fn get_inverted_selection_columns(selected_cells: &[(i32, i32)], columns: i32) -> Vec<i32> {
+    let mut cells: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+    for (row, column) in selected_cells {
+        cells.entry(*row).or_insert_with(|| vec![]).push(*column);
+    }
+    cells.values_mut().for_each(|x| { x.sort(); x.dedup(); });
+
+    let mut rows = cells.values();
+    match rows.next() {
+        Some(first) if first.len() < columns as usize && rows.all(|x| x == first) => first.to_vec(),
+        _ => (0..columns).collect(),
+    }
+}
+
+/// This function returns a copy of the provided row of the provided model, painted as added. If there is a suffix for the keys
+/// (see `get_cloned_key()`), it's added to the text key columns of the copy.
+pub fn clone_row(model: *mut StandardItemModel, row: i32, table_definition: &TableDefinition, key_suffix: &str) -> ListStandardItemMutPtr {
+    let key_columns = get_key_columns(table_definition);
+    let mut qlist = ListStandardItemMutPtr::new(());
+    for column in 0..table_definition.fields.len() {
+        let item = unsafe { model.as_mut().unwrap().item((row, column as i32)).as_mut().unwrap().clone() };
+        unsafe { item.as_mut().unwrap().set_background(&Brush::new(if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { GlobalColor::DarkGreen } else { GlobalColor::Green })); }
+        if key_columns.contains(&column) {
+            let key = unsafe { item.as_mut().unwrap().text().to_std_string() };
+            if let Some(key) = get_cloned_key(&table_definition.fields[column].field_type, &key, key_suffix) {
+                unsafe { item.as_mut().unwrap().set_text(&QString::from_std_str(key)); }
+            }
+        }
+        unsafe { qlist.append_unsafe(&item); }
+    }
+    qlist
+}
+
+/// This function creates as many new rows with the default values of each field (see `get_default_row()`) as you ask it for.
 pub fn create_empty_rows(table_definition: &TableDefinition, amount: u32) -> Vec<ListStandardItemMutPtr> {
 
-    // For each field we have, just create a cell of his type with default values, and add it to the list.
+    // For each field we have, just create a cell of his type with his default value, and add it to the list.
+    let default_row = get_default_row(table_definition);
     let mut rows = vec![];
     for _ in 0..amount {
         let mut qlist = ListStandardItemMutPtr::new(());
-        for field in &table_definition.fields {
-            let mut item = match field.field_type {
-                FieldType::Boolean => {
+        for field in &default_row {
+            let mut item = match field {
+                DecodedData::Boolean(data) => {
                     let mut item = StandardItem::new(());
                     item.set_editable(false);
                     item.set_checkable(true);
-                    item.set_check_state(CheckState::Unchecked);
+                    item.set_check_state(if *data { CheckState::Checked } else { CheckState::Unchecked });
                     item
                 },
-                FieldType::Float => {
+                DecodedData::Float(data) => {
                     let mut item = StandardItem::new(());
-                    item.set_data((&Variant::new2(0.0f32), 2));
+                    item.set_data((&Variant::new2(*data), 2));
                     item
                 },
-                FieldType::Integer => {
+                DecodedData::Integer(data) => {
                     let mut item = StandardItem::new(());
-                    item.set_data((&Variant::new0(0i32), 2));
+                    item.set_data((&Variant::new0(*data), 2));
                     item
                 },
-                FieldType::LongInteger => {
+                DecodedData::LongInteger(data) => {
                     let mut item = StandardItem::new(());
-                    item.set_data((&Variant::new2(0i64), 2));
+                    item.set_data((&Variant::new2(*data), 2));
                     item
                 },
 
+                DecodedData::Double(data) => {
+                    let mut item = StandardItem::new(());
+                    item.set_data((&Variant::new0(*data), 2));
+                    item
+                },
+
+                // This one is stored as text, as it's shown in hex.
+                DecodedData::Colour(_) => StandardItem::new(&QString::from_std_str(field.data_to_string())),
+
                 // All these are Strings, so it can be together.
-                FieldType::StringU8 |
-                FieldType::StringU16 |
-                FieldType::OptionalStringU8 |
-                FieldType::OptionalStringU16 => StandardItem::new(&QString::from_std_str("")),
+                DecodedData::StringU8(data) |
+                DecodedData::StringU16(data) |
+                DecodedData::OptionalStringU8(data) |
+                DecodedData::OptionalStringU16(data) => StandardItem::new(&QString::from_std_str(data)),
             };
 
             // Paint the cells.
@@ -223,3 +773,369 @@ pub fn create_empty_rows(table_definition: &TableDefinition, amount: u32) -> Vec
     }
     rows
 }
+
+/// This function checks if the provided item already has the provided value, as the tables show it.
+pub fn item_has_value(item: *mut StandardItem, value: &DecodedData) -> bool {
+    let item = unsafe { item.as_mut().unwrap() };
+    match value {
+        DecodedData::Boolean(value) => item.check_state() == if *value { CheckState::Checked } else { CheckState::Unchecked },
+        DecodedData::Float(value) => item.data(2).to_string().to_std_string().parse::<f32>().ok() == Some(*value),
+        DecodedData::Integer(value) => item.data(2).to_string().to_std_string().parse::<i32>().ok() == Some(*value),
+        DecodedData::LongInteger(value) => item.data(2).to_string().to_std_string().parse::<i64>().ok() == Some(*value),
+        DecodedData::Double(value) => item.data(2).to_double() == *value,
+        DecodedData::Colour(value) => u32::from_str_radix(item.text().to_std_string().trim_start_matches('#'), 16).ok() == Some(*value),
+        DecodedData::StringU8(value) |
+        DecodedData::StringU16(value) |
+        DecodedData::OptionalStringU8(value) |
+        DecodedData::OptionalStringU16(value) => item.text().to_std_string() == *value,
+    }
+}
+
+/// This function sets the provided value in the provided item, the same way the tables set it when loading their data.
+pub fn set_item_value(item: *mut StandardItem, value: &DecodedData) {
+    let item = unsafe { item.as_mut().unwrap() };
+    match value {
+        DecodedData::Boolean(value) => item.set_check_state(if *value { CheckState::Checked } else { CheckState::Unchecked }),
+        DecodedData::Float(value) => item.set_data((&Variant::new2(*value), 2)),
+        DecodedData::Integer(value) => item.set_data((&Variant::new0(*value), 2)),
+        DecodedData::LongInteger(value) => item.set_data((&Variant::new2(*value), 2)),
+        DecodedData::Double(value) => item.set_data((&Variant::new0(*value), 2)),
+        DecodedData::Colour(_) => item.set_text(&QString::from_std_str(value.data_to_string())),
+        DecodedData::StringU8(value) |
+        DecodedData::StringU16(value) |
+        DecodedData::OptionalStringU8(value) |
+        DecodedData::OptionalStringU16(value) => item.set_text(&QString::from_std_str(value)),
+    }
+}
+
+/// This function returns the color (in RGB) used to tint the header of a column, depending on the type of the column.
+pub fn get_color_for_field_type(field_type: FieldType) -> (i32, i32, i32) {
+    match field_type {
+        FieldType::Boolean => (186, 85, 211),
+        FieldType::Float | FieldType::Double => (218, 165, 32),
+        FieldType::Integer | FieldType::LongInteger => (60, 179, 113),
+        FieldType::Colour => (205, 92, 92),
+        FieldType::StringU8 |
+        FieldType::StringU16 |
+        FieldType::OptionalStringU8 |
+        FieldType::OptionalStringU16 => (70, 130, 180),
+    }
+}
+
+/// This function returns the text of the legend for the colors of the column headers, ready to be put in a Label.
+pub fn get_field_type_colors_legend() -> String {
+    [
+        (FieldType::StringU8, "Strings"),
+        (FieldType::Integer, "Integers"),
+        (FieldType::Float, "Floats"),
+        (FieldType::Boolean, "Booleans"),
+        (FieldType::Colour, "Colours"),
+    ].iter().map(|(field_type, name)| {
+        let (red, green, blue) = get_color_for_field_type(*field_type);
+        format!("<span style=\"background-color: rgb({}, {}, {});\">&nbsp;&nbsp;&nbsp;&nbsp;</span> {}&nbsp;&nbsp;", red, green, blue, name)
+    }).collect::<String>()
+}
+
+/// This function paints the background of the cells of the provided model matching the provided highlight rules.
+/// The data of the cells is not touched.
+pub fn apply_highlight_rules(model: *mut StandardItemModel, table_definition: &TableDefinition, rules: &[HighlightRule], undo_lock: &Rc<RefCell<bool>>) {
+    if rules.is_empty() { return; }
+
+    // We don't block the signals of the model here, so the views get the `dataChanged` of the painted cells and repaint them.
+    // Instead, we lock the undo, so the painting doesn't get registered as an edition. The role of these changes (BackgroundRole)
+    // is not one of the roles that trigger a save.
+    let column_names = table_definition.fields.iter().map(|x| x.field_name.to_owned()).collect::<Vec<String>>();
+    *undo_lock.borrow_mut() = true;
+    for row in 0..unsafe { model.as_mut().unwrap().row_count(()) } {
+        let items = (0..column_names.len()).map(|x| unsafe { model.as_mut().unwrap().item((row, x as i32)) }).collect::<Vec<*mut StandardItem>>();
+        let values = items.iter().map(|x| unsafe {
+            if x.as_mut().unwrap().is_checkable() {
+                if let CheckState::Checked = x.as_mut().unwrap().check_state() { "true".to_owned() } else { "false".to_owned() }
+            } else { x.as_mut().unwrap().text().to_std_string() }
+        }).collect::<Vec<String>>();
+
+        for (column, color) in get_highlighted_cells(rules, &column_names, &values) {
+            unsafe { items[column].as_mut().unwrap().set_background(&Brush::new(&Color::new(color))); }
+        }
+    }
+    *undo_lock.borrow_mut() = false;
+}
+
+/// This function marks the rows of the model that don't pass all the provided column filters, so the filter model can hide them.
+/// The mark is stored with the `COLUMN_FILTER_ROLE` in the first cell of each row: `0` for filtered rows, empty for the rest.
+pub fn mark_filtered_rows(model: *mut StandardItemModel, column_count: usize, filters: &[(usize, ColumnFilter)]) {
+
+    // We block the signals of the model for marking, so this doesn't trigger any edition stuff.
+    let mut blocker = unsafe { SignalBlocker::new(model.as_mut().unwrap().static_cast_mut() as &mut Object) };
+    for row in 0..unsafe { model.as_mut().unwrap().row_count(()) } {
+        let values = (0..column_count).map(|x| unsafe {
+            let item = model.as_mut().unwrap().item((row, x as i32));
+            if item.as_mut().unwrap().is_checkable() {
+                if let CheckState::Checked = item.as_mut().unwrap().check_state() { "true".to_owned() } else { "false".to_owned() }
+            } else { item.as_mut().unwrap().text().to_std_string() }
+        }).collect::<Vec<String>>();
+
+        let mark = if row_matches_column_filters(&values, filters) { "" } else { "0" };
+        unsafe { model.as_mut().unwrap().item((row, 0)).as_mut().unwrap().set_data((&Variant::new0(&QString::from_std_str(mark)), COLUMN_FILTER_ROLE)); }
+    }
+    blocker.unblock();
+}
+
+/// This function places each column filter over his column, following the visual order and size of the sections of the header of the provided TableView.
+/// It has to be called every time the header changes (resized or moved sections, scroll,...) so the filters don't get out of sync with their columns.
+pub fn update_column_filters_geometry(table_view: *mut TableView, column_filter_line_edits: &[*mut LineEdit]) {
+    let header = unsafe { table_view.as_mut().unwrap().horizontal_header().as_mut().unwrap() };
+    let vertical_header = unsafe { table_view.as_mut().unwrap().vertical_header().as_mut().unwrap() };
+
+    // The sections start after the frame of the TableView and his vertical header, if it's visible.
+    let offset = unsafe { table_view.as_mut().unwrap().frame_width() } + if vertical_header.is_visible() { vertical_header.width() } else { 0 };
+    for (index, line_edit) in column_filter_line_edits.iter().enumerate() {
+        let line_edit = unsafe { line_edit.as_mut().unwrap() };
+        let height = line_edit.size_hint().height();
+        line_edit.set_geometry((offset + header.section_viewport_position(index as i32), 0, header.section_size(index as i32), height));
+    }
+}
+
+/// This function applies the font and row height of the provided style to the provided TableView. Anything not set in the style is left as it is.
+pub fn apply_table_view_style(table_view: *mut TableView, style: &TableViewStyle) {
+    if style.font_family.is_some() || style.font_size.is_some() {
+        let mut font = unsafe { Font::new(table_view.as_mut().unwrap().font()) };
+        if let Some(ref font_family) = style.font_family { font.set_family(&QString::from_std_str(font_family)); }
+        if let Some(font_size) = style.font_size { font.set_point_size(font_size); }
+        unsafe { table_view.as_mut().unwrap().set_font(&font); }
+    }
+
+    if let Some(row_height) = style.row_height {
+        unsafe { table_view.as_mut().unwrap().vertical_header().as_mut().unwrap().set_default_section_size(row_height); }
+    }
+}
+
+/// This function returns the columns of the provided definition that hold a colour, which are the `Colour` ones.
+pub fn get_colour_columns(table_definition: &TableDefinition) -> Vec<usize> {
+    table_definition.fields.iter().enumerate().filter(|(_, x)| x.field_type == FieldType::Colour).map(|(x, _)| x).collect()
+}
+
+/// This function returns the colour stored in the text of a colour cell (`RRGGBB` in hex, with or without `#`), if it's a valid colour.
+pub fn get_colour_from_text(text: &str) -> Option<(u8, u8, u8)> {
+    let text = text.trim_start_matches('#');
+    if text.len() != 6 { return None }
+    let value = u32::from_str_radix(text, 16).ok()?;
+    Some(((value >> 16) as u8, (value >> 8) as u8, value as u8))
+}
+
+/// This function returns the text a colour cell shows for the provided colour.
+pub fn get_text_from_colour((red, green, blue): (u8, u8, u8)) -> String {
+    format!("{:02X}{:02X}{:02X}", red, green, blue)
+}
+
+/// This function paints a swatch of the colour in the text of the provided colour cell, as his decoration.
+/// Cells without a valid colour get their swatch removed. Their text is not touched.
+pub fn update_colour_swatch(item: *mut StandardItem) {
+    let item = unsafe { item.as_mut().unwrap() };
+    match get_colour_from_text(&item.text().to_std_string()) {
+        Some((red, green, blue)) => item.set_data((&Color::new((i32::from(red), i32::from(green), i32::from(blue))).as_q_variant(), 1)),
+        None => item.set_data((&Variant::new0(()), 1)),
+    }
+}
+
+/// This function returns, for each row with a key also used by other rows, the other rows using it.
+/// Rows with all the key cells empty are ignored, as they're usually rows still being filled.
+pub fn get_duplicated_keys(keys: &[Vec<String>]) -> BTreeMap<usize, Vec<usize>> {
+    let mut rows_by_key: BTreeMap<&[String], Vec<usize>> = BTreeMap::new();
+    for (row, key) in keys.iter().enumerate() {
+        if key.iter().all(|x| x.is_empty()) { continue }
+        rows_by_key.entry(key.as_slice()).or_insert_with(|| vec![]).push(row);
+    }
+
+    let mut duplicated_keys = BTreeMap::new();
+    for rows in rows_by_key.values().filter(|x| x.len() > 1) {
+        for row in rows {
+            duplicated_keys.insert(*row, rows.iter().filter(|x| *x != row).cloned().collect());
+        }
+    }
+    duplicated_keys
+}
+
+/// This function tints the rows of the provided model with a key used by other rows, with a tooltip naming the other rows.
+/// The rows tinted by a previous check that are no longer duplicated get their tint and tooltip cleaned.
+pub fn paint_duplicated_keys(model: *mut StandardItemModel, key_columns: &[usize], column_count: usize, undo_lock: &Rc<RefCell<bool>>) {
+    if key_columns.is_empty() { return }
+    let rows = unsafe { model.as_mut().unwrap().row_count(()) };
+    let duplicated_keys = get_duplicated_keys(&get_model_rows(model, key_columns));
+
+    // We don't block the signals of the model here, so the views get the `dataChanged` of the tinted cells and repaint them.
+    // Instead, we lock the undo, so the tinting doesn't get registered as an edition. Only cells whose tint changes are touched.
+    let color = if SETTINGS.lock().unwrap().settings_bool["use_dark_theme"] { "#8c4600" } else { "#ffa550" };
+    *undo_lock.borrow_mut() = true;
+    for row in 0..rows {
+        let tooltip = match duplicated_keys.get(&(row as usize)) {
+            Some(other_rows) => format!("Duplicated key. The same key is used in the rows: {}.", other_rows.iter().map(|x| (x + 1).to_string()).collect::<Vec<String>>().join(", ")),
+            None => String::new(),
+        };
+
+        let first_item = unsafe { model.as_mut().unwrap().item((row, 0)).as_mut().unwrap() };
+        let is_marked = !first_item.data(DUPLICATED_KEY_ROLE).to_string().to_std_string().is_empty();
+        if (!is_marked && tooltip.is_empty()) || (is_marked && first_item.tool_tip().to_std_string() == tooltip) { continue }
+        for column in 0..column_count {
+            let item = unsafe { model.as_mut().unwrap().item((row, column as i32)).as_mut().unwrap() };
+            if tooltip.is_empty() { item.set_data((&Variant::new0(()), DUPLICATED_KEY_ROLE)); }
+            else { item.set_data((&Variant::new0(&QString::from_std_str(color)), DUPLICATED_KEY_ROLE)); }
+            item.set_tool_tip(&QString::from_std_str(&tooltip));
+            update_table_checks_tint(item);
+        }
+    }
+    *undo_lock.borrow_mut() = false;
+}
+
+/// This function returns the values of the provided columns of every row the provided model, as they're shown in the table.
+pub fn get_model_rows(model: *mut StandardItemModel, columns: &[usize]) -> Vec<Vec<String>> {
+    (0..unsafe { model.as_mut().unwrap().row_count(()) }).map(|row| get_model_row(model, row, columns)).collect()
+}
+
+/// This function returns the values of the provided columns of the provided row of the provided model, as they're shown in the table.
+pub fn get_model_row(model: *mut StandardItemModel, row: i32, columns: &[usize]) -> Vec<String> {
+    columns.iter().map(|column| unsafe {
+        let item = model.as_mut().unwrap().item((row, *column as i32));
+        if item.as_mut().unwrap().is_checkable() {
+            if let CheckState::Checked = item.as_mut().unwrap().check_state() { "true".to_owned() } else { "false".to_owned() }
+        } else { item.as_mut().unwrap().text().to_std_string() }
+    }).collect()
+}
+
+/// This function compares the provided rows with their vanilla version (see `get_vanilla_rows()`), returning the rows that differ.
+/// Columns vanilla doesn't have are never different, and floats are compared with the three decimals the tables show.
+pub fn get_vanilla_diff(rows: &[Vec<String>], key_columns: &[usize], vanilla_rows: &BTreeMap<Vec<String>, Vec<Option<String>>>) -> BTreeMap<usize, VanillaDiff> {
+    let mut diff = BTreeMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        let key = key_columns.iter().map(|x| row[*x].to_owned()).collect::<Vec<String>>();
+        match vanilla_rows.get(&key) {
+            Some(vanilla_row) => {
+                let columns = row.iter().zip(vanilla_row.iter()).enumerate()
+                    .filter(|(_, (value, vanilla_value))| vanilla_value.as_ref().map_or(false, |x| !is_same_vanilla_value(value, x)))
+                    .map(|(column, _)| column)
+                    .collect::<Vec<usize>>();
+                if !columns.is_empty() { diff.insert(index, VanillaDiff::Changed(columns)); }
+            }
+            None => { diff.insert(index, VanillaDiff::Added); }
+        }
+    }
+    diff
+}
+
+/// This function returns the vanilla value of each one of the provided cells (row, column) that differs from it, finding their vanilla
+/// row with the key of the provided rows. Cells of rows or columns vanilla doesn't have are skipped.
+pub fn get_cells_to_revert(
+    rows: &[Vec<String>],
+    cells: &[(usize, usize)],
+    key_columns: &[usize],
+    vanilla_rows: &BTreeMap<Vec<String>, Vec<Option<String>>>
+) -> Vec<((usize, usize), String)> {
+    cells.iter().filter_map(|(row, column)| {
+        let key = key_columns.iter().map(|x| rows[*row][*x].to_owned()).collect::<Vec<String>>();
+        match vanilla_rows.get(&key).and_then(|x| x.get(*column).cloned()).and_then(|x| x) {
+            Some(ref vanilla_value) if !is_same_vanilla_value(&rows[*row][*column], vanilla_value) => Some(((*row, *column), vanilla_value.to_owned())),
+            _ => None,
+        }
+    }).collect()
+}
+
+/// This function checks if a value of a table is the same as his vanilla value. Floats are compared with the three decimals the tables show.
+fn is_same_vanilla_value(value: &str, vanilla_value: &str) -> bool {
+    value == vanilla_value || match (value.parse::<f64>(), vanilla_value.parse::<f64>()) {
+        (Ok(value), Ok(vanilla_value)) => (value - vanilla_value).abs() < 0.001,
+        _ => false,
+    }
+}
+
+/// This function returns the text of the selection statistics of a table: the rows visible, the selected cells and, if any of them
+/// is numeric, the sum, average, min and max of the numeric ones. Integers are shown as integers, unless there are floats in the
+/// selection. Pass `None` as values if the selection is too big to calculate them.
+pub fn get_selection_stats_text(visible_rows: usize, selected_cells: usize, values: Option<&[f64]>, has_floats: bool) -> String {
+    let mut text = format!("Rows: {} | Selected: {}", visible_rows, selected_cells);
+    match values {
+        Some(values) if !values.is_empty() => {
+            let sum = values.iter().sum::<f64>();
+            let min = values.iter().cloned().fold(std::f64::INFINITY, f64::min);
+            let max = values.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max);
+            let format_value = |value: f64| if has_floats { format!("{:.3}", value) } else { format!("{}", value as i64) };
+            text.push_str(&format!(" | Sum: {} | Avg: {:.3} | Min: {} | Max: {}", format_value(sum), sum / values.len() as f64, format_value(min), format_value(max)));
+        }
+        Some(_) => {},
+        None => text.push_str(" | Sum: … | Avg: … | Min: … | Max: …"),
+    }
+    text
+}
+
+/// This function tints the cells of the provided model that differ from vanilla: entire rows for rows vanilla doesn't have,
+/// and single cells for the rest. Rows are matched with their vanilla version by the provided identity columns (see `get_row_identity_columns()`).
+/// Passing no vanilla rows just cleans the tints. Only cells whose tint changes are touched.
+pub fn paint_vanilla_diff(
+    model: *mut StandardItemModel,
+    identity_columns: &[usize],
+    column_count: usize,
+    vanilla_rows: Option<&BTreeMap<Vec<String>, Vec<Option<String>>>>,
+    undo_lock: &Rc<RefCell<bool>>,
+) {
+    let all_columns = (0..column_count).collect::<Vec<usize>>();
+    let diff = match vanilla_rows {
+        Some(vanilla_rows) => get_vanilla_diff(&get_model_rows(model, &all_columns), identity_columns, vanilla_rows),
+        None => BTreeMap::new(),
+    };
+
+    // We don't block the signals of the model here, so the views get the `dataChanged` of the tinted cells and repaint them.
+    // Instead, we lock the undo, so the tinting doesn't get registered as an edition.
+    let use_dark_theme = SETTINGS.lock().unwrap().settings_bool["use_dark_theme"];
+    let added_color = if use_dark_theme { "#005a6e" } else { "#a0e1f0" };
+    let changed_color = if use_dark_theme { "#5a3278" } else { "#d7b4f5" };
+    *undo_lock.borrow_mut() = true;
+    for row in 0..unsafe { model.as_mut().unwrap().row_count(()) } {
+        let (color, columns) = match diff.get(&(row as usize)) {
+            Some(VanillaDiff::Added) => (added_color, &all_columns[..]),
+            Some(VanillaDiff::Changed(columns)) => (changed_color, &columns[..]),
+            None => (changed_color, &[][..]),
+        };
+
+        for column in 0..column_count {
+            let item = unsafe { model.as_mut().unwrap().item((row, column as i32)).as_mut().unwrap() };
+            let new_color = if columns.contains(&column) { color } else { "" };
+            if item.data(VANILLA_DIFF_ROLE).to_string().to_std_string() != new_color {
+                if new_color.is_empty() { item.set_data((&Variant::new0(()), VANILLA_DIFF_ROLE)); }
+                else { item.set_data((&Variant::new0(&QString::from_std_str(new_color)), VANILLA_DIFF_ROLE)); }
+                update_table_checks_tint(item);
+            }
+        }
+    }
+    *undo_lock.borrow_mut() = false;
+}
+
+/// This function paints the background of the provided cell with the tint of the table checks it fails, if any, with the duplicated key
+/// tint over the vanilla one. The background the cell had before being tinted is kept apart, and restored once the cell passes the checks.
+pub fn update_table_checks_tint(item: &mut StandardItem) {
+    let duplicated_key_tint = item.data(DUPLICATED_KEY_ROLE).to_string().to_std_string();
+    let tint = if duplicated_key_tint.is_empty() { item.data(VANILLA_DIFF_ROLE).to_string().to_std_string() } else { duplicated_key_tint };
+    let painted_tint = item.data(TABLE_CHECKS_TINT_ROLE).to_string().to_std_string();
+    if tint == painted_tint { return }
+
+    if painted_tint.is_empty() {
+        let background = item.data(8);
+        item.set_data((&background, TABLE_CHECKS_BACKGROUND_ROLE));
+    }
+
+    if tint.is_empty() {
+        let background = item.data(TABLE_CHECKS_BACKGROUND_ROLE);
+        item.set_data((&background, 8));
+        item.set_data((&Variant::new0(()), TABLE_CHECKS_BACKGROUND_ROLE));
+        item.set_data((&Variant::new0(()), TABLE_CHECKS_TINT_ROLE));
+    }
+    else {
+        item.set_background(&Brush::new(&Color::new(&QString::from_std_str(&tint))));
+        item.set_data((&Variant::new0(&QString::from_std_str(&tint)), TABLE_CHECKS_TINT_ROLE));
+    }
+}
+
+/// This function sets the background of the provided cell, like the ones marking edited cells. If the cell is tinted by the table checks,
+/// the tint is kept, and the background is set once the tint is cleaned.
+pub fn set_cell_background(item: &mut StandardItem, brush: &Brush) {
+    if item.data(TABLE_CHECKS_TINT_ROLE).to_string().to_std_string().is_empty() { item.set_background(brush); }
+    else { item.set_data((&brush.as_q_variant(), TABLE_CHECKS_BACKGROUND_ROLE)); }
+}
@@ -10,12 +10,24 @@
 
 // In this file are all the stuff needed for the undo system to work for tables.
 
+use qt_widgets::list_view::ListView;
+
+use qt_gui::brush::Brush;
+use qt_gui::standard_item::StandardItem;
 use qt_gui::standard_item_model::StandardItemModel;
 
+use qt_core::flags::Flags;
+use qt_core::item_selection_model::SelectionFlag;
 use qt_core::model_index::ModelIndex;
+use qt_core::qt::GlobalColor;
 
 use std::cmp::Ordering;
 
+use crate::QString;
+use crate::schema::TableDefinition;
+use crate::ui::packedfile_table::TableOperations;
+use crate::ui::table_state::TableStateData;
+
 //----------------------------------------------------------------------------//
 //         Custom Struct for storing automatically ordered ModelIndex
 //----------------------------------------------------------------------------//
@@ -93,3 +105,34 @@ pub fn load_colors_from_undo_model(model: *mut StandardItemModel, undo_model: *m
         }
     }
 }
+
+/// This function returns the entries of the history of a table, from oldest to newest: the table before any change,
+/// then the undo history, and then the redo history in the order it'd be redone.
+pub fn get_history_entries(undo_history: &[TableOperations], redo_history: &[TableOperations], table_definition: &TableDefinition) -> Vec<String> {
+    let mut entries = vec!["Original table".to_owned()];
+    entries.extend(undo_history.iter().map(|x| x.get_description(table_definition, false)));
+    entries.extend(redo_history.iter().rev().map(|x| x.get_description(table_definition, true)));
+    entries
+}
+
+/// This function fills the history panel of a table with his history (see `get_history_entries()`). The current state
+/// of the table is shown in bold and selected, and the entries that can be redone are greyed out.
+pub fn update_history_model(history_view: *mut ListView, history_model: *mut StandardItemModel, table_state_data: &TableStateData, table_definition: &TableDefinition) {
+    let current = table_state_data.undo_history.len();
+    unsafe { history_model.as_mut().unwrap().clear(); }
+    for (index, entry) in get_history_entries(&table_state_data.undo_history, &table_state_data.redo_history, table_definition).iter().enumerate() {
+        let mut item = StandardItem::new(&QString::from_std_str(entry));
+        item.set_editable(false);
+        if index == current {
+            let mut font = item.font();
+            font.set_bold(true);
+            item.set_font(&font);
+        }
+        else if index > current { item.set_foreground(&Brush::new(GlobalColor::Gray)); }
+        unsafe { history_model.as_mut().unwrap().set_item((index as i32, 0, item.into_raw())); }
+    }
+
+    let model_index = unsafe { history_model.as_mut().unwrap().index((current as i32, 0)) };
+    unsafe { history_view.as_mut().unwrap().selection_model().as_mut().unwrap().select((&model_index, Flags::from_enum(SelectionFlag::ClearAndSelect))); }
+    unsafe { history_view.as_mut().unwrap().scroll_to(&model_index); }
+}
@@ -0,0 +1,317 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the helpers of the table views that don't need Qt to work.
+
+use regex::Regex;
+
+use crate::common::tests::{new_db_packed_file, new_table_definitions_with_references};
+use crate::packedfile::{DecodedData, get_vanilla_rows};
+use crate::packfile::{PackFile, PFHVersion};
+use crate::schema::{Field, FieldType, Schema, TableDefinition};
+use crate::ui::packedfile_table::packedfile_table_extras::{GoToRow, VanillaDiff, apply_maths_to_value, fit_rows_to_columns, get_cells_to_revert, get_cloned_key, get_color_for_field_type, get_colour_columns, get_colour_from_text, get_duplicated_keys, get_go_to_row_target, get_inverted_selection_columns, get_rows_to_insert_above, get_selection_stats_text, get_text_from_colour, get_tiled_cell, get_vanilla_diff, is_valid_for_field_type, parse_maths_operation, replace_search_text, rewrite_text};
+use crate::ui::packedfile_table::TableOperations;
+
+use std::collections::BTreeMap;
+use std::ptr::null_mut;
+
+/// Test to make sure the vanilla rows of a table (`get_vanilla_rows()`) are returned by key, in the column order of the provided definition,
+/// and that the rows that differ from them (`get_vanilla_diff()`) are found.
+#[test]
+fn test_get_vanilla_diff() {
+    let mut table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    table_definitions.versions[0].fields.push(Field::new("cost".to_owned(), FieldType::Integer, false, None, String::new()));
+    let mut dependency_database = vec![new_db_packed_file(&["db".to_owned(), "units_tables".to_owned(), "data__".to_owned()], &table_definitions.versions[0], vec![
+        vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::Integer(100)],
+        vec![DecodedData::StringU8("unit_2".to_owned()), DecodedData::Integer(200)],
+    ])];
+
+    let mut schema = Schema::new();
+    schema.add_table_definitions(table_definitions.clone());
+
+    // Our version of the table has a new column, so it has to be ignored when comparing.
+    let mut table_definition = table_definitions.versions[0].clone();
+    table_definition.fields.push(Field::new("speed".to_owned(), FieldType::Float, false, None, String::new()));
+
+    let pack_file = PackFile::new_with_name("test.pack".to_owned(), PFHVersion::PFH5);
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    let vanilla_rows = get_vanilla_rows(&pack_file, &mut dependency_database, &path, &table_definition, &schema).unwrap();
+    assert_eq!(vanilla_rows[&vec!["unit_1".to_owned()]], vec![Some("unit_1".to_owned()), Some("100".to_owned()), None]);
+    assert!(get_vanilla_rows(&pack_file, &mut dependency_database, &["db".to_owned(), "land_units_tables".to_owned(), "test".to_owned()], &table_definition, &schema).unwrap().is_empty());
+
+    let rows = vec![
+        vec!["unit_1".to_owned(), "100".to_owned(), "1.5".to_owned()],
+        vec!["unit_2".to_owned(), "250".to_owned(), "1.5".to_owned()],
+        vec!["unit_3".to_owned(), "300".to_owned(), "1.5".to_owned()],
+    ];
+    let diff = get_vanilla_diff(&rows, &[0], &vanilla_rows);
+    assert_eq!(diff.into_iter().collect::<Vec<(usize, VanillaDiff)>>(), vec![(1, VanillaDiff::Changed(vec![1])), (2, VanillaDiff::Added)]);
+
+    // With our columns in another order, vanilla rows are still keyed by our key column.
+    let mut reordered_definition = table_definition.clone();
+    reordered_definition.fields.swap(0, 1);
+    let vanilla_rows = get_vanilla_rows(&pack_file, &mut dependency_database, &path, &reordered_definition, &schema).unwrap();
+    assert_eq!(vanilla_rows[&vec!["unit_2".to_owned()]], vec![Some("200".to_owned()), Some("unit_2".to_owned()), None]);
+
+    // Without key columns, vanilla rows are keyed by all our columns, and none of them match if vanilla lacks any of them.
+    let mut keyless_definition = table_definitions.versions[0].clone();
+    keyless_definition.fields.iter_mut().for_each(|x| x.field_is_key = false);
+    let vanilla_rows = get_vanilla_rows(&pack_file, &mut dependency_database, &path, &keyless_definition, &schema).unwrap();
+    assert_eq!(vanilla_rows.keys().cloned().collect::<Vec<Vec<String>>>(), vec![vec!["unit_1".to_owned(), "100".to_owned()], vec!["unit_2".to_owned(), "200".to_owned()]]);
+    keyless_definition.fields.push(Field::new("speed".to_owned(), FieldType::Float, false, None, String::new()));
+    assert!(get_vanilla_rows(&pack_file, &mut dependency_database, &path, &keyless_definition, &schema).unwrap().is_empty());
+}
+
+/// Test to make sure reverting cells to vanilla (`get_cells_to_revert()`) only returns the cells that differ from their vanilla values,
+/// skipping the rows and columns vanilla doesn't have.
+#[test]
+fn test_get_cells_to_revert() {
+    let mut vanilla_rows = BTreeMap::new();
+    vanilla_rows.insert(vec!["unit_1".to_owned()], vec![Some("unit_1".to_owned()), Some("100".to_owned()), Some("1.5".to_owned()), None]);
+    vanilla_rows.insert(vec!["unit_2".to_owned()], vec![Some("unit_2".to_owned()), Some("200".to_owned()), Some("2.5".to_owned()), None]);
+
+    let rows = vec![
+        vec!["unit_1".to_owned(), "150".to_owned(), "1.500".to_owned(), "new".to_owned()],
+        vec!["unit_2".to_owned(), "200".to_owned(), "3.000".to_owned(), "new".to_owned()],
+        vec!["unit_3".to_owned(), "300".to_owned(), "1.500".to_owned(), "new".to_owned()],
+    ];
+    let cells = (0..3).flat_map(|row| (0..4).map(move |column| (row, column))).collect::<Vec<(usize, usize)>>();
+    assert_eq!(get_cells_to_revert(&rows, &cells, &[0], &vanilla_rows), vec![((0, 1), "100".to_owned()), ((1, 2), "2.5".to_owned())]);
+    assert!(get_cells_to_revert(&rows, &[(0, 2), (2, 1)], &[0], &vanilla_rows).is_empty());
+}
+
+/// Test to make sure the selection statistics (`get_selection_stats_text()`) format integers and floats properly.
+#[test]
+fn test_get_selection_stats_text() {
+    assert_eq!(get_selection_stats_text(10, 2, Some(&[]), false), "Rows: 10 | Selected: 2");
+    assert_eq!(get_selection_stats_text(10, 3, Some(&[1.0, 2.0, 4.0]), false), "Rows: 10 | Selected: 3 | Sum: 7 | Avg: 2.333 | Min: 1 | Max: 4");
+    assert_eq!(get_selection_stats_text(10, 3, Some(&[1.5, 2.0, -4.0]), true), "Rows: 10 | Selected: 3 | Sum: -0.500 | Avg: -0.167 | Min: -4.000 | Max: 2.000");
+    assert_eq!(get_selection_stats_text(10, 200_000, None, false), "Rows: 10 | Selected: 200000 | Sum: … | Avg: … | Min: … | Max: …");
+}
+
+/// Test to make sure the "Go to Row" dialog (`get_go_to_row_target()`) takes numbers as visible rows, and everything else as keys.
+#[test]
+fn test_get_go_to_row_target() {
+    let keys = vec![vec!["unit_1".to_owned()], vec!["unit_2".to_owned()], vec!["500".to_owned()]];
+    assert_eq!(get_go_to_row_target("2", 3, &keys), Some(GoToRow::Visible(1)));
+    assert_eq!(get_go_to_row_target(" 3 ", 3, &keys), Some(GoToRow::Visible(2)));
+    assert_eq!(get_go_to_row_target("unit_2", 3, &keys), Some(GoToRow::Source(1)));
+    assert_eq!(get_go_to_row_target("500", 3, &keys), Some(GoToRow::Source(2)));
+    assert_eq!(get_go_to_row_target("unit", 3, &keys), None);
+    assert_eq!(get_go_to_row_target("0", 3, &keys), None);
+}
+
+/// Test to make sure the operations of the undo history of a table (`TableOperations::get_description()`) are described
+/// as what the user did, even when they're in the redo history as their opposite.
+#[test]
+fn test_table_operations_get_description() {
+    let table_definitions = new_table_definitions_with_references("units_tables", &[]);
+    let mut table_definition = table_definitions.versions[0].clone();
+    table_definition.fields.push(Field::new("hit_points".to_owned(), FieldType::Integer, false, None, String::new()));
+
+    let editing = TableOperations::Editing(vec![((0, 1), null_mut()), ((1, 1), null_mut()), ((2, 1), null_mut())]);
+    assert_eq!(editing.get_description(&table_definition, false), "Edited 3 cells in column 'Hit Points'");
+    assert_eq!(TableOperations::Editing(vec![((0, 0), null_mut()), ((0, 1), null_mut())]).get_description(&table_definition, false), "Edited 2 cells in 2 columns");
+    assert_eq!(TableOperations::AddRows(vec![4, 3]).get_description(&table_definition, false), "Added 2 rows");
+    assert_eq!(TableOperations::AddRows(vec![4]).get_description(&table_definition, true), "Deleted 1 row");
+    assert_eq!(TableOperations::Carolina(vec![editing.clone(), TableOperations::AddRows(vec![5])]).get_description(&table_definition, false), "Pasted: Edited 3 cells in column 'Hit Points', Added 1 row");
+
+    // Undoing that paste leaves his opposite, with the operations in reverse, in the redo history.
+    assert_eq!(TableOperations::Carolina(vec![TableOperations::RemoveRows(vec![vec![(5, vec![])]]), editing]).get_description(&table_definition, true), "Pasted: Edited 3 cells in column 'Hit Points', Added 1 row");
+}
+
+/// Test to make sure the column header colors (`get_color_for_field_type()`) are the same for each group of types,
+/// and different between groups.
+#[test]
+fn test_get_color_for_field_type() {
+    let string_color = get_color_for_field_type(FieldType::StringU8);
+    let integer_color = get_color_for_field_type(FieldType::Integer);
+    let float_color = get_color_for_field_type(FieldType::Float);
+    let boolean_color = get_color_for_field_type(FieldType::Boolean);
+
+    assert_eq!(get_color_for_field_type(FieldType::StringU16), string_color);
+    assert_eq!(get_color_for_field_type(FieldType::OptionalStringU8), string_color);
+    assert_eq!(get_color_for_field_type(FieldType::OptionalStringU16), string_color);
+    assert_eq!(get_color_for_field_type(FieldType::LongInteger), integer_color);
+
+    let mut colors = vec![string_color, integer_color, float_color, boolean_color];
+    colors.sort();
+    colors.dedup();
+    assert_eq!(colors.len(), 4);
+}
+
+/// Test to make sure the blocks pasted to fill a selection (`get_tiled_cell()`) repeat to fill it, and that only values valid
+/// for their column are pasted in it (`is_valid_for_field_type()`).
+#[test]
+fn test_get_tiled_cell() {
+    let single = vec![vec!["false".to_owned()]];
+    assert_eq!(get_tiled_cell(&single, 39, 0), "false");
+
+    let block = vec![
+        vec!["a".to_owned(), "b".to_owned()],
+        vec!["c".to_owned(), "d".to_owned()],
+    ];
+    assert_eq!(get_tiled_cell(&block, 0, 1), "b");
+    assert_eq!(get_tiled_cell(&block, 3, 2), "c");
+    assert_eq!(get_tiled_cell(&block, 2, 3), "b");
+
+    assert!(is_valid_for_field_type("False", FieldType::Boolean));
+    assert!(!is_valid_for_field_type("no", FieldType::Boolean));
+    assert!(is_valid_for_field_type("1.5", FieldType::Float));
+    assert!(!is_valid_for_field_type("1.5", FieldType::Integer));
+    assert!(is_valid_for_field_type("anything", FieldType::StringU16));
+}
+
+/// Test to make sure the maths operations (`apply_maths_to_value()`) are computed in the type of the cell, rounding and clamping integers,
+/// and don't return results that are not numbers.
+#[test]
+fn test_apply_maths_to_value() {
+    let operation = parse_maths_operation("x * 1.1").unwrap();
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Integer(10)), Some(DecodedData::Integer(11)));
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Integer(14)), Some(DecodedData::Integer(15)));
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Float(10.0)), Some(DecodedData::Float(10.0f32 * 1.1f32)));
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Double(10.0)), Some(DecodedData::Double(10.0 * 1.1)));
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::StringU8("10".to_owned())), None);
+
+    // Floats are computed as f32, so the result is the same as doing it in the game.
+    let operation = parse_maths_operation("x * 3").unwrap();
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Float(0.1)), Some(DecodedData::Float(0.1f32 * 3.0f32)));
+
+    let operation = parse_maths_operation("{x} + 50").unwrap();
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Integer(std::i32::MAX)), Some(DecodedData::Integer(std::i32::MAX)));
+
+    // LongIntegers don't lose precision with whole operations, and get rounded with the rest.
+    let operation = parse_maths_operation("x + 1").unwrap();
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::LongInteger(9_007_199_254_740_993)), Some(DecodedData::LongInteger(9_007_199_254_740_994)));
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::LongInteger(std::i64::MAX)), Some(DecodedData::LongInteger(std::i64::MAX)));
+    let operation = parse_maths_operation("x / 4").unwrap();
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::LongInteger(10)), Some(DecodedData::LongInteger(3)));
+
+    let operation = parse_maths_operation("max(x, 2) + sqrt(4)").unwrap();
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Float(1.0)), Some(DecodedData::Float(4.0)));
+
+    let operation = parse_maths_operation("x / 0").unwrap();
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Float(10.0)), None);
+    assert_eq!(apply_maths_to_value(&operation, &DecodedData::Integer(10)), None);
+
+    assert!(parse_maths_operation("x * * 2").is_err());
+    assert!(parse_maths_operation("y * 2").is_err());
+}
+
+/// Test to make sure the rewrites of selections (`rewrite_text()`) work with `{x}` and with regex capture groups.
+#[test]
+fn test_rewrite_text() {
+    assert_eq!(rewrite_text("unit", "wh2_{x}_custom", None), "wh2_unit_custom");
+    assert_eq!(rewrite_text("unit", "{X}{x}", None), "unitunit");
+
+    let regex = Regex::new("^wh_main_(.+)$").unwrap();
+    assert_eq!(rewrite_text("wh_main_emp_spearmen", "wh2_dlc_$1", Some(&regex)), "wh2_dlc_emp_spearmen");
+    assert_eq!(rewrite_text("wh2_dlc_emp_spearmen", "wh2_dlc_$1", Some(&regex)), "wh2_dlc_emp_spearmen");
+
+    let regex = Regex::new("[0-9]").unwrap();
+    assert_eq!(rewrite_text("a1b22", "", Some(&regex)), "ab");
+}
+
+/// Test to make sure the rows pasted as new rows (`fit_rows_to_columns()`) get defaults for their missing cells, and lose the extra ones.
+#[test]
+fn test_fit_rows_to_columns() {
+    let fields = vec![
+        Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()),
+        Field::new("value".to_owned(), FieldType::Integer, false, None, String::new()),
+        Field::new("enabled".to_owned(), FieldType::Boolean, false, None, String::new()),
+    ];
+    let fields = fields.iter().collect::<Vec<&Field>>();
+    let rows = vec![
+        vec!["key_1".to_owned()],
+        vec!["key_2".to_owned(), "5".to_owned(), "true".to_owned()],
+    ];
+
+    let (fitted, truncated) = fit_rows_to_columns(&rows, &fields);
+    assert_eq!(fitted, vec![
+        vec!["key_1".to_owned(), "0".to_owned(), "false".to_owned()],
+        vec!["key_2".to_owned(), "5".to_owned(), "true".to_owned()],
+    ]);
+    assert!(!truncated);
+
+    let (fitted, truncated) = fit_rows_to_columns(&[vec!["key_3".to_owned(), "1".to_owned(), "0".to_owned(), "extra".to_owned()]], &fields);
+    assert_eq!(fitted, vec![vec!["key_3".to_owned(), "1".to_owned(), "0".to_owned()]]);
+    assert!(truncated);
+}
+
+/// Test to make sure the keys of cloned rows (`get_cloned_key()`) only get the suffix when there is one and the key is a text.
+#[test]
+fn test_get_cloned_key() {
+    assert_eq!(get_cloned_key(&FieldType::StringU8, "wh_main_unit", "_copy"), Some("wh_main_unit_copy".to_owned()));
+    assert_eq!(get_cloned_key(&FieldType::OptionalStringU16, "wh_main_unit", "_copy"), Some("wh_main_unit_copy".to_owned()));
+    assert_eq!(get_cloned_key(&FieldType::StringU8, "wh_main_unit", ""), None);
+    assert_eq!(get_cloned_key(&FieldType::Integer, "5", "_copy"), None);
+}
+
+/// Test to make sure "Invert Selection" (`get_inverted_selection_columns()`) keeps column-shaped selections within their columns.
+#[test]
+fn test_get_inverted_selection_columns() {
+    assert_eq!(get_inverted_selection_columns(&[], 3), vec![0, 1, 2]);
+    assert_eq!(get_inverted_selection_columns(&[(0, 1), (4, 1), (2, 1)], 3), vec![1]);
+    assert_eq!(get_inverted_selection_columns(&[(0, 2), (0, 0), (3, 0), (3, 2)], 3), vec![0, 2]);
+    assert_eq!(get_inverted_selection_columns(&[(0, 1), (1, 2)], 3), vec![0, 1, 2]);
+    assert_eq!(get_inverted_selection_columns(&[(0, 0), (0, 1), (0, 2)], 3), vec![0, 1, 2]);
+}
+
+/// Test to make sure "Insert Rows Above" (`get_rows_to_insert_above()`) inserts above the first visible selected row, even when sorting changes the order.
+#[test]
+fn test_get_rows_to_insert_above() {
+    assert_eq!(get_rows_to_insert_above(&[]), (0, vec![0]));
+    assert_eq!(get_rows_to_insert_above(&[(2, 7), (2, 7), (3, 8)]), (7, vec![8, 7]));
+    assert_eq!(get_rows_to_insert_above(&[(5, 1), (0, 9), (4, 3)]), (9, vec![11, 10, 9]));
+}
+
+/// Test to make sure the search and replace (`replace_search_text()`) replaces the text literally, with and without case sensitivity.
+#[test]
+fn test_replace_search_text() {
+    assert_eq!(replace_search_text("wh_main_Unit_unit", "unit", "lord", true), "wh_main_Unit_lord");
+    assert_eq!(replace_search_text("wh_main_Unit_unit", "unit", "lord", false), "wh_main_lord_lord");
+    assert_eq!(replace_search_text("1.5 + 1x5", "1.5", "$1", false), "$1 + 1x5");
+    assert_eq!(replace_search_text("unit", "", "lord", false), "unit");
+}
+
+/// Test to make sure duplicated keys (`get_duplicated_keys()`) are reported for every row using them, ignoring rows with empty keys.
+#[test]
+fn test_get_duplicated_keys() {
+    let keys = vec![
+        vec!["unit_1".to_owned(), "1".to_owned()],
+        vec!["unit_1".to_owned(), "2".to_owned()],
+        vec!["unit_1".to_owned(), "1".to_owned()],
+        vec![String::new(), String::new()],
+        vec![String::new(), String::new()],
+        vec!["unit_1".to_owned(), "1".to_owned()],
+    ];
+
+    let duplicated_keys = get_duplicated_keys(&keys);
+    assert_eq!(duplicated_keys.keys().cloned().collect::<Vec<usize>>(), vec![0, 2, 5]);
+    assert_eq!(duplicated_keys[&0], vec![2, 5]);
+    assert_eq!(duplicated_keys[&5], vec![0, 2]);
+    assert!(get_duplicated_keys(&keys[..2]).is_empty());
+}
+
+/// Test to make sure only `Colour` columns are colour columns, whatever their name is, and their colours are read and written in hex.
+#[test]
+fn test_colour_columns() {
+    let mut table_definition = TableDefinition::new(0);
+    table_definition.fields.push(Field::new("key".to_owned(), FieldType::StringU8, true, None, String::new()));
+    table_definition.fields.push(Field::new("banner".to_owned(), FieldType::Colour, false, None, String::new()));
+    table_definition.fields.push(Field::new("banner_colour".to_owned(), FieldType::LongInteger, false, None, String::new()));
+    assert_eq!(get_colour_columns(&table_definition), vec![1]);
+
+    assert_eq!(get_colour_from_text("FF8000"), Some((255, 128, 0)));
+    assert_eq!(get_colour_from_text("#FF8000"), Some((255, 128, 0)));
+    assert_eq!(get_colour_from_text("FF80"), None);
+    assert_eq!(get_colour_from_text("orange"), None);
+    assert_eq!(get_text_from_colour((0, 0, 255)), "0000FF");
+}
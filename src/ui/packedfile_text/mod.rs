@@ -17,6 +17,10 @@ use qt_widgets::plain_text_edit::PlainTextEdit;
 use qt_widgets::widget::Widget;
 
 use qt_core::connection::Signal;
+use qt_core::object::Object;
+use qt_core::signal_blocker::SignalBlocker;
+
+use cpp_utils::StaticCast;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -49,6 +53,7 @@ pub enum TextType {
 pub struct PackedFileTextView {
     pub save_changes: SlotNoArgs<'static>,
     pub check_syntax: SlotNoArgs<'static>,
+    pub reload: SlotNoArgs<'static>,
     pub close_note: SlotNoArgs<'static>,
     pub close_note_action: *mut Action,
 }
@@ -82,6 +87,11 @@ impl PackedFileTextView {
         let plain_text_edit = PlainTextEdit::new(&QString::from_std_str(&text)).into_raw();
         let check_syntax_button = PushButton::new(&QString::from_std_str("Check Syntax")).into_raw();
         let close_button = PushButton::new(&QString::from_std_str("Close Note")).into_raw();
+        let reload_button = PushButton::new(&QString::from_std_str("Reload from PackFile")).into_raw();
+        unsafe { reload_button.as_mut().unwrap().set_tool_tip(&QString::from_std_str("Discard all the changes done to this file since the PackFile was last saved, reloading it from the PackFile on disk.")); }
+
+        // This keeps track of the changes done since the file was opened (or reloaded), so we don't lose them without asking first.
+        let is_edited = Rc::new(RefCell::new(false));
 
         // Add it to the view.
         unsafe { layout.as_mut().unwrap().add_widget((plain_text_edit as *mut Widget, 0, 0, 1, 1)); }
@@ -89,6 +99,7 @@ impl PackedFileTextView {
             if packed_file_path.borrow().last().unwrap().ends_with(".lua") && SUPPORTED_GAMES.get(&**GAME_SELECTED.lock().unwrap()).unwrap().ca_types_file.is_some() {
                 unsafe { layout.as_mut().unwrap().add_widget((check_syntax_button as *mut Widget, 1, 0, 1, 1)); }
             }
+            unsafe { layout.as_mut().unwrap().add_widget((reload_button as *mut Widget, 2, 0, 1, 1)); }
         }
 
         // Create the stuff needed for this to work.
@@ -97,9 +108,11 @@ impl PackedFileTextView {
                 packed_file_path,
                 app_ui,
                 text_type,
+                is_edited,
                 receiver_qt,
                 sender_qt,
                 sender_qt_data => move || {
+                    *is_edited.borrow_mut() = true;
 
                     // Get the text from the PlainTextEdit and save it, depending on his type.
                     let text = unsafe { plain_text_edit.as_mut().unwrap().to_plain_text().to_std_string() };
@@ -107,6 +120,12 @@ impl PackedFileTextView {
                         TextType::PackedFile(_) => {
                             sender_qt.send(Commands::EncodePackedFileText).unwrap();
                             sender_qt_data.send(Data::StringVecString((text, packed_file_path.borrow().to_vec()))).unwrap();
+                            match check_message_validity_tryrecv(&receiver_qt) {
+                                Data::Success => {},
+                                Data::String(report) => return show_dry_run_report(app_ui.window, &report),
+                                Data::Error(error) => show_dialog(app_ui.window, false, error),
+                                _ => panic!(THREADS_MESSAGE_ERROR),
+                            }
 
                             update_treeview(
                                 &sender_qt,
@@ -190,6 +209,55 @@ impl PackedFileTextView {
                     unsafe { dialog.as_mut().unwrap().show(); }
                 }
             )),
+            reload: SlotNoArgs::new(clone!(
+                packed_file_path,
+                app_ui,
+                is_edited,
+                receiver_qt,
+                sender_qt,
+                sender_qt_data => move || {
+
+                    // If the file has changes, make sure the user really wants to lose them.
+                    if *is_edited.borrow() && !ask_question(&app_ui, "This file has changes that will be lost. Are you sure you want to reload it from the PackFile?") { return }
+
+                    // Restore the PackedFile from the PackFile on disk, then get it again with the same command we used to open it.
+                    sender_qt.send(Commands::ReloadPackedFileFromDisk).unwrap();
+                    sender_qt_data.send(Data::VecString(packed_file_path.borrow().to_vec())).unwrap();
+                    // In "Dry Run" mode nothing is reloaded, so we keep the view and his history as they are.
+                    match check_message_validity_recv2(&receiver_qt) {
+                        Data::Success => {},
+                        Data::String(report) => return show_dialog(app_ui.window, true, report),
+                        Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    }
+
+                    sender_qt.send(Commands::DecodePackedFileText).unwrap();
+                    sender_qt_data.send(Data::VecString(packed_file_path.borrow().to_vec())).unwrap();
+                    let text = match check_message_validity_recv2(&receiver_qt) {
+                        Data::String(data) => data,
+                        Data::Error(error) => return show_dialog(app_ui.window, false, error),
+                        _ => panic!(THREADS_MESSAGE_ERROR),
+                    };
+
+                    // Block the signals while replacing the text, so it doesn't get saved again. This also clears the undo history of the view.
+                    let mut blocker = unsafe { SignalBlocker::new(plain_text_edit.as_mut().unwrap().static_cast_mut() as &mut Object) };
+                    unsafe { plain_text_edit.as_mut().unwrap().set_plain_text(&QString::from_std_str(&text)); }
+                    blocker.unblock();
+                    *is_edited.borrow_mut() = false;
+
+                    // Remove the "modified" mark of the PackedFile, as it no longer has changes.
+                    update_treeview(
+                        &sender_qt,
+                        &sender_qt_data,
+                        &receiver_qt,
+                        &app_ui,
+                        app_ui.folder_tree_view,
+                        Some(app_ui.folder_tree_filter),
+                        app_ui.folder_tree_model,
+                        TreeViewOperation::Undo(vec![TreePathType::File(packed_file_path.borrow().to_vec())]),
+                    );
+                }
+            )),
             close_note: SlotNoArgs::new(clone!(
                 packedfiles_open_in_packedfile_view,
                 app_ui => move || {
@@ -205,6 +273,7 @@ impl PackedFileTextView {
         // Actions to trigger the slots.
         unsafe { plain_text_edit.as_ref().unwrap().signals().text_changed().connect(&stuff.save_changes); }
         unsafe { check_syntax_button.as_ref().unwrap().signals().released().connect(&stuff.check_syntax); }
+        unsafe { reload_button.as_ref().unwrap().signals().released().connect(&stuff.reload); }
 
         // If it's a note, add the close button to the view.
         if let TextType::Notes(_) = *text_type.borrow() {
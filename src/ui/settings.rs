@@ -52,6 +52,7 @@ use super::{create_grid_layout_unsafe, show_dialog};
 pub struct SettingsDialog {
     pub paths_mymod_line_edit: *mut LineEdit,
     pub paths_games_line_edits: BTreeMap<String, *mut LineEdit>,
+    pub paths_assembly_kit_line_edit: *mut LineEdit,
     pub ui_adjust_columns_to_content: *mut CheckBox,
     pub ui_extend_last_column_on_tables: *mut CheckBox,
     pub ui_disable_combos_on_tables: *mut CheckBox,
@@ -60,6 +61,13 @@ pub struct SettingsDialog {
     pub ui_use_dark_theme: *mut CheckBox,
     pub ui_table_view_remember_column_sorting: *mut CheckBox,
     pub ui_table_view_remember_column_visual_order: *mut CheckBox,
+    pub ui_table_view_tint_column_headers_by_type: *mut CheckBox,
+    pub ui_table_view_font_family: *mut LineEdit,
+    pub ui_table_view_font_size: *mut LineEdit,
+    pub ui_table_view_row_height: *mut LineEdit,
+    pub ui_table_view_cloned_key_suffix: *mut LineEdit,
+    pub ui_table_view_loc_key_templates: *mut LineEdit,
+    pub ui_table_view_untranslated_ignored_keys: *mut LineEdit,
     pub extra_default_game_combobox: *mut ComboBox,
     pub extra_allow_editing_of_ca_packfiles: *mut CheckBox,
     pub extra_check_updates_on_start: *mut CheckBox,
@@ -67,6 +75,9 @@ pub struct SettingsDialog {
     pub extra_use_dependency_checker: *mut CheckBox,
     pub extra_use_lazy_loading_checker: *mut CheckBox,
     pub extra_optimize_not_renamed_packedfiles_checker: *mut CheckBox,
+    pub extra_autosave_recovery_prompt_checker: *mut CheckBox,
+    pub extra_autosave_interval_line_edit: *mut LineEdit,
+    pub extra_trim_nulls_on_extraction_games_line_edit: *mut LineEdit,
     pub debug_check_for_missing_table_definitions: *mut CheckBox,
 }
 
@@ -147,6 +158,17 @@ impl SettingsDialog {
             game_buttons.insert(folder_name.to_string(), game_button);
         }
 
+        // Create the Assembly Kit's path stuff...
+        let assembly_kit_label = Label::new(&QString::from_std_str("Assembly Kit's Raw DB Folder:")).into_raw();
+        let assembly_kit_line_edit = LineEdit::new(()).into_raw();
+        let assembly_kit_button = PushButton::new(&QString::from_std_str("...")).into_raw();
+        unsafe { assembly_kit_line_edit.as_mut().unwrap().set_placeholder_text(&QString::from_std_str("This is the folder with the TWaD_*.xml files of the Assembly Kit. Leave it empty to use the \"assembly_kit/raw_data/db\" folder of the game selected.")); }
+
+        let assembly_kit_row = (SUPPORTED_GAMES.len() + 1) as i32;
+        unsafe { paths_grid.as_mut().unwrap().add_widget((assembly_kit_label as *mut Widget, assembly_kit_row, 0, 1, 1)); }
+        unsafe { paths_grid.as_mut().unwrap().add_widget((assembly_kit_line_edit as *mut Widget, assembly_kit_row, 1, 1, 1)); }
+        unsafe { paths_grid.as_mut().unwrap().add_widget((assembly_kit_button as *mut Widget, assembly_kit_row, 2, 1, 1)); }
+
         // Create the "UI Settings" frame and Grid.
         let ui_settings_frame = GroupBox::new(&QString::from_std_str("UI Settings")).into_raw();
         let ui_settings_grid = create_grid_layout_unsafe(ui_settings_frame as *mut Widget);
@@ -171,6 +193,13 @@ impl SettingsDialog {
 
         let mut remember_column_sorting_label = Label::new(&QString::from_std_str("Remember Column's Sorting State:"));
         let mut remember_column_visual_order_label = Label::new(&QString::from_std_str("Remember Column's Visual Order:"));
+        let mut tint_column_headers_by_type_label = Label::new(&QString::from_std_str("Tint Column Headers by Type:"));
+        let mut font_family_label = Label::new(&QString::from_std_str("Font Family:"));
+        let mut font_size_label = Label::new(&QString::from_std_str("Font Size:"));
+        let mut row_height_label = Label::new(&QString::from_std_str("Row Height:"));
+        let mut cloned_key_suffix_label = Label::new(&QString::from_std_str("Suffix for Keys of Cloned Rows:"));
+        let mut loc_key_templates_label = Label::new(&QString::from_std_str("Templates for Generated Loc Keys:"));
+        let mut untranslated_ignored_keys_label = Label::new(&QString::from_std_str("Keys Ignored when Finding Untranslated Entries:"));
 
         let mut adjust_columns_to_content_checkbox = CheckBox::new(());
         let mut extend_last_column_on_tables_checkbox = CheckBox::new(());
@@ -181,6 +210,19 @@ impl SettingsDialog {
 
         let mut remember_column_sorting_checkbox = CheckBox::new(());
         let mut remember_column_visual_order_checkbox = CheckBox::new(());
+        let mut tint_column_headers_by_type_checkbox = CheckBox::new(());
+        let mut font_family_line_edit = LineEdit::new(());
+        let mut font_size_line_edit = LineEdit::new(());
+        let mut row_height_line_edit = LineEdit::new(());
+        let mut cloned_key_suffix_line_edit = LineEdit::new(());
+        let mut loc_key_templates_line_edit = LineEdit::new(());
+        let mut untranslated_ignored_keys_line_edit = LineEdit::new(());
+        font_family_line_edit.set_placeholder_text(&QString::from_std_str("Default"));
+        font_size_line_edit.set_placeholder_text(&QString::from_std_str("Default"));
+        row_height_line_edit.set_placeholder_text(&QString::from_std_str("Default"));
+        cloned_key_suffix_line_edit.set_placeholder_text(&QString::from_std_str("None"));
+        loc_key_templates_line_edit.set_placeholder_text(&QString::from_std_str("Localisable fields of the table"));
+        untranslated_ignored_keys_line_edit.set_placeholder_text(&QString::from_std_str("None"));
 
         // Tips for the UI settings.
         let adjust_columns_to_content_tip = QString::from_std_str("If you enable this, when you open a DB Table or Loc File, all columns will be automatically resized depending on their content's size.\nOtherwise, columns will have a predefined size. Either way, you'll be able to resize them manually after the initial resize.\nNOTE: This can make very big tables take more time to load.");
@@ -192,6 +234,13 @@ impl SettingsDialog {
         
         let remember_column_sorting_tip = QString::from_std_str("Enable this to make RPFM remember for what column was a DB Table/LOC sorted when closing it and opening it again.");
         let remember_column_visual_order_tip = QString::from_std_str("Enable this to make RPFM remember the visual order of the columns of a DB Table/LOC, when closing it and opening it again.");
+        let tint_column_headers_by_type_tip = QString::from_std_str("Enable this to tint the headers of the columns of DB Tables/LOCs depending on the type of data they hold, with a legend under the table. Requires reopening the table.");
+        let font_family_tip = QString::from_std_str("Font used in DB Tables/LOCs (Consolas, Arial,...). Leave it empty to use the default one. Requires reopening the table.");
+        let font_size_tip = QString::from_std_str("Size, in points, of the font used in DB Tables/LOCs. Leave it empty to use the default one. Requires reopening the table.");
+        let row_height_tip = QString::from_std_str("Height, in pixels, of the rows of DB Tables/LOCs. Leave it empty to use the default one. Requires reopening the table.");
+        let cloned_key_suffix_tip = QString::from_std_str("Text added at the end of the key columns of DB Table rows when cloning them (for example, '_copy'), so the cloned rows don't collide with the original ones. Leave it empty to clone the rows as they are.");
+        let loc_key_templates_tip = QString::from_std_str("Templates used by 'Generate Loc Entries' to build the loc keys of each row of a DB Table, separated by commas (for example, '{table}_onscreen_name_{key}, {table}_description_{key}'). {table} is the name of the table without '_tables', and {key} is the key of the row. Leave it empty to use the localisable fields of the table.");
+        let untranslated_ignored_keys_tip = QString::from_std_str("Patterns (regex) of the loc keys ignored by default by 'Find Untranslated Loc Entries', separated by commas (for example, '^names_name_, _onscreen_name_lord_'). Use it for keys with texts that are meant to be the same in every language, like names.");
 
         adjust_columns_to_content_label.set_tool_tip(&adjust_columns_to_content_tip);
         adjust_columns_to_content_checkbox.set_tool_tip(&adjust_columns_to_content_tip);
@@ -210,6 +259,20 @@ impl SettingsDialog {
         remember_column_sorting_checkbox.set_tool_tip(&remember_column_sorting_tip);
         remember_column_visual_order_label.set_tool_tip(&remember_column_visual_order_tip);
         remember_column_visual_order_checkbox.set_tool_tip(&remember_column_visual_order_tip);
+        tint_column_headers_by_type_label.set_tool_tip(&tint_column_headers_by_type_tip);
+        tint_column_headers_by_type_checkbox.set_tool_tip(&tint_column_headers_by_type_tip);
+        font_family_label.set_tool_tip(&font_family_tip);
+        font_family_line_edit.set_tool_tip(&font_family_tip);
+        font_size_label.set_tool_tip(&font_size_tip);
+        font_size_line_edit.set_tool_tip(&font_size_tip);
+        row_height_label.set_tool_tip(&row_height_tip);
+        row_height_line_edit.set_tool_tip(&row_height_tip);
+        cloned_key_suffix_label.set_tool_tip(&cloned_key_suffix_tip);
+        cloned_key_suffix_line_edit.set_tool_tip(&cloned_key_suffix_tip);
+        loc_key_templates_label.set_tool_tip(&loc_key_templates_tip);
+        loc_key_templates_line_edit.set_tool_tip(&loc_key_templates_tip);
+        untranslated_ignored_keys_label.set_tool_tip(&untranslated_ignored_keys_tip);
+        untranslated_ignored_keys_line_edit.set_tool_tip(&untranslated_ignored_keys_tip);
 
         unsafe { ui_settings_grid.as_mut().unwrap().add_widget((adjust_columns_to_content_label.static_cast_mut() as *mut Widget, 0, 0, 1, 1)); }
         unsafe { ui_settings_grid.as_mut().unwrap().add_widget((adjust_columns_to_content_checkbox.static_cast_mut() as *mut Widget, 0, 1, 1, 1)); }
@@ -239,6 +302,27 @@ impl SettingsDialog {
         unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((remember_column_visual_order_label.static_cast_mut() as *mut Widget, 1, 0, 1, 1)); }
         unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((remember_column_visual_order_checkbox.static_cast_mut() as *mut Widget, 1, 1, 1, 1)); }
 
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((tint_column_headers_by_type_label.static_cast_mut() as *mut Widget, 2, 0, 1, 1)); }
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((tint_column_headers_by_type_checkbox.static_cast_mut() as *mut Widget, 2, 1, 1, 1)); }
+
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((font_family_label.static_cast_mut() as *mut Widget, 3, 0, 1, 1)); }
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((font_family_line_edit.static_cast_mut() as *mut Widget, 3, 1, 1, 1)); }
+
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((font_size_label.static_cast_mut() as *mut Widget, 4, 0, 1, 1)); }
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((font_size_line_edit.static_cast_mut() as *mut Widget, 4, 1, 1, 1)); }
+
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((row_height_label.static_cast_mut() as *mut Widget, 5, 0, 1, 1)); }
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((row_height_line_edit.static_cast_mut() as *mut Widget, 5, 1, 1, 1)); }
+
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((cloned_key_suffix_label.static_cast_mut() as *mut Widget, 6, 0, 1, 1)); }
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((cloned_key_suffix_line_edit.static_cast_mut() as *mut Widget, 6, 1, 1, 1)); }
+
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((loc_key_templates_label.static_cast_mut() as *mut Widget, 7, 0, 1, 1)); }
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((loc_key_templates_line_edit.static_cast_mut() as *mut Widget, 7, 1, 1, 1)); }
+
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((untranslated_ignored_keys_label.static_cast_mut() as *mut Widget, 8, 0, 1, 1)); }
+        unsafe { ui_table_view_settings_grid.as_mut().unwrap().add_widget((untranslated_ignored_keys_line_edit.static_cast_mut() as *mut Widget, 8, 1, 1, 1)); }
+
         // Create the "Extra Settings" frame and Grid.
         let extra_settings_frame = GroupBox::new(&QString::from_std_str("Extra Settings")).into_raw();
         let extra_settings_grid = create_grid_layout_unsafe(extra_settings_frame as *mut Widget);
@@ -269,6 +353,9 @@ impl SettingsDialog {
         let mut use_dependency_checker_label = Label::new(&QString::from_std_str("Enable Dependency Checker for DB Tables:"));
         let mut use_lazy_loading_label = Label::new(&QString::from_std_str("Use Lazy-Loading for PackFiles:"));
         let mut optimize_not_renamed_packedfiles_label = Label::new(&QString::from_std_str("Optimize Non-Renamed PackedFiles:"));
+        let mut autosave_recovery_prompt_label = Label::new(&QString::from_std_str("Offer Autosave Recovery on Start:"));
+        let mut autosave_interval_label = Label::new(&QString::from_std_str("Autosave Interval (Minutes):"));
+        let mut trim_nulls_on_extraction_games_label = Label::new(&QString::from_std_str("Trim Trailing Nulls on Extraction for:"));
         
         let mut check_for_missing_table_definitions_label = Label::new(&QString::from_std_str("Check for Missing Table Definitions"));
 
@@ -278,6 +365,11 @@ impl SettingsDialog {
         let mut use_dependency_checker_checkbox = CheckBox::new(());
         let mut use_lazy_loading_checkbox = CheckBox::new(());
         let mut optimize_not_renamed_packedfiles_checkbox = CheckBox::new(());
+        let mut autosave_recovery_prompt_checkbox = CheckBox::new(());
+        let mut autosave_interval_line_edit = LineEdit::new(());
+        autosave_interval_line_edit.set_placeholder_text(&QString::from_std_str("Disabled"));
+        let mut trim_nulls_on_extraction_games_line_edit = LineEdit::new(());
+        trim_nulls_on_extraction_games_line_edit.set_placeholder_text(&QString::from_std_str("None"));
 
         let mut check_for_missing_table_definitions_checkbox = CheckBox::new(());
 
@@ -288,6 +380,9 @@ impl SettingsDialog {
         let use_dependency_checker_tip = QString::from_std_str("If you enable this, when opening a DB Table RPFM will try to get his dependencies and mark all cells with a reference to another table as 'Not Found In Table' (Red), 'Referenced Table Not Found' (Blue) or 'Correct Reference' (Black). It makes opening a big table a bit slower.");
         let use_lazy_loading_tip = QString::from_std_str("If you enable this, PackFiles will load their data on-demand from the disk instead of loading the entire PackFile to Ram. This reduces Ram usage by a lot, but if something else changes/deletes the PackFile while it's open, the PackFile will likely be unrecoverable and you'll lose whatever is in it.\nIf you mainly mod in Warhammer 2's /data folder LEAVE THIS DISABLED, as a bug in the Assembly Kit causes PackFiles to become broken/be deleted when you have this enabled.");
         let optimize_not_renamed_packedfiles_tip = QString::from_std_str("If you enable this, when running the 'Optimize PackFile' feature RPFM will optimize Tables and Locs that have the same name as their vanilla counterparts.\nUsually, those files are intended to fully override their vanilla counterparts, so by default (this setting off) they are ignored by the optimizer. But it can be useful sometimes to optimize them too (AssKit including too many files), so that's why this setting exists.");
+        let autosave_recovery_prompt_tip = QString::from_std_str("If you enable this, when RPFM starts and finds an autosave of a PackFile newer than the PackFile itself (usually after a crash), it'll ask you if you want to recover it.\nIf you say no, the autosave is kept until RPFM autosaves again or it's closed properly.");
+        let autosave_interval_tip = QString::from_std_str("Every how many minutes RPFM saves a copy of the open PackFile in his 'autosaves' folder, so it can be recovered if RPFM crashes. The copy is deleted when you save the PackFile or close RPFM properly.\nLeave it empty or set it to 0 to disable autosaving.");
+        let trim_nulls_on_extraction_games_tip = QString::from_std_str("Games (by their folder name, like 'warhammer_2') for which the trailing null bytes of the Text PackedFiles are removed after extracting them, separated by commas. UTF-16 files are left untouched.");
         
         let check_for_missing_table_definitions_tip = QString::from_std_str("If you enable this, RPFM will try to decode EVERY TABLE in the current PackFile when opening it or when changing the Game Selected, and it'll output all the tables without an schema to a \"missing_table_definitions.txt\" file.\nDEBUG FEATURE, VERY SLOW. DON'T ENABLE IT UNLESS YOU REALLY WANT TO USE IT.");
 
@@ -298,6 +393,9 @@ impl SettingsDialog {
         use_dependency_checker_checkbox.set_tool_tip(&use_dependency_checker_tip);
         use_lazy_loading_checkbox.set_tool_tip(&use_lazy_loading_tip);
         optimize_not_renamed_packedfiles_checkbox.set_tool_tip(&optimize_not_renamed_packedfiles_tip);
+        autosave_recovery_prompt_checkbox.set_tool_tip(&autosave_recovery_prompt_tip);
+        autosave_interval_line_edit.set_tool_tip(&autosave_interval_tip);
+        trim_nulls_on_extraction_games_line_edit.set_tool_tip(&trim_nulls_on_extraction_games_tip);
 
         check_for_missing_table_definitions_checkbox.set_tool_tip(&check_for_missing_table_definitions_tip);
 
@@ -308,6 +406,9 @@ impl SettingsDialog {
         use_dependency_checker_label.set_tool_tip(&use_dependency_checker_tip);
         use_lazy_loading_label.set_tool_tip(&use_lazy_loading_tip);
         optimize_not_renamed_packedfiles_label.set_tool_tip(&optimize_not_renamed_packedfiles_tip);
+        autosave_recovery_prompt_label.set_tool_tip(&autosave_recovery_prompt_tip);
+        autosave_interval_label.set_tool_tip(&autosave_interval_tip);
+        trim_nulls_on_extraction_games_label.set_tool_tip(&trim_nulls_on_extraction_games_tip);
 
         check_for_missing_table_definitions_label.set_tool_tip(&check_for_missing_table_definitions_tip);
 
@@ -333,6 +434,15 @@ impl SettingsDialog {
         unsafe { extra_settings_grid.as_mut().unwrap().add_widget((optimize_not_renamed_packedfiles_label.into_raw() as *mut Widget, 6, 0, 1, 1)); }
         unsafe { extra_settings_grid.as_mut().unwrap().add_widget((optimize_not_renamed_packedfiles_checkbox.static_cast_mut() as *mut Widget, 6, 1, 1, 1)); }
 
+        unsafe { extra_settings_grid.as_mut().unwrap().add_widget((autosave_recovery_prompt_label.into_raw() as *mut Widget, 7, 0, 1, 1)); }
+        unsafe { extra_settings_grid.as_mut().unwrap().add_widget((autosave_recovery_prompt_checkbox.static_cast_mut() as *mut Widget, 7, 1, 1, 1)); }
+
+        unsafe { extra_settings_grid.as_mut().unwrap().add_widget((autosave_interval_label.into_raw() as *mut Widget, 8, 0, 1, 1)); }
+        unsafe { extra_settings_grid.as_mut().unwrap().add_widget((autosave_interval_line_edit.static_cast_mut() as *mut Widget, 8, 1, 1, 1)); }
+
+        unsafe { extra_settings_grid.as_mut().unwrap().add_widget((trim_nulls_on_extraction_games_label.into_raw() as *mut Widget, 9, 0, 1, 1)); }
+        unsafe { extra_settings_grid.as_mut().unwrap().add_widget((trim_nulls_on_extraction_games_line_edit.static_cast_mut() as *mut Widget, 9, 1, 1, 1)); }
+
         unsafe { extra_settings_grid.as_mut().unwrap().add_widget((debug_settings_frame as *mut Widget, 99, 0, 1, 2)); }
 
         unsafe { debug_settings_grid.as_mut().unwrap().add_widget((check_for_missing_table_definitions_label.static_cast_mut() as *mut Widget, 0, 0, 1, 1)); }
@@ -365,6 +475,11 @@ impl SettingsDialog {
             update_entry_path(mymod_line_edit, dialog);
         });
 
+        // What happens when we hit the "..." button for the Assembly Kit.
+        let slot_select_assembly_kit_path = SlotNoArgs::new(move || {
+            update_entry_path(assembly_kit_line_edit, dialog);
+        });
+
         // What happens when we hit any of the "..." buttons for the games.
         let mut slots_select_paths = BTreeMap::new();
         for (key, path) in &game_paths {
@@ -411,6 +526,9 @@ impl SettingsDialog {
         // What happens when we hit the "..." button for MyMods.
         unsafe { mymod_button.as_mut().unwrap().signals().released().connect(&slot_select_mymod_path); }
 
+        // What happens when we hit the "..." button for the Assembly Kit.
+        unsafe { assembly_kit_button.as_mut().unwrap().signals().released().connect(&slot_select_assembly_kit_path); }
+
         // What happens when we hit the "..." button for Games.
         for (key, button) in game_buttons.iter() {
             unsafe { button.as_mut().unwrap().signals().released().connect(&slots_select_paths[key]); }
@@ -432,6 +550,7 @@ impl SettingsDialog {
         let mut settings_dialog = Self {
             paths_mymod_line_edit: mymod_line_edit,
             paths_games_line_edits: game_paths.clone(),
+            paths_assembly_kit_line_edit: assembly_kit_line_edit,
             ui_adjust_columns_to_content: adjust_columns_to_content_checkbox.into_raw(),
             ui_extend_last_column_on_tables: extend_last_column_on_tables_checkbox.into_raw(),
             ui_disable_combos_on_tables: disable_combos_on_tables_checkbox.into_raw(),
@@ -440,6 +559,13 @@ impl SettingsDialog {
             ui_use_dark_theme: use_dark_theme_checkbox.into_raw(),
             ui_table_view_remember_column_sorting: remember_column_sorting_checkbox.into_raw(),
             ui_table_view_remember_column_visual_order: remember_column_visual_order_checkbox.into_raw(),
+            ui_table_view_tint_column_headers_by_type: tint_column_headers_by_type_checkbox.into_raw(),
+            ui_table_view_font_family: font_family_line_edit.into_raw(),
+            ui_table_view_font_size: font_size_line_edit.into_raw(),
+            ui_table_view_row_height: row_height_line_edit.into_raw(),
+            ui_table_view_cloned_key_suffix: cloned_key_suffix_line_edit.into_raw(),
+            ui_table_view_loc_key_templates: loc_key_templates_line_edit.into_raw(),
+            ui_table_view_untranslated_ignored_keys: untranslated_ignored_keys_line_edit.into_raw(),
             extra_default_game_combobox: default_game_combobox.into_raw(),
             extra_allow_editing_of_ca_packfiles: allow_editing_of_ca_packfiles_checkbox.into_raw(),
             extra_check_updates_on_start: check_updates_on_start_checkbox.into_raw(),
@@ -447,6 +573,9 @@ impl SettingsDialog {
             extra_use_dependency_checker: use_dependency_checker_checkbox.into_raw(),
             extra_use_lazy_loading_checker: use_lazy_loading_checkbox.into_raw(),
             extra_optimize_not_renamed_packedfiles_checker: optimize_not_renamed_packedfiles_checkbox.into_raw(),
+            extra_autosave_recovery_prompt_checker: autosave_recovery_prompt_checkbox.into_raw(),
+            extra_autosave_interval_line_edit: autosave_interval_line_edit.into_raw(),
+            extra_trim_nulls_on_extraction_games_line_edit: trim_nulls_on_extraction_games_line_edit.into_raw(),
             debug_check_for_missing_table_definitions: check_for_missing_table_definitions_checkbox.into_raw(),
         };
 
@@ -490,6 +619,9 @@ impl SettingsDialog {
             unsafe { path.as_mut().unwrap().set_text(&QString::from_std_str(&settings.paths[key].clone().unwrap_or_else(||PathBuf::new()).to_string_lossy())); }
         }
 
+        // Load the Assembly Kit Path, if exists.
+        unsafe { self.paths_assembly_kit_line_edit.as_mut().unwrap().set_text(&QString::from_std_str(settings.paths["assembly_kit_raw_db_path"].clone().unwrap_or_else(||PathBuf::new()).to_string_lossy())); }
+
         // Get the Default Game.
         for (index, (folder_name,_)) in SUPPORTED_GAMES.iter().enumerate() {
             if *folder_name == settings.settings_string["default_game"] {
@@ -509,6 +641,13 @@ impl SettingsDialog {
         // Load the UI TableView Stuff.
         unsafe { self.ui_table_view_remember_column_sorting.as_mut().unwrap().set_checked(settings.settings_bool["remember_column_sorting"]); }
         unsafe { self.ui_table_view_remember_column_visual_order.as_mut().unwrap().set_checked(settings.settings_bool["remember_column_visual_order"]); }
+        unsafe { self.ui_table_view_tint_column_headers_by_type.as_mut().unwrap().set_checked(settings.settings_bool["tint_column_headers_by_type"]); }
+        unsafe { self.ui_table_view_font_family.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["table_font_family"])); }
+        unsafe { self.ui_table_view_font_size.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["table_font_size"])); }
+        unsafe { self.ui_table_view_row_height.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["table_row_height"])); }
+        unsafe { self.ui_table_view_cloned_key_suffix.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["cloned_key_suffix"])); }
+        unsafe { self.ui_table_view_loc_key_templates.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["loc_key_templates"])); }
+        unsafe { self.ui_table_view_untranslated_ignored_keys.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["untranslated_ignored_keys"])); }
 
         // Load the Extra Stuff.
         unsafe { self.extra_allow_editing_of_ca_packfiles.as_mut().unwrap().set_checked(settings.settings_bool["allow_editing_of_ca_packfiles"]); }
@@ -517,6 +656,9 @@ impl SettingsDialog {
         unsafe { self.extra_use_dependency_checker.as_mut().unwrap().set_checked(settings.settings_bool["use_dependency_checker"]); }
         unsafe { self.extra_use_lazy_loading_checker.as_mut().unwrap().set_checked(settings.settings_bool["use_lazy_loading"]); }
         unsafe { self.extra_optimize_not_renamed_packedfiles_checker.as_mut().unwrap().set_checked(settings.settings_bool["optimize_not_renamed_packedfiles"]); }
+        unsafe { self.extra_autosave_recovery_prompt_checker.as_mut().unwrap().set_checked(settings.settings_bool["autosave_recovery_prompt"]); }
+        unsafe { self.extra_autosave_interval_line_edit.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["autosave_interval"])); }
+        unsafe { self.extra_trim_nulls_on_extraction_games_line_edit.as_mut().unwrap().set_text(&QString::from_std_str(&settings.settings_string["trim_nulls_on_extraction_games"])); }
 
         // Load the Debug Stuff.
         unsafe { self.debug_check_for_missing_table_definitions.as_mut().unwrap().set_checked(settings.settings_bool["check_for_missing_table_definitions"]); }
@@ -545,6 +687,12 @@ impl SettingsDialog {
             });
         }
 
+        let assembly_kit_new_path = unsafe { PathBuf::from(self.paths_assembly_kit_line_edit.as_mut().unwrap().text().to_std_string()) };
+        settings.paths.insert("assembly_kit_raw_db_path".to_owned(), match assembly_kit_new_path.is_dir() {
+            true => Some(assembly_kit_new_path),
+            false => None,
+        });
+
         // We get his game's folder, depending on the selected game.
         let mut game = unsafe { self.extra_default_game_combobox.as_mut().unwrap().current_text().to_std_string() };
         if let Some(index) = game.find('&') { game.remove(index); }
@@ -562,6 +710,13 @@ impl SettingsDialog {
         // Get the UI TableView Settings.
         unsafe { settings.settings_bool.insert("remember_column_sorting".to_owned(), self.ui_table_view_remember_column_sorting.as_mut().unwrap().is_checked()); }
         unsafe { settings.settings_bool.insert("remember_column_visual_order".to_owned(), self.ui_table_view_remember_column_visual_order.as_mut().unwrap().is_checked()); }
+        unsafe { settings.settings_bool.insert("tint_column_headers_by_type".to_owned(), self.ui_table_view_tint_column_headers_by_type.as_mut().unwrap().is_checked()); }
+        unsafe { settings.settings_string.insert("table_font_family".to_owned(), self.ui_table_view_font_family.as_mut().unwrap().text().to_std_string()); }
+        unsafe { settings.settings_string.insert("table_font_size".to_owned(), self.ui_table_view_font_size.as_mut().unwrap().text().to_std_string()); }
+        unsafe { settings.settings_string.insert("table_row_height".to_owned(), self.ui_table_view_row_height.as_mut().unwrap().text().to_std_string()); }
+        unsafe { settings.settings_string.insert("cloned_key_suffix".to_owned(), self.ui_table_view_cloned_key_suffix.as_mut().unwrap().text().to_std_string()); }
+        unsafe { settings.settings_string.insert("loc_key_templates".to_owned(), self.ui_table_view_loc_key_templates.as_mut().unwrap().text().to_std_string()); }
+        unsafe { settings.settings_string.insert("untranslated_ignored_keys".to_owned(), self.ui_table_view_untranslated_ignored_keys.as_mut().unwrap().text().to_std_string()); }
 
         // Get the Extra Settings.
         unsafe { settings.settings_bool.insert("allow_editing_of_ca_packfiles".to_owned(), self.extra_allow_editing_of_ca_packfiles.as_mut().unwrap().is_checked()); }
@@ -570,10 +725,16 @@ impl SettingsDialog {
         unsafe { settings.settings_bool.insert("use_dependency_checker".to_owned(), self.extra_use_dependency_checker.as_mut().unwrap().is_checked()); }
         unsafe { settings.settings_bool.insert("use_lazy_loading".to_owned(), self.extra_use_lazy_loading_checker.as_mut().unwrap().is_checked()); }
         unsafe { settings.settings_bool.insert("optimize_not_renamed_packedfiles".to_owned(), self.extra_optimize_not_renamed_packedfiles_checker.as_mut().unwrap().is_checked()); }
+        unsafe { settings.settings_bool.insert("autosave_recovery_prompt".to_owned(), self.extra_autosave_recovery_prompt_checker.as_mut().unwrap().is_checked()); }
+        unsafe { settings.settings_string.insert("autosave_interval".to_owned(), self.extra_autosave_interval_line_edit.as_mut().unwrap().text().to_std_string()); }
+        unsafe { settings.settings_string.insert("trim_nulls_on_extraction_games".to_owned(), self.extra_trim_nulls_on_extraction_games_line_edit.as_mut().unwrap().text().to_std_string()); }
 
         // Get the Debug Settings.
         unsafe { settings.settings_bool.insert("check_for_missing_table_definitions".to_owned(), self.debug_check_for_missing_table_definitions.as_mut().unwrap().is_checked()); }
 
+        // The column layouts of the tables are not in the dialog, so we keep the ones we have.
+        settings.table_column_layouts = SETTINGS.lock().unwrap().table_column_layouts.clone();
+
         // Return the new Settings.
         settings
     }
@@ -12,31 +12,41 @@
 
 use qt_gui::standard_item_model::StandardItemModel;
 
+use regex::Regex;
 use serde_derive::{Serialize, Deserialize};
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 use std::fs::File;
 use std::io::Write;
 use std::io::{BufReader, BufWriter};
 
+use crate::FROZEN_KEY_COLUMNS;
 use crate::RPFM_PATH;
 use crate::TABLE_STATES_UI;
 use crate::error::Result;
+use crate::schema::FieldType;
 use crate::ui::packedfile_table::TableOperations;
 
 /// Name of the file to load/save from.
 const TABLES_STATE_FILE: &str = "table_state.json";
 
+/// Name of the file to load/save the names of the tables with their key columns frozen from.
+const FROZEN_KEY_COLUMNS_FILE: &str = "frozen_key_columns.json";
+
 /// This struct keeps the current state of the "configurable" stuff from a TableView.
 /// - Filter: Keeps the `String` used for the filter, the column filtered and if it's case sensitive or not.
 /// - Search: Keeps the `String` used search, the `String` used to replace, the column filtered, if it's case sensitive or not and the currently selected match.
 /// - Columns: Keeps the order the user sets for the columns.
+/// - Highlight Rules: Keeps the rules used to highlight the cells of the table.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TableStateUI {
     pub filter_state: FilterState,
     pub search_state: SearchState,
     pub columns_state: ColumnsState,
+
+    #[serde(default)]
+    pub highlight_rules: Vec<HighlightRule>,
 }
 
 /// This Struct stores the last state of the filter of a TableView.
@@ -64,6 +74,40 @@ pub struct ColumnsState {
     pub visual_history: Vec<VisualHistory>,
 }
 
+/// This Struct stores a rule to highlight with a color the cells of a column whose value matches a condition (`tier > 3`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HighlightRule {
+    pub column: String,
+    pub operator: HighlightOperator,
+    pub value: String,
+    pub color: (i32, i32, i32),
+}
+
+/// This enum represents the operators of the conditions of the highlight rules. `Greater` and `Lower` only work with numbers.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HighlightOperator {
+    Equal,
+    NotEqual,
+    Greater,
+    Lower,
+    Contains,
+}
+
+/// This enum represents the filter of a single column of a table, from the column filters row.
+/// - Text: the cell must match a regex. Used by non-numeric columns, and by numeric ones when the filter is not a comparison.
+/// - Greater/GreaterOrEqual/Lower/LowerOrEqual/Equal: the cell must be a number, and compare to the value like that.
+/// - Range: the cell must be a number between both values, inclusive (`50..200`).
+#[derive(Clone, Debug)]
+pub enum ColumnFilter {
+    Text(Regex),
+    Greater(f64),
+    GreaterOrEqual(f64),
+    Lower(f64),
+    LowerOrEqual(f64),
+    Equal(f64),
+    Range(f64, f64),
+}
+
 /// This struct stores the "data" changes of a table, like the undo/redo history, and the painted cells.
 pub struct TableStateData {
     pub undo_history: Vec<TableOperations>,
@@ -96,6 +140,7 @@ impl TableStateUI {
             filter_state: FilterState::new(String::new(), 0, false),
             search_state: SearchState::new(String::new(), String::new(), 0, false),
             columns_state: ColumnsState::new((-1, 0), vec![]),
+            highlight_rules: vec![],
         }
     }
 
@@ -200,3 +245,139 @@ impl TableStateData {
         }
     }
 }
+
+/// Implementation of HighlightRule.
+impl HighlightRule {
+
+    /// This function creates a HighlightRule from a condition like `column operator value` (`tier > 3`, `category == infantry`)
+    /// and the name of a color. The supported operators are `==`, `!=`, `>`, `<` and `contains`. If the operator or the color
+    /// are not valid, it returns None.
+    pub fn new_from_text(condition: &str, color: &str) -> Option<Self> {
+        let color = match &*color.trim().to_lowercase() {
+            "red" => (205, 92, 92),
+            "green" => (60, 179, 113),
+            "blue" => (100, 149, 237),
+            "yellow" => (238, 232, 170),
+            "orange" => (244, 164, 96),
+            "purple" => (186, 85, 211),
+            _ => return None,
+        };
+
+        // `contains` goes first, so values with symbols on them don't get mistaken for operators.
+        let operators = [(" contains ", HighlightOperator::Contains), ("==", HighlightOperator::Equal), ("!=", HighlightOperator::NotEqual), (">", HighlightOperator::Greater), ("<", HighlightOperator::Lower)];
+        for (token, operator) in &operators {
+            if let Some(index) = condition.find(token) {
+                let column = condition[..index].trim().to_owned();
+                if column.is_empty() { return None; }
+                return Some(Self {
+                    column,
+                    operator: *operator,
+                    value: condition[index + token.len()..].trim().to_owned(),
+                    color,
+                });
+            }
+        }
+        None
+    }
+
+    /// This function checks if the provided value of a cell matches the condition of this rule.
+    /// If both values are numbers, they're compared as numbers. Otherwise, they're compared as text.
+    pub fn matches(&self, value: &str) -> bool {
+        let numbers = (value.trim().parse::<f64>(), self.value.parse::<f64>());
+        match self.operator {
+            HighlightOperator::Equal => if let (Ok(a), Ok(b)) = numbers { (a - b).abs() < std::f64::EPSILON } else { value == self.value },
+            HighlightOperator::NotEqual => if let (Ok(a), Ok(b)) = numbers { (a - b).abs() >= std::f64::EPSILON } else { value != self.value },
+            HighlightOperator::Greater => if let (Ok(a), Ok(b)) = numbers { a > b } else { false },
+            HighlightOperator::Lower => if let (Ok(a), Ok(b)) = numbers { a < b } else { false },
+            HighlightOperator::Contains => value.contains(&self.value),
+        }
+    }
+}
+
+/// Implementation of ColumnFilter.
+impl ColumnFilter {
+
+    /// This function creates a ColumnFilter from the text of a column filter. For numeric columns, it accepts comparisons like
+    /// `>100`, `>=100`, `<100`, `<=100`, `=100` and ranges like `50..200`. Anything else is used as a regex, or as plain text if it's
+    /// not a valid regex. If the text is empty, it returns None.
+    pub fn new(text: &str, field_type: FieldType, case_sensitive: bool) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() { return None; }
+
+        match field_type {
+            FieldType::Float | FieldType::Integer | FieldType::LongInteger | FieldType::Double => {
+                let number = |x: &str| x.trim().parse::<f64>().ok();
+                let comparison = if text.starts_with(">=") { number(&text[2..]).map(ColumnFilter::GreaterOrEqual) }
+                    else if text.starts_with("<=") { number(&text[2..]).map(ColumnFilter::LowerOrEqual) }
+                    else if text.starts_with('>') { number(&text[1..]).map(ColumnFilter::Greater) }
+                    else if text.starts_with('<') { number(&text[1..]).map(ColumnFilter::Lower) }
+                    else if text.starts_with('=') { number(&text[1..]).map(ColumnFilter::Equal) }
+                    else if let Some(index) = text.find("..") {
+                        match (number(&text[..index]), number(&text[index + 2..])) {
+                            (Some(start), Some(end)) => Some(ColumnFilter::Range(start.min(end), start.max(end))),
+                            _ => None,
+                        }
+                    } else { None };
+
+                if comparison.is_some() { comparison } else { Some(Self::new_text(text, case_sensitive)) }
+            }
+            _ => Some(Self::new_text(text, case_sensitive)),
+        }
+    }
+
+    /// This function creates a Text ColumnFilter from a regex. If the regex is not valid, the text is searched as it is.
+    pub fn new_text(text: &str, case_sensitive: bool) -> Self {
+        let flags = if case_sensitive { "" } else { "(?i)" };
+        let regex = Regex::new(&format!("{}{}", flags, text)).unwrap_or_else(|_| Regex::new(&format!("{}{}", flags, regex::escape(text))).unwrap());
+        ColumnFilter::Text(regex)
+    }
+
+    /// This function checks if the provided value of a cell passes this filter. Cells that are not numbers never pass a numeric filter.
+    pub fn matches(&self, value: &str) -> bool {
+        match (self, value.trim().parse::<f64>()) {
+            (ColumnFilter::Text(regex), _) => regex.is_match(value),
+            (ColumnFilter::Greater(x), Ok(value)) => value > *x,
+            (ColumnFilter::GreaterOrEqual(x), Ok(value)) => value >= *x,
+            (ColumnFilter::Lower(x), Ok(value)) => value < *x,
+            (ColumnFilter::LowerOrEqual(x), Ok(value)) => value <= *x,
+            (ColumnFilter::Equal(x), Ok(value)) => (value - x).abs() < std::f64::EPSILON,
+            (ColumnFilter::Range(start, end), Ok(value)) => value >= *start && value <= *end,
+            (_, Err(_)) => false,
+        }
+    }
+}
+
+/// This function checks if a row passes all the provided column filters. Filters for columns the row doesn't have are ignored.
+pub fn row_matches_column_filters(row: &[String], filters: &[(usize, ColumnFilter)]) -> bool {
+    filters.iter().all(|(column, filter)| row.get(*column).map_or(true, |value| filter.matches(value)))
+}
+
+/// This function takes a frozen_key_columns.json file and reads it into a list of table names.
+pub fn load_frozen_key_columns() -> Result<BTreeSet<String>> {
+    let path = RPFM_PATH.to_path_buf().join(PathBuf::from(FROZEN_KEY_COLUMNS_FILE));
+    let file = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(file)?)
+}
+
+/// This function saves the names of the tables with their key columns frozen into a frozen_key_columns.json file.
+pub fn save_frozen_key_columns() -> Result<()> {
+    let path = RPFM_PATH.to_path_buf().join(PathBuf::from(FROZEN_KEY_COLUMNS_FILE));
+    let mut file = BufWriter::new(File::create(path)?);
+    let tables = serde_json::to_string_pretty(&*FROZEN_KEY_COLUMNS.lock().unwrap());
+    file.write_all(tables.unwrap().as_bytes())?;
+    Ok(())
+}
+
+/// This function evaluates the provided highlight rules over a row, returning the cells to highlight (their column) and their colors.
+/// If more than one rule matches a cell, the last one wins. Rules for columns that don't exist in the table are ignored.
+pub fn get_highlighted_cells(rules: &[HighlightRule], column_names: &[String], row: &[String]) -> Vec<(usize, (i32, i32, i32))> {
+    let mut cells: BTreeMap<usize, (i32, i32, i32)> = BTreeMap::new();
+    for rule in rules {
+        if let Some(column) = column_names.iter().position(|x| *x == rule.column) {
+            if let Some(value) = row.get(column) {
+                if rule.matches(value) { cells.insert(column, rule.color); }
+            }
+        }
+    }
+    cells.into_iter().collect()
+}
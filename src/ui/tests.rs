@@ -0,0 +1,94 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2019 Ismael Gutiérrez González. All rights reserved.
+// 
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+// 
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+// This module contain tests for the logic of the UI that doesn't need Qt to work, like the filters and highlights of the tables.
+
+use crate::schema::FieldType;
+use crate::ui::table_state::{ColumnFilter, HighlightRule, get_highlighted_cells, row_matches_column_filters};
+
+/// Test to make sure the highlight rules (`get_highlighted_cells()`) work with numeric conditions, and ignore missing columns.
+#[test]
+fn test_get_highlighted_cells_numeric() {
+    let column_names = vec!["key".to_owned(), "tier".to_owned()];
+    let rules = vec![
+        HighlightRule::new_from_text("tier > 3", "red").unwrap(),
+        HighlightRule::new_from_text("cost < 100", "green").unwrap(),
+    ];
+
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["unit_1".to_owned(), "5".to_owned()]), vec![(1, (205, 92, 92))]);
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["unit_2".to_owned(), "3".to_owned()]), vec![]);
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["unit_3".to_owned(), "10".to_owned()]), vec![(1, (205, 92, 92))]);
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["unit_4".to_owned(), "".to_owned()]), vec![]);
+    assert!(HighlightRule::new_from_text("tier > 3", "not_a_color").is_none());
+    assert!(HighlightRule::new_from_text("tier 3", "red").is_none());
+}
+
+/// Test to make sure the highlight rules (`get_highlighted_cells()`) work with text conditions, with the last matching rule winning.
+#[test]
+fn test_get_highlighted_cells_string() {
+    let column_names = vec!["key".to_owned(), "category".to_owned()];
+    let rules = vec![
+        HighlightRule::new_from_text("category == infantry", "blue").unwrap(),
+        HighlightRule::new_from_text("key contains lord", "yellow").unwrap(),
+        HighlightRule::new_from_text("key contains lord_boss", "purple").unwrap(),
+    ];
+
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["unit_1".to_owned(), "infantry".to_owned()]), vec![(1, (100, 149, 237))]);
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["unit_2".to_owned(), "cavalry".to_owned()]), vec![]);
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["lord_1".to_owned(), "cavalry".to_owned()]), vec![(0, (238, 232, 170))]);
+    assert_eq!(get_highlighted_cells(&rules, &column_names, &["lord_boss".to_owned(), "infantry".to_owned()]), vec![(0, (186, 85, 211)), (1, (100, 149, 237))]);
+}
+
+/// Test to make sure the column filters (`ColumnFilter`) accept comparisons and ranges only on numeric columns,
+/// and fall back to regex (or plain text, if it's not a valid regex) on everything else.
+#[test]
+fn test_column_filter() {
+    let filter = ColumnFilter::new(">100", FieldType::Integer, false).unwrap();
+    assert!(filter.matches("150"));
+    assert!(!filter.matches("100"));
+    assert!(!filter.matches("not_a_number"));
+
+    let filter = ColumnFilter::new(" <= 2.5 ", FieldType::Float, false).unwrap();
+    assert!(filter.matches("2.5"));
+    assert!(!filter.matches("3"));
+
+    let filter = ColumnFilter::new("200..50", FieldType::LongInteger, false).unwrap();
+    assert!(filter.matches("50"));
+    assert!(filter.matches("200"));
+    assert!(!filter.matches("201"));
+
+    let filter = ColumnFilter::new(">100", FieldType::StringU8, false).unwrap();
+    assert!(filter.matches("x>100"));
+    assert!(!filter.matches("150"));
+
+    let filter = ColumnFilter::new("^Lord", FieldType::StringU8, false).unwrap();
+    assert!(filter.matches("lord_1"));
+    assert!(!filter.matches("unit_lord"));
+    assert!(!ColumnFilter::new("^Lord", FieldType::StringU8, true).unwrap().matches("lord_1"));
+
+    let filter = ColumnFilter::new("unit_(", FieldType::StringU8, false).unwrap();
+    assert!(filter.matches("unit_(1)"));
+    assert!(ColumnFilter::new("  ", FieldType::Integer, false).is_none());
+}
+
+/// Test to make sure the column filters (`row_matches_column_filters()`) must all pass for a row to pass.
+#[test]
+fn test_row_matches_column_filters() {
+    let filters = vec![
+        (0, ColumnFilter::new("lord", FieldType::StringU8, false).unwrap()),
+        (1, ColumnFilter::new("50..200", FieldType::Integer, false).unwrap()),
+        (5, ColumnFilter::new("10", FieldType::Integer, false).unwrap()),
+    ];
+
+    assert!(row_matches_column_filters(&["lord_1".to_owned(), "100".to_owned()], &filters));
+    assert!(!row_matches_column_filters(&["lord_1".to_owned(), "300".to_owned()], &filters));
+    assert!(!row_matches_column_filters(&["unit_1".to_owned(), "100".to_owned()], &filters));
+    assert!(row_matches_column_filters(&["unit_1".to_owned(), "300".to_owned()], &[]));
+}